@@ -5,6 +5,7 @@
 //! - Basic directional movement and jumping
 //! - Support for both keyboard and gamepad input
 //! - A configurable maximum slope angle
+//! - Slippery and non-walkable surfaces using the `Walkable` component
 //! - Collision response for kinematic bodies
 //!
 //! The character controller logic is contained within the `plugin` module.
@@ -28,7 +29,7 @@ use bevy::{
     render::{render_asset::RenderAssetUsages, render_resource::PrimitiveTopology},
 };
 use examples_common_2d::ExampleCommonPlugin;
-use plugin::*;
+use plugin::{CharacterControllerPlugin, *};
 
 fn main() {
     App::new()
@@ -83,15 +84,17 @@ fn setup(
         RigidBody::Static,
         Collider::rectangle(1100.0, 50.0),
     ));
+    // A slippery platform
     commands.spawn((
         Sprite {
-            color: Color::srgb(0.7, 0.7, 0.8),
+            color: Color::srgb(0.6, 0.8, 0.95),
             custom_size: Some(Vec2::new(300.0, 25.0)),
             ..default()
         },
         Transform::from_xyz(175.0, -35.0, 0.0),
         RigidBody::Static,
         Collider::rectangle(300.0, 25.0),
+        Walkable::Slippery,
     ));
     commands.spawn((
         Sprite {
@@ -103,15 +106,17 @@ fn setup(
         RigidBody::Static,
         Collider::rectangle(300.0, 25.0),
     ));
+    // A block that can't be walked on
     commands.spawn((
         Sprite {
-            color: Color::srgb(0.7, 0.7, 0.8),
+            color: Color::srgb(0.8, 0.3, 0.3),
             custom_size: Some(Vec2::new(150.0, 80.0)),
             ..default()
         },
         Transform::from_xyz(475.0, -110.0, 0.0),
         RigidBody::Static,
         Collider::rectangle(150.0, 80.0),
+        Walkable::No,
     ));
     commands.spawn((
         Sprite {
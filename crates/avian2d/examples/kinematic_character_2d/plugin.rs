@@ -18,6 +18,7 @@ impl Plugin for CharacterControllerPlugin {
                 )
                     .chain(),
             )
+            .add_systems(PostUpdate, draw_ground_surface)
             .add_systems(
                 // Run collision handling after collision detection.
                 //
@@ -66,6 +67,29 @@ pub struct ControllerGravity(Vector);
 #[derive(Component)]
 pub struct MaxSlopeAngle(Scalar);
 
+/// Classifies a surface for a character with an optional [`MaxSlopeAngle`].
+///
+/// Characters without a maximum slope angle can walk on slopes of any steepness.
+fn classify_surface(
+    normal: Vector,
+    walkable: Walkable,
+    max_slope_angle: Option<&MaxSlopeAngle>,
+) -> SurfaceClass {
+    let max_slope_angle = max_slope_angle.map_or(PI, |angle| angle.0);
+    SurfaceClass::classify(normal, walkable, Vector::Y, max_slope_angle)
+}
+
+/// The ground surface below a character controller, updated by ground detection.
+///
+/// The character is only [`Grounded`] if the surface is [`SurfaceClass::Walkable`].
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GroundSurface {
+    /// The world-space surface normal.
+    pub normal: Vector,
+    /// The classification of the surface.
+    pub class: SurfaceClass,
+}
+
 /// A bundle that contains the components needed for a basic
 /// kinematic character controller.
 #[derive(Bundle)]
@@ -174,29 +198,42 @@ fn gamepad_input(
     }
 }
 
-/// Updates the [`Grounded`] status for character controllers.
+/// Updates the [`Grounded`] status and [`GroundSurface`] for character controllers.
 fn update_grounded(
     mut commands: Commands,
     mut query: Query<
         (Entity, &ShapeHits, &Rotation, Option<&MaxSlopeAngle>),
         With<CharacterController>,
     >,
+    walkables: Query<&Walkable>,
 ) {
     for (entity, hits, rotation, max_slope_angle) in &mut query {
-        // The character is grounded if the shape caster has a hit with a normal
-        // that isn't too steep.
-        let is_grounded = hits.iter().any(|hit| {
-            if let Some(angle) = max_slope_angle {
-                (rotation * -hit.normal2).angle_to(Vector::Y).abs() <= angle.0
-            } else {
-                true
-            }
-        });
+        // Classify the surfaces hit by the shape caster.
+        // Walkable ground is preferred over other surfaces.
+        let ground = hits
+            .iter()
+            .map(|hit| {
+                let normal = rotation * -hit.normal2;
+                let walkable = walkables.get(hit.entity).copied().unwrap_or_default();
+                GroundSurface {
+                    normal,
+                    class: classify_surface(normal, walkable, max_slope_angle),
+                }
+            })
+            .min_by_key(|ground| ground.class != SurfaceClass::Walkable);
 
-        if is_grounded {
-            commands.entity(entity).insert(Grounded);
-        } else {
-            commands.entity(entity).remove::<Grounded>();
+        let mut entity_commands = commands.entity(entity);
+
+        match ground {
+            Some(ground) if ground.class == SurfaceClass::Walkable => {
+                entity_commands.insert((Grounded, ground));
+            }
+            Some(ground) => {
+                entity_commands.insert(ground).remove::<Grounded>();
+            }
+            None => {
+                entity_commands.remove::<(Grounded, GroundSurface)>();
+            }
         }
     }
 }
@@ -249,8 +286,20 @@ fn apply_gravity(
 }
 
 /// Slows down movement in the X direction.
-fn apply_movement_damping(mut query: Query<(&MovementDampingFactor, &mut LinearVelocity)>) {
-    for (damping_factor, mut linear_velocity) in &mut query {
+///
+/// Movement is not slowed down on [slippery](Walkable::Slippery) ground.
+fn apply_movement_damping(
+    mut query: Query<(
+        &MovementDampingFactor,
+        &mut LinearVelocity,
+        Option<&GroundSurface>,
+    )>,
+) {
+    for (damping_factor, mut linear_velocity, ground) in &mut query {
+        if ground.is_some_and(|ground| ground.class == SurfaceClass::Slippery) {
+            continue;
+        }
+
         // We could use `LinearDamping`, but we don't want to dampen movement along the Y axis
         linear_velocity.x *= damping_factor.0;
     }
@@ -268,6 +317,7 @@ fn kinematic_controller_collisions(
     collisions: Res<Collisions>,
     bodies: Query<&RigidBody>,
     collider_parents: Query<&ColliderParent, Without<Sensor>>,
+    walkables: Query<&Walkable>,
     mut character_controllers: Query<
         (
             &mut Position,
@@ -319,6 +369,14 @@ fn kinematic_controller_collisions(
             continue;
         }
 
+        // Determine how the other collider's surface should be treated.
+        let other_collider = if is_first {
+            contacts.entity2
+        } else {
+            contacts.entity1
+        };
+        let walkable = walkables.get(other_collider).copied().unwrap_or_default();
+
         // Iterate through contact manifolds and their contacts.
         // Each contact in a single manifold shares the same contact normal.
         for manifold in contacts.manifolds.iter() {
@@ -343,9 +401,11 @@ fn kinematic_controller_collisions(
                 continue;
             }
 
-            // Determine if the slope is climbable or if it's too steep to walk on.
+            // Determine if the slope is climbable or if it's too steep
+            // or otherwise not allowed to be walked on.
             let slope_angle = normal.angle_to(Vector::Y);
-            let climbable = max_slope_angle.is_some_and(|angle| slope_angle.abs() <= angle.0);
+            let climbable = max_slope_angle.is_some()
+                && classify_surface(normal, walkable, max_slope_angle) == SurfaceClass::Walkable;
 
             if deepest_penetration > 0.0 {
                 // If the slope is climbable, snap the velocity so that the character
@@ -421,3 +481,18 @@ fn kinematic_controller_collisions(
         }
     }
 }
+
+/// Draws the normal of the [`GroundSurface`] below each character controller,
+/// colored based on its [`SurfaceClass`].
+fn draw_ground_surface(mut gizmos: Gizmos, query: Query<(&GlobalTransform, &GroundSurface)>) {
+    for (transform, ground) in &query {
+        let color = match ground.class {
+            SurfaceClass::Walkable => Color::srgb(0.2, 0.9, 0.3),
+            SurfaceClass::TooSteep => Color::srgb(0.9, 0.7, 0.2),
+            SurfaceClass::Slippery => Color::srgb(0.4, 0.7, 1.0),
+            SurfaceClass::Forbidden => Color::srgb(0.9, 0.2, 0.2),
+        };
+        let start = transform.translation().truncate();
+        gizmos.arrow_2d(start, start + ground.normal.f32() * 40.0, color);
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use avian3d::dynamics::solver::SolverConfig;
+use avian3d::math::*;
+use avian3d::prelude::*;
+use benches_common_3d::bench_app;
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Spawns `size` x `size` stacks of cubes, each five cubes high.
+///
+/// The stacks don't touch each other, so each of them is its own simulation island.
+fn setup_stacks(app: &mut App, size: u32, contact_batches: bool) {
+    app.insert_resource(SubstepCount(8));
+    app.insert_resource(SolverConfig {
+        contact_batches,
+        ..default()
+    });
+    app.add_systems(Startup, move |mut commands: Commands| {
+        commands.spawn((
+            RigidBody::Static,
+            Position(-0.5 * Vector::Y),
+            Collider::cuboid(200.0, 1.0, 200.0),
+        ));
+
+        for x in 0..size {
+            for z in 0..size {
+                for y in 0..5 {
+                    commands.spawn((
+                        RigidBody::Dynamic,
+                        Position(Vector::new(
+                            x as Scalar * 2.0,
+                            0.5 + y as Scalar,
+                            z as Scalar * 2.0,
+                        )),
+                        Collider::cuboid(1.0, 1.0, 1.0),
+                    ));
+                }
+            }
+        }
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    for size in [5, 10, 20] {
+        c.bench_function(&format!("stacks {size}x{size}, 30 steps"), |b| {
+            bench_app(b, 30, |app| setup_stacks(app, size, false))
+        });
+
+        c.bench_function(
+            &format!("stacks {size}x{size}, contact batches, 30 steps"),
+            |b| bench_app(b, 30, |app| setup_stacks(app, size, true)),
+        );
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(20));
+    targets = criterion_benchmark
+);
+criterion_main!(benches);
@@ -5,6 +5,7 @@
 //! - Basic directional movement and jumping
 //! - Support for both keyboard and gamepad input
 //! - A configurable maximum slope angle
+//! - Slippery and non-walkable surfaces using the `Walkable` component
 //! - Collision response for kinematic bodies
 //! - Loading a platformer environment from a glTF
 //!
@@ -26,7 +27,7 @@ mod plugin;
 use avian3d::{math::*, prelude::*};
 use bevy::prelude::*;
 use examples_common_3d::ExampleCommonPlugin;
-use plugin::*;
+use plugin::{CharacterControllerPlugin, *};
 
 fn main() {
     App::new()
@@ -64,6 +65,26 @@ fn setup(
         Transform::from_xyz(3.0, 2.0, 3.0),
     ));
 
+    // A slippery ramp
+    commands.spawn((
+        RigidBody::Static,
+        Collider::cuboid(4.0, 0.5, 4.0),
+        Walkable::Slippery,
+        Mesh3d(meshes.add(Cuboid::new(4.0, 0.5, 4.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.6, 0.8, 0.95))),
+        Transform::from_xyz(-4.0, 0.5, 4.0).with_rotation(Quat::from_rotation_x(0.2)),
+    ));
+
+    // A block that can't be walked on
+    commands.spawn((
+        RigidBody::Static,
+        Collider::cuboid(2.0, 1.0, 2.0),
+        Walkable::No,
+        Mesh3d(meshes.add(Cuboid::new(2.0, 1.0, 2.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.3, 0.3))),
+        Transform::from_xyz(4.0, 0.5, -4.0),
+    ));
+
     // Environment (see the `collider_constructors` example for creating colliders from scenes)
     commands.spawn((
         SceneRoot(assets.load("character_controller_demo.glb#Scene0")),
@@ -0,0 +1,403 @@
+//! A dynamic [bounding volume hierarchy](https://en.wikipedia.org/wiki/Bounding_volume_hierarchy)
+//! of AABBs, used by the broad phase when [`BroadPhaseMethod::Bvh`] is selected.
+
+use crate::prelude::*;
+
+/// The index used for missing nodes.
+const NULL_NODE: u32 = u32::MAX;
+
+/// A node in a [`DynamicAabbTree`].
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    /// The AABB enclosing the node. For leaves, this is the enlarged AABB of the proxy.
+    aabb: ColliderAabb,
+    /// The parent of the node, or the next free node if the node is not in use.
+    parent: u32,
+    /// The children of the node. Leaves have no children.
+    children: [u32; 2],
+    /// The height of the subtree rooted at this node. Leaves have a height of zero.
+    height: i32,
+    /// The user data associated with a leaf.
+    data: usize,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        self.children[0] == NULL_NODE
+    }
+}
+
+/// A dynamic AABB tree, following the approach used by Box2D.
+///
+/// Leaves store enlarged ("fat") AABBs, so that small movements of a proxy don't require
+/// the tree to be modified. When a proxy moves outside of its enlarged AABB, its leaf is reinserted
+/// and the AABBs of its ancestors are refitted. The tree is kept balanced using tree rotations.
+#[derive(Clone, Debug)]
+pub(super) struct DynamicAabbTree {
+    nodes: Vec<Node>,
+    root: u32,
+    free_list: u32,
+    leaf_count: usize,
+}
+
+impl Default for DynamicAabbTree {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: NULL_NODE,
+            free_list: NULL_NODE,
+            leaf_count: 0,
+        }
+    }
+}
+
+impl DynamicAabbTree {
+    /// Returns the number of proxies in the tree.
+    pub(super) fn len(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Removes all proxies from the tree.
+    pub(super) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Inserts a proxy with the given enlarged AABB and user data, and returns its ID.
+    pub(super) fn insert(&mut self, aabb: ColliderAabb, data: usize) -> u32 {
+        let leaf = self.allocate_node(Node {
+            aabb,
+            parent: NULL_NODE,
+            children: [NULL_NODE; 2],
+            height: 0,
+            data,
+        });
+        self.insert_leaf(leaf);
+        self.leaf_count += 1;
+        leaf
+    }
+
+    /// Removes the proxy with the given ID.
+    pub(super) fn remove(&mut self, proxy: u32) {
+        debug_assert!(self.nodes[proxy as usize].is_leaf());
+        self.remove_leaf(proxy);
+        self.free_node(proxy);
+        self.leaf_count -= 1;
+    }
+
+    /// Updates the AABB of a proxy. If the new `aabb` is no longer contained in the enlarged AABB
+    /// of the proxy, the proxy is reinserted with the `aabb` enlarged by the given `margin`.
+    ///
+    /// Returns `true` if the proxy was reinserted.
+    pub(super) fn update(&mut self, proxy: u32, aabb: ColliderAabb, margin: Scalar) -> bool {
+        let fat_aabb = self.nodes[proxy as usize].aabb;
+
+        if fat_aabb.min.cmple(aabb.min).all() && aabb.max.cmple(fat_aabb.max).all() {
+            return false;
+        }
+
+        self.remove_leaf(proxy);
+        self.nodes[proxy as usize].aabb = aabb.grow(Vector::splat(margin));
+        self.insert_leaf(proxy);
+        true
+    }
+
+    /// Sets the user data of a proxy.
+    pub(super) fn set_data(&mut self, proxy: u32, data: usize) {
+        self.nodes[proxy as usize].data = data;
+    }
+
+    /// Calls the given `callback` with the user data of every proxy whose enlarged AABB
+    /// intersects the given `aabb`.
+    pub(super) fn query(&self, aabb: &ColliderAabb, mut callback: impl FnMut(usize)) {
+        if self.root == NULL_NODE {
+            return;
+        }
+
+        let mut stack = vec![self.root];
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+
+            if !node.aabb.intersects(aabb) {
+                continue;
+            }
+
+            if node.is_leaf() {
+                callback(node.data);
+            } else {
+                stack.extend(node.children);
+            }
+        }
+    }
+
+    fn allocate_node(&mut self, node: Node) -> u32 {
+        if self.free_list == NULL_NODE {
+            self.nodes.push(node);
+            return self.nodes.len() as u32 - 1;
+        }
+
+        let index = self.free_list;
+        self.free_list = self.nodes[index as usize].parent;
+        self.nodes[index as usize] = node;
+        index
+    }
+
+    fn free_node(&mut self, index: u32) {
+        let node = &mut self.nodes[index as usize];
+        node.parent = self.free_list;
+        node.height = -1;
+        self.free_list = index;
+    }
+
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL_NODE {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent = NULL_NODE;
+            return;
+        }
+
+        // Find the best sibling for the new leaf using the surface area heuristic.
+        let leaf_aabb = self.nodes[leaf as usize].aabb;
+        let mut index = self.root;
+
+        while !self.nodes[index as usize].is_leaf() {
+            let node = &self.nodes[index as usize];
+            let [child1, child2] = node.children;
+
+            let area = surface_area(&node.aabb);
+            let combined_area = surface_area(&node.aabb.merged(leaf_aabb));
+
+            // The cost of creating a new parent for this node and the new leaf.
+            let cost = 2.0 * combined_area;
+
+            // The minimum cost of pushing the leaf further down the tree.
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let cost1 = self.descend_cost(child1, &leaf_aabb) + inheritance_cost;
+            let cost2 = self.descend_cost(child2, &leaf_aabb) + inheritance_cost;
+
+            if cost < cost1 && cost < cost2 {
+                break;
+            }
+
+            index = if cost1 < cost2 { child1 } else { child2 };
+        }
+
+        // Create a new parent for the sibling and the new leaf.
+        let sibling = index;
+        let old_parent = self.nodes[sibling as usize].parent;
+        let new_parent = self.allocate_node(Node {
+            aabb: leaf_aabb.merged(self.nodes[sibling as usize].aabb),
+            parent: old_parent,
+            children: [sibling, leaf],
+            height: self.nodes[sibling as usize].height + 1,
+            data: 0,
+        });
+
+        if old_parent == NULL_NODE {
+            self.root = new_parent;
+        } else {
+            self.replace_child(old_parent, sibling, new_parent);
+        }
+
+        self.nodes[sibling as usize].parent = new_parent;
+        self.nodes[leaf as usize].parent = new_parent;
+
+        self.refit_ancestors(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL_NODE;
+            return;
+        }
+
+        let parent = self.nodes[leaf as usize].parent;
+        let grandparent = self.nodes[parent as usize].parent;
+        let [child1, child2] = self.nodes[parent as usize].children;
+        let sibling = if child1 == leaf { child2 } else { child1 };
+
+        // Replace the parent with the sibling.
+        self.nodes[sibling as usize].parent = grandparent;
+        self.free_node(parent);
+
+        if grandparent == NULL_NODE {
+            self.root = sibling;
+        } else {
+            self.replace_child(grandparent, parent, sibling);
+            self.refit_ancestors(grandparent);
+        }
+    }
+
+    /// Returns the cost of descending into the given child when inserting a leaf with the given AABB.
+    fn descend_cost(&self, child: u32, leaf_aabb: &ColliderAabb) -> Scalar {
+        let node = &self.nodes[child as usize];
+        let merged_area = surface_area(&node.aabb.merged(*leaf_aabb));
+
+        if node.is_leaf() {
+            merged_area
+        } else {
+            merged_area - surface_area(&node.aabb)
+        }
+    }
+
+    fn replace_child(&mut self, parent: u32, old_child: u32, new_child: u32) {
+        let children = &mut self.nodes[parent as usize].children;
+        if children[0] == old_child {
+            children[0] = new_child;
+        } else {
+            children[1] = new_child;
+        }
+    }
+
+    /// Walks up the tree from the given node, rebalancing it and refitting the AABBs and heights.
+    fn refit_ancestors(&mut self, mut index: u32) {
+        while index != NULL_NODE {
+            index = self.balance(index);
+
+            let [child1, child2] = self.nodes[index as usize].children;
+            let (node1, node2) = (self.nodes[child1 as usize], self.nodes[child2 as usize]);
+
+            let node = &mut self.nodes[index as usize];
+            node.height = 1 + node1.height.max(node2.height);
+            node.aabb = node1.aabb.merged(node2.aabb);
+
+            index = node.parent;
+        }
+    }
+
+    /// Performs a tree rotation if the subtree rooted at `a` is imbalanced.
+    /// Returns the new root of the subtree.
+    fn balance(&mut self, a: u32) -> u32 {
+        let node_a = &self.nodes[a as usize];
+
+        if node_a.is_leaf() || node_a.height < 2 {
+            return a;
+        }
+
+        let [b, c] = node_a.children;
+        let balance = self.nodes[c as usize].height - self.nodes[b as usize].height;
+
+        if balance > 1 {
+            self.rotate_up(a, c, b)
+        } else if balance < -1 {
+            self.rotate_up(a, b, c)
+        } else {
+            a
+        }
+    }
+
+    /// Promotes the child `c` of `a` to take its place, where `b` is the other child of `a`.
+    /// Returns the new root of the subtree.
+    fn rotate_up(&mut self, a: u32, c: u32, b: u32) -> u32 {
+        let [f, g] = self.nodes[c as usize].children;
+
+        // Swap `a` and `c`.
+        let parent = self.nodes[a as usize].parent;
+        self.nodes[c as usize].children[0] = a;
+        self.nodes[c as usize].parent = parent;
+        self.nodes[a as usize].parent = c;
+
+        if parent == NULL_NODE {
+            self.root = c;
+        } else {
+            self.replace_child(parent, a, c);
+        }
+
+        // Keep the taller child of `c`, and move the shorter one under `a`.
+        let (keep, moved) = if self.nodes[f as usize].height > self.nodes[g as usize].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+
+        self.nodes[c as usize].children[1] = keep;
+        self.replace_child(a, c, moved);
+        self.nodes[moved as usize].parent = a;
+
+        let (node_b, node_moved, node_keep) = (
+            self.nodes[b as usize],
+            self.nodes[moved as usize],
+            self.nodes[keep as usize],
+        );
+
+        let node_a = &mut self.nodes[a as usize];
+        node_a.aabb = node_b.aabb.merged(node_moved.aabb);
+        node_a.height = 1 + node_b.height.max(node_moved.height);
+        let node_a = *node_a;
+
+        let node_c = &mut self.nodes[c as usize];
+        node_c.aabb = node_a.aabb.merged(node_keep.aabb);
+        node_c.height = 1 + node_a.height.max(node_keep.height);
+
+        c
+    }
+}
+
+/// Computes the perimeter of the AABB in 2D and the surface area in 3D.
+fn surface_area(aabb: &ColliderAabb) -> Scalar {
+    let size = aabb.size();
+    #[cfg(feature = "2d")]
+    {
+        2.0 * (size.x + size.y)
+    }
+    #[cfg(feature = "3d")]
+    {
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aabb_at(x: Scalar) -> ColliderAabb {
+        ColliderAabb::new(Vector::X * x, Vector::splat(0.5))
+    }
+
+    fn query(tree: &DynamicAabbTree, aabb: &ColliderAabb) -> Vec<usize> {
+        let mut hits = Vec::new();
+        tree.query(aabb, |data| hits.push(data));
+        hits.sort_unstable();
+        hits
+    }
+
+    #[test]
+    fn query_finds_intersecting_proxies() {
+        let mut tree = DynamicAabbTree::default();
+
+        for i in 0..100 {
+            tree.insert(aabb_at(i as Scalar * 2.0), i);
+        }
+
+        assert_eq!(tree.len(), 100);
+        assert_eq!(query(&tree, &aabb_at(10.0)), vec![5]);
+        assert_eq!(query(&tree, &aabb_at(11.0)), vec![5, 6]);
+        assert!(query(&tree, &aabb_at(-10.0)).is_empty());
+
+        // The tree should be balanced, even though the proxies were inserted in order.
+        assert!(tree.nodes[tree.root as usize].height < 16);
+    }
+
+    #[test]
+    fn update_and_remove_proxies() {
+        let mut tree = DynamicAabbTree::default();
+
+        let proxy1 = tree.insert(aabb_at(0.0).grow(Vector::splat(0.1)), 1);
+        let proxy2 = tree.insert(aabb_at(5.0).grow(Vector::splat(0.1)), 2);
+
+        // Small movements stay within the enlarged AABB.
+        assert!(!tree.update(proxy1, aabb_at(0.05), 0.1));
+        assert!(tree.update(proxy1, aabb_at(5.0), 0.1));
+        assert_eq!(query(&tree, &aabb_at(5.0)), vec![1, 2]);
+
+        tree.remove(proxy2);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(query(&tree, &aabb_at(5.0)), vec![1]);
+
+        // Freed nodes are reused.
+        let node_count = tree.nodes.len();
+        tree.insert(aabb_at(10.0), 3);
+        assert_eq!(tree.nodes.len(), node_count);
+    }
+}
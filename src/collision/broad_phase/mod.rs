@@ -0,0 +1,978 @@
+//! Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+//! [AABB](ColliderAabb) intersection checks.
+//!
+//! See [`BroadPhasePlugin`].
+
+mod bvh;
+
+use crate::prelude::*;
+use bevy::{
+    ecs::{
+        entity::{EntityHashMap, EntityHashSet, EntityMapper, MapEntities},
+        system::{lifetimeless::Read, ReadOnlySystemParam, StaticSystemParam, SystemParamItem},
+    },
+    prelude::*,
+};
+use bvh::DynamicAabbTree;
+use core::marker::PhantomData;
+
+/// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+/// [AABB](ColliderAabb) intersection checks. This speeds up narrow phase collision detection,
+/// as the number of precise collision checks required is greatly reduced.
+///
+/// By default, the broad phase uses the [sweep and prune](https://en.wikipedia.org/wiki/Sweep_and_prune) algorithm.
+/// A [bounding volume hierarchy](https://en.wikipedia.org/wiki/Bounding_volume_hierarchy) can be used instead
+/// by configuring the [`BroadPhaseConfig`] resource.
+///
+/// Colliders attached to [static](RigidBody::Static) bodies are always stored in a separate
+/// bounding volume hierarchy. They are never tested against each other, and only need to be
+/// updated when they move, which keeps large levels with few dynamic bodies cheap.
+///
+/// The broad phase systems run in [`PhysicsStepSet::BroadPhase`].
+pub struct BroadPhasePlugin;
+
+impl Plugin for BroadPhasePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BroadCollisionPairs>()
+            .init_resource::<BroadPhaseConfig>()
+            .init_resource::<AabbIntervals>()
+            .init_resource::<AabbTree>()
+            .init_resource::<StaticAabbIntervals>()
+            .register_type::<BroadPhaseConfig>();
+
+        app.configure_sets(
+            PhysicsSchedule,
+            (
+                BroadPhaseSet::First,
+                BroadPhaseSet::UpdateStructures,
+                BroadPhaseSet::CollectCollisions,
+                BroadPhaseSet::FilterPairs,
+                BroadPhaseSet::Last,
+            )
+                .chain()
+                .in_set(PhysicsStepSet::BroadPhase),
+        );
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(
+            (
+                update_aabb_intervals,
+                add_new_aabb_intervals,
+                update_aabb_trees,
+            )
+                .chain()
+                .in_set(BroadPhaseSet::UpdateStructures),
+        );
+
+        physics_schedule
+            .add_systems(collect_collision_pairs.in_set(BroadPhaseSet::CollectCollisions));
+
+        physics_schedule.add_systems(
+            (
+                #[cfg(feature = "2d")]
+                filter_z_layers,
+                filter_blocker_volumes,
+            )
+                .chain()
+                .in_set(BroadPhaseSet::FilterPairs),
+        );
+    }
+}
+
+/// A rule for rejecting pairs of colliders collected by the broad phase
+/// before they are passed to the narrow phase.
+///
+/// Pair filters can use arbitrary component data through the [`SystemParam`](bevy::ecs::system::SystemParam)
+/// they are implemented for, which makes them more flexible than [`CollisionLayers`].
+/// This can be used for rules based on teams, ownership, and so on.
+///
+/// Pair filters are registered with the [`BroadPhasePairFilterPlugin`], and run in [`BroadPhaseSet::FilterPairs`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::{ecs::system::SystemParam, prelude::*};
+///
+/// #[derive(Component, PartialEq)]
+/// struct Team(u32);
+///
+/// // Colliders of bodies on the same team don't collide with each other.
+/// #[derive(SystemParam)]
+/// struct TeamFilter<'w, 's> {
+///     colliders: Query<'w, 's, &'static ColliderParent>,
+///     teams: Query<'w, 's, &'static Team>,
+/// }
+///
+/// impl BroadPhasePairFilter for TeamFilter<'_, '_> {
+///     fn filter_pair(&self, collider1: Entity, collider2: Entity) -> bool {
+///         let team = |collider: Entity| {
+///             let parent = self.colliders.get(collider).ok()?;
+///             self.teams.get(parent.get()).ok()
+///         };
+///         match (team(collider1), team(collider2)) {
+///             (Some(team1), Some(team2)) => team1 != team2,
+///             _ => true,
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             BroadPhasePairFilterPlugin::<TeamFilter>::default(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub trait BroadPhasePairFilter: ReadOnlySystemParam + Send + Sync {
+    /// Returns `true` if the given colliders should be passed to the narrow phase,
+    /// or `false` if the pair should be rejected.
+    fn filter_pair(&self, collider1: Entity, collider2: Entity) -> bool;
+}
+
+/// A plugin that registers a [`BroadPhasePairFilter`] for rejecting pairs collected by the [`BroadPhasePlugin`].
+///
+/// The filter runs in [`BroadPhaseSet::FilterPairs`]. Multiple filters can be registered by adding
+/// the plugin for each of them, and a pair is only kept if all of the filters keep it.
+///
+/// The plugin must be added after the [`PhysicsPlugins`].
+pub struct BroadPhasePairFilterPlugin<F: BroadPhasePairFilter + 'static>(PhantomData<F>);
+
+impl<F: BroadPhasePairFilter + 'static> Default for BroadPhasePairFilterPlugin<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: BroadPhasePairFilter + 'static> Plugin for BroadPhasePairFilterPlugin<F>
+where
+    for<'w, 's> SystemParamItem<'w, 's, F>: BroadPhasePairFilter,
+{
+    fn build(&self, app: &mut App) {
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(filter_pairs::<F>.in_set(BroadPhaseSet::FilterPairs));
+    }
+}
+
+/// Removes collision pairs rejected by the [`BroadPhasePairFilter`] `F`.
+fn filter_pairs<F: BroadPhasePairFilter + 'static>(
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    filter: StaticSystemParam<F>,
+) where
+    for<'w, 's> SystemParamItem<'w, 's, F>: BroadPhasePairFilter,
+{
+    broad_collision_pairs.retain(|&(entity1, entity2)| filter.filter_pair(entity1, entity2));
+}
+
+/// System sets for systems running in [`PhysicsStepSet::BroadPhase`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BroadPhaseSet {
+    /// Runs at the start of the broad phase. Empty by default.
+    First,
+    /// Updates acceleration structures and other data needed for broad phase collision detection.
+    UpdateStructures,
+    /// Detects potential intersections between entities and adds them to the [`BroadCollisionPairs`] resource.
+    CollectCollisions,
+    /// Removes pairs from the [`BroadCollisionPairs`] that should not be considered in the narrow phase.
+    ///
+    /// The built-in filters in this set remove pairs:
+    ///
+    #[cfg_attr(
+        feature = "2d",
+        doc = "- whose colliders are in non-overlapping [`ZLayer`] depth bands"
+    )]
+    /// - between [`BlockerVolume`]s and colliders that they don't block
+    ///
+    /// Systems in this set can reject pairs based on custom rules that cannot be expressed
+    /// with [`CollisionLayers`]. See [`BroadCollisionPairs`] for an example,
+    /// or use a [`BroadPhasePairFilter`].
+    FilterPairs,
+    /// Runs at the end of the broad phase. Empty by default.
+    Last,
+}
+
+/// Configures the broad phase used by the [`BroadPhasePlugin`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(BroadPhaseConfig {
+///             method: BroadPhaseMethod::Bvh,
+///             ..default()
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct BroadPhaseConfig {
+    /// The algorithm used for finding pairs of colliders with intersecting AABBs.
+    ///
+    /// Default: [`BroadPhaseMethod::SweepAndPrune`]
+    pub method: BroadPhaseMethod,
+    /// The amount by which the AABBs stored in the [BVH](BroadPhaseMethod::Bvh) are enlarged
+    /// in each direction. Colliders that move less than this don't need to be reinserted into the tree,
+    /// but larger values produce more false positives.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.1`
+    pub bvh_aabb_margin: Scalar,
+}
+
+impl Default for BroadPhaseConfig {
+    fn default() -> Self {
+        Self {
+            method: BroadPhaseMethod::default(),
+            bvh_aabb_margin: 0.1,
+        }
+    }
+}
+
+/// The algorithm used by the broad phase for finding pairs of colliders with intersecting AABBs.
+/// See [`BroadPhaseConfig`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum BroadPhaseMethod {
+    /// Sorts the AABBs along the x-axis and checks for overlaps between consecutive AABBs.
+    ///
+    /// This is very fast when the colliders are spread out evenly, but degrades
+    /// when many of them overlap on the x-axis, for example in tall stacks or in worlds
+    /// that are mostly spread out along another axis.
+    #[default]
+    SweepAndPrune,
+    /// Stores the AABBs in a dynamic [bounding volume hierarchy](https://en.wikipedia.org/wiki/Bounding_volume_hierarchy)
+    /// that is updated incrementally as colliders move.
+    ///
+    /// This performs consistently regardless of how the colliders are distributed,
+    /// at the cost of some overhead for maintaining the tree.
+    Bvh,
+}
+
+/// A list of entity pairs for potential collisions collected during the broad phase.
+///
+/// The pairs can be filtered by systems in [`BroadPhaseSet::FilterPairs`] before they are passed
+/// to the narrow phase. This can be used to cheaply reject pairs based on custom rules
+/// that cannot be expressed with [`CollisionLayers`], like projectiles not colliding with
+/// members of the team that fired them. Note that the [`AabbIntersections`] are not affected.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(
+    feature = "2d",
+    doc = "use avian2d::{prelude::*, collision::broad_phase::BroadPhaseSet};"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "use avian3d::{prelude::*, collision::broad_phase::BroadPhaseSet};"
+)]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component, PartialEq)]
+/// struct Team(u32);
+///
+/// #[derive(Component)]
+/// struct Projectile;
+///
+/// fn setup(app: &mut App) {
+///     app.add_systems(
+///         PhysicsSchedule,
+///         filter_friendly_fire.in_set(BroadPhaseSet::FilterPairs),
+///     );
+/// }
+///
+/// // Projectiles don't collide with members of their own team.
+/// fn filter_friendly_fire(
+///     mut pairs: ResMut<BroadCollisionPairs>,
+///     colliders: Query<&ColliderParent>,
+///     teams: Query<(&Team, Has<Projectile>)>,
+/// ) {
+///     pairs.retain(|&(entity1, entity2)| {
+///         let (Ok(parent1), Ok(parent2)) = (colliders.get(entity1), colliders.get(entity2)) else {
+///             return true;
+///         };
+///         let (Ok((team1, is_projectile1)), Ok((team2, is_projectile2))) =
+///             (teams.get(parent1.get()), teams.get(parent2.get()))
+///         else {
+///             return true;
+///         };
+///         !((is_projectile1 || is_projectile2) && team1 == team2)
+///     });
+/// }
+/// ```
+#[derive(Reflect, Resource, Debug, Default, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource)]
+pub struct BroadCollisionPairs(pub Vec<(Entity, Entity)>);
+
+/// Contains the entities whose AABBs intersect the AABB of this entity.
+/// Updated automatically during broad phase collision detection.
+///
+/// Note that this component is only added to bodies with [`SweptCcd`] by default,
+/// but can be added to any entity.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, Reflect)]
+#[reflect(Component)]
+pub struct AabbIntersections(pub Vec<Entity>);
+
+/// True if the rigid body should store [`AabbIntersections`].
+type StoreAabbIntersections = bool;
+
+/// True if the rigid body hasn't moved.
+type IsBodyInactive = bool;
+
+/// A collider with a [`ColliderAabb`], along with the data needed for filtering collision pairs.
+type AabbInterval = (
+    Entity,
+    ColliderParent,
+    ColliderAabb,
+    CollisionLayers,
+    StoreAabbIntersections,
+    IsBodyInactive,
+);
+
+/// Entities with [`ColliderAabb`]s sorted along an axis by their extents.
+///
+/// Colliders attached to [static](RigidBody::Static) bodies are stored separately
+/// in the [`StaticAabbIntervals`].
+#[derive(Resource, Default)]
+struct AabbIntervals(Vec<AabbInterval>);
+
+/// Colliders attached to [static](RigidBody::Static) bodies, stored in an [`AabbTree`].
+///
+/// Static colliders are never tested against each other, and they rarely move,
+/// so keeping them separate from the [`AabbIntervals`] avoids sorting and testing them every step.
+/// Only the static colliders that were changed or removed are processed each step.
+#[derive(Resource, Default)]
+struct StaticAabbIntervals {
+    intervals: Vec<AabbInterval>,
+    /// The index of each collider in the `intervals`.
+    indices: EntityHashMap<usize>,
+    /// Colliders whose proxies in the `tree` need to be inserted or updated.
+    changed: Vec<Entity>,
+    tree: AabbTree,
+}
+
+impl StaticAabbIntervals {
+    /// Adds the interval, or replaces the existing interval of the same collider.
+    fn insert(&mut self, interval: AabbInterval) {
+        let entity = interval.0;
+        if let Some(&index) = self.indices.get(&entity) {
+            self.intervals[index] = interval;
+        } else {
+            self.indices.insert(entity, self.intervals.len());
+            self.intervals.push(interval);
+        }
+        self.changed.push(entity);
+    }
+
+    /// Removes the interval of the given collider, and returns it if it existed.
+    fn remove(&mut self, entity: Entity) -> Option<AabbInterval> {
+        let index = self.indices.remove(&entity)?;
+        let interval = self.intervals.swap_remove(index);
+        self.tree.remove(entity);
+
+        // The last interval was moved into the place of the removed one.
+        if let Some(&(moved_entity, ..)) = self.intervals.get(index) {
+            self.indices.insert(moved_entity, index);
+            self.tree.set_index(moved_entity, index);
+        }
+
+        Some(interval)
+    }
+}
+
+impl MapEntities for StaticAabbIntervals {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for interval in self.intervals.iter_mut() {
+            interval.0 = entity_mapper.map_entity(interval.0);
+            interval.1.map_entities(entity_mapper);
+        }
+        for entity in self.changed.iter_mut() {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+        self.indices = self
+            .indices
+            .drain()
+            .map(|(entity, index)| (entity_mapper.map_entity(entity), index))
+            .collect();
+        self.tree.map_entities(entity_mapper);
+    }
+}
+
+/// A [`DynamicAabbTree`] containing [`AabbInterval`]s.
+///
+/// This is used for the [`StaticAabbIntervals`], and for the [`AabbIntervals`]
+/// when [`BroadPhaseMethod::Bvh`] is selected.
+#[derive(Resource, Default)]
+struct AabbTree {
+    tree: DynamicAabbTree,
+    /// The IDs of the proxies of each collider in the tree.
+    proxies: EntityHashMap<u32>,
+}
+
+impl AabbTree {
+    /// Updates the tree to match the given intervals.
+    ///
+    /// Proxies are only reinserted when their AABB moves outside of their enlarged AABB in the tree.
+    fn update(&mut self, intervals: &[AabbInterval], margin: Scalar) {
+        let Self { tree, proxies } = &mut *self;
+
+        // Remove the proxies of colliders that were removed or disabled.
+        let colliders: EntityHashSet = intervals.iter().map(|interval| interval.0).collect();
+        proxies.retain(|entity, proxy| {
+            let keep = colliders.contains(entity);
+            if !keep {
+                tree.remove(*proxy);
+            }
+            keep
+        });
+
+        for (index, (entity, _, aabb, ..)) in intervals.iter().enumerate() {
+            self.insert_or_update(*entity, *aabb, index, margin);
+        }
+    }
+
+    /// Inserts a proxy for the collider, or updates its existing proxy.
+    ///
+    /// The proxy is only reinserted when the AABB moves outside of its enlarged AABB in the tree.
+    fn insert_or_update(
+        &mut self,
+        entity: Entity,
+        aabb: ColliderAabb,
+        index: usize,
+        margin: Scalar,
+    ) {
+        if let Some(&proxy) = self.proxies.get(&entity) {
+            self.tree.update(proxy, aabb, margin);
+            // The order of the intervals can change, so the index must be updated.
+            self.tree.set_data(proxy, index);
+        } else {
+            let proxy = self.tree.insert(aabb.grow(Vector::splat(margin)), index);
+            self.proxies.insert(entity, proxy);
+        }
+    }
+
+    /// Removes the proxy of the collider, if it has one.
+    fn remove(&mut self, entity: Entity) {
+        if let Some(proxy) = self.proxies.remove(&entity) {
+            self.tree.remove(proxy);
+        }
+    }
+
+    /// Sets the index of the interval of the collider, if it has a proxy.
+    fn set_index(&mut self, entity: Entity, index: usize) {
+        if let Some(&proxy) = self.proxies.get(&entity) {
+            self.tree.set_data(proxy, index);
+        }
+    }
+
+    /// Removes all proxies from the tree.
+    fn clear(&mut self) {
+        self.tree.clear();
+        self.proxies.clear();
+    }
+}
+
+impl MapEntities for AabbTree {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.proxies = self
+            .proxies
+            .drain()
+            .map(|(entity, proxy)| (entity_mapper.map_entity(entity), proxy))
+            .collect();
+    }
+}
+
+impl MapEntities for AabbIntervals {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for interval in self.0.iter_mut() {
+            interval.0 = entity_mapper.map_entity(interval.0);
+            interval.1.map_entities(entity_mapper);
+        }
+    }
+}
+
+/// Updates [`AabbIntervals`] and [`StaticAabbIntervals`] to keep them in sync with the [`ColliderAabb`]s.
+///
+/// Colliders are moved between the two when their rigid body becomes static or stops being static.
+///
+/// All of the [`AabbIntervals`] are updated, but only the static colliders that were changed
+/// or removed are updated in the [`StaticAabbIntervals`].
+#[allow(clippy::type_complexity)]
+fn update_aabb_intervals(
+    aabbs: Query<
+        (
+            &ColliderAabb,
+            Option<&ColliderParent>,
+            Option<&CollisionLayers>,
+            Has<AabbIntersections>,
+            Has<Sleeping>,
+        ),
+        (Without<ColliderDisabled>, Without<QueryOnly>),
+    >,
+    changed_colliders: Query<
+        Entity,
+        Or<(
+            Changed<ColliderAabb>,
+            Changed<ColliderParent>,
+            Changed<CollisionLayers>,
+            Added<AabbIntersections>,
+            Added<ColliderDisabled>,
+            Added<QueryOnly>,
+        )>,
+    >,
+    changed_rbs: Query<(Entity, Ref<RigidBody>), Changed<RigidBody>>,
+    rbs: Query<&RigidBody>,
+    mut intervals: ResMut<AabbIntervals>,
+    mut static_intervals: ResMut<StaticAabbIntervals>,
+    mut removed_aabbs: RemovedComponents<ColliderAabb>,
+    mut removed_intersections: RemovedComponents<AabbIntersections>,
+) {
+    // Updates the interval, and returns whether it should be kept in its current list.
+    // Intervals that need to be moved to the other list are pushed to `moved_intervals`.
+    let update_interval = |interval: &mut AabbInterval,
+                           in_static_list: bool,
+                           moved_intervals: &mut Vec<AabbInterval>|
+     -> bool {
+        let (collider_entity, collider_parent, aabb, layers, store_intersections, is_inactive) =
+            interval;
+
+        let Ok((new_aabb, new_parent, new_layers, new_store_intersections, is_sleeping)) =
+            aabbs.get(*collider_entity)
+        else {
+            return false;
+        };
+
+        if !new_aabb.min.is_finite() || !new_aabb.max.is_finite() {
+            return false;
+        }
+
+        *aabb = *new_aabb;
+        *collider_parent = new_parent.map_or(ColliderParent(*collider_entity), |p| *p);
+        *layers = new_layers.map_or(CollisionLayers::default(), |layers| *layers);
+
+        let is_static =
+            new_parent.is_some_and(|p| rbs.get(p.get()).is_ok_and(RigidBody::is_static));
+        *is_inactive = is_static || is_sleeping;
+        *store_intersections = new_store_intersections;
+
+        if is_static != in_static_list {
+            moved_intervals.push(*interval);
+            return false;
+        }
+
+        true
+    };
+
+    let mut became_static = Vec::new();
+    intervals
+        .0
+        .retain_mut(|interval| update_interval(interval, false, &mut became_static));
+
+    for entity in removed_aabbs.read() {
+        static_intervals.remove(entity);
+    }
+
+    let mut changed_statics: Vec<Entity> = changed_colliders
+        .iter()
+        .chain(removed_intersections.read())
+        .collect();
+
+    // The colliders of bodies that stop being static aren't changed themselves,
+    // so the static colliders must be searched for them. This only happens when a body type changes.
+    let no_longer_static: EntityHashSet = changed_rbs
+        .iter()
+        .filter(|(_, rb)| !rb.is_added() && !rb.is_static())
+        .map(|(entity, _)| entity)
+        .collect();
+    if !no_longer_static.is_empty() {
+        changed_statics.extend(
+            static_intervals
+                .intervals
+                .iter()
+                .filter(|(_, parent, ..)| no_longer_static.contains(&parent.get()))
+                .map(|(entity, ..)| *entity),
+        );
+    }
+
+    let mut became_dynamic = Vec::new();
+    for entity in changed_statics {
+        let Some(&index) = static_intervals.indices.get(&entity) else {
+            continue;
+        };
+        let mut interval = static_intervals.intervals[index];
+        if update_interval(&mut interval, true, &mut became_dynamic) {
+            static_intervals.insert(interval);
+        } else {
+            static_intervals.remove(entity);
+        }
+    }
+
+    for interval in became_static {
+        static_intervals.insert(interval);
+    }
+    intervals.0.extend(became_dynamic);
+}
+
+type AabbIntervalQueryData = (
+    Entity,
+    Option<Read<ColliderParent>>,
+    Read<ColliderAabb>,
+    Option<Read<CollisionLayers>>,
+    Has<AabbIntersections>,
+);
+
+/// Adds new [`ColliderAabb`]s to [`AabbIntervals`] or [`StaticAabbIntervals`].
+#[allow(clippy::type_complexity)]
+fn add_new_aabb_intervals(
+    added_aabbs: Query<
+        AabbIntervalQueryData,
+        (
+            Added<ColliderAabb>,
+            Without<ColliderDisabled>,
+            Without<QueryOnly>,
+        ),
+    >,
+    aabbs: Query<AabbIntervalQueryData, (Without<ColliderDisabled>, Without<QueryOnly>)>,
+    rbs: Query<&RigidBody>,
+    mut intervals: ResMut<AabbIntervals>,
+    mut static_intervals: ResMut<StaticAabbIntervals>,
+    mut re_enabled_colliders: RemovedComponents<ColliderDisabled>,
+    mut removed_query_only: RemovedComponents<QueryOnly>,
+) {
+    // Colliders that are no longer disabled or query-only are added back to the broad phase.
+    let re_enabled_aabbs =
+        aabbs.iter_many(re_enabled_colliders.read().chain(removed_query_only.read()));
+
+    for (ent, parent, aabb, layers, store_intersections) in
+        added_aabbs.iter().chain(re_enabled_aabbs)
+    {
+        let parent = parent.map_or(ColliderParent(ent), |p| *p);
+        let is_static = rbs.get(parent.get()).is_ok_and(RigidBody::is_static);
+        let interval = (
+            ent,
+            parent,
+            *aabb,
+            layers.map_or(CollisionLayers::default(), |layers| *layers),
+            store_intersections,
+            is_static,
+        );
+
+        if is_static {
+            static_intervals.insert(interval);
+        } else {
+            intervals.0.push(interval);
+        }
+    }
+}
+
+/// Updates the [`AabbTree`] of the [`StaticAabbIntervals`], and the [`AabbTree`] for the [`AabbIntervals`]
+/// when [`BroadPhaseMethod::Bvh`] is selected.
+fn update_aabb_trees(
+    config: Res<BroadPhaseConfig>,
+    intervals: Res<AabbIntervals>,
+    mut aabb_tree: ResMut<AabbTree>,
+    mut static_intervals: ResMut<StaticAabbIntervals>,
+    length_unit: Res<PhysicsLengthUnit>,
+) {
+    let margin = config.bvh_aabb_margin * length_unit.0;
+
+    // Only the proxies of static colliders that were added or changed need to be updated.
+    let StaticAabbIntervals {
+        intervals: statics,
+        indices,
+        changed,
+        tree,
+    } = &mut *static_intervals;
+    for entity in changed.drain(..) {
+        if let Some(&index) = indices.get(&entity) {
+            tree.insert_or_update(entity, statics[index].2, index, margin);
+        }
+    }
+
+    if config.method == BroadPhaseMethod::Bvh {
+        aabb_tree.update(&intervals.0, margin);
+    } else if aabb_tree.tree.len() > 0 {
+        // Free the memory used by the tree if the method was changed.
+        aabb_tree.clear();
+    }
+}
+
+/// Collects bodies that are potentially colliding.
+fn collect_collision_pairs(
+    config: Res<BroadPhaseConfig>,
+    intervals: ResMut<AabbIntervals>,
+    aabb_tree: Res<AabbTree>,
+    static_intervals: Res<StaticAabbIntervals>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut aabb_intersection_query: Query<&mut AabbIntersections>,
+) {
+    for mut intersections in &mut aabb_intersection_query {
+        intersections.clear();
+    }
+
+    // Clear broad phase collisions from previous iteration.
+    broad_collision_pairs.clear();
+
+    match config.method {
+        BroadPhaseMethod::SweepAndPrune => sweep_and_prune(
+            intervals,
+            &mut broad_collision_pairs.0,
+            &mut aabb_intersection_query,
+        ),
+        BroadPhaseMethod::Bvh => query_aabb_tree(
+            &intervals,
+            &aabb_tree,
+            &mut broad_collision_pairs.0,
+            &mut aabb_intersection_query,
+        ),
+    }
+
+    query_static_aabb_tree(
+        &intervals,
+        &static_intervals,
+        &mut broad_collision_pairs.0,
+        &mut aabb_intersection_query,
+    );
+}
+
+/// Removes collision pairs whose colliders are in non-overlapping [`ZLayer`] depth bands.
+#[cfg(feature = "2d")]
+fn filter_z_layers(
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    colliders: Query<(Option<&ZLayer>, &ColliderParent)>,
+    z_layers: Query<&ZLayer>,
+) {
+    if z_layers.is_empty() {
+        return;
+    }
+
+    let z_layer = |entity: Entity| {
+        let (layer, parent) = colliders.get(entity).ok()?;
+        layer.or_else(|| z_layers.get(parent.get()).ok()).copied()
+    };
+
+    broad_collision_pairs.retain(|&(entity1, entity2)| {
+        match (z_layer(entity1), z_layer(entity2)) {
+            (Some(layer1), Some(layer2)) => layer1.overlaps(layer2),
+            _ => true,
+        }
+    });
+}
+
+/// Removes collision pairs between [`BlockerVolume`]s and colliders that they don't block.
+fn filter_blocker_volumes(
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    blockers: Query<&BlockerVolume>,
+    layers: Query<&CollisionLayers>,
+) {
+    if blockers.is_empty() {
+        return;
+    }
+
+    let blocks = |blocker: Entity, other: Entity| {
+        blockers.get(blocker).map_or(true, |blocker| {
+            blocker.blocks(layers.get(other).copied().unwrap_or_default())
+        })
+    };
+
+    broad_collision_pairs
+        .retain(|&(entity1, entity2)| blocks(entity1, entity2) && blocks(entity2, entity1));
+}
+
+/// Returns `true` if the colliders of the given intervals can interact with each other.
+///
+/// There are no collisions between bodies that haven't moved, colliders with incompatible layers,
+/// or colliders with the same parent.
+fn can_interact(interval1: &AabbInterval, interval2: &AabbInterval) -> bool {
+    let (_, parent1, _, layers1, _, inactive1) = interval1;
+    let (_, parent2, _, layers2, _, inactive2) = interval2;
+    !(*inactive1 && *inactive2) && layers1.interacts_with(*layers2) && parent1 != parent2
+}
+
+/// Adds the colliders of the given intervals as a potential collision pair,
+/// and stores their [`AabbIntersections`] if needed.
+fn add_collision_pair(
+    interval1: &AabbInterval,
+    interval2: &AabbInterval,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    aabb_intersection_query: &mut Query<&mut AabbIntersections>,
+) {
+    let (ent1, _, _, _, store_intersections1, _) = interval1;
+    let (ent2, _, _, _, store_intersections2, _) = interval2;
+
+    broad_collision_pairs.push((*ent1, *ent2));
+
+    if *store_intersections1 {
+        if let Ok(mut intersections) = aabb_intersection_query.get_mut(*ent1) {
+            intersections.push(*ent2);
+        }
+    }
+    if *store_intersections2 {
+        if let Ok(mut intersections) = aabb_intersection_query.get_mut(*ent2) {
+            intersections.push(*ent1);
+        }
+    }
+}
+
+/// Collects the entity pairs that have intersecting AABBs by querying the [`AabbTree`]
+/// with the AABB of each collider.
+fn query_aabb_tree(
+    intervals: &AabbIntervals,
+    aabb_tree: &AabbTree,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    aabb_intersection_query: &mut Query<&mut AabbIntersections>,
+) {
+    let mut candidates = Vec::new();
+
+    for (i, interval1) in intervals.0.iter().enumerate() {
+        // Only consider colliders after this one to avoid reporting pairs twice.
+        candidates.clear();
+        aabb_tree.tree.query(&interval1.2, |j| {
+            if j > i {
+                candidates.push(j);
+            }
+        });
+
+        // Sort the candidates so that the pairs are collected in a deterministic order.
+        candidates.sort_unstable();
+
+        for &j in &candidates {
+            let interval2 = &intervals.0[j];
+
+            // The tree stores enlarged AABBs, so the actual AABBs must still be checked.
+            if can_interact(interval1, interval2) && interval1.2.intersects(&interval2.2) {
+                add_collision_pair(
+                    interval1,
+                    interval2,
+                    broad_collision_pairs,
+                    aabb_intersection_query,
+                );
+            }
+        }
+    }
+}
+
+/// Collects the entity pairs between the [`AabbIntervals`] and the [`StaticAabbIntervals`]
+/// that have intersecting AABBs.
+fn query_static_aabb_tree(
+    intervals: &AabbIntervals,
+    static_intervals: &StaticAabbIntervals,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    aabb_intersection_query: &mut Query<&mut AabbIntersections>,
+) {
+    let mut candidates = Vec::new();
+
+    for interval1 in intervals.0.iter() {
+        // Inactive bodies can't collide with static bodies.
+        if interval1.5 {
+            continue;
+        }
+
+        candidates.clear();
+        static_intervals
+            .tree
+            .tree
+            .query(&interval1.2, |j| candidates.push(j));
+
+        // Sort the candidates so that the pairs are collected in a deterministic order.
+        candidates.sort_unstable();
+
+        for &j in &candidates {
+            let interval2 = &static_intervals.intervals[j];
+
+            if can_interact(interval1, interval2) && interval1.2.intersects(&interval2.2) {
+                add_collision_pair(
+                    interval1,
+                    interval2,
+                    broad_collision_pairs,
+                    aabb_intersection_query,
+                );
+            }
+        }
+    }
+}
+
+/// Sorts the entities by their minimum extents along an axis and collects the entity pairs that have intersecting AABBs.
+///
+/// Sweep and prune exploits temporal coherence, as bodies are unlikely to move significantly between two simulation steps. Insertion sort is used, as it is good at sorting nearly sorted lists efficiently.
+fn sweep_and_prune(
+    mut intervals: ResMut<AabbIntervals>,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    aabb_intersection_query: &mut Query<&mut AabbIntersections>,
+) {
+    // Sort bodies along the x-axis using insertion sort, a sorting algorithm great for sorting nearly sorted lists.
+    insertion_sort(&mut intervals.0, |a, b| a.2.min.x > b.2.min.x);
+
+    // Find potential collisions by checking for AABB intersections along all axes.
+    for (i, interval1) in intervals.0.iter().enumerate() {
+        let aabb1 = &interval1.2;
+
+        for interval2 in intervals.0.iter().skip(i + 1) {
+            let aabb2 = &interval2.2;
+
+            // x doesn't intersect; check this first so we can discard as soon as possible
+            if aabb2.min.x > aabb1.max.x {
+                break;
+            }
+
+            if !can_interact(interval1, interval2) {
+                continue;
+            }
+
+            // y doesn't intersect
+            if aabb1.min.y > aabb2.max.y || aabb1.max.y < aabb2.min.y {
+                continue;
+            }
+
+            #[cfg(feature = "3d")]
+            // z doesn't intersect
+            if aabb1.min.z > aabb2.max.z || aabb1.max.z < aabb2.min.z {
+                continue;
+            }
+
+            add_collision_pair(
+                interval1,
+                interval2,
+                broad_collision_pairs,
+                aabb_intersection_query,
+            );
+        }
+    }
+}
+
+/// Sorts a list iteratively using comparisons. In an ascending sort order, when a smaller value is encountered, it is moved lower in the list until it is larger than the item before it.
+///
+/// This is relatively slow for large lists, but very efficient in cases where the list is already mostly sorted.
+fn insertion_sort<T>(items: &mut [T], comparison: fn(&T, &T) -> bool) {
+    for i in 1..items.len() {
+        let mut j = i;
+        while j > 0 && comparison(&items[j - 1], &items[j]) {
+            items.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
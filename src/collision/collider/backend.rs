@@ -495,6 +495,10 @@ fn pretty_name(name: Option<&Name>, entity: Entity) -> String {
         .unwrap_or_else(|| format!("<unnamed entity {}>", entity.index()))
 }
 
+/// The rotation angle in radians during a single step above which the AABBs
+/// of bodies using [`SweepMode::NonLinear`] are expanded to cover their full rotation.
+const SWEPT_ROTATION_BOUND_ANGLE: Scalar = PI / 4.0;
+
 /// Updates the Axis-Aligned Bounding Boxes of all colliders.
 #[allow(clippy::type_complexity)]
 fn update_aabb<C: AnyCollider>(
@@ -507,7 +511,7 @@ fn update_aabb<C: AnyCollider>(
             Option<&ColliderParent>,
             Option<&CollisionMargin>,
             Option<&SpeculativeMargin>,
-            Has<SweptCcd>,
+            Option<&SweptCcd>,
             Option<&LinearVelocity>,
             Option<&AngularVelocity>,
         ),
@@ -519,12 +523,14 @@ fn update_aabb<C: AnyCollider>(
             Changed<C>,
         )>,
     >,
-    parent_velocity: Query<
+    parent_bodies: Query<
         (
             &Position,
             &ComputedCenterOfMass,
             Option<&LinearVelocity>,
             Option<&AngularVelocity>,
+            Option<&CollisionMargin>,
+            Option<&SpeculativeMargin>,
         ),
         With<Children>,
     >,
@@ -544,16 +550,23 @@ fn update_aabb<C: AnyCollider>(
         collider_parent,
         collision_margin,
         speculative_margin,
-        has_swept_ccd,
+        swept_ccd,
         lin_vel,
         ang_vel,
     ) in &mut colliders
     {
-        let collision_margin = collision_margin.map_or(0.0, |margin| margin.0);
-        let speculative_margin = if has_swept_ccd {
+        let parent_body = collider_parent.and_then(|p| parent_bodies.get(p.get()).ok());
+
+        // The margins of the collider take precedence over the margins of its rigid body.
+        let collision_margin = collision_margin
+            .or(parent_body.and_then(|(.., margin, _)| margin))
+            .map_or(0.0, |margin| margin.0);
+        let speculative_margin = if swept_ccd.is_some() {
             Scalar::MAX
         } else {
-            speculative_margin.map_or(default_speculative_margin, |margin| margin.0)
+            speculative_margin
+                .or(parent_body.and_then(|(.., margin)| margin))
+                .map_or(default_speculative_margin, |margin| margin.0)
         };
 
         if speculative_margin <= 0.0 {
@@ -566,8 +579,8 @@ fn update_aabb<C: AnyCollider>(
         // Expand the AABB based on the body's velocity and CCD speculative margin.
         let (lin_vel, ang_vel) = if let (Some(lin_vel), Some(ang_vel)) = (lin_vel, ang_vel) {
             (*lin_vel, *ang_vel)
-        } else if let Some(Ok((parent_pos, center_of_mass, Some(lin_vel), Some(ang_vel)))) =
-            collider_parent.map(|p| parent_velocity.get(p.get()))
+        } else if let Some((parent_pos, center_of_mass, Some(lin_vel), Some(ang_vel), ..)) =
+            parent_body
         {
             // If the rigid body is rotating, off-center colliders will orbit around it,
             // which affects their linear velocities. We need to compute the linear velocity
@@ -613,9 +626,27 @@ fn update_aabb<C: AnyCollider>(
         };
         // Compute swept AABB, the space that the body would occupy if it was integrated for one frame
         // TODO: Should we expand the AABB in all directions for speculative contacts?
-        *aabb = collider
-            .swept_aabb(start_pos.0, start_rot, end_pos, end_rot)
-            .grow(Vector::splat(collision_margin));
+        let mut swept_aabb = collider.swept_aabb(start_pos.0, start_rot, end_pos, end_rot);
+
+        // For non-linear swept CCD, the shape can pass through space that is not covered
+        // by the AABBs at the start and end of a large rotation, like a spinning propeller.
+        // Cover the full rotation with the bounding sphere of the shape around its origin.
+        #[cfg(feature = "2d")]
+        let rotation_angle = ang_vel.0.abs() * delta_secs;
+        #[cfg(feature = "3d")]
+        let rotation_angle = ang_vel.0.length() * delta_secs;
+        if swept_ccd.is_some_and(|ccd| ccd.mode == SweepMode::NonLinear)
+            && rotation_angle > SWEPT_ROTATION_BOUND_ANGLE
+        {
+            let local_aabb = collider.aabb(Vector::ZERO, Rotation::default());
+            let radius = local_aabb.min.abs().max(local_aabb.max.abs()).length();
+            let half_extents = Vector::splat(radius);
+            swept_aabb = swept_aabb
+                .merged(ColliderAabb::new(start_pos.0, half_extents))
+                .merged(ColliderAabb::new(end_pos, half_extents));
+        }
+
+        *aabb = swept_aabb.grow(Vector::splat(collision_margin));
     }
 }
 
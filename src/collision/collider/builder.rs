@@ -0,0 +1,304 @@
+use std::fmt;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A builder for spawning a collider together with its companion components,
+/// such as a [`CollisionMargin`], [`Friction`], or [`CollisionLayers`].
+///
+/// Each option is validated, and [`build`](Self::build) returns a [`ColliderBuilderError`]
+/// instead of producing a bundle that would make the simulation misbehave, like a negative density
+/// or a collider with a non-finite size.
+///
+/// Only the components for the options that were set are included in the bundle.
+/// This way, properties like [`Friction`] and [`CollisionMargin`] can still be inherited
+/// from the rigid body if they are not set on the collider. Each option can only be set once.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+#[cfg_attr(
+    feature = "2d",
+    doc = "    let collider = ColliderBuilder::new(Collider::circle(0.5))"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    let collider = ColliderBuilder::new(Collider::sphere(0.5))"
+)]
+///         .margin(0.01)
+///         .friction(Friction::new(0.8))
+///         .restitution(Restitution::new(0.2))
+///         .density(2.5)
+///         .user_data(7)
+///         .build()
+///         .expect("the collider options should be valid");
+///
+///     commands.spawn((RigidBody::Dynamic, collider));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct ColliderBuilder<C: AnyCollider, B: Bundle = ()> {
+    collider: C,
+    bundle: B,
+    set_options: Vec<&'static str>,
+    error: Option<ColliderBuilderError>,
+}
+
+impl<C: AnyCollider> ColliderBuilder<C> {
+    /// Creates a new [`ColliderBuilder`] for the given collider.
+    pub fn new(collider: C) -> Self {
+        Self {
+            collider,
+            bundle: (),
+            set_options: vec![],
+            error: None,
+        }
+    }
+}
+
+impl<C: AnyCollider, B: Bundle> ColliderBuilder<C, B> {
+    /// Sets the [`CollisionMargin`] of the collider.
+    ///
+    /// The margin must be finite and non-negative.
+    pub fn margin(self, margin: Scalar) -> ColliderBuilder<C, (B, CollisionMargin)> {
+        let valid = margin.is_finite() && margin >= 0.0;
+        self.with_option(
+            "margin",
+            CollisionMargin(margin),
+            valid
+                .then_some(())
+                .ok_or(ColliderBuilderError::InvalidMargin(margin)),
+        )
+    }
+
+    /// Sets the [`Friction`] of the collider.
+    ///
+    /// The friction coefficients must be in the [valid range](PhysicsProperty::valid_range)
+    /// of [`PhysicsProperty::DynamicFriction`] and [`PhysicsProperty::StaticFriction`].
+    pub fn friction(self, friction: Friction) -> ColliderBuilder<C, (B, Friction)> {
+        let valid = is_valid(
+            PhysicsProperty::DynamicFriction,
+            friction.dynamic_coefficient,
+        ) && is_valid(PhysicsProperty::StaticFriction, friction.static_coefficient);
+        self.with_option(
+            "friction",
+            friction,
+            valid
+                .then_some(())
+                .ok_or(ColliderBuilderError::InvalidFriction(friction)),
+        )
+    }
+
+    /// Sets the [`Restitution`] of the collider.
+    ///
+    /// The coefficient of restitution must be in the [valid range](PhysicsProperty::valid_range)
+    /// of [`PhysicsProperty::Restitution`], between `0.0` and `1.0`.
+    pub fn restitution(self, restitution: Restitution) -> ColliderBuilder<C, (B, Restitution)> {
+        let valid = is_valid(PhysicsProperty::Restitution, restitution.coefficient);
+        self.with_option(
+            "restitution",
+            restitution,
+            valid
+                .then_some(())
+                .ok_or(ColliderBuilderError::InvalidRestitution(restitution)),
+        )
+    }
+
+    /// Sets the [`CollisionLayers`] of the collider.
+    pub fn layers(self, layers: CollisionLayers) -> ColliderBuilder<C, (B, CollisionLayers)> {
+        self.with_option("layers", layers, Ok(()))
+    }
+
+    /// Sets the [`ColliderDensity`] of the collider.
+    ///
+    /// The density must be in the [valid range](PhysicsProperty::valid_range)
+    /// of [`PhysicsProperty::ColliderDensity`], so it must be positive.
+    pub fn density(self, density: f32) -> ColliderBuilder<C, (B, ColliderDensity)> {
+        let valid = is_valid(PhysicsProperty::ColliderDensity, density as Scalar);
+        self.with_option(
+            "density",
+            ColliderDensity(density),
+            valid
+                .then_some(())
+                .ok_or(ColliderBuilderError::InvalidDensity(density)),
+        )
+    }
+
+    /// Makes the collider a [`Sensor`].
+    pub fn sensor(self) -> ColliderBuilder<C, (B, Sensor)> {
+        self.with_option("sensor", Sensor, Ok(()))
+    }
+
+    /// Sets the [`CollisionUserData`] of the collider.
+    pub fn user_data(
+        self,
+        user_data: impl Into<CollisionUserData>,
+    ) -> ColliderBuilder<C, (B, CollisionUserData)> {
+        self.with_option("user data", user_data.into(), Ok(()))
+    }
+
+    /// Validates the collider and its options, and returns a bundle
+    /// with the collider and the components for the options that were set.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ColliderBuilderError`] that was encountered.
+    pub fn build(self) -> Result<(C, B), ColliderBuilderError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let aabb = self.collider.aabb(Vector::ZERO, Rotation::default());
+        if !aabb.min.is_finite() || !aabb.max.is_finite() {
+            return Err(ColliderBuilderError::InvalidShape);
+        }
+
+        Ok((self.collider, self.bundle))
+    }
+
+    /// Adds a component to the bundle, recording an error if the option is invalid or has already been set.
+    fn with_option<T: Component>(
+        mut self,
+        name: &'static str,
+        component: T,
+        validation: Result<(), ColliderBuilderError>,
+    ) -> ColliderBuilder<C, (B, T)> {
+        if self.error.is_none() {
+            if self.set_options.contains(&name) {
+                // A bundle can't contain the same component twice.
+                self.error = Some(ColliderBuilderError::Duplicate(name));
+            } else {
+                self.error = validation.err();
+            }
+        }
+        self.set_options.push(name);
+
+        ColliderBuilder {
+            collider: self.collider,
+            bundle: (self.bundle, component),
+            set_options: self.set_options,
+            error: self.error,
+        }
+    }
+}
+
+/// Returns `true` if the value is in the [valid range](PhysicsProperty::valid_range) of the property.
+/// This matches the validation done for colliders at runtime.
+fn is_valid(property: PhysicsProperty, value: Scalar) -> bool {
+    let (min, max) = property.valid_range();
+    (min..=max).contains(&value)
+}
+
+/// An error returned by [`ColliderBuilder::build`] when the collider or one of its options is invalid.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColliderBuilderError {
+    /// The shape of the collider has a non-finite size, for example because of `NaN` dimensions.
+    InvalidShape,
+    /// The [`CollisionMargin`] is negative or not finite.
+    InvalidMargin(Scalar),
+    /// A [`Friction`] coefficient is negative or `NaN`.
+    InvalidFriction(Friction),
+    /// The [`Restitution`] coefficient is not between `0.0` and `1.0`.
+    InvalidRestitution(Restitution),
+    /// The [`ColliderDensity`] is not positive.
+    InvalidDensity(f32),
+    /// The option with the given name was set more than once.
+    Duplicate(&'static str),
+}
+
+impl fmt::Display for ColliderBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidShape => write!(f, "the collider shape has a non-finite size"),
+            Self::InvalidMargin(margin) => write!(
+                f,
+                "the collision margin must be finite and non-negative, got {margin}"
+            ),
+            Self::InvalidFriction(friction) => write!(
+                f,
+                "the friction coefficients must be non-negative, got {} (dynamic) and {} (static)",
+                friction.dynamic_coefficient, friction.static_coefficient
+            ),
+            Self::InvalidRestitution(restitution) => write!(
+                f,
+                "the coefficient of restitution must be between 0 and 1, got {}",
+                restitution.coefficient
+            ),
+            Self::InvalidDensity(density) => {
+                write!(f, "the collider density must be positive, got {density}")
+            }
+            Self::Duplicate(name) => write!(f, "the collider {name} was set more than once"),
+        }
+    }
+}
+
+impl std::error::Error for ColliderBuilderError {}
+
+#[cfg(test)]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_options() {
+        #[cfg(feature = "2d")]
+        let collider = Collider::circle(0.5);
+        #[cfg(feature = "3d")]
+        let collider = Collider::sphere(0.5);
+
+        // Only the components for the options that were set are included.
+        let (_, ((((((), margin), friction), density), Sensor), user_data)) =
+            ColliderBuilder::new(collider.clone())
+                .margin(0.05)
+                .friction(Friction::new(0.8))
+                .density(2.0)
+                .sensor()
+                .user_data(3)
+                .build()
+                .unwrap();
+        assert_eq!(margin, CollisionMargin(0.05));
+        assert_eq!(friction, Friction::new(0.8));
+        assert_eq!(density, ColliderDensity(2.0));
+        assert_eq!(user_data, CollisionUserData::Value(3));
+
+        assert_eq!(
+            ColliderBuilder::new(collider.clone())
+                .density(-1.0)
+                .build()
+                .unwrap_err(),
+            ColliderBuilderError::InvalidDensity(-1.0)
+        );
+        // The builder uses the same valid range as the runtime validation.
+        assert_eq!(
+            ColliderBuilder::new(collider.clone())
+                .density(0.0)
+                .build()
+                .unwrap_err(),
+            ColliderBuilderError::InvalidDensity(0.0)
+        );
+        assert_eq!(
+            ColliderBuilder::new(collider.clone())
+                .margin(0.1)
+                .margin(0.2)
+                .build()
+                .unwrap_err(),
+            ColliderBuilderError::Duplicate("margin")
+        );
+        assert!(matches!(
+            ColliderBuilder::new(collider)
+                .restitution(Restitution::new(1.5))
+                .build(),
+            Err(ColliderBuilderError::InvalidRestitution(_))
+        ));
+    }
+}
@@ -9,9 +9,11 @@ use bevy::{
 use derive_more::From;
 
 mod backend;
+mod builder;
 mod hierarchy;
 
 pub use backend::{ColliderBackendPlugin, ColliderMarker};
+pub use builder::{ColliderBuilder, ColliderBuilderError};
 pub use hierarchy::ColliderHierarchyPlugin;
 
 /// The default [`Collider`] that uses Parry.
@@ -299,6 +301,78 @@ impl From<Transform> for ColliderTransform {
 #[reflect(Debug, Component, Default, PartialEq)]
 pub struct Sensor;
 
+/// A marker component that disables the collision response for a collider or rigid body,
+/// while still computing full contact data for it.
+///
+/// Unlike a [`Sensor`], the collider still contributes to the mass properties of its rigid body,
+/// and the [`Contacts`] are not marked as sensor contacts. The contact manifolds are computed as usual,
+/// and [collision events](ContactReportingPlugin#collision-events) are sent, but no contact constraints
+/// are generated, so the bodies pass through each other.
+///
+/// This can be useful for telegraphing attacks, previewing motion in slow motion,
+/// or for analyzing contacts without affecting the simulation.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // Spawn a preview of an attack that reports contacts without pushing anything.
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((RigidBody::Kinematic, Collider::circle(0.5), ContactResponseDisabled));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((RigidBody::Kinematic, Collider::sphere(0.5), ContactResponseDisabled));"
+)]
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct ContactResponseDisabled;
+
+/// A marker component that limits a [collider](Collider) to [spatial queries](crate::spatial_query).
+///
+/// Query-only colliders are never added to the broad phase, so they don't generate collision pairs,
+/// [`Contacts`], or [collision events](ContactReportingPlugin#collision-events), but they can still be found
+/// with ray casts, shape casts, and other spatial queries. This is cheaper than using a [`Sensor`],
+/// which makes it useful for large numbers of static targets that are only interacted with through queries,
+/// like interaction targets and clickable volumes.
+///
+/// Like a [`ColliderDisabled`] collider, a query-only collider still contributes to the mass properties
+/// of the rigid body it is attached to.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // Spawn a door handle that can be found by the interaction ray of the player.
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((RigidBody::Static, Collider::circle(0.1), QueryOnly));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((RigidBody::Static, Collider::sphere(0.1), QueryOnly));"
+)]
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct QueryOnly;
+
 /// The Axis-Aligned Bounding Box of a [collider](Collider).
 #[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -410,6 +484,32 @@ impl Default for ColliderAabb {
     }
 }
 
+/// A bounding sphere (or circle in 2D) of a [collider](Collider).
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct ColliderBoundingSphere {
+    /// The center of the bounding sphere.
+    pub center: Vector,
+    /// The radius of the bounding sphere.
+    pub radius: Scalar,
+}
+
+impl ColliderBoundingSphere {
+    /// Creates a new [`ColliderBoundingSphere`] from the given `center` and `radius`.
+    pub fn new(center: Vector, radius: Scalar) -> Self {
+        Self { center, radius }
+    }
+
+    /// Checks if `self` intersects with `other`.
+    #[inline(always)]
+    pub fn intersects(&self, other: &Self) -> bool {
+        let radius_sum = self.radius + other.radius;
+        self.center.distance_squared(other.center) <= radius_sum * radius_sum
+    }
+}
+
 /// A component that adds an extra margin or "skin" around [`Collider`] shapes to help maintain
 /// additional separation to other objects. This added thickness can help improve
 /// stability and performance in some cases, especially for thin shapes such as trimeshes.
@@ -471,6 +571,93 @@ impl Default for ColliderAabb {
 #[doc(alias = "ContactSkin")]
 pub struct CollisionMargin(pub Scalar);
 
+/// A small piece of user data attached to a collider, carried over to the [`Contacts`]
+/// and [collision events](ContactReportingPlugin#collision-events) involving it.
+///
+/// The narrow phase already reads the components of both colliders in a collision pair,
+/// so reading the user data from the [`Contacts`] avoids a separate component lookup
+/// for each contact in systems that iterate over a large number of collisions.
+///
+/// The data can be either an arbitrary `u64` value or an [`Entity`].
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// const PROJECTILE: u64 = 1;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(0.1),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(0.1),")]
+///         CollisionUserData::Value(PROJECTILE),
+///     ));
+/// }
+///
+/// fn count_projectile_hits(collisions: Res<Collisions>) {
+///     let hits = collisions
+///         .iter()
+///         .filter(|contacts| {
+///             contacts.user_data1 == Some(CollisionUserData::Value(PROJECTILE))
+///                 || contacts.user_data2 == Some(CollisionUserData::Value(PROJECTILE))
+///         })
+///         .count();
+///     println!("{hits} projectile hits");
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq, Hash)]
+pub enum CollisionUserData {
+    /// An arbitrary user-defined value.
+    Value(u64),
+    /// An [`Entity`], for example the entity that owns the collider in game logic.
+    Entity(Entity),
+}
+
+impl CollisionUserData {
+    /// Returns the value if the user data is a [`CollisionUserData::Value`].
+    pub const fn value(&self) -> Option<u64> {
+        match self {
+            Self::Value(value) => Some(*value),
+            Self::Entity(_) => None,
+        }
+    }
+
+    /// Returns the entity if the user data is a [`CollisionUserData::Entity`].
+    pub const fn entity(&self) -> Option<Entity> {
+        match self {
+            Self::Value(_) => None,
+            Self::Entity(entity) => Some(*entity),
+        }
+    }
+}
+
+impl From<u64> for CollisionUserData {
+    fn from(value: u64) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl From<Entity> for CollisionUserData {
+    fn from(entity: Entity) -> Self {
+        Self::Entity(entity)
+    }
+}
+
+impl MapEntities for CollisionUserData {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let Self::Entity(entity) = self {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
 /// A component for reading which entities are colliding with a collider entity.
 /// Must be added manually for desired colliders.
 ///
@@ -6,7 +6,7 @@ use bevy::render::mesh::{Indices, VertexAttributeValues};
 use bevy::{log, prelude::*};
 use collision::contact_query::UnsupportedShape;
 use itertools::Either;
-use parry::shape::{RoundShape, SharedShape, TypedShape};
+use parry::shape::{RoundShape, Shape, SharedShape, TypedShape};
 
 #[cfg(feature = "2d")]
 mod primitives2d;
@@ -357,6 +357,17 @@ impl From<TrimeshFlags> for parry::shape::TriMeshFlags {
 /// To get a reference to the internal [`SharedShape`], you can use the [`Collider::shape()`]
 /// or [`Collider::shape_scaled()`] methods.
 ///
+/// ## Sharing Shapes
+///
+/// The shape of a collider is reference-counted, so cloning a collider with [`Collider::clone_shared()`]
+/// or [`Clone::clone`] doesn't copy the shape. Instead, the clones share the same shape allocation,
+/// which saves a lot of memory and makes spawning faster when there are thousands of identical colliders,
+/// especially for large shapes like [triangle meshes](Collider::trimesh).
+/// Colliders with the same scale also share their scaled shape.
+///
+/// A shared shape is only copied when it is modified with [`Collider::modify_shape()`],
+/// so that modifying the shape of one collider doesn't affect the other colliders.
+///
 /// `Collider` is currently not `Reflect`. If you need to reflect it, you can use [`ColliderConstructor`] as a workaround.
 #[derive(Clone, Component, Debug)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -371,6 +382,16 @@ pub struct Collider {
     scaled_shape: SharedShape,
     /// The global scale used for the collider shape.
     scale: Vector,
+    /// User-defined tags for the child shapes of a compound collider, in the same order as the shapes.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    sub_shape_tags: Vec<u32>,
+    /// For each vertex of a polyline, the indices of up to two segments that share the vertex
+    /// or [`u32::MAX`] for missing segments, used for smoothing the normals of contacts at the internal vertices of the polyline.
+    ///
+    /// `None` if the normals are not smoothed.
+    #[cfg(feature = "2d")]
+    #[cfg_attr(feature = "serialize", serde(default))]
+    polyline_vertex_segments: Option<Vec<[u32; 2]>>,
 }
 
 impl From<SharedShape> for Collider {
@@ -379,6 +400,9 @@ impl From<SharedShape> for Collider {
             shape: value.clone(),
             scaled_shape: value,
             scale: Vector::ONE,
+            sub_shape_tags: vec![],
+            #[cfg(feature = "2d")]
+            polyline_vertex_segments: None,
         }
     }
 }
@@ -530,10 +554,56 @@ impl Collider {
         &self.scaled_shape
     }
 
+    /// Returns a new collider that shares the same shape allocation as `self`.
+    ///
+    /// This is equivalent to [`Clone::clone`], as the shapes of colliders are reference-counted,
+    /// but makes the intent of sharing the shape explicit. See [sharing shapes](Collider#sharing-shapes).
+    pub fn clone_shared(&self) -> Self {
+        self.clone()
+    }
+
+    /// Returns `true` if `self` and `other` share the same unscaled shape allocation.
+    ///
+    /// See [sharing shapes](Collider#sharing-shapes).
+    pub fn shares_shape_with(&self, other: &Self) -> bool {
+        std::sync::Arc::ptr_eq(&self.shape.0, &other.shape.0)
+    }
+
+    /// Modifies the unscaled shape of the collider with the given function.
+    /// The collider's scale will be applied to the modified shape.
+    ///
+    /// If the shape is shared with other colliders, it is copied first,
+    /// so that the other colliders are not affected. See [sharing shapes](Collider#sharing-shapes).
+    pub fn modify_shape(&mut self, f: impl FnOnce(&mut dyn Shape)) {
+        // Release the references held by this collider, so that the shape
+        // is only copied if it is shared with other colliders.
+        let placeholder = SharedShape::ball(0.0);
+        let mut shape = std::mem::replace(&mut self.shape, placeholder.clone());
+        self.scaled_shape = placeholder;
+
+        f(shape.make_mut());
+        self.set_shape(shape);
+    }
+
     /// Sets the unscaled shape of the collider. The collider's scale will be applied to this shape.
     pub fn set_shape(&mut self, shape: SharedShape) {
         self.shape = shape;
 
+        // Keep the polyline adjacency up to date with the new shape.
+        #[cfg(feature = "2d")]
+        if self.polyline_vertex_segments.is_some() {
+            self.polyline_vertex_segments = self
+                .shape
+                .as_polyline()
+                .map(contact_query::polyline_vertex_segments);
+        }
+
+        if self.scale == Vector::ONE {
+            // Trivial case. Share the unscaled shape.
+            self.scaled_shape = self.shape.clone();
+            return;
+        }
+
         // TODO: The number of subdivisions probably shouldn't be hard-coded
         if let Ok(scaled) = scale_shape(&self.shape, self.scale, 10) {
             self.scaled_shape = scaled;
@@ -547,6 +617,44 @@ impl Collider {
         self.scale
     }
 
+    /// Computes the [Axis-Aligned Bounding Box](ColliderAabb) of the collider in its local space,
+    /// with the collider's scale applied.
+    ///
+    /// Unlike the [`ColliderAabb`] component, this doesn't require the collider to be in the world,
+    /// so it can be used for things like camera framing or fitting objects into a space before spawning them.
+    /// Use [`aabb`](AnyCollider::aabb) to compute the AABB for a given pose.
+    pub fn local_aabb(&self) -> ColliderAabb {
+        self.aabb(Vector::ZERO, Rotation::default())
+    }
+
+    /// Computes the [bounding sphere](ColliderBoundingSphere) of the collider in its local space,
+    /// with the collider's scale applied.
+    ///
+    /// Use [`world_bounding_sphere`](Self::world_bounding_sphere) to compute the bounding sphere for a given pose.
+    pub fn bounding_sphere(&self) -> ColliderBoundingSphere {
+        let sphere = self.shape_scaled().compute_local_bounding_sphere();
+        ColliderBoundingSphere {
+            center: sphere.center.into(),
+            radius: sphere.radius,
+        }
+    }
+
+    /// Computes the [bounding sphere](ColliderBoundingSphere) of the collider
+    /// transformed by `translation` and `rotation`, with the collider's scale applied.
+    pub fn world_bounding_sphere(
+        &self,
+        translation: impl Into<Position>,
+        rotation: impl Into<Rotation>,
+    ) -> ColliderBoundingSphere {
+        let sphere = self
+            .shape_scaled()
+            .compute_bounding_sphere(&make_isometry(translation, rotation));
+        ColliderBoundingSphere {
+            center: sphere.center.into(),
+            radius: sphere.radius,
+        }
+    }
+
     /// Set the global scaling factor of this shape.
     ///
     /// If the scaling factor is not uniform, and the scaled shape can’t be
@@ -702,6 +810,126 @@ impl Collider {
         SharedShape::compound(shapes).into()
     }
 
+    /// Sets user-defined tags for the child shapes of a [compound](Collider::compound) collider,
+    /// in the same order as the shapes.
+    ///
+    /// The tags are reported in the [`SubShapeHit`] of [ray](RayHitData) and [shape](ShapeHitData) hits,
+    /// which can be used for distinguishing different hitboxes in a single compound collider.
+    ///
+    /// # Example
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    ///
+    /// const BODY: u32 = 0;
+    /// const HEAD: u32 = 1;
+    ///
+    /// # #[cfg(feature = "f32")]
+    /// let collider = Collider::compound(vec![
+    #[cfg_attr(
+        feature = "2d",
+        doc = "    (Position::default(), Rotation::default(), Collider::capsule(0.4, 1.0)),"
+    )]
+    #[cfg_attr(
+        feature = "2d",
+        doc = "    (Position::from_xy(0.0, 1.2), Rotation::default(), Collider::circle(0.3)),"
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "    (Position::default(), Rotation::default(), Collider::capsule(0.4, 1.0)),"
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "    (Position::from_xyz(0.0, 1.2, 0.0), Rotation::default(), Collider::sphere(0.3)),"
+    )]
+    /// ])
+    /// .with_sub_shape_tags([BODY, HEAD]);
+    /// ```
+    pub fn with_sub_shape_tags(mut self, tags: impl IntoIterator<Item = u32>) -> Self {
+        self.sub_shape_tags = tags.into_iter().collect();
+        self
+    }
+
+    /// Returns the user-defined tag of the child shape with the given index
+    /// in a [compound](Collider::compound) collider, if it has one.
+    pub fn sub_shape_tag(&self, index: u32) -> Option<u32> {
+        self.sub_shape_tags.get(index as usize).copied()
+    }
+
+    /// Returns the child shape of a [compound](Collider::compound) collider that is closest to the given
+    /// world-space `point`, when the collider has the given `position` and `rotation`.
+    ///
+    /// Returns `None` if the collider is not a compound collider.
+    pub fn sub_shape_at_point(
+        &self,
+        position: Vector,
+        rotation: impl Into<Rotation>,
+        point: Vector,
+    ) -> Option<SubShapeHit> {
+        self.sub_shape_at_point_with_isometry(&make_isometry(position, rotation), point)
+    }
+
+    /// Returns the child shape of a [compound](Collider::compound) collider that is closest to the given
+    /// world-space `point`, when the collider has the given `isometry`.
+    ///
+    /// The child shape is the one that the point is projected onto by Parry.
+    pub(crate) fn sub_shape_at_point_with_isometry(
+        &self,
+        isometry: &parry::math::Isometry<Scalar>,
+        point: Vector,
+    ) -> Option<SubShapeHit> {
+        use parry::query::details::PointCompositeShapeProjBestFirstVisitor;
+
+        let compound = self.shape_scaled().as_compound()?;
+        let local_point = isometry.inverse_transform_point(&point.into());
+
+        let mut visitor =
+            PointCompositeShapeProjBestFirstVisitor::new(compound, &local_point, true);
+        let (_, (_, index)) = compound.qbvh().traverse_best_first(&mut visitor)?;
+
+        Some(self.sub_shape_hit(index))
+    }
+
+    /// Casts a ray against the collider with the given `isometry`, and returns the intersection
+    /// along with the child shape that was hit if the collider is a [compound](Collider::compound) collider.
+    pub(crate) fn cast_ray_with_sub_shape(
+        &self,
+        isometry: &parry::math::Isometry<Scalar>,
+        ray: &parry::query::Ray,
+        max_distance: Scalar,
+        solid: bool,
+    ) -> Option<(parry::query::RayIntersection, Option<SubShapeHit>)> {
+        use parry::query::{details::RayCompositeShapeToiAndNormalBestFirstVisitor, RayCast};
+
+        let shape = self.shape_scaled();
+        let Some(compound) = shape.as_compound() else {
+            return shape
+                .cast_ray_and_get_normal(isometry, ray, max_distance, solid)
+                .map(|hit| (hit, None));
+        };
+
+        // Cast the ray in the local space of the compound shape to get the index of the child shape that was hit.
+        let local_ray = ray.inverse_transform_by(isometry);
+        let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
+            compound,
+            &local_ray,
+            max_distance,
+            solid,
+        );
+        let (_, (index, hit)) = compound.qbvh().traverse_best_first(&mut visitor)?;
+
+        Some((hit.transform_by(isometry), Some(self.sub_shape_hit(index))))
+    }
+
+    /// Returns the [`SubShapeHit`] for the child shape with the given index.
+    fn sub_shape_hit(&self, index: u32) -> SubShapeHit {
+        SubShapeHit {
+            index,
+            tag: self.sub_shape_tag(index),
+        }
+    }
+
     /// Creates a collider with a circle shape defined by its radius.
     #[cfg(feature = "2d")]
     pub fn circle(radius: Scalar) -> Self {
@@ -848,6 +1076,21 @@ impl Collider {
         SharedShape::polyline(vertices, indices).into()
     }
 
+    /// Creates a collider with a polyline shape defined by its vertices and optionally an index buffer,
+    /// with the option to smooth the normals of contacts at the internal vertices of the polyline.
+    ///
+    /// When `smooth_normals` is `true`, shapes rolling or sliding along the polyline don't bounce
+    /// or catch at the joins between segments. This is useful for terrain made of many segments.
+    /// See [`set_internal_edge_fixing`](Self::set_internal_edge_fixing) for more details.
+    #[cfg(feature = "2d")]
+    pub fn polyline_with_config(
+        vertices: Vec<Vector>,
+        indices: Option<Vec<[u32; 2]>>,
+        smooth_normals: bool,
+    ) -> Self {
+        Self::polyline(vertices, indices).with_internal_edge_fixing(smooth_normals)
+    }
+
     /// Creates a collider with a triangle mesh shape defined by its vertex and index buffers.
     ///
     /// Note that the resulting collider will be hollow and have no interior. This makes it more prone to tunneling and other collision issues.
@@ -875,6 +1118,113 @@ impl Collider {
         SharedShape::trimesh_with_flags(vertices, indices, flags.into()).into()
     }
 
+    /// Enables or disables internal edge fixing for triangle mesh, heightfield, and 2D polyline colliders,
+    /// and returns the collider.
+    ///
+    /// See [`set_internal_edge_fixing`](Self::set_internal_edge_fixing) for more details.
+    pub fn with_internal_edge_fixing(mut self, enabled: bool) -> Self {
+        self.set_internal_edge_fixing(enabled);
+        self
+    }
+
+    /// Enables or disables internal edge fixing for triangle mesh, heightfield, and 2D polyline colliders.
+    ///
+    /// Shapes sliding across the flat surface of a triangulated collider can catch on the edges
+    /// between adjacent triangles, even though these edges are not part of the actual surface.
+    /// These *ghost collisions* cause bumps and sudden stops, especially for capsules and boxes
+    /// moving across floors made of many triangles.
+    ///
+    /// When enabled, contact normals are corrected using the normals of the adjacent triangles,
+    /// so that internal edges behave like the flat surface around them.
+    /// This is the same as using [`TrimeshFlags::FIX_INTERNAL_EDGES`] for triangle meshes.
+    ///
+    /// In 2D, this smooths the normals of contacts at the internal vertices of polylines
+    /// using the normals of the adjacent segments, so circles rolling along the polyline
+    /// don't bounce at the joins between segments. Triangle meshes in 2D are not affected.
+    ///
+    /// For other shapes, this does nothing.
+    ///
+    /// Note that for triangle meshes and heightfields, this recomputes the adjacency information
+    /// of the shape, which can be expensive for large meshes.
+    pub fn set_internal_edge_fixing(&mut self, enabled: bool) {
+        use parry::shape::{ShapeType, TriMeshFlags};
+
+        if self.fixes_internal_edges() == enabled {
+            return;
+        }
+
+        #[cfg(feature = "2d")]
+        if let Some(polyline) = self.shape.as_polyline() {
+            self.polyline_vertex_segments =
+                enabled.then(|| contact_query::polyline_vertex_segments(polyline));
+            return;
+        }
+
+        let mut shape = self.shape.clone();
+
+        match self.shape.shape_type() {
+            ShapeType::TriMesh => {
+                let Some(trimesh) = shape.make_mut().as_trimesh_mut() else {
+                    return;
+                };
+                let flags = if enabled {
+                    trimesh.flags() | TriMeshFlags::FIX_INTERNAL_EDGES
+                } else {
+                    // This also clears the orientation and vertex merging flags
+                    // that internal edge fixing depends on.
+                    trimesh.flags() - TriMeshFlags::FIX_INTERNAL_EDGES
+                };
+                if let Err(error) = trimesh.set_flags(flags) {
+                    log::error!(
+                        "Failed to set internal edge fixing for trimesh collider: {error:?}"
+                    );
+                    return;
+                }
+            }
+            #[cfg(feature = "3d")]
+            ShapeType::HeightField => {
+                let Some(heightfield) = shape.make_mut().as_heightfield_mut() else {
+                    return;
+                };
+                let mut flags = heightfield.flags();
+                flags.set(parry::shape::HeightFieldFlags::FIX_INTERNAL_EDGES, enabled);
+                heightfield.set_flags(flags);
+            }
+            _ => return,
+        }
+
+        self.set_shape(shape);
+    }
+
+    /// Returns the indices of up to two segments that share each vertex of the polyline,
+    /// if the collider is a polyline with [internal edge fixing](Self::set_internal_edge_fixing) enabled.
+    #[cfg(feature = "2d")]
+    pub(crate) fn polyline_vertex_segments(&self) -> Option<&[[u32; 2]]> {
+        self.polyline_vertex_segments.as_deref()
+    }
+
+    /// Returns `true` if internal edge fixing is enabled for the collider.
+    ///
+    /// See [`set_internal_edge_fixing`](Self::set_internal_edge_fixing) for more details.
+    pub fn fixes_internal_edges(&self) -> bool {
+        #[cfg(feature = "2d")]
+        if self.shape.as_polyline().is_some() {
+            return self.polyline_vertex_segments.is_some();
+        }
+        if let Some(trimesh) = self.shape.as_trimesh() {
+            return trimesh
+                .flags()
+                .contains(parry::shape::TriMeshFlags::FIX_INTERNAL_EDGES);
+        }
+        #[cfg(feature = "3d")]
+        if let Some(heightfield) = self.shape.as_heightfield() {
+            return heightfield
+                .flags()
+                .contains(parry::shape::HeightFieldFlags::FIX_INTERNAL_EDGES);
+        }
+        false
+    }
+
     /// Creates a collider shape with a compound shape obtained from the decomposition of a given polyline
     /// defined by its vertex and index buffers.
     #[cfg(feature = "2d")]
@@ -1499,3 +1849,35 @@ fn scale_shape(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn bounding_volumes_without_world() {
+        #[cfg(feature = "2d")]
+        let collider = Collider::rectangle(2.0, 4.0);
+        #[cfg(feature = "3d")]
+        let collider = Collider::cuboid(2.0, 4.0, 2.0);
+
+        let aabb = collider.local_aabb();
+        assert_relative_eq!(aabb.min.x, -1.0);
+        assert_relative_eq!(aabb.max.y, 2.0);
+
+        let sphere = collider.bounding_sphere();
+        assert_relative_eq!(sphere.center.length(), 0.0);
+        assert!(sphere.radius >= 2.0);
+
+        // The world-space variants take the pose into account.
+        let position = Vector::X * 5.0;
+        let world_sphere = collider.world_bounding_sphere(position, Rotation::default());
+        assert_relative_eq!(world_sphere.center.x, 5.0);
+        assert_relative_eq!(world_sphere.radius, sphere.radius);
+
+        let world_aabb = collider.aabb(position, Rotation::default());
+        assert_relative_eq!(world_aabb.min.x, 4.0);
+        assert_relative_eq!(world_aabb.max.x, 6.0);
+    }
+}
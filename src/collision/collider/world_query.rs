@@ -20,8 +20,10 @@ pub struct ColliderQuery<C: AnyCollider> {
     pub speculative_margin: Option<&'static SpeculativeMargin>,
     pub is_rb: Has<RigidBody>,
     pub is_sensor: Has<Sensor>,
+    pub is_response_disabled: Has<ContactResponseDisabled>,
     pub friction: Option<&'static Friction>,
     pub restitution: Option<&'static Restitution>,
+    pub user_data: Option<&'static CollisionUserData>,
     pub shape: &'static C,
 }
 
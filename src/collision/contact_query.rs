@@ -11,6 +11,20 @@
 //! | [`intersection_test`] | Tests whether two [`Collider`]s are intersecting each other.              |
 //! | [`time_of_impact`]    | Computes when two moving [`Collider`]s hit each other for the first time. |
 //!
+//! The queries are stateless: they only take the shapes and their poses, and don't need
+//! any ECS data or a Bevy `App`. This makes them usable in tools, tests, and other code
+//! that runs outside of the physics simulation.
+//!
+//! Queries against a single shape, like raycasts and point projection, are methods on [`Collider`]:
+//!
+//! | Shape query                           | Description                                                          |
+//! | ------------------------------------- | -------------------------------------------------------------------- |
+//! | [`Collider::cast_ray`]                | Computes the distance and normal where a ray hits a [`Collider`].    |
+//! | [`Collider::intersects_ray`]          | Tests whether a ray intersects a [`Collider`].                       |
+//! | [`Collider::project_point`]           | Projects a point onto a [`Collider`].                                |
+//! | [`Collider::distance_to_point`]       | Computes the distance between a point and a [`Collider`].            |
+//! | [`Collider::contains_point`]          | Tests whether a point is inside of a [`Collider`].                   |
+//!
 //! For geometric queries that query the entire world for intersections, like raycasting, shapecasting
 //! and point projection, see [spatial queries](spatial_query).
 
@@ -208,6 +222,27 @@ pub fn contact_manifolds(
         }
     }
 
+    // Smooth the normals of contacts at the internal vertices of polylines.
+    #[cfg(feature = "2d")]
+    {
+        if let (Some(polyline), Some(vertex_segments)) = (
+            collider1.shape_scaled().as_polyline(),
+            collider1.polyline_vertex_segments(),
+        ) {
+            for manifold in manifolds.iter_mut() {
+                smooth_polyline_manifold(polyline, vertex_segments, manifold, &isometry12, false);
+            }
+        }
+        if let (Some(polyline), Some(vertex_segments)) = (
+            collider2.shape_scaled().as_polyline(),
+            collider2.polyline_vertex_segments(),
+        ) {
+            for manifold in manifolds.iter_mut() {
+                smooth_polyline_manifold(polyline, vertex_segments, manifold, &isometry12, true);
+            }
+        }
+    }
+
     let mut manifold_index = 0;
 
     manifolds
@@ -258,6 +293,164 @@ pub fn contact_manifolds(
         .collect()
 }
 
+/// Corrects the normal of a contact manifold between a polyline and another shape
+/// if the contact is at an internal vertex of the polyline, and the normal points
+/// outside of the range of valid normals at that vertex.
+///
+/// `vertex_segments` stores the segments that share each vertex, computed with [`polyline_vertex_segments`].
+/// If `flipped` is `true`, the polyline is the second shape of the manifold.
+#[cfg(feature = "2d")]
+fn smooth_polyline_manifold(
+    polyline: &parry::shape::Polyline,
+    vertex_segments: &[[u32; 2]],
+    manifold: &mut parry::query::ContactManifold<(), ()>,
+    isometry12: &parry::math::Isometry<Scalar>,
+    flipped: bool,
+) {
+    // The polyline segments are in the local space of the polyline.
+    let (segment_index, polyline_normal) = if flipped {
+        (manifold.subshape2, manifold.local_n2)
+    } else {
+        (manifold.subshape1, manifold.local_n1)
+    };
+
+    // Use the deepest contact to determine which vertex of the segment is being hit.
+    let Some(deepest) = manifold
+        .points
+        .iter()
+        .min_by(|a, b| a.dist.total_cmp(&b.dist))
+    else {
+        return;
+    };
+    let point = if flipped {
+        deepest.local_p2
+    } else {
+        deepest.local_p1
+    };
+
+    let Some(normal) = smoothed_polyline_normal(
+        polyline,
+        vertex_segments,
+        segment_index,
+        polyline_normal.into(),
+        point.into(),
+    ) else {
+        return;
+    };
+    let normal: parry::math::Vector<Scalar> = normal.into();
+
+    // Transform the normal and points to the frame of the polyline
+    // and the frame of the other shape, and recompute the penetration depths.
+    if flipped {
+        let subshape_pos1 = manifold.subshape_pos1.unwrap_or_default();
+        let to_polyline = isometry12.inverse() * subshape_pos1;
+        manifold.local_n2 = normal;
+        manifold.local_n1 = to_polyline.inverse_transform_vector(&-normal);
+        for contact in manifold.points.iter_mut() {
+            contact.dist = (to_polyline * contact.local_p1 - contact.local_p2).dot(&normal);
+        }
+    } else {
+        let subshape_pos2 = manifold.subshape_pos2.unwrap_or_default();
+        let to_polyline = isometry12 * subshape_pos2;
+        manifold.local_n1 = normal;
+        manifold.local_n2 = to_polyline.inverse_transform_vector(&-normal);
+        for contact in manifold.points.iter_mut() {
+            contact.dist = (to_polyline * contact.local_p2 - contact.local_p1).dot(&normal);
+        }
+    }
+}
+
+/// Computes a smoothed contact normal for a contact with the given normal at the given point
+/// on the segment of a polyline, in the local space of the polyline.
+///
+/// At a flat or concave internal vertex, the normal of the segment is the only valid normal.
+/// At a convex internal vertex, the valid normals are between the normals of the two adjacent segments.
+/// Returns `None` if the normal doesn't need to be changed.
+#[cfg(feature = "2d")]
+fn smoothed_polyline_normal(
+    polyline: &parry::shape::Polyline,
+    vertex_segments: &[[u32; 2]],
+    segment_index: u32,
+    normal: Vector,
+    point: Vector,
+) -> Option<Vector> {
+    let indices = polyline.indices();
+    let vertices = polyline.vertices();
+    let [a_index, b_index] = *indices.get(segment_index as usize)?;
+    let a = Vector::from(vertices[a_index as usize]);
+    let b = Vector::from(vertices[b_index as usize]);
+
+    // The normal of the segment on the side of the other shape.
+    let edge = b - a;
+    let mut segment_normal = edge.perp().try_normalize()?;
+    if segment_normal.dot(normal) < 0.0 {
+        segment_normal = -segment_normal;
+    }
+
+    // Contacts on the interior of the segment already use the segment normal.
+    if segment_normal.dot(normal) >= 1.0 - 1e-4 {
+        return None;
+    }
+
+    // Find the vertex closest to the contact point and the segment adjacent to it.
+    let (vertex_index, vertex, other_end) = if (point - a).dot(edge) < 0.5 * edge.length_squared() {
+        (a_index, a, b)
+    } else {
+        (b_index, b, a)
+    };
+    let adjacent_segment = vertex_segments
+        .get(vertex_index as usize)?
+        .iter()
+        .find(|&&segment| segment != segment_index && segment != u32::MAX);
+
+    // Vertices at the ends of the polyline are not smoothed.
+    let [i1, i2] = *indices.get(*adjacent_segment? as usize)?;
+    let adjacent_end = if i1 == vertex_index { i2 } else { i1 };
+    let adjacent_edge = Vector::from(vertices[adjacent_end as usize]) - vertex;
+    let mut adjacent_normal = adjacent_edge.perp().try_normalize()?;
+
+    // If the adjacent segment doesn't bend away from the contact, the vertex is flat or concave.
+    if adjacent_edge.dot(segment_normal) >= -1e-4 * adjacent_edge.length() {
+        return Some(segment_normal);
+    }
+
+    // The vertex is convex. Orient the adjacent normal away from the other end of the segment,
+    // and clamp the contact normal between the two segment normals.
+    if adjacent_normal.dot(other_end - vertex) > 0.0 {
+        adjacent_normal = -adjacent_normal;
+    }
+    let side = segment_normal.perp_dot(adjacent_normal);
+    if segment_normal.perp_dot(normal) * side >= 0.0
+        && normal.perp_dot(adjacent_normal) * side >= 0.0
+    {
+        None
+    } else if normal.dot(segment_normal) >= normal.dot(adjacent_normal) {
+        Some(segment_normal)
+    } else {
+        Some(adjacent_normal)
+    }
+}
+
+/// Returns the indices of up to two segments that share each vertex of the given polyline,
+/// with [`u32::MAX`] for missing segments.
+///
+/// This is used for smoothing the normals of contacts at the internal vertices of the polyline.
+#[cfg(feature = "2d")]
+pub(crate) fn polyline_vertex_segments(polyline: &parry::shape::Polyline) -> Vec<[u32; 2]> {
+    let mut vertex_segments = vec![[u32::MAX; 2]; polyline.vertices().len()];
+    for (segment_index, segment) in polyline.indices().iter().enumerate() {
+        for &vertex_index in segment {
+            let Some(segments) = vertex_segments.get_mut(vertex_index as usize) else {
+                continue;
+            };
+            if let Some(slot) = segments.iter_mut().find(|slot| **slot == u32::MAX) {
+                *slot = segment_index as u32;
+            }
+        }
+    }
+    vertex_segments
+}
+
 /// Information about the closest points between two [`Collider`]s.
 ///
 /// The closest points can be computed using [`closest_points`].
@@ -40,11 +40,17 @@ use bevy::prelude::*;
 ///     }
 /// }
 /// ```
+///
+/// By default, collision events are sent for all collisions. When there are lots of contacts
+/// that nothing is interested in, the events can be limited to colliders that opt in
+/// with [`CollisionEventMode::OptIn`] and the [`CollisionEventsEnabled`] component.
+/// [`CollidingEntities`] are still updated for all colliders.
 pub struct ContactReportingPlugin;
 
 impl Plugin for ContactReportingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<Collision>()
+        app.init_resource::<CollisionEventMode>()
+            .add_event::<Collision>()
             .add_event::<CollisionStarted>()
             .add_event::<CollisionEnded>();
 
@@ -56,6 +62,63 @@ impl Plugin for ContactReportingPlugin {
     }
 }
 
+/// A resource that determines which collisions send [collision events](ContactReportingPlugin#collision-events).
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub enum CollisionEventMode {
+    /// Collision events are sent for all collisions.
+    #[default]
+    All,
+    /// Collision events are only sent for collisions where either collider
+    /// or its rigid body has the [`CollisionEventsEnabled`] component.
+    ///
+    /// This can greatly reduce the cost of collision events in scenes with lots of contacts
+    /// that nothing is interested in, like bullet hell games.
+    OptIn,
+}
+
+/// A marker component that enables [collision events](ContactReportingPlugin#collision-events)
+/// for a collider, or all colliders of a rigid body, when the [`CollisionEventMode`] is
+/// [`OptIn`](CollisionEventMode::OptIn).
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(CollisionEventMode::OptIn)
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn setup(mut commands: Commands) {
+///     // Only collisions involving the player send collision events.
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((Player, RigidBody::Dynamic, Collider::circle(0.5), CollisionEventsEnabled));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((Player, RigidBody::Dynamic, Collider::sphere(0.5), CollisionEventsEnabled));"
+)]
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct CollisionEventsEnabled;
+
 /// A [collision event](ContactReportingPlugin#collision-events)
 /// that is sent for each collision.
 ///
@@ -105,18 +168,35 @@ pub struct Collision(pub Contacts);
 /// }
 ///
 /// fn print_started_collisions(mut collision_event_reader: EventReader<CollisionStarted>) {
-///     for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
+///     for event in collision_event_reader.read() {
 ///         println!(
-///             "Entities {} and {} started colliding",
-///             entity1,
-///             entity2,
+///             "Entities {} and {} started colliding with normal {:?}",
+///             event.entity1,
+///             event.entity2,
+///             event.normal,
 ///         );
 ///     }
 /// }
 /// ```
-#[derive(Event, Clone, Debug, PartialEq)]
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-pub struct CollisionStarted(pub Entity, pub Entity);
+pub struct CollisionStarted {
+    /// The first collider entity in the collision.
+    pub entity1: Entity,
+    /// The second collider entity in the collision.
+    pub entity2: Entity,
+    /// The rigid body of the first collider.
+    pub body_entity1: Option<Entity>,
+    /// The rigid body of the second collider.
+    pub body_entity2: Option<Entity>,
+    /// True if either of the colliders is a [`Sensor`] or doesn't belong to a rigid body,
+    /// meaning that the collision doesn't produce a contact response.
+    /// See [`Contacts::is_sensor`].
+    pub is_sensor: bool,
+    /// The world-space normal of the first contact manifold,
+    /// pointing from the first collider towards the second.
+    pub normal: Option<Vector>,
+}
 
 /// A [collision event](ContactReportingPlugin#collision-events)
 /// that is sent when two entities stop colliding.
@@ -136,35 +216,88 @@ pub struct CollisionStarted(pub Entity, pub Entity);
 /// }
 ///
 /// fn print_ended_collisions(mut collision_event_reader: EventReader<CollisionEnded>) {
-///     for CollisionEnded(entity1, entity2) in collision_event_reader.read() {
+///     for event in collision_event_reader.read() {
 ///         println!(
 ///             "Entities {} and {} stopped colliding",
-///             entity1,
-///             entity2,
+///             event.entity1,
+///             event.entity2,
 ///         );
 ///     }
 /// }
 /// ```
-#[derive(Event, Clone, Debug, PartialEq)]
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-pub struct CollisionEnded(pub Entity, pub Entity);
+pub struct CollisionEnded {
+    /// The first collider entity in the collision.
+    pub entity1: Entity,
+    /// The second collider entity in the collision.
+    pub entity2: Entity,
+    /// The rigid body of the first collider.
+    pub body_entity1: Option<Entity>,
+    /// The rigid body of the second collider.
+    pub body_entity2: Option<Entity>,
+    /// True if either of the colliders is a [`Sensor`] or doesn't belong to a rigid body,
+    /// meaning that the collision didn't produce a contact response.
+    /// See [`Contacts::is_sensor`].
+    pub is_sensor: bool,
+    /// The world-space normal of the first contact manifold during the last frame
+    /// that the colliders were touching, pointing from the first collider towards the second.
+    pub normal: Option<Vector>,
+}
 
 /// Sends collision events and updates [`CollidingEntities`].
 pub fn report_contacts(
     mut colliders: Query<&mut CollidingEntities>,
+    rotations: Query<&Rotation>,
+    events_enabled: Query<(), With<CollisionEventsEnabled>>,
+    mode: Res<CollisionEventMode>,
     collisions: Res<Collisions>,
     mut collision_ev_writer: EventWriter<Collision>,
     mut collision_started_ev_writer: EventWriter<CollisionStarted>,
     mut collision_ended_ev_writer: EventWriter<CollisionEnded>,
 ) {
+    // The world-space normal of the first manifold, pointing from the first collider towards the second.
+    let normal = |contacts: &Contacts| {
+        let manifold = contacts.manifolds.first()?;
+        let rotation = rotations.get(contacts.entity1).ok()?;
+        Some(manifold.global_normal1(rotation))
+    };
+
+    // Checks if collision events should be sent for the collision.
+    let should_send_events = |contacts: &Contacts| match *mode {
+        CollisionEventMode::All => true,
+        CollisionEventMode::OptIn => [
+            Some(contacts.entity1),
+            Some(contacts.entity2),
+            contacts.body_entity1,
+            contacts.body_entity2,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|entity| events_enabled.contains(entity)),
+    };
+
     // TODO: Would batching events be worth it?
     for ((entity1, entity2), contacts) in collisions.get_internal().iter() {
+        let send_events = should_send_events(contacts);
+
         if contacts.during_current_frame {
-            collision_ev_writer.send(Collision(contacts.clone()));
+            if send_events {
+                collision_ev_writer.send(Collision(contacts.clone()));
+            }
 
             // Collision started
             if !contacts.during_previous_frame {
-                collision_started_ev_writer.send(CollisionStarted(*entity1, *entity2));
+                if send_events {
+                    collision_started_ev_writer.send(CollisionStarted {
+                        entity1: contacts.entity1,
+                        entity2: contacts.entity2,
+                        body_entity1: contacts.body_entity1,
+                        body_entity2: contacts.body_entity2,
+                        is_sensor: contacts.is_sensor,
+                        normal: normal(contacts),
+                    });
+                }
 
                 if let Ok(mut colliding_entities1) = colliders.get_mut(*entity1) {
                     colliding_entities1.insert(*entity2);
@@ -177,7 +310,16 @@ pub fn report_contacts(
 
         // Collision ended
         if !contacts.during_current_frame && contacts.during_previous_frame {
-            collision_ended_ev_writer.send(CollisionEnded(*entity1, *entity2));
+            if send_events {
+                collision_ended_ev_writer.send(CollisionEnded {
+                    entity1: contacts.entity1,
+                    entity2: contacts.entity2,
+                    body_entity1: contacts.body_entity1,
+                    body_entity2: contacts.body_entity2,
+                    is_sensor: contacts.is_sensor,
+                    normal: normal(contacts),
+                });
+            }
 
             if let Ok(mut colliding_entities1) = colliders.get_mut(*entity1) {
                 colliding_entities1.remove(entity2);
@@ -0,0 +1,254 @@
+//! Aggregated impact events for driving audio and visual effects.
+//!
+//! See [`ImpactEventsPlugin`].
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+/// A plugin that sends a bounded number of [`ImpactEvent`]s per physics step
+/// when colliders hit each other.
+///
+/// An impact is detected when the contact between two colliders first applies a normal impulse.
+/// Resting contacts don't produce new impacts until the colliders have separated again.
+///
+/// When many bodies collide at once, for example when a pile of boxes collapses,
+/// sending an event for every new contact can easily flood audio and particle systems.
+/// Instead, the plugin coalesces the impacts based on the [`ImpactEventConfig`]:
+///
+/// - Impacts with a normal impulse below [`min_impulse`](ImpactEventConfig::min_impulse) are ignored.
+/// - Only the strongest [`max_events_per_material_pair`](ImpactEventConfig::max_events_per_material_pair)
+///   impacts are sent for each pair of [`ImpactMaterial`]s.
+/// - A body can only be involved in one impact per [`min_interval`](ImpactEventConfig::min_interval).
+///   Static bodies and colliders without a body are not rate-limited.
+/// - At most [`max_events_per_step`](ImpactEventConfig::max_events_per_step) impacts are sent in total.
+///
+/// The plugin must be added after the [`PhysicsPlugins`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// const WOOD: ImpactMaterial = ImpactMaterial(1);
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), ImpactEventsPlugin))
+///         .insert_resource(ImpactEventConfig {
+///             max_events_per_material_pair: 2,
+///             ..default()
+///         })
+///         .add_systems(Update, play_impact_sounds)
+///         .run();
+/// }
+///
+/// fn play_impact_sounds(mut impact_event_reader: EventReader<ImpactEvent>) {
+///     for impact in impact_event_reader.read() {
+///         if impact.materials.contains(&WOOD) {
+///             println!("Wooden thud with impulse {}", impact.impulse);
+///         }
+///     }
+/// }
+/// ```
+pub struct ImpactEventsPlugin;
+
+impl Plugin for ImpactEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(ImpactMaterial, ImpactEventConfig)>()
+            .init_resource::<ImpactEventConfig>()
+            .add_event::<ImpactEvent>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(
+            send_impact_events
+                .in_set(PhysicsStepSet::ReportContacts)
+                .after(super::contact_reporting::report_contacts),
+        );
+    }
+}
+
+/// An identifier for the surface material of a collider, used for grouping [`ImpactEvent`]s.
+///
+/// If a collider doesn't have an [`ImpactMaterial`], the material of its rigid body is used.
+/// Otherwise, the [default](ImpactMaterial::DEFAULT) material is used.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, Default, PartialEq, Hash)]
+pub struct ImpactMaterial(pub u32);
+
+impl ImpactMaterial {
+    /// The material used for colliders without an [`ImpactMaterial`].
+    pub const DEFAULT: Self = Self(0);
+}
+
+/// Configures how collisions are coalesced into [`ImpactEvent`]s by the [`ImpactEventsPlugin`].
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct ImpactEventConfig {
+    /// The minimum normal impulse required for a collision to be reported as an impact.
+    ///
+    /// Default: `0.0`
+    pub min_impulse: Scalar,
+    /// The maximum number of impacts reported per physics step for each pair of [`ImpactMaterial`]s.
+    /// The strongest impacts are reported first.
+    ///
+    /// Default: `4`
+    pub max_events_per_material_pair: usize,
+    /// The maximum total number of impacts reported per physics step.
+    ///
+    /// Default: `32`
+    pub max_events_per_step: usize,
+    /// The minimum time in seconds between two impacts involving the same dynamic or kinematic body.
+    ///
+    /// Default: `0.1`
+    pub min_interval: Scalar,
+}
+
+impl Default for ImpactEventConfig {
+    fn default() -> Self {
+        Self {
+            min_impulse: 0.0,
+            max_events_per_material_pair: 4,
+            max_events_per_step: 32,
+            min_interval: 0.1,
+        }
+    }
+}
+
+/// An event sent by the [`ImpactEventsPlugin`] when two colliders hit each other.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImpactEvent {
+    /// The first collider in the impact.
+    pub collider1: Entity,
+    /// The second collider in the impact.
+    pub collider2: Entity,
+    /// The rigid body of the first collider.
+    pub body1: Option<Entity>,
+    /// The rigid body of the second collider.
+    pub body2: Option<Entity>,
+    /// The [`ImpactMaterial`]s of the first and second collider.
+    pub materials: [ImpactMaterial; 2],
+    /// The world-space point of the deepest contact, on the surface of the first collider.
+    pub point: Vector,
+    /// The world-space contact normal, pointing from the first collider towards the second.
+    pub normal: Vector,
+    /// The magnitude of the total normal impulse applied during the impact.
+    pub impulse: Scalar,
+}
+
+#[allow(clippy::type_complexity)]
+fn send_impact_events(
+    collisions: Res<Collisions>,
+    colliders: Query<(
+        &Position,
+        &Rotation,
+        Option<&ImpactMaterial>,
+        Option<&ColliderParent>,
+    )>,
+    bodies: Query<(&RigidBody, Option<&ImpactMaterial>)>,
+    config: Res<ImpactEventConfig>,
+    time: Res<Time>,
+    mut impacted_pairs: Local<HashSet<(Entity, Entity)>>,
+    mut last_impacts: Local<EntityHashMap<f64>>,
+    mut impact_ev_writer: EventWriter<ImpactEvent>,
+) {
+    let now = time.elapsed_secs_f64();
+    let min_interval = config.min_interval as f64;
+
+    // Forget separated pairs and bodies whose rate limit has expired.
+    impacted_pairs.retain(|&(entity1, entity2)| collisions.contains(entity1, entity2));
+    last_impacts.retain(|_, last_impact| now - *last_impact < min_interval);
+
+    let mut impacts = collisions
+        .iter()
+        .filter(|contacts| {
+            !contacts.is_sensor
+                && contacts.total_normal_impulse != 0.0
+                && impacted_pairs.insert((contacts.entity1, contacts.entity2))
+                && contacts.total_normal_impulse.abs() >= config.min_impulse
+        })
+        .filter_map(|contacts| {
+            let (position1, rotation1, material1, parent1) =
+                colliders.get(contacts.entity1).ok()?;
+            let (_, _, material2, parent2) = colliders.get(contacts.entity2).ok()?;
+
+            let (manifold, contact) = contacts
+                .manifolds
+                .iter()
+                .filter_map(|manifold| Some((manifold, manifold.find_deepest_contact()?)))
+                .max_by(|(_, a), (_, b)| a.penetration.total_cmp(&b.penetration))?;
+
+            let material = |material: Option<&ImpactMaterial>, parent: Option<&ColliderParent>| {
+                material
+                    .or_else(|| bodies.get(parent?.get()).ok()?.1)
+                    .copied()
+                    .unwrap_or_default()
+            };
+
+            Some(ImpactEvent {
+                collider1: contacts.entity1,
+                collider2: contacts.entity2,
+                body1: parent1.map(|parent| parent.get()),
+                body2: parent2.map(|parent| parent.get()),
+                materials: [material(material1, parent1), material(material2, parent2)],
+                point: contact.global_point1(position1, rotation1),
+                normal: manifold.global_normal1(rotation1),
+                impulse: contacts.total_normal_impulse.abs(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Report the strongest impacts first.
+    impacts.sort_by(|a, b| b.impulse.total_cmp(&a.impulse));
+
+    let mut pair_counts = HashMap::<[ImpactMaterial; 2], usize>::default();
+    let mut sent = 0;
+
+    for impact in impacts {
+        if sent >= config.max_events_per_step {
+            break;
+        }
+
+        let mut pair = impact.materials;
+        pair.sort();
+        let pair_count = pair_counts.entry(pair).or_default();
+        if *pair_count >= config.max_events_per_material_pair {
+            continue;
+        }
+
+        // Static bodies and colliders without a body are not rate-limited.
+        let limited_bodies = [impact.body1, impact.body2]
+            .into_iter()
+            .flatten()
+            .filter(|&body| bodies.get(body).is_ok_and(|(rb, _)| !rb.is_static()))
+            .collect::<HashSet<_>>();
+        if limited_bodies
+            .iter()
+            .any(|body| last_impacts.contains_key(body))
+        {
+            continue;
+        }
+
+        for body in limited_bodies {
+            last_impacts.insert(body, now);
+        }
+        *pair_count += 1;
+        sent += 1;
+
+        impact_ev_writer.send(impact);
+    }
+}
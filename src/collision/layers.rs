@@ -1,6 +1,6 @@
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
 
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 
 /// A layer used for determining which entities should interact with each other.
 /// Physics layers are used heavily by [`CollisionLayers`].
@@ -280,7 +280,7 @@ impl Not for LayerMask {
 #[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
 #[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
 /// #
-/// #[derive(PhysicsLayer, Default)]
+/// #[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
 /// enum GameLayer {
 ///     #[default]
 ///     Default, // Layer 0 - the default layer that objects are assigned to
@@ -418,6 +418,37 @@ impl CollisionLayers {
         }
     }
 
+    /// Creates a new [`CollisionLayers`] configuration from the names of layers
+    /// in the given [`CollisionLayerRegistry`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollisionLayerRegistryError::UnknownLayer`] if any of the names is not registered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+    /// let mut registry = CollisionLayerRegistry::default();
+    /// registry.register("player").unwrap();
+    /// registry.register("enemy").unwrap();
+    /// registry.register("terrain").unwrap();
+    ///
+    /// let layers = CollisionLayers::from_names(&registry, ["player"], ["enemy", "terrain"]).unwrap();
+    /// assert_eq!(layers, CollisionLayers::new(0b0010, 0b1100));
+    /// ```
+    pub fn from_names<'a>(
+        registry: &CollisionLayerRegistry,
+        memberships: impl IntoIterator<Item = &'a str>,
+        filters: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, CollisionLayerRegistryError> {
+        Ok(Self {
+            memberships: registry.mask(memberships)?,
+            filters: registry.mask(filters)?,
+        })
+    }
+
     /// Returns true if an entity with this [`CollisionLayers`] configuration
     /// can interact with an entity with the `other` [`CollisionLayers`] configuration.
     pub fn interacts_with(self, other: Self) -> bool {
@@ -432,6 +463,315 @@ impl Default for CollisionLayers {
     }
 }
 
+/// A resource that maps layer names to layer bits, for defining collision layers at runtime
+/// instead of with a [`PhysicsLayer`] enum.
+///
+/// This is useful for data-driven games and mods, where layers are defined in configuration files.
+/// With the `serialize` feature, the registry is (de)serialized as a map from names to bit indices,
+/// so it can be loaded from formats like [RON]. The `settings-asset` feature can also load it
+/// as a part of the `PhysicsSettings`.
+///
+/// The first bit is reserved for the default layer, which is registered as `"default"`
+/// in new registries. See [`LayerMask`].
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let mut registry = CollisionLayerRegistry::default();
+///     for name in ["player", "enemy", "terrain"] {
+///         registry.register(name).unwrap();
+///     }
+///
+///     // Players belong to the `player` layer, and collide with enemies and terrain.
+///     let player_layers =
+///         CollisionLayers::from_names(&registry, ["player"], ["enemy", "terrain"]).unwrap();
+///     commands.spawn((RigidBody::Dynamic, player_layers));
+///
+///     commands.insert_resource(registry);
+/// }
+/// ```
+///
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Resource, Reflect, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(transparent))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct CollisionLayerRegistry {
+    /// The bit indices of the layers by name.
+    layers: HashMap<String, u32>,
+}
+
+impl Default for CollisionLayerRegistry {
+    fn default() -> Self {
+        let mut layers = HashMap::default();
+        layers.insert("default".to_string(), 0);
+        Self { layers }
+    }
+}
+
+impl CollisionLayerRegistry {
+    /// The number of layers supported by a [`LayerMask`].
+    pub const MAX_LAYERS: u32 = 32;
+
+    /// Registers a layer with the given name using the lowest free bit, and returns its [`LayerMask`].
+    /// The first bit is never used, as it is reserved for the default layer.
+    ///
+    /// If a layer with the name is already registered, its existing mask is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollisionLayerRegistryError::NoFreeLayers`] if all bits are already in use.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+    ) -> Result<LayerMask, CollisionLayerRegistryError> {
+        let name = name.into();
+        if let Some(mask) = self.get(&name) {
+            return Ok(mask);
+        }
+
+        let used = self
+            .iter()
+            .fold(LayerMask::DEFAULT, |used, (_, mask)| used | mask);
+        let bit = (1..Self::MAX_LAYERS)
+            .find(|bit| used.0 & (1 << bit) == 0)
+            .ok_or(CollisionLayerRegistryError::NoFreeLayers)?;
+
+        self.layers.insert(name, bit);
+        Ok(LayerMask(1 << bit))
+    }
+
+    /// Registers a layer with the given name using the given bit index, and returns its [`LayerMask`].
+    /// If a layer with the name is already registered, it is replaced.
+    ///
+    /// Several names can share the same bit, which can be used for aliases.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollisionLayerRegistryError::InvalidBit`] if the bit index is not smaller than [`Self::MAX_LAYERS`].
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        bit: u32,
+    ) -> Result<LayerMask, CollisionLayerRegistryError> {
+        if bit >= Self::MAX_LAYERS {
+            return Err(CollisionLayerRegistryError::InvalidBit(bit));
+        }
+        self.layers.insert(name.into(), bit);
+        Ok(LayerMask(1 << bit))
+    }
+
+    /// Removes the layer with the given name, and returns its [`LayerMask`] if it was registered.
+    pub fn remove(&mut self, name: &str) -> Option<LayerMask> {
+        let mask = self.get(name);
+        self.layers.remove(name);
+        mask
+    }
+
+    /// Returns the [`LayerMask`] of the layer with the given name, or `None` if it is not registered.
+    ///
+    /// Layers with bit indices that are too large, for example from a deserialized registry, are ignored.
+    pub fn get(&self, name: &str) -> Option<LayerMask> {
+        self.layers
+            .get(name)
+            .filter(|bit| **bit < Self::MAX_LAYERS)
+            .map(|bit| LayerMask(1 << bit))
+    }
+
+    /// Returns a [`LayerMask`] containing all of the layers with the given names.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollisionLayerRegistryError::UnknownLayer`] if any of the names is not registered.
+    pub fn mask<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<LayerMask, CollisionLayerRegistryError> {
+        names.into_iter().try_fold(LayerMask::NONE, |mask, name| {
+            self.get(name)
+                .map(|layer| mask | layer)
+                .ok_or_else(|| CollisionLayerRegistryError::UnknownLayer(name.to_string()))
+        })
+    }
+
+    /// Returns an iterator over the names and [`LayerMask`]s of the registered layers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, LayerMask)> {
+        self.layers
+            .iter()
+            .filter(|(_, bit)| **bit < Self::MAX_LAYERS)
+            .map(|(name, bit)| (name.as_str(), LayerMask(1 << bit)))
+    }
+}
+
+/// An error returned by the [`CollisionLayerRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CollisionLayerRegistryError {
+    /// No layer is registered with the given name.
+    UnknownLayer(String),
+    /// All of the layer bits are already in use.
+    NoFreeLayers,
+    /// The given bit index is too large for a [`LayerMask`].
+    InvalidBit(u32),
+}
+
+impl std::fmt::Display for CollisionLayerRegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownLayer(name) => write!(f, "no collision layer is named `{name}`"),
+            Self::NoFreeLayers => write!(
+                f,
+                "all {} collision layers are in use",
+                CollisionLayerRegistry::MAX_LAYERS
+            ),
+            Self::InvalidBit(bit) => write!(
+                f,
+                "collision layer bit {bit} is out of range, the maximum is {}",
+                CollisionLayerRegistry::MAX_LAYERS - 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CollisionLayerRegistryError {}
+
+/// A component that makes a collider solid only for colliders that are members of the given layers.
+///
+/// For all other colliders, the blocker volume is fully transparent: no contacts are computed,
+/// and it does not even act as a [`Sensor`]. This is useful for things like invisible walls
+/// that only block players, or barriers that only stop projectiles.
+///
+/// Collision pairs with non-matching colliders are removed in the [broad phase](crate::collision::broad_phase),
+/// so no contact manifolds are generated for them. The [`CollisionLayers`] of the blocker volume
+/// are still respected as usual. The filtering doesn't affect spatial queries,
+/// which can be filtered using a [`SpatialQueryFilter`] instead.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// #[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
+/// enum GameLayer {
+///     #[default]
+///     Default,
+///     Player,
+///     Enemy,
+/// }
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     // An invisible wall that only blocks players.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 10.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 10.0, 10.0),")]
+///         BlockerVolume::new(GameLayer::Player),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct BlockerVolume {
+    /// The layers that the blocker volume is solid for.
+    pub blocks: LayerMask,
+}
+
+impl BlockerVolume {
+    /// Creates a [`BlockerVolume`] that is solid for colliders that are members of the given layers.
+    pub fn new(blocks: impl Into<LayerMask>) -> Self {
+        Self {
+            blocks: blocks.into(),
+        }
+    }
+
+    /// Returns `true` if the blocker volume is solid for a collider with the given [`CollisionLayers`].
+    pub fn blocks(self, layers: CollisionLayers) -> bool {
+        (layers.memberships & self.blocks) != LayerMask::NONE
+    }
+}
+
+/// A band of depth layers that an entity occupies along the z-axis in 2D.
+///
+/// Colliders only collide with each other if their depth bands overlap.
+/// Colliders without a [`ZLayer`] collide with colliders in all bands.
+/// This is useful for pseudo-3D games like beat 'em ups, where characters move
+/// in separate lanes, without spending [`CollisionLayers`] on each lane.
+///
+/// The [`ZLayer`] can be added to colliders or rigid bodies. The band of a collider
+/// takes precedence over the band of the rigid body it is attached to.
+/// The filtering is done in the [broad phase](crate::collision::broad_phase),
+/// so it doesn't affect spatial queries.
+///
+/// # Example
+///
+/// ```
+/// # use avian2d::prelude::*;
+/// # use bevy::prelude::*;
+/// #
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     // A character walking in lane 1.
+///     commands.spawn((RigidBody::Dynamic, Collider::capsule(0.4, 1.0), ZLayer::new(1)));
+///
+///     // A large obstacle that blocks lanes 0 to 2.
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::rectangle(4.0, 2.0),
+///         ZLayer::band(0, 2),
+///     ));
+/// }
+/// ```
+#[cfg(feature = "2d")]
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq, Hash)]
+pub struct ZLayer {
+    /// The lowest depth layer of the band, inclusive.
+    pub min: i32,
+    /// The highest depth layer of the band, inclusive.
+    pub max: i32,
+}
+
+#[cfg(feature = "2d")]
+impl ZLayer {
+    /// Creates a [`ZLayer`] that only occupies the given depth layer.
+    pub const fn new(layer: i32) -> Self {
+        Self {
+            min: layer,
+            max: layer,
+        }
+    }
+
+    /// Creates a [`ZLayer`] that occupies the depth layers from `min` to `max`, inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub const fn band(min: i32, max: i32) -> Self {
+        assert!(
+            min <= max,
+            "the minimum depth layer must not exceed the maximum"
+        );
+        Self { min, max }
+    }
+
+    /// Returns `true` if the depth band overlaps the `other` band.
+    pub const fn overlaps(self, other: Self) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Needed for `PhysicsLayer` derive macro
@@ -472,4 +812,45 @@ mod tests {
             .has_all([GameLayer::Player, GameLayer::Ground]));
         assert!(!with_bitmask.filters.has_all(GameLayer::Enemy));
     }
+
+    #[test]
+    fn registered_names() {
+        let mut registry = CollisionLayerRegistry::default();
+        assert_eq!(registry.get("default"), Some(LayerMask::DEFAULT));
+
+        let player = registry.register("player").unwrap();
+        let enemy = registry.register("enemy").unwrap();
+        let terrain = registry.insert("terrain", 8).unwrap();
+        assert_eq!(player, LayerMask(1 << 1));
+        assert_eq!(enemy, LayerMask(1 << 2));
+        assert_eq!(terrain, LayerMask(1 << 8));
+        // Registering an existing name returns the same layer.
+        assert_eq!(registry.register("player"), Ok(player));
+
+        let player_layers =
+            CollisionLayers::from_names(&registry, ["player"], ["enemy", "terrain"]).unwrap();
+        let enemy_layers = CollisionLayers::from_names(&registry, ["enemy"], ["player"]).unwrap();
+        assert_eq!(player_layers, CollisionLayers::new(player, enemy | terrain));
+        assert!(player_layers.interacts_with(enemy_layers));
+
+        assert_eq!(
+            CollisionLayers::from_names(&registry, ["player"], ["water"]),
+            Err(CollisionLayerRegistryError::UnknownLayer(
+                "water".to_string()
+            ))
+        );
+        assert_eq!(
+            registry.insert("sky", 32),
+            Err(CollisionLayerRegistryError::InvalidBit(32))
+        );
+
+        // All 31 non-default bits can be used.
+        for i in 0..28 {
+            registry.register(format!("layer{i}")).unwrap();
+        }
+        assert_eq!(
+            registry.register("one too many"),
+            Err(CollisionLayerRegistryError::NoFreeLayers)
+        );
+    }
 }
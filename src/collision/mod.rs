@@ -11,6 +11,9 @@
 //! - [`NarrowPhasePlugin`]: Computes [`Contacts`] for each pair in [`BroadCollisionPairs`], adding them to [`Collisions`].
 //! - [`ContactReportingPlugin`] (optional): Sends collision events and updates [`CollidingEntities`] based on [`Collisions`].
 //!
+//! The optional [`ImpactEventsPlugin`] can be used to coalesce collisions into a bounded number of [`ImpactEvent`]s
+//! for audio and visual effects, and the optional [`SensorEntryPlugin`] reports where colliders enter [`Sensor`]s.
+//!
 //! Spatial queries are handled separately by the [`SpatialQueryPlugin`].
 //!
 //! You can also find several utility methods for computing contacts in [`contact_query`].
@@ -22,7 +25,31 @@ pub mod broad_phase;
 ))]
 pub mod contact_query;
 pub mod contact_reporting;
+pub mod impact_events;
+pub use impact_events::{ImpactEvent, ImpactEventConfig, ImpactEventsPlugin, ImpactMaterial};
 pub mod narrow_phase;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod sensor_entry;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use sensor_entry::{SensorEntered, SensorEntryPlugin};
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod sensor_zones;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use sensor_zones::{
+    SensorZone, SensorZoneEntities, SensorZoneKind, SensorZoneOf, SensorZones, SensorZonesPlugin,
+};
 
 pub mod collider;
 pub use collider::*;
@@ -64,6 +91,7 @@ use indexmap::IndexMap;
 /// - [`contains`](Self::contains)
 /// - [`collisions_with_entity`](Self::collisions_with_entity) and
 ///   [`collisions_with_entity_mut`](Self::collisions_with_entity_mut)
+/// - [`write_columns`](Self::write_columns) for getting the contact data in a column-major [`ContactColumns`] layout
 ///
 /// The collisions can be accessed at any time, but modifications to contacts should be performed
 /// in the [`PostProcessCollisions`] schedule. Otherwise, the physics solver will use the old contact data.
@@ -256,6 +284,142 @@ impl Collisions {
     pub fn remove_collisions_with_entity(&mut self, entity: Entity) {
         self.retain(|contacts| contacts.entity1 != entity && contacts.entity2 != entity);
     }
+
+    /// Clears the given [`ContactColumns`] and fills them with the contact points
+    /// of the collisions that have happened during the current physics frame.
+    ///
+    /// The columns can be reused across frames to avoid reallocating them.
+    pub fn write_columns(&self, columns: &mut ContactColumns) {
+        columns.clear();
+
+        for contacts in self.iter() {
+            for manifold in &contacts.manifolds {
+                for contact in &manifold.contacts {
+                    columns
+                        .entity_pairs
+                        .push((contacts.entity1, contacts.entity2));
+                    columns.points1.push(contact.point1);
+                    columns.points2.push(contact.point2);
+                    columns.normals1.push(contact.normal1);
+                    columns.normals2.push(contact.normal2);
+                    columns.penetrations.push(contact.penetration);
+                    columns.normal_impulses.push(contact.normal_impulse);
+                    columns.tangent_impulses.push(contact.tangent_impulse);
+                }
+            }
+        }
+    }
+}
+
+/// Contact data stored in a column-major layout, with a separate slice for each property of the contact points.
+///
+/// This is useful for consuming large amounts of contact data efficiently, for example
+/// for analytics, machine learning, or SIMD processing, without going through the nested
+/// [`Contacts`], [`ContactManifold`], and [`ContactData`] structures.
+///
+/// The columns are filled using [`Collisions::write_columns`]. All columns have the same length,
+/// and the values at a given index belong to the same contact point. Like in [`ContactData`],
+/// the points and normals are in the local space of the corresponding entities.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn total_penetration(collisions: Res<Collisions>, mut columns: Local<ContactColumns>) {
+///     collisions.write_columns(&mut columns);
+///     let total: Scalar = columns.penetrations().iter().sum();
+///     println!("Total penetration of {} contacts: {total}", columns.len());
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContactColumns {
+    entity_pairs: Vec<(Entity, Entity)>,
+    points1: Vec<Vector>,
+    points2: Vec<Vector>,
+    normals1: Vec<Vector>,
+    normals2: Vec<Vector>,
+    penetrations: Vec<Scalar>,
+    normal_impulses: Vec<Scalar>,
+    #[cfg(feature = "2d")]
+    tangent_impulses: Vec<Scalar>,
+    #[cfg(feature = "3d")]
+    tangent_impulses: Vec<Vector2>,
+}
+
+impl ContactColumns {
+    /// Returns the number of contact points.
+    pub fn len(&self) -> usize {
+        self.entity_pairs.len()
+    }
+
+    /// Returns `true` if there are no contact points.
+    pub fn is_empty(&self) -> bool {
+        self.entity_pairs.is_empty()
+    }
+
+    /// Removes all contact points, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.entity_pairs.clear();
+        self.points1.clear();
+        self.points2.clear();
+        self.normals1.clear();
+        self.normals2.clear();
+        self.penetrations.clear();
+        self.normal_impulses.clear();
+        self.tangent_impulses.clear();
+    }
+
+    /// Returns the pairs of colliding entities for each contact point.
+    pub fn entity_pairs(&self) -> &[(Entity, Entity)] {
+        &self.entity_pairs
+    }
+
+    /// Returns the contact points on the first entity in local coordinates.
+    pub fn points1(&self) -> &[Vector] {
+        &self.points1
+    }
+
+    /// Returns the contact points on the second entity in local coordinates.
+    pub fn points2(&self) -> &[Vector] {
+        &self.points2
+    }
+
+    /// Returns the contact normals expressed in the local space of the first entity.
+    pub fn normals1(&self) -> &[Vector] {
+        &self.normals1
+    }
+
+    /// Returns the contact normals expressed in the local space of the second entity.
+    pub fn normals2(&self) -> &[Vector] {
+        &self.normals2
+    }
+
+    /// Returns the penetration depths of the contact points.
+    pub fn penetrations(&self) -> &[Scalar] {
+        &self.penetrations
+    }
+
+    /// Returns the impulses applied to the first entity along the normal.
+    pub fn normal_impulses(&self) -> &[Scalar] {
+        &self.normal_impulses
+    }
+
+    /// Returns the impulses applied to the first entity along the tangent,
+    /// corresponding to the impulses caused by friction.
+    #[cfg(feature = "2d")]
+    pub fn tangent_impulses(&self) -> &[Scalar] {
+        &self.tangent_impulses
+    }
+
+    /// Returns the impulses applied to the first entity along the tangent,
+    /// corresponding to the impulses caused by friction.
+    #[cfg(feature = "3d")]
+    pub fn tangent_impulses(&self) -> &[Vector2] {
+        &self.tangent_impulses
+    }
 }
 
 /// All contacts between two colliders.
@@ -274,6 +438,10 @@ pub struct Contacts {
     pub body_entity1: Option<Entity>,
     /// The entity of the second body involved in the contact.
     pub body_entity2: Option<Entity>,
+    /// The [`CollisionUserData`] of the first collider, if it has any.
+    pub user_data1: Option<CollisionUserData>,
+    /// The [`CollisionUserData`] of the second collider, if it has any.
+    pub user_data2: Option<CollisionUserData>,
     /// A list of contact manifolds between two colliders.
     /// Each manifold contains one or more contact points, but each contact
     /// in a given manifold shares the same contact normal.
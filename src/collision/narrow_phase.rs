@@ -6,7 +6,7 @@ use std::marker::PhantomData;
 
 use crate::{
     dynamics::solver::{
-        contact::ContactConstraint, ContactConstraints, ContactSoftnessCoefficients,
+        contact::ContactConstraint, ContactConstraints, ContactSoftnessCoefficients, SolverConfig,
     },
     prelude::*,
 };
@@ -74,6 +74,7 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
         app.init_resource::<NarrowPhaseInitialized>()
             .init_resource::<NarrowPhaseConfig>()
             .init_resource::<Collisions>()
+            .init_resource::<ContactImpulseCache>()
             .init_resource::<DefaultFriction>()
             .init_resource::<DefaultRestitution>()
             .register_type::<(NarrowPhaseConfig, DefaultFriction, DefaultRestitution)>();
@@ -219,6 +220,19 @@ pub struct NarrowPhaseConfig {
     ///
     /// Default: `true`
     pub match_contacts: bool,
+
+    /// The number of frames that the contact impulses of colliders that stopped touching are cached for.
+    ///
+    /// If the colliders start touching again within this many frames, their new contacts are matched
+    /// with the cached contacts based on feature IDs, and the cached impulses are used for warm starting.
+    /// This can improve the stability of stacks and piles where contacts are briefly lost,
+    /// especially at low substep counts.
+    ///
+    /// Zero disables the cache, so only the contacts from the previous frame are matched.
+    /// Only used if [`match_contacts`](Self::match_contacts) is `true`.
+    ///
+    /// Default: `0`
+    pub impulse_cache_frames: u32,
 }
 
 impl Default for NarrowPhaseConfig {
@@ -227,10 +241,41 @@ impl Default for NarrowPhaseConfig {
             default_speculative_margin: Scalar::MAX,
             contact_tolerance: 0.005,
             match_contacts: true,
+            impulse_cache_frames: 0,
         }
     }
 }
 
+/// A resource that stores the contacts of colliders that stopped touching in recent frames,
+/// along with their impulses, for warm starting if the colliders start touching again.
+///
+/// See [`NarrowPhaseConfig::impulse_cache_frames`].
+#[derive(Resource, Default)]
+pub struct ContactImpulseCache(bevy::utils::HashMap<(Entity, Entity), CachedContacts>);
+
+impl ContactImpulseCache {
+    /// Returns the cached contacts of the given colliders, or `None` if there are none.
+    ///
+    /// The order of the entities does not matter, but the contact data is given
+    /// in the order of the entities sorted by their IDs.
+    pub fn get(&self, entity1: Entity, entity2: Entity) -> Option<&[ContactData]> {
+        let key = if entity1 < entity2 {
+            (entity1, entity2)
+        } else {
+            (entity2, entity1)
+        };
+        self.0.get(&key).map(|cached| cached.contacts.as_slice())
+    }
+}
+
+/// Contacts stored in the [`ContactImpulseCache`].
+struct CachedContacts {
+    /// The number of frames since the colliders stopped touching.
+    age: u32,
+    /// The contacts of all manifolds of the colliders, in the order of the entities of the key.
+    contacts: Vec<ContactData>,
+}
+
 /// System sets for systems running in [`PhysicsStepSet::NarrowPhase`].
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum NarrowPhaseSet {
@@ -380,6 +425,8 @@ pub struct NarrowPhase<'w, 's, C: AnyCollider> {
     pub collisions: ResMut<'w, Collisions>,
     /// Configuration options for the narrow phase.
     pub config: Res<'w, NarrowPhaseConfig>,
+    impulse_cache: Res<'w, ContactImpulseCache>,
+    solver_config: Option<Res<'w, SolverConfig>>,
     length_unit: Res<'w, PhysicsLengthUnit>,
     // These are scaled by the length unit.
     default_speculative_margin: Local<'s, Scalar>,
@@ -573,15 +620,12 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
         );
 
         // Get the previous contacts if there are any.
-        let previous_contacts = if collider1.entity < collider2.entity {
-            self.collisions
-                .get_internal()
-                .get(&(collider1.entity, collider2.entity))
+        let key = if collider1.entity < collider2.entity {
+            (collider1.entity, collider2.entity)
         } else {
-            self.collisions
-                .get_internal()
-                .get(&(collider2.entity, collider1.entity))
+            (collider2.entity, collider1.entity)
         };
+        let previous_contacts = self.collisions.get_internal().get(&key);
 
         let mut total_normal_impulse = 0.0;
         let mut total_tangent_impulse = default();
@@ -605,6 +649,18 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
                         }
                     }
                 }
+            } else if let Some(cached) = self.impulse_cache.0.get(&key) {
+                // The colliders touched in a recent frame, so use the cached impulses.
+                let distance_threshold = 0.1 * self.length_unit.0;
+
+                for manifold in manifolds.iter_mut() {
+                    manifold.match_contacts(&cached.contacts, distance_threshold);
+
+                    for contact in manifold.contacts.iter() {
+                        total_normal_impulse += contact.normal_impulse;
+                        total_tangent_impulse += contact.tangent_impulse;
+                    }
+                }
             }
         }
 
@@ -613,6 +669,8 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
             entity2: collider2.entity,
             body_entity1: collider1.parent.map(|p| p.get()),
             body_entity2: collider2.parent.map(|p| p.get()),
+            user_data1: collider1.user_data.copied(),
+            user_data2: collider2.user_data.copied(),
             during_current_frame: true,
             during_previous_frame: previous_contacts.map_or(false, |c| c.during_previous_frame),
             manifolds,
@@ -653,11 +711,13 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
         let inactive1 = body1.rb.is_static() || body1.is_sleeping;
         let inactive2 = body2.rb.is_static() || body2.is_sleeping;
 
-        // No collision response if both bodies are static or sleeping
-        // or if either of the colliders is a sensor collider.
+        // No collision response if both bodies are static or sleeping,
+        // if either of the colliders is a sensor collider, or if the response is disabled.
         if (inactive1 && inactive2)
             || (collider1.is_sensor || body1.is_sensor)
             || (collider2.is_sensor || body2.is_sensor)
+            || (collider1.is_response_disabled || body1.is_response_disabled)
+            || (collider2.is_response_disabled || body2.is_response_disabled)
         {
             return;
         }
@@ -677,6 +737,14 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
             contact_softness.dynamic
         };
 
+        let warm_start = self.config.match_contacts
+            && self
+                .solver_config
+                .as_ref()
+                .map_or(true, |config| config.warm_starting)
+            && !body1.skip_warm_start
+            && !body2.skip_warm_start;
+
         // Generate contact constraints for each contact.
         for (i, contact_manifold) in contacts.manifolds.iter().enumerate() {
             let constraint = ContactConstraint::generate(
@@ -694,7 +762,7 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
                 friction,
                 restitution,
                 contact_softness,
-                self.config.match_contacts,
+                warm_start,
                 delta_secs,
             );
 
@@ -705,8 +773,48 @@ impl<C: AnyCollider> NarrowPhase<'_, '_, C> {
     }
 }
 
-fn remove_ended_collisions(mut collisions: ResMut<Collisions>) {
-    collisions.retain(|contacts| contacts.during_current_frame);
+fn remove_ended_collisions(
+    mut collisions: ResMut<Collisions>,
+    mut impulse_cache: ResMut<ContactImpulseCache>,
+    config: Res<NarrowPhaseConfig>,
+) {
+    let max_age = config.impulse_cache_frames;
+    if max_age == 0 || !config.match_contacts {
+        impulse_cache.0.clear();
+        collisions.retain(|contacts| contacts.during_current_frame);
+        return;
+    }
+
+    // Age the cached contacts, and remove them if they are too old.
+    impulse_cache.0.retain(|_, cached| {
+        cached.age += 1;
+        cached.age <= max_age
+    });
+
+    collisions.retain(|contacts| {
+        let key = if contacts.entity1 < contacts.entity2 {
+            (contacts.entity1, contacts.entity2)
+        } else {
+            (contacts.entity2, contacts.entity1)
+        };
+
+        if contacts.during_current_frame {
+            // The colliders are touching, so the cached contacts are no longer needed.
+            impulse_cache.0.remove(&key);
+            return true;
+        }
+
+        // Cache the contacts and impulses of the ended collision.
+        let contacts = contacts
+            .manifolds
+            .iter()
+            .flat_map(|manifold| manifold.contacts.iter().copied())
+            .collect();
+        impulse_cache
+            .0
+            .insert(key, CachedContacts { age: 0, contacts });
+        false
+    });
 }
 
 // TODO: The collision state handling feels a bit confusing and error-prone.
@@ -0,0 +1,203 @@
+//! Entry points and normals for colliders entering [`Sensor`]s.
+//!
+//! See [`SensorEntryPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin that sends a [`SensorEntered`] event with the approximate entry point and normal
+/// when a collider starts overlapping a [`Sensor`].
+///
+/// The entry point is found with a shape cast of the entering collider from its previous pose
+/// to its current pose relative to the sensor, based on the linear velocities of the bodies.
+/// Rotation during the step is ignored. If the cast doesn't find an entry point, for example
+/// because the collider was teleported, the deepest contact between the colliders is used instead.
+///
+/// This is useful for spawning trigger-based effects at the right location, like splashes
+/// when something falls into water, or impacts on a shield.
+///
+/// The plugin must be added after the [`PhysicsPlugins`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), SensorEntryPlugin))
+///         .add_systems(Update, spawn_splashes)
+///         .run();
+/// }
+///
+/// fn spawn_splashes(mut sensor_entered_event_reader: EventReader<SensorEntered>) {
+///     for event in sensor_entered_event_reader.read() {
+///         println!(
+///             "{} entered water {} at {} with normal {}",
+///             event.entity, event.sensor, event.point, event.normal,
+///         );
+///     }
+/// }
+/// ```
+pub struct SensorEntryPlugin;
+
+impl Plugin for SensorEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SensorEntered>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(
+            send_sensor_entered_events
+                .in_set(PhysicsStepSet::ReportContacts)
+                .after(super::contact_reporting::report_contacts),
+        );
+    }
+}
+
+/// An event sent by the [`SensorEntryPlugin`] when a collider starts overlapping a [`Sensor`].
+///
+/// If both colliders are sensors, an event is sent for each of them.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorEntered {
+    /// The sensor collider that was entered.
+    pub sensor: Entity,
+    /// The collider that entered the sensor.
+    pub entity: Entity,
+    /// The approximate world-space point where the collider entered the sensor,
+    /// on the surface of the sensor.
+    pub point: Vector,
+    /// The approximate world-space outward normal of the sensor at the entry point.
+    pub normal: Vector,
+}
+
+#[allow(clippy::type_complexity)]
+fn send_sensor_entered_events(
+    collisions: Res<Collisions>,
+    colliders: Query<(
+        &Collider,
+        &Position,
+        &Rotation,
+        Has<Sensor>,
+        Option<&ColliderParent>,
+    )>,
+    bodies: Query<(Option<&LinearVelocity>, Has<Sensor>)>,
+    time: Res<Time>,
+    mut sensor_entered_ev_writer: EventWriter<SensorEntered>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    let body_data = |parent: Option<&ColliderParent>| {
+        parent
+            .and_then(|parent| bodies.get(parent.get()).ok())
+            .map_or((Vector::ZERO, false), |(velocity, is_sensor)| {
+                (
+                    velocity.map_or(Vector::ZERO, |velocity| velocity.0),
+                    is_sensor,
+                )
+            })
+    };
+
+    for contacts in collisions.iter() {
+        if !contacts.during_current_frame || contacts.during_previous_frame {
+            continue;
+        }
+
+        let Ok([collider1, collider2]) = colliders.get_many([contacts.entity1, contacts.entity2])
+        else {
+            continue;
+        };
+
+        for (sensor_entity, other_entity, sensor, other, sensor_is_first) in [
+            (
+                contacts.entity1,
+                contacts.entity2,
+                collider1,
+                collider2,
+                true,
+            ),
+            (
+                contacts.entity2,
+                contacts.entity1,
+                collider2,
+                collider1,
+                false,
+            ),
+        ] {
+            let (sensor_collider, sensor_position, sensor_rotation, is_sensor, sensor_parent) =
+                sensor;
+            let (other_collider, other_position, other_rotation, _, other_parent) = other;
+
+            let (sensor_velocity, is_sensor_body) = body_data(sensor_parent);
+            if !is_sensor && !is_sensor_body {
+                continue;
+            }
+            let (other_velocity, _) = body_data(other_parent);
+
+            // Cast the entering collider from its previous pose relative to the sensor.
+            let relative_velocity = other_velocity - sensor_velocity;
+            let previous_position = other_position.0 - relative_velocity * delta_secs;
+            let hit = contact_query::time_of_impact(
+                sensor_collider,
+                *sensor_position,
+                *sensor_rotation,
+                Vector::ZERO,
+                other_collider,
+                previous_position,
+                *other_rotation,
+                relative_velocity,
+                delta_secs,
+            )
+            .ok()
+            .flatten()
+            .filter(|hit| hit.time_of_impact > 0.0);
+
+            let entry = if let Some(hit) = hit {
+                Some((
+                    sensor_position.0 + *sensor_rotation * hit.point1,
+                    *sensor_rotation * hit.normal1,
+                ))
+            } else {
+                // Fall back to the deepest contact between the colliders.
+                contacts
+                    .manifolds
+                    .iter()
+                    .filter_map(|manifold| Some((manifold, manifold.find_deepest_contact()?)))
+                    .max_by(|(_, a), (_, b)| a.penetration.total_cmp(&b.penetration))
+                    .map(|(manifold, contact)| {
+                        if sensor_is_first {
+                            (
+                                contact.global_point1(sensor_position, sensor_rotation),
+                                manifold.global_normal1(sensor_rotation),
+                            )
+                        } else {
+                            (
+                                contact.global_point2(sensor_position, sensor_rotation),
+                                manifold.global_normal2(sensor_rotation),
+                            )
+                        }
+                    })
+            };
+
+            let (point, normal) = entry.unwrap_or_else(|| {
+                (
+                    other_position.0,
+                    (other_position.0 - sensor_position.0).normalize_or_zero(),
+                )
+            });
+
+            sensor_entered_ev_writer.send(SensorEntered {
+                sensor: sensor_entity,
+                entity: other_entity,
+                point,
+                normal,
+            });
+        }
+    }
+}
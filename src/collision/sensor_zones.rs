@@ -0,0 +1,285 @@
+//! Sensor colliders attached to an entity through child entities, declared with a single component.
+//!
+//! See [`SensorZonesPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin that spawns and maintains the child [`Sensor`] colliders declared by [`SensorZones`].
+///
+/// This is useful for common action game setups, where a character needs several sensors
+/// for different purposes, like a hitbox, an interaction reach, or a ground probe.
+///
+/// The plugin must be added after the [`PhysicsPlugins`].
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Kinematic,
+///         Collider::capsule(0.4, 1.0),
+///         SensorZones::new([
+#[cfg_attr(
+    feature = "2d",
+    doc = "            SensorZone::hitbox(Collider::rectangle(1.0, 1.8)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            SensorZone::hitbox(Collider::cuboid(1.0, 1.8, 1.0)),"
+)]
+///             SensorZone::interaction_reach(1.5),
+///             SensorZone::ground_probe(0.3, Vector::NEG_Y * 0.9),
+///         ]),
+///     ));
+/// }
+///
+/// fn log_interactions(
+///     zones: Query<(&SensorZoneOf, &CollidingEntities)>,
+/// ) {
+///     for (zone, colliding_entities) in &zones {
+///         if zone.kind == SensorZoneKind::InteractionReach && !colliding_entities.is_empty() {
+///             println!("{} can interact with {colliding_entities:?}", zone.owner);
+///         }
+///     }
+/// }
+/// ```
+pub struct SensorZonesPlugin;
+
+impl Plugin for SensorZonesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SensorZoneKind>()
+            .register_type::<SensorZoneOf>()
+            .register_type::<SensorZoneEntities>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(sync_sensor_zones.in_set(PhysicsStepSet::First));
+    }
+}
+
+/// A component that declares [`Sensor`] colliders that should be attached to an entity,
+/// typically a character.
+///
+/// Each [`SensorZone`] is spawned as a child entity with a [`SensorZoneOf`] component
+/// by the [`SensorZonesPlugin`]. The sensors are respawned when the [`SensorZones`] are changed,
+/// or when a sensor entity is despawned, and reattached if they are removed from the hierarchy of the entity.
+/// Removing the component despawns the sensors.
+///
+/// The sensors have a [`ColliderDensity`] of zero, so they don't affect the mass properties of the rigid body.
+///
+/// See the [`SensorZonesPlugin`] for an example.
+#[derive(Component, Clone, Debug, Default)]
+pub struct SensorZones(pub Vec<SensorZone>);
+
+impl SensorZones {
+    /// Creates new [`SensorZones`] from the given zones.
+    pub fn new(zones: impl IntoIterator<Item = SensorZone>) -> Self {
+        Self(zones.into_iter().collect())
+    }
+}
+
+/// A [`Sensor`] collider declared in [`SensorZones`].
+#[derive(Clone, Debug)]
+pub struct SensorZone {
+    /// The purpose of the sensor, used for finding the sensor later.
+    pub kind: SensorZoneKind,
+    /// The shape of the sensor.
+    pub collider: Collider,
+    /// The offset of the sensor relative to the entity.
+    pub offset: Vector,
+    /// The [`CollisionLayers`] of the sensor. If `None`, the default layers are used.
+    pub layers: Option<CollisionLayers>,
+}
+
+impl SensorZone {
+    /// Creates a new [`SensorZone`] with the given kind and collider, and no offset.
+    pub fn new(kind: SensorZoneKind, collider: Collider) -> Self {
+        Self {
+            kind,
+            collider,
+            offset: Vector::ZERO,
+            layers: None,
+        }
+    }
+
+    /// Creates a [`SensorZoneKind::Hitbox`] with the given collider.
+    pub fn hitbox(collider: Collider) -> Self {
+        Self::new(SensorZoneKind::Hitbox, collider)
+    }
+
+    /// Creates a [`SensorZoneKind::InteractionReach`] with the given radius.
+    pub fn interaction_reach(radius: Scalar) -> Self {
+        #[cfg(feature = "2d")]
+        let collider = Collider::circle(radius);
+        #[cfg(feature = "3d")]
+        let collider = Collider::sphere(radius);
+        Self::new(SensorZoneKind::InteractionReach, collider)
+    }
+
+    /// Creates a [`SensorZoneKind::GroundProbe`] with the given radius at the given offset,
+    /// typically just below the feet of the character.
+    pub fn ground_probe(radius: Scalar, offset: Vector) -> Self {
+        #[cfg(feature = "2d")]
+        let collider = Collider::circle(radius);
+        #[cfg(feature = "3d")]
+        let collider = Collider::sphere(radius);
+        Self::new(SensorZoneKind::GroundProbe, collider).with_offset(offset)
+    }
+
+    /// Sets the offset of the sensor relative to the entity.
+    pub fn with_offset(mut self, offset: Vector) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the [`CollisionLayers`] of the sensor.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+}
+
+/// The purpose of a [`SensorZone`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum SensorZoneKind {
+    /// A sensor for detecting hits, like attacks or projectiles.
+    Hitbox,
+    /// A sensor for detecting objects that are close enough to interact with.
+    InteractionReach,
+    /// A sensor for detecting whether the entity is standing on the ground.
+    GroundProbe,
+    /// A sensor with a custom purpose, identified by the given index.
+    Custom(u32),
+}
+
+/// A component for a [`Sensor`] entity spawned for one of the [`SensorZones`] of its owner.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[reflect(Debug, Component, PartialEq)]
+pub struct SensorZoneOf {
+    /// The entity with the [`SensorZones`].
+    pub owner: Entity,
+    /// The purpose of the sensor.
+    pub kind: SensorZoneKind,
+    /// The index of the sensor in the [`SensorZones`] of the owner.
+    pub index: usize,
+}
+
+/// A component storing the [`Sensor`] entities spawned for the [`SensorZones`] of an entity,
+/// in the same order as the zones. Inserted automatically by the [`SensorZonesPlugin`].
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq)]
+#[reflect(Debug, Component, PartialEq)]
+pub struct SensorZoneEntities(pub Vec<Entity>);
+
+impl SensorZoneEntities {
+    /// Returns the sensor entity of the first zone with the given kind.
+    pub fn get(&self, kind: SensorZoneKind, zones: &SensorZones) -> Option<Entity> {
+        zones
+            .0
+            .iter()
+            .position(|zone| zone.kind == kind)
+            .and_then(|index| self.0.get(index).copied())
+    }
+}
+
+/// Spawns, respawns, reattaches, and despawns the sensors of [`SensorZones`].
+#[allow(clippy::type_complexity)]
+fn sync_sensor_zones(
+    mut commands: Commands,
+    mut owners: Query<(Entity, Ref<SensorZones>, Option<&mut SensorZoneEntities>)>,
+    zones: Query<(Entity, &SensorZoneOf, Option<&Parent>)>,
+    stale_owners: Query<Entity, (With<SensorZoneEntities>, Without<SensorZones>)>,
+) {
+    for (owner, sensor_zones, zone_entities) in &mut owners {
+        let Some(mut zone_entities) = zone_entities.filter(|_| !sensor_zones.is_changed()) else {
+            // The zones were added or changed, so (re)spawn all sensors.
+            for (entity, zone_of, _) in &zones {
+                if zone_of.owner == owner {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+
+            let entities = sensor_zones
+                .0
+                .iter()
+                .enumerate()
+                .map(|(index, zone)| spawn_sensor_zone(&mut commands, owner, index, zone))
+                .collect();
+            commands.entity(owner).insert(SensorZoneEntities(entities));
+            continue;
+        };
+
+        for (index, zone) in sensor_zones.0.iter().enumerate() {
+            let Some(entity) = zone_entities.0.get(index).copied() else {
+                continue;
+            };
+
+            match zones.get(entity) {
+                Ok((_, _, parent)) => {
+                    // Reattach sensors that were removed from the hierarchy of the owner.
+                    if parent.map(|parent| parent.get()) != Some(owner) {
+                        commands.entity(owner).add_child(entity);
+                    }
+                }
+                Err(_) => {
+                    // Respawn sensors that were despawned.
+                    zone_entities.0[index] = spawn_sensor_zone(&mut commands, owner, index, zone);
+                }
+            }
+        }
+    }
+
+    // Despawn the sensors of entities that no longer have `SensorZones`.
+    for (entity, zone_of, _) in &zones {
+        if !owners.contains(zone_of.owner) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for owner in &stale_owners {
+        commands.entity(owner).remove::<SensorZoneEntities>();
+    }
+}
+
+/// Spawns the sensor entity for a [`SensorZone`] as a child of the owner.
+fn spawn_sensor_zone(
+    commands: &mut Commands,
+    owner: Entity,
+    index: usize,
+    zone: &SensorZone,
+) -> Entity {
+    #[cfg(feature = "2d")]
+    let translation = zone.offset.f32().extend(0.0);
+    #[cfg(feature = "3d")]
+    let translation = zone.offset.f32();
+
+    let mut sensor = commands.spawn((
+        zone.collider.clone(),
+        Sensor,
+        ColliderDensity(0.0),
+        Transform::from_translation(translation),
+        SensorZoneOf {
+            owner,
+            kind: zone.kind,
+            index,
+        },
+    ));
+    if let Some(layers) = zone.layers {
+        sensor.insert(layers);
+    }
+
+    let entity = sensor.id();
+    commands.entity(owner).add_child(entity);
+    entity
+}
@@ -128,6 +128,8 @@ impl Plugin for PhysicsDebugPlugin {
                     debug_render_joints::<RevoluteJoint>,
                     #[cfg(feature = "3d")]
                     debug_render_joints::<SphericalJoint>,
+                    #[cfg(feature = "3d")]
+                    debug_render_joints::<UniversalJoint>,
                     debug_render_raycasts,
                     #[cfg(all(
                         feature = "default-collider",
@@ -0,0 +1,445 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// The magic bytes at the start of every capture file.
+const MAGIC: &[u8; 8] = b"AVIANCAP";
+
+/// The version of the capture format. Incremented whenever the format changes.
+const VERSION: u32 = 1;
+
+/// The number of dimensions of the simulation, stored in the header
+/// so that 2D captures are not loaded as 3D captures and vice versa.
+#[cfg(feature = "2d")]
+const DIMENSION: u8 = 2;
+#[cfg(feature = "3d")]
+const DIMENSION: u8 = 3;
+
+/// A plugin that records the poses and velocities of all [rigid bodies](RigidBody)
+/// and a summary of all contacts at the end of every physics step, and writes them into a file.
+///
+/// The captures are stored in a compact binary format, and can be loaded with [`PhysicsCapture::load`]
+/// for offline analysis, for example to inspect a capture attached to a bug report.
+/// All values are stored with 64-bit precision, so captures don't depend on the `f32` or `f64` feature,
+/// but 2D and 3D captures are not compatible with each other.
+///
+/// Frames are buffered, and the file is flushed when the [`PhysicsCaptureWriter`] resource is removed
+/// or the app exits. I/O errors are logged, and stop the recording.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsCapturePlugin::new("capture.avcap"),
+///         ))
+///         .run();
+/// }
+///
+/// // Later, for example in a separate tool:
+/// fn analyze() -> std::io::Result<()> {
+///     let capture = PhysicsCapture::load("capture.avcap")?;
+///
+///     for frame in &capture.frames {
+///         println!(
+///             "Step {}: {} bodies, {} contacts",
+///             frame.step,
+///             frame.bodies.len(),
+///             frame.contacts.len()
+///         );
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub struct PhysicsCapturePlugin {
+    path: PathBuf,
+}
+
+impl PhysicsCapturePlugin {
+    /// Creates a [`PhysicsCapturePlugin`] that writes the capture into the file at the given path.
+    /// The file is overwritten if it already exists.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Plugin for PhysicsCapturePlugin {
+    fn build(&self, app: &mut App) {
+        match PhysicsCaptureWriter::create(&self.path) {
+            Ok(writer) => {
+                app.insert_resource(writer);
+            }
+            Err(error) => {
+                error!(
+                    "Failed to create physics capture file {}: {error}",
+                    self.path.display()
+                );
+                return;
+            }
+        }
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(record_capture_frame.in_set(PhysicsStepSet::Last));
+    }
+}
+
+/// A resource that writes [`CaptureFrame`]s into a file. Inserted by the [`PhysicsCapturePlugin`].
+///
+/// Removing the resource stops the recording and flushes the file.
+#[derive(Resource)]
+pub struct PhysicsCaptureWriter {
+    writer: Option<BufWriter<File>>,
+    step: u64,
+}
+
+impl PhysicsCaptureWriter {
+    /// Creates the file at the given path and writes the header of the capture.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&[DIMENSION])?;
+
+        Ok(Self {
+            writer: Some(writer),
+            step: 0,
+        })
+    }
+
+    /// Writes a frame into the capture.
+    pub fn write_frame(&mut self, frame: &CaptureFrame) -> io::Result<()> {
+        if let Some(writer) = &mut self.writer {
+            frame.write(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the buffered frames into the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if let Some(writer) = &mut self.writer {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the writer is still recording.
+    ///
+    /// Recording stops after an I/O error.
+    pub fn is_recording(&self) -> bool {
+        self.writer.is_some()
+    }
+}
+
+/// A capture of a physics simulation, loaded from a file written by the [`PhysicsCapturePlugin`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhysicsCapture {
+    /// The recorded frames, one for each physics step.
+    pub frames: Vec<CaptureFrame>,
+}
+
+impl PhysicsCapture {
+    /// Loads a capture from the file at the given path.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a capture from the given reader.
+    ///
+    /// Returns an error if the data is not a valid capture, or if it was recorded
+    /// in a different dimension.
+    pub fn read(reader: &mut impl Read) -> io::Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a physics capture"));
+        }
+
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported capture version {version}, expected {VERSION}"
+            )));
+        }
+
+        let mut dimension = [0];
+        reader.read_exact(&mut dimension)?;
+        if dimension[0] != DIMENSION {
+            return Err(invalid_data(format!(
+                "capture was recorded in {}D, but this is a {DIMENSION}D build",
+                dimension[0]
+            )));
+        }
+
+        let mut frames = vec![];
+        while let Some(frame) = CaptureFrame::read(reader)? {
+            frames.push(frame);
+        }
+
+        Ok(Self { frames })
+    }
+}
+
+/// The state of the simulation at the end of a physics step, stored in a [`PhysicsCapture`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CaptureFrame {
+    /// The index of the physics step, starting from zero when the recording started.
+    pub step: u64,
+    /// The elapsed time of the [`Physics`] clock in seconds.
+    pub time: f64,
+    /// The state of each rigid body, sorted by entity.
+    pub bodies: Vec<CapturedBody>,
+    /// A summary of each contact pair.
+    pub contacts: Vec<CapturedContact>,
+}
+
+/// The state of a rigid body in a [`CaptureFrame`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapturedBody {
+    /// The entity of the body at the time of recording.
+    pub entity: Entity,
+    /// The position of the body.
+    pub position: Vector,
+    /// The rotation of the body.
+    pub rotation: Rotation,
+    /// The linear velocity of the body.
+    pub linear_velocity: Vector,
+    /// The angular velocity of the body.
+    #[cfg(feature = "2d")]
+    pub angular_velocity: Scalar,
+    /// The angular velocity of the body.
+    #[cfg(feature = "3d")]
+    pub angular_velocity: Vector,
+}
+
+/// A summary of the contacts between two colliders in a [`CaptureFrame`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapturedContact {
+    /// The first collider entity.
+    pub entity1: Entity,
+    /// The second collider entity.
+    pub entity2: Entity,
+    /// The total number of contact points in all contact manifolds.
+    pub point_count: u32,
+    /// The largest penetration depth of the contact points.
+    pub max_penetration: Scalar,
+    /// The total normal impulse applied to the first body.
+    pub total_normal_impulse: Scalar,
+}
+
+impl CaptureFrame {
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&self.step.to_le_bytes())?;
+        writer.write_all(&self.time.to_le_bytes())?;
+
+        writer.write_all(&(self.bodies.len() as u32).to_le_bytes())?;
+        for body in &self.bodies {
+            writer.write_all(&body.entity.to_bits().to_le_bytes())?;
+            write_scalars(writer, &body.position.to_array())?;
+            #[cfg(feature = "2d")]
+            write_scalars(writer, &[body.rotation.cos, body.rotation.sin])?;
+            #[cfg(feature = "3d")]
+            write_scalars(writer, &body.rotation.to_array())?;
+            write_scalars(writer, &body.linear_velocity.to_array())?;
+            #[cfg(feature = "2d")]
+            write_scalars(writer, &[body.angular_velocity])?;
+            #[cfg(feature = "3d")]
+            write_scalars(writer, &body.angular_velocity.to_array())?;
+        }
+
+        writer.write_all(&(self.contacts.len() as u32).to_le_bytes())?;
+        for contact in &self.contacts {
+            writer.write_all(&contact.entity1.to_bits().to_le_bytes())?;
+            writer.write_all(&contact.entity2.to_bits().to_le_bytes())?;
+            writer.write_all(&contact.point_count.to_le_bytes())?;
+            write_scalars(
+                writer,
+                &[contact.max_penetration, contact.total_normal_impulse],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the next frame, or returns `None` at the end of the data.
+    fn read(reader: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut step = [0; 8];
+        match reader.read_exact(&mut step) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+
+        let mut frame = CaptureFrame {
+            step: u64::from_le_bytes(step),
+            time: read_f64(reader)?,
+            ..default()
+        };
+
+        let body_count = read_u32(reader)?;
+        for _ in 0..body_count {
+            let entity = read_entity(reader)?;
+            let position = Vector::from_array(read_scalars(reader)?);
+            #[cfg(feature = "2d")]
+            let rotation = {
+                let [cos, sin] = read_scalars(reader)?;
+                Rotation::from_sin_cos(sin, cos)
+            };
+            #[cfg(feature = "3d")]
+            let rotation = Rotation(Quaternion::from_array(read_scalars(reader)?));
+            let linear_velocity = Vector::from_array(read_scalars(reader)?);
+            #[cfg(feature = "2d")]
+            let angular_velocity = read_scalars::<1>(reader)?[0];
+            #[cfg(feature = "3d")]
+            let angular_velocity = Vector::from_array(read_scalars(reader)?);
+
+            frame.bodies.push(CapturedBody {
+                entity,
+                position,
+                rotation,
+                linear_velocity,
+                angular_velocity,
+            });
+        }
+
+        let contact_count = read_u32(reader)?;
+        for _ in 0..contact_count {
+            let entity1 = read_entity(reader)?;
+            let entity2 = read_entity(reader)?;
+            let point_count = read_u32(reader)?;
+            let [max_penetration, total_normal_impulse] = read_scalars(reader)?;
+
+            frame.contacts.push(CapturedContact {
+                entity1,
+                entity2,
+                point_count,
+                max_penetration,
+                total_normal_impulse,
+            });
+        }
+
+        Ok(Some(frame))
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn write_scalars(writer: &mut impl Write, values: &[Scalar]) -> io::Result<()> {
+    for value in values {
+        writer.write_all(&(*value as f64).to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_f64(reader: &mut impl Read) -> io::Result<f64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(f64::from_le_bytes(bytes))
+}
+
+fn read_scalars<const N: usize>(reader: &mut impl Read) -> io::Result<[Scalar; N]> {
+    let mut values = [0.0; N];
+    for value in values.iter_mut() {
+        *value = read_f64(reader)? as Scalar;
+    }
+    Ok(values)
+}
+
+fn read_entity(reader: &mut impl Read) -> io::Result<Entity> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Entity::try_from_bits(u64::from_le_bytes(bytes)).map_err(|_| invalid_data("invalid entity"))
+}
+
+/// Records the state of the simulation into the [`PhysicsCaptureWriter`].
+fn record_capture_frame(
+    writer: Option<ResMut<PhysicsCaptureWriter>>,
+    bodies: Query<
+        (
+            Entity,
+            &Position,
+            &Rotation,
+            &LinearVelocity,
+            &AngularVelocity,
+        ),
+        With<RigidBody>,
+    >,
+    collisions: Res<Collisions>,
+    time: Res<Time>,
+) {
+    let Some(mut writer) = writer else {
+        return;
+    };
+
+    if !writer.is_recording() {
+        return;
+    }
+
+    let mut frame = CaptureFrame {
+        step: writer.step,
+        time: time.elapsed_secs_f64(),
+        bodies: bodies
+            .iter()
+            .map(
+                |(entity, position, rotation, linear_velocity, angular_velocity)| CapturedBody {
+                    entity,
+                    position: position.0,
+                    rotation: *rotation,
+                    linear_velocity: linear_velocity.0,
+                    angular_velocity: angular_velocity.0,
+                },
+            )
+            .collect(),
+        contacts: collisions
+            .iter()
+            .filter(|contacts| contacts.during_current_frame)
+            .map(|contacts| CapturedContact {
+                entity1: contacts.entity1,
+                entity2: contacts.entity2,
+                point_count: contacts
+                    .manifolds
+                    .iter()
+                    .map(|manifold| manifold.contacts.len() as u32)
+                    .sum(),
+                max_penetration: contacts
+                    .find_deepest_contact()
+                    .map_or(0.0, |contact| contact.penetration),
+                total_normal_impulse: contacts.total_normal_impulse,
+            })
+            .collect(),
+    };
+
+    // Sort the bodies so that the capture doesn't depend on the query iteration order.
+    frame.bodies.sort_by_key(|body| body.entity);
+
+    writer.step += 1;
+
+    if let Err(error) = writer.write_frame(&frame) {
+        error!("Failed to write physics capture, stopping the recording: {error}");
+        writer.writer = None;
+    }
+}
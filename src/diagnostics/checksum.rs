@@ -0,0 +1,170 @@
+use std::hash::Hasher;
+
+use crate::prelude::*;
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, RegisterDiagnostic},
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// A plugin that computes a [`PhysicsChecksum`] of the state of all [rigid bodies](RigidBody)
+/// at the end of every physics step.
+///
+/// This is primarily useful for lockstep networking, where every peer runs the same simulation
+/// and needs a cheap way to detect when the simulations have diverged. Peers can exchange
+/// the checksum for a given step and compare it against their own.
+///
+/// The checksum covers the [`Position`], [`Rotation`], [`LinearVelocity`], and [`AngularVelocity`]
+/// of every rigid body. It does not depend on the order in which the bodies are iterated,
+/// nor on their [`Entity`] IDs, so peers only need to agree on the simulated state itself.
+///
+/// If Bevy's `DiagnosticsPlugin` has been added, the lower 32 bits of the checksum
+/// are also recorded under [`PhysicsChecksum::DIAGNOSTIC_PATH`].
+///
+/// Note that bit-for-bit identical results across machines generally require
+/// the `enhanced-determinism` feature.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsChecksumPlugin::default(),
+///         ))
+///         .add_systems(PostUpdate, send_checksum)
+///         .run();
+/// }
+///
+/// fn send_checksum(checksum: Res<PhysicsChecksum>) {
+///     if checksum.is_changed() {
+///         // Send `checksum.value()` and `checksum.step()` to the other peers...
+///     }
+/// }
+/// ```
+pub struct PhysicsChecksumPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PhysicsChecksumPlugin {
+    /// Creates a [`PhysicsChecksumPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PhysicsSchedule`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PhysicsChecksumPlugin {
+    fn default() -> Self {
+        Self::new(PhysicsSchedule)
+    }
+}
+
+impl Plugin for PhysicsChecksumPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsChecksum>()
+            .register_type::<PhysicsChecksum>();
+
+        app.add_systems(
+            self.schedule,
+            update_physics_checksum.in_set(PhysicsStepSet::Last),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Only record the diagnostic if diagnostics are enabled for the app.
+        if !app.world().contains_resource::<DiagnosticsStore>() {
+            return;
+        }
+
+        app.register_diagnostic(Diagnostic::new(PhysicsChecksum::DIAGNOSTIC_PATH));
+        app.add_systems(
+            self.schedule,
+            record_checksum_diagnostic
+                .after(update_physics_checksum)
+                .in_set(PhysicsStepSet::Last),
+        );
+    }
+}
+
+/// A checksum of the state of all [rigid bodies](RigidBody), computed at the end of each physics step
+/// by the [`PhysicsChecksumPlugin`].
+///
+/// See the plugin documentation for more information.
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct PhysicsChecksum {
+    value: u64,
+    step: u64,
+}
+
+impl PhysicsChecksum {
+    /// The [`DiagnosticPath`] under which the checksum is recorded when diagnostics are enabled.
+    pub const DIAGNOSTIC_PATH: DiagnosticPath = DiagnosticPath::const_new("avian/physics_checksum");
+
+    /// Returns the checksum computed for the most recent physics step.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns the number of physics steps that have been checksummed so far.
+    ///
+    /// Peers should compare checksums for the same step.
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+}
+
+fn update_physics_checksum(
+    bodies: Query<(&Position, &Rotation, &LinearVelocity, &AngularVelocity), With<RigidBody>>,
+    mut checksum: ResMut<PhysicsChecksum>,
+) {
+    let mut value = 0_u64;
+
+    for (position, rotation, linear_velocity, angular_velocity) in &bodies {
+        let mut hasher = fxhash::FxHasher64::default();
+
+        hash_scalars(&mut hasher, &position.to_array());
+        #[cfg(feature = "2d")]
+        hash_scalars(&mut hasher, &[rotation.cos, rotation.sin]);
+        #[cfg(feature = "3d")]
+        hash_scalars(&mut hasher, &rotation.to_array());
+        hash_scalars(&mut hasher, &linear_velocity.to_array());
+        #[cfg(feature = "2d")]
+        hash_scalars(&mut hasher, &[angular_velocity.0]);
+        #[cfg(feature = "3d")]
+        hash_scalars(&mut hasher, &angular_velocity.to_array());
+
+        // Combine the per-body hashes with a commutative operation
+        // so that the result is independent of the iteration order.
+        value = value.wrapping_add(hasher.finish());
+    }
+
+    checksum.value = value;
+    checksum.step += 1;
+}
+
+fn hash_scalars(hasher: &mut impl Hasher, values: &[Scalar]) {
+    for value in values {
+        #[cfg(feature = "f32")]
+        hasher.write_u32(value.to_bits());
+        #[cfg(feature = "f64")]
+        hasher.write_u64(value.to_bits());
+    }
+}
+
+fn record_checksum_diagnostic(checksum: Res<PhysicsChecksum>, mut diagnostics: Diagnostics) {
+    // Diagnostics are stored as `f64`, so only the lower 32 bits can be represented exactly.
+    diagnostics.add_measurement(&PhysicsChecksum::DIAGNOSTIC_PATH, || {
+        (checksum.value as u32) as f64
+    });
+}
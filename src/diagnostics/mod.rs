@@ -0,0 +1,13 @@
+//! Tools for inspecting and validating the state of the physics simulation.
+//!
+//! The plugins in this module are not part of [`PhysicsPlugins`] and must be added manually.
+//!
+//! See [`PhysicsChecksumPlugin`], [`PhysicsCapturePlugin`], and [`PhysicsDebugExt`].
+
+mod capture;
+mod checksum;
+mod state_dump;
+
+pub use capture::*;
+pub use checksum::*;
+pub use state_dump::*;
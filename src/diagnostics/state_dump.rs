@@ -0,0 +1,229 @@
+use std::fmt;
+
+use crate::{dynamics::solver::xpbd::XpbdConstraint, prelude::*};
+use bevy::prelude::*;
+
+/// An extension trait for [`World`] for collecting the physics state of an entity
+/// into a single [`PhysicsStateReport`].
+///
+/// This is useful for attaching the state of a misbehaving body to a bug report,
+/// or for showing it in an in-game debug panel.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn print_selected(world: &mut World) {
+///     let mut selected = world.query_filtered::<Entity, With<Selected>>();
+///
+///     for entity in selected.iter(world) {
+///         if let Some(report) = world.dump_physics_state(entity) {
+///             println!("{report}");
+///         }
+///     }
+/// }
+/// #
+/// # #[derive(Component)]
+/// # struct Selected;
+/// ```
+pub trait PhysicsDebugExt {
+    /// Collects the physics components, contacts, joints, island, and sleeping state
+    /// of the given entity into a [`PhysicsStateReport`].
+    ///
+    /// Returns `None` if the entity does not exist.
+    fn dump_physics_state(&self, entity: Entity) -> Option<PhysicsStateReport>;
+}
+
+/// A snapshot of the physics state of an entity, created by [`PhysicsDebugExt::dump_physics_state`].
+///
+/// The [`Display`](fmt::Display) implementation formats the report as readable text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhysicsStateReport {
+    /// The entity that the report was created for.
+    pub entity: Entity,
+    /// The names and [`Debug`](fmt::Debug) representations of the physics components on the entity.
+    pub components: Vec<(&'static str, String)>,
+    /// The contacts that the entity is currently involved in.
+    pub contacts: Vec<Contacts>,
+    /// The joints attached to the entity, along with the name of the joint type.
+    pub joints: Vec<(Entity, &'static str)>,
+    /// The index of the [island](PhysicsIslands) that the entity or its rigid body belongs to.
+    pub island: Option<usize>,
+    /// Whether the entity or its rigid body is [`Sleeping`].
+    pub is_sleeping: bool,
+}
+
+impl PhysicsDebugExt for World {
+    fn dump_physics_state(&self, entity: Entity) -> Option<PhysicsStateReport> {
+        let entity_ref = self.get_entity(entity).ok()?;
+
+        let mut components = vec![];
+
+        macro_rules! collect_components {
+            ($($component:ty),* $(,)?) => {
+                $(
+                    if let Some(component) = entity_ref.get::<$component>() {
+                        components.push((stringify!($component), format!("{component:?}")));
+                    }
+                )*
+            };
+        }
+
+        collect_components!(
+            RigidBody,
+            Position,
+            Rotation,
+            LinearVelocity,
+            AngularVelocity,
+            ExternalForce,
+            ExternalTorque,
+            ExternalImpulse,
+            ExternalAngularImpulse,
+            ComputedMass,
+            ComputedAngularInertia,
+            ComputedCenterOfMass,
+            LinearDamping,
+            AngularDamping,
+            MaxLinearSpeed,
+            MaxAngularSpeed,
+            MaxOverlapSolveSpeed,
+            SkipWarmStart,
+            PlatformVelocityTransfer,
+            MinInertiaRadius,
+            GravityScale,
+            GravityOverride,
+            LockedAxes,
+            Dominance,
+            Friction,
+            Restitution,
+            ColliderParent,
+            ColliderAabb,
+            ColliderDensity,
+            CollisionLayers,
+            CollisionMargin,
+            SpeculativeMargin,
+            SweptCcd,
+            PhysicsIslandId,
+            Sensor,
+            ContactResponseDisabled,
+            QueryOnly,
+            CollisionUserData,
+            RigidBodyDisabled,
+            Frozen,
+            ColliderDisabled,
+            Sleeping,
+            SleepingDisabled,
+            SleepThreshold,
+            TimeSleeping,
+        );
+
+        // Colliders share the island and sleeping state of their rigid body.
+        let body = entity_ref
+            .get::<ColliderParent>()
+            .map_or(entity, |p| p.get());
+
+        let contacts = self
+            .get_resource::<Collisions>()
+            .map(|collisions| collisions.collisions_with_entity(entity).cloned().collect())
+            .unwrap_or_default();
+
+        let island = self
+            .get_resource::<PhysicsIslands>()
+            .and_then(|islands| islands.island_index(body));
+
+        let is_sleeping = self
+            .get_entity(body)
+            .is_ok_and(|body| body.contains::<Sleeping>());
+
+        Some(PhysicsStateReport {
+            entity,
+            components,
+            contacts,
+            joints: attached_joints(self, entity),
+            island,
+            is_sleeping,
+        })
+    }
+}
+
+/// Returns the joint entities attached to the given entity, along with the name of the joint type.
+fn attached_joints(world: &World, entity: Entity) -> Vec<(Entity, &'static str)> {
+    let mut joints = vec![];
+
+    for joint_entity in world.iter_entities() {
+        macro_rules! collect_joints {
+            ($($joint:ty),* $(,)?) => {
+                $(
+                    if joint_entity
+                        .get::<$joint>()
+                        .is_some_and(|joint| joint.entities().contains(&entity))
+                    {
+                        joints.push((joint_entity.id(), stringify!($joint)));
+                    }
+                )*
+            };
+        }
+
+        collect_joints!(
+            FixedJoint,
+            DistanceJoint,
+            PrismaticJoint,
+            RevoluteJoint,
+            MirrorConstraint,
+            GearJoint,
+            RackAndPinionJoint
+        );
+        #[cfg(feature = "3d")]
+        collect_joints!(SphericalJoint, UniversalJoint);
+    }
+
+    joints
+}
+
+impl fmt::Display for PhysicsStateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Physics state of {}", self.entity)?;
+
+        match self.island {
+            Some(island) => writeln!(f, "  Island: {island}")?,
+            None => writeln!(f, "  Island: none")?,
+        }
+        writeln!(f, "  Sleeping: {}", self.is_sleeping)?;
+
+        writeln!(f, "  Components:")?;
+        for (name, value) in &self.components {
+            writeln!(f, "    {name}: {value}")?;
+        }
+
+        writeln!(f, "  Contacts: {}", self.contacts.len())?;
+        for contacts in &self.contacts {
+            let other = if contacts.entity1 == self.entity {
+                contacts.entity2
+            } else {
+                contacts.entity1
+            };
+            let point_count: usize = contacts
+                .manifolds
+                .iter()
+                .map(|manifold| manifold.contacts.len())
+                .sum();
+            writeln!(
+                f,
+                "    with {other}: {point_count} points in {} manifolds, total normal impulse {}, sensor: {}",
+                contacts.manifolds.len(),
+                contacts.total_normal_impulse,
+                contacts.is_sensor,
+            )?;
+        }
+
+        writeln!(f, "  Joints: {}", self.joints.len())?;
+        for (joint, name) in &self.joints {
+            writeln!(f, "    {name} {joint}")?;
+        }
+
+        Ok(())
+    }
+}
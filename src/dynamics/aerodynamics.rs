@@ -0,0 +1,461 @@
+//! Aerodynamic drag and lift caused by the movement of bodies relative to the surrounding air.
+//!
+//! See [`AerodynamicsPlugin`].
+
+use crate::{dynamics::integrator::IntegrationSet, prelude::*};
+use bevy::{
+    ecs::{entity::EntityHashMap, intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// A plugin that applies aerodynamic [`Drag`] and [`Lift`] forces to dynamic bodies
+/// based on their velocity relative to the [`Wind`].
+///
+/// The forces are applied to the center of mass of the bodies in every substep,
+/// before [velocity integration](IntegrationSet::Velocity). The air velocity of a body is the global [`Wind`]
+/// plus the velocity of any [`WindVolume`]s that its colliders are inside. The density of the air
+/// is configured with the [`AirDensity`] resource.
+///
+/// This is useful for things like projectiles, falling leaves, and gliders.
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             AerodynamicsPlugin::default(),
+///         ))
+///         .insert_resource(Wind(Vector::X * 2.0))
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     // A ball with the drag coefficient of a sphere and a cross-sectional area of about 0.03 square meters.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(0.1),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(0.1),")]
+///         Drag::new(Drag::SPHERE, 0.0314),
+///     ));
+/// }
+/// # #[cfg(not(feature = "f32"))]
+/// # fn setup() {}
+/// ```
+pub struct AerodynamicsPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl AerodynamicsPlugin {
+    /// Creates an [`AerodynamicsPlugin`] with the schedule that the forces are applied in.
+    ///
+    /// The default schedule is [`SubstepSchedule`].
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for AerodynamicsPlugin {
+    fn default() -> Self {
+        Self::new(SubstepSchedule)
+    }
+}
+
+impl Plugin for AerodynamicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(Drag, ReferenceArea, Lift, WindVolume, Wind, AirDensity)>();
+
+        app.init_resource::<Wind>().init_resource::<AirDensity>();
+
+        app.add_systems(
+            self.schedule.intern(),
+            apply_aerodynamic_forces.before(IntegrationSet::Velocity),
+        );
+    }
+}
+
+/// A resource for the global velocity of the air, used for computing aerodynamic forces
+/// with the [`AerodynamicsPlugin`].
+///
+/// The default is no wind.
+///
+/// The wind can be changed locally using [`WindVolume`]s.
+#[derive(Reflect, Resource, Clone, Copy, Debug, Default, PartialEq, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, Default, PartialEq)]
+pub struct Wind(pub Vector);
+
+/// A resource for the density of the air in kilograms per cubic meter, used for computing
+/// aerodynamic forces with the [`AerodynamicsPlugin`].
+///
+/// The default is `1.225`, the density of air at sea level. Use a larger value,
+/// like `1000.0` for water, for bodies moving through denser fluids.
+#[derive(Reflect, Resource, Clone, Copy, Debug, PartialEq, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, Default, PartialEq)]
+pub struct AirDensity(pub Scalar);
+
+impl Default for AirDensity {
+    fn default() -> Self {
+        Self(1.225)
+    }
+}
+
+/// A component that adds the given velocity to the [`Wind`] for bodies whose colliders are inside
+/// the collider of the entity. Typically attached to a [`Sensor`] collider.
+///
+/// The bodies inside the volume are determined using the [`CollidingEntities`] of the volume,
+/// so the wind is applied based on the contacts of the previous physics step.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[require(CollidingEntities)]
+pub struct WindVolume(pub Vector);
+
+/// A component for the aerodynamic drag of a dynamic [rigid body](RigidBody), slowing it down
+/// relative to the surrounding air. Requires the [`AerodynamicsPlugin`].
+///
+/// The drag force is computed using the drag equation `0.5 * ρ * C_d * A * |v|²`,
+/// where `ρ` is the [`AirDensity`], `C_d` is the drag coefficient, `A` is the reference area,
+/// and `v` is the velocity of the body relative to the [`Wind`].
+///
+/// Unlike [`LinearDamping`], drag grows quadratically with speed, and it is affected by the wind.
+///
+/// The reference area can either be fixed, or computed automatically from the [`Collider`]
+/// of the body based on its orientation relative to the flow. See [`ReferenceArea`].
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     // A box whose drag depends on which side faces the flow.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 0.2),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 0.2, 1.0),")]
+///         Drag::from_collider(Drag::CUBE),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Drag {
+    /// The dimensionless drag coefficient. Typical values for common shapes are provided
+    /// as associated constants, like [`Drag::SPHERE`] and [`Drag::CUBE`].
+    pub coefficient: Scalar,
+    /// The reference area, typically the cross-sectional area of the body perpendicular to the flow.
+    pub area: ReferenceArea,
+}
+
+impl Drag {
+    /// The approximate drag coefficient of a sphere.
+    pub const SPHERE: Scalar = 0.47;
+    /// The approximate drag coefficient of a cube facing the flow.
+    pub const CUBE: Scalar = 1.05;
+    /// The approximate drag coefficient of a long cylinder moving perpendicular to its axis.
+    pub const CYLINDER: Scalar = 0.82;
+    /// The approximate drag coefficient of a flat plate facing the flow.
+    pub const FLAT_PLATE: Scalar = 1.28;
+    /// The approximate drag coefficient of a streamlined body, like an arrow or a fish.
+    pub const STREAMLINED: Scalar = 0.04;
+
+    /// Creates a new [`Drag`] with the given drag coefficient and a fixed reference area.
+    pub fn new(coefficient: Scalar, area: Scalar) -> Self {
+        Self {
+            coefficient,
+            area: ReferenceArea::Fixed(area),
+        }
+    }
+
+    /// Creates a new [`Drag`] with the given drag coefficient and a reference area
+    /// computed from the [`Collider`] of the body.
+    pub fn from_collider(coefficient: Scalar) -> Self {
+        Self {
+            coefficient,
+            area: ReferenceArea::FromCollider,
+        }
+    }
+
+    /// Computes the drag force for a body with the given reference area and velocity relative to the air.
+    pub fn force(&self, area: Scalar, relative_velocity: Vector, air_density: Scalar) -> Vector {
+        -0.5 * air_density
+            * self.coefficient
+            * area
+            * relative_velocity.length()
+            * relative_velocity
+    }
+}
+
+/// The reference area used for computing [`Drag`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum ReferenceArea {
+    /// A fixed reference area.
+    Fixed(Scalar),
+    /// The area of the [`Collider`] on the body entity projected onto a plane perpendicular to the flow,
+    /// recomputed every substep as the body rotates. In 2D, this is the projected length of the collider.
+    ///
+    /// Balls, cuboids, capsules, and cylinders are handled exactly. For other shapes,
+    /// the projected area of their [`ColliderAabb`] is used. If the body has no collider,
+    /// the area is zero.
+    FromCollider,
+}
+
+/// Computes the area of the given collider projected onto a plane perpendicular to the given direction.
+/// In 2D, this is the length of the collider projected onto a line perpendicular to the direction.
+///
+/// Balls, cuboids, capsules, and cylinders are handled exactly. For other shapes,
+/// the projected area of their axis-aligned bounding box is used.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub fn projected_area(collider: &Collider, rotation: &Rotation, direction: Vector) -> Scalar {
+    use parry::shape::TypedShape;
+
+    let Some(direction) = direction.try_normalize() else {
+        return 0.0;
+    };
+    let local_direction = rotation.inverse() * direction;
+
+    // Returns the sine of the angle between the flow and the given local axis.
+    let sin_angle_to = |axis: Vector| {
+        let cos = axis.normalize_or_zero().dot(local_direction);
+        (1.0 - cos * cos).max(0.0).sqrt()
+    };
+
+    match collider.shape_scaled().as_typed_shape() {
+        #[cfg(feature = "2d")]
+        TypedShape::Ball(ball) => 2.0 * ball.radius,
+        #[cfg(feature = "3d")]
+        TypedShape::Ball(ball) => PI * ball.radius * ball.radius,
+        TypedShape::Cuboid(cuboid) => {
+            box_projected_area(Vector::from(cuboid.half_extents), local_direction)
+        }
+        #[cfg(feature = "2d")]
+        TypedShape::Capsule(capsule) => {
+            let axis = Vector::from(capsule.segment.b - capsule.segment.a);
+            2.0 * capsule.radius + axis.length() * sin_angle_to(axis)
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::Capsule(capsule) => {
+            let axis = Vector::from(capsule.segment.b - capsule.segment.a);
+            PI * capsule.radius * capsule.radius
+                + 2.0 * capsule.radius * axis.length() * sin_angle_to(axis)
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::Cylinder(cylinder) => {
+            let cos = local_direction.y.abs();
+            PI * cylinder.radius * cylinder.radius * cos
+                + 4.0 * cylinder.radius * cylinder.half_height * sin_angle_to(Vector::Y)
+        }
+        _ => {
+            let aabb = collider.aabb(Vector::ZERO, *rotation);
+            box_projected_area((aabb.max - aabb.min) * 0.5, direction)
+        }
+    }
+}
+
+/// Computes the area of a box with the given half-extents projected onto a plane perpendicular
+/// to the given normalized direction in the local space of the box.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn box_projected_area(half_extents: Vector, direction: Vector) -> Scalar {
+    let direction = direction.abs();
+    #[cfg(feature = "2d")]
+    {
+        2.0 * (half_extents.y * direction.x + half_extents.x * direction.y)
+    }
+    #[cfg(feature = "3d")]
+    {
+        4.0 * (half_extents.y * half_extents.z * direction.x
+            + half_extents.x * half_extents.z * direction.y
+            + half_extents.x * half_extents.y * direction.z)
+    }
+}
+
+/// A component for the aerodynamic lift of a dynamic [rigid body](RigidBody), pushing it perpendicular
+/// to its velocity relative to the surrounding air. Requires the [`AerodynamicsPlugin`].
+///
+/// Lift is computed using a simple flat plate model. The lift force is `0.5 * ρ * C_l * A * |v|² * sin(α)`,
+/// where `ρ` is the [`AirDensity`], `C_l` is the lift coefficient, `A` is the area of the plate,
+/// `v` is the velocity of the body relative to the [`Wind`], and `α` is the angle of attack
+/// between the velocity and the plate. The force is perpendicular to the velocity, pointing towards
+/// the side of the plate facing away from the flow.
+///
+/// This can be used for things like gliders, wings, and falling leaves.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Lift {
+    /// The dimensionless lift coefficient.
+    pub coefficient: Scalar,
+    /// The area of the lifting surface.
+    pub area: Scalar,
+    /// The normal of the lifting surface in the local space of the body.
+    pub normal: Vector,
+}
+
+impl Lift {
+    /// Creates a new [`Lift`] with the given lift coefficient and area,
+    /// for a surface facing the local up direction.
+    pub fn new(coefficient: Scalar, area: Scalar) -> Self {
+        Self {
+            coefficient,
+            area,
+            normal: Vector::Y,
+        }
+    }
+
+    /// Sets the normal of the lifting surface in the local space of the body.
+    pub fn with_normal(mut self, normal: Vector) -> Self {
+        self.normal = normal;
+        self
+    }
+
+    /// Computes the lift force for a body with the given rotation and velocity relative to the air.
+    pub fn force(
+        &self,
+        rotation: &Rotation,
+        relative_velocity: Vector,
+        air_density: Scalar,
+    ) -> Vector {
+        let speed = relative_velocity.length();
+        let normal = (*rotation * self.normal).normalize_or_zero();
+        if speed == 0.0 || normal == Vector::ZERO {
+            return Vector::ZERO;
+        }
+
+        // The lift is perpendicular to the flow, in the plane of the velocity and the surface normal.
+        let direction = relative_velocity / speed;
+        let sin_angle_of_attack = -normal.dot(direction);
+        let lift_direction = (normal - direction * normal.dot(direction)).normalize_or_zero();
+
+        0.5 * air_density
+            * self.coefficient
+            * self.area
+            * speed
+            * speed
+            * sin_angle_of_attack
+            * lift_direction
+    }
+}
+
+/// Applies [`Drag`] and [`Lift`] forces to dynamic bodies.
+#[allow(clippy::type_complexity)]
+fn apply_aerodynamic_forces(
+    mut bodies: Query<
+        (
+            Entity,
+            &RigidBody,
+            &Rotation,
+            &mut LinearVelocity,
+            &ComputedMass,
+            Option<&Drag>,
+            Option<&Lift>,
+            Option<&LockedAxes>,
+        ),
+        (Or<(With<Drag>, With<Lift>)>, RigidBodyActiveFilter),
+    >,
+    wind_volumes: Query<(&WindVolume, &CollidingEntities)>,
+    collider_parents: Query<&ColliderParent>,
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    colliders: Query<&Collider>,
+    wind: Res<Wind>,
+    air_density: Res<AirDensity>,
+    mut local_winds: Local<EntityHashMap<Vector>>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    // Compute the wind added by wind volumes for each body.
+    local_winds.clear();
+    for (volume, colliding_entities) in &wind_volumes {
+        let mut bodies_in_volume: Vec<Entity> = colliding_entities
+            .iter()
+            .filter_map(|&entity| collider_parents.get(entity).ok().map(|p| p.get()))
+            .collect();
+        bodies_in_volume.sort();
+        bodies_in_volume.dedup();
+
+        for body in bodies_in_volume {
+            *local_winds.entry(body).or_default() += volume.0;
+        }
+    }
+
+    for (entity, rb, rotation, mut lin_vel, mass, drag, lift, locked_axes) in &mut bodies {
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        let air_velocity = wind.0 + local_winds.get(&entity).copied().unwrap_or_default();
+        let relative_velocity = lin_vel.0 - air_velocity;
+
+        let mut force = Vector::ZERO;
+        if let Some(drag) = drag {
+            let area = match drag.area {
+                ReferenceArea::Fixed(area) => area,
+                #[cfg(all(
+                    feature = "default-collider",
+                    any(feature = "parry-f32", feature = "parry-f64")
+                ))]
+                ReferenceArea::FromCollider => colliders.get(entity).map_or(0.0, |collider| {
+                    projected_area(collider, rotation, relative_velocity)
+                }),
+                #[cfg(not(all(
+                    feature = "default-collider",
+                    any(feature = "parry-f32", feature = "parry-f64")
+                )))]
+                ReferenceArea::FromCollider => 0.0,
+            };
+            force += drag.force(area, relative_velocity, air_density.0);
+        }
+        if let Some(lift) = lift {
+            force += lift.force(rotation, relative_velocity, air_density.0);
+        }
+
+        let locked_axes = locked_axes.copied().unwrap_or_default();
+        let mut delta_vel = locked_axes.apply_to_vec(force * mass.inverse() * delta_secs);
+
+        // Drag can't reverse the direction of the relative velocity, even with large time steps.
+        let parallel = delta_vel.dot(relative_velocity);
+        let speed_squared = relative_velocity.length_squared();
+        if parallel < -speed_squared {
+            delta_vel -= relative_velocity * (parallel + speed_squared) / speed_squared;
+        }
+
+        if delta_vel != Vector::ZERO {
+            lin_vel.0 += delta_vel;
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! A per-step feed of bodies that moved, for updating expensive systems only where something changed.
+//!
+//! See [`BodyMovedPlugin`].
+
+use crate::prelude::*;
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+/// A plugin that sends a [`BodyMoved`] event with the previous and current pose of each rigid body
+/// that moved more than the thresholds of the [`BodyMovedConfig`] during a physics step.
+///
+/// This is useful for expensive systems that depend on the positions of bodies,
+/// like audio occlusion, reflection probes, or navigation mesh updates,
+/// so that they only need to be updated for bodies that actually moved.
+///
+/// The previous pose is the pose of the body when it was last reported, or when it was added.
+/// This way, slow movement accumulates over several steps until it exceeds the thresholds,
+/// and movement between physics steps, like teleporting a body, is also reported.
+/// Bodies are not reported on the step they are spawned on.
+///
+/// Only bodies whose [`Position`] or [`Rotation`] changed are checked, so static and sleeping bodies
+/// don't add any overhead.
+///
+/// The plugin must be added after the [`PhysicsPlugins`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), BodyMovedPlugin))
+///         .insert_resource(BodyMovedConfig {
+///             distance_threshold: 0.5,
+///             ..default()
+///         })
+///         .add_systems(Update, update_audio_occlusion)
+///         .run();
+/// }
+///
+/// fn update_audio_occlusion(mut body_moved_event_reader: EventReader<BodyMoved>) {
+///     for event in body_moved_event_reader.read() {
+///         println!(
+///             "{} moved from {} to {}",
+///             event.entity, event.previous_position, event.position,
+///         );
+///     }
+/// }
+/// ```
+pub struct BodyMovedPlugin;
+
+impl Plugin for BodyMovedPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BodyMovedConfig>()
+            .init_resource::<BodyMovedConfig>()
+            .add_event::<BodyMoved>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(send_body_moved_events.in_set(PhysicsStepSet::Last));
+    }
+}
+
+/// Configures which movement is reported as [`BodyMoved`] events by the [`BodyMovedPlugin`].
+///
+/// A body is reported if it exceeds either of the thresholds.
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct BodyMovedConfig {
+    /// The distance that a body must move from its last reported position to be reported again.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.01`
+    pub distance_threshold: Scalar,
+    /// The angle in radians that a body must rotate from its last reported rotation to be reported again.
+    ///
+    /// Default: `0.01`
+    pub angle_threshold: Scalar,
+}
+
+impl Default for BodyMovedConfig {
+    fn default() -> Self {
+        Self {
+            distance_threshold: 0.01,
+            angle_threshold: 0.01,
+        }
+    }
+}
+
+/// An event sent by the [`BodyMovedPlugin`] when a rigid body moved more than
+/// the thresholds of the [`BodyMovedConfig`] since it was last reported.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BodyMoved {
+    /// The rigid body that moved.
+    pub entity: Entity,
+    /// The position of the body when it was last reported, or when it was added.
+    pub previous_position: Vector,
+    /// The rotation of the body when it was last reported, or when it was added.
+    pub previous_rotation: Rotation,
+    /// The position of the body at the end of this physics step.
+    pub position: Vector,
+    /// The rotation of the body at the end of this physics step.
+    pub rotation: Rotation,
+}
+
+impl BodyMoved {
+    /// Returns the distance that the body moved.
+    pub fn distance(&self) -> Scalar {
+        self.position.distance(self.previous_position)
+    }
+
+    /// Returns the angle in radians that the body rotated.
+    pub fn angle(&self) -> Scalar {
+        rotation_angle(self.previous_rotation, self.rotation)
+    }
+}
+
+#[cfg(feature = "2d")]
+fn rotation_angle(from: Rotation, to: Rotation) -> Scalar {
+    from.angle_between(to).abs()
+}
+
+#[cfg(feature = "3d")]
+fn rotation_angle(from: Rotation, to: Rotation) -> Scalar {
+    from.0.angle_between(to.0)
+}
+
+/// Sends [`BodyMoved`] events for bodies that moved past the thresholds since they were last reported.
+#[allow(clippy::type_complexity)]
+fn send_body_moved_events(
+    bodies: Query<
+        (Entity, &Position, &Rotation),
+        (With<RigidBody>, Or<(Changed<Position>, Changed<Rotation>)>),
+    >,
+    mut removed_bodies: RemovedComponents<RigidBody>,
+    config: Res<BodyMovedConfig>,
+    length_unit: Res<PhysicsLengthUnit>,
+    mut reported_poses: Local<EntityHashMap<(Vector, Rotation)>>,
+    mut body_moved_ev_writer: EventWriter<BodyMoved>,
+) {
+    let distance_threshold = config.distance_threshold * length_unit.0;
+
+    // Forget removed bodies.
+    for entity in removed_bodies.read() {
+        reported_poses.remove(&entity);
+    }
+
+    for (entity, position, rotation) in &bodies {
+        let Some(&(previous_position, previous_rotation)) = reported_poses.get(&entity) else {
+            // The body was just added. Start tracking it from its current pose.
+            reported_poses.insert(entity, (position.0, *rotation));
+            continue;
+        };
+
+        let event = BodyMoved {
+            entity,
+            previous_position,
+            previous_rotation,
+            position: position.0,
+            rotation: *rotation,
+        };
+
+        // Only update the reported pose when the body is reported,
+        // so that slow movement accumulates until it exceeds the thresholds.
+        if event.distance() > distance_threshold || event.angle() > config.angle_threshold {
+            reported_poses.insert(entity, (position.0, *rotation));
+            body_moved_ev_writer.send(event);
+        }
+    }
+}
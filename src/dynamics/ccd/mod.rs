@@ -128,11 +128,13 @@
 //! the collision during the next frame.
 //!
 //! There are two variants of swept CCD: [`Linear`](SweepMode::Linear)
-//! and [`NonLinear`](SweepMode::Linear). The difference between the two
+//! and [`NonLinear`](SweepMode::NonLinear). The difference between the two
 //! is that [`Linear`](SweepMode::Linear) only considers translational motion,
 //! so bodies can still pass through objects that are spinning at high speeds,
 //! while [`NonLinear`](SweepMode::NonLinear) also considers rotational motion,
-//! but is more expensive.
+//! but is more expensive. This makes [`NonLinear`](SweepMode::NonLinear) suitable for
+//! long, thin bodies that rotate quickly, like swords or propellers, which can otherwise
+//! pass through objects between two frames even when using speculative collision.
 //!
 //! <svg width="300" height="350" viewBox="0 0 300 350" fill="none" xmlns="http://www.w3.org/2000/svg">
 //!     <rect x="141" y="1" width="18" height="298" fill="#64C850" stroke="black" stroke-width="2"/>
@@ -268,6 +270,11 @@ pub struct SweptCcdSet;
 /// by default, meaning that the margin can extend infinitely. The margin can be bounded
 /// for individual entities using this component in case speculative contacts are causing
 /// issues like the ones outlined [here](self#caveats-of-speculative-collision).
+/// For example, fast projectiles can use a large margin, while small debris can use
+/// a small margin to prevent ghost collisions.
+///
+/// The component can be added to rigid bodies and colliders. The margin of a collider
+/// takes precedence over the margin of the rigid body it is attached to.
 ///
 /// See the [module-level documentation](self) for information about
 /// Speculative Conllision and Continuous Collision Detection in general.
@@ -478,6 +485,9 @@ pub enum SweepMode {
     /// stopping the bodies at the first time of impact.
     ///
     /// This mode considers both translational and rotational motion.
+    /// If a body rotates by a large angle during a single frame, its AABB is expanded
+    /// to cover the whole rotation so that no potential collisions are missed.
+    ///
     /// For the cheaper version that only considers translational motion,
     /// consider using [`SweepMode::Linear`].
     #[default]
@@ -0,0 +1,929 @@
+//! Kinematic character controllers that slide along obstacles, step over low obstacles like stairs,
+//! crouch, and swim.
+//!
+//! See [`CharacterControllerPlugin`].
+
+use crate::prelude::*;
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// A plugin for moving kinematic [`CharacterController`]s.
+///
+/// Each physics step, the movement of the character is resolved with shape casts of its [`Collider`]
+/// against the world. The character slides along walls and slopes that are too steep to walk on,
+/// stays attached to the ground when walking down slopes and small ledges, and automatically
+/// steps over low obstacles like stairs. The resolved movement is applied to the [`LinearVelocity`]
+/// of the kinematic body, so the character still pushes dynamic bodies through contacts.
+///
+/// When the character is standing on a moving body, like a kinematic platform,
+/// the velocity of the body at the contact point is added to the movement of the character.
+///
+/// The capsule collider of the character can be resized safely for crouching
+/// by setting the [`target_height`](CharacterController::target_height).
+///
+/// Characters that are submerged deep enough in a [`FluidVolume`] [swim](CharacterController::is_swimming).
+/// While swimming, gravity is counteracted by the buoyancy of the fluid, the velocity is damped by its drag,
+/// and the character can climb out of the fluid onto ledges near the surface.
+///
+/// [Sensors](Sensor) are ignored by the shape casts, so they don't block the movement of the character.
+///
+/// Colliders can be made [slippery](Walkable::Slippery) or [non-walkable](Walkable::No) with the [`Walkable`]
+/// component. Characters can't stand on such surfaces regardless of their slope, and slide off of them instead.
+///
+/// The movement is resolved by [`resolve_movement`], which can also be called manually,
+/// for example for client-side prediction and rollback in networked games.
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+/// The [`SpatialQueryPlugin`] is required for the shape casts.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             CharacterControllerPlugin::default(),
+///         ))
+///         .add_systems(Startup, setup)
+///         .add_systems(Update, walk)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         CharacterController::default().with_max_step_height(0.3),
+///         Collider::capsule(0.4, 1.0),
+///     ));
+/// }
+///
+/// fn walk(keyboard: Res<ButtonInput<KeyCode>>, mut characters: Query<&mut CharacterController>) {
+///     for mut character in &mut characters {
+///         let direction = keyboard.pressed(KeyCode::KeyD) as u8 as Scalar
+///             - keyboard.pressed(KeyCode::KeyA) as u8 as Scalar;
+///         character.movement = Vector::X * direction * 4.0;
+///
+///         // Jump.
+///         if character.is_grounded() && keyboard.just_pressed(KeyCode::Space) {
+///             character.velocity = Vector::Y * 6.0;
+///         }
+///
+///         // Crouch. The character stands back up once there is room above it.
+///         let height = if keyboard.pressed(KeyCode::KeyC) { 1.0 } else { 1.8 };
+///         character.target_height = Some(height);
+///     }
+/// }
+/// ```
+pub struct CharacterControllerPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl CharacterControllerPlugin {
+    /// Creates a [`CharacterControllerPlugin`] with the schedule that the characters are moved in.
+    ///
+    /// The default schedule is [`PhysicsSchedule`].
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for CharacterControllerPlugin {
+    fn default() -> Self {
+        Self::new(PhysicsSchedule)
+    }
+}
+
+impl Plugin for CharacterControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(
+            CharacterController,
+            CharacterGround,
+            CharacterSwimming,
+            FluidVolume,
+            Walkable,
+            SurfaceClass,
+        )>();
+
+        app.add_systems(
+            self.schedule.intern(),
+            move_characters.in_set(PhysicsStepSet::First),
+        );
+    }
+}
+
+/// A component for a kinematic character, moved by the [`CharacterControllerPlugin`].
+///
+/// The entity should have a [`Collider`], which is used for the shape casts. A [kinematic](RigidBody::Kinematic)
+/// rigid body is added automatically.
+///
+/// The character is controlled by setting the desired [`movement`](Self::movement), typically in a system
+/// based on user input. Gravity accumulates in the [`velocity`](Self::velocity) while the character is in the air,
+/// and jumps can be performed by setting the `velocity` in the [`up`](Self::up) direction.
+///
+/// See the [`CharacterControllerPlugin`] for an example.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[require(RigidBody(character_rigid_body))]
+pub struct CharacterController {
+    /// The desired movement velocity of the character, typically set based on user input.
+    pub movement: Vector,
+    /// The velocity of the character that is not controlled by the [`movement`](Self::movement),
+    /// like gravity and jumps.
+    ///
+    /// While the character is [grounded](Self::is_grounded), velocity into the ground is removed.
+    pub velocity: Vector,
+    /// The up direction of the character, used for determining which surfaces can be walked on.
+    ///
+    /// Default: `Dir::Y`
+    pub up: Dir,
+    /// The scaling factor for the [`Gravity`] applied to the character while it is in the air.
+    ///
+    /// Default: `1.0`
+    pub gravity_scale: Scalar,
+    /// The maximum angle in radians between the [`up`](Self::up) direction and the normal of a surface
+    /// for the surface to be walkable. Steeper surfaces are treated as walls.
+    ///
+    /// Default: `PI / 4.0` (45 degrees)
+    pub max_slope_angle: Scalar,
+    /// The maximum height of obstacles that the character automatically steps over while grounded.
+    /// Zero disables auto-stepping.
+    ///
+    /// Default: `0.25`
+    pub max_step_height: Scalar,
+    /// The minimum depth of the walkable surface on top of an obstacle for the character to step onto it.
+    ///
+    /// Default: `0.1`
+    pub min_step_depth: Scalar,
+    /// If `true`, the character can step onto [dynamic](RigidBody::Dynamic) bodies.
+    /// Otherwise, dynamic bodies are treated as walls and pushed instead.
+    ///
+    /// Default: `false`
+    pub step_on_dynamic_bodies: bool,
+    /// The distance kept between the collider of the character and other colliders,
+    /// which prevents the character from getting stuck in the geometry.
+    ///
+    /// Default: `0.01`
+    pub skin_width: Scalar,
+    /// The maximum distance that the character is pulled down to stay on the ground,
+    /// for example when walking down slopes or stairs.
+    ///
+    /// Default: `0.2`
+    pub ground_snap_distance: Scalar,
+    /// The maximum number of times that the movement is redirected along the obstacles hit in a single step.
+    ///
+    /// Default: `4`
+    pub max_slide_iterations: usize,
+    /// The desired total height of the capsule collider of the character,
+    /// or `None` to keep the collider unchanged. This can be used for crouching.
+    ///
+    /// When the height changes, the capsule is resized such that the bottom of the character stays in place.
+    /// Before growing, the space above the character is checked with a shape cast, and the capsule
+    /// only grows as far as there is room, so the character can't stand up into the geometry above it.
+    /// The capsule keeps growing in later steps once there is room.
+    ///
+    /// The collider must be an unscaled [capsule](Collider::capsule) aligned with the [`up`](Self::up) direction.
+    /// Other colliders are not resized.
+    ///
+    /// Default: `None`
+    pub target_height: Option<Scalar>,
+    /// The fraction of the height of the character that must be submerged in a [`FluidVolume`]
+    /// for the character to [swim](Self::is_swimming).
+    ///
+    /// Default: `0.5`
+    pub swim_submersion: Scalar,
+    /// The maximum height of ledges that a swimming character at the surface of a [`FluidVolume`]
+    /// climbs onto when moving against them, for getting out of the fluid.
+    ///
+    /// Default: `0.6`
+    pub max_swim_exit_height: Scalar,
+    ground: Option<CharacterGround>,
+    swimming: Option<CharacterSwimming>,
+    height_blocked: bool,
+}
+
+fn character_rigid_body() -> RigidBody {
+    RigidBody::Kinematic
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            movement: Vector::ZERO,
+            velocity: Vector::ZERO,
+            up: Dir::Y,
+            gravity_scale: 1.0,
+            max_slope_angle: PI / 4.0,
+            max_step_height: 0.25,
+            min_step_depth: 0.1,
+            step_on_dynamic_bodies: false,
+            skin_width: 0.01,
+            ground_snap_distance: 0.2,
+            max_slide_iterations: 4,
+            target_height: None,
+            swim_submersion: 0.5,
+            max_swim_exit_height: 0.6,
+            ground: None,
+            swimming: None,
+            height_blocked: false,
+        }
+    }
+}
+
+impl CharacterController {
+    /// Sets the up direction of the character.
+    pub fn with_up(mut self, up: Dir) -> Self {
+        self.up = up;
+        self
+    }
+
+    /// Sets the maximum angle in radians between the up direction and the normal of a walkable surface.
+    pub fn with_max_slope_angle(mut self, max_angle: Scalar) -> Self {
+        self.max_slope_angle = max_angle;
+        self
+    }
+
+    /// Sets the maximum height of obstacles that the character automatically steps over.
+    /// Zero disables auto-stepping.
+    pub fn with_max_step_height(mut self, max_height: Scalar) -> Self {
+        self.max_step_height = max_height;
+        self
+    }
+
+    /// Sets the distance kept between the collider of the character and other colliders.
+    pub fn with_skin_width(mut self, skin_width: Scalar) -> Self {
+        self.skin_width = skin_width;
+        self
+    }
+
+    /// Sets the maximum distance that the character is pulled down to stay on the ground.
+    pub fn with_ground_snap_distance(mut self, distance: Scalar) -> Self {
+        self.ground_snap_distance = distance;
+        self
+    }
+
+    /// Sets the desired total height of the capsule collider of the character.
+    pub fn with_target_height(mut self, height: Scalar) -> Self {
+        self.target_height = Some(height);
+        self
+    }
+
+    /// Returns `true` if the character is standing on a walkable surface.
+    pub fn is_grounded(&self) -> bool {
+        self.ground.is_some()
+    }
+
+    /// Returns the ground that the character is standing on, or `None` if the character is in the air.
+    pub fn ground(&self) -> Option<&CharacterGround> {
+        self.ground.as_ref()
+    }
+
+    /// Returns `true` if the character is swimming in a [`FluidVolume`].
+    pub fn is_swimming(&self) -> bool {
+        self.swimming.is_some()
+    }
+
+    /// Returns the state of the character in the [`FluidVolume`] that it is swimming in,
+    /// or `None` if the character is not swimming.
+    pub fn swimming(&self) -> Option<&CharacterSwimming> {
+        self.swimming.as_ref()
+    }
+
+    /// Returns `true` if the capsule collider of the character couldn't grow to the
+    /// [`target_height`](Self::target_height) in the last physics step because of the geometry above it,
+    /// for example when crouching under a low ceiling.
+    pub fn is_height_blocked(&self) -> bool {
+        self.height_blocked
+    }
+
+    /// Classifies a surface with the given normal and [`Walkable`] flag
+    /// based on the [`up`](Self::up) direction and [`max_slope_angle`](Self::max_slope_angle) of the character.
+    pub fn classify_surface(&self, normal: Vector, walkable: Walkable) -> SurfaceClass {
+        SurfaceClass::classify(
+            normal,
+            walkable,
+            self.up.adjust_precision(),
+            self.max_slope_angle,
+        )
+    }
+
+    /// Returns `true` if a surface with the given normal and [`Walkable`] flag can be walked on.
+    pub fn is_walkable(&self, normal: Vector, walkable: Walkable) -> bool {
+        self.classify_surface(normal, walkable) == SurfaceClass::Walkable
+    }
+}
+
+/// Determines how [`CharacterController`]s treat the surface of a collider.
+///
+/// Colliders without this component are treated as [`Walkable::Yes`].
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub enum Walkable {
+    /// The surface can be walked on if it is not steeper than the
+    /// [`max_slope_angle`](CharacterController::max_slope_angle).
+    #[default]
+    Yes,
+    /// The surface has no traction. Characters slide along it and can't stand
+    /// or jump on it, regardless of the slope.
+    Slippery,
+    /// The surface can never be walked on, regardless of the slope.
+    No,
+}
+
+/// The classification of a surface from the point of view of a [`CharacterController`].
+///
+/// See [`CharacterController::classify_surface`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum SurfaceClass {
+    /// A surface that the character can walk on.
+    Walkable,
+    /// A [walkable](Walkable::Yes) surface that is steeper than the
+    /// [`max_slope_angle`](CharacterController::max_slope_angle).
+    TooSteep,
+    /// A surface marked as [`Walkable::Slippery`].
+    Slippery,
+    /// A surface marked as [`Walkable::No`].
+    Forbidden,
+}
+
+impl SurfaceClass {
+    /// Classifies a surface based on its world-space normal, its [`Walkable`] flag,
+    /// and the up direction and maximum slope angle in radians of the character.
+    pub fn classify(
+        normal: Vector,
+        walkable: Walkable,
+        up: Vector,
+        max_slope_angle: Scalar,
+    ) -> Self {
+        match walkable {
+            Walkable::Yes if normal.dot(up) >= max_slope_angle.cos() => Self::Walkable,
+            Walkable::Yes => Self::TooSteep,
+            Walkable::Slippery => Self::Slippery,
+            Walkable::No => Self::Forbidden,
+        }
+    }
+}
+
+/// The ground that a [`CharacterController`] is standing on.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct CharacterGround {
+    /// The entity of the collider that the character is standing on.
+    pub entity: Entity,
+    /// The contact point on the ground in world space.
+    pub point: Vector,
+    /// The surface normal of the ground at the contact point in world space.
+    pub normal: Vector,
+}
+
+impl CharacterGround {
+    fn from_hit(hit: &ShapeHitData) -> Self {
+        Self {
+            entity: hit.entity,
+            point: hit.point1,
+            normal: hit.normal1,
+        }
+    }
+}
+
+/// The state of a [`CharacterController`] swimming in a [`FluidVolume`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct CharacterSwimming {
+    /// The entity of the [`FluidVolume`] that the character is swimming in.
+    pub entity: Entity,
+    /// The fraction of the height of the character that is submerged, between `0.0` and `1.0`.
+    pub submersion: Scalar,
+    /// The point on the surface of the fluid directly above the center of the character in world space,
+    /// or `None` if the character is fully submerged.
+    pub surface: Option<Vector>,
+}
+
+impl CharacterSwimming {
+    /// Returns `true` if the character is at the surface of the fluid, and not fully submerged.
+    pub fn is_at_surface(&self) -> bool {
+        self.surface.is_some()
+    }
+}
+
+/// A component for a volume of fluid, like water, that [`CharacterController`]s can swim in.
+///
+/// The volume is defined by the [`Collider`] of the entity, which is made a [`Sensor`].
+/// The surface of the fluid is the top of the collider relative to the [`up`](CharacterController::up)
+/// direction of the character.
+///
+/// See the [`CharacterControllerPlugin`] for more information.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[require(Sensor)]
+pub struct FluidVolume {
+    /// The upward force of the fluid on a fully submerged character, relative to the gravity of the character.
+    ///
+    /// A partially submerged character is pushed up in proportion to its submersion, so with a buoyancy
+    /// above `1.0`, characters float at the surface with a submersion of `1.0 / buoyancy`.
+    /// With a buoyancy below `1.0`, characters slowly sink.
+    ///
+    /// Default: `1.2`
+    pub buoyancy: Scalar,
+    /// The linear drag of the fluid, which damps the [`velocity`](CharacterController::velocity)
+    /// of swimming characters.
+    ///
+    /// Default: `2.0`
+    pub drag: Scalar,
+}
+
+impl Default for FluidVolume {
+    fn default() -> Self {
+        Self {
+            buoyancy: 1.2,
+            drag: 2.0,
+        }
+    }
+}
+
+impl FluidVolume {
+    /// Creates a new [`FluidVolume`] with the given buoyancy and drag.
+    pub fn new(buoyancy: Scalar, drag: Scalar) -> Self {
+        Self { buoyancy, drag }
+    }
+}
+
+/// The input for [`resolve_movement`], describing the character and its environment.
+///
+/// The environment is accessed through the given functions, so that the movement can be resolved
+/// without access to the ECS, for example for client-side prediction and rollback.
+pub struct MovementInput<'a> {
+    /// The collider of the character.
+    pub collider: &'a Collider,
+    /// The position of the character.
+    pub position: Vector,
+    /// The rotation of the character.
+    pub rotation: Rotation,
+    /// The gravity applied to the character, before it is scaled by the [`gravity_scale`](CharacterController::gravity_scale).
+    pub gravity: Vector,
+    /// The duration of the step in seconds.
+    pub delta_secs: Scalar,
+    /// The velocity of the [ground](CharacterController::ground) at the contact point,
+    /// like the velocity of a moving platform. Zero for static ground.
+    pub ground_velocity: Vector,
+    /// The filter for the shape casts and intersection tests. It should exclude the character itself.
+    pub filter: SpatialQueryFilter,
+    /// A predicate for the colliders that the character interacts with. Colliders for which
+    /// the predicate returns `false` are ignored, like other colliders attached to the character.
+    pub predicate: &'a dyn Fn(Entity) -> bool,
+    /// Returns `true` if the given collider is attached to a [dynamic](RigidBody::Dynamic) body.
+    pub is_dynamic: &'a dyn Fn(Entity) -> bool,
+    /// Returns the [`Walkable`] flag of the given collider. Colliders without the component
+    /// should be treated as [`Walkable::Yes`].
+    pub walkable: &'a dyn Fn(Entity) -> Walkable,
+    /// Returns the [`FluidVolume`] of the given collider along with its collider, position, and rotation,
+    /// or `None` if the collider is not a fluid volume.
+    #[allow(clippy::type_complexity)]
+    pub fluid_volume:
+        &'a dyn Fn(Entity) -> Option<(&'a FluidVolume, &'a Collider, Position, Rotation)>,
+}
+
+/// The result of [`resolve_movement`].
+#[derive(Clone, Debug)]
+pub struct MovementResult {
+    /// The resized collider of the character, or `None` if the collider was not resized.
+    pub collider: Option<Collider>,
+    /// The position of the character after resizing the collider. The collider is resized
+    /// such that the bottom of the character stays in place, which moves its center.
+    pub position: Vector,
+    /// The linear velocity that moves the character to its resolved position by the end of the step.
+    pub linear_velocity: Vector,
+    /// The new [`velocity`](CharacterController::velocity) of the character.
+    pub velocity: Vector,
+    /// The ground that the character is standing on after the movement.
+    pub ground: Option<CharacterGround>,
+    /// The swimming state of the character.
+    pub swimming: Option<CharacterSwimming>,
+    /// Whether the collider couldn't grow to the [`target_height`](CharacterController::target_height).
+    pub height_blocked: bool,
+}
+
+impl MovementResult {
+    /// Updates the state of the given [`CharacterController`] with the result.
+    ///
+    /// The collider, position, and velocity of the body are not changed.
+    pub fn apply_to(&self, controller: &mut CharacterController) {
+        controller.velocity = self.velocity;
+        controller.ground = self.ground;
+        controller.swimming = self.swimming;
+        controller.height_blocked = self.height_blocked;
+    }
+}
+
+/// Resolves the movement of [`CharacterController`]s with shape casts, and applies it
+/// to the [`LinearVelocity`] of the characters.
+#[allow(clippy::type_complexity)]
+fn move_characters(
+    mut characters: Query<
+        (
+            Entity,
+            &mut CharacterController,
+            &mut Collider,
+            &mut Position,
+            &Rotation,
+            &mut LinearVelocity,
+        ),
+        RigidBodyActiveFilter,
+    >,
+    bodies: Query<
+        (
+            &RigidBody,
+            &Position,
+            &Rotation,
+            &LinearVelocity,
+            &AngularVelocity,
+            &ComputedCenterOfMass,
+        ),
+        Without<CharacterController>,
+    >,
+    fluids: Query<(&FluidVolume, &Collider, &Position, &Rotation), Without<CharacterController>>,
+    sensors: Query<(), With<Sensor>>,
+    walkables: Query<&Walkable>,
+    collider_parents: Query<&ColliderParent>,
+    query_pipeline: Res<SpatialQueryPipeline>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs <= 0.0 {
+        return;
+    }
+
+    let body_of = |collider: Entity| {
+        collider_parents
+            .get(collider)
+            .ok()
+            .and_then(|parent| bodies.get(parent.get()).ok())
+    };
+    let is_dynamic = |collider: Entity| body_of(collider).is_some_and(|(rb, ..)| rb.is_dynamic());
+    let walkable = |collider: Entity| walkables.get(collider).copied().unwrap_or_default();
+    let fluid_volume = |collider: Entity| {
+        fluids
+            .get(collider)
+            .ok()
+            .map(|(fluid, collider, position, rotation)| (fluid, collider, *position, *rotation))
+    };
+
+    for (entity, mut controller, mut collider, mut position, rotation, mut lin_vel) in
+        &mut characters
+    {
+        // The velocity of the body that the character is standing on.
+        let mut ground_velocity = Vector::ZERO;
+        if let Some(ground) = controller.ground {
+            if let Some((rb, body_pos, body_rot, body_lin_vel, body_ang_vel, center_of_mass)) =
+                body_of(ground.entity)
+            {
+                if !rb.is_static() {
+                    let r = ground.point - (body_pos.0 + *body_rot * center_of_mass.0);
+                    #[cfg(feature = "2d")]
+                    {
+                        ground_velocity = body_lin_vel.0 + body_ang_vel.0 * r.perp();
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        ground_velocity = body_lin_vel.0 + body_ang_vel.0.cross(r);
+                    }
+                }
+            }
+        }
+
+        // Ignore the colliders of the character itself and sensors.
+        let predicate = |collider: Entity| {
+            !sensors.contains(collider)
+                && !collider_parents
+                    .get(collider)
+                    .is_ok_and(|p| p.get() == entity)
+        };
+
+        let result = resolve_movement(
+            MovementInput {
+                collider: &collider,
+                position: position.0,
+                rotation: *rotation,
+                gravity: gravity.0,
+                delta_secs,
+                ground_velocity,
+                filter: SpatialQueryFilter::default().with_excluded_entities([entity]),
+                predicate: &predicate,
+                is_dynamic: &is_dynamic,
+                walkable: &walkable,
+                fluid_volume: &fluid_volume,
+            },
+            &controller,
+            &query_pipeline,
+        );
+
+        result.apply_to(&mut controller);
+        if let Some(resized) = result.collider {
+            *collider = resized;
+        }
+        position.0 = result.position;
+        lin_vel.0 = result.linear_velocity;
+    }
+}
+
+/// Resolves the movement of a [`CharacterController`] for a single step.
+///
+/// This is the core of the [`CharacterControllerPlugin`]. It doesn't access any resources or components,
+/// so it can also be called outside of the normal schedule, for example for client-side prediction
+/// and rollback in networked games. For the same input, state, and spatial query pipeline,
+/// the result is always the same.
+///
+/// The `state` is not modified. The result can be applied to it with [`MovementResult::apply_to`].
+/// If `delta_secs` is not positive, the character doesn't move, and the result keeps the current state.
+pub fn resolve_movement(
+    input: MovementInput,
+    state: &CharacterController,
+    query_pipeline: &SpatialQueryPipeline,
+) -> MovementResult {
+    let MovementInput {
+        collider,
+        position: mut start,
+        rotation,
+        gravity,
+        delta_secs,
+        ground_velocity,
+        filter,
+        predicate,
+        is_dynamic,
+        walkable,
+        fluid_volume,
+    } = input;
+
+    if delta_secs <= 0.0 {
+        return MovementResult {
+            collider: None,
+            position: start,
+            linear_velocity: Vector::ZERO,
+            velocity: state.velocity,
+            ground: state.ground,
+            swimming: state.swimming,
+            height_blocked: state.height_blocked,
+        };
+    }
+
+    let up_dir = state.up;
+    let down_dir = -up_dir;
+    let up = up_dir.adjust_precision();
+    let skin = state.skin_width;
+
+    let config = ShapeCastConfig {
+        ignore_origin_penetration: true,
+        ..default()
+    };
+
+    // Resize the capsule towards the target height, keeping the bottom of the character in place.
+    let mut resized = None;
+    let mut height_blocked = false;
+    if let Some(target_height) = state.target_height {
+        if let Some(capsule) = collider.shape().as_capsule() {
+            let radius = capsule.radius;
+            let height = capsule.height() + 2.0 * radius;
+            let mut growth = target_height.max(2.0 * radius) - height;
+
+            // Only grow as far as there is room above the character.
+            if growth > 0.0 {
+                if let Some(hit) = query_pipeline.cast_shape_predicate(
+                    collider,
+                    start,
+                    RotationValue::from(rotation),
+                    up_dir,
+                    &config.clone().with_max_distance(growth + skin),
+                    &filter,
+                    predicate,
+                ) {
+                    growth = (hit.distance - skin).clamp(0.0, growth);
+                    height_blocked = true;
+                }
+            }
+
+            if growth.abs() > Scalar::EPSILON {
+                let a = Vector::from(capsule.segment.a.coords);
+                let b = Vector::from(capsule.segment.b.coords);
+                let center = (a + b) * 0.5;
+                let half_segment =
+                    (b - a).normalize_or(Vector::Y) * ((height + growth) * 0.5 - radius);
+                resized = Some(Collider::capsule_endpoints(
+                    radius,
+                    center - half_segment,
+                    center + half_segment,
+                ));
+                start += up * growth * 0.5;
+            }
+        }
+    }
+    let collider = resized.as_ref().unwrap_or(collider);
+
+    // Find the fluid volume that the character is submerged in the deepest.
+    let aabb = collider.aabb(start, rotation);
+    let half_height = ((aabb.max - aabb.min) * 0.5).dot(up.abs());
+    let top = (aabb.min + aabb.max) * 0.5 + up * half_height;
+    let swimming = query_pipeline
+        .shape_intersections(collider, start, RotationValue::from(rotation), &filter)
+        .into_iter()
+        .filter_map(|fluid_entity| {
+            let (fluid, fluid_collider, fluid_pos, fluid_rot) = fluid_volume(fluid_entity)?;
+
+            // Find the surface by casting a ray down from the top of the character.
+            // The ray starts inside of the fluid if the character is fully submerged.
+            let (depth, _) =
+                fluid_collider.cast_ray(fluid_pos, fluid_rot, top, -up, 2.0 * half_height, true)?;
+            let swimming = CharacterSwimming {
+                entity: fluid_entity,
+                submersion: (1.0 - depth / (2.0 * half_height)).clamp(0.0, 1.0),
+                surface: (depth > 0.0).then(|| top - up * depth),
+            };
+            Some((swimming, *fluid))
+        })
+        .max_by(|(a, _), (b, _)| a.submersion.total_cmp(&b.submersion))
+        .filter(|(swimming, _)| swimming.submersion >= state.swim_submersion);
+    let at_surface = swimming.is_some_and(|(swimming, _)| swimming.is_at_surface());
+
+    let max_step_height = state.max_step_height;
+    let min_step_depth = state.min_step_depth;
+    let is_walkable = |hit: &ShapeHitData| state.is_walkable(hit.normal1, walkable(hit.entity));
+
+    let cast = |origin: Vector, direction: Dir, max_distance: Scalar| {
+        query_pipeline.cast_shape_predicate(
+            collider,
+            origin,
+            RotationValue::from(rotation),
+            direction,
+            &config.clone().with_max_distance(max_distance),
+            &filter,
+            predicate,
+        )
+    };
+
+    let can_step_on = |collider: Entity| state.step_on_dynamic_bodies || !is_dynamic(collider);
+
+    // Steps over an obstacle with the given remaining movement, and returns the new position
+    // and the ground on top of the obstacle.
+    let step_up = |current: Vector, remaining: Vector, max_step_height: Scalar| {
+        let horizontal = remaining - up * remaining.dot(up);
+        let (forward_dir, forward_distance) = direction_and_length(horizontal)?;
+        let forward = forward_dir.adjust_precision();
+
+        // Move up by the step height, or until hitting a ceiling.
+        let up_distance = cast(current, up_dir, max_step_height + skin)
+            .map_or(max_step_height, |hit| (hit.distance - skin).max(0.0));
+        let raised = current + up * up_distance;
+
+        // The top of the obstacle must be deep enough and walkable.
+        let probe_distance = forward_distance.max(min_step_depth);
+        if cast(raised, forward_dir, probe_distance + skin).is_some() {
+            return None;
+        }
+        let probe_hit = cast(
+            raised + forward * probe_distance,
+            down_dir,
+            up_distance + skin,
+        )?;
+        if !is_walkable(&probe_hit) || !can_step_on(probe_hit.entity) {
+            return None;
+        }
+
+        // Move forward and back down onto the obstacle.
+        let advanced = raised + forward * forward_distance;
+        let drop = cast(advanced, down_dir, up_distance + skin)
+            .map_or(up_distance, |hit| (hit.distance - skin).max(0.0));
+        let landed = advanced - up * drop;
+
+        ((landed - current).dot(up) > Scalar::EPSILON)
+            .then(|| (landed, CharacterGround::from_hit(&probe_hit)))
+    };
+
+    // Gravity and the velocity of the body that the character is standing on.
+    let mut velocity = state.velocity;
+    let mut platform_velocity = Vector::ZERO;
+    let was_grounded = state.ground.is_some();
+
+    if let Some((swimming, fluid)) = swimming {
+        // Buoyancy counteracts gravity in proportion to the submersion of the character.
+        let buoyancy = 1.0 - fluid.buoyancy * swimming.submersion;
+        velocity += gravity * state.gravity_scale * buoyancy * delta_secs;
+        velocity /= 1.0 + fluid.drag * delta_secs;
+    } else if was_grounded {
+        platform_velocity = ground_velocity;
+    } else {
+        velocity += gravity * state.gravity_scale * delta_secs;
+    }
+
+    let mut current = start;
+    let mut remaining = (state.movement + velocity + platform_velocity) * delta_secs;
+    let mut step_ground = None;
+
+    // Move and slide along the obstacles.
+    for _ in 0..state.max_slide_iterations {
+        let Some((direction, distance)) = direction_and_length(remaining) else {
+            break;
+        };
+
+        let Some(hit) = cast(current, direction, distance + skin) else {
+            current += remaining;
+            break;
+        };
+
+        let travel = (hit.distance - skin).max(0.0);
+        current += remaining * (travel / distance);
+        remaining *= 1.0 - travel / distance;
+
+        let normal = hit.normal1;
+        if is_walkable(&hit) {
+            // Slide along walkable surfaces.
+            remaining -= normal * remaining.dot(normal);
+            continue;
+        }
+
+        // Step over low obstacles that are too steep to walk on,
+        // or climb out of the fluid onto ledges near the surface.
+        let step_height = if at_surface {
+            max_step_height.max(state.max_swim_exit_height)
+        } else if was_grounded {
+            max_step_height
+        } else {
+            0.0
+        };
+        if step_height > 0.0 && can_step_on(hit.entity) {
+            if let Some((stepped, ground)) = step_up(current, remaining, step_height) {
+                current = stepped;
+                step_ground = Some(ground);
+                break;
+            }
+        }
+
+        // Slide along walls, but don't climb them while grounded.
+        remaining -= normal * remaining.dot(normal);
+        let climb = remaining.dot(up);
+        if was_grounded && climb > 0.0 {
+            remaining -= up * climb;
+        }
+    }
+
+    // Find the ground, and snap to it if the character was already grounded.
+    let mut ground = None;
+    if velocity.dot(up) <= 0.0 {
+        let snap_distance = if was_grounded && swimming.is_none() {
+            state.ground_snap_distance
+        } else {
+            0.0
+        };
+        if let Some(hit) = cast(current, down_dir, snap_distance + 2.0 * skin) {
+            if is_walkable(&hit) {
+                current -= up * (hit.distance - skin).max(0.0);
+                ground = Some(CharacterGround::from_hit(&hit));
+            }
+        }
+        ground = ground.or(step_ground);
+    }
+
+    // Remove velocity into the ground.
+    if ground.is_some() {
+        let down_speed = velocity.dot(up);
+        if down_speed < 0.0 {
+            velocity -= up * down_speed;
+        }
+    }
+
+    MovementResult {
+        collider: resized,
+        position: start,
+        linear_velocity: (current - start) / delta_secs,
+        velocity,
+        ground,
+        swimming: swimming.map(|(swimming, _)| swimming),
+        height_blocked,
+    }
+}
+
+/// Splits the given vector into a direction and a length, or returns `None` if the vector is too short.
+fn direction_and_length(vector: Vector) -> Option<(Dir, Scalar)> {
+    let length = vector.length();
+    (length > Scalar::EPSILON).then(|| (Dir::new_unchecked((vector / length).f32()), length))
+}
@@ -0,0 +1,257 @@
+//! Large crowds of simple kinematic agents, like the units of RTS games.
+//!
+//! See [`CrowdPlugin`].
+
+use crate::{collision::broad_phase::BroadPhaseSet, prelude::*};
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::*,
+};
+
+/// A plugin for moving large numbers of kinematic [`CrowdAgent`]s cheaply,
+/// for example the units of RTS games or the people of crowd simulations.
+///
+/// Unlike the [`CharacterControllerPlugin`], which resolves the movement of each character with
+/// several shape casts, agents only use a single ray cast per physics step to find the ground below them.
+/// The ground probes of all agents are run in parallel. Agents don't slide along walls or step over obstacles,
+/// so they are expected to be steered by navigation, like a navigation mesh or a flow field.
+///
+/// All agents share the [`Collider`] of the [`CrowdConfig`], so the shape is only stored once.
+/// By default, contacts are only generated between agents and [dynamic](RigidBody::Dynamic) bodies,
+/// so agents can still push dynamic bodies, but contacts against the level and other agents
+/// don't cost anything. See [`CrowdConfig::dynamic_contacts_only`].
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+/// The [`SpatialQueryPlugin`] is required for the ground probes.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), CrowdPlugin))
+///         .add_systems(Startup, setup)
+///         .add_systems(Update, steer)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     for i in 0..1000 {
+///         let x = (i % 40) as Scalar * 1.5;
+///         let y = (i / 40) as Scalar * 1.5;
+///         commands.spawn((CrowdAgent::default(), Position(Vector::X * x + Vector::Y * y)));
+///     }
+/// }
+///
+/// fn steer(mut agents: Query<&mut CrowdAgent>) {
+///     for mut agent in &mut agents {
+///         agent.movement = Vector::X * 2.0;
+///     }
+/// }
+/// ```
+pub struct CrowdPlugin;
+
+impl Plugin for CrowdPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CrowdConfig>()
+            .register_type::<CrowdAgent>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(move_crowd_agents.in_set(PhysicsStepSet::First));
+        physics.add_systems(filter_crowd_contacts.in_set(BroadPhaseSet::FilterPairs));
+    }
+}
+
+/// Configuration for the [`CrowdAgent`]s moved by the [`CrowdPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct CrowdConfig {
+    /// The collider shared by all agents. It is added to agents that don't have a collider of their own.
+    ///
+    /// The ground probes assume that the agents are not rotated.
+    ///
+    /// Default: A capsule with a radius of `0.4` and a length of `1.0`.
+    pub collider: Collider,
+    /// The maximum distance below the bottom of the collider of an agent that the ground is searched for.
+    /// Agents are snapped to ground within this distance, which keeps them on the ground
+    /// when walking down slopes and small ledges.
+    ///
+    /// Default: `0.5`
+    pub ground_probe_distance: Scalar,
+    /// If `true`, contacts are only generated between agents and [dynamic](RigidBody::Dynamic) bodies.
+    /// Otherwise, contacts are also generated against static and kinematic bodies, including other agents,
+    /// which is only useful for [collision events](ContactReportingPlugin).
+    ///
+    /// Default: `true`
+    pub dynamic_contacts_only: bool,
+}
+
+impl Default for CrowdConfig {
+    fn default() -> Self {
+        Self {
+            collider: Collider::capsule(0.4, 1.0),
+            ground_probe_distance: 0.5,
+            dynamic_contacts_only: true,
+        }
+    }
+}
+
+/// A component for a simple kinematic agent in a crowd, moved by the [`CrowdPlugin`].
+///
+/// A [kinematic](RigidBody::Kinematic) rigid body is added automatically, along with the shared
+/// [`CrowdConfig::collider`] if the entity doesn't have a [`Collider`].
+///
+/// See the [`CrowdPlugin`] for an example.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[require(RigidBody(agent_rigid_body))]
+#[component(on_add = on_add_crowd_agent)]
+pub struct CrowdAgent {
+    /// The desired movement velocity of the agent, typically set by navigation.
+    pub movement: Vector,
+    /// The velocity of the agent that is not controlled by the [`movement`](Self::movement), like gravity.
+    ///
+    /// While the agent is [grounded](Self::is_grounded), velocity into the ground is removed.
+    pub velocity: Vector,
+    ground: Option<Entity>,
+}
+
+fn agent_rigid_body() -> RigidBody {
+    RigidBody::Kinematic
+}
+
+impl CrowdAgent {
+    /// Creates a new [`CrowdAgent`] with the given movement velocity.
+    pub fn new(movement: Vector) -> Self {
+        Self {
+            movement,
+            ..default()
+        }
+    }
+
+    /// Returns `true` if the agent is standing on the ground.
+    pub fn is_grounded(&self) -> bool {
+        self.ground.is_some()
+    }
+
+    /// Returns the entity of the collider that the agent is standing on, or `None` if the agent is in the air.
+    pub fn ground(&self) -> Option<Entity> {
+        self.ground
+    }
+}
+
+fn on_add_crowd_agent(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
+    if world.get::<Collider>(entity).is_some() {
+        return;
+    }
+    let Some(collider) = world
+        .get_resource::<CrowdConfig>()
+        .map(|config| config.collider.clone())
+    else {
+        return;
+    };
+    world.commands().entity(entity).insert(collider);
+}
+
+/// Probes the ground below [`CrowdAgent`]s, and applies their movement to their [`LinearVelocity`].
+#[allow(clippy::type_complexity)]
+fn move_crowd_agents(
+    mut agents: Query<(&mut CrowdAgent, &Position, &mut LinearVelocity), RigidBodyActiveFilter>,
+    agent_entities: Query<(), With<CrowdAgent>>,
+    sensors: Query<(), With<Sensor>>,
+    collider_parents: Query<&ColliderParent>,
+    config: Res<CrowdConfig>,
+    spatial_query: SpatialQuery,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    let down = Dir::new(gravity.0.f32()).unwrap_or(Dir::NEG_Y);
+    let up = -down.adjust_precision();
+
+    // All agents share the same collider, so its extents only need to be computed once.
+    let aabb = config.collider.aabb(Vector::ZERO, Rotation::default());
+    let half_height = ((aabb.max - aabb.min) * 0.5).dot(up.abs());
+    let probe_distance = half_height + config.ground_probe_distance;
+
+    // Ignore agents and sensors.
+    let filter = SpatialQueryFilter::default();
+    let predicate = |collider: Entity| {
+        !sensors.contains(collider)
+            && !collider_parents
+                .get(collider)
+                .is_ok_and(|parent| agent_entities.contains(parent.get()))
+    };
+
+    agents
+        .par_iter_mut()
+        .for_each(|(mut agent, position, mut lin_vel)| {
+            let mut velocity = agent.velocity;
+
+            let hit = (velocity.dot(up) <= 0.0)
+                .then(|| {
+                    spatial_query.cast_ray_predicate(
+                        position.0,
+                        down,
+                        probe_distance,
+                        true,
+                        &filter,
+                        &predicate,
+                    )
+                })
+                .flatten();
+
+            let mut snap = Vector::ZERO;
+            if let Some(hit) = hit {
+                // Snap to the ground, and remove velocity into it.
+                snap = up * (half_height - hit.distance) / delta_secs;
+                velocity -= up * velocity.dot(up).min(0.0);
+                agent.ground = Some(hit.entity);
+            } else {
+                velocity += gravity.0 * delta_secs;
+                agent.ground = None;
+            }
+
+            agent.velocity = velocity;
+            lin_vel.0 = agent.movement + velocity + snap;
+        });
+}
+
+/// Removes the collision pairs of [`CrowdAgent`]s that don't involve a dynamic body
+/// if [`CrowdConfig::dynamic_contacts_only`] is enabled.
+fn filter_crowd_contacts(
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    agents: Query<(), With<CrowdAgent>>,
+    bodies: Query<&RigidBody>,
+    collider_parents: Query<&ColliderParent>,
+    config: Res<CrowdConfig>,
+) {
+    if !config.dynamic_contacts_only || agents.is_empty() {
+        return;
+    }
+
+    let body = |collider: Entity| collider_parents.get(collider).ok().map(|p| p.get());
+    let is_agent = |collider: Entity| body(collider).is_some_and(|body| agents.contains(body));
+    let is_dynamic = |collider: Entity| {
+        body(collider)
+            .and_then(|body| bodies.get(body).ok())
+            .is_some_and(|rb| rb.is_dynamic())
+    };
+
+    broad_collision_pairs.retain(|&(entity1, entity2)| {
+        (!is_agent(entity1) || is_dynamic(entity2)) && (!is_agent(entity2) || is_dynamic(entity1))
+    });
+}
@@ -0,0 +1,199 @@
+//! Budgeting for short-lived [`Debris`] bodies.
+//!
+//! See [`DebrisPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin that limits the number of live [`Debris`] bodies based on the [`DebrisManager`].
+///
+/// When there are more debris bodies than [`DebrisManager::max_bodies`], the excess bodies
+/// are [evicted](DebrisEviction), starting with sleeping bodies and then the oldest ones.
+/// Debris bodies that are older than [`DebrisManager::lifetime`] are also evicted.
+/// This makes it possible to spawn debris freely, for example in explosions,
+/// without making the cost of the simulation grow without bounds.
+///
+/// The plugin must be added after the [`PhysicsPlugins`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), DebrisPlugin))
+///         .insert_resource(DebrisManager::new(200).with_lifetime(20.0))
+///         .add_systems(Update, explode)
+///         .run();
+/// }
+///
+/// fn explode(mut commands: Commands, keyboard: Res<ButtonInput<KeyCode>>) {
+///     if !keyboard.just_pressed(KeyCode::Space) {
+///         return;
+///     }
+///     for i in 0..50 {
+///         commands.spawn((
+///             Debris::default(),
+///             RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "            Collider::rectangle(0.2, 0.2),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::cuboid(0.2, 0.2, 0.2),")]
+///             Position(Vector::Y * i as Scalar * 0.3),
+///         ));
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct DebrisPlugin;
+
+impl Plugin for DebrisPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(Debris, DebrisManager, DebrisEviction)>()
+            .init_resource::<DebrisManager>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(evict_debris.in_set(PhysicsStepSet::Last));
+    }
+}
+
+/// A resource that configures the budget for [`Debris`] bodies with the [`DebrisPlugin`].
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct DebrisManager {
+    /// The maximum number of live debris bodies.
+    ///
+    /// Default: `100`
+    pub max_bodies: usize,
+    /// The maximum age of debris bodies in seconds of simulated time, or `None` for no limit.
+    ///
+    /// Default: `None`
+    pub lifetime: Option<Scalar>,
+    /// What happens to debris bodies that are evicted.
+    ///
+    /// Default: [`DebrisEviction::Despawn`]
+    pub eviction: DebrisEviction,
+    pub(crate) live_bodies: usize,
+}
+
+impl Default for DebrisManager {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl DebrisManager {
+    /// Creates a new [`DebrisManager`] with the given maximum number of live debris bodies.
+    pub const fn new(max_bodies: usize) -> Self {
+        Self {
+            max_bodies,
+            lifetime: None,
+            eviction: DebrisEviction::Despawn,
+            live_bodies: 0,
+        }
+    }
+
+    /// Sets the maximum age of debris bodies in seconds of simulated time.
+    pub const fn with_lifetime(mut self, lifetime: Scalar) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    /// Sets what happens to debris bodies that are evicted.
+    pub const fn with_eviction(mut self, eviction: DebrisEviction) -> Self {
+        self.eviction = eviction;
+        self
+    }
+
+    /// Returns the number of live debris bodies after the last physics step.
+    pub const fn live_bodies(&self) -> usize {
+        self.live_bodies
+    }
+}
+
+/// Determines what happens to [`Debris`] bodies that are evicted by the [`DebrisManager`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum DebrisEviction {
+    /// The entity is despawned along with its descendants.
+    #[default]
+    Despawn,
+    /// The body is made [static](RigidBody::Static) and the [`Debris`] component is removed,
+    /// leaving it in place like a decal. Static bodies have very little cost in the simulation.
+    MakeStatic,
+}
+
+/// A component that marks a rigid body as debris managed by the [`DebrisManager`].
+///
+/// See the [`DebrisPlugin`] for more information.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct Debris {
+    age: Scalar,
+}
+
+impl Debris {
+    /// Returns the age of the debris body in seconds of simulated time.
+    pub const fn age(&self) -> Scalar {
+        self.age
+    }
+}
+
+/// Ages [`Debris`] bodies and evicts them when they exceed the budget of the [`DebrisManager`].
+fn evict_debris(
+    mut commands: Commands,
+    mut debris: Query<(Entity, &mut Debris, Has<Sleeping>)>,
+    mut manager: ResMut<DebrisManager>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    let mut candidates = vec![];
+    let mut evicted = vec![];
+
+    for (entity, mut debris, is_sleeping) in &mut debris {
+        debris.age += delta_secs;
+        if manager
+            .lifetime
+            .is_some_and(|lifetime| debris.age > lifetime)
+        {
+            evicted.push(entity);
+        } else {
+            candidates.push((entity, debris.age, is_sleeping));
+        }
+    }
+
+    // Evict sleeping bodies first, and then the oldest bodies.
+    if candidates.len() > manager.max_bodies {
+        candidates.sort_by(|(_, age1, sleeping1), (_, age2, sleeping2)| {
+            sleeping2.cmp(sleeping1).then_with(|| age2.total_cmp(age1))
+        });
+        let excess = candidates.len() - manager.max_bodies;
+        evicted.extend(candidates.drain(..excess).map(|(entity, ..)| entity));
+    }
+
+    manager.live_bodies = candidates.len();
+
+    for entity in evicted {
+        match manager.eviction {
+            DebrisEviction::Despawn => commands.entity(entity).despawn_recursive(),
+            DebrisEviction::MakeStatic => {
+                commands
+                    .entity(entity)
+                    .remove::<Debris>()
+                    .insert(RigidBody::Static);
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Gravity fields that change the gravity of bodies inside sensor volumes.
+//!
+//! See [`GravityField`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A component that changes the gravity of dynamic bodies inside the [collider](Collider) of the entity.
+///
+/// The field is typically attached to a [`Sensor`] collider. While the collider of a body overlaps the field,
+/// the acceleration of the field either replaces the global [`Gravity`] or is added to it,
+/// depending on the [`GravityFieldMode`]. This can be used for things like spherical planets
+/// or rooms where gravity is flipped.
+///
+/// If a body is inside several fields, the accelerations of all replacing fields are summed to replace
+/// the global gravity, and the accelerations of additive fields are added on top. [`GravityScale`]
+/// is applied to the final gravity.
+///
+/// The fields are evaluated at the [`Position`] of the body during [velocity integration](IntegrationSet::Velocity),
+/// so point and cylindrical fields follow the body across substeps. Directions and axes are in the local space
+/// of the field, so rotating the field rotates its gravity. The bodies inside each field
+/// are updated once per physics step, and can be read from the [`ActiveGravityFields`] component.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     // A small planet that pulls bodies within its atmosphere towards its center.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(10.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(10.0),")]
+///     ));
+///     commands.spawn((
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(30.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(30.0),")]
+///         Sensor,
+///         GravityField::point(9.81),
+///     ));
+///
+///     // A room where gravity points up instead of down.
+///     commands.spawn((
+///         Transform::from_xyz(100.0, 0.0, 0.0),
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(20.0, 10.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(20.0, 10.0, 20.0),")]
+///         Sensor,
+///         GravityField::directional(Vector::Y * 9.81),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct GravityField {
+    /// The shape of the field, determining the direction of its acceleration.
+    pub kind: GravityFieldKind,
+    /// Whether the field replaces the global [`Gravity`] or is added to it.
+    pub mode: GravityFieldMode,
+}
+
+impl GravityField {
+    /// Creates a [`GravityFieldKind::Directional`] field with the given acceleration in the local space of the field,
+    /// replacing the global [`Gravity`].
+    pub fn directional(acceleration: Vector) -> Self {
+        Self {
+            kind: GravityFieldKind::Directional(acceleration),
+            mode: GravityFieldMode::Replace,
+        }
+    }
+
+    /// Creates a [`GravityFieldKind::Point`] field that pulls bodies towards the position of the field
+    /// with the given acceleration, replacing the global [`Gravity`].
+    pub fn point(strength: Scalar) -> Self {
+        Self {
+            kind: GravityFieldKind::Point { strength },
+            mode: GravityFieldMode::Replace,
+        }
+    }
+
+    /// Creates a [`GravityFieldKind::Cylindrical`] field that pulls bodies towards the line going through
+    /// the position of the field along the given local `axis`, replacing the global [`Gravity`].
+    pub fn cylindrical(axis: Vector, strength: Scalar) -> Self {
+        Self {
+            kind: GravityFieldKind::Cylindrical { axis, strength },
+            mode: GravityFieldMode::Replace,
+        }
+    }
+
+    /// Makes the field add to the global [`Gravity`] instead of replacing it.
+    pub fn additive(mut self) -> Self {
+        self.mode = GravityFieldMode::Add;
+        self
+    }
+
+    /// Computes the acceleration of the field at the given `point`,
+    /// when the field has the given `position` and `rotation`.
+    pub fn acceleration_at(&self, position: Vector, rotation: &Rotation, point: Vector) -> Vector {
+        match self.kind {
+            GravityFieldKind::Directional(acceleration) => *rotation * acceleration,
+            GravityFieldKind::Point { strength } => {
+                (position - point).normalize_or_zero() * strength
+            }
+            GravityFieldKind::Cylindrical { axis, strength } => {
+                let axis = (*rotation * axis).normalize_or_zero();
+                let offset = position - point;
+                (offset - axis * offset.dot(axis)).normalize_or_zero() * strength
+            }
+        }
+    }
+}
+
+/// The shape of a [`GravityField`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum GravityFieldKind {
+    /// A uniform acceleration in the local space of the field.
+    Directional(Vector),
+    /// An acceleration with the given magnitude pointing towards the position of the field.
+    Point {
+        /// The magnitude of the acceleration.
+        strength: Scalar,
+    },
+    /// An acceleration with the given magnitude pointing towards the line going through
+    /// the position of the field along the `axis` in the local space of the field.
+    Cylindrical {
+        /// The axis of the cylinder in the local space of the field.
+        axis: Vector,
+        /// The magnitude of the acceleration.
+        strength: Scalar,
+    },
+}
+
+/// Determines how a [`GravityField`] is combined with the global [`Gravity`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum GravityFieldMode {
+    /// The field replaces the global [`Gravity`].
+    #[default]
+    Replace,
+    /// The field is added to the global [`Gravity`].
+    Add,
+}
+
+/// A component storing the [`GravityField`] entities that a body is currently inside.
+///
+/// Updated automatically every physics step, before the solver runs.
+/// Only present on bodies that are inside at least one field.
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq)]
+#[reflect(Debug, Component, PartialEq)]
+pub struct ActiveGravityFields(pub(crate) Vec<Entity>);
+
+impl ActiveGravityFields {
+    /// Returns the [`GravityField`] entities that the body is inside, sorted by entity.
+    pub fn fields(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+/// Computes the gravity of a body at the given `point` based on the global `gravity`
+/// and the [`GravityField`]s that the body is inside.
+pub(super) fn gravity_at(
+    gravity: Vector,
+    active_fields: Option<&ActiveGravityFields>,
+    fields: &Query<(&GravityField, &Position, &Rotation)>,
+    point: Vector,
+) -> Vector {
+    let Some(active_fields) = active_fields else {
+        return gravity;
+    };
+
+    let mut replaced = None;
+    let mut added = Vector::ZERO;
+
+    for (field, position, rotation) in fields.iter_many(active_fields.fields()) {
+        let acceleration = field.acceleration_at(position.0, rotation, point);
+        match field.mode {
+            GravityFieldMode::Replace => *replaced.get_or_insert(Vector::ZERO) += acceleration,
+            GravityFieldMode::Add => added += acceleration,
+        }
+    }
+
+    replaced.unwrap_or(gravity) + added
+}
+
+/// Updates the [`ActiveGravityFields`] of bodies based on the [`Collisions`] with [`GravityField`] colliders.
+pub(super) fn update_active_gravity_fields(
+    mut commands: Commands,
+    collisions: Res<Collisions>,
+    fields: Query<(), With<GravityField>>,
+    mut bodies: Query<(Entity, &RigidBody, Option<&mut ActiveGravityFields>)>,
+) {
+    let mut body_fields = bevy::ecs::entity::EntityHashMap::<Vec<Entity>>::default();
+
+    for contacts in collisions.iter() {
+        if !contacts.during_current_frame {
+            continue;
+        }
+
+        let pairs = [
+            (contacts.entity1, contacts.body_entity2),
+            (contacts.entity2, contacts.body_entity1),
+        ];
+        for (field, body) in pairs {
+            let Some(body) = body.filter(|_| fields.contains(field)) else {
+                continue;
+            };
+            let entry = body_fields.entry(body).or_default();
+            if !entry.contains(&field) {
+                entry.push(field);
+            }
+        }
+    }
+
+    for (entity, rb, active_fields) in &mut bodies {
+        let mut new_fields = body_fields
+            .remove(&entity)
+            .filter(|_| rb.is_dynamic())
+            .unwrap_or_default();
+        new_fields.sort();
+
+        match (new_fields.is_empty(), active_fields) {
+            (false, Some(mut active_fields)) => {
+                if active_fields.0 != new_fields {
+                    active_fields.0 = new_fields;
+                }
+            }
+            (false, None) => {
+                commands
+                    .entity(entity)
+                    .try_insert(ActiveGravityFields(new_fields));
+            }
+            (true, Some(_)) => {
+                commands.entity(entity).remove::<ActiveGravityFields>();
+            }
+            (true, None) => {}
+        }
+    }
+}
@@ -6,7 +6,11 @@
 #[doc(alias = "symplectic_euler")]
 pub mod semi_implicit_euler;
 
-use crate::prelude::*;
+mod gravity_field;
+
+pub use gravity_field::{ActiveGravityFields, GravityField, GravityFieldKind, GravityFieldMode};
+
+use crate::{dynamics::solver::schedule::SubstepSolverSet, prelude::*};
 use bevy::{
     ecs::{intern::Interned, query::QueryData, schedule::ScheduleLabel},
     prelude::*,
@@ -21,6 +25,13 @@ use bevy::{
 /// is supported. It is the standard for game physics, being simple, efficient, and sufficiently accurate.
 ///
 /// The plugin adds systems in the [`IntegrationSet::Velocity`] and [`IntegrationSet::Position`] system sets.
+///
+/// Velocities are clamped to the [`MaxLinearSpeed`] and [`MaxAngularSpeed`] of bodies both after
+/// velocity integration and after the constraints have been solved, in every substep and after
+/// [restitution](SolverSet::Restitution).
+///
+/// Bodies inside [`GravityField`] volumes use the gravity of the fields instead of
+/// or in addition to the global [`Gravity`], and a [`GravityOverride`] replaces the gravity of a single body.
 pub struct IntegratorPlugin {
     schedule: Interned<dyn ScheduleLabel>,
 }
@@ -44,7 +55,17 @@ impl Default for IntegratorPlugin {
 
 impl Plugin for IntegratorPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Gravity>();
+        app.register_type::<(
+            GravityField,
+            GravityFieldKind,
+            GravityFieldMode,
+            ActiveGravityFields,
+        )>();
+
+        app.init_resource::<Gravity>()
+            .init_resource::<RigidBodyLimitsConfig>()
+            .init_resource::<CurrentSubstep>()
+            .init_resource::<Collisions>();
 
         app.configure_sets(
             self.schedule.intern(),
@@ -59,6 +80,12 @@ impl Plugin for IntegratorPlugin {
             ),
         );
 
+        // Clamp velocities again after the constraints have been solved in each substep.
+        app.add_systems(
+            self.schedule.intern(),
+            clamp_solved_velocities.after(SubstepSolverSet::XpbdVelocityProjection),
+        );
+
         #[cfg(feature = "3d")]
         app.add_systems(
             self.schedule.intern(),
@@ -67,15 +94,25 @@ impl Plugin for IntegratorPlugin {
                 .after(integrate_positions),
         );
 
-        app.get_schedule_mut(PhysicsSchedule)
-            .expect("add PhysicsSchedule first")
-            .add_systems(
-                (
-                    apply_impulses.before(SolverSet::Substep),
-                    clear_forces_and_impulses.after(SolverSet::Substep),
-                )
-                    .in_set(PhysicsStepSet::Solver),
-            );
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        // Drive kinematic bodies towards their targets before contacts are computed,
+        // so that the narrow phase and solver see the velocities of the bodies.
+        physics.add_systems(apply_kinematic_targets.in_set(PhysicsStepSet::First));
+
+        physics.add_systems(
+            (
+                gravity_field::update_active_gravity_fields.before(SolverSet::Substep),
+                apply_impulses.before(SolverSet::Substep),
+                clear_forces_and_impulses.after(SolverSet::Substep),
+                clamp_solved_velocities
+                    .after(SolverSet::Restitution)
+                    .before(SolverSet::ApplyTranslation),
+            )
+                .in_set(PhysicsStepSet::Solver),
+        );
     }
 }
 
@@ -97,6 +134,7 @@ pub enum IntegrationSet {
 ///
 /// You can also control how gravity affects a specific [rigid body](RigidBody) using the [`GravityScale`]
 /// component. The magnitude of the gravity will be multiplied by this scaling factor.
+/// The gravity of a body can also be replaced entirely using the [`GravityOverride`] component.
 ///
 /// # Example
 ///
@@ -141,13 +179,71 @@ impl Gravity {
     pub const ZERO: Gravity = Gravity(Vector::ZERO);
 }
 
+/// Global limits for the angular velocity and angular inertia of dynamic [rigid bodies](RigidBody).
+///
+/// Very small bodies have a tiny angular inertia, so even small contact impulses can make them spin
+/// extremely fast. This is common for debris, and can destabilize the contacts of the bodies around them.
+/// These limits can be used to keep such bodies under control.
+///
+/// - [`max_angular_speed`](Self::max_angular_speed) clamps the [`AngularVelocity`] of dynamic bodies.
+///   It can be overridden per body using the [`MaxAngularSpeed`] component.
+/// - [`min_inertia_radius`](Self::min_inertia_radius) clamps the [`ComputedAngularInertia`] of dynamic bodies
+///   such that they behave as if their mass was at least this far from the center of mass.
+///   It can be overridden per body using the [`MinInertiaRadius`] component.
+///
+/// By default, no limits are applied.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(RigidBodyLimitsConfig {
+///             max_angular_speed: 50.0,
+///             min_inertia_radius: 0.05,
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct RigidBodyLimitsConfig {
+    /// The maximum angular speed of dynamic bodies in radians per second.
+    ///
+    /// Default: `Scalar::INFINITY`
+    pub max_angular_speed: Scalar,
+
+    /// The minimum radius of gyration of dynamic bodies. The angular inertia of a body
+    /// is clamped to be at least `mass * min_inertia_radius²` about each principal axis.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.0`
+    pub min_inertia_radius: Scalar,
+}
+
+impl Default for RigidBodyLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_angular_speed: Scalar::INFINITY,
+            min_inertia_radius: 0.0,
+        }
+    }
+}
+
 #[derive(QueryData)]
 #[query_data(mutable)]
 struct VelocityIntegrationQuery {
     rb: &'static RigidBody,
     pos: &'static Position,
     prev_pos: Option<&'static mut PreSolveAccumulatedTranslation>,
-    #[cfg(feature = "3d")]
     rot: &'static Rotation,
     lin_vel: &'static mut LinearVelocity,
     ang_vel: &'static mut AngularVelocity,
@@ -162,6 +258,8 @@ struct VelocityIntegrationQuery {
     max_linear_speed: Option<&'static MaxLinearSpeed>,
     max_angular_speed: Option<&'static MaxAngularSpeed>,
     gravity_scale: Option<&'static GravityScale>,
+    gravity_override: Option<&'static GravityOverride>,
+    active_gravity_fields: Option<&'static ActiveGravityFields>,
     locked_axes: Option<&'static LockedAxes>,
 }
 
@@ -169,6 +267,9 @@ struct VelocityIntegrationQuery {
 fn integrate_velocities(
     mut bodies: Query<VelocityIntegrationQuery, RigidBodyActiveFilter>,
     gravity: Res<Gravity>,
+    gravity_fields: Query<(&GravityField, &Position, &Rotation)>,
+    limits: Res<RigidBodyLimitsConfig>,
+    current_substep: Res<CurrentSubstep>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
@@ -205,9 +306,19 @@ fn integrate_velocities(
                 }
             }
 
-            let external_force = body.force.force();
-            let external_torque = body.torque.torque() + body.force.torque();
-            let gravity = gravity.0 * body.gravity_scale.map_or(1.0, |scale| scale.0);
+            let (external_force, force_torque) = body
+                .force
+                .world_force_and_torque_at(current_substep.fraction(), body.rot);
+            let external_torque = body.torque.world_torque(body.rot) + force_torque;
+            let gravity = match body.gravity_override {
+                Some(gravity_override) => gravity_override.0,
+                None => gravity_field::gravity_at(
+                    gravity.0,
+                    body.active_gravity_fields,
+                    &gravity_fields,
+                    body.pos.0,
+                ),
+            } * body.gravity_scale.map_or(1.0, |scale| scale.0);
 
             semi_implicit_euler::integrate_velocity(
                 &mut body.lin_vel.0,
@@ -226,27 +337,118 @@ fn integrate_velocities(
             );
         }
 
-        // Clamp velocities
-        if let Some(max_linear_speed) = body.max_linear_speed {
-            let linear_speed_squared = body.lin_vel.0.length_squared();
-            if linear_speed_squared > max_linear_speed.0.powi(2) {
-                body.lin_vel.0 *= max_linear_speed.0 / linear_speed_squared.sqrt();
-            }
-        }
-        if let Some(max_angular_speed) = body.max_angular_speed {
-            #[cfg(feature = "2d")]
-            if body.ang_vel.abs() > max_angular_speed.0 {
-                body.ang_vel.0 = max_angular_speed.copysign(body.ang_vel.0);
-            }
-            #[cfg(feature = "3d")]
-            {
-                let angular_speed_squared = body.ang_vel.0.length_squared();
-                if angular_speed_squared > max_angular_speed.0.powi(2) {
-                    body.ang_vel.0 *= max_angular_speed.0 / angular_speed_squared.sqrt();
+        clamp_velocity(
+            body.rb,
+            &mut body.lin_vel,
+            &mut body.ang_vel,
+            body.max_linear_speed,
+            body.max_angular_speed,
+            &limits,
+        );
+    });
+}
+
+/// Clamps the velocities of bodies to their [`MaxLinearSpeed`] and [`MaxAngularSpeed`]
+/// after the constraints have been solved, so that solver impulses can't exceed the limits either.
+///
+/// Unless the [`RigidBodyLimitsConfig`] has a global angular speed limit,
+/// only bodies with one of the limit components are visited.
+#[allow(clippy::type_complexity)]
+fn clamp_solved_velocities(
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+            Option<&MaxLinearSpeed>,
+            Option<&MaxAngularSpeed>,
+        ),
+        RigidBodyActiveFilter,
+    >,
+    limited_bodies: Query<
+        Entity,
+        (
+            RigidBodyActiveFilter,
+            Or<(With<MaxLinearSpeed>, With<MaxAngularSpeed>)>,
+        ),
+    >,
+    limits: Res<RigidBodyLimitsConfig>,
+) {
+    if limits.max_angular_speed < Scalar::INFINITY {
+        // The global limit applies to all dynamic bodies.
+        bodies.par_iter_mut().for_each(
+            |(rb, mut lin_vel, mut ang_vel, max_linear_speed, max_angular_speed)| {
+                if rb.is_static() {
+                    return;
                 }
+                clamp_velocity(
+                    rb,
+                    &mut lin_vel,
+                    &mut ang_vel,
+                    max_linear_speed,
+                    max_angular_speed,
+                    &limits,
+                );
+            },
+        );
+        return;
+    }
+
+    // Otherwise, only bodies with their own limits need to be clamped.
+    let mut limited = bodies.iter_many_mut(&limited_bodies);
+    while let Some((rb, mut lin_vel, mut ang_vel, max_linear_speed, max_angular_speed)) =
+        limited.fetch_next()
+    {
+        if rb.is_static() {
+            continue;
+        }
+        clamp_velocity(
+            rb,
+            &mut lin_vel,
+            &mut ang_vel,
+            max_linear_speed,
+            max_angular_speed,
+            &limits,
+        );
+    }
+}
+
+/// Clamps the velocity of a body to its [`MaxLinearSpeed`] and [`MaxAngularSpeed`],
+/// falling back to the [`RigidBodyLimitsConfig`] for the angular speed of dynamic bodies.
+fn clamp_velocity(
+    rb: &RigidBody,
+    lin_vel: &mut Mut<LinearVelocity>,
+    ang_vel: &mut Mut<AngularVelocity>,
+    max_linear_speed: Option<&MaxLinearSpeed>,
+    max_angular_speed: Option<&MaxAngularSpeed>,
+    limits: &RigidBodyLimitsConfig,
+) {
+    if let Some(max_linear_speed) = max_linear_speed {
+        let linear_speed_squared = lin_vel.0.length_squared();
+        if linear_speed_squared > max_linear_speed.0.powi(2) {
+            lin_vel.0 *= max_linear_speed.0 / linear_speed_squared.sqrt();
+        }
+    }
+    // The global limit only applies to dynamic bodies, so that kinematic bodies
+    // can still be moved at any speed.
+    let max_angular_speed = match max_angular_speed {
+        Some(max_angular_speed) => max_angular_speed.0,
+        None if rb.is_dynamic() => limits.max_angular_speed,
+        None => Scalar::INFINITY,
+    };
+    if max_angular_speed < Scalar::INFINITY {
+        #[cfg(feature = "2d")]
+        if ang_vel.abs() > max_angular_speed {
+            ang_vel.0 = max_angular_speed.copysign(ang_vel.0);
+        }
+        #[cfg(feature = "3d")]
+        {
+            let angular_speed_squared = ang_vel.0.length_squared();
+            if angular_speed_squared > max_angular_speed.powi(2) {
+                ang_vel.0 *= max_angular_speed / angular_speed_squared.sqrt();
             }
         }
-    });
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -322,6 +524,38 @@ type ImpulseQueryComponents = (
     Option<&'static LockedAxes>,
 );
 
+/// Sets the velocities of [kinematic](RigidBody::Kinematic) bodies so that they reach
+/// their [`KinematicTarget`] by the end of the physics step.
+#[allow(clippy::type_complexity)]
+fn apply_kinematic_targets(
+    mut bodies: Query<(
+        &RigidBody,
+        &KinematicTarget,
+        &Position,
+        &Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (rb, target, position, rotation, mut lin_vel, mut ang_vel) in &mut bodies {
+        if !rb.is_kinematic() {
+            continue;
+        }
+        if let Some(target_position) = target.position {
+            *lin_vel = LinearVelocity::to_reach(position.0, target_position, delta_secs);
+        }
+        if let Some(target_rotation) = target.rotation {
+            *ang_vel = AngularVelocity::to_reach(*rotation, target_rotation, delta_secs);
+        }
+    }
+}
+
 fn apply_impulses(mut bodies: Query<ImpulseQueryComponents, RigidBodyActiveFilter>) {
     for (
         rb,
@@ -379,6 +613,8 @@ pub fn clear_forces_and_impulses(mut forces: Query<ForceComponents, ForceCompone
     for (mut force, mut torque, mut impulse, mut angular_ímpulse) in &mut forces {
         if !force.persistent {
             force.clear();
+        } else if force.ramp_end().is_some() {
+            force.finish_ramp();
         }
         if !torque.persistent {
             torque.clear();
@@ -0,0 +1,684 @@
+//! Simulation islands, groups of dynamic bodies that can interact with each other
+//! through contacts and joints.
+//!
+//! See [`IslandPlugin`].
+
+use std::ops::Range;
+
+use crate::{
+    dynamics::solver::{contact::ContactConstraint, xpbd::XpbdConstraint, ContactConstraints},
+    prelude::*,
+};
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+/// A plugin that splits the simulation into *islands*, groups of dynamic bodies
+/// that are connected to each other through contacts or [joints](dynamics::solver::joints).
+///
+/// Bodies in different islands cannot affect each other during a time step,
+/// so their constraints can be solved independently. With the `parallel` feature,
+/// the [`SolverPlugin`] uses this to solve the contacts of different islands in parallel.
+///
+/// Static and kinematic bodies are not part of any island. They are not affected by contacts,
+/// so they do not connect the islands of the bodies touching them.
+///
+/// Islands are also used by the [`SleepingPlugin`] to make bodies sleep and wake up as a unit.
+///
+/// The computed islands can be inspected using the [`PhysicsIslands`] resource,
+/// and the island of each dynamic body is stored in its [`PhysicsIslandId`] component.
+/// When islands merge or split compared to the previous physics step, [`IslandsMerged`]
+/// and [`IslandSplit`] events are sent.
+///
+/// Custom constraints registered with [`XpbdConstraintAppExt::add_physics_constraint`]
+/// also connect the islands of their bodies, like Avian's joints.
+///
+/// Islands are rebuilt every physics step in [`SolverSet::PreSubstep`], after the contact constraints
+/// have been generated. The [`ContactConstraints`] are reordered such that the constraints
+/// of each island are stored contiguously. The order of the constraints within an island is preserved,
+/// so the simulation produces the same results as if the constraints were not reordered.
+///
+/// If [`SolverConfig::graph_coloring_threshold`] is set, the constraints of large islands are also
+/// sorted into *colors*, groups of constraints that share no dynamic bodies and can be solved in parallel.
+pub struct IslandPlugin;
+
+impl Plugin for IslandPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PhysicsIslandId>();
+
+        app.add_event::<IslandsMerged>().add_event::<IslandSplit>();
+
+        app.init_resource::<PhysicsIslands>()
+            .init_resource::<ConstraintLinks>()
+            .init_resource::<Collisions>()
+            .init_resource::<ContactConstraints>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(
+            (update_islands, update_island_ids)
+                .chain()
+                .in_set(SolverSet::PreSubstep),
+        );
+    }
+}
+
+/// A resource storing the simulation islands computed by the [`IslandPlugin`] for the current physics step.
+///
+/// An island is a group of dynamic bodies that are connected to each other through contacts
+/// or [joints](dynamics::solver::joints). This can be useful for tooling, or for making decisions
+/// for whole groups of interacting bodies at once, such as simulation level of detail.
+///
+/// The islands are rebuilt every physics step, so island indices are not stable across steps.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn log_islands(islands: Res<PhysicsIslands>) {
+///     for island in islands.iter() {
+///         println!(
+///             "Island with {} bodies, sleeping: {}",
+///             island.len(),
+///             island.is_sleeping()
+///         );
+///     }
+/// }
+///
+/// fn log_player_island(islands: Res<PhysicsIslands>, player: Single<Entity, With<Player>>) {
+///     if let Some(island) = islands.island_of(*player) {
+///         println!("The player is interacting with {} bodies", island.len() - 1);
+///     }
+/// }
+/// #
+/// # #[derive(Component)]
+/// # struct Player;
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct PhysicsIslands {
+    /// The islands, ordered by the ranges of their constraints in [`ContactConstraints`].
+    pub(crate) islands: Vec<Island>,
+    /// The index of the island that each dynamic body belongs to.
+    pub(crate) body_islands: EntityHashMap<usize>,
+    /// The range of [`ContactConstraints`] that do not involve any dynamic bodies,
+    /// for example contacts between kinematic and static bodies.
+    ///
+    /// These are always stored after the constraints of the islands.
+    pub(crate) non_dynamic_constraints: Range<usize>,
+}
+
+impl PhysicsIslands {
+    /// Returns the number of islands.
+    pub fn len(&self) -> usize {
+        self.islands.len()
+    }
+
+    /// Returns `true` if there are no islands.
+    pub fn is_empty(&self) -> bool {
+        self.islands.is_empty()
+    }
+
+    /// Returns the island at the given index, if it exists.
+    pub fn get(&self, index: usize) -> Option<&Island> {
+        self.islands.get(index)
+    }
+
+    /// Returns an iterator over all islands.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &Island> {
+        self.islands.iter()
+    }
+
+    /// Returns the index of the island that the given body belongs to.
+    ///
+    /// Returns `None` if the entity is not a dynamic body.
+    pub fn island_index(&self, entity: Entity) -> Option<usize> {
+        self.body_islands.get(&entity).copied()
+    }
+
+    /// Returns the island that the given body belongs to.
+    ///
+    /// Returns `None` if the entity is not a dynamic body.
+    pub fn island_of(&self, entity: Entity) -> Option<&Island> {
+        self.island_index(entity).map(|index| &self.islands[index])
+    }
+
+    /// Returns `true` if the constraint ranges of the islands match the given constraints.
+    ///
+    /// This can be `false` if constraints were added or removed after the islands were built.
+    pub(crate) fn matches_constraints(&self, constraints: &[ContactConstraint]) -> bool {
+        self.non_dynamic_constraints.end == constraints.len()
+    }
+}
+
+/// A component storing the index of the [island](PhysicsIslands) that a dynamic body belongs to.
+///
+/// The component is inserted and updated by the [`IslandPlugin`] every physics step,
+/// and removed when the body is no longer part of an island, for example because it became static
+/// or was disabled. This can be used for grouping bodies by island in gameplay logic,
+/// such as playing a single collapse sound for a whole structure.
+///
+/// Like the indices in [`PhysicsIslands`], the index is not stable across physics steps.
+/// It is only valid for comparing bodies within the same step.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::{prelude::*, utils::HashMap};
+///
+/// fn count_bodies_per_island(bodies: Query<&PhysicsIslandId>) {
+///     let mut counts = HashMap::<PhysicsIslandId, usize>::new();
+///
+///     for island_id in &bodies {
+///         *counts.entry(*island_id).or_default() += 1;
+///     }
+///
+///     for (island_id, count) in counts {
+///         println!("Island {} has {count} bodies", island_id.0);
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq, Hash)]
+pub struct PhysicsIslandId(pub usize);
+
+/// An event that is sent when two or more [islands](PhysicsIslands) from the previous physics step
+/// have become connected into a single island, for example because a body landed on a pile of other bodies.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn log_merges(mut merges: EventReader<IslandsMerged>) {
+///     for event in merges.read() {
+///         println!(
+///             "{} islands merged into an island with {} bodies",
+///             event.previous_islands.len(),
+///             event.bodies.len()
+///         );
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct IslandsMerged {
+    /// The bodies of the merged island.
+    pub bodies: Vec<Entity>,
+    /// The bodies of each island that was merged, as they were in the previous physics step.
+    pub previous_islands: Vec<Vec<Entity>>,
+}
+
+/// An event that is sent when an [island](PhysicsIslands) from the previous physics step
+/// has been split into two or more islands, for example because a structure broke apart
+/// and its pieces are no longer connected.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn play_collapse_sounds(mut splits: EventReader<IslandSplit>) {
+///     for event in splits.read() {
+///         for island in &event.islands {
+///             println!("A piece with {} bodies broke off", island.len());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct IslandSplit {
+    /// The bodies of the island before it was split, as they were in the previous physics step.
+    pub bodies: Vec<Entity>,
+    /// The bodies of each island that the island was split into.
+    pub islands: Vec<Vec<Entity>>,
+}
+
+/// A group of dynamic bodies that are connected to each other through contacts or joints.
+///
+/// See [`PhysicsIslands`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Island {
+    /// The dynamic bodies in the island.
+    pub(crate) bodies: Vec<Entity>,
+    /// The range of [`ContactConstraints`] that belong to the island.
+    pub(crate) contact_constraints: Range<usize>,
+    /// Whether all bodies in the island are sleeping.
+    pub(crate) sleeping: bool,
+    /// The colors of the contact constraints, if the island is large enough for graph coloring.
+    pub(crate) coloring: Option<IslandColoring>,
+}
+
+/// The contact constraints of an [`Island`] split into colors using graph coloring.
+///
+/// See [`SolverConfig::graph_coloring_threshold`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct IslandColoring {
+    /// The ranges of [`ContactConstraints`] for each color.
+    /// The constraints in a color share no dynamic bodies.
+    pub(crate) colors: Vec<Range<usize>>,
+    /// The range of [`ContactConstraints`] that could not be given a color,
+    /// because their bodies already use all of the available colors.
+    /// These must be solved serially.
+    pub(crate) overflow: Range<usize>,
+}
+
+impl Island {
+    /// Returns the dynamic bodies in the island.
+    pub fn bodies(&self) -> &[Entity] {
+        &self.bodies
+    }
+
+    /// Returns the number of bodies in the island.
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// Returns `true` if the island has no bodies.
+    ///
+    /// This is never the case for islands stored in [`PhysicsIslands`].
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty()
+    }
+
+    /// Returns the number of contact constraints between the bodies of the island,
+    /// including contacts with static and kinematic bodies.
+    pub fn contact_count(&self) -> usize {
+        self.contact_constraints.len()
+    }
+
+    /// Returns `true` if all bodies in the island are [`Sleeping`].
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// Returns the number of colors that the contact constraints of the island were split into
+    /// using graph coloring, or `0` if the island was not colored.
+    ///
+    /// See [`SolverConfig::graph_coloring_threshold`].
+    pub fn color_count(&self) -> usize {
+        self.coloring
+            .as_ref()
+            .map_or(0, |coloring| coloring.colors.len())
+    }
+}
+
+/// The maximum number of colors used for [graph coloring](SolverConfig::graph_coloring_threshold).
+/// Constraints that can't be given a color are solved serially.
+const MAX_COLORS: usize = u64::BITS as usize;
+
+/// Assigns colors to constraints such that no two constraints of the same color share a dynamic body.
+///
+/// Colors are assigned greedily, using the lowest color that is not yet used by either body.
+#[derive(Default)]
+struct ConstraintColorer {
+    /// The colors used by each dynamic body, as a bit mask.
+    body_colors: EntityHashMap<u64>,
+}
+
+impl ConstraintColorer {
+    /// Returns the color of a constraint between the given dynamic bodies,
+    /// or [`MAX_COLORS`] if all colors are already in use.
+    fn color(&mut self, bodies: impl Iterator<Item = Entity> + Clone) -> usize {
+        let used = bodies
+            .clone()
+            .filter_map(|entity| self.body_colors.get(&entity))
+            .fold(0, |used, colors| used | colors);
+        let color = used.trailing_ones() as usize;
+
+        if color < MAX_COLORS {
+            for entity in bodies {
+                *self.body_colors.entry(entity).or_default() |= 1 << color;
+            }
+        }
+
+        color
+    }
+}
+
+/// A disjoint-set forest for finding connected groups of bodies.
+struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(count: usize) -> Self {
+        Self {
+            parents: (0..count).collect(),
+            sizes: vec![1; count],
+        }
+    }
+
+    fn find(&mut self, mut index: usize) -> usize {
+        // Path halving
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let mut a = self.find(a);
+        let mut b = self.find(b);
+
+        if a == b {
+            return;
+        }
+
+        // Union by size
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+    }
+}
+
+/// Pairs of bodies connected by custom constraints registered with
+/// [`XpbdConstraintAppExt::add_physics_constraint`].
+///
+/// The links are collected by [`link_constraint_bodies`] every physics step,
+/// and consumed by [`update_islands`].
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub(crate) struct ConstraintLinks(pub(crate) Vec<[Entity; 2]>);
+
+/// Collects the bodies connected by constraints of type `C` into the [`ConstraintLinks`],
+/// so that they are part of the same island.
+///
+/// Added by [`XpbdConstraintAppExt::add_physics_constraint`].
+pub(crate) fn link_constraint_bodies<
+    C: XpbdConstraint<ENTITY_COUNT> + Component,
+    const ENTITY_COUNT: usize,
+>(
+    constraints: Query<&C, (Without<RigidBody>, Without<JointDisabled>)>,
+    mut links: ResMut<ConstraintLinks>,
+) {
+    for constraint in &constraints {
+        // Linking consecutive bodies is enough to connect all of them.
+        links.0.extend(
+            constraint
+                .entities()
+                .windows(2)
+                .map(|pair| [pair[0], pair[1]]),
+        );
+    }
+}
+
+/// Builds the [`PhysicsIslands`] and sorts the [`ContactConstraints`] by island.
+///
+/// Sends [`IslandsMerged`] and [`IslandSplit`] events for islands that changed since the previous step.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_islands(
+    mut islands: ResMut<PhysicsIslands>,
+    mut merged_events: EventWriter<IslandsMerged>,
+    mut split_events: EventWriter<IslandSplit>,
+    mut constraints: ResMut<ContactConstraints>,
+    collisions: Res<Collisions>,
+    bodies: Query<(Entity, &RigidBody, Has<Sleeping>), Without<RigidBodyDisabled>>,
+    fixed_joints: Query<&FixedJoint, Without<JointDisabled>>,
+    distance_joints: Query<&DistanceJoint, Without<JointDisabled>>,
+    prismatic_joints: Query<&PrismaticJoint, Without<JointDisabled>>,
+    revolute_joints: Query<&RevoluteJoint, Without<JointDisabled>>,
+    #[cfg(feature = "3d")] spherical_joints: Query<&SphericalJoint, Without<JointDisabled>>,
+    #[cfg(feature = "3d")] universal_joints: Query<&UniversalJoint, Without<JointDisabled>>,
+    mirror_constraints: Query<&MirrorConstraint, Without<JointDisabled>>,
+    gear_joints: Query<&GearJoint, Without<JointDisabled>>,
+    rack_and_pinion_joints: Query<&RackAndPinionJoint, Without<JointDisabled>>,
+    mut constraint_links: ResMut<ConstraintLinks>,
+    solver_config: Option<Res<SolverConfig>>,
+) {
+    let islands = &mut *islands;
+
+    // Only dynamic bodies are part of islands.
+    let dynamic_bodies: Vec<(Entity, bool)> = bodies
+        .iter()
+        .filter_map(|(entity, rb, is_sleeping)| rb.is_dynamic().then_some((entity, is_sleeping)))
+        .collect();
+    let body_indices: EntityHashMap<usize> = dynamic_bodies
+        .iter()
+        .enumerate()
+        .map(|(index, &(entity, _))| (entity, index))
+        .collect();
+
+    let mut union_find = UnionFind::new(dynamic_bodies.len());
+    let mut connect = |[entity1, entity2]: [Entity; 2]| {
+        if let (Some(&index1), Some(&index2)) =
+            (body_indices.get(&entity1), body_indices.get(&entity2))
+        {
+            union_find.union(index1, index2);
+        }
+    };
+
+    // Contacts between sleeping bodies don't generate constraints,
+    // so the islands are built from the collisions instead.
+    for contacts in collisions.iter() {
+        if contacts.is_sensor {
+            continue;
+        }
+        if let (Some(body1), Some(body2)) = (contacts.body_entity1, contacts.body_entity2) {
+            connect([body1, body2]);
+        }
+    }
+
+    fixed_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    distance_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    prismatic_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    revolute_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    #[cfg(feature = "3d")]
+    spherical_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    #[cfg(feature = "3d")]
+    universal_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    mirror_constraints
+        .iter()
+        .for_each(|constraint| connect(constraint.entities()));
+    gear_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    rack_and_pinion_joints
+        .iter()
+        .for_each(|joint| connect(joint.entities()));
+    constraint_links.0.drain(..).for_each(&mut connect);
+
+    // Keep the previous islands for detecting merges and splits.
+    let previous_islands = std::mem::take(&mut islands.islands);
+    let previous_body_islands = std::mem::take(&mut islands.body_islands);
+
+    // Assign island indices in the order of the bodies so that they are deterministic.
+    let mut root_islands: Vec<Option<usize>> = vec![None; dynamic_bodies.len()];
+
+    for (index, &(entity, is_sleeping)) in dynamic_bodies.iter().enumerate() {
+        let root = union_find.find(index);
+        let island_index = *root_islands[root].get_or_insert_with(|| {
+            islands.islands.push(Island {
+                sleeping: true,
+                ..default()
+            });
+            islands.islands.len() - 1
+        });
+
+        let island = &mut islands.islands[island_index];
+        island.bodies.push(entity);
+        island.sleeping &= is_sleeping;
+        islands.body_islands.insert(entity, island_index);
+    }
+
+    // Returns the sorted and deduplicated island indices of the given bodies.
+    let island_indices = |bodies: &[Entity], body_islands: &EntityHashMap<usize>| {
+        let mut indices: Vec<usize> = bodies
+            .iter()
+            .filter_map(|entity| body_islands.get(entity).copied())
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    };
+
+    // Islands whose bodies belonged to several islands in the previous step were merged.
+    for island in islands.islands.iter() {
+        let indices = island_indices(&island.bodies, &previous_body_islands);
+        if indices.len() > 1 {
+            merged_events.send(IslandsMerged {
+                bodies: island.bodies.clone(),
+                previous_islands: indices
+                    .iter()
+                    .map(|&index| previous_islands[index].bodies.clone())
+                    .collect(),
+            });
+        }
+    }
+
+    // Previous islands whose bodies now belong to several islands were split.
+    for previous_island in previous_islands.iter() {
+        let indices = island_indices(&previous_island.bodies, &islands.body_islands);
+        if indices.len() > 1 {
+            split_events.send(IslandSplit {
+                bodies: previous_island.bodies.clone(),
+                islands: indices
+                    .iter()
+                    .map(|&index| islands.islands[index].bodies.clone())
+                    .collect(),
+            });
+        }
+    }
+
+    // Find the island of each constraint. Constraints without dynamic bodies are placed last.
+    let island_count = islands.islands.len();
+    let mut keyed_constraints: Vec<(usize, usize, ContactConstraint)> =
+        std::mem::take(&mut constraints.0)
+            .into_iter()
+            .map(|constraint| {
+                let island_index = islands
+                    .body_islands
+                    .get(&constraint.entity1)
+                    .or_else(|| islands.body_islands.get(&constraint.entity2))
+                    .copied()
+                    .unwrap_or(island_count);
+                (island_index, 0, constraint)
+            })
+            .collect();
+
+    // Color the constraints of islands that are large enough for graph coloring.
+    let graph_coloring_threshold = solver_config.and_then(|config| config.graph_coloring_threshold);
+    let colored_islands: Vec<bool> = if let Some(threshold) = graph_coloring_threshold {
+        let mut constraint_counts = vec![0; island_count];
+        for (island_index, ..) in keyed_constraints.iter() {
+            if let Some(count) = constraint_counts.get_mut(*island_index) {
+                *count += 1;
+            }
+        }
+        let colored_islands: Vec<bool> = constraint_counts
+            .into_iter()
+            .map(|count| count >= threshold)
+            .collect();
+
+        let mut colorer = ConstraintColorer::default();
+        for (island_index, color, constraint) in keyed_constraints.iter_mut() {
+            if colored_islands.get(*island_index).copied().unwrap_or(false) {
+                // Only dynamic bodies are solved mutably, so static and kinematic bodies can be shared.
+                let dynamic_bodies = [constraint.entity1, constraint.entity2]
+                    .into_iter()
+                    .filter(|entity| islands.body_islands.contains_key(entity));
+                *color = colorer.color(dynamic_bodies);
+            }
+        }
+
+        colored_islands
+    } else {
+        vec![false; island_count]
+    };
+
+    // Sort the constraints by island and color.
+    // The sort is stable, so the solve order within each island and color is unchanged.
+    keyed_constraints.sort_by_key(|(island_index, color, _)| (*island_index, *color));
+
+    // Compute the constraint ranges of each island and its colors.
+    let mut start = 0;
+    for (island_index, island) in islands.islands.iter_mut().enumerate() {
+        let count = keyed_constraints[start..]
+            .iter()
+            .take_while(|(index, ..)| *index == island_index)
+            .count();
+        island.contact_constraints = start..start + count;
+
+        if colored_islands[island_index] {
+            let end = start + count;
+            let mut coloring = IslandColoring {
+                overflow: end..end,
+                ..default()
+            };
+            let mut color_start = start;
+            while color_start < end {
+                let color = keyed_constraints[color_start].1;
+                let color_count = keyed_constraints[color_start..end]
+                    .iter()
+                    .take_while(|(_, other_color, _)| *other_color == color)
+                    .count();
+                let range = color_start..color_start + color_count;
+                if color < MAX_COLORS {
+                    coloring.colors.push(range);
+                } else {
+                    coloring.overflow = range;
+                }
+                color_start += color_count;
+            }
+            island.coloring = Some(coloring);
+        }
+
+        start += count;
+    }
+    islands.non_dynamic_constraints = start..keyed_constraints.len();
+
+    constraints.0 = keyed_constraints
+        .into_iter()
+        .map(|(.., constraint)| constraint)
+        .collect();
+}
+
+/// Inserts, updates, and removes the [`PhysicsIslandId`] components of bodies.
+fn update_island_ids(
+    mut commands: Commands,
+    islands: Res<PhysicsIslands>,
+    mut bodies: Query<(Entity, Option<&mut PhysicsIslandId>), With<RigidBody>>,
+) {
+    for (entity, island_id) in &mut bodies {
+        match (islands.island_index(entity), island_id) {
+            (Some(index), Some(mut island_id)) => {
+                island_id.set_if_neq(PhysicsIslandId(index));
+            }
+            (Some(index), None) => {
+                commands.entity(entity).try_insert(PhysicsIslandId(index));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<PhysicsIslandId>();
+            }
+            (None, None) => {}
+        }
+    }
+}
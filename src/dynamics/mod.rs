@@ -21,12 +21,25 @@
 //!
 //! # Plugins
 //!
-//! | Plugin               | Description                                                                                                                           |
-//! | -------------------- | ------------------------------------------------------------------------------------------------------------------------------------- |
-//! | [`IntegratorPlugin`] | Handles motion caused by velocity, and applies external forces and gravity.                                                           |
-//! | [`SolverPlugin`]     | Solves constraints (contacts and joints).                                                                                             |
-//! | [`CcdPlugin`]        | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component to prevent tunneling. |
-//! | [`SleepingPlugin`]   | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                              |
+//! | Plugin                 | Description                                                                                                                           |
+//! | ---------------------- | ------------------------------------------------------------------------------------------------------------------------------------- |
+//! | [`IntegratorPlugin`]   | Handles motion caused by velocity, and applies external forces and gravity.                                                           |
+//! | [`SolverPlugin`]       | Solves constraints (contacts and joints).                                                                                             |
+//! | [`IslandPlugin`]       | Splits bodies into independent simulation islands that can be solved in parallel.                                                     |
+//! | [`CcdPlugin`]          | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component to prevent tunneling. |
+//! | [`SleepingPlugin`]     | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                              |
+//! | [`RemoteBodyPlugin`]   | Interpolates [`RemoteBody`] entities between authoritative samples received over the network.                                         |
+//! | [`AerodynamicsPlugin`] | Applies aerodynamic [`Drag`] and [`Lift`] forces based on the [`Wind`]. Not included in the [`PhysicsPlugins`] by default.            |
+//! | [`BodyMovedPlugin`]    | Sends [`BodyMoved`] events for bodies that moved during a physics step. Not included in the [`PhysicsPlugins`] by default.             |
+//! | `CharacterControllerPlugin` | Moves kinematic `CharacterController`s with collide-and-slide movement, auto-stepping, and swimming. Not included in the [`PhysicsPlugins`] by default. |
+//! | `CrowdPlugin`          | Moves large numbers of simple kinematic `CrowdAgent`s with parallel ground probes. Not included in the [`PhysicsPlugins`] by default. |
+//! | `DebrisPlugin`         | Limits the number of live [`Debris`] bodies based on the [`DebrisManager`]. Not included in the [`PhysicsPlugins`] by default.        |
+//! | `VehiclePlugin`        | Simulates raycast vehicles with suspension, gearboxes, steering, and tire traction. 3D only. Not included in the [`PhysicsPlugins`] by default.  |
+//! | `ClothPlugin`          | Simulates [`Cloth`](particles::Cloth) as particles with XPBD constraints. Not included in the [`PhysicsPlugins`] by default.          |
+//! | `RopePlugin`           | Simulates [`Rope`](particles::Rope)s as chains of particles with XPBD constraints. Not included in the [`PhysicsPlugins`] by default. |
+//! | `SoftBodyPlugin`       | Simulates pressure-based [`SoftBody`](particles::SoftBody)s. 2D only. Not included in the [`PhysicsPlugins`] by default.              |
+//! | `FluidPlugin`          | Simulates position-based [`Fluid`](particles::Fluid)s that push dynamic bodies. Not included in the [`PhysicsPlugins`] by default.    |
+//! | `RagdollPlugin`        | Switches ragdolls built from skeletons between animation and simulation. 3D only. Not included in the [`PhysicsPlugins`] by default.  |
 //!
 //! # Accuracy
 //!
@@ -61,17 +74,76 @@
 //! [Gauss-Seidel]: https://en.wikipedia.org/wiki/Gauss%E2%80%93Seidel_method
 //! [Semi-implicit Euler]: https://en.wikipedia.org/wiki/Semi-implicit_Euler_method
 
+pub mod aerodynamics;
+pub mod body_moved;
 pub mod ccd;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod character_controller;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod crowd;
+pub mod debris;
 pub mod integrator;
+pub mod islands;
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+pub mod particles;
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+pub mod ragdoll;
+pub mod remote_body;
 pub mod rigid_body;
 pub mod sleeping;
 pub mod solver;
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+pub mod vehicle;
 
 /// Re-exports common types related to the rigid body dynamics functionality.
 pub mod prelude {
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use super::character_controller::{
+        resolve_movement, CharacterController, CharacterControllerPlugin, CharacterGround,
+        CharacterSwimming, FluidVolume, MovementInput, MovementResult, SurfaceClass, Walkable,
+    };
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use super::crowd::{CrowdAgent, CrowdConfig, CrowdPlugin};
+    #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+    pub use super::particles::{
+        Cloth, ClothPlugin, Fluid, FluidPlugin, ParticleAttachment, ParticleConstraint, Particles,
+        Rope, RopePlugin,
+    };
+    #[cfg(all(feature = "2d", any(feature = "parry-f32", feature = "parry-f64")))]
+    pub use super::particles::{SoftBody, SoftBodyPlugin};
+    #[cfg(all(
+        feature = "3d",
+        feature = "settings-asset",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use super::vehicle::{VehicleConfig, VehicleConfigHandle};
     pub use super::{
+        aerodynamics::{
+            AerodynamicsPlugin, AirDensity, Drag, Lift, ReferenceArea, Wind, WindVolume,
+        },
+        body_moved::{BodyMoved, BodyMovedConfig, BodyMovedPlugin},
         ccd::{CcdPlugin, SpeculativeMargin, SweepMode, SweptCcd},
-        integrator::{Gravity, IntegratorPlugin},
+        debris::{Debris, DebrisEviction, DebrisManager, DebrisPlugin},
+        integrator::{
+            ActiveGravityFields, Gravity, GravityField, GravityFieldKind, GravityFieldMode,
+            IntegratorPlugin, RigidBodyLimitsConfig,
+        },
+        islands::{
+            Island, IslandPlugin, IslandSplit, IslandsMerged, PhysicsIslandId, PhysicsIslands,
+        },
+        remote_body::{RemoteBody, RemoteBodyPlugin, RemoteSample},
         rigid_body::{
             mass_properties::{
                 bevy_heavy::{
@@ -90,8 +162,20 @@ pub mod prelude {
         sleeping::{DeactivationTime, SleepingPlugin, SleepingThreshold},
         solver::{
             joints::*,
-            schedule::{SolverSchedulePlugin, SolverSet, SubstepCount, SubstepSchedule},
-            PhysicsLengthUnit, SolverPlugin,
+            schedule::{
+                AdaptiveSubsteps, CurrentSubstep, SolverSchedulePlugin, SolverSet, SubstepCount,
+                SubstepSchedule,
+            },
+            xpbd::XpbdConstraintAppExt,
+            ContactStiffness, PhysicsLengthUnit, SolverPlugin,
+        },
+    };
+    #[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+    pub use super::{
+        ragdoll::{Ragdoll, RagdollBone, RagdollBuilder, RagdollJoint, RagdollMode, RagdollPlugin},
+        vehicle::{
+            Gearbox, TireModel, TorqueCurve, TractionCurve, Vehicle, VehiclePlugin, Wheel,
+            WheelContact,
         },
     };
     pub(crate) use crate::dynamics::rigid_body::mass_properties::{
@@ -0,0 +1,296 @@
+//! [`Cloth`] component and [`ClothPlugin`].
+
+use super::*;
+
+/// A plugin for simulating [`Cloth`].
+///
+/// The cloth is simulated inside the [substepping loop](SubstepSchedule) in
+/// [`SubstepSolverSet::SolveUserConstraints`](dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+/// after the rigid bodies have been moved for the substep.
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+#[derive(Default)]
+pub struct ClothPlugin;
+
+impl Plugin for ClothPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(Cloth, Particles, ParticleConstraint, ParticleAttachment)>();
+
+        app.add_systems(
+            SubstepSchedule,
+            simulate_cloth
+                .in_set(dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+        );
+    }
+}
+
+/// A component for a piece of cloth, simulated as a grid or mesh of particles by the [`ClothPlugin`].
+///
+/// Neighboring particles are connected by stretch constraints that keep the cloth from stretching,
+/// and particles two steps apart are connected by bend constraints that resist folding.
+/// Both are solved with [XPBD](dynamics::solver::xpbd), and their stiffness can be configured
+/// with [compliance](Self::stretch_compliance).
+///
+/// The particles are affected by [`Gravity`], and collide with regular [colliders](Collider)
+/// based on the [`CollisionLayers`] of the cloth entity. Particles can be [pinned](Particles::pin)
+/// in place or [attached](Particles::attach) to rigid bodies, which is useful for things like
+/// flags, capes, and nets. Attached dynamic bodies are pulled by the cloth.
+///
+/// The cloth dereferences to its [`Particles`]. The particle positions are in world space,
+/// and can be read with [`positions`](Particles::positions) to render the cloth,
+/// for example by updating the vertices of a mesh.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), ClothPlugin))
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     let pole = commands
+///         .spawn((
+///             RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "            Collider::rectangle(0.2, 4.0),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::cylinder(0.1, 4.0),")]
+///         ))
+///         .id();
+///
+///     // A flag with 20x10 particles attached to the top of the pole.
+///     // The pole is at the origin, so the local anchors are the same as the particle positions.
+///     let mut flag = Cloth::grid(Vector::Y, Vector::X * 2.0, Vector::Y, 20, 10);
+///     for row in 0..10 {
+///         let index = Cloth::grid_index(20, 0, row);
+///         let anchor = flag.positions()[index];
+///         flag.attach(index, pole, anchor);
+///     }
+///     commands.spawn(flag);
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Deref, DerefMut, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Cloth {
+    #[deref]
+    pub(crate) particles: Particles,
+    pub(crate) stretch_constraints: Vec<ParticleConstraint>,
+    pub(crate) bend_constraints: Vec<ParticleConstraint>,
+    /// The mass of each particle.
+    ///
+    /// Default: `0.01`
+    pub particle_mass: Scalar,
+    /// The compliance of the stretch constraints, the inverse of stiffness.
+    /// Zero means that the cloth can't be stretched.
+    ///
+    /// Default: `0.0`
+    pub stretch_compliance: Scalar,
+    /// The compliance of the bend constraints, the inverse of stiffness.
+    /// Larger values make the cloth fold more easily.
+    ///
+    /// Default: `0.01`
+    pub bend_compliance: Scalar,
+    /// The radius of the particles used for collisions.
+    ///
+    /// Default: `0.02`
+    pub thickness: Scalar,
+    /// The coefficient of friction between the particles and colliders.
+    ///
+    /// Default: `0.5`
+    pub friction: Scalar,
+    /// The linear damping of the particles, reducing their velocity over time.
+    ///
+    /// Default: `0.1`
+    pub damping: Scalar,
+}
+
+impl Cloth {
+    /// Creates a new [`Cloth`] with particles at the given world-space `positions`,
+    /// connected by stretch constraints along the given `edges`.
+    ///
+    /// No bend constraints are created. They can be added with [`with_bend_edges`](Self::with_bend_edges).
+    pub fn new(positions: Vec<Vector>, edges: impl IntoIterator<Item = [usize; 2]>) -> Self {
+        let stretch_constraints = edges
+            .into_iter()
+            .map(|[i, j]| ParticleConstraint::from_positions(&positions, i, j))
+            .collect();
+        Self {
+            particles: Particles::new(positions),
+            stretch_constraints,
+            bend_constraints: vec![],
+            particle_mass: 0.01,
+            stretch_compliance: 0.0,
+            bend_compliance: 0.01,
+            thickness: 0.02,
+            friction: 0.5,
+            damping: 0.1,
+        }
+    }
+
+    /// Creates a rectangular [`Cloth`] with a grid of `columns` x `rows` particles.
+    ///
+    /// The cloth starts at the world-space `origin` and spans the `width` and `height` vectors.
+    /// The particle in the given column and row is at the index returned by [`Cloth::grid_index`].
+    ///
+    /// Adjacent and diagonal particles are connected by stretch constraints,
+    /// and particles two steps apart are connected by bend constraints.
+    pub fn grid(
+        origin: Vector,
+        width: Vector,
+        height: Vector,
+        columns: usize,
+        rows: usize,
+    ) -> Self {
+        let columns = columns.max(2);
+        let rows = rows.max(2);
+
+        let mut positions = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                positions.push(
+                    origin
+                        + width * (column as Scalar / (columns - 1) as Scalar)
+                        + height * (row as Scalar / (rows - 1) as Scalar),
+                );
+            }
+        }
+
+        let index = |column: usize, row: usize| Self::grid_index(columns, column, row);
+        let mut edges = vec![];
+        let mut bend_edges = vec![];
+        for row in 0..rows {
+            for column in 0..columns {
+                if column + 1 < columns {
+                    edges.push([index(column, row), index(column + 1, row)]);
+                }
+                if row + 1 < rows {
+                    edges.push([index(column, row), index(column, row + 1)]);
+                }
+                // Shear constraints along the diagonals.
+                if column + 1 < columns && row + 1 < rows {
+                    edges.push([index(column, row), index(column + 1, row + 1)]);
+                    edges.push([index(column + 1, row), index(column, row + 1)]);
+                }
+                if column + 2 < columns {
+                    bend_edges.push([index(column, row), index(column + 2, row)]);
+                }
+                if row + 2 < rows {
+                    bend_edges.push([index(column, row), index(column, row + 2)]);
+                }
+            }
+        }
+
+        Self::new(positions, edges).with_bend_edges(bend_edges)
+    }
+
+    /// Returns the index of the particle in the given `column` and `row` of a [`Cloth::grid`]
+    /// with the given number of `columns`.
+    pub const fn grid_index(columns: usize, column: usize, row: usize) -> usize {
+        row * columns + column
+    }
+
+    /// Adds bend constraints along the given `edges`, keeping the particles at their current distance.
+    pub fn with_bend_edges(mut self, edges: impl IntoIterator<Item = [usize; 2]>) -> Self {
+        self.bend_constraints.extend(
+            edges
+                .into_iter()
+                .map(|[i, j]| ParticleConstraint::from_positions(&self.particles.positions, i, j)),
+        );
+        self
+    }
+
+    /// Sets the mass of each particle.
+    pub fn with_particle_mass(mut self, mass: Scalar) -> Self {
+        self.particle_mass = mass;
+        self
+    }
+
+    /// Sets the compliance of the stretch and bend constraints.
+    pub fn with_compliance(mut self, stretch_compliance: Scalar, bend_compliance: Scalar) -> Self {
+        self.stretch_compliance = stretch_compliance;
+        self.bend_compliance = bend_compliance;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_thickness(mut self, thickness: Scalar) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the coefficient of friction between the particles and colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+}
+
+/// Simulates [`Cloth`] for one substep.
+fn simulate_cloth(
+    mut cloths: Query<(&mut Cloth, Option<&CollisionLayers>)>,
+    mut bodies: ParticleBodies,
+    world: ParticleWorld,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (mut cloth, layers) in &mut cloths {
+        let cloth = &mut *cloth;
+
+        let inverse_masses = cloth.particles.inverse_masses(cloth.particle_mass);
+        let mut reactions = vec![Vector::ZERO; cloth.particles.len()];
+
+        cloth.particles.predict(
+            &inverse_masses,
+            gravity.0,
+            cloth.damping,
+            delta_secs,
+            &world,
+        );
+
+        cloth.particles.solve_constraints(
+            &cloth.stretch_constraints,
+            &inverse_masses,
+            cloth.stretch_compliance,
+            delta_secs,
+            &mut reactions,
+        );
+        cloth.particles.solve_constraints(
+            &cloth.bend_constraints,
+            &inverse_masses,
+            cloth.bend_compliance,
+            delta_secs,
+            &mut reactions,
+        );
+
+        cloth
+            .particles
+            .apply_attachment_reactions(&reactions, &mut bodies, &world);
+
+        cloth.particles.collide_and_update_velocities(
+            &inverse_masses,
+            cloth.thickness,
+            cloth.friction,
+            layers.copied().unwrap_or_default(),
+            &world,
+            delta_secs,
+        );
+    }
+}
@@ -0,0 +1,506 @@
+//! [`Fluid`] component and [`FluidPlugin`].
+
+use super::{spatial_hash::SpatialHash, *};
+
+/// A plugin for simulating [`Fluid`]s.
+///
+/// The fluids are simulated inside the [substepping loop](SubstepSchedule) in
+/// [`SubstepSolverSet::SolveUserConstraints`](dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+/// after the rigid bodies have been moved for the substep.
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+///
+/// This is an experimental feature, intended for small amounts of water in gameplay rather than
+/// accurate fluid dynamics.
+#[derive(Default)]
+pub struct FluidPlugin;
+
+impl Plugin for FluidPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Fluid>();
+
+        app.add_systems(
+            SubstepSchedule,
+            simulate_fluids
+                .in_set(dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+        );
+    }
+}
+
+/// A component for a body of liquid, simulated as particles by the [`FluidPlugin`]
+/// using Position Based Fluids (PBF).
+///
+/// Each particle has a density constraint that keeps the density around it at the rest density,
+/// making the fluid nearly incompressible. Nearby particles are found using a spatial hash.
+///
+/// The particles are affected by [`Gravity`], and collide with regular [colliders](Collider)
+/// based on the [`CollisionLayers`] of the fluid entity. The collisions are one-way,
+/// but dynamic rigid bodies in contact with the particles receive buoyancy and [drag](Self::drag)
+/// impulses, so that light bodies float and heavy bodies sink.
+///
+/// The particle positions are in world space, and can be read with [`positions`](Self::positions)
+/// to render the fluid, for example with sprites or a metaball shader.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), FluidPlugin))
+///         .add_systems(Startup, setup)
+///         .add_systems(Update, draw_fluid)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     // A pool with a floor and walls.
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::compound(vec![
+#[cfg_attr(
+    feature = "2d",
+    doc = "            (Position::from_xy(0.0, -3.0), Rotation::default(), Collider::rectangle(6.0, 0.2)),"
+)]
+#[cfg_attr(
+    feature = "2d",
+    doc = "            (Position::from_xy(-3.0, 0.0), Rotation::default(), Collider::rectangle(0.2, 6.0)),"
+)]
+#[cfg_attr(
+    feature = "2d",
+    doc = "            (Position::from_xy(3.0, 0.0), Rotation::default(), Collider::rectangle(0.2, 6.0)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            (Position::from_xyz(0.0, -3.0, 0.0), Rotation::default(), Collider::cuboid(6.0, 0.2, 6.0)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            (Position::from_xyz(-3.0, 0.0, 0.0), Rotation::default(), Collider::cuboid(0.2, 6.0, 6.0)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            (Position::from_xyz(3.0, 0.0, 0.0), Rotation::default(), Collider::cuboid(0.2, 6.0, 6.0)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            (Position::from_xyz(0.0, 0.0, -3.0), Rotation::default(), Collider::cuboid(6.0, 6.0, 0.2)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            (Position::from_xyz(0.0, 0.0, 3.0), Rotation::default(), Collider::cuboid(6.0, 6.0, 0.2)),"
+)]
+///         ]),
+///     ));
+///
+///     // Water filling the bottom of the pool.
+///     commands.spawn(Fluid::block(Vector::splat(-2.8), Vector::splat(2.8).with_y(0.0), 0.05));
+///
+///     // A wooden crate floating on the water.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 1.0, 1.0),")]
+///         ColliderDensity(500.0),
+///         Position(Vector::Y),
+///     ));
+/// }
+///
+/// fn draw_fluid(fluids: Query<&Fluid>, mut gizmos: Gizmos) {
+///     for fluid in &fluids {
+///         let radius = fluid.particle_radius as f32;
+///         for position in fluid.positions() {
+#[cfg_attr(
+    feature = "2d",
+    doc = "            let position = Vec2::new(position.x as f32, position.y as f32);"
+)]
+#[cfg_attr(
+    feature = "2d",
+    doc = "            gizmos.circle_2d(position, radius, Color::srgb(0.2, 0.5, 1.0));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            let position = Vec3::new(position.x as f32, position.y as f32, position.z as f32);"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            gizmos.sphere(position, radius, Color::srgb(0.2, 0.5, 1.0));"
+)]
+///         }
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Fluid {
+    pub(crate) positions: Vec<Vector>,
+    pub(crate) previous_positions: Vec<Vector>,
+    pub(crate) velocities: Vec<Vector>,
+    /// The radius of the particles. The particles are spaced two radii apart at rest,
+    /// and interact with other particles within four radii.
+    ///
+    /// Default: `0.05`
+    pub particle_radius: Scalar,
+    /// The rest density of the fluid in kilograms per cubic meter (or per square meter in 2D),
+    /// used for the masses of the particles.
+    ///
+    /// Default: `1000.0`
+    pub density: Scalar,
+    /// The number of times the density constraints are solved per substep.
+    ///
+    /// Default: `2`
+    pub iterations: u32,
+    /// The strength of the XSPH viscosity, smoothing the velocities of nearby particles.
+    /// Values between `0.0` and `1.0` are typical.
+    ///
+    /// Default: `0.05`
+    pub viscosity: Scalar,
+    /// The coefficient of friction between the particles and colliders.
+    ///
+    /// Default: `0.1`
+    pub friction: Scalar,
+    /// The rate at which the velocity of a dynamic body in contact with the particles
+    /// is matched to the velocity of the particles.
+    ///
+    /// Default: `1.0`
+    pub drag: Scalar,
+}
+
+impl Fluid {
+    /// Creates a new [`Fluid`] with particles at the given world-space `positions`.
+    pub fn new(positions: Vec<Vector>, particle_radius: Scalar) -> Self {
+        let count = positions.len();
+        Self {
+            previous_positions: positions.clone(),
+            positions,
+            velocities: vec![Vector::ZERO; count],
+            particle_radius,
+            density: 1000.0,
+            iterations: 2,
+            viscosity: 0.05,
+            friction: 0.1,
+            drag: 1.0,
+        }
+    }
+
+    /// Creates a [`Fluid`] that fills the box between the world-space `min` and `max` points
+    /// with particles of the given radius.
+    pub fn block(min: Vector, max: Vector, particle_radius: Scalar) -> Self {
+        let spacing = 2.0 * particle_radius;
+        let counts = ((max - min) / spacing).max(Vector::ONE);
+        let positions = lattice_points(Vector::ZERO, counts)
+            .map(|point| min + (point + Vector::splat(0.5)) * spacing)
+            .collect();
+        Self::new(positions, particle_radius)
+    }
+
+    /// Sets the rest density of the fluid in kilograms per cubic meter (or per square meter in 2D).
+    pub fn with_density(mut self, density: Scalar) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets the number of times the density constraints are solved per substep.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the strength of the XSPH viscosity.
+    pub fn with_viscosity(mut self, viscosity: Scalar) -> Self {
+        self.viscosity = viscosity;
+        self
+    }
+
+    /// Sets the coefficient of friction between the particles and colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the rate at which the velocity of dynamic bodies is matched to the velocity of the particles.
+    pub fn with_drag(mut self, drag: Scalar) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    /// Adds a particle at the given world-space position with the given velocity.
+    ///
+    /// This can be used for emitting fluid, for example from a faucet.
+    pub fn add_particle(&mut self, position: Vector, velocity: Vector) {
+        self.positions.push(position);
+        self.previous_positions.push(position);
+        self.velocities.push(velocity);
+    }
+
+    /// Removes the particles for which the given predicate returns `false`.
+    ///
+    /// The predicate is called with the position and velocity of each particle.
+    pub fn retain_particles(&mut self, mut predicate: impl FnMut(Vector, Vector) -> bool) {
+        let mut i = 0;
+        while i < self.positions.len() {
+            if predicate(self.positions[i], self.velocities[i]) {
+                i += 1;
+            } else {
+                self.positions.swap_remove(i);
+                self.previous_positions.swap_remove(i);
+                self.velocities.swap_remove(i);
+            }
+        }
+    }
+
+    /// Returns the world-space positions of the particles.
+    pub fn positions(&self) -> &[Vector] {
+        &self.positions
+    }
+
+    /// Returns the velocities of the particles.
+    pub fn velocities(&self) -> &[Vector] {
+        &self.velocities
+    }
+
+    /// Returns the number of particles.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if the fluid has no particles.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the mass of each particle.
+    pub fn particle_mass(&self) -> Scalar {
+        self.density * (2.0 * self.particle_radius).powi(DIM as i32)
+    }
+}
+
+/// Returns the points with integer coordinates from `min` (inclusive) to `max` (exclusive),
+/// truncating the coordinates of `min` and `max` to integers.
+#[cfg(feature = "2d")]
+fn lattice_points(min: Vector, max: Vector) -> impl Iterator<Item = Vector> {
+    let (min, max) = (min.as_ivec2(), max.as_ivec2());
+    (min.x..max.x)
+        .flat_map(move |x| (min.y..max.y).map(move |y| Vector::new(x as Scalar, y as Scalar)))
+}
+
+/// Returns the points with integer coordinates from `min` (inclusive) to `max` (exclusive),
+/// truncating the coordinates of `min` and `max` to integers.
+#[cfg(feature = "3d")]
+fn lattice_points(min: Vector, max: Vector) -> impl Iterator<Item = Vector> {
+    let (min, max) = (min.as_ivec3(), max.as_ivec3());
+    (min.x..max.x).flat_map(move |x| {
+        (min.y..max.y).flat_map(move |y| {
+            (min.z..max.z).map(move |z| Vector::new(x as Scalar, y as Scalar, z as Scalar))
+        })
+    })
+}
+
+/// The smoothing kernels used for computing densities and their gradients.
+struct Kernels {
+    radius: Scalar,
+    poly6_factor: Scalar,
+    spiky_gradient_factor: Scalar,
+}
+
+impl Kernels {
+    #[cfg(feature = "2d")]
+    fn new(radius: Scalar) -> Self {
+        Self {
+            radius,
+            poly6_factor: 4.0 / (PI * radius.powi(8)),
+            spiky_gradient_factor: -30.0 / (PI * radius.powi(5)),
+        }
+    }
+
+    #[cfg(feature = "3d")]
+    fn new(radius: Scalar) -> Self {
+        Self {
+            radius,
+            poly6_factor: 315.0 / (64.0 * PI * radius.powi(9)),
+            spiky_gradient_factor: -45.0 / (PI * radius.powi(6)),
+        }
+    }
+
+    fn poly6(&self, distance_squared: Scalar) -> Scalar {
+        let radius_squared = self.radius * self.radius;
+        if distance_squared >= radius_squared {
+            return 0.0;
+        }
+        self.poly6_factor * (radius_squared - distance_squared).powi(3)
+    }
+
+    fn spiky_gradient(&self, offset: Vector) -> Vector {
+        let distance = offset.length();
+        if distance >= self.radius || distance <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+        offset / distance * self.spiky_gradient_factor * (self.radius - distance).powi(2)
+    }
+}
+
+/// Relaxation added to the denominators of the density constraints for stability.
+const RELAXATION: Scalar = 1.0e-3;
+
+/// Simulates [`Fluid`]s for one substep.
+fn simulate_fluids(
+    mut fluids: Query<(&mut Fluid, Option<&CollisionLayers>)>,
+    mut bodies: ParticleBodies,
+    world: ParticleWorld,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+    mut spatial_hash: Local<SpatialHash>,
+    mut contacts: Local<Vec<ParticleContact>>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (mut fluid, layers) in &mut fluids {
+        let fluid = &mut *fluid;
+        if fluid.positions.is_empty() || fluid.particle_radius <= 0.0 {
+            continue;
+        }
+
+        let count = fluid.positions.len();
+        let spacing = 2.0 * fluid.particle_radius;
+        let kernels = Kernels::new(2.0 * spacing);
+        let particle_mass = fluid.particle_mass();
+        let inverse_masses = vec![1.0 / particle_mass; count];
+
+        // The rest density is computed from the kernel for particles on a uniform lattice,
+        // so that the fluid is at rest at the particle spacing.
+        let rest_density = particle_mass
+            * lattice_points(Vector::splat(-2.0), Vector::splat(3.0))
+                .map(|offset| kernels.poly6((offset * spacing).length_squared()))
+                .sum::<Scalar>();
+
+        // Predict the new positions of the particles.
+        predict_particles(
+            &mut fluid.positions,
+            &mut fluid.previous_positions,
+            &mut fluid.velocities,
+            &inverse_masses,
+            gravity.0,
+            0.0,
+            delta_secs,
+        );
+
+        // Find the neighbors of each particle.
+        spatial_hash.rebuild(&fluid.positions, kernels.radius);
+        let radius_squared = kernels.radius * kernels.radius;
+        let neighbors: Vec<Vec<usize>> = (0..count)
+            .map(|i| {
+                let mut neighbors = vec![];
+                spatial_hash.for_each_nearby(fluid.positions[i], |j| {
+                    if j != i
+                        && fluid.positions[i].distance_squared(fluid.positions[j]) < radius_squared
+                    {
+                        neighbors.push(j);
+                    }
+                });
+                neighbors
+            })
+            .collect();
+
+        // Solve the density constraints.
+        let mut lagrange = vec![0.0; count];
+        let mut deltas = vec![Vector::ZERO; count];
+        let gradient_scale = particle_mass / rest_density;
+        for _ in 0..fluid.iterations.max(1) {
+            for i in 0..count {
+                let position = fluid.positions[i];
+                let mut density = particle_mass * kernels.poly6(0.0);
+                let mut gradient_i = Vector::ZERO;
+                let mut gradient_sum_squared = 0.0;
+                for &j in &neighbors[i] {
+                    let offset = position - fluid.positions[j];
+                    density += particle_mass * kernels.poly6(offset.length_squared());
+                    let gradient = kernels.spiky_gradient(offset) * gradient_scale;
+                    gradient_i += gradient;
+                    gradient_sum_squared += gradient.length_squared();
+                }
+                gradient_sum_squared += gradient_i.length_squared();
+
+                // Only resist compression, so that particles at the free surface don't clump together.
+                let c = (density / rest_density - 1.0).max(0.0);
+                lagrange[i] = -c / (gradient_sum_squared + RELAXATION);
+            }
+
+            for i in 0..count {
+                let position = fluid.positions[i];
+                deltas[i] = neighbors[i]
+                    .iter()
+                    .map(|&j| {
+                        (lagrange[i] + lagrange[j])
+                            * kernels.spiky_gradient(position - fluid.positions[j])
+                    })
+                    .sum::<Vector>()
+                    * gradient_scale;
+            }
+            for (position, delta) in fluid.positions.iter_mut().zip(&deltas) {
+                *position += *delta;
+            }
+        }
+
+        // Push the particles out of colliders.
+        contacts.clear();
+        collide_particles_with_contacts(
+            &mut fluid.positions,
+            &fluid.previous_positions,
+            &inverse_masses,
+            fluid.particle_radius,
+            fluid.friction,
+            layers.copied().unwrap_or_default(),
+            &world,
+            Some(&mut contacts),
+        );
+
+        // Apply buoyancy and drag to dynamic bodies in contact with the particles.
+        for contact in contacts.iter() {
+            let Some(body_velocity) = bodies.velocity_at(contact.body, contact.point, &world)
+            else {
+                continue;
+            };
+            let particle_velocity = fluid.velocities[contact.particle];
+
+            // The reaction to pushing the particle out, and drag towards the particle velocity.
+            let impulse = -contact.normal * contact.penetration * particle_mass / delta_secs
+                + (particle_velocity - body_velocity)
+                    * (fluid.drag * delta_secs).min(1.0)
+                    * particle_mass;
+
+            bodies.apply_impulse(contact.body, contact.point, impulse, &world);
+        }
+
+        // Update the velocities based on the change in position.
+        update_particle_velocities(
+            &fluid.positions,
+            &fluid.previous_positions,
+            &mut fluid.velocities,
+            delta_secs,
+        );
+
+        // Apply XSPH viscosity to smooth the velocities of nearby particles.
+        if fluid.viscosity > 0.0 {
+            let velocities = fluid.velocities.clone();
+            for i in 0..count {
+                let position = fluid.positions[i];
+                let smoothing = neighbors[i]
+                    .iter()
+                    .map(|&j| {
+                        (velocities[j] - velocities[i])
+                            * kernels.poly6(position.distance_squared(fluid.positions[j]))
+                    })
+                    .sum::<Vector>();
+                fluid.velocities[i] += smoothing * fluid.viscosity * gradient_scale;
+            }
+        }
+    }
+}
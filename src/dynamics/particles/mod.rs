@@ -0,0 +1,687 @@
+//! Particle-based simulation of deformable objects like [`Cloth`], [`Rope`]s, [`Fluid`]s, and 2D soft bodies.
+//!
+//! Deformable objects are simulated as particles connected by distance constraints,
+//! solved with [XPBD](dynamics::solver::xpbd) inside the [substepping loop](SubstepSchedule).
+//! The particles collide with regular [colliders](Collider), and can be attached to [rigid bodies](RigidBody).
+//!
+//! The particles of ropes, cloth, and soft bodies are stored in [`Particles`].
+//! Collisions are one-way: the particles are pushed out of the colliders, but don't push them back.
+//! Attachments are two-way: attached particles follow their rigid bodies, and dynamic bodies
+//! receive the reaction impulses of the constraints pulling on the particles.
+
+mod cloth;
+mod fluid;
+mod rope;
+#[cfg(feature = "2d")]
+mod soft_body;
+mod spatial_hash;
+
+pub use cloth::*;
+pub use fluid::*;
+pub use rope::*;
+#[cfg(feature = "2d")]
+pub use soft_body::*;
+
+use crate::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// A distance constraint between two particles of a deformable object.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ParticleConstraint {
+    /// The indices of the constrained particles.
+    pub particles: [usize; 2],
+    /// The distance that the particles are kept at.
+    pub rest_length: Scalar,
+}
+
+impl ParticleConstraint {
+    /// Creates a [`ParticleConstraint`] that keeps the given particles at their current distance.
+    pub(crate) fn from_positions(positions: &[Vector], particle1: usize, particle2: usize) -> Self {
+        Self {
+            particles: [particle1, particle2],
+            rest_length: positions[particle1].distance(positions[particle2]),
+        }
+    }
+
+    /// Moves the particles towards the rest length, weighted by their inverse masses.
+    ///
+    /// Returns the correction, which is subtracted from the first particle and added to the second particle
+    /// before being scaled by their inverse masses.
+    pub(crate) fn solve(
+        &self,
+        positions: &mut [Vector],
+        inverse_masses: &[Scalar],
+        compliance: Scalar,
+        dt: Scalar,
+    ) -> Vector {
+        let [i, j] = self.particles;
+        let w_sum = inverse_masses[i] + inverse_masses[j];
+        if w_sum <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let offset = positions[j] - positions[i];
+        let distance = offset.length();
+        if distance <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        // The Lagrange multipliers are reset every substep, so they can be omitted.
+        let c = distance - self.rest_length;
+        let delta_lagrange = -c / (w_sum + compliance / dt.powi(2));
+        let correction = offset / distance * delta_lagrange;
+
+        positions[i] -= correction * inverse_masses[i];
+        positions[j] += correction * inverse_masses[j];
+
+        correction
+    }
+}
+
+/// The particles of a deformable object like a [`Rope`] or [`Cloth`].
+///
+/// Particles can be [pinned](Self::pin) in place or [attached](Self::attach) to rigid bodies.
+/// The positions are in world space.
+#[derive(Reflect, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct Particles {
+    pub(crate) positions: Vec<Vector>,
+    pub(crate) previous_positions: Vec<Vector>,
+    pub(crate) velocities: Vec<Vector>,
+    pub(crate) pinned: Vec<bool>,
+    pub(crate) attachments: Vec<ParticleAttachment>,
+}
+
+impl Particles {
+    /// Creates [`Particles`] at rest at the given world-space `positions`.
+    pub fn new(positions: Vec<Vector>) -> Self {
+        let count = positions.len();
+        Self {
+            previous_positions: positions.clone(),
+            positions,
+            velocities: vec![Vector::ZERO; count],
+            pinned: vec![false; count],
+            attachments: vec![],
+        }
+    }
+
+    /// Returns the number of particles.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if there are no particles.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Pins the particle at the given index in place, so that it isn't moved by the simulation.
+    pub fn pin(&mut self, index: usize) {
+        self.pinned[index] = true;
+    }
+
+    /// Unpins the particle at the given index, and removes its attachments.
+    pub fn unpin(&mut self, index: usize) {
+        self.pinned[index] = false;
+        self.attachments
+            .retain(|attachment| attachment.particle != index);
+    }
+
+    /// Attaches the particle at the given index to the `local_anchor` on the given rigid body.
+    ///
+    /// The particle follows the attachment point. If the body is dynamic, the constraints pulling on
+    /// the particle also pull on the body.
+    pub fn attach(&mut self, index: usize, body: Entity, local_anchor: Vector) {
+        self.pinned[index] = true;
+        self.attachments.push(ParticleAttachment {
+            particle: index,
+            body,
+            local_anchor,
+        });
+    }
+
+    /// Returns the world-space positions of the particles.
+    pub fn positions(&self) -> &[Vector] {
+        &self.positions
+    }
+
+    /// Returns the velocities of the particles.
+    pub fn velocities(&self) -> &[Vector] {
+        &self.velocities
+    }
+
+    /// Returns the attachments of the particles to rigid bodies.
+    pub fn attachments(&self) -> &[ParticleAttachment] {
+        &self.attachments
+    }
+
+    /// Returns `true` if the particle at the given index is pinned or attached.
+    pub fn is_pinned(&self, index: usize) -> bool {
+        self.pinned[index]
+    }
+
+    /// Sets the world-space position of the particle at the given index, resetting its velocity.
+    pub fn set_position(&mut self, index: usize, position: Vector) {
+        self.positions[index] = position;
+        self.previous_positions[index] = position;
+        self.velocities[index] = Vector::ZERO;
+    }
+
+    /// Returns the inverse masses of the particles with the given `mass`, with zero for pinned particles.
+    pub(crate) fn inverse_masses(&self, mass: Scalar) -> Vec<Scalar> {
+        let inverse_mass = if mass > 0.0 { 1.0 / mass } else { 0.0 };
+        self.pinned
+            .iter()
+            .map(|&pinned| if pinned { 0.0 } else { inverse_mass })
+            .collect()
+    }
+
+    /// Predicts the new positions of the free particles, and moves the attached particles with their bodies.
+    pub(crate) fn predict(
+        &mut self,
+        inverse_masses: &[Scalar],
+        gravity: Vector,
+        damping: Scalar,
+        delta_secs: Scalar,
+        world: &ParticleWorld,
+    ) {
+        predict_particles(
+            &mut self.positions,
+            &mut self.previous_positions,
+            &mut self.velocities,
+            inverse_masses,
+            gravity,
+            damping,
+            delta_secs,
+        );
+
+        for attachment in &self.attachments {
+            if let Some(position) = world.attachment_position(attachment) {
+                self.positions[attachment.particle] = position;
+            }
+        }
+    }
+
+    /// Solves the given distance `constraints`, and adds the impulses that they apply
+    /// to each particle to the `reactions`.
+    pub(crate) fn solve_constraints(
+        &mut self,
+        constraints: &[ParticleConstraint],
+        inverse_masses: &[Scalar],
+        compliance: Scalar,
+        delta_secs: Scalar,
+        reactions: &mut [Vector],
+    ) {
+        for constraint in constraints {
+            let correction =
+                constraint.solve(&mut self.positions, inverse_masses, compliance, delta_secs);
+            let [i, j] = constraint.particles;
+            reactions[i] -= correction / delta_secs;
+            reactions[j] += correction / delta_secs;
+        }
+    }
+
+    /// Applies the `reactions` of the attached particles to their rigid bodies.
+    ///
+    /// Attached particles don't move in response to the constraints, so the impulses
+    /// that the constraints apply to them are transferred to the bodies instead.
+    pub(crate) fn apply_attachment_reactions(
+        &self,
+        reactions: &[Vector],
+        bodies: &mut ParticleBodies,
+        world: &ParticleWorld,
+    ) {
+        for attachment in &self.attachments {
+            bodies.apply_impulse(
+                attachment.body,
+                self.positions[attachment.particle],
+                reactions[attachment.particle],
+                world,
+            );
+        }
+    }
+
+    /// Pushes the free particles with the given `radius` out of the colliders that interact with the given `layers`,
+    /// and updates the velocities of the particles based on their change in position.
+    pub(crate) fn collide_and_update_velocities(
+        &mut self,
+        inverse_masses: &[Scalar],
+        radius: Scalar,
+        friction: Scalar,
+        layers: CollisionLayers,
+        world: &ParticleWorld,
+        delta_secs: Scalar,
+    ) {
+        collide_particles(
+            &mut self.positions,
+            &self.previous_positions,
+            inverse_masses,
+            radius,
+            friction,
+            layers,
+            world,
+        );
+        update_particle_velocities(
+            &self.positions,
+            &self.previous_positions,
+            &mut self.velocities,
+            delta_secs,
+        );
+    }
+}
+
+/// Attaches a particle of a deformable object to a point on a [rigid body](RigidBody).
+///
+/// Attached particles follow the body. If the body is dynamic, it receives the reaction impulses
+/// of the constraints pulling on the particle, so that for example a rope can pull the body along.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ParticleAttachment {
+    /// The index of the attached particle.
+    pub particle: usize,
+    /// The rigid body that the particle is attached to.
+    pub body: Entity,
+    /// The attachment point in the local space of the body.
+    pub local_anchor: Vector,
+}
+
+/// A collider that particles can collide with during a substep.
+pub(crate) struct ParticleCollider<'a> {
+    collider: &'a Collider,
+    body: Option<Entity>,
+    position: Vector,
+    rotation: Rotation,
+}
+
+/// A contact between a particle and a collider attached to a rigid body,
+/// created when the particle is pushed out of the collider.
+pub(crate) struct ParticleContact {
+    /// The index of the particle.
+    pub particle: usize,
+    /// The rigid body that the collider is attached to.
+    pub body: Entity,
+    /// The world-space position of the particle after it was pushed out.
+    pub point: Vector,
+    /// The world-space contact normal, pointing from the collider towards the particle.
+    pub normal: Vector,
+    /// The distance that the particle was pushed along the normal.
+    pub penetration: Scalar,
+}
+
+/// A [`SystemParam`] for reading the current poses of rigid bodies and colliders
+/// inside the [substepping loop](SubstepSchedule), where [`Position`] isn't updated yet.
+#[derive(SystemParam)]
+pub(crate) struct ParticleWorld<'w, 's> {
+    bodies: Query<
+        'w,
+        's,
+        (
+            &'static Position,
+            &'static Rotation,
+            &'static AccumulatedTranslation,
+            &'static PreviousRotation,
+            &'static ComputedCenterOfMass,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
+    #[allow(clippy::type_complexity)]
+    colliders: Query<
+        'w,
+        's,
+        (
+            &'static Collider,
+            &'static ColliderAabb,
+            &'static Position,
+            &'static Rotation,
+            Option<&'static ColliderParent>,
+            Option<&'static ColliderTransform>,
+            Option<&'static CollisionLayers>,
+        ),
+        (
+            Without<Sensor>,
+            Without<ColliderDisabled>,
+            Without<QueryOnly>,
+        ),
+    >,
+    pipeline: Option<Res<'w, SpatialQueryPipeline>>,
+}
+
+impl ParticleWorld<'_, '_> {
+    /// Returns the current position and rotation of the given rigid body.
+    pub(crate) fn body_pose(&self, entity: Entity) -> Option<(Vector, Rotation)> {
+        let (position, rotation, translation, previous_rotation, center_of_mass) =
+            self.bodies.get(entity).ok()?;
+        let position = position.0
+            + crate::utils::get_pos_translation(
+                translation,
+                previous_rotation,
+                rotation,
+                center_of_mass,
+            );
+        Some((position, *rotation))
+    }
+
+    /// Returns the current world-space position of the given attachment.
+    pub(crate) fn attachment_position(&self, attachment: &ParticleAttachment) -> Option<Vector> {
+        self.body_pose(attachment.body)
+            .map(|(position, rotation)| position + rotation * attachment.local_anchor)
+    }
+
+    /// Returns the colliders that interact with the given `layers`
+    /// and whose [`ColliderAabb`] intersects the given `aabb`.
+    ///
+    /// The candidates are found using the [`SpatialQueryPipeline`] if it exists.
+    /// The pipeline is only updated at the end of the physics step, so the current
+    /// [`ColliderAabb`] of each candidate is still tested against the `aabb`.
+    pub(crate) fn colliders_in(
+        &self,
+        aabb: ColliderAabb,
+        layers: CollisionLayers,
+    ) -> Vec<ParticleCollider> {
+        let Some(pipeline) = &self.pipeline else {
+            // Without the spatial query pipeline, test all colliders.
+            return self
+                .colliders
+                .iter()
+                .filter_map(|item| self.particle_collider(item, aabb, layers))
+                .collect();
+        };
+
+        let mut colliders = vec![];
+        pipeline.aabb_intersections_with_aabb_callback(aabb, |entity| {
+            if let Some(collider) = self
+                .colliders
+                .get(entity)
+                .ok()
+                .and_then(|item| self.particle_collider(item, aabb, layers))
+            {
+                colliders.push(collider);
+            }
+            true
+        });
+        colliders
+    }
+
+    /// Returns the [`ParticleCollider`] for the given collider if it interacts with the given `layers`
+    /// and its [`ColliderAabb`] intersects the given `aabb`.
+    #[allow(clippy::type_complexity)]
+    fn particle_collider<'a>(
+        &self,
+        (collider, collider_aabb, position, rotation, parent, collider_transform, collider_layers): (
+            &'a Collider,
+            &ColliderAabb,
+            &Position,
+            &Rotation,
+            Option<&ColliderParent>,
+            Option<&ColliderTransform>,
+            Option<&CollisionLayers>,
+        ),
+        aabb: ColliderAabb,
+        layers: CollisionLayers,
+    ) -> Option<ParticleCollider<'a>> {
+        if !collider_aabb.intersects(&aabb)
+            || collider_layers
+                .is_some_and(|collider_layers| !collider_layers.interacts_with(layers))
+        {
+            return None;
+        }
+
+        // Colliders attached to bodies move with the current pose of the body.
+        let pose = parent
+            .zip(collider_transform)
+            .and_then(|(parent, collider_transform)| {
+                let (body_position, body_rotation) = self.body_pose(parent.get())?;
+                Some((
+                    body_position + body_rotation * collider_transform.translation,
+                    body_rotation * collider_transform.rotation,
+                ))
+            });
+        let (position, rotation) = pose.unwrap_or((position.0, *rotation));
+
+        Some(ParticleCollider {
+            collider,
+            body: parent.map(|parent| parent.get()),
+            position,
+            rotation,
+        })
+    }
+}
+
+/// A [`SystemParam`] for applying impulses from particles to dynamic [rigid bodies](RigidBody)
+/// inside the [substepping loop](SubstepSchedule).
+#[derive(SystemParam)]
+pub(crate) struct ParticleBodies<'w, 's> {
+    #[allow(clippy::type_complexity)]
+    bodies: Query<
+        'w,
+        's,
+        (
+            &'static RigidBody,
+            &'static mut LinearVelocity,
+            &'static mut AngularVelocity,
+            &'static ComputedMass,
+            &'static ComputedAngularInertia,
+            &'static ComputedCenterOfMass,
+            Option<&'static LockedAxes>,
+        ),
+        RigidBodyActiveFilter,
+    >,
+}
+
+impl ParticleBodies<'_, '_> {
+    /// Returns the velocity of the given dynamic rigid body at the given world-space `point`,
+    /// or `None` if the body isn't dynamic.
+    pub(crate) fn velocity_at(
+        &self,
+        body: Entity,
+        point: Vector,
+        world: &ParticleWorld,
+    ) -> Option<Vector> {
+        let (rb, lin_vel, ang_vel, _, _, center_of_mass, _) = self.bodies.get(body).ok()?;
+        if !rb.is_dynamic() {
+            return None;
+        }
+        let (position, rotation) = world.body_pose(body)?;
+        let r = point - (position + rotation * center_of_mass.0);
+        #[cfg(feature = "2d")]
+        let velocity = lin_vel.0 + ang_vel.0 * r.perp();
+        #[cfg(feature = "3d")]
+        let velocity = lin_vel.0 + ang_vel.0.cross(r);
+        Some(velocity)
+    }
+
+    /// Applies the given `impulse` to the given dynamic rigid body at the given world-space `point`.
+    pub(crate) fn apply_impulse(
+        &mut self,
+        body: Entity,
+        point: Vector,
+        impulse: Vector,
+        world: &ParticleWorld,
+    ) {
+        let Ok((rb, mut lin_vel, mut ang_vel, mass, angular_inertia, center_of_mass, locked_axes)) =
+            self.bodies.get_mut(body)
+        else {
+            return;
+        };
+        let Some((position, rotation)) = world.body_pose(body) else {
+            return;
+        };
+        if !rb.is_dynamic() {
+            return;
+        }
+
+        let r = point - (position + rotation * center_of_mass.0);
+        #[cfg(feature = "2d")]
+        let inverse_inertia = angular_inertia.inverse();
+        #[cfg(feature = "3d")]
+        let inverse_inertia = angular_inertia.rotated(rotation.0).inverse();
+
+        let locked_axes = locked_axes.copied().unwrap_or_default();
+        lin_vel.0 += locked_axes.apply_to_vec(impulse * mass.inverse());
+        ang_vel.0 += locked_axes.apply_to_angular_velocity(inverse_inertia * cross(r, impulse));
+    }
+}
+
+/// Stores the current positions as the previous positions, and moves the free particles
+/// based on their velocities, `gravity`, and linear `damping`.
+pub(crate) fn predict_particles(
+    positions: &mut [Vector],
+    previous_positions: &mut [Vector],
+    velocities: &mut [Vector],
+    inverse_masses: &[Scalar],
+    gravity: Vector,
+    damping: Scalar,
+    delta_secs: Scalar,
+) {
+    let damping = 1.0 / (1.0 + delta_secs * damping);
+    for (i, &inverse_mass) in inverse_masses.iter().enumerate() {
+        previous_positions[i] = positions[i];
+        if inverse_mass > 0.0 {
+            velocities[i] = (velocities[i] + gravity * delta_secs) * damping;
+            positions[i] += velocities[i] * delta_secs;
+        }
+    }
+}
+
+/// Computes the velocities of the particles based on the change in position.
+pub(crate) fn update_particle_velocities(
+    positions: &[Vector],
+    previous_positions: &[Vector],
+    velocities: &mut [Vector],
+    delta_secs: Scalar,
+) {
+    for i in 0..positions.len() {
+        velocities[i] = (positions[i] - previous_positions[i]) / delta_secs;
+    }
+}
+
+/// Computes the [`ColliderAabb`] containing the given particles grown by the given `margin`.
+fn particle_aabb(positions: &[Vector], margin: Scalar) -> ColliderAabb {
+    let (min, max) = positions.iter().fold(
+        (Vector::INFINITY, Vector::NEG_INFINITY),
+        |(min, max), position| (min.min(*position), max.max(*position)),
+    );
+    ColliderAabb::from_min_max(min, max).grow(Vector::splat(margin))
+}
+
+/// Pushes the free particles with the given `radius` out of the colliders that interact with the given `layers`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn collide_particles(
+    positions: &mut [Vector],
+    previous_positions: &[Vector],
+    inverse_masses: &[Scalar],
+    radius: Scalar,
+    friction: Scalar,
+    layers: CollisionLayers,
+    world: &ParticleWorld,
+) {
+    collide_particles_with_contacts(
+        positions,
+        previous_positions,
+        inverse_masses,
+        radius,
+        friction,
+        layers,
+        world,
+        None,
+    );
+}
+
+/// Pushes the free particles with the given `radius` out of the colliders that interact with the given `layers`,
+/// and collects the contacts with colliders attached to rigid bodies into `contacts`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn collide_particles_with_contacts(
+    positions: &mut [Vector],
+    previous_positions: &[Vector],
+    inverse_masses: &[Scalar],
+    radius: Scalar,
+    friction: Scalar,
+    layers: CollisionLayers,
+    world: &ParticleWorld,
+    mut contacts: Option<&mut Vec<ParticleContact>>,
+) {
+    let aabb = particle_aabb(positions, radius);
+    let colliders = world.colliders_in(aabb, layers);
+    if colliders.is_empty() {
+        return;
+    }
+
+    for (i, &inverse_mass) in inverse_masses.iter().enumerate() {
+        if inverse_mass > 0.0 {
+            collide_particle(
+                i,
+                &mut positions[i],
+                previous_positions[i],
+                radius,
+                friction,
+                &colliders,
+                contacts.as_deref_mut(),
+            );
+        }
+    }
+}
+
+/// Pushes a particle with the given `radius` out of the given colliders.
+///
+/// The tangential motion of the particle since `previous_position` is reduced by friction
+/// proportional to the penetration depth.
+fn collide_particle(
+    index: usize,
+    position: &mut Vector,
+    previous_position: Vector,
+    radius: Scalar,
+    friction: Scalar,
+    colliders: &[ParticleCollider],
+    mut contacts: Option<&mut Vec<ParticleContact>>,
+) {
+    for collider in colliders {
+        let (point, inside) =
+            collider
+                .collider
+                .project_point(collider.position, collider.rotation, *position, false);
+        let offset = *position - point;
+        let distance = offset.length();
+        if distance <= Scalar::EPSILON {
+            continue;
+        }
+
+        let (normal, penetration) = if inside {
+            (-offset / distance, distance + radius)
+        } else {
+            (offset / distance, radius - distance)
+        };
+        if penetration <= 0.0 {
+            continue;
+        }
+
+        *position += normal * penetration;
+
+        if let (Some(contacts), Some(body)) = (contacts.as_deref_mut(), collider.body) {
+            contacts.push(ParticleContact {
+                particle: index,
+                body,
+                point: *position,
+                normal,
+                penetration,
+            });
+        }
+
+        // Position-based Coulomb friction.
+        let displacement = *position - previous_position;
+        let tangential = displacement - normal * displacement.dot(normal);
+        let tangential_length = tangential.length();
+        let max_friction = friction * penetration;
+        if tangential_length <= max_friction {
+            *position -= tangential;
+        } else if tangential_length > Scalar::EPSILON {
+            *position -= tangential * (max_friction / tangential_length);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! [`Rope`] component and [`RopePlugin`].
+
+use super::*;
+
+/// A plugin for simulating [`Rope`]s.
+///
+/// The ropes are simulated inside the [substepping loop](SubstepSchedule) in
+/// [`SubstepSolverSet::SolveUserConstraints`](dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+/// after the rigid bodies have been moved for the substep.
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+#[derive(Default)]
+pub struct RopePlugin;
+
+impl Plugin for RopePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(Rope, Particles, ParticleConstraint, ParticleAttachment)>();
+
+        app.add_systems(
+            SubstepSchedule,
+            simulate_ropes
+                .in_set(dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+        );
+    }
+}
+
+/// A component for a rope or chain, simulated as a line of particles by the [`RopePlugin`].
+///
+/// Consecutive particles are connected by distance constraints, solved with [XPBD](dynamics::solver::xpbd).
+/// This is much cheaper and more stable than a chain of small rigid bodies connected by joints,
+/// especially for long ropes. Optionally, particles two steps apart can also be connected
+/// by [bend constraints](Self::bend_compliance) to make the rope resist bending, like a cable.
+///
+/// The particles are affected by [`Gravity`], and collide with regular [colliders](Collider)
+/// based on the [`CollisionLayers`] of the rope entity. Particles can be [pinned](Particles::pin)
+/// in place or [attached](Particles::attach) to rigid bodies. Attached dynamic bodies are pulled by the rope,
+/// so it can be used to hang or tow objects.
+///
+/// Note that only the particles collide with colliders, not the segments between them.
+/// To keep the rope from passing through thin colliders, use enough segments for the
+/// particles to be closer to each other than the thickness of the colliders.
+///
+/// The rope dereferences to its [`Particles`]. The particle positions are in world space,
+/// and can be read with [`positions`](Particles::positions) to render the rope.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), RopePlugin))
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     let ceiling = commands
+///         .spawn((
+///             RigidBody::Static,
+///             Position(Vector::Y * 5.0),
+#[cfg_attr(feature = "2d", doc = "            Collider::rectangle(4.0, 0.2),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::cuboid(4.0, 0.2, 4.0),")]
+///         ))
+///         .id();
+///
+///     // A rope with 40 segments hanging from the bottom of the ceiling.
+///     let mut rope = Rope::new(Vector::Y * 4.9, Vector::X * 4.0 + Vector::Y * 4.9, 40)
+///         .with_radius(0.03);
+///     rope.attach_start(ceiling, Vector::NEG_Y * 0.1);
+///     commands.spawn(rope);
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Deref, DerefMut, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Rope {
+    #[deref]
+    pub(crate) particles: Particles,
+    pub(crate) stretch_constraints: Vec<ParticleConstraint>,
+    pub(crate) bend_constraints: Vec<ParticleConstraint>,
+    /// The mass of each particle.
+    ///
+    /// Default: `0.01`
+    pub particle_mass: Scalar,
+    /// The compliance of the distance constraints between consecutive particles, the inverse of stiffness.
+    /// Zero means that the rope can't be stretched.
+    ///
+    /// Default: `0.0`
+    pub stretch_compliance: Scalar,
+    /// The compliance of the bend constraints, the inverse of stiffness.
+    /// If `None`, the rope bends freely.
+    ///
+    /// Default: `None`
+    pub bend_compliance: Option<Scalar>,
+    /// The number of times the constraints are solved per substep.
+    ///
+    /// Long ropes need more iterations to keep them from stretching under load.
+    ///
+    /// Default: `4`
+    pub iterations: u32,
+    /// The radius of the particles used for collisions.
+    ///
+    /// Default: `0.05`
+    pub radius: Scalar,
+    /// The coefficient of friction between the particles and colliders.
+    ///
+    /// Default: `0.5`
+    pub friction: Scalar,
+    /// The linear damping of the particles, reducing their velocity over time.
+    ///
+    /// Default: `0.1`
+    pub damping: Scalar,
+}
+
+impl Rope {
+    /// Creates a straight [`Rope`] from `start` to `end` in world space,
+    /// divided into the given number of `segments`.
+    pub fn new(start: Vector, end: Vector, segments: usize) -> Self {
+        let segments = segments.max(1);
+        let points = (0..=segments)
+            .map(|i| start.lerp(end, i as Scalar / segments as Scalar))
+            .collect();
+        Self::from_points(points)
+    }
+
+    /// Creates a [`Rope`] with particles at the given world-space `points`.
+    ///
+    /// The rest lengths of the segments are the distances between consecutive points.
+    pub fn from_points(points: Vec<Vector>) -> Self {
+        let stretch_constraints = (1..points.len())
+            .map(|i| ParticleConstraint::from_positions(&points, i - 1, i))
+            .collect();
+        let bend_constraints = (2..points.len())
+            .map(|i| ParticleConstraint::from_positions(&points, i - 2, i))
+            .collect();
+        Self {
+            particles: Particles::new(points),
+            stretch_constraints,
+            bend_constraints,
+            particle_mass: 0.01,
+            stretch_compliance: 0.0,
+            bend_compliance: None,
+            iterations: 4,
+            radius: 0.05,
+            friction: 0.5,
+            damping: 0.1,
+        }
+    }
+
+    /// Sets the mass of each particle.
+    pub fn with_particle_mass(mut self, mass: Scalar) -> Self {
+        self.particle_mass = mass;
+        self
+    }
+
+    /// Sets the compliance of the distance constraints between consecutive particles.
+    pub fn with_stretch_compliance(mut self, compliance: Scalar) -> Self {
+        self.stretch_compliance = compliance;
+        self
+    }
+
+    /// Makes the rope resist bending with the given compliance.
+    pub fn with_bend_compliance(mut self, compliance: Scalar) -> Self {
+        self.bend_compliance = Some(compliance);
+        self
+    }
+
+    /// Sets the number of times the constraints are solved per substep.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the coefficient of friction between the particles and colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Attaches the first particle to the `local_anchor` on the given rigid body.
+    pub fn attach_start(&mut self, body: Entity, local_anchor: Vector) {
+        self.attach(0, body, local_anchor);
+    }
+
+    /// Attaches the last particle to the `local_anchor` on the given rigid body.
+    pub fn attach_end(&mut self, body: Entity, local_anchor: Vector) {
+        self.attach(self.particles.len() - 1, body, local_anchor);
+    }
+
+    /// Returns the total rest length of the rope.
+    pub fn rest_length(&self) -> Scalar {
+        self.stretch_constraints
+            .iter()
+            .map(|constraint| constraint.rest_length)
+            .sum()
+    }
+
+    /// Returns the current length of the rope along its particles.
+    pub fn current_length(&self) -> Scalar {
+        self.particles
+            .positions
+            .windows(2)
+            .map(|pair| pair[0].distance(pair[1]))
+            .sum()
+    }
+}
+
+/// Simulates [`Rope`]s for one substep.
+fn simulate_ropes(
+    mut ropes: Query<(&mut Rope, Option<&CollisionLayers>)>,
+    mut bodies: ParticleBodies,
+    world: ParticleWorld,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (mut rope, layers) in &mut ropes {
+        let rope = &mut *rope;
+
+        let inverse_masses = rope.particles.inverse_masses(rope.particle_mass);
+        let mut reactions = vec![Vector::ZERO; rope.particles.len()];
+
+        rope.particles
+            .predict(&inverse_masses, gravity.0, rope.damping, delta_secs, &world);
+
+        for _ in 0..rope.iterations.max(1) {
+            rope.particles.solve_constraints(
+                &rope.stretch_constraints,
+                &inverse_masses,
+                rope.stretch_compliance,
+                delta_secs,
+                &mut reactions,
+            );
+            if let Some(bend_compliance) = rope.bend_compliance {
+                rope.particles.solve_constraints(
+                    &rope.bend_constraints,
+                    &inverse_masses,
+                    bend_compliance,
+                    delta_secs,
+                    &mut reactions,
+                );
+            }
+        }
+
+        rope.particles
+            .apply_attachment_reactions(&reactions, &mut bodies, &world);
+
+        rope.particles.collide_and_update_velocities(
+            &inverse_masses,
+            rope.radius,
+            rope.friction,
+            layers.copied().unwrap_or_default(),
+            &world,
+            delta_secs,
+        );
+    }
+}
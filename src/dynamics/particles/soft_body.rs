@@ -0,0 +1,326 @@
+//! [`SoftBody`] component and [`SoftBodyPlugin`].
+
+use super::*;
+
+/// A plugin for simulating 2D [`SoftBody`]s.
+///
+/// The soft bodies are simulated inside the [substepping loop](SubstepSchedule) in
+/// [`SubstepSolverSet::SolveUserConstraints`](dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+/// after the rigid bodies have been moved for the substep.
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+#[derive(Default)]
+pub struct SoftBodyPlugin;
+
+impl Plugin for SoftBodyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(SoftBody, Particles, ParticleConstraint, ParticleAttachment)>();
+
+        app.add_systems(
+            SubstepSchedule,
+            simulate_soft_bodies
+                .in_set(dynamics::solver::schedule::SubstepSolverSet::SolveUserConstraints),
+        );
+    }
+}
+
+/// A component for a deformable 2D soft body, simulated by the [`SoftBodyPlugin`].
+///
+/// The soft body is a closed loop of particles, like a balloon. Consecutive particles are connected
+/// by distance constraints, and an area constraint pushes the particles outwards to keep the enclosed
+/// area at the [`pressure`](Self::pressure) times the rest area. Both are solved with [XPBD](dynamics::solver::xpbd),
+/// and lowering the pressure or increasing the [compliance](Self::area_compliance) makes the body squishier.
+/// This is useful for things like jelly-like characters and squishy props.
+///
+/// The particles are affected by [`Gravity`], and collide with regular [colliders](Collider)
+/// based on the [`CollisionLayers`] of the soft body entity. Soft bodies don't collide with each other.
+///
+/// The soft body dereferences to its [`Particles`]. The particle positions are in world space
+/// and in counterclockwise order, and can be read with [`positions`](Particles::positions)
+/// to render the soft body. Particles can also be [attached](Particles::attach) to rigid bodies,
+/// which pulls dynamic bodies along with the soft body.
+///
+/// # Example
+///
+/// ```no_run
+/// use avian2d::prelude::*;
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), SoftBodyPlugin))
+///         .add_systems(Startup, setup)
+///         .add_systems(Update, jump)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::rectangle(20.0, 1.0),
+///         Position(Vector::NEG_Y * 5.0),
+///     ));
+///
+///     // A slightly deflated blob of jelly with 32 particles.
+///     commands.spawn(SoftBody::circle(Vector::ZERO, 1.0, 32).with_pressure(0.9));
+/// }
+///
+/// fn jump(keyboard: Res<ButtonInput<KeyCode>>, mut soft_bodies: Query<&mut SoftBody>) {
+///     if keyboard.just_pressed(KeyCode::Space) {
+///         for mut soft_body in &mut soft_bodies {
+///             soft_body.add_velocity(Vector::Y * 5.0);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Deref, DerefMut, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct SoftBody {
+    #[deref]
+    pub(crate) particles: Particles,
+    pub(crate) edge_constraints: Vec<ParticleConstraint>,
+    pub(crate) rest_area: Scalar,
+    /// The mass of each particle.
+    ///
+    /// Default: `0.01`
+    pub particle_mass: Scalar,
+    /// The target area of the soft body relative to its rest area.
+    /// Values above `1.0` inflate the body, and values below `1.0` deflate it.
+    ///
+    /// Default: `1.0`
+    pub pressure: Scalar,
+    /// The compliance of the distance constraints between consecutive particles, the inverse of stiffness.
+    ///
+    /// Default: `0.0`
+    pub edge_compliance: Scalar,
+    /// The compliance of the area constraint, the inverse of stiffness.
+    /// Larger values make the soft body easier to squish.
+    ///
+    /// Default: `0.0`
+    pub area_compliance: Scalar,
+    /// The radius of the particles used for collisions.
+    ///
+    /// Default: `0.02`
+    pub radius: Scalar,
+    /// The coefficient of friction between the particles and colliders.
+    ///
+    /// Default: `0.5`
+    pub friction: Scalar,
+    /// The linear damping of the particles, reducing their velocity over time.
+    ///
+    /// Default: `0.1`
+    pub damping: Scalar,
+}
+
+impl SoftBody {
+    /// Creates a circular [`SoftBody`] with the given world-space `center` and `radius`,
+    /// made of the given number of particles.
+    pub fn circle(center: Vector, radius: Scalar, particles: usize) -> Self {
+        let particles = particles.max(3);
+        let points = (0..particles)
+            .map(|i| {
+                let angle = i as Scalar / particles as Scalar * TAU;
+                center + Vector::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect();
+        Self::from_polygon(points)
+    }
+
+    /// Creates a [`SoftBody`] with particles at the given world-space `points`,
+    /// which form a simple polygon in either winding order.
+    ///
+    /// The rest lengths of the edges and the rest area are computed from the points.
+    pub fn from_polygon(mut points: Vec<Vector>) -> Self {
+        // The area constraint assumes a counterclockwise winding order.
+        if polygon_area(&points) < 0.0 {
+            points.reverse();
+        }
+
+        let count = points.len();
+        let edge_constraints = (0..count)
+            .map(|i| ParticleConstraint::from_positions(&points, i, (i + 1) % count))
+            .collect();
+        Self {
+            rest_area: polygon_area(&points),
+            particles: Particles::new(points),
+            edge_constraints,
+            particle_mass: 0.01,
+            pressure: 1.0,
+            edge_compliance: 0.0,
+            area_compliance: 0.0,
+            radius: 0.02,
+            friction: 0.5,
+            damping: 0.1,
+        }
+    }
+
+    /// Sets the mass of each particle.
+    pub fn with_particle_mass(mut self, mass: Scalar) -> Self {
+        self.particle_mass = mass;
+        self
+    }
+
+    /// Sets the target area of the soft body relative to its rest area.
+    pub fn with_pressure(mut self, pressure: Scalar) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// Sets the compliance of the edge and area constraints.
+    pub fn with_compliance(mut self, edge_compliance: Scalar, area_compliance: Scalar) -> Self {
+        self.edge_compliance = edge_compliance;
+        self.area_compliance = area_compliance;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the coefficient of friction between the particles and colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Adds the given velocity to all particles that aren't pinned.
+    pub fn add_velocity(&mut self, velocity: Vector) {
+        let particles = &mut self.particles;
+        for (particle_velocity, &pinned) in particles.velocities.iter_mut().zip(&particles.pinned) {
+            if !pinned {
+                *particle_velocity += velocity;
+            }
+        }
+    }
+
+    /// Returns the average position of the particles.
+    pub fn center(&self) -> Vector {
+        self.particles.positions.iter().sum::<Vector>() / self.particles.len() as Scalar
+    }
+
+    /// Returns the current area enclosed by the particles.
+    pub fn area(&self) -> Scalar {
+        polygon_area(&self.particles.positions)
+    }
+
+    /// Returns the area enclosed by the particles at rest.
+    pub fn rest_area(&self) -> Scalar {
+        self.rest_area
+    }
+}
+
+/// Computes the signed area of a polygon, which is positive for a counterclockwise winding order.
+fn polygon_area(points: &[Vector]) -> Scalar {
+    let count = points.len();
+    0.5 * (0..count)
+        .map(|i| points[i].perp_dot(points[(i + 1) % count]))
+        .sum::<Scalar>()
+}
+
+/// Moves the particles to keep the enclosed area at the target area,
+/// and adds the impulses applied to each particle to the `reactions`.
+fn solve_area_constraint(
+    positions: &mut [Vector],
+    inverse_masses: &[Scalar],
+    target_area: Scalar,
+    compliance: Scalar,
+    dt: Scalar,
+    reactions: &mut [Vector],
+) {
+    let count = positions.len();
+
+    // The gradient of the area with respect to each particle is perpendicular
+    // to the line between its neighbors, pointing outwards.
+    let gradients: Vec<Vector> = (0..count)
+        .map(|i| {
+            let previous = positions[(i + count - 1) % count];
+            let next = positions[(i + 1) % count];
+            0.5 * Vector::new(next.y - previous.y, previous.x - next.x)
+        })
+        .collect();
+
+    let w_sum: Scalar = gradients
+        .iter()
+        .zip(inverse_masses)
+        .map(|(gradient, inverse_mass)| inverse_mass * gradient.length_squared())
+        .sum();
+    if w_sum <= Scalar::EPSILON {
+        return;
+    }
+
+    let c = polygon_area(positions) - target_area;
+    let delta_lagrange = -c / (w_sum + compliance / dt.powi(2));
+
+    for i in 0..count {
+        positions[i] += gradients[i] * delta_lagrange * inverse_masses[i];
+        reactions[i] += gradients[i] * delta_lagrange / dt;
+    }
+}
+
+/// Simulates [`SoftBody`]s for one substep.
+fn simulate_soft_bodies(
+    mut soft_bodies: Query<(&mut SoftBody, Option<&CollisionLayers>)>,
+    mut bodies: ParticleBodies,
+    world: ParticleWorld,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (mut soft_body, layers) in &mut soft_bodies {
+        let soft_body = &mut *soft_body;
+
+        let inverse_masses = soft_body.particles.inverse_masses(soft_body.particle_mass);
+        let mut reactions = vec![Vector::ZERO; soft_body.particles.len()];
+
+        soft_body.particles.predict(
+            &inverse_masses,
+            gravity.0,
+            soft_body.damping,
+            delta_secs,
+            &world,
+        );
+
+        soft_body.particles.solve_constraints(
+            &soft_body.edge_constraints,
+            &inverse_masses,
+            soft_body.edge_compliance,
+            delta_secs,
+            &mut reactions,
+        );
+        solve_area_constraint(
+            &mut soft_body.particles.positions,
+            &inverse_masses,
+            soft_body.rest_area * soft_body.pressure,
+            soft_body.area_compliance,
+            delta_secs,
+            &mut reactions,
+        );
+
+        soft_body
+            .particles
+            .apply_attachment_reactions(&reactions, &mut bodies, &world);
+
+        soft_body.particles.collide_and_update_velocities(
+            &inverse_masses,
+            soft_body.radius,
+            soft_body.friction,
+            layers.copied().unwrap_or_default(),
+            &world,
+            delta_secs,
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! A spatial hash for finding nearby particles.
+
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+
+#[cfg(feature = "2d")]
+type Cell = IVec2;
+#[cfg(feature = "3d")]
+type Cell = IVec3;
+
+#[cfg(feature = "2d")]
+fn to_cell(position: Vector) -> Cell {
+    position.as_ivec2()
+}
+
+#[cfg(feature = "3d")]
+fn to_cell(position: Vector) -> Cell {
+    position.as_ivec3()
+}
+
+/// A uniform grid that maps cells to the indices of the particles inside them,
+/// used for finding the neighbors of particles without testing every pair.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SpatialHash {
+    cell_size: Scalar,
+    cells: HashMap<Cell, Vec<usize>>,
+}
+
+impl SpatialHash {
+    /// Clears the spatial hash and inserts the given particles into cells of the given size.
+    ///
+    /// For finding neighbors within some radius, the cell size should be at least the radius.
+    pub(crate) fn rebuild(&mut self, positions: &[Vector], cell_size: Scalar) {
+        self.cell_size = cell_size;
+        for indices in self.cells.values_mut() {
+            indices.clear();
+        }
+        for (i, &position) in positions.iter().enumerate() {
+            self.cells.entry(self.cell(position)).or_default().push(i);
+        }
+        self.cells.retain(|_, indices| !indices.is_empty());
+    }
+
+    /// Calls `f` for each particle in the cell containing `position` and the adjacent cells.
+    pub(crate) fn for_each_nearby(&self, position: Vector, mut f: impl FnMut(usize)) {
+        let cell = self.cell(position);
+        for x in -1..=1 {
+            for y in -1..=1 {
+                #[cfg(feature = "2d")]
+                let offsets = [Cell::new(x, y)];
+                #[cfg(feature = "3d")]
+                let offsets = [-1, 0, 1].map(|z| Cell::new(x, y, z));
+                for offset in offsets {
+                    if let Some(indices) = self.cells.get(&(cell + offset)) {
+                        indices.iter().copied().for_each(&mut f);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the cell containing the given position.
+    fn cell(&self, position: Vector) -> Cell {
+        to_cell((position / self.cell_size).floor())
+    }
+}
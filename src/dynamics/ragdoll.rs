@@ -0,0 +1,571 @@
+//! Ragdolls generated from skeleton hierarchies, with blending between animation and simulation.
+//!
+//! See [`RagdollBuilder`] and [`RagdollPlugin`].
+
+use crate::prelude::*;
+use bevy::{
+    ecs::{entity::EntityHashMap, intern::Interned, schedule::ScheduleLabel, world::Command},
+    prelude::*,
+    transform::TransformSystem,
+};
+
+/// A plugin for driving [`Ragdoll`]s created with the [`RagdollBuilder`].
+///
+/// While a ragdoll is in [`RagdollMode::Animated`], its bodies are [kinematic](RigidBody::Kinematic),
+/// and are moved with velocities to follow the animated poses of the bones. This way, the ragdoll
+/// pushes other bodies around like a real animated character would.
+///
+/// While a ragdoll is in [`RagdollMode::Simulated`], its bodies are [dynamic](RigidBody::Dynamic),
+/// and the `Transform`s of the bones are written from the simulated poses of the bodies,
+/// blended with the animated poses based on the [`blend`](Ragdoll::blend) of the ragdoll.
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+///
+/// # Example
+///
+/// ```no_run
+/// use avian3d::prelude::*;
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), RagdollPlugin::default()))
+///         .add_systems(Update, (create_ragdolls, knock_out))
+///         .run();
+/// }
+///
+/// // Create a ragdoll for the skeleton of each character once it has been loaded.
+/// fn create_ragdolls(mut commands: Commands, skeletons: Query<Entity, Added<SkeletonRoot>>) {
+///     for root in &skeletons {
+///         commands.queue(
+///             RagdollBuilder::new(root)
+///                 .with_radius(0.08)
+///                 .with_joint("LeftKnee", RagdollJoint::revolute(Vector::X, -2.5, 0.0))
+///                 .with_joint("RightKnee", RagdollJoint::revolute(Vector::X, -2.5, 0.0))
+///                 .without_bone("LeftHand")
+///                 .without_bone("RightHand"),
+///         );
+///     }
+/// }
+///
+/// // Let the characters go limp when they are hit.
+/// fn knock_out(mut ragdolls: Query<&mut Ragdoll, With<Hit>>) {
+///     for mut ragdoll in &mut ragdolls {
+///         ragdoll.mode = RagdollMode::Simulated;
+///     }
+/// }
+/// #
+/// # #[derive(Component)]
+/// # struct SkeletonRoot;
+/// #
+/// # #[derive(Component)]
+/// # struct Hit;
+/// ```
+pub struct RagdollPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl RagdollPlugin {
+    /// Creates a [`RagdollPlugin`] with the schedule that the ragdoll bodies are updated in.
+    /// This should be the same schedule that the [`PhysicsPlugins`] run in.
+    ///
+    /// The default schedule is `FixedPostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for RagdollPlugin {
+    fn default() -> Self {
+        Self::new(FixedPostUpdate)
+    }
+}
+
+impl Plugin for RagdollPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(Ragdoll, RagdollMode, RagdollBone, RagdollJoint)>();
+
+        app.add_systems(
+            self.schedule.intern(),
+            (update_ragdoll_modes, follow_animated_bones)
+                .chain()
+                .before(PhysicsSet::Prepare),
+        );
+
+        app.add_systems(
+            PostUpdate,
+            write_simulated_bones
+                .after(PhysicsSet::Sync)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// A component for the root bone of a skeleton that has been turned into a ragdoll
+/// by the [`RagdollBuilder`]. Driven by the [`RagdollPlugin`].
+///
+/// The [`mode`](Self::mode) determines whether the bodies of the ragdoll follow the animation,
+/// or are simulated and move the bones. The [`blend`](Self::blend) can be used to smoothly
+/// transition the bones between the animated and simulated poses.
+#[derive(Reflect, Clone, Component, Debug, PartialEq)]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Ragdoll {
+    /// Whether the ragdoll follows the animation or is simulated.
+    pub mode: RagdollMode,
+    /// How much the simulated poses of the bodies affect the bones in [`RagdollMode::Simulated`],
+    /// in the `[0, 1]` range. At `0`, the bones keep their animated poses, and at `1`,
+    /// they follow the bodies exactly.
+    ///
+    /// Default: `1.0`
+    pub blend: Scalar,
+    pub(crate) bodies: Vec<Entity>,
+}
+
+impl Ragdoll {
+    /// Returns the entities of the [`RagdollBone`] bodies of the ragdoll.
+    ///
+    /// Parents always come before their children.
+    pub fn bodies(&self) -> &[Entity] {
+        &self.bodies
+    }
+}
+
+/// Determines whether a [`Ragdoll`] follows the animation or is simulated.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum RagdollMode {
+    /// The bodies are [kinematic](RigidBody::Kinematic) and follow the animated poses of the bones.
+    #[default]
+    Animated,
+    /// The bodies are [dynamic](RigidBody::Dynamic), and the bones follow the simulated poses of the bodies.
+    Simulated,
+}
+
+/// A component for a rigid body generated for a bone by the [`RagdollBuilder`].
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[reflect(Debug, Component, PartialEq)]
+pub struct RagdollBone {
+    /// The root bone entity with the [`Ragdoll`] component.
+    pub ragdoll: Entity,
+    /// The bone entity that the body belongs to.
+    pub bone: Entity,
+    /// The rotation of the body relative to the bone.
+    pub(crate) local_rotation: Quaternion,
+}
+
+/// The type of joint connecting the body of a bone to the body of its parent bone in a ragdoll.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum RagdollJoint {
+    /// A [`SphericalJoint`] for joints like shoulders and hips, limited relative to the rest pose.
+    Spherical {
+        /// The maximum angle in radians that the bone can swing away from its rest direction.
+        swing_limit: Scalar,
+        /// The maximum angle in radians that the bone can twist around its own direction.
+        twist_limit: Scalar,
+    },
+    /// A [`RevoluteJoint`] for hinges like elbows and knees, limited relative to the rest pose.
+    Revolute {
+        /// The hinge axis in the local space of the bone.
+        axis: Vector,
+        /// The minimum angle in radians around the axis.
+        min: Scalar,
+        /// The maximum angle in radians around the axis.
+        max: Scalar,
+    },
+}
+
+impl Default for RagdollJoint {
+    fn default() -> Self {
+        Self::spherical(0.7, 0.35)
+    }
+}
+
+impl RagdollJoint {
+    /// Creates a [`RagdollJoint::Spherical`] with the given swing and twist limits in radians.
+    pub const fn spherical(swing_limit: Scalar, twist_limit: Scalar) -> Self {
+        Self::Spherical {
+            swing_limit,
+            twist_limit,
+        }
+    }
+
+    /// Creates a [`RagdollJoint::Revolute`] around the given `axis` in the local space of the bone,
+    /// with the given angle limits in radians.
+    pub const fn revolute(axis: Vector, min: Scalar, max: Scalar) -> Self {
+        Self::Revolute { axis, min, max }
+    }
+}
+
+/// A [`Command`] that turns a skeleton hierarchy into a [`Ragdoll`].
+///
+/// The builder walks the hierarchy of bones starting from the `root` entity. Every bone with child bones
+/// gets a rigid body with a capsule [`Collider`] reaching from the bone to its children. The body of each bone
+/// is connected to the body of its parent bone with a limited [`RagdollJoint`], which is a spherical joint
+/// by default, and can be changed for individual bones by their [`Name`] using [`with_joint`](Self::with_joint).
+/// The joint limits are relative to the pose of the skeleton when the ragdoll is created.
+///
+/// Leaf bones don't get bodies, as their length is unknown, and simply move with their parents.
+///
+/// The bodies are spawned as separate entities with a [`RagdollBone`] component, and the [`Ragdoll`] component
+/// is added to the `root`. The ragdoll is driven by the [`RagdollPlugin`].
+///
+/// The capsules are shortened so that the bodies of connected bones only touch at the joint.
+/// If bones are very close to each other, [`CollisionLayers`] can be used to prevent
+/// the bodies of the ragdoll from colliding with each other.
+///
+/// See the [`RagdollPlugin`] for an example.
+#[derive(Clone, Debug)]
+pub struct RagdollBuilder {
+    root: Entity,
+    mode: RagdollMode,
+    radius: Scalar,
+    joint: RagdollJoint,
+    joints: Vec<(String, RagdollJoint)>,
+    excluded_bones: Vec<String>,
+    collision_layers: CollisionLayers,
+}
+
+impl RagdollBuilder {
+    /// Creates a [`RagdollBuilder`] for the skeleton hierarchy starting from the given `root` bone.
+    pub fn new(root: Entity) -> Self {
+        Self {
+            root,
+            mode: RagdollMode::Animated,
+            radius: 0.1,
+            joint: RagdollJoint::default(),
+            joints: vec![],
+            excluded_bones: vec![],
+            collision_layers: CollisionLayers::default(),
+        }
+    }
+
+    /// Sets the initial [`RagdollMode`] of the ragdoll.
+    ///
+    /// Default: [`RagdollMode::Animated`]
+    pub fn with_mode(mut self, mode: RagdollMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the radius of the capsule colliders of the bones.
+    ///
+    /// Default: `0.1`
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the joint used for bones without a joint set with [`with_joint`](Self::with_joint).
+    ///
+    /// Default: [`RagdollJoint::spherical(0.7, 0.35)`](RagdollJoint::spherical)
+    pub fn with_default_joint(mut self, joint: RagdollJoint) -> Self {
+        self.joint = joint;
+        self
+    }
+
+    /// Sets the joint connecting the bone with the given [`Name`] to its parent bone.
+    pub fn with_joint(mut self, bone_name: impl Into<String>, joint: RagdollJoint) -> Self {
+        self.joints.push((bone_name.into(), joint));
+        self
+    }
+
+    /// Excludes the bone with the given [`Name`] and its children from the ragdoll.
+    pub fn without_bone(mut self, bone_name: impl Into<String>) -> Self {
+        self.excluded_bones.push(bone_name.into());
+        self
+    }
+
+    /// Sets the [`CollisionLayers`] of the bodies of the ragdoll.
+    pub fn with_collision_layers(mut self, layers: CollisionLayers) -> Self {
+        self.collision_layers = layers;
+        self
+    }
+
+    fn joint_for(&self, name: Option<&Name>) -> RagdollJoint {
+        name.and_then(|name| {
+            self.joints
+                .iter()
+                .find(|(bone_name, _)| name.as_str() == bone_name)
+        })
+        .map_or(self.joint, |(_, joint)| *joint)
+    }
+
+    fn is_excluded(&self, name: Option<&Name>) -> bool {
+        name.is_some_and(|name| {
+            self.excluded_bones
+                .iter()
+                .any(|bone_name| name.as_str() == bone_name)
+        })
+    }
+}
+
+/// A bone found while walking the skeleton hierarchy.
+struct BoneInfo {
+    entity: Entity,
+    parent: Option<usize>,
+    global_transform: GlobalTransform,
+    joint: RagdollJoint,
+}
+
+impl Command for RagdollBuilder {
+    fn apply(self, world: &mut World) {
+        let Some(root_transform) = world.get::<Transform>(self.root).copied() else {
+            return;
+        };
+
+        // The global transforms are computed from the local transforms,
+        // so the ragdoll can be created before the transforms have been propagated.
+        let root_global_transform = world
+            .get::<Parent>(self.root)
+            .and_then(|parent| world.get::<GlobalTransform>(parent.get()))
+            .copied()
+            .unwrap_or_default()
+            * root_transform;
+
+        // Walk the hierarchy depth-first so that parents come before their children.
+        let mut bones = Vec::<BoneInfo>::new();
+        let mut stack = vec![(self.root, None, root_global_transform)];
+        while let Some((entity, parent, global_transform)) = stack.pop() {
+            let index = bones.len();
+            bones.push(BoneInfo {
+                entity,
+                parent,
+                global_transform,
+                joint: self.joint_for(world.get::<Name>(entity)),
+            });
+
+            let Some(children) = world.get::<Children>(entity) else {
+                continue;
+            };
+            for &child in children.iter().rev() {
+                let Some(transform) = world.get::<Transform>(child) else {
+                    continue;
+                };
+                if self.is_excluded(world.get::<Name>(child)) {
+                    continue;
+                }
+                stack.push((child, Some(index), global_transform * *transform));
+            }
+        }
+
+        // All bodies share the rotation of the root, so that the joint limits are relative to the current pose.
+        let body_rotation = Rotation(root_global_transform.rotation().adjust_precision());
+        let rigid_body = match self.mode {
+            RagdollMode::Animated => RigidBody::Kinematic,
+            RagdollMode::Simulated => RigidBody::Dynamic,
+        };
+
+        let mut bone_bodies = vec![None; bones.len()];
+        let mut bodies = vec![];
+
+        for (index, bone) in bones.iter().enumerate() {
+            let child_positions: Vec<Vector> = bones
+                .iter()
+                .filter(|child| child.parent == Some(index))
+                .map(|child| child.global_transform.translation().adjust_precision())
+                .collect();
+            if child_positions.is_empty() {
+                continue;
+            }
+
+            let position = bone.global_transform.translation().adjust_precision();
+            let end = child_positions.iter().sum::<Vector>() / child_positions.len() as Scalar;
+
+            // Shorten the capsule so that its ends reach the bone and the center of its children.
+            let local_end = body_rotation.inverse() * (end - position);
+            let length = local_end.length();
+            let direction = local_end.try_normalize();
+            let collider = match direction {
+                Some(direction) if length > 2.0 * self.radius => Collider::capsule_endpoints(
+                    self.radius,
+                    direction * self.radius,
+                    local_end - direction * self.radius,
+                ),
+                _ => Collider::capsule_endpoints(self.radius, local_end * 0.5, local_end * 0.5),
+            };
+
+            let bone_rotation = bone.global_transform.rotation().adjust_precision();
+            let local_rotation = bone_rotation.inverse() * body_rotation.0;
+            let body = world
+                .spawn((
+                    rigid_body,
+                    collider,
+                    self.collision_layers,
+                    Position(position),
+                    body_rotation,
+                    Transform::from_translation(position.f32())
+                        .with_rotation(body_rotation.0.f32()),
+                    RagdollBone {
+                        ragdoll: self.root,
+                        bone: bone.entity,
+                        local_rotation,
+                    },
+                ))
+                .id();
+            bone_bodies[index] = Some(body);
+            bodies.push(body);
+
+            let Some(parent_body) = bone.parent.and_then(|parent| bone_bodies[parent]) else {
+                continue;
+            };
+            let parent_position = bones[bone.parent.unwrap()]
+                .global_transform
+                .translation()
+                .adjust_precision();
+            let anchor = body_rotation.inverse() * (position - parent_position);
+
+            match bone.joint {
+                RagdollJoint::Spherical {
+                    swing_limit,
+                    twist_limit,
+                } => {
+                    let axis = direction.or(anchor.try_normalize()).unwrap_or(Vector::Y);
+                    let mut joint = SphericalJoint::new(parent_body, body)
+                        .with_local_anchor_1(anchor)
+                        .with_cone_limit(swing_limit)
+                        .with_twist_limits(-twist_limit, twist_limit);
+                    joint.swing_axis = axis;
+                    joint.twist_axis = axis.any_orthonormal_vector();
+                    world.spawn(joint);
+                }
+                RagdollJoint::Revolute { axis, min, max } => {
+                    world.spawn(
+                        RevoluteJoint::new(parent_body, body)
+                            .with_local_anchor_1(anchor)
+                            .with_aligned_axis(local_rotation.inverse() * axis)
+                            .with_angle_limits(min, max),
+                    );
+                }
+            }
+        }
+
+        if let Ok(mut root) = world.get_entity_mut(self.root) {
+            root.insert(Ragdoll {
+                mode: self.mode,
+                blend: 1.0,
+                bodies,
+            });
+        }
+    }
+}
+
+/// Switches the bodies of [`Ragdoll`]s between kinematic and dynamic when their [`RagdollMode`] changes.
+fn update_ragdoll_modes(
+    ragdolls: Query<&Ragdoll, Changed<Ragdoll>>,
+    mut bodies: Query<&mut RigidBody, With<RagdollBone>>,
+) {
+    for ragdoll in &ragdolls {
+        let rigid_body = match ragdoll.mode {
+            RagdollMode::Animated => RigidBody::Kinematic,
+            RagdollMode::Simulated => RigidBody::Dynamic,
+        };
+        let mut iter = bodies.iter_many_mut(&ragdoll.bodies);
+        while let Some(mut rb) = iter.fetch_next() {
+            if *rb != rigid_body {
+                *rb = rigid_body;
+            }
+        }
+    }
+}
+
+/// Moves the kinematic bodies of animated [`Ragdoll`]s towards the poses of their bones
+/// by setting their velocities.
+fn follow_animated_bones(
+    ragdolls: Query<&Ragdoll>,
+    mut bodies: Query<(
+        &RagdollBone,
+        &Position,
+        &Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    bones: Query<&GlobalTransform>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (ragdoll_bone, position, rotation, mut linear_velocity, mut angular_velocity) in &mut bodies
+    {
+        if !ragdolls
+            .get(ragdoll_bone.ragdoll)
+            .is_ok_and(|ragdoll| ragdoll.mode == RagdollMode::Animated)
+        {
+            continue;
+        }
+        let Ok(bone_transform) = bones.get(ragdoll_bone.bone) else {
+            continue;
+        };
+
+        let target_position = bone_transform.translation().adjust_precision();
+        let target_rotation =
+            bone_transform.rotation().adjust_precision() * ragdoll_bone.local_rotation;
+
+        linear_velocity.0 = (target_position - position.0) / delta_secs;
+
+        // Take the shortest path to the target rotation.
+        let mut delta_rotation = target_rotation * rotation.0.inverse();
+        if delta_rotation.w < 0.0 {
+            delta_rotation = -delta_rotation;
+        }
+        let (axis, angle) = delta_rotation.to_axis_angle();
+        angular_velocity.0 = axis * angle / delta_secs;
+    }
+}
+
+/// Writes the `Transform`s of the bones of simulated [`Ragdoll`]s based on the poses of their bodies,
+/// blended with the animated poses.
+fn write_simulated_bones(
+    ragdolls: Query<&Ragdoll>,
+    bodies: Query<(&RagdollBone, &Position, &Rotation)>,
+    mut bones: Query<(&mut Transform, Option<&Parent>)>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    for ragdoll in &ragdolls {
+        if ragdoll.mode != RagdollMode::Simulated || ragdoll.blend <= 0.0 {
+            continue;
+        }
+        let blend = ragdoll.blend.min(1.0) as f32;
+
+        // The new global transforms of the bones that have been updated so far.
+        // Parents come before their children, so children are placed relative to the updated parents.
+        let mut updated_transforms = EntityHashMap::<GlobalTransform>::default();
+
+        for (ragdoll_bone, position, rotation) in bodies.iter_many(&ragdoll.bodies) {
+            let Ok((mut transform, parent)) = bones.get_mut(ragdoll_bone.bone) else {
+                continue;
+            };
+
+            let parent_transform = parent
+                .and_then(|parent| {
+                    updated_transforms
+                        .get(&parent.get())
+                        .or(global_transforms.get(parent.get()).ok())
+                        .copied()
+                })
+                .unwrap_or_default();
+            let (_, parent_rotation, _) = parent_transform.to_scale_rotation_translation();
+
+            let target_translation = parent_transform
+                .affine()
+                .inverse()
+                .transform_point3(position.0.f32());
+            let target_rotation = parent_rotation.inverse()
+                * (rotation.0 * ragdoll_bone.local_rotation.inverse()).f32();
+
+            transform.translation = transform.translation.lerp(target_translation, blend);
+            transform.rotation = transform.rotation.slerp(target_rotation, blend);
+
+            updated_transforms.insert(ragdoll_bone.bone, parent_transform * *transform);
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! Network interpolation for bodies whose motion is driven by authoritative state
+//! received from elsewhere, such as a game server.
+//!
+//! See [`RemoteBody`].
+
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin that moves [`RemoteBody`] entities along their buffered authoritative samples.
+pub struct RemoteBodyPlugin;
+
+impl Plugin for RemoteBodyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RemoteBody>()
+            .register_type::<RemoteSample>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(drive_remote_bodies.in_set(PhysicsStepSet::First));
+    }
+}
+
+/// A single authoritative sample of the state of a [`RemoteBody`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct RemoteSample {
+    /// The time of the sample in seconds.
+    ///
+    /// This must be on the same timeline as the elapsed time of the [`Physics`] clock.
+    pub time: f64,
+    /// The position of the body.
+    pub position: Vector,
+    /// The rotation of the body.
+    pub rotation: Rotation,
+    /// The linear velocity of the body.
+    pub linear_velocity: Vector,
+    /// The angular velocity of the body.
+    #[cfg(feature = "2d")]
+    pub angular_velocity: Scalar,
+    /// The angular velocity of the body.
+    #[cfg(feature = "3d")]
+    pub angular_velocity: Vector,
+}
+
+/// A component for bodies that are not simulated locally, but instead follow authoritative
+/// [samples](RemoteSample) of their state received from elsewhere, for example from a game server.
+///
+/// Samples are buffered and played back with a configurable [`delay`](Self::delay), so that there is
+/// usually a sample on both sides of the current playback time to interpolate between.
+/// Interpolation uses both the positions and the velocities of the samples, which produces smooth
+/// motion even when samples arrive at a low rate. If the buffer runs dry, the body is extrapolated
+/// using the velocity of the latest sample for at most [`max_extrapolation`](Self::max_extrapolation) seconds.
+///
+/// Remote bodies are [kinematic](RigidBody::Kinematic). Instead of teleporting them,
+/// the engine sets their [`LinearVelocity`] and [`AngularVelocity`] at the start of each
+/// physics step such that they reach the playback target at the end of the step.
+/// This means that they still collide with and push locally simulated bodies,
+/// and contacts see the actual velocity of the body, which is important for local prediction.
+///
+/// Remote bodies never fall asleep.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component)]
+/// struct NetworkId(u64);
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         NetworkId(5),
+///         // Play samples back with a delay of 100 milliseconds.
+///         RemoteBody::new(0.1),
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(0.5),")]
+///     ));
+/// }
+///
+/// // Somewhere in the networking code...
+/// fn receive_state(
+///     mut bodies: Query<(&NetworkId, &mut RemoteBody)>,
+///     time: Res<Time<Physics>>,
+/// ) {
+///     for (id, mut remote) in &mut bodies {
+///         // Push the state received for this body.
+///         remote.push_sample(RemoteSample {
+///             time: time.elapsed_secs_f64(),
+///             ..default()
+///         });
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Debug, Component, PartialEq)]
+#[require(RigidBody(remote_rigid_body), SleepingDisabled)]
+pub struct RemoteBody {
+    /// How far in the past samples are played back, in seconds.
+    ///
+    /// A larger delay can hide more network jitter and packet loss,
+    /// but makes the body lag further behind its authoritative state.
+    pub delay: Scalar,
+    /// The maximum time in seconds that the body is extrapolated past the latest sample.
+    ///
+    /// After this, the body stops until a new sample is received.
+    pub max_extrapolation: Scalar,
+    samples: VecDeque<RemoteSample>,
+}
+
+fn remote_rigid_body() -> RigidBody {
+    RigidBody::Kinematic
+}
+
+impl Default for RemoteBody {
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
+impl RemoteBody {
+    /// Creates a new [`RemoteBody`] with the given playback delay in seconds.
+    pub fn new(delay: Scalar) -> Self {
+        Self {
+            delay,
+            max_extrapolation: 0.25,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Sets the maximum time in seconds that the body is extrapolated past the latest sample.
+    pub fn with_max_extrapolation(mut self, max_extrapolation: Scalar) -> Self {
+        self.max_extrapolation = max_extrapolation;
+        self
+    }
+
+    /// Adds an authoritative sample to the buffer.
+    ///
+    /// Samples can be pushed out of order. A sample with the same time as an existing one replaces it.
+    pub fn push_sample(&mut self, sample: RemoteSample) {
+        let index = self.samples.partition_point(|s| s.time < sample.time);
+
+        if self
+            .samples
+            .get(index)
+            .is_some_and(|s| s.time == sample.time)
+        {
+            self.samples[index] = sample;
+        } else {
+            self.samples.insert(index, sample);
+        }
+    }
+
+    /// Returns the buffered samples, ordered by time.
+    pub fn samples(&self) -> impl ExactSizeIterator<Item = &RemoteSample> {
+        self.samples.iter()
+    }
+
+    /// Returns the most recent buffered sample, if any.
+    pub fn latest_sample(&self) -> Option<&RemoteSample> {
+        self.samples.back()
+    }
+
+    /// Removes all buffered samples.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Computes the interpolated or extrapolated state of the body at the given `time`.
+    ///
+    /// Returns `None` if there are no samples.
+    pub fn sample_at(&self, time: f64) -> Option<RemoteSample> {
+        let first = self.samples.front()?;
+        let last = self.samples.back()?;
+
+        if time <= first.time {
+            return Some(*first);
+        }
+
+        if time >= last.time {
+            return Some(extrapolate(last, time, self.max_extrapolation));
+        }
+
+        // Find the two samples surrounding the given time.
+        let next_index = self.samples.partition_point(|s| s.time <= time);
+        let start = &self.samples[next_index - 1];
+        let end = &self.samples[next_index];
+
+        Some(interpolate(start, end, time))
+    }
+
+    /// Removes samples that are no longer needed for playback at the given time.
+    /// The latest sample at or before the time is kept.
+    fn prune(&mut self, time: f64) {
+        while self.samples.len() > 1 && self.samples[1].time <= time {
+            self.samples.pop_front();
+        }
+    }
+}
+
+/// Interpolates between two samples using cubic Hermite interpolation for the position
+/// and spherical linear interpolation for the rotation.
+fn interpolate(start: &RemoteSample, end: &RemoteSample, time: f64) -> RemoteSample {
+    let h = (end.time - start.time) as Scalar;
+    let t = ((time - start.time) / (end.time - start.time)) as Scalar;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    // Hermite basis functions and their derivatives
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+    let dh00 = 6.0 * t2 - 6.0 * t;
+    let dh10 = 3.0 * t2 - 4.0 * t + 1.0;
+    let dh01 = -6.0 * t2 + 6.0 * t;
+    let dh11 = 3.0 * t2 - 2.0 * t;
+
+    let position = h00 * start.position
+        + h10 * h * start.linear_velocity
+        + h01 * end.position
+        + h11 * h * end.linear_velocity;
+    let linear_velocity = (dh00 * start.position + dh01 * end.position) / h
+        + dh10 * start.linear_velocity
+        + dh11 * end.linear_velocity;
+
+    RemoteSample {
+        time,
+        position,
+        rotation: start.rotation.slerp(end.rotation, t),
+        linear_velocity,
+        angular_velocity: start.angular_velocity
+            + (end.angular_velocity - start.angular_velocity) * t,
+    }
+}
+
+/// Extrapolates the given sample to the given time, limited by `max_extrapolation`.
+fn extrapolate(sample: &RemoteSample, time: f64, max_extrapolation: Scalar) -> RemoteSample {
+    let delta_secs = ((time - sample.time) as Scalar).min(max_extrapolation.max(0.0));
+
+    #[cfg(feature = "2d")]
+    let rotation = Rotation::radians(sample.angular_velocity * delta_secs) * sample.rotation;
+    #[cfg(feature = "3d")]
+    let rotation = Rotation(
+        Quaternion::from_scaled_axis(sample.angular_velocity * delta_secs) * sample.rotation.0,
+    );
+
+    RemoteSample {
+        time,
+        position: sample.position + sample.linear_velocity * delta_secs,
+        rotation,
+        ..*sample
+    }
+}
+
+/// Sets the velocities of [`RemoteBody`] entities such that they reach their playback target
+/// at the end of the physics step.
+fn drive_remote_bodies(
+    mut bodies: Query<(
+        &mut RemoteBody,
+        &Position,
+        &Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    // The physics clock has already been advanced, so this is the time at the end of the step.
+    let end_time = time.elapsed_secs_f64();
+
+    for (mut remote_body, position, rotation, mut linear_velocity, mut angular_velocity) in
+        &mut bodies
+    {
+        let target_time = end_time - remote_body.delay as f64;
+
+        remote_body.prune(target_time);
+
+        let Some(target) = remote_body.sample_at(target_time) else {
+            continue;
+        };
+
+        linear_velocity.0 = (target.position - position.0) / delta_secs;
+
+        #[cfg(feature = "2d")]
+        {
+            angular_velocity.0 = rotation.angle_between(target.rotation) / delta_secs;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let mut delta_rotation = target.rotation.0 * rotation.0.inverse();
+
+            // Take the shortest path.
+            if delta_rotation.w < 0.0 {
+                delta_rotation = -delta_rotation;
+            }
+
+            angular_velocity.0 = delta_rotation.to_scaled_axis() / delta_secs;
+        }
+    }
+}
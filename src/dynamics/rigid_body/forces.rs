@@ -21,8 +21,8 @@ impl FloatZero for Scalar {
 
 /// An external force applied continuously to a dynamic [rigid body](RigidBody).
 ///
-/// The force is stored in world space. If you want to apply forces in local space, you need to
-/// use the body's rotation to [transform the local force into world space](#local-forces).
+/// The force is stored in world space by default. Forces can also be stored in the local space
+/// of the body using [`new_local`](Self::new_local), see [local forces](#local-forces).
 ///
 /// By default, the force persists across frames. You can clear the force manually using
 /// [`clear`](Self::clear) or set `persistent` to false.
@@ -62,10 +62,13 @@ impl FloatZero for Scalar {
 ///
 /// # Local Forces
 ///
-/// The force stored in `ExternalForce` is in world space.
+/// By default, the force stored in `ExternalForce` is in world space.
 ///
 /// If you want to apply a force in some direction relative to the body's frame of reference,
-/// you need to rotate the force using the body's `Transform` or [`Rotation`].
+/// you can create the force using [`new_local`](Self::new_local). The force is then stored in the local space
+/// of the body, and it is rotated into world space using the current [`Rotation`] of the body
+/// at every [substep](SubstepCount). This way, the force follows the body as it rotates,
+/// like a thruster attached to a ship.
 ///
 /// ```
 /// # #[cfg(feature = "2d")]
@@ -77,18 +80,16 @@ impl FloatZero for Scalar {
 /// # #[cfg(all(feature = "3d", feature = "f32"))]
 /// fn setup(mut commands: Commands) {
 ///     // Spawn a rotated body and apply a force in the local up direction.
-///     let transform = Transform::from_rotation(Quat::from_rotation_z(0.2));
 ///     commands.spawn((
 ///         RigidBody::Dynamic,
-///         ExternalForce::new(transform.rotation * Vec3::Y),
-///         transform,
+///         ExternalForce::new_local(Vec3::Y),
+///         Transform::from_rotation(Quat::from_rotation_z(0.2)),
 ///     ));
 /// }
 /// ```
 ///
-/// Note that the actual force stored in `ExternalForce` is still in world space.
-/// If you want to apply a force in the same local direction every frame,
-/// consider setting `persistent` to `false` and running [`apply_force`](Self::apply_force) in a system.
+/// For local forces, all of the methods of `ExternalForce` also take and return forces
+/// and points in local space.
 #[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, From)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
@@ -103,6 +104,10 @@ pub struct ExternalForce {
     pub persistent: bool,
     /// The torque caused by forces applied at certain points using [`apply_force_at_point`](Self::apply_force_at_point).
     torque: Torque,
+    /// The force at the end of the physics step if the force is [ramped](Self::ramp).
+    ramp_end: Option<Vector>,
+    /// True if the force and torque are in the local space of the body.
+    local: bool,
 }
 
 impl Deref for ExternalForce {
@@ -125,6 +130,8 @@ impl Default for ExternalForce {
             force: Vector::ZERO,
             persistent: true,
             torque: Torque::ZERO,
+            ramp_end: None,
+            local: false,
         }
     }
 }
@@ -135,6 +142,8 @@ impl ExternalForce {
         force: Vector::ZERO,
         persistent: true,
         torque: Torque::ZERO,
+        ramp_end: None,
+        local: false,
     };
 
     /// Creates a new [`ExternalForce`] component with a given world-space `force`.
@@ -142,15 +151,67 @@ impl ExternalForce {
         Self { force, ..default() }
     }
 
+    /// Creates a new [`ExternalForce`] component with a given `force` in the local space of the body.
+    ///
+    /// The force is rotated into world space using the current [`Rotation`] of the body
+    /// at every [substep](SubstepCount), so it follows the body as it rotates.
+    /// See [local forces](#local-forces) for more details.
+    pub fn new_local(force: Vector) -> Self {
+        Self {
+            force,
+            local: true,
+            ..default()
+        }
+    }
+
+    /// Creates a new [`ExternalForce`] component with a world-space force that changes linearly
+    /// from `from` to `to` over the course of the next physics step.
+    ///
+    /// The force is evaluated separately for each [substep](SubstepCount), which integrates
+    /// thrust curves and quickly changing control inputs more accurately than a constant force,
+    /// especially at low physics tick rates. If the force is persistent, it stays at `to`
+    /// after the physics step.
+    ///
+    /// # Example
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    /// use bevy::prelude::*;
+    ///
+    /// fn throttle_up(mut thrusters: Query<&mut ExternalForce>) {
+    ///     for mut force in &mut thrusters {
+    ///         // Increase the thrust smoothly during the next physics step.
+    ///         let current = force.force();
+    ///         *force = ExternalForce::ramp(current, current + Vector::Y * 10.0);
+    ///     }
+    /// }
+    /// ```
+    pub fn ramp(from: Vector, to: Vector) -> Self {
+        Self {
+            force: from,
+            ramp_end: Some(to),
+            ..default()
+        }
+    }
+
     /// Sets the world-space force. Note that the torque caused by any forces will not be reset.
+    ///
+    /// This stops any [ramp](Self::ramp).
     pub fn set_force(&mut self, force: Vector) -> &mut Self {
         **self = force;
+        self.ramp_end = None;
         self
     }
 
     /// Adds the given world-space `force` to the force that will be applied.
+    ///
+    /// If the force is [ramped](Self::ramp), the force is added to both ends of the ramp.
     pub fn apply_force(&mut self, force: Vector) -> &mut Self {
         **self += force;
+        if let Some(ramp_end) = &mut self.ramp_end {
+            *ramp_end += force;
+        }
         self
     }
 
@@ -176,16 +237,67 @@ impl ExternalForce {
     }
 
     /// Returns the force in world space.
+    ///
+    /// If the force is [ramped](Self::ramp), this is the force at the start of the physics step.
     pub fn force(&self) -> Vector {
         self.force
     }
 
+    /// Returns the force at the end of the physics step if the force is [ramped](Self::ramp).
+    pub fn ramp_end(&self) -> Option<Vector> {
+        self.ramp_end
+    }
+
+    /// Returns the force in world space at the given fraction of the physics step, in the `[0, 1]` range.
+    ///
+    /// If the force is not [ramped](Self::ramp), this is the same as [`force`](Self::force).
+    pub fn force_at(&self, fraction: Scalar) -> Vector {
+        match self.ramp_end {
+            Some(ramp_end) => self.force.lerp(ramp_end, fraction),
+            None => self.force,
+        }
+    }
+
+    /// Returns the force and the torque caused by the force in world space at the given fraction
+    /// of the physics step, rotating [local forces](#local-forces) using the given `rotation` of the body.
+    pub(crate) fn world_force_and_torque_at(
+        &self,
+        fraction: Scalar,
+        rotation: &Rotation,
+    ) -> (Vector, Torque) {
+        let force = self.force_at(fraction);
+        if !self.local {
+            return (force, self.torque);
+        }
+
+        #[cfg(feature = "2d")]
+        let torque = self.torque;
+        #[cfg(feature = "3d")]
+        let torque = *rotation * self.torque;
+
+        (*rotation * force, torque)
+    }
+
+    /// Finishes the [ramp](Self::ramp), setting the force to the end of the ramp.
+    pub(crate) fn finish_ramp(&mut self) {
+        if let Some(ramp_end) = self.ramp_end.take() {
+            self.force = ramp_end;
+        }
+    }
+
     /// Returns the torque caused by forces applied at certain points using
     /// [`apply_force_at_point`](Self::apply_force_at_point).
     pub fn torque(&self) -> Torque {
         self.torque
     }
 
+    /// Returns `true` if the force is in the local space of the body.
+    ///
+    /// See [local forces](#local-forces) for more details.
+    pub fn is_local(&self) -> bool {
+        self.local
+    }
+
     /// Determines if the force is persistent or if it should be automatically cleared every physics frame.
     #[doc(alias = "clear_automatically")]
     pub fn with_persistence(mut self, is_persistent: bool) -> Self {
@@ -197,11 +309,16 @@ impl ExternalForce {
     pub fn clear(&mut self) {
         self.force = Vector::ZERO;
         self.torque = Torque::ZERO;
+        self.ramp_end = None;
     }
 }
 
 /// An external torque applied continuously to a dynamic [rigid body](RigidBody).
 ///
+/// The torque is in world space by default. In 3D, the torque can also be stored in the local space
+/// of the body using [`new_local`](Self::new_local), in which case it is rotated into world space
+/// using the current [`Rotation`] of the body at every [substep](SubstepCount).
+///
 /// By default, the torque persists across frames. You can clear the torque manually using
 /// [`clear`](Self::clear) or set `persistent` to false.
 ///
@@ -240,6 +357,8 @@ pub struct ExternalTorque {
     torque: Torque,
     /// True if the torque persists across frames, and false if the torque is automatically cleared every physics frame.
     pub persistent: bool,
+    /// True if the torque is in the local space of the body.
+    local: bool,
 }
 
 impl Deref for ExternalTorque {
@@ -261,6 +380,7 @@ impl Default for ExternalTorque {
         Self {
             torque: Torque::ZERO,
             persistent: true,
+            local: false,
         }
     }
 }
@@ -270,6 +390,7 @@ impl ExternalTorque {
     pub const ZERO: Self = Self {
         torque: Torque::ZERO,
         persistent: true,
+        local: false,
     };
 
     /// Creates a new [`ExternalTorque`] component with a given `torque`.
@@ -280,6 +401,19 @@ impl ExternalTorque {
         }
     }
 
+    /// Creates a new [`ExternalTorque`] component with a given `torque` in the local space of the body.
+    ///
+    /// The torque is rotated into world space using the current [`Rotation`] of the body
+    /// at every [substep](SubstepCount), so it follows the body as it rotates.
+    /// In 2D, local and world-space torques are the same.
+    pub fn new_local(torque: Torque) -> Self {
+        Self {
+            torque,
+            local: true,
+            ..default()
+        }
+    }
+
     /// Sets the torque.
     pub fn set_torque(&mut self, torque: Torque) -> &mut Self {
         **self = torque;
@@ -304,6 +438,21 @@ impl ExternalTorque {
         self.torque
     }
 
+    /// Returns `true` if the torque is in the local space of the body.
+    pub fn is_local(&self) -> bool {
+        self.local
+    }
+
+    /// Returns the torque in world space, rotating a local torque using the given `rotation` of the body.
+    #[allow(unused_variables)]
+    pub(crate) fn world_torque(&self, rotation: &Rotation) -> Torque {
+        #[cfg(feature = "3d")]
+        if self.local {
+            return *rotation * self.torque;
+        }
+        self.torque
+    }
+
     /// Sets the torque to zero.
     pub fn clear(&mut self) {
         self.torque = Torque::ZERO;
@@ -289,6 +289,8 @@ impl Plugin for MassPropertyPlugin {
             NoAutoCenterOfMass,
         )>();
 
+        app.init_resource::<RigidBodyLimitsConfig>();
+
         // Force mass property computation for new rigid bodies.
         app.register_required_components::<RigidBody, RecomputeMassProperties>();
 
@@ -319,6 +321,7 @@ impl Plugin for MassPropertyPlugin {
             self.schedule,
             (
                 update_mass_properties,
+                clamp_angular_inertia,
                 #[cfg(feature = "3d")]
                 update_global_angular_inertia::<Added<RigidBody>>,
                 warn_missing_mass,
@@ -408,6 +411,73 @@ fn update_mass_properties(
     }
 }
 
+/// Clamps the [`ComputedAngularInertia`] of dynamic bodies based on their [`MinInertiaRadius`]
+/// or [`RigidBodyLimitsConfig::min_inertia_radius`].
+#[allow(clippy::type_complexity)]
+fn clamp_angular_inertia(
+    mut commands: Commands,
+    mut bodies: Query<(
+        Entity,
+        Ref<RigidBody>,
+        Ref<ComputedMass>,
+        &mut ComputedAngularInertia,
+        Option<Ref<MinInertiaRadius>>,
+    )>,
+    limits: Res<RigidBodyLimitsConfig>,
+    length_unit: Res<PhysicsLengthUnit>,
+) {
+    let limits_changed = limits.is_changed() || length_unit.is_changed();
+
+    for (entity, rb, mass, mut angular_inertia, min_radius) in &mut bodies {
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        // A clamped angular inertia can't be restored, so if the limit itself changed,
+        // recompute the mass properties and clamp them again on the next run.
+        if (limits_changed && !limits.is_added())
+            || min_radius
+                .as_ref()
+                .is_some_and(|radius| radius.is_changed() && !radius.is_added())
+        {
+            commands.entity(entity).try_insert(RecomputeMassProperties);
+            continue;
+        }
+
+        if !(rb.is_changed() || mass.is_changed() || angular_inertia.is_changed()) {
+            continue;
+        }
+
+        let radius =
+            min_radius.map_or(limits.min_inertia_radius, |radius| radius.0) * length_unit.0;
+
+        if radius <= 0.0 || mass.inverse() == 0.0 {
+            continue;
+        }
+
+        // The inverse angular inertia must be at most `1 / (mass * radius²)`.
+        // An inverse of zero represents infinite angular inertia, which is not affected.
+        let max_inverse = mass.inverse() / (radius * radius);
+
+        #[cfg(feature = "2d")]
+        if angular_inertia.inverse() > max_inverse {
+            *angular_inertia.inverse_mut() = max_inverse;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let inverse = AngularInertia::from_tensor(angular_inertia.inverse_tensor().f32());
+            let principal_inverse = inverse.principal.adjust_precision();
+
+            if principal_inverse.max_element() > max_inverse {
+                let local_frame = Matrix::from_quat(inverse.local_frame.adjust_precision());
+                *angular_inertia.inverse_tensor_mut() = local_frame
+                    * Matrix::from_diagonal(principal_inverse.min(Vector::splat(max_inverse)))
+                    * local_frame.transpose();
+            }
+        }
+    }
+}
+
 /// Updates [`GlobalAngularInertia`] for entities that match the given query filter `F`.
 #[cfg(feature = "3d")]
 pub(crate) fn update_global_angular_inertia<F: QueryFilter>(
@@ -20,23 +20,395 @@ pub(crate) use forces::FloatZero;
 pub(crate) use forces::Torque;
 
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    ecs::{component::ComponentId, world::DeferredWorld},
+    prelude::*,
+};
 use derive_more::From;
 
-/// A non-deformable body used for the simulation of most physics objects.
+/// Calls the given macro with a list of the components that are [required](RigidBody#required-components)
+/// for simulating a rigid body, in addition to its [`Position`] and [`Rotation`].
+///
+/// The required components of [`RigidBody`], their documentation, [`missing_rigid_body_components`],
+/// and [`NoAutoRigidBodyComponents`] are all built from this list to keep them in sync.
+macro_rules! with_rigid_body_components {
+    ($callback:ident! { $($input:tt)* }) => {
+        with_rigid_body_components!(@dimension $callback { $($input)* } [
+            LinearVelocity,
+            AngularVelocity,
+            // TODO: Make these force components optional.
+            ExternalForce,
+            ExternalTorque,
+            ExternalImpulse,
+            ExternalAngularImpulse,
+            ComputedMass,
+            ComputedAngularInertia,
+            ComputedCenterOfMass,
+            // Currently required for solver internals.
+            // Some of these might be removed in the future.
+            AccumulatedTranslation,
+            PreSolveAccumulatedTranslation,
+            PreSolveLinearVelocity,
+            PreSolveAngularVelocity,
+            PreSolveRotation,
+            PreviousRotation,
+        ]);
+    };
+    (@dimension $callback:ident { $($input:tt)* } [$($component:ident),* $(,)?]) => {
+        #[cfg(feature = "2d")]
+        $callback! { [$($component),*] $($input)* }
+        #[cfg(feature = "3d")]
+        $callback! { [$($component,)* GlobalAngularInertia] $($input)* }
+    };
+}
+
+/// Defines the [`RigidBody`] component with the given required components,
+/// and lists them in its documentation in place of `@required_components`.
+macro_rules! define_rigid_body {
+    (
+        [$($component:ident),*]
+        $(#[$before:meta])*
+        @required_components
+        $(#[$after:meta])*
+        $vis:vis enum $name:ident { $($body:tt)* }
+    ) => {
+        $(#[$before])*
+        $(#[doc = concat!("- [`", stringify!($component), "`]")])*
+        $(#[$after])*
+        #[require($($component),*)]
+        $vis enum $name { $($body)* }
+    };
+}
+
+with_rigid_body_components!(define_rigid_body! {
+    /// A non-deformable body used for the simulation of most physics objects.
+    ///
+    /// # Rigid Body Types
+    ///
+    /// A rigid body can be either dynamic, kinematic or static.
+    ///
+    /// - **Dynamic bodies** are similar to real life objects and are affected by forces and contacts.
+    /// - **Kinematic bodies** can only be moved programmatically, which is useful for things like character controllers and moving platforms.
+    /// - **Static bodies** can not move, so they can be good for objects in the environment like the ground and walls.
+    ///
+    /// # Creation
+    ///
+    /// Creating a rigid body is as simple as adding the [`RigidBody`] component,
+    /// and an optional [`Collider`]:
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    /// use bevy::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands) {
+    ///     // Spawn a dynamic rigid body and specify its position.
+    ///     commands.spawn((
+    ///         RigidBody::Dynamic,
+    ///         Collider::capsule(0.5, 1.5),
+    ///         Transform::from_xyz(0.0, 3.0, 0.0),
+    ///     ));
+    /// }
+    /// ```
+    ///
+    /// By default, dynamic rigid bodies will have mass properties computed based on the attached colliders
+    /// and their [`ColliderDensity`]. See the [Mass properties](#mass-properties) section for more information.
+    ///
+    /// # Movement
+    ///
+    /// A rigid body can be moved in three ways: by modifying its position directly,
+    /// by changing its velocity, or by applying forces or impulses.
+    ///
+    /// To change the position of a rigid body, you can simply modify its `Transform`:
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    /// use bevy::prelude::*;
+    ///
+    /// fn move_bodies(mut query: Query<&mut Transform, With<RigidBody>>) {
+    ///     for mut transform in query.iter_mut() {
+    ///         transform.translation.x += 0.1;
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// However, moving a dynamic body by changing its position directly is similar
+    /// to teleporting the body, which can result in unexpected behavior since the body can move
+    /// inside walls.
+    ///
+    /// You can instead change the velocity of a dynamic or kinematic body with the [`LinearVelocity`]
+    /// and [`AngularVelocity`] components:
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    /// use bevy::prelude::*;
+    ///
+    /// fn accelerate_bodies(mut query: Query<(&mut LinearVelocity, &mut AngularVelocity)>) {
+    ///     for (mut linear_velocity, mut angular_velocity) in query.iter_mut() {
+    ///         linear_velocity.x += 0.05;
+    #[cfg_attr(feature = "2d", doc = "        angular_velocity.0 += 0.05;")]
+    #[cfg_attr(feature = "3d", doc = "        angular_velocity.z += 0.05;")]
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// For applying forces and impulses to dynamic bodies, see the [`ExternalForce`], [`ExternalTorque`],
+    /// [`ExternalImpulse`] and [`ExternalAngularImpulse`] components.
+    ///
+    /// Avian does not have a built-in character controller, so if you need one,
+    /// you will need to implement it yourself or use a third party option.
+    /// You can take a look at the [`basic_dynamic_character`] and [`basic_kinematic_character`]
+    /// examples for a simple implementation.
+    ///
+    /// [`basic_dynamic_character`]: https://github.com/Jondolf/avian/blob/42fb8b21c756a7f4dd91071597dc251245ddaa8f/crates/avian3d/examples/basic_dynamic_character.rs
+    /// [`basic_kinematic_character`]: https://github.com/Jondolf/avian/blob/42fb8b21c756a7f4dd91071597dc251245ddaa8f/crates/avian3d/examples/basic_kinematic_character.rs
+    ///
+    /// # Mass Properties
+    ///
+    /// Every dynamic rigid body has [mass], [angular inertia], and a [center of mass].
+    /// These mass properties determine how the rigid body responds to forces and torques.
+    ///
+    /// - **Mass**: Represents resistance to linear acceleration. A higher mass requires more force for the same acceleration.
+    /// - **Angular Inertia**: Represents resistance to angular acceleration. A higher angular inertia requires more torque for the same angular acceleration.
+    /// - **Center of Mass**: The average position of mass in the body. Applying forces at this point produces no torque.
+    ///
+    /// Static and kinematic rigid bodies have infinite mass and angular inertia,
+    /// and do not respond to forces or torques. Zero mass for a dynamic body is also
+    /// treated as a special case, and corresponds to infinite mass.
+    ///
+    /// If no mass properties are set, they are computed automatically from attached colliders
+    /// based on their shape and density.
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+    /// # use bevy::prelude::*;
+    /// #
+    /// # fn setup(mut commands: Commands) {
+    /// // Note: `ColliderDensity` is optional, and defaults to `1.0` if not present.
+    /// commands.spawn((
+    ///     RigidBody::Dynamic,
+    ///     Collider::capsule(0.5, 1.5),
+    ///     ColliderDensity(2.0),
+    /// ));
+    /// # }
+    /// ```
+    ///
+    /// If mass properties are set with the [`Mass`], [`AngularInertia`], and [`CenterOfMass`] components,
+    /// they override the values computed from colliders.
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+    /// # use bevy::prelude::*;
+    /// #
+    /// # fn setup(mut commands: Commands) {
+    /// // Override mass and the center of mass, but use the collider's angular inertia.
+    /// commands.spawn((
+    ///     RigidBody::Dynamic,
+    ///     Collider::capsule(0.5, 1.5),
+    ///     Mass(5.0),
+    #[cfg_attr(feature = "2d", doc = "    CenterOfMass::new(0.0, -0.5),")]
+    #[cfg_attr(feature = "3d", doc = "    CenterOfMass::new(0.0, -0.5, 0.0),")]
+    /// ));
+    /// # }
+    /// ```
+    ///
+    /// If the rigid body has child colliders, their mass properties will be combined for
+    /// the total [`ComputedMass`], [`ComputedAngularInertia`], and [`ComputedCenterOfMass`].
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+    /// # use bevy::prelude::*;
+    /// #
+    /// # fn setup(mut commands: Commands) {
+    /// // Total mass: 10.0 + 5.0 = 15.0
+    #[cfg_attr(
+        feature = "2d",
+        doc = "// Total center of mass: (10.0 * [0.0, -0.5] + 5.0 * [0.0, 4.0]) / (10.0 + 5.0) = [0.0, 1.0]"
+    )]
+    #[cfg_attr(
+        feature = "3d",
+        doc = "// Total center of mass: (10.0 * [0.0, -0.5, 0.0] + 5.0 * [0.0, 4.0, 0.0]) / (10.0 + 5.0) = [0.0, 1.0, 0.0]"
+    )]
+    /// commands.spawn((
+    ///     RigidBody::Dynamic,
+    ///     Collider::capsule(0.5, 1.5),
+    ///     Mass(10.0),
+    #[cfg_attr(feature = "2d", doc = "    CenterOfMass::new(0.0, -0.5),")]
+    #[cfg_attr(feature = "3d", doc = "    CenterOfMass::new(0.0, -0.5, 0.0),")]
+    ///     Transform::default(),
+    /// ))
+    /// .with_child((
+    #[cfg_attr(feature = "2d", doc = "    Collider::circle(1.0),")]
+    #[cfg_attr(feature = "3d", doc = "    Collider::sphere(1.0),")]
+    ///     Mass(5.0),
+    ///     Transform::from_xyz(0.0, 4.0, 0.0),
+    /// ));
+    /// # }
+    /// ```
+    ///
+    /// To prevent child entities from contributing to the total mass properties, use the [`NoAutoMass`],
+    /// [`NoAutoAngularInertia`], and [`NoAutoCenterOfMass`] marker components.
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+    /// # use bevy::prelude::*;
+    /// #
+    /// # fn setup(mut commands: Commands) {
+    /// // Total mass: 10.0
+    #[cfg_attr(feature = "2d", doc = "// Total center of mass: [0.0, -0.5]")]
+    #[cfg_attr(feature = "3d", doc = "// Total center of mass: [0.0, -0.5, 0.0]")]
+    /// commands.spawn((
+    ///     RigidBody::Dynamic,
+    ///     Collider::capsule(0.5, 1.5),
+    ///     Mass(10.0),
+    #[cfg_attr(feature = "2d", doc = "    CenterOfMass::new(0.0, -0.5),")]
+    #[cfg_attr(feature = "3d", doc = "    CenterOfMass::new(0.0, -0.5, 0.0),")]
+    ///     NoAutoMass,
+    ///     NoAutoCenterOfMass,
+    ///     Transform::default(),
+    /// ))
+    /// .with_child((
+    #[cfg_attr(feature = "2d", doc = "    Collider::circle(1.0),")]
+    #[cfg_attr(feature = "3d", doc = "    Collider::sphere(1.0),")]
+    ///     Mass(5.0),
+    ///     Transform::from_xyz(0.0, 4.0, 0.0),
+    /// ));
+    /// # }
+    /// ```
+    ///
+    /// See the [`mass_properties`] module for more information.
+    ///
+    /// [mass]: mass_properties::components::Mass
+    /// [angular inertia]: mass_properties::components::AngularInertia
+    /// [center of mass]: mass_properties::components::CenterOfMass
+    /// [mass properties]: mass_properties
+    ///
+    /// # Required Components
+    ///
+    /// Rigid bodies need a number of components to be simulated. Adding a [`RigidBody`] inserts
+    /// the following components with their default values, unless they already exist on the entity:
+    ///
+    @required_components
+    ///
+    /// Some of these are currently only required for solver internals, and might be removed in the future.
+    /// The components can be removed again for individual entities with the [`NoAutoRigidBodyComponents`] marker,
+    /// for example when they are supplied by other means.
+    ///
+    /// Some plugins register additional [required components]. For example, the [`SleepingPlugin`]
+    /// requires [`TimeSleeping`], and the [`PhysicsInterpolationPlugin`] can be configured
+    /// to require interpolation components for all rigid bodies.
+    ///
+    /// The [`Position`] and [`Rotation`] are not inserted when the body is added. Instead, the [`PreparePlugin`]
+    /// initializes them from the [`Transform`] of the body, or the other way around.
+    /// This can be disabled for individual entities with [`PrepareOverride::DISABLED`], in which case
+    /// the position and rotation must be inserted manually.
+    ///
+    /// Rigid bodies that are missing any of the components listed above are not simulated,
+    /// so an error listing the missing components is logged for them.
+    ///
+    /// [required components]: Component#required-components
+    ///
+    /// # See More
+    ///
+    /// - [Colliders](Collider)
+    /// - [Gravity], [gravity scale](GravityScale), and [gravity overrides](GravityOverride)
+    /// - [Linear](LinearDamping) and [angular](AngularDamping) velocity damping
+    /// - [Friction] and [restitution](Restitution) (bounciness)
+    /// - [Lock translational and rotational axes](LockedAxes)
+    /// - [Dominance]
+    /// - [Continuous Collision Detection](dynamics::ccd)
+    /// - [Temporarily disabling a rigid body](RigidBodyDisabled)
+    /// - [Pausing the simulation of a rigid body](Frozen)
+    /// - [Automatic deactivation with sleeping](Sleeping)
+    #[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+    #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+    #[reflect(Debug, Component, Default, PartialEq)]
+    pub enum RigidBody {
+        /// Dynamic bodies are bodies that are affected by forces, velocity and collisions.
+        #[default]
+        Dynamic,
+
+        /// Static bodies are not affected by any forces, collisions or velocity, and they act as if they have an infinite mass and moment of inertia.
+        /// The only way to move a static body is to manually change its position.
+        ///
+        /// Collisions with static bodies will affect dynamic bodies, but not other static bodies or kinematic bodies.
+        ///
+        /// Static bodies are typically used for things like the ground, walls and any other objects that you don't want to move.
+        Static,
+
+        /// Kinematic bodies are bodies that are not affected by any external forces or collisions.
+        /// They will realistically affect colliding dynamic bodies, but not other kinematic bodies.
+        ///
+        /// Unlike static bodies, kinematic bodies can have velocity.
+        /// The engine doesn't modify the values of a kinematic body's components,
+        /// so you have full control of them.
+        Kinematic,
+    }
+});
+
+impl RigidBody {
+    /// Checks if the rigid body is dynamic.
+    pub fn is_dynamic(&self) -> bool {
+        *self == Self::Dynamic
+    }
+
+    /// Checks if the rigid body is static.
+    pub fn is_static(&self) -> bool {
+        *self == Self::Static
+    }
+
+    /// Checks if the rigid body is kinematic.
+    pub fn is_kinematic(&self) -> bool {
+        *self == Self::Kinematic
+    }
+}
+
+/// Returns the names of the components that the given rigid body is missing for it to be simulated,
+/// including its [`Position`] and [`Rotation`].
 ///
-/// # Rigid Body Types
+/// See the [required components](RigidBody#required-components) of rigid bodies.
+pub(crate) fn missing_rigid_body_components(entity: EntityRef) -> Vec<&'static str> {
+    let mut missing = Vec::new();
+
+    macro_rules! check {
+        ([$($component:ty),*]) => {
+            $(
+                if !entity.contains::<$component>() {
+                    missing.push(stringify!($component));
+                }
+            )*
+        };
+    }
+
+    check!([Position, Rotation]);
+    with_rigid_body_components!(check! {});
+
+    missing
+}
+
+/// A marker component that opts a [rigid body](RigidBody) out of the
+/// [components](RigidBody#required-components) that are automatically inserted for simulating it.
 ///
-/// A rigid body can be either dynamic, kinematic or static.
+/// This is useful for advanced use cases where the components are supplied by other means,
+/// for example by a networking or serialization layer that inserts them in a later command.
+/// Until then, the body is not simulated with default values.
 ///
-/// - **Dynamic bodies** are similar to real life objects and are affected by forces and contacts.
-/// - **Kinematic bodies** can only be moved programmatically, which is useful for things like character controllers and moving platforms.
-/// - **Static bodies** can not move, so they can be good for objects in the environment like the ground and walls.
+/// When the marker is added to an entity with a [`RigidBody`], all of the components are removed again,
+/// including ones that were inserted together with the marker. They must be inserted afterwards,
+/// for example in a later command. Rigid bodies without the marker are not affected by this,
+/// and adding the marker before the [`RigidBody`] has no effect.
 ///
-/// # Creation
+/// Rigid bodies that are still missing any of the components in the next physics step
+/// are not simulated, and an error listing the missing components is logged.
 ///
-/// Creating a rigid body is as simple as adding the [`RigidBody`] component,
-/// and an optional [`Collider`]:
+/// # Example
 ///
 /// ```
 #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
@@ -44,266 +416,50 @@ use derive_more::From;
 /// use bevy::prelude::*;
 ///
 /// fn setup(mut commands: Commands) {
-///     // Spawn a dynamic rigid body and specify its position.
-///     commands.spawn((
-///         RigidBody::Dynamic,
-///         Collider::capsule(0.5, 1.5),
-///         Transform::from_xyz(0.0, 3.0, 0.0),
+///     let body = commands
+///         .spawn((RigidBody::Dynamic, NoAutoRigidBodyComponents))
+///         .id();
+///
+///     // Insert the components manually, for example based on saved data.
+///     commands.entity(body).insert((
+///         LinearVelocity::ZERO,
+///         AngularVelocity::ZERO,
+///         // ...
 ///     ));
 /// }
 /// ```
-///
-/// By default, dynamic rigid bodies will have mass properties computed based on the attached colliders
-/// and their [`ColliderDensity`]. See the [Mass properties](#mass-properties) section for more information.
-///
-/// # Movement
-///
-/// A rigid body can be moved in three ways: by modifying its position directly,
-/// by changing its velocity, or by applying forces or impulses.
-///
-/// To change the position of a rigid body, you can simply modify its `Transform`:
-///
-/// ```
-#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
-#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
-/// use bevy::prelude::*;
-///
-/// fn move_bodies(mut query: Query<&mut Transform, With<RigidBody>>) {
-///     for mut transform in query.iter_mut() {
-///         transform.translation.x += 0.1;
-///     }
-/// }
-/// ```
-///
-/// However, moving a dynamic body by changing its position directly is similar
-/// to teleporting the body, which can result in unexpected behavior since the body can move
-/// inside walls.
-///
-/// You can instead change the velocity of a dynamic or kinematic body with the [`LinearVelocity`]
-/// and [`AngularVelocity`] components:
-///
-/// ```
-#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
-#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
-/// use bevy::prelude::*;
-///
-/// fn accelerate_bodies(mut query: Query<(&mut LinearVelocity, &mut AngularVelocity)>) {
-///     for (mut linear_velocity, mut angular_velocity) in query.iter_mut() {
-///         linear_velocity.x += 0.05;
-#[cfg_attr(feature = "2d", doc = "        angular_velocity.0 += 0.05;")]
-#[cfg_attr(feature = "3d", doc = "        angular_velocity.z += 0.05;")]
-///     }
-/// }
-/// ```
-///
-/// For applying forces and impulses to dynamic bodies, see the [`ExternalForce`], [`ExternalTorque`],
-/// [`ExternalImpulse`] and [`ExternalAngularImpulse`] components.
-///
-/// Avian does not have a built-in character controller, so if you need one,
-/// you will need to implement it yourself or use a third party option.
-/// You can take a look at the [`basic_dynamic_character`] and [`basic_kinematic_character`]
-/// examples for a simple implementation.
-///
-/// [`basic_dynamic_character`]: https://github.com/Jondolf/avian/blob/42fb8b21c756a7f4dd91071597dc251245ddaa8f/crates/avian3d/examples/basic_dynamic_character.rs
-/// [`basic_kinematic_character`]: https://github.com/Jondolf/avian/blob/42fb8b21c756a7f4dd91071597dc251245ddaa8f/crates/avian3d/examples/basic_kinematic_character.rs
-///
-/// # Mass Properties
-///
-/// Every dynamic rigid body has [mass], [angular inertia], and a [center of mass].
-/// These mass properties determine how the rigid body responds to forces and torques.
-///
-/// - **Mass**: Represents resistance to linear acceleration. A higher mass requires more force for the same acceleration.
-/// - **Angular Inertia**: Represents resistance to angular acceleration. A higher angular inertia requires more torque for the same angular acceleration.
-/// - **Center of Mass**: The average position of mass in the body. Applying forces at this point produces no torque.
-///
-/// Static and kinematic rigid bodies have infinite mass and angular inertia,
-/// and do not respond to forces or torques. Zero mass for a dynamic body is also
-/// treated as a special case, and corresponds to infinite mass.
-///
-/// If no mass properties are set, they are computed automatically from attached colliders
-/// based on their shape and density.
-///
-/// ```
-#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
-#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
-/// # use bevy::prelude::*;
-/// #
-/// # fn setup(mut commands: Commands) {
-/// // Note: `ColliderDensity` is optional, and defaults to `1.0` if not present.
-/// commands.spawn((
-///     RigidBody::Dynamic,
-///     Collider::capsule(0.5, 1.5),
-///     ColliderDensity(2.0),
-/// ));
-/// # }
-/// ```
-///
-/// If mass properties are set with the [`Mass`], [`AngularInertia`], and [`CenterOfMass`] components,
-/// they override the values computed from colliders.
-///
-/// ```
-#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
-#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
-/// # use bevy::prelude::*;
-/// #
-/// # fn setup(mut commands: Commands) {
-/// // Override mass and the center of mass, but use the collider's angular inertia.
-/// commands.spawn((
-///     RigidBody::Dynamic,
-///     Collider::capsule(0.5, 1.5),
-///     Mass(5.0),
-#[cfg_attr(feature = "2d", doc = "    CenterOfMass::new(0.0, -0.5),")]
-#[cfg_attr(feature = "3d", doc = "    CenterOfMass::new(0.0, -0.5, 0.0),")]
-/// ));
-/// # }
-/// ```
-///
-/// If the rigid body has child colliders, their mass properties will be combined for
-/// the total [`ComputedMass`], [`ComputedAngularInertia`], and [`ComputedCenterOfMass`].
-///
-/// ```
-#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
-#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
-/// # use bevy::prelude::*;
-/// #
-/// # fn setup(mut commands: Commands) {
-/// // Total mass: 10.0 + 5.0 = 15.0
-#[cfg_attr(
-    feature = "2d",
-    doc = "// Total center of mass: (10.0 * [0.0, -0.5] + 5.0 * [0.0, 4.0]) / (10.0 + 5.0) = [0.0, 1.0]"
-)]
-#[cfg_attr(
-    feature = "3d",
-    doc = "// Total center of mass: (10.0 * [0.0, -0.5, 0.0] + 5.0 * [0.0, 4.0, 0.0]) / (10.0 + 5.0) = [0.0, 1.0, 0.0]"
-)]
-/// commands.spawn((
-///     RigidBody::Dynamic,
-///     Collider::capsule(0.5, 1.5),
-///     Mass(10.0),
-#[cfg_attr(feature = "2d", doc = "    CenterOfMass::new(0.0, -0.5),")]
-#[cfg_attr(feature = "3d", doc = "    CenterOfMass::new(0.0, -0.5, 0.0),")]
-///     Transform::default(),
-/// ))
-/// .with_child((
-#[cfg_attr(feature = "2d", doc = "    Collider::circle(1.0),")]
-#[cfg_attr(feature = "3d", doc = "    Collider::sphere(1.0),")]
-///     Mass(5.0),
-///     Transform::from_xyz(0.0, 4.0, 0.0),
-/// ));
-/// # }
-/// ```
-///
-/// To prevent child entities from contributing to the total mass properties, use the [`NoAutoMass`],
-/// [`NoAutoAngularInertia`], and [`NoAutoCenterOfMass`] marker components.
-///
-/// ```
-#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
-#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
-/// # use bevy::prelude::*;
-/// #
-/// # fn setup(mut commands: Commands) {
-/// // Total mass: 10.0
-#[cfg_attr(feature = "2d", doc = "// Total center of mass: [0.0, -0.5]")]
-#[cfg_attr(feature = "3d", doc = "// Total center of mass: [0.0, -0.5, 0.0]")]
-/// commands.spawn((
-///     RigidBody::Dynamic,
-///     Collider::capsule(0.5, 1.5),
-///     Mass(10.0),
-#[cfg_attr(feature = "2d", doc = "    CenterOfMass::new(0.0, -0.5),")]
-#[cfg_attr(feature = "3d", doc = "    CenterOfMass::new(0.0, -0.5, 0.0),")]
-///     NoAutoMass,
-///     NoAutoCenterOfMass,
-///     Transform::default(),
-/// ))
-/// .with_child((
-#[cfg_attr(feature = "2d", doc = "    Collider::circle(1.0),")]
-#[cfg_attr(feature = "3d", doc = "    Collider::sphere(1.0),")]
-///     Mass(5.0),
-///     Transform::from_xyz(0.0, 4.0, 0.0),
-/// ));
-/// # }
-/// ```
-///
-/// See the [`mass_properties`] module for more information.
-///
-/// [mass]: mass_properties::components::Mass
-/// [angular inertia]: mass_properties::components::AngularInertia
-/// [center of mass]: mass_properties::components::CenterOfMass
-/// [mass properties]: mass_properties
-///
-/// # See More
-///
-/// - [Colliders](Collider)
-/// - [Gravity] and [gravity scale](GravityScale)
-/// - [Linear](LinearDamping) and [angular](AngularDamping) velocity damping
-/// - [Friction] and [restitution](Restitution) (bounciness)
-/// - [Lock translational and rotational axes](LockedAxes)
-/// - [Dominance]
-/// - [Continuous Collision Detection](dynamics::ccd)
-/// - [Temporarily disabling a rigid body](RigidBodyDisabled)
-/// - [Automatic deactivation with sleeping](Sleeping)
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
 #[reflect(Debug, Component, Default, PartialEq)]
-#[require(
-    LinearVelocity,
-    AngularVelocity,
-    // TODO: Make these force components optional.
-    ExternalForce,
-    ExternalTorque,
-    ExternalImpulse,
-    ExternalAngularImpulse,
-    ComputedMass,
-    ComputedAngularInertia,
-    ComputedCenterOfMass,
-    // Currently required for solver internals.
-    // Some of these might be removed in the future.
-    AccumulatedTranslation,
-    PreSolveAccumulatedTranslation,
-    PreSolveLinearVelocity,
-    PreSolveAngularVelocity,
-    PreSolveRotation,
-    PreviousRotation,
-)]
-#[cfg_attr(feature = "3d", require(GlobalAngularInertia))]
-pub enum RigidBody {
-    /// Dynamic bodies are bodies that are affected by forces, velocity and collisions.
-    #[default]
-    Dynamic,
-
-    /// Static bodies are not affected by any forces, collisions or velocity, and they act as if they have an infinite mass and moment of inertia.
-    /// The only way to move a static body is to manually change its position.
-    ///
-    /// Collisions with static bodies will affect dynamic bodies, but not other static bodies or kinematic bodies.
-    ///
-    /// Static bodies are typically used for things like the ground, walls and any other objects that you don't want to move.
-    Static,
+#[component(on_add = on_add_no_auto_rigid_body_components)]
+pub struct NoAutoRigidBodyComponents;
 
-    /// Kinematic bodies are bodies that are not affected by any external forces or collisions.
-    /// They will realistically affect colliding dynamic bodies, but not other kinematic bodies.
-    ///
-    /// Unlike static bodies, kinematic bodies can have velocity.
-    /// The engine doesn't modify the values of a kinematic body's components,
-    /// so you have full control of them.
-    Kinematic,
-}
-
-impl RigidBody {
-    /// Checks if the rigid body is dynamic.
-    pub fn is_dynamic(&self) -> bool {
-        *self == Self::Dynamic
+/// Removes the [components](RigidBody#required-components) that were inserted for simulating
+/// the rigid body that the [`NoAutoRigidBodyComponents`] marker was added to.
+fn on_add_no_auto_rigid_body_components(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _id: ComponentId,
+) {
+    if world.get::<RigidBody>(entity).is_none() {
+        return;
     }
 
-    /// Checks if the rigid body is static.
-    pub fn is_static(&self) -> bool {
-        *self == Self::Static
+    macro_rules! remove {
+        ([$($component:ty),*]) => {
+            world
+                .commands()
+                .entity(entity)
+                .queue(|entity: Entity, world: &mut World| {
+                    if let Ok(mut entity) = world.get_entity_mut(entity) {
+                        $(entity.remove::<$component>();)*
+                    }
+                });
+        };
     }
 
-    /// Checks if the rigid body is kinematic.
-    pub fn is_kinematic(&self) -> bool {
-        *self == Self::Kinematic
-    }
+    with_rigid_body_components!(remove! {});
 }
 
 /// A query filter that selects rigid bodies that are neither disabled nor sleeping.
@@ -355,6 +511,119 @@ pub(crate) type RigidBodyActiveFilter = (Without<RigidBodyDisabled>, Without<Sle
 #[reflect(Debug, Component, Default)]
 pub struct RigidBodyDisabled;
 
+/// A component that pauses the simulation of a [rigid body](RigidBody) until it is removed,
+/// for example during cutscenes or for time-stop mechanics.
+///
+/// A frozen body is not moved by the simulation, and other bodies collide with it
+/// as if it was [static](RigidBody::Static). When the component is removed, the body resumes
+/// with the velocity it had when it was frozen.
+///
+/// Unlike [sleeping](Sleeping), frozen bodies are not woken up by other bodies or forces,
+/// and unlike [`RigidBodyDisabled`], they still act as obstacles for other bodies.
+///
+/// Internally, the body is made static while it is frozen, and its [`RigidBody`] type
+/// and velocities are restored when the component is removed. The type of the body
+/// should not be changed while it is frozen.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "# use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "# use avian3d::prelude::*;")]
+/// # use bevy::prelude::*;
+/// #
+/// #[derive(Component)]
+/// pub struct Enemy;
+///
+/// /// Stops time for all enemies.
+/// fn stop_time(mut commands: Commands, query: Query<Entity, With<Enemy>>) {
+///     for entity in &query {
+///         commands.entity(entity).insert(Frozen::default());
+///     }
+/// }
+///
+/// /// Resumes time for all enemies, keeping their momentum.
+/// fn resume_time(mut commands: Commands, query: Query<Entity, With<Enemy>>) {
+///     for entity in &query {
+///         commands.entity(entity).remove::<Frozen>();
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[component(on_add = on_add_frozen, on_remove = on_remove_frozen)]
+pub struct Frozen {
+    rigid_body: Option<RigidBody>,
+    linear_velocity: LinearVelocity,
+    angular_velocity: AngularVelocity,
+}
+
+impl Frozen {
+    /// Returns the [`RigidBody`] type that the body is restored to when it is unfrozen.
+    pub fn rigid_body(&self) -> Option<RigidBody> {
+        self.rigid_body
+    }
+}
+
+/// Stores the type and velocities of a rigid body and makes it static when it is frozen.
+fn on_add_frozen(mut world: DeferredWorld, entity: Entity, _id: ComponentId) {
+    let Some(rigid_body) = world.get::<RigidBody>(entity).copied() else {
+        return;
+    };
+    if rigid_body.is_static() {
+        return;
+    }
+
+    let linear_velocity = world
+        .get::<LinearVelocity>(entity)
+        .copied()
+        .unwrap_or_default();
+    let angular_velocity = world
+        .get::<AngularVelocity>(entity)
+        .copied()
+        .unwrap_or_default();
+
+    if let Some(mut frozen) = world.get_mut::<Frozen>(entity) {
+        *frozen = Frozen {
+            rigid_body: Some(rigid_body),
+            linear_velocity,
+            angular_velocity,
+        };
+    }
+
+    if let Some(mut rb) = world.get_mut::<RigidBody>(entity) {
+        *rb = RigidBody::Static;
+    }
+    if let Some(mut velocity) = world.get_mut::<LinearVelocity>(entity) {
+        *velocity = LinearVelocity::ZERO;
+    }
+    if let Some(mut velocity) = world.get_mut::<AngularVelocity>(entity) {
+        *velocity = AngularVelocity::ZERO;
+    }
+}
+
+/// Restores the type and velocities of a rigid body when it is unfrozen.
+fn on_remove_frozen(mut world: DeferredWorld, entity: Entity, _id: ComponentId) {
+    let Some(frozen) = world.get::<Frozen>(entity).copied() else {
+        return;
+    };
+    let Some(rigid_body) = frozen.rigid_body else {
+        return;
+    };
+
+    if let Some(mut rb) = world.get_mut::<RigidBody>(entity) {
+        *rb = rigid_body;
+    }
+    if let Some(mut velocity) = world.get_mut::<LinearVelocity>(entity) {
+        *velocity = frozen.linear_velocity;
+    }
+    if let Some(mut velocity) = world.get_mut::<AngularVelocity>(entity) {
+        *velocity = frozen.angular_velocity;
+    }
+}
+
 /// Indicates that a [rigid body](RigidBody) is not simulated by the physics engine until woken up again.
 /// This is done to improve performance and to help prevent small jitter that is typically present in collisions.
 ///
@@ -365,6 +634,7 @@ pub struct RigidBodyDisabled;
 ///
 /// Sleeping can be disabled for specific entities with the [`SleepingDisabled`] component,
 /// or for all entities by setting the [`SleepingThreshold`] to a negative value.
+/// The thresholds can also be configured per entity using the [`SleepThreshold`] component.
 #[derive(Reflect, Clone, Copy, Component, Debug, Default)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
@@ -382,12 +652,90 @@ pub struct Sleeping;
 pub struct TimeSleeping(pub Scalar);
 
 /// Indicates that the body can not be deactivated by the physics engine. See [`Sleeping`] for information about sleeping.
+///
+/// This takes precedence over a [`SleepThreshold`] on the same entity. Because bodies sleep
+/// one [island](PhysicsIslands) at a time, a body with sleeping disabled also keeps
+/// the bodies it is in contact with or connected to by joints awake.
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq, From)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
 #[reflect(Debug, Component, Default, PartialEq)]
 pub struct SleepingDisabled;
 
+/// Overrides the global [`SleepingThreshold`] and [`DeactivationTime`] for a specific body.
+///
+/// This can be used to prevent slow-moving bodies that are important for gameplay,
+/// like a slowly rotating puzzle piece, from being put to sleep, while still letting them
+/// sleep once they come to rest. To prevent a body from ever sleeping, use [`SleepingDisabled`] instead,
+/// which takes precedence over this component.
+///
+/// Like with [`SleepingThreshold`], setting a negative linear or angular threshold disables sleeping for the body.
+///
+/// See [`Sleeping`] for further information about sleeping.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A puzzle piece that rotates very slowly, but should still be simulated.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         SleepThreshold {
+///             linear: 0.01,
+///             angular: 0.001,
+///             time: 2.0,
+///         },
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct SleepThreshold {
+    /// The maximum linear velocity allowed for the body to be marked as sleeping.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.15`
+    pub linear: Scalar,
+    /// The maximum angular velocity allowed for the body to be marked as sleeping.
+    ///
+    /// Default: `0.15`
+    pub angular: Scalar,
+    /// How long in seconds the linear and angular velocity of the body need to be
+    /// below the thresholds before the body is deactivated.
+    ///
+    /// Default: `0.5`
+    pub time: Scalar,
+}
+
+impl Default for SleepThreshold {
+    fn default() -> Self {
+        Self {
+            linear: 0.15,
+            angular: 0.15,
+            time: 0.5,
+        }
+    }
+}
+
+impl SleepThreshold {
+    /// Creates a new [`SleepThreshold`] with the given linear and angular velocity thresholds
+    /// and deactivation time in seconds.
+    pub const fn new(linear: Scalar, angular: Scalar, time: Scalar) -> Self {
+        Self {
+            linear,
+            angular,
+            time,
+        }
+    }
+}
+
 /// Translation accumulated during the physics frame.
 ///
 /// When updating position during integration or constraint solving, the required translation
@@ -431,6 +779,21 @@ pub struct LinearVelocity(pub Vector);
 impl LinearVelocity {
     /// Zero linear velocity.
     pub const ZERO: LinearVelocity = LinearVelocity(Vector::ZERO);
+
+    /// Computes the linear velocity needed to move from `current` to `target` in `delta_secs` seconds.
+    ///
+    /// This can be used to move [kinematic](RigidBody::Kinematic) bodies towards a target position.
+    /// Unlike setting the [`Position`] directly, this gives the body a velocity,
+    /// so that it interacts correctly with dynamic bodies that it touches.
+    /// See also [`KinematicTarget`].
+    ///
+    /// If `delta_secs` is zero or negative, zero linear velocity is returned.
+    pub fn to_reach(current: Vector, target: Vector, delta_secs: Scalar) -> Self {
+        if delta_secs <= 0.0 {
+            return Self::ZERO;
+        }
+        Self((target - current) / delta_secs)
+    }
 }
 
 /// The maximum linear speed of a [rigid body](RigidBody), clamping the [`LinearVelocity`].
@@ -466,6 +829,8 @@ impl Default for MaxLinearSpeed {
 ///
 /// This can be useful for limiting how fast bodies can rotate, and can help control behavior and prevent instability.
 ///
+/// This overrides [`RigidBodyLimitsConfig::max_angular_speed`] for the body.
+///
 /// # Example
 ///
 /// ```
@@ -491,9 +856,182 @@ impl Default for MaxAngularSpeed {
     }
 }
 
+/// The maximum speed at which the solver pushes a [rigid body](RigidBody) out of overlap with other bodies.
+///
+/// This overrides [`SolverConfig::max_overlap_solve_speed`](dynamics::solver::SolverConfig::max_overlap_solve_speed)
+/// for contacts involving the body. If both bodies in a contact have a [`MaxOverlapSolveSpeed`],
+/// the smaller one is used. Unlike the global setting, the value is not scaled by the [`PhysicsLengthUnit`].
+///
+/// A small value is useful for props that are spawned slightly overlapping,
+/// so that they settle gently instead of being launched apart.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// // Spawn a dynamic body that is pushed out of overlap at `0.5` units per second at most.
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((RigidBody::Dynamic, MaxOverlapSolveSpeed(0.5)));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Deref, DerefMut, PartialEq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+#[doc(alias = "MaxDepenetrationVelocity", alias = "MaxDepenetrationSpeed")]
+pub struct MaxOverlapSolveSpeed(pub Scalar);
+
+/// Disables warm starting for contacts involving a [rigid body](RigidBody) for the given number
+/// of physics steps. The component is removed automatically once the steps have elapsed.
+///
+/// Warm starting reuses the contact impulses from the previous step to help the solver converge.
+/// For a body that is spawned or teleported directly into contact at high speed, the matched
+/// contacts may be stale, which can apply incorrect impulses on the first steps. Inserting this
+/// component when spawning or teleporting the body avoids this.
+///
+/// Default: `1` step
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// // Spawn a fast projectile without warm starting for its first two physics steps.
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         LinearVelocity(Vector::X * 100.0),
+///         SkipWarmStart(2),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Deref, DerefMut, PartialEq, Eq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct SkipWarmStart(pub u32);
+
+impl Default for SkipWarmStart {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Controls how much of the velocity of a kinematic [rigid body](RigidBody) is transferred
+/// to the bodies in contact with it through [`Friction`], like bodies standing on a moving platform.
+///
+/// By default, friction uses the full velocity of kinematic bodies at the contact points,
+/// including the tangential velocity caused by their [`AngularVelocity`]. This way, a rotating
+/// platform carries the bodies standing on it like a carousel, instead of spinning under them.
+///
+/// Setting `linear` or `angular` to a value between `0.0` and `1.0` transfers only a part of the
+/// linear or angular velocity of the platform, and `0.0` lets the platform move under the bodies
+/// without carrying them. The contact normal is not affected, so bodies are still pushed by the platform.
+///
+/// This only affects kinematic bodies.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A spinning platform that moves bodies along with its linear velocity,
+///     // but doesn't make them spin around with it.
+///     commands.spawn((
+///         RigidBody::Kinematic,
+#[cfg_attr(feature = "2d", doc = "        AngularVelocity(1.0),")]
+#[cfg_attr(feature = "3d", doc = "        AngularVelocity(Vector::Y),")]
+///         PlatformVelocityTransfer::new(1.0, 0.0),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct PlatformVelocityTransfer {
+    /// The fraction of the [`LinearVelocity`] of the platform that is transferred through friction.
+    ///
+    /// Default: `1.0`
+    pub linear: Scalar,
+    /// The fraction of the tangential velocity caused by the [`AngularVelocity`] of the platform
+    /// that is transferred through friction.
+    ///
+    /// Default: `1.0`
+    pub angular: Scalar,
+}
+
+impl Default for PlatformVelocityTransfer {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl PlatformVelocityTransfer {
+    /// The full velocity of the platform is transferred. This is the default.
+    pub const FULL: Self = Self {
+        linear: 1.0,
+        angular: 1.0,
+    };
+
+    /// No velocity is transferred, so the platform moves under bodies without carrying them.
+    pub const NONE: Self = Self {
+        linear: 0.0,
+        angular: 0.0,
+    };
+
+    /// Creates a new [`PlatformVelocityTransfer`] with the given fractions
+    /// of the linear and angular velocity.
+    pub const fn new(linear: Scalar, angular: Scalar) -> Self {
+        Self { linear, angular }
+    }
+}
+
+/// The minimum radius of gyration of a dynamic [rigid body](RigidBody), clamping its [`ComputedAngularInertia`].
+///
+/// The angular inertia of the body is clamped to be at least `mass * radius²` about each principal axis,
+/// as if its mass was at least this far from the center of mass. This prevents very small bodies
+/// from spinning up to extreme speeds from small contact impulses.
+///
+/// This overrides [`RigidBodyLimitsConfig::min_inertia_radius`] for the body,
+/// and is implicitly scaled by the [`PhysicsLengthUnit`].
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// // Spawn a tiny piece of debris that rotates like a body with a radius of `0.1`.
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(0.01),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(0.01),")]
+///         MinInertiaRadius(0.1),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct MinInertiaRadius(pub Scalar);
+
 /// The linear velocity of a [rigid body](RigidBody) before the velocity solve is performed.
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
-#[reflect(Component)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
 pub(crate) struct PreSolveLinearVelocity(pub Vector);
 
 /// The angular velocity of a [rigid body](RigidBody) in radians per second.
@@ -559,20 +1097,173 @@ impl AngularVelocity {
     /// Zero angular velocity.
     #[cfg(feature = "3d")]
     pub const ZERO: AngularVelocity = AngularVelocity(Vector::ZERO);
+
+    /// Computes the angular velocity needed to rotate from `current` to `target`
+    /// along the shortest path in `delta_secs` seconds.
+    ///
+    /// This can be used to rotate [kinematic](RigidBody::Kinematic) bodies towards a target rotation.
+    /// Unlike setting the [`Rotation`] directly, this gives the body a velocity,
+    /// so that it interacts correctly with dynamic bodies that it touches.
+    ///
+    /// If `delta_secs` is zero or negative, zero angular velocity is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct TargetRotation(Rotation);
+    ///
+    /// fn rotate_towards_target(
+    ///     mut query: Query<(&Rotation, &TargetRotation, &mut AngularVelocity)>,
+    ///     time: Res<Time<Fixed>>,
+    /// ) {
+    ///     for (rotation, target, mut angular_velocity) in &mut query {
+    ///         *angular_velocity =
+    ///             AngularVelocity::to_reach(*rotation, target.0, time.timestep().as_secs_f32() as Scalar);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "2d")]
+    pub fn to_reach(current: Rotation, target: Rotation, delta_secs: Scalar) -> Self {
+        if delta_secs <= 0.0 {
+            return Self::ZERO;
+        }
+        Self(current.angle_between(target) / delta_secs)
+    }
+
+    /// Computes the angular velocity needed to rotate from `current` to `target`
+    /// along the shortest path in `delta_secs` seconds.
+    ///
+    /// This can be used to rotate [kinematic](RigidBody::Kinematic) bodies towards a target rotation.
+    /// Unlike setting the [`Rotation`] directly, this gives the body a velocity,
+    /// so that it interacts correctly with dynamic bodies that it touches.
+    ///
+    /// If `delta_secs` is zero or negative, zero angular velocity is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct TargetRotation(Rotation);
+    ///
+    /// fn rotate_towards_target(
+    ///     mut query: Query<(&Rotation, &TargetRotation, &mut AngularVelocity)>,
+    ///     time: Res<Time<Fixed>>,
+    /// ) {
+    ///     for (rotation, target, mut angular_velocity) in &mut query {
+    ///         *angular_velocity =
+    ///             AngularVelocity::to_reach(*rotation, target.0, time.timestep().as_secs_f32() as Scalar);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "3d")]
+    pub fn to_reach(current: Rotation, target: Rotation, delta_secs: Scalar) -> Self {
+        if delta_secs <= 0.0 {
+            return Self::ZERO;
+        }
+
+        let mut delta = target.0 * current.0.inverse();
+
+        // Take the shortest path.
+        if delta.w < 0.0 {
+            delta = -delta;
+        }
+
+        Self(delta.to_scaled_axis() / delta_secs)
+    }
+}
+
+/// A target pose for a [kinematic](RigidBody::Kinematic) body to reach by the end of the next physics step.
+///
+/// Before each physics step, the [`LinearVelocity`] and [`AngularVelocity`] of the body are set
+/// with [`LinearVelocity::to_reach`] and [`AngularVelocity::to_reach`] so that the body moves to the target
+/// during the step. Unlike setting the [`Position`] and [`Rotation`] directly, this gives the body
+/// a velocity, so that it pushes and carries dynamic bodies correctly.
+///
+/// The target can be updated at any time, for example to follow an animation or a path.
+/// Once the target has been reached, the velocity of the body is zero until the target changes.
+/// A `None` position or rotation leaves the corresponding velocity untouched.
+///
+/// The component has no effect on dynamic and static bodies.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Elevator;
+///
+/// fn move_elevators(mut query: Query<&mut KinematicTarget, With<Elevator>>, time: Res<Time>) {
+///     for mut target in &mut query {
+///         let height = 5.0 * (time.elapsed_secs() as Scalar).sin();
+///         target.position = Some(Vector::Y * height);
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct KinematicTarget {
+    /// The target position of the body.
+    pub position: Option<Vector>,
+    /// The target rotation of the body.
+    pub rotation: Option<Rotation>,
+}
+
+impl KinematicTarget {
+    /// Creates a new [`KinematicTarget`] with the given target position and rotation.
+    pub fn new(position: Vector, rotation: impl Into<Rotation>) -> Self {
+        Self {
+            position: Some(position),
+            rotation: Some(rotation.into()),
+        }
+    }
+
+    /// Creates a new [`KinematicTarget`] with the given target position, leaving the rotation untouched.
+    pub fn from_position(position: Vector) -> Self {
+        Self {
+            position: Some(position),
+            rotation: None,
+        }
+    }
+
+    /// Creates a new [`KinematicTarget`] with the given target rotation, leaving the position untouched.
+    pub fn from_rotation(rotation: impl Into<Rotation>) -> Self {
+        Self {
+            position: None,
+            rotation: Some(rotation.into()),
+        }
+    }
 }
 
 /// The angular velocity of a [rigid body](RigidBody) in radians per second, before
 /// the velocity solve is performed. Positive values will result in counterclockwise rotation.
 #[cfg(feature = "2d")]
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, From)]
-#[reflect(Component)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
 pub(crate) struct PreSolveAngularVelocity(pub Scalar);
 
 /// The angular velocity of a [rigid body](RigidBody) as a rotation axis
 /// multiplied by the angular speed in radians per second, before the velocity solve is performed.
 #[cfg(feature = "3d")]
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
-#[reflect(Component)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
 pub(crate) struct PreSolveAngularVelocity(pub Vector);
 
 /// Controls how [gravity](Gravity) affects a specific [rigid body](RigidBody).
@@ -604,6 +1295,44 @@ impl Default for GravityScale {
     }
 }
 
+/// Overrides the gravitational acceleration of a specific [rigid body](RigidBody),
+/// replacing the global [`Gravity`] and any [`GravityField`]s that the body is inside.
+///
+/// The [`GravityScale`] of the body is still applied to the overridden gravity.
+/// The override can be changed at any time, for example to make a body fall sideways
+/// or towards a moving attractor.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// // Spawn a dynamic body that falls to the right.
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((RigidBody::Dynamic, GravityOverride(Vector::X * 9.81)));
+/// }
+///
+/// // Pull bodies towards an attractor.
+/// fn attract(
+///     attractor: Single<&Position, With<Attractor>>,
+///     mut bodies: Query<(&Position, &mut GravityOverride), Without<Attractor>>,
+/// ) {
+///     for (position, mut gravity) in &mut bodies {
+///         gravity.0 = (attractor.0 - position.0).normalize_or_zero() * 9.81;
+///     }
+/// }
+/// #
+/// # #[derive(Component)]
+/// # struct Attractor;
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Default, Deref, DerefMut, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct GravityOverride(pub Vector);
+
 /// Automatically slows down a dynamic [rigid body](RigidBody), decreasing its
 /// [linear velocity](LinearVelocity) each frame. This can be used to simulate air resistance.
 ///
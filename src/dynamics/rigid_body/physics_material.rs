@@ -283,6 +283,10 @@ impl From<Scalar> for Friction {
 /// - Collisions can have more or less bounce than expected, especially when objects are moving very fast.
 ///   This is largely due to the the sequential solver and [speculative collision](dynamics::ccd#speculative-collision).
 ///
+/// - When several bodies collide in the same time step, like billiard balls in a tight cluster,
+///   energy can be lost with the default restitution model. Using
+///   [`RestitutionModel::Poisson`](dynamics::solver::RestitutionModel::Poisson) may help mitigate this.
+///
 /// - An object falling flat on the ground with multiple contact points may tip over on one side or corner a bit.
 ///   This is because contact points are solved sequentially, and the order of contact points affects the result.
 ///   Configuring [`SolverConfig::restitution_iterations`](dynamics::solver::SolverConfig::restitution_iterations) may help mitigate this.
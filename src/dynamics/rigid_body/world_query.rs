@@ -29,9 +29,13 @@ pub struct RigidBodyQuery {
     pub restitution: Option<&'static Restitution>,
     pub locked_axes: Option<&'static LockedAxes>,
     pub dominance: Option<&'static Dominance>,
+    pub max_overlap_solve_speed: Option<&'static MaxOverlapSolveSpeed>,
+    pub platform_velocity_transfer: Option<&'static PlatformVelocityTransfer>,
     pub time_sleeping: &'static mut TimeSleeping,
     pub is_sleeping: Has<Sleeping>,
     pub is_sensor: Has<Sensor>,
+    pub is_response_disabled: Has<ContactResponseDisabled>,
+    pub skip_warm_start: Has<SkipWarmStart>,
 }
 
 impl RigidBodyQueryItem<'_> {
@@ -16,6 +16,10 @@ use bevy::{
 /// Bodies are woken up when an active body or constraint interacts with them, or when gravity changes,
 /// or when the body's position, rotation, velocity, or external forces are changed.
 ///
+/// Bodies that are connected through contacts or joints form [simulation islands](PhysicsIslands),
+/// which fall asleep and wake up as a unit. An island only falls asleep once all of its bodies
+/// have been still for long enough, and waking up any body in an island wakes up the whole island.
+///
 /// This plugin does *not* handle constraints waking up bodies. That is done by the [solver](dynamics::solver).
 ///
 /// The sleeping systems run in [`PhysicsStepSet::Sleeping`].
@@ -40,6 +44,7 @@ impl Plugin for SleepingPlugin {
             (
                 wake_on_changed,
                 wake_all_sleeping_bodies.run_if(resource_changed::<Gravity>),
+                wake_islands,
                 mark_sleeping_bodies,
             )
                 .chain()
@@ -111,19 +116,27 @@ impl Default for DeactivationTime {
 
 /// Adds the [`Sleeping`] component to bodies whose linear and anigular velocities have been
 /// under the [`SleepingThreshold`] for a duration indicated by [`DeactivationTime`].
+///
+/// Bodies with a [`SleepThreshold`] component use it instead of the global resources.
+///
+/// Bodies are put to sleep one [island](PhysicsIslands) at a time, only once every body
+/// in the island has been still for long enough. If the [`IslandPlugin`] is disabled,
+/// bodies only sleep if they are not in contact with other dynamic bodies.
 #[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
 pub fn mark_sleeping_bodies(
     mut commands: Commands,
-    mut query: Query<
-        (
-            Entity,
-            &RigidBody,
-            &mut LinearVelocity,
-            &mut AngularVelocity,
-            &mut TimeSleeping,
-        ),
-        (Without<Sleeping>, Without<SleepingDisabled>),
-    >,
+    mut query: Query<(
+        Entity,
+        &RigidBody,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+        &mut TimeSleeping,
+        Has<Sleeping>,
+        Has<SleepingDisabled>,
+        Option<&SleepThreshold>,
+    )>,
+    islands: Option<Res<PhysicsIslands>>,
     collisions: Res<Collisions>,
     rb_query: Query<&RigidBody>,
     deactivation_time: Res<DeactivationTime>,
@@ -134,28 +147,26 @@ pub fn mark_sleeping_bodies(
     let length_unit_sq = length_unit.powi(2);
     let delta_secs = time.delta_seconds_adjusted();
 
-    for (entity, rb, mut lin_vel, mut ang_vel, mut time_sleeping) in &mut query {
-        let colliding_entities = collisions.collisions_with_entity(entity).map(|c| {
-            if entity == c.entity1 {
-                c.entity2
-            } else {
-                c.entity1
-            }
-        });
+    let global_threshold = SleepThreshold {
+        linear: sleep_threshold.linear,
+        angular: sleep_threshold.angular,
+        time: deactivation_time.0,
+    };
 
-        // Only dynamic bodies can sleep, and only if they are not
-        // in contact with other dynamic bodies.
-        //
-        // Contacts with other types of bodies will be allowed once
-        // sleeping/waking is implemented with simulation islands.
-        if !rb.is_dynamic()
-            || rb_query
-                .iter_many(colliding_entities)
-                .any(|rb| rb.is_dynamic())
-        {
+    for (_, rb, lin_vel, ang_vel, mut time_sleeping, is_sleeping, sleeping_disabled, threshold) in
+        &mut query
+    {
+        // Only awake dynamic bodies need to track how long they have been still.
+        if !rb.is_dynamic() || is_sleeping || sleeping_disabled {
             continue;
         }
 
+        let threshold = threshold.unwrap_or(&global_threshold);
+
+        // Negative thresholds indicate that sleeping is disabled.
+        let lin_sleeping_threshold_sq = length_unit_sq * threshold.linear * threshold.linear.abs();
+        let ang_sleeping_threshold_sq = threshold.angular * threshold.angular.abs();
+
         let lin_vel_sq = lin_vel.length_squared();
 
         #[cfg(feature = "2d")]
@@ -163,11 +174,6 @@ pub fn mark_sleeping_bodies(
         #[cfg(feature = "3d")]
         let ang_vel_sq = ang_vel.0.dot(ang_vel.0);
 
-        // Negative thresholds indicate that sleeping is disabled.
-        let lin_sleeping_threshold_sq =
-            length_unit_sq * sleep_threshold.linear * sleep_threshold.linear.abs();
-        let ang_sleeping_threshold_sq = sleep_threshold.angular * sleep_threshold.angular.abs();
-
         // If linear and angular velocity are below the sleeping threshold,
         // add delta time to the time sleeping, i.e. the time that the body has remained still.
         if lin_vel_sq < lin_sleeping_threshold_sq && ang_vel_sq < ang_sleeping_threshold_sq {
@@ -175,12 +181,120 @@ pub fn mark_sleeping_bodies(
         } else {
             time_sleeping.0 = 0.0;
         }
+    }
 
-        // If the body has been still for long enough, set it to sleep and reset velocities.
-        if time_sleeping.0 > deactivation_time.0 {
+    // A body is ready to sleep if it is already sleeping, or if it has been still for long enough.
+    let is_ready_to_sleep = |time_sleeping: &TimeSleeping,
+                             is_sleeping: bool,
+                             disabled: bool,
+                             threshold: Option<&SleepThreshold>| {
+        let time = threshold.map_or(deactivation_time.0, |threshold| threshold.time);
+        is_sleeping || (!disabled && time_sleeping.0 > time)
+    };
+
+    let mut put_to_sleep =
+        |entity: Entity, lin_vel: &mut LinearVelocity, ang_vel: &mut AngularVelocity| {
             commands.entity(entity).try_insert(Sleeping);
             *lin_vel = LinearVelocity::ZERO;
             *ang_vel = AngularVelocity::ZERO;
+        };
+
+    let Some(islands) = islands else {
+        // Without islands, only bodies that are not in contact
+        // with other dynamic bodies can sleep.
+        for (
+            entity,
+            rb,
+            mut lin_vel,
+            mut ang_vel,
+            time_sleeping,
+            is_sleeping,
+            disabled,
+            threshold,
+        ) in &mut query
+        {
+            if !rb.is_dynamic()
+                || is_sleeping
+                || !is_ready_to_sleep(&time_sleeping, is_sleeping, disabled, threshold)
+            {
+                continue;
+            }
+
+            let colliding_entities = collisions.collisions_with_entity(entity).map(|c| {
+                if entity == c.entity1 {
+                    c.entity2
+                } else {
+                    c.entity1
+                }
+            });
+
+            if rb_query
+                .iter_many(colliding_entities)
+                .any(|rb| rb.is_dynamic())
+            {
+                continue;
+            }
+
+            put_to_sleep(entity, &mut lin_vel, &mut ang_vel);
+        }
+        return;
+    };
+
+    // Islands only sleep as a whole, so that for example a stack of boxes
+    // doesn't collapse when the bottom box falls asleep before the ones above it.
+    for island in islands.iter() {
+        let ready_to_sleep = query.iter_many(island.bodies()).all(
+            |(.., time_sleeping, is_sleeping, disabled, threshold)| {
+                is_ready_to_sleep(time_sleeping, is_sleeping, disabled, threshold)
+            },
+        );
+
+        if !ready_to_sleep {
+            continue;
+        }
+
+        let mut bodies = query.iter_many_mut(island.bodies());
+        while let Some((entity, _, mut lin_vel, mut ang_vel, _, is_sleeping, ..)) =
+            bodies.fetch_next()
+        {
+            if !is_sleeping {
+                put_to_sleep(entity, &mut lin_vel, &mut ang_vel);
+            }
+        }
+    }
+}
+
+/// Wakes up every body in [islands](PhysicsIslands) where some of the bodies are sleeping
+/// and others are awake, so that islands sleep and wake up as a unit.
+///
+/// This happens when a sleeping body is woken up by a collision, a joint, or a change made by the user.
+fn wake_islands(
+    mut commands: Commands,
+    islands: Option<Res<PhysicsIslands>>,
+    mut bodies: Query<(Entity, &mut TimeSleeping, Has<Sleeping>), Without<RigidBodyDisabled>>,
+) {
+    let Some(islands) = islands else {
+        return;
+    };
+
+    for island in islands.iter() {
+        let mut body_count = 0;
+        let mut sleeping_count = 0;
+        for (_, _, is_sleeping) in bodies.iter_many(island.bodies()) {
+            body_count += 1;
+            sleeping_count += is_sleeping as usize;
+        }
+
+        if sleeping_count == 0 || sleeping_count == body_count {
+            continue;
+        }
+
+        let mut island_bodies = bodies.iter_many_mut(island.bodies());
+        while let Some((entity, mut time_sleeping, is_sleeping)) = island_bodies.fetch_next() {
+            if is_sleeping {
+                commands.entity(entity).remove::<Sleeping>();
+            }
+            time_sleeping.0 = 0.0;
         }
     }
 }
@@ -0,0 +1,847 @@
+//! Contact points stored in batches for solving several contacts at once.
+//!
+//! See [`SolverConfig::contact_batches`](crate::dynamics::solver::SolverConfig::contact_batches).
+
+use super::{wide::*, ContactConstraint, ContactConstraintPoint};
+use crate::prelude::*;
+#[cfg(feature = "parallel")]
+use bevy::tasks::ComputeTaskPool;
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use std::ops::Range;
+
+/// The contact points of the [`ContactConstraints`](crate::dynamics::solver::ContactConstraints)
+/// stored in batches of [`LANES`] points using a structure-of-arrays layout,
+/// such that the points of a batch can be solved lane-wise.
+///
+/// No two points in a batch share a dynamic body, so the velocities of the bodies can be gathered
+/// for each lane, updated lane-wise, and scattered back without conflicts.
+///
+/// The batches are built from the contact constraints before the substepping loop,
+/// and the accumulated impulses are written back to the constraints after it.
+///
+/// With the `parallel` feature and the [`IslandPlugin`], the batches are split into
+/// [`BatchGroup`]s of whole [simulation islands](crate::dynamics::islands) that are solved in parallel.
+#[derive(Resource, Default)]
+pub(crate) struct ContactConstraintBatches {
+    /// The groups of batches. No two groups share a dynamic body.
+    groups: Vec<BatchGroup>,
+}
+
+/// The batches of a range of [simulation islands](crate::dynamics::islands), solved by a single task.
+#[derive(Default)]
+struct BatchGroup {
+    /// The bodies referenced by the batches. The first body is a placeholder
+    /// with no mass that is used for the empty lanes of batches.
+    bodies: Vec<Entity>,
+    /// The batches of contact points.
+    batches: Vec<ContactBatch>,
+    /// The solver state of each body, gathered from the ECS for each solve.
+    states: Vec<SolverBodyState>,
+}
+
+/// A batch of [`LANES`] contact points that don't share any dynamic bodies.
+#[derive(Default)]
+struct ContactBatch {
+    /// The indices of the constraint and point of each lane, or `None` for empty lanes.
+    points: [Option<(usize, usize)>; LANES],
+    /// The indices of the first bodies in [`BatchGroup::bodies`].
+    body1: [usize; LANES],
+    /// The indices of the second bodies in [`BatchGroup::bodies`].
+    body2: [usize; LANES],
+    normal: WideVector,
+    local_anchor1: [Vector; LANES],
+    local_anchor2: [Vector; LANES],
+    anchor1: WideVector,
+    anchor2: WideVector,
+    initial_separation: Wide,
+    max_overlap_solve_speed: [Option<Scalar>; LANES],
+    normal_impulse: Wide,
+    normal_effective_mass: Wide,
+    softness_bias: Wide,
+    softness_mass_scale: Wide,
+    softness_impulse_scale: Wide,
+    max_normal_impulse: Wide,
+    total_normal_impulse: Wide,
+    friction: Wide,
+    friction_velocity_offset: WideVector,
+    #[cfg(feature = "2d")]
+    tangent_impulse: Wide,
+    #[cfg(feature = "2d")]
+    tangent_effective_mass: Wide,
+    #[cfg(feature = "3d")]
+    tangent_impulse: [Wide; 2],
+    #[cfg(feature = "3d")]
+    tangent_effective_inverse_mass: [Wide; 3],
+    /// The tangent used when the relative velocity of the bodies is along the normal.
+    #[cfg(feature = "3d")]
+    fallback_tangent: WideVector,
+}
+
+/// The state of a body used by the contact solver.
+#[derive(Clone, Copy, Default)]
+struct SolverBodyState {
+    linear_velocity: Vector,
+    #[cfg(feature = "2d")]
+    angular_velocity: Scalar,
+    #[cfg(feature = "3d")]
+    angular_velocity: Vector,
+    inverse_mass: Vector,
+    #[cfg(feature = "2d")]
+    inverse_angular_inertia: Scalar,
+    #[cfg(feature = "3d")]
+    inverse_angular_inertia: Matrix,
+    rotation: Rotation,
+    accumulated_translation: Vector,
+    is_dynamic: bool,
+    dominance: i8,
+}
+
+impl SolverBodyState {
+    /// The state of the placeholder body, which has no mass.
+    fn placeholder() -> Self {
+        Self {
+            inverse_angular_inertia: ComputedAngularInertia::INFINITY.inverse(),
+            ..default()
+        }
+    }
+
+    fn from_body(body: &RigidBodyQueryReadOnlyItem) -> Self {
+        Self {
+            linear_velocity: body.linear_velocity.0,
+            angular_velocity: body.angular_velocity.0,
+            inverse_mass: body.effective_inverse_mass(),
+            inverse_angular_inertia: body.effective_global_angular_inertia().inverse(),
+            rotation: *body.rotation,
+            accumulated_translation: body.accumulated_translation.0,
+            is_dynamic: body.rb.is_dynamic(),
+            dominance: body.dominance(),
+        }
+    }
+}
+
+/// The bodies of the lanes of a [`ContactBatch`], gathered from the [`SolverBodyState`]s.
+struct WideBodyPair {
+    linear_velocity1: WideVector,
+    angular_velocity1: WideAngular,
+    linear_velocity2: WideVector,
+    angular_velocity2: WideAngular,
+    inverse_mass1: WideVector,
+    inverse_mass2: WideVector,
+    inverse_angular_inertia1: WideAngularInertia,
+    inverse_angular_inertia2: WideAngularInertia,
+}
+
+impl WideBodyPair {
+    /// Gathers the bodies of the given batch.
+    ///
+    /// A body is only affected by the contact if it is dynamic and its dominance is not higher than that
+    /// of the other body. Otherwise, its inverse mass and angular inertia are zero.
+    fn gather(batch: &ContactBatch, states: &[SolverBodyState]) -> Self {
+        let body1 = |i: usize| &states[batch.body1[i]];
+        let body2 = |i: usize| &states[batch.body2[i]];
+        let affected1: [bool; LANES] = std::array::from_fn(|i| {
+            body1(i).is_dynamic && body1(i).dominance <= body2(i).dominance
+        });
+        let affected2: [bool; LANES] = std::array::from_fn(|i| {
+            body2(i).is_dynamic && body2(i).dominance <= body1(i).dominance
+        });
+        let no_inertia = ComputedAngularInertia::INFINITY.inverse();
+
+        Self {
+            linear_velocity1: WideVector::from_fn(|i| body1(i).linear_velocity),
+            angular_velocity1: wide_angular_from_fn(|i| body1(i).angular_velocity),
+            linear_velocity2: WideVector::from_fn(|i| body2(i).linear_velocity),
+            angular_velocity2: wide_angular_from_fn(|i| body2(i).angular_velocity),
+            inverse_mass1: WideVector::from_fn(|i| {
+                if affected1[i] {
+                    body1(i).inverse_mass
+                } else {
+                    Vector::ZERO
+                }
+            }),
+            inverse_mass2: WideVector::from_fn(|i| {
+                if affected2[i] {
+                    body2(i).inverse_mass
+                } else {
+                    Vector::ZERO
+                }
+            }),
+            inverse_angular_inertia1: wide_angular_inertia_from_fn(|i| {
+                if affected1[i] {
+                    body1(i).inverse_angular_inertia
+                } else {
+                    no_inertia
+                }
+            }),
+            inverse_angular_inertia2: wide_angular_inertia_from_fn(|i| {
+                if affected2[i] {
+                    body2(i).inverse_angular_inertia
+                } else {
+                    no_inertia
+                }
+            }),
+        }
+    }
+
+    /// Scatters the velocities of the bodies of the given batch back to the [`SolverBodyState`]s.
+    fn scatter(&self, batch: &ContactBatch, states: &mut [SolverBodyState]) {
+        for i in 0..LANES {
+            if batch.points[i].is_none() {
+                continue;
+            }
+
+            let body1 = &mut states[batch.body1[i]];
+            body1.linear_velocity = self.linear_velocity1.lane(i);
+            body1.angular_velocity = wide_angular_lane(&self.angular_velocity1, i);
+
+            let body2 = &mut states[batch.body2[i]];
+            body2.linear_velocity = self.linear_velocity2.lane(i);
+            body2.angular_velocity = wide_angular_lane(&self.angular_velocity2, i);
+        }
+    }
+
+    /// Computes the velocity of the second body relative to the first body at the given points.
+    #[inline]
+    fn relative_velocity(&self, r1: WideVector, r2: WideVector) -> WideVector {
+        velocity_at_point(self.linear_velocity2, self.angular_velocity2, r2)
+            - velocity_at_point(self.linear_velocity1, self.angular_velocity1, r1)
+    }
+
+    /// Applies the given impulse at the given points, negated for the first body.
+    #[inline]
+    fn apply_impulse(&mut self, impulse: WideVector, r1: WideVector, r2: WideVector) {
+        self.linear_velocity1 -= impulse * self.inverse_mass1;
+        self.angular_velocity1 -= self.inverse_angular_inertia1 * wide_cross(r1, impulse);
+        self.linear_velocity2 += impulse * self.inverse_mass2;
+        self.angular_velocity2 += self.inverse_angular_inertia2 * wide_cross(r2, impulse);
+    }
+}
+
+impl ContactConstraintBatches {
+    /// Rebuilds the batches from the given contact constraints.
+    ///
+    /// If the `islands` match the constraints and the `parallel` feature is enabled,
+    /// the constraints are split into [`BatchGroup`]s of whole islands. Otherwise,
+    /// all constraints are batched in a single group.
+    pub(crate) fn build(
+        &mut self,
+        constraints: &[ContactConstraint],
+        islands: Option<&PhysicsIslands>,
+        bodies: &Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    ) {
+        let ranges = group_ranges(constraints, islands);
+        self.groups.resize_with(ranges.len(), BatchGroup::default);
+
+        for (group, (constraint_range, island_range)) in self.groups.iter_mut().zip(ranges) {
+            let islands = islands.zip(island_range);
+            group.build(constraints, constraint_range, islands, bodies);
+        }
+    }
+
+    /// Writes the accumulated impulses of the batches back to the given contact constraints.
+    pub(crate) fn write_back(&self, constraints: &mut [ContactConstraint]) {
+        for batch in self.groups.iter().flat_map(|group| group.batches.iter()) {
+            for (lane, indices) in batch.points.iter().enumerate() {
+                let Some(point) = indices.and_then(|(constraint_index, point_index)| {
+                    constraints
+                        .get_mut(constraint_index)
+                        .and_then(|constraint| constraint.points.get_mut(point_index))
+                }) else {
+                    continue;
+                };
+
+                point.normal_part.impulse = batch.normal_impulse.lane(lane);
+                point.max_normal_impulse = batch.max_normal_impulse.lane(lane);
+                point.total_normal_impulse = batch.total_normal_impulse.lane(lane);
+
+                if let Some(tangent_part) = point.tangent_part.as_mut() {
+                    #[cfg(feature = "2d")]
+                    {
+                        tangent_part.impulse = batch.tangent_impulse.lane(lane);
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        tangent_part.impulse = Vector2::new(
+                            batch.tangent_impulse[0].lane(lane),
+                            batch.tangent_impulse[1].lane(lane),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Warm starts the contact points by applying the accumulated impulses
+    /// from the previous frame or substep.
+    pub(crate) fn warm_start(
+        &mut self,
+        bodies: &mut Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+        warm_start_coefficient: Scalar,
+    ) {
+        self.for_each_group(bodies, |group| {
+            for batch in group.batches.iter_mut() {
+                let mut pair = WideBodyPair::gather(batch, &group.states);
+                batch.warm_start(&mut pair, warm_start_coefficient);
+                pair.scatter(batch, &mut group.states);
+            }
+        });
+    }
+
+    /// Solves the contact points, applying impulses to the bodies.
+    ///
+    /// The `max_overlap_solve_speed` is only used for points whose constraint
+    /// doesn't have its own [`max_overlap_solve_speed`](ContactConstraint::max_overlap_solve_speed).
+    pub(crate) fn solve(
+        &mut self,
+        bodies: &mut Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+        delta_secs: Scalar,
+        use_bias: bool,
+        max_overlap_solve_speed: Scalar,
+    ) {
+        self.for_each_group(bodies, |group| {
+            for batch in group.batches.iter_mut() {
+                let mut pair = WideBodyPair::gather(batch, &group.states);
+                let separation = batch.separation(&group.states);
+                batch.solve(
+                    &mut pair,
+                    separation,
+                    delta_secs,
+                    use_bias,
+                    max_overlap_solve_speed,
+                );
+                pair.scatter(batch, &mut group.states);
+            }
+        });
+    }
+
+    /// Gathers the bodies of each group from the ECS, calls `f` for the group,
+    /// and writes the velocities of the bodies back.
+    ///
+    /// With the `parallel` feature, the groups are solved in parallel.
+    fn for_each_group(
+        &mut self,
+        bodies: &mut Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+        f: impl Fn(&mut BatchGroup) + Send + Sync,
+    ) {
+        let bodies = &*bodies;
+        let solve_group = |group: &mut BatchGroup| {
+            group.gather_bodies(bodies);
+            f(group);
+            // SAFETY: No other task can access the dynamic bodies of this group at the same time:
+            //
+            //         - `BatchGroup::build` only adds points whose dynamic bodies belong to
+            //           the islands of the group, and the island ranges of the groups are disjoint.
+            //         - Each group is solved by exactly one task.
+            //         - The query is borrowed mutably, so it is not accessed outside of the tasks.
+            unsafe { group.scatter_bodies(bodies) };
+        };
+
+        #[cfg(feature = "parallel")]
+        if self.groups.len() > 1 {
+            let solve_group = &solve_group;
+            ComputeTaskPool::get().scope(|scope| {
+                for group in self.groups.iter_mut() {
+                    scope.spawn(async move { solve_group(group) });
+                }
+            });
+            return;
+        }
+
+        self.groups.iter_mut().for_each(solve_group);
+    }
+}
+
+/// The minimum number of contact constraints in a [`BatchGroup`] of several islands.
+/// Smaller groups are not worth the overhead of a task.
+#[cfg(feature = "parallel")]
+const MIN_GROUP_SIZE: usize = 64;
+
+/// Splits the constraints into ranges for the [`BatchGroup`]s, returning the range of constraints
+/// and the range of islands of each group.
+///
+/// If the `islands` match the constraints and the `parallel` feature is enabled, each group
+/// contains whole islands. Otherwise, all constraints are in a single group with no island range.
+fn group_ranges(
+    constraints: &[ContactConstraint],
+    islands: Option<&PhysicsIslands>,
+) -> Vec<(Range<usize>, Option<Range<usize>>)> {
+    #[cfg(feature = "parallel")]
+    if let Some(islands) = islands.filter(|islands| islands.matches_constraints(constraints)) {
+        // Use a few groups per thread so that the load is balanced even when island sizes vary.
+        let thread_count = ComputeTaskPool::get().thread_num().max(1);
+        let group_size = (constraints.len() / (thread_count * 4)).max(MIN_GROUP_SIZE);
+
+        let mut ranges = vec![];
+        let mut island_index = 0;
+
+        while island_index < islands.islands.len() {
+            // Gather whole islands until the group is large enough.
+            let first_island = island_index;
+            let start = islands.islands[island_index].contact_constraints.start;
+            let mut end = start;
+            while island_index < islands.islands.len() && end - start < group_size {
+                end = islands.islands[island_index].contact_constraints.end;
+                island_index += 1;
+            }
+            ranges.push((start..end, Some(first_island..island_index)));
+        }
+
+        // The remaining constraints don't involve any dynamic bodies.
+        if !islands.non_dynamic_constraints.is_empty() {
+            ranges.push((islands.non_dynamic_constraints.clone(), Some(0..0)));
+        }
+
+        return ranges;
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    let _ = islands;
+
+    vec![(0..constraints.len(), None)]
+}
+
+impl BatchGroup {
+    /// Rebuilds the batches from the given range of contact constraints.
+    ///
+    /// Each point is added to the first batch that comes after all batches containing
+    /// its dynamic bodies and has an empty lane. This keeps the points of each body
+    /// in the same order as in the constraints.
+    ///
+    /// If `islands` is given, constraints with dynamic bodies that don't belong to the given
+    /// range of islands are skipped, so that the groups never share a dynamic body.
+    fn build(
+        &mut self,
+        constraints: &[ContactConstraint],
+        constraint_range: Range<usize>,
+        islands: Option<(&PhysicsIslands, Range<usize>)>,
+        bodies: &Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    ) {
+        self.bodies.clear();
+        self.bodies.push(Entity::PLACEHOLDER);
+        self.batches.clear();
+
+        let mut body_indices = EntityHashMap::<usize>::default();
+        // For each body, one plus the index of the last batch containing it, or zero if there is none.
+        let mut next_batches = Vec::<usize>::new();
+        let mut lane_counts = Vec::<usize>::new();
+
+        let mut body_index = |entity: Entity| {
+            *body_indices.entry(entity).or_insert_with(|| {
+                self.bodies.push(entity);
+                self.bodies.len() - 1
+            })
+        };
+
+        let is_in_group = |entity: Entity| match &islands {
+            Some((islands, island_range)) => islands
+                .body_islands
+                .get(&entity)
+                .is_some_and(|island_index| island_range.contains(island_index)),
+            None => true,
+        };
+
+        for (constraint_index, constraint) in constraints
+            .iter()
+            .enumerate()
+            .take(constraint_range.end)
+            .skip(constraint_range.start)
+        {
+            if constraint.entity1 == constraint.entity2 {
+                continue;
+            }
+            let (Ok(body1), Ok(body2)) = (
+                bodies.get(constraint.entity1),
+                bodies.get(constraint.entity2),
+            ) else {
+                continue;
+            };
+            let is_dynamic1 = body1.rb.is_dynamic();
+            let is_dynamic2 = body2.rb.is_dynamic();
+
+            // This should only happen if the constraints were modified after the islands were built.
+            if (is_dynamic1 && !is_in_group(constraint.entity1))
+                || (is_dynamic2 && !is_in_group(constraint.entity2))
+            {
+                continue;
+            }
+
+            let index1 = body_index(constraint.entity1);
+            let index2 = body_index(constraint.entity2);
+            let max_index = index1.max(index2);
+            if next_batches.len() <= max_index {
+                next_batches.resize(max_index + 1, 0);
+            }
+
+            for (point_index, point) in constraint.points.iter().enumerate() {
+                let mut batch_index = 0;
+                if is_dynamic1 {
+                    batch_index = batch_index.max(next_batches[index1]);
+                }
+                if is_dynamic2 {
+                    batch_index = batch_index.max(next_batches[index2]);
+                }
+                while batch_index < lane_counts.len() && lane_counts[batch_index] == LANES {
+                    batch_index += 1;
+                }
+                if batch_index == lane_counts.len() {
+                    self.batches.push(ContactBatch::default());
+                    lane_counts.push(0);
+                }
+
+                let lane = lane_counts[batch_index];
+                lane_counts[batch_index] += 1;
+                self.batches[batch_index].set_lane(
+                    lane,
+                    (constraint_index, point_index),
+                    [index1, index2],
+                    constraint,
+                    point,
+                );
+
+                if is_dynamic1 {
+                    next_batches[index1] = batch_index + 1;
+                }
+                if is_dynamic2 {
+                    next_batches[index2] = batch_index + 1;
+                }
+            }
+        }
+    }
+
+    /// Gathers the [`SolverBodyState`] of each body from the ECS.
+    fn gather_bodies(&mut self, bodies: &Query<RigidBodyQuery, Without<RigidBodyDisabled>>) {
+        self.states.clear();
+        self.states.push(SolverBodyState::placeholder());
+        self.states
+            .extend(self.bodies.iter().skip(1).map(|&entity| {
+                bodies
+                    .get(entity)
+                    .map_or(SolverBodyState::placeholder(), |body| {
+                        SolverBodyState::from_body(&body)
+                    })
+            }));
+    }
+
+    /// Writes the velocities of the dynamic bodies back to the ECS.
+    ///
+    /// # Safety
+    ///
+    /// No other task may access the dynamic bodies of the group at the same time.
+    unsafe fn scatter_bodies(&self, bodies: &Query<RigidBodyQuery, Without<RigidBodyDisabled>>) {
+        for (&entity, state) in self.bodies.iter().zip(self.states.iter()).skip(1) {
+            if !state.is_dynamic {
+                continue;
+            }
+            // SAFETY: Upheld by the caller.
+            if let Ok(mut body) = unsafe { bodies.get_unchecked(entity) } {
+                body.linear_velocity.0 = state.linear_velocity;
+                body.angular_velocity.0 = state.angular_velocity;
+            }
+        }
+    }
+}
+
+impl ContactBatch {
+    /// Sets the given lane to the given contact point.
+    fn set_lane(
+        &mut self,
+        lane: usize,
+        indices: (usize, usize),
+        [body1, body2]: [usize; 2],
+        constraint: &ContactConstraint,
+        point: &ContactConstraintPoint,
+    ) {
+        self.points[lane] = Some(indices);
+        self.body1[lane] = body1;
+        self.body2[lane] = body2;
+        self.normal.set_lane(lane, constraint.normal);
+        self.local_anchor1[lane] = point.local_anchor1;
+        self.local_anchor2[lane] = point.local_anchor2;
+        self.anchor1.set_lane(lane, point.anchor1);
+        self.anchor2.set_lane(lane, point.anchor2);
+        self.initial_separation
+            .set_lane(lane, point.initial_separation);
+        self.max_overlap_solve_speed[lane] = constraint.max_overlap_solve_speed;
+
+        let normal_part = &point.normal_part;
+        self.normal_impulse.set_lane(lane, normal_part.impulse);
+        self.normal_effective_mass
+            .set_lane(lane, normal_part.effective_mass);
+        self.softness_bias.set_lane(lane, normal_part.softness.bias);
+        self.softness_mass_scale
+            .set_lane(lane, normal_part.softness.mass_scale);
+        self.softness_impulse_scale
+            .set_lane(lane, normal_part.softness.impulse_scale);
+        self.max_normal_impulse
+            .set_lane(lane, point.max_normal_impulse);
+        self.total_normal_impulse
+            .set_lane(lane, point.total_normal_impulse);
+        self.friction_velocity_offset
+            .set_lane(lane, point.friction_velocity_offset);
+
+        // Points without a tangent part have no friction.
+        let tangent_part = point.tangent_part.clone().unwrap_or_default();
+        self.friction.set_lane(
+            lane,
+            if point.tangent_part.is_some() {
+                constraint.friction.dynamic_coefficient
+            } else {
+                0.0
+            },
+        );
+        #[cfg(feature = "2d")]
+        {
+            self.tangent_impulse.set_lane(lane, tangent_part.impulse);
+            self.tangent_effective_mass
+                .set_lane(lane, tangent_part.effective_mass);
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.tangent_impulse[0].set_lane(lane, tangent_part.impulse.x);
+            self.tangent_impulse[1].set_lane(lane, tangent_part.impulse.y);
+            for (i, value) in tangent_part.effective_inverse_mass.into_iter().enumerate() {
+                self.tangent_effective_inverse_mass[i].set_lane(lane, value);
+            }
+            self.fallback_tangent
+                .set_lane(lane, (-constraint.normal).any_orthonormal_vector());
+        }
+    }
+
+    /// Computes the current separation distance of each point based on the positions of the bodies.
+    fn separation(&self, states: &[SolverBodyState]) -> Wide {
+        Wide::from_fn(|i| {
+            let body1 = &states[self.body1[i]];
+            let body2 = &states[self.body2[i]];
+            let r1 = body1.rotation * self.local_anchor1[i];
+            let r2 = body2.rotation * self.local_anchor2[i];
+
+            // TODO: Consider rotation delta for anchors
+            let delta_separation =
+                body2.accumulated_translation - body1.accumulated_translation + (r2 - r1);
+            delta_separation.dot(self.normal.lane(i)) + self.initial_separation.lane(i)
+        })
+    }
+
+    /// Computes `DIM - 1` tangent directions for each lane, like [`ContactConstraint::tangent_directions`].
+    #[allow(unused_variables)]
+    fn tangent_directions(&self, pair: &WideBodyPair) -> [WideVector; DIM - 1] {
+        #[cfg(feature = "2d")]
+        {
+            [WideVector {
+                x: self.normal.y,
+                y: -self.normal.x,
+            }]
+        }
+        #[cfg(feature = "3d")]
+        {
+            let force_direction = -self.normal;
+            let relative_velocity = pair.linear_velocity1 - pair.linear_velocity2;
+            let tangent_velocity =
+                relative_velocity - force_direction * force_direction.dot(relative_velocity);
+
+            let length_recip = Wide::splat(1.0) / tangent_velocity.length_squared().sqrt();
+            let is_valid = length_recip.is_finite() & length_recip.greater_than(Wide::ZERO);
+            let tangent = WideVector::select(
+                is_valid,
+                tangent_velocity * length_recip,
+                self.fallback_tangent,
+            );
+            let bitangent = force_direction.cross(tangent);
+            [tangent, bitangent]
+        }
+    }
+
+    /// Warm starts the points by applying the accumulated impulses, like [`ContactConstraint::warm_start`].
+    fn warm_start(&mut self, pair: &mut WideBodyPair, warm_start_coefficient: Scalar) {
+        let coefficient = Wide::splat(warm_start_coefficient);
+        let tangent_directions = self.tangent_directions(pair);
+
+        self.total_normal_impulse += coefficient * self.normal_impulse;
+
+        #[cfg(feature = "2d")]
+        let p = (self.normal * self.normal_impulse + tangent_directions[0] * self.tangent_impulse)
+            * coefficient;
+        #[cfg(feature = "3d")]
+        let p = (self.normal * self.normal_impulse
+            + tangent_directions[0] * self.tangent_impulse[0]
+            + tangent_directions[1] * self.tangent_impulse[1])
+            * coefficient;
+
+        pair.apply_impulse(p, self.anchor1, self.anchor2);
+    }
+
+    /// Solves the points, like [`ContactConstraint::solve`].
+    fn solve(
+        &mut self,
+        pair: &mut WideBodyPair,
+        separation: Wide,
+        delta_secs: Scalar,
+        use_bias: bool,
+        max_overlap_solve_speed: Scalar,
+    ) {
+        let r1 = self.anchor1;
+        let r2 = self.anchor2;
+
+        // Normal impulses
+        let normal_speed = pair.relative_velocity(r1, r2).dot(self.normal);
+
+        // Speculative contact: Push back the part of the velocity that would cause penetration.
+        let speculative_impulse =
+            -self.normal_effective_mass * (normal_speed + separation / Wide::splat(delta_secs));
+
+        let contact_impulse = if use_bias {
+            // Contact using bias: Incorporate softness parameters.
+            let max_overlap_solve_speed = Wide::from_fn(|i| {
+                self.max_overlap_solve_speed[i].unwrap_or(max_overlap_solve_speed)
+            });
+            let bias = (self.softness_bias * separation).max(-max_overlap_solve_speed);
+            let scaled_mass = self.softness_mass_scale * self.normal_effective_mass;
+            let scaled_impulse = self.softness_impulse_scale * self.normal_impulse;
+
+            -scaled_mass * (normal_speed + bias) - scaled_impulse
+        } else {
+            // Contact without bias: Solve normally without softness parameters.
+            -self.normal_effective_mass * normal_speed
+        };
+
+        let impulse = Wide::select(
+            separation.greater_than(Wide::ZERO),
+            speculative_impulse,
+            contact_impulse,
+        );
+
+        // Clamp the accumulated impulse.
+        let new_impulse = (self.normal_impulse + impulse).max(Wide::ZERO);
+        let impulse = new_impulse - self.normal_impulse;
+        self.normal_impulse = new_impulse;
+
+        // Store the maximum and total impulse for restitution.
+        self.max_normal_impulse = impulse.max(self.max_normal_impulse);
+        self.total_normal_impulse += impulse;
+
+        pair.apply_impulse(self.normal * impulse, r1, r2);
+
+        // Friction
+        let tangent_directions = self.tangent_directions(pair);
+        let impulse_limit = self.friction * self.normal_impulse;
+
+        // Relative velocity at contact point, excluding the velocity that kinematic bodies don't transfer.
+        let relative_velocity = pair.relative_velocity(r1, r2) - self.friction_velocity_offset;
+
+        #[cfg(feature = "2d")]
+        let impulse = {
+            let tangent = tangent_directions[0];
+            let tangent_speed = relative_velocity.dot(tangent);
+
+            // Compute the incremental tangent impulse magnitude.
+            let impulse = -self.tangent_effective_mass * tangent_speed;
+
+            // Clamp the accumulated impulse.
+            let new_impulse = (self.tangent_impulse + impulse)
+                .max(-impulse_limit)
+                .min(impulse_limit);
+            let impulse = new_impulse - self.tangent_impulse;
+            self.tangent_impulse = new_impulse;
+
+            tangent * impulse
+        };
+
+        #[cfg(feature = "3d")]
+        let impulse = {
+            // Solve the two tangent directions simultaneously, like `ContactTangentPart::solve_impulse`.
+            let tangent_speed1 = relative_velocity.dot(tangent_directions[0]);
+            let tangent_speed2 = relative_velocity.dot(tangent_directions[1]);
+
+            let t11 = tangent_speed1 * tangent_speed1;
+            let t22 = tangent_speed2 * tangent_speed2;
+            let t12 = tangent_speed1 * tangent_speed2;
+            let inv = t11 * self.tangent_effective_inverse_mass[0]
+                + t22 * self.tangent_effective_inverse_mass[1]
+                + t12 * self.tangent_effective_inverse_mass[2];
+
+            // Compute the effective mass "seen" by the constraint along the tangent.
+            // Note the guard against division by zero.
+            let effective_mass = (t11 + t22) / inv.max(Wide::splat(1e-16));
+
+            // Compute the new accumulated impulse, and clamp its length.
+            let mut new_impulse = [
+                self.tangent_impulse[0] - effective_mass * tangent_speed1,
+                self.tangent_impulse[1] - effective_mass * tangent_speed2,
+            ];
+            let length_squared = new_impulse[0] * new_impulse[0] + new_impulse[1] * new_impulse[1];
+            let scale = Wide::select(
+                length_squared.greater_than(impulse_limit * impulse_limit),
+                impulse_limit / length_squared.sqrt(),
+                Wide::splat(1.0),
+            );
+            new_impulse = [new_impulse[0] * scale, new_impulse[1] * scale];
+
+            let impulse = [
+                new_impulse[0] - self.tangent_impulse[0],
+                new_impulse[1] - self.tangent_impulse[1],
+            ];
+
+            // Skip lanes where the impulse is not finite.
+            let is_finite = impulse[0].is_finite() & impulse[1].is_finite();
+            let impulse = [
+                Wide::select(is_finite, impulse[0], Wide::ZERO),
+                Wide::select(is_finite, impulse[1], Wide::ZERO),
+            ];
+            self.tangent_impulse = [
+                Wide::select(is_finite, new_impulse[0], self.tangent_impulse[0]),
+                Wide::select(is_finite, new_impulse[1], self.tangent_impulse[1]),
+            ];
+
+            tangent_directions[0] * impulse[0] + tangent_directions[1] * impulse[1]
+        };
+
+        pair.apply_impulse(impulse, r1, r2);
+    }
+}
+
+/// Computes the velocity at the given points relative to the centers of mass.
+#[inline]
+fn velocity_at_point(
+    linear_velocity: WideVector,
+    angular_velocity: WideAngular,
+    point: WideVector,
+) -> WideVector {
+    #[cfg(feature = "2d")]
+    {
+        linear_velocity + point.perp() * angular_velocity
+    }
+    #[cfg(feature = "3d")]
+    {
+        linear_velocity + angular_velocity.cross(point)
+    }
+}
+
+#[cfg(feature = "2d")]
+fn wide_angular_from_fn(f: impl FnMut(usize) -> Scalar) -> WideAngular {
+    Wide::from_fn(f)
+}
+
+#[cfg(feature = "3d")]
+fn wide_angular_from_fn(f: impl FnMut(usize) -> Vector) -> WideAngular {
+    WideVector::from_fn(f)
+}
+
+#[cfg(feature = "2d")]
+fn wide_angular_lane(value: &WideAngular, i: usize) -> Scalar {
+    value.lane(i)
+}
+
+#[cfg(feature = "3d")]
+fn wide_angular_lane(value: &WideAngular, i: usize) -> Vector {
+    value.lane(i)
+}
+
+#[cfg(feature = "2d")]
+fn wide_angular_inertia_from_fn(f: impl FnMut(usize) -> Scalar) -> WideAngularInertia {
+    Wide::from_fn(f)
+}
+
+#[cfg(feature = "3d")]
+fn wide_angular_inertia_from_fn(f: impl FnMut(usize) -> Matrix) -> WideAngularInertia {
+    WideMatrix::from_fn(f)
+}
@@ -1,8 +1,11 @@
 //! Constraints and other types used for solving contacts.
 
+mod batch;
 mod normal_part;
 mod tangent_part;
+mod wide;
 
+pub(crate) use batch::ContactConstraintBatches;
 pub use normal_part::ContactNormalPart;
 pub use tangent_part::ContactTangentPart;
 
@@ -31,6 +34,12 @@ pub struct ContactConstraintPoint {
     /// This is used for determining whether restitution should be applied.
     pub max_normal_impulse: Scalar,
 
+    /// The total impulse magnitude applied along the contact normal during this frame,
+    /// summed over all substeps.
+    ///
+    /// This is used for [Poisson restitution](crate::dynamics::solver::RestitutionModel::Poisson).
+    pub total_normal_impulse: Scalar,
+
     // TODO: If a rotation delta was used for bodies, these local anchors could be removed.
     /// The local contact point relative to the center of mass of the first body.
     pub local_anchor1: Vector,
@@ -47,6 +56,12 @@ pub struct ContactConstraintPoint {
     /// The relative velocity of the bodies along the normal at the contact point.
     pub normal_speed: Scalar,
 
+    /// The part of the relative velocity at the contact point that is ignored by friction.
+    ///
+    /// This is non-zero for kinematic bodies that don't transfer their full velocity
+    /// based on their [`PlatformVelocityTransfer`].
+    pub friction_velocity_offset: Vector,
+
     /// The pre-solve separation distance between the bodies.
     ///
     /// A negative separation indicates penetration.
@@ -77,6 +92,11 @@ pub struct ContactConstraint {
     pub points: Vec<ContactConstraintPoint>,
     /// The index of the [`ContactManifold`] in the [`Contacts`] stored for the two bodies.
     pub manifold_index: usize,
+    /// The maximum speed at which the bodies are pushed apart, overriding
+    /// [`SolverConfig::max_overlap_solve_speed`](crate::dynamics::solver::SolverConfig::max_overlap_solve_speed).
+    ///
+    /// This is the smaller [`MaxOverlapSolveSpeed`] of the bodies, or `None` if neither body has one.
+    pub max_overlap_solve_speed: Option<Scalar>,
 }
 
 impl ContactConstraint {
@@ -127,6 +147,13 @@ impl ContactConstraint {
             normal,
             points: Vec::with_capacity(manifold.contacts.len()),
             manifold_index: manifold_id,
+            max_overlap_solve_speed: match (
+                body1.max_overlap_solve_speed,
+                body2.max_overlap_solve_speed,
+            ) {
+                (Some(speed1), Some(speed2)) => Some(speed1.0.min(speed2.0)),
+                (speed1, speed2) => speed1.or(speed2).map(|speed| speed.0),
+            },
         };
 
         let tangents =
@@ -192,11 +219,14 @@ impl ContactConstraint {
                     ),
                 ),
                 max_normal_impulse: 0.0,
+                total_normal_impulse: 0.0,
                 local_anchor1,
                 local_anchor2,
                 anchor1: r1,
                 anchor2: r2,
                 normal_speed: normal.dot(relative_velocity),
+                friction_velocity_offset: untransferred_velocity(body2, r2)
+                    - untransferred_velocity(body1, r1),
                 initial_separation: -contact.penetration - (r2 - r1).dot(normal),
             };
 
@@ -208,9 +238,9 @@ impl ContactConstraint {
 
     /// Warm starts the contact constraint by applying the impulses from the previous frame or substep.
     pub fn warm_start(
-        &self,
-        body1: &mut RigidBodyQueryItem,
-        body2: &mut RigidBodyQueryItem,
+        &mut self,
+        body1: &mut impl ContactBody,
+        body2: &mut impl ContactBody,
         normal: Vector,
         tangent_directions: [Vector; DIM - 1],
         warm_start_coefficient: Scalar,
@@ -220,11 +250,13 @@ impl ContactConstraint {
         let inv_inertia1 = body1.effective_global_angular_inertia().inverse();
         let inv_inertia2 = body2.effective_global_angular_inertia().inverse();
 
-        for point in self.points.iter() {
+        for point in self.points.iter_mut() {
             // Fixed anchors
             let r1 = point.anchor1;
             let r2 = point.anchor2;
 
+            point.total_normal_impulse += warm_start_coefficient * point.normal_part.impulse;
+
             let tangent_impulse = point
                 .tangent_part
                 .as_ref()
@@ -239,37 +271,42 @@ impl ContactConstraint {
                     + tangent_impulse.x * tangent_directions[0]
                     + tangent_impulse.y * tangent_directions[1]);
 
-            if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
-                body1.linear_velocity.0 -= p * inv_mass1;
-                body1.angular_velocity.0 -= inv_inertia1 * cross(r1, p);
+            if body1.rigid_body().is_dynamic() && body1.dominance() <= body2.dominance() {
+                body1.apply_velocity_change(-p * inv_mass1, -(inv_inertia1 * cross(r1, p)));
             }
-            if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
-                body2.linear_velocity.0 += p * inv_mass2;
-                body2.angular_velocity.0 += inv_inertia2 * cross(r2, p);
+            if body2.rigid_body().is_dynamic() && body2.dominance() <= body1.dominance() {
+                body2.apply_velocity_change(p * inv_mass2, inv_inertia2 * cross(r2, p));
             }
         }
     }
 
     /// Solves the [`ContactConstraint`], applying an impulse to the given bodies.
+    ///
+    /// The `max_overlap_solve_speed` is only used if the constraint doesn't have its own
+    /// [`max_overlap_solve_speed`](Self::max_overlap_solve_speed).
     pub fn solve(
         &mut self,
-        body1: &mut RigidBodyQueryItem,
-        body2: &mut RigidBodyQueryItem,
+        body1: &mut impl ContactBody,
+        body2: &mut impl ContactBody,
         delta_secs: Scalar,
         use_bias: bool,
         max_overlap_solve_speed: Scalar,
     ) {
+        let max_overlap_solve_speed = self
+            .max_overlap_solve_speed
+            .unwrap_or(max_overlap_solve_speed);
+
         let inv_mass1 = body1.effective_inverse_mass();
         let inv_mass2 = body2.effective_inverse_mass();
         let inv_inertia1 = body1.effective_global_angular_inertia().inverse();
         let inv_inertia2 = body2.effective_global_angular_inertia().inverse();
 
-        let delta_translation = body2.accumulated_translation.0 - body1.accumulated_translation.0;
+        let delta_translation = body2.accumulated_translation() - body1.accumulated_translation();
 
         // Normal impulses
         for point in self.points.iter_mut() {
-            let r1 = *body1.rotation * point.local_anchor1;
-            let r2 = *body2.rotation * point.local_anchor2;
+            let r1 = *body1.rotation() * point.local_anchor1;
+            let r2 = *body2.rotation() * point.local_anchor2;
 
             // TODO: Consider rotation delta for anchors
             let delta_separation = delta_translation + (r2 - r1);
@@ -292,8 +329,9 @@ impl ContactConstraint {
                 delta_secs,
             );
 
-            // Store the maximum impulse for restitution.
+            // Store the maximum and total impulse for restitution.
             point.max_normal_impulse = impulse_magnitude.max(point.max_normal_impulse);
+            point.total_normal_impulse += impulse_magnitude;
 
             if impulse_magnitude == 0.0 {
                 continue;
@@ -302,19 +340,20 @@ impl ContactConstraint {
             let impulse = impulse_magnitude * self.normal;
 
             // Apply the impulse.
-            if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
-                body1.linear_velocity.0 -= impulse * inv_mass1;
-                body1.angular_velocity.0 -= inv_inertia1 * cross(r1, impulse);
+            if body1.rigid_body().is_dynamic() && body1.dominance() <= body2.dominance() {
+                body1.apply_velocity_change(
+                    -impulse * inv_mass1,
+                    -(inv_inertia1 * cross(r1, impulse)),
+                );
             }
-            if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
-                body2.linear_velocity.0 += impulse * inv_mass2;
-                body2.angular_velocity.0 += inv_inertia2 * cross(r2, impulse);
+            if body2.rigid_body().is_dynamic() && body2.dominance() <= body1.dominance() {
+                body2.apply_velocity_change(impulse * inv_mass2, inv_inertia2 * cross(r2, impulse));
             }
         }
 
         let friction = self.friction;
         let tangent_directions =
-            self.tangent_directions(body1.linear_velocity.0, body2.linear_velocity.0);
+            self.tangent_directions(body1.linear_velocity(), body2.linear_velocity());
 
         // Friction
         for point in self.points.iter_mut() {
@@ -326,8 +365,10 @@ impl ContactConstraint {
             let r1 = point.anchor1;
             let r2 = point.anchor2;
 
-            // Relative velocity at contact point
-            let relative_velocity = body2.velocity_at_point(r2) - body1.velocity_at_point(r1);
+            // Relative velocity at contact point, excluding the velocity that kinematic bodies don't transfer.
+            let relative_velocity = body2.velocity_at_point(r2)
+                - body1.velocity_at_point(r1)
+                - point.friction_velocity_offset;
 
             // Compute the incremental impulse. The clamping and impulse accumulation is handled by the method.
             let impulse = friction_part.solve_impulse(
@@ -338,13 +379,14 @@ impl ContactConstraint {
             );
 
             // Apply the impulse.
-            if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
-                body1.linear_velocity.0 -= impulse * inv_mass1;
-                body1.angular_velocity.0 -= inv_inertia1 * cross(r1, impulse);
+            if body1.rigid_body().is_dynamic() && body1.dominance() <= body2.dominance() {
+                body1.apply_velocity_change(
+                    -impulse * inv_mass1,
+                    -(inv_inertia1 * cross(r1, impulse)),
+                );
             }
-            if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
-                body2.linear_velocity.0 += impulse * inv_mass2;
-                body2.angular_velocity.0 += inv_inertia2 * cross(r2, impulse);
+            if body2.rigid_body().is_dynamic() && body2.dominance() <= body1.dominance() {
+                body2.apply_velocity_change(impulse * inv_mass2, inv_inertia2 * cross(r2, impulse));
             }
         }
     }
@@ -353,8 +395,8 @@ impl ContactConstraint {
     /// along the contact normal exceeds the given `threshold`.
     pub fn apply_restitution(
         &mut self,
-        body1: &mut RigidBodyQueryItem,
-        body2: &mut RigidBodyQueryItem,
+        body1: &mut impl ContactBody,
+        body2: &mut impl ContactBody,
         threshold: Scalar,
     ) {
         for point in self.points.iter_mut() {
@@ -390,13 +432,64 @@ impl ContactConstraint {
             // Apply the impulse.
             let impulse = impulse * self.normal;
 
-            if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
-                body1.linear_velocity.0 -= impulse * inv_mass1;
-                body1.angular_velocity.0 -= inv_inertia1 * cross(r1, impulse);
+            if body1.rigid_body().is_dynamic() && body1.dominance() <= body2.dominance() {
+                body1.apply_velocity_change(
+                    -impulse * inv_mass1,
+                    -(inv_inertia1 * cross(r1, impulse)),
+                );
+            }
+            if body2.rigid_body().is_dynamic() && body2.dominance() <= body1.dominance() {
+                body2.apply_velocity_change(impulse * inv_mass2, inv_inertia2 * cross(r2, impulse));
+            }
+        }
+    }
+
+    /// Applies [restitution](Restitution) for the given bodies using
+    /// [Poisson's hypothesis](crate::dynamics::solver::RestitutionModel::Poisson).
+    ///
+    /// Each contact point receives an additional impulse along the normal equal to the total normal impulse
+    /// applied during this frame, scaled by the coefficient of restitution.
+    /// Points where the impulse corresponds to a speed change smaller than `threshold` are skipped.
+    pub fn apply_poisson_restitution(
+        &mut self,
+        body1: &mut impl ContactBody,
+        body2: &mut impl ContactBody,
+        threshold: Scalar,
+    ) {
+        let inv_mass1 = body1.effective_inverse_mass();
+        let inv_mass2 = body2.effective_inverse_mass();
+        let inv_inertia1 = body1.effective_global_angular_inertia().inverse();
+        let inv_inertia2 = body2.effective_global_angular_inertia().inverse();
+
+        for point in self.points.iter_mut() {
+            // Skip restitution for impulses that only stopped a slow approach, like for resting contacts.
+            // We also skip contacts that don't apply an impulse to account for speculative contacts.
+            if point.total_normal_impulse <= threshold * point.normal_part.effective_mass
+                || point.max_normal_impulse == 0.0
+            {
+                continue;
+            }
+
+            // Fixed anchors
+            let r1 = point.anchor1;
+            let r2 = point.anchor2;
+
+            // The restitution impulse is proportional to the compression impulse.
+            let impulse = self.restitution.coefficient * point.total_normal_impulse;
+            point.normal_part.impulse += impulse;
+            point.total_normal_impulse += impulse;
+
+            // Apply the impulse.
+            let impulse = impulse * self.normal;
+
+            if body1.rigid_body().is_dynamic() && body1.dominance() <= body2.dominance() {
+                body1.apply_velocity_change(
+                    -impulse * inv_mass1,
+                    -(inv_inertia1 * cross(r1, impulse)),
+                );
             }
-            if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
-                body2.linear_velocity.0 += impulse * inv_mass2;
-                body2.angular_velocity.0 += inv_inertia2 * cross(r2, impulse);
+            if body2.rigid_body().is_dynamic() && body2.dominance() <= body1.dominance() {
+                body2.apply_velocity_change(impulse * inv_mass2, inv_inertia2 * cross(r2, impulse));
             }
         }
     }
@@ -430,3 +523,148 @@ impl MapEntities for ContactConstraint {
         self.entity2 = entity_mapper.map_entity(self.entity2);
     }
 }
+
+/// Computes the part of the velocity of a kinematic body at the given point relative to its center of mass
+/// that is not transferred to other bodies through friction, based on its [`PlatformVelocityTransfer`].
+fn untransferred_velocity(body: &RigidBodyQueryReadOnlyItem, point: Vector) -> Vector {
+    let Some(transfer) = body
+        .platform_velocity_transfer
+        .filter(|_| body.rb.is_kinematic())
+    else {
+        return Vector::ZERO;
+    };
+
+    #[cfg(feature = "2d")]
+    let angular_velocity = body.angular_velocity.0 * point.perp();
+    #[cfg(feature = "3d")]
+    let angular_velocity = body.angular_velocity.cross(point);
+
+    body.linear_velocity.0 * (1.0 - transfer.linear) + angular_velocity * (1.0 - transfer.angular)
+}
+
+/// A rigid body that [`ContactConstraint`]s can be solved for.
+///
+/// This is implemented for both mutable and read-only [`RigidBodyQuery`] items.
+/// Velocity changes applied to read-only bodies are ignored, which allows static and kinematic bodies
+/// to be shared by constraints that are solved in parallel.
+pub trait ContactBody {
+    /// Returns the type of the rigid body.
+    fn rigid_body(&self) -> RigidBody;
+
+    /// Returns the rotation of the body.
+    fn rotation(&self) -> &Rotation;
+
+    /// Returns the translation accumulated by the body during the current time step.
+    fn accumulated_translation(&self) -> Vector;
+
+    /// Returns the linear velocity of the body.
+    fn linear_velocity(&self) -> Vector;
+
+    /// Computes the velocity at the given `point` relative to the center of mass.
+    fn velocity_at_point(&self, point: Vector) -> Vector;
+
+    /// Computes the effective inverse mass, taking into account any translation locking.
+    fn effective_inverse_mass(&self) -> Vector;
+
+    /// Computes the effective world-space angular inertia, taking into account any rotation locking.
+    fn effective_global_angular_inertia(&self) -> ComputedAngularInertia;
+
+    /// Returns the [dominance](Dominance) of the body.
+    fn dominance(&self) -> i8;
+
+    /// Adds the given changes to the linear and angular velocity of the body.
+    ///
+    /// Does nothing if the body can't be modified.
+    fn apply_velocity_change(
+        &mut self,
+        linear: Vector,
+        #[cfg(feature = "2d")] angular: Scalar,
+        #[cfg(feature = "3d")] angular: Vector,
+    );
+}
+
+impl ContactBody for RigidBodyQueryItem<'_> {
+    fn rigid_body(&self) -> RigidBody {
+        *self.rb
+    }
+
+    fn rotation(&self) -> &Rotation {
+        &self.rotation
+    }
+
+    fn accumulated_translation(&self) -> Vector {
+        self.accumulated_translation.0
+    }
+
+    fn linear_velocity(&self) -> Vector {
+        self.linear_velocity.0
+    }
+
+    fn velocity_at_point(&self, point: Vector) -> Vector {
+        self.velocity_at_point(point)
+    }
+
+    fn effective_inverse_mass(&self) -> Vector {
+        self.effective_inverse_mass()
+    }
+
+    fn effective_global_angular_inertia(&self) -> ComputedAngularInertia {
+        self.effective_global_angular_inertia()
+    }
+
+    fn dominance(&self) -> i8 {
+        self.dominance()
+    }
+
+    fn apply_velocity_change(
+        &mut self,
+        linear: Vector,
+        #[cfg(feature = "2d")] angular: Scalar,
+        #[cfg(feature = "3d")] angular: Vector,
+    ) {
+        self.linear_velocity.0 += linear;
+        self.angular_velocity.0 += angular;
+    }
+}
+
+impl ContactBody for RigidBodyQueryReadOnlyItem<'_> {
+    fn rigid_body(&self) -> RigidBody {
+        *self.rb
+    }
+
+    fn rotation(&self) -> &Rotation {
+        self.rotation
+    }
+
+    fn accumulated_translation(&self) -> Vector {
+        self.accumulated_translation.0
+    }
+
+    fn linear_velocity(&self) -> Vector {
+        self.linear_velocity.0
+    }
+
+    fn velocity_at_point(&self, point: Vector) -> Vector {
+        self.velocity_at_point(point)
+    }
+
+    fn effective_inverse_mass(&self) -> Vector {
+        self.effective_inverse_mass()
+    }
+
+    fn effective_global_angular_inertia(&self) -> ComputedAngularInertia {
+        self.effective_global_angular_inertia()
+    }
+
+    fn dominance(&self) -> i8 {
+        self.dominance()
+    }
+
+    fn apply_velocity_change(
+        &mut self,
+        _linear: Vector,
+        #[cfg(feature = "2d")] _angular: Scalar,
+        #[cfg(feature = "3d")] _angular: Vector,
+    ) {
+    }
+}
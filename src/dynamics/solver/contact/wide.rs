@@ -0,0 +1,401 @@
+//! Wide types for solving several contact points at once.
+//!
+//! [`Wide`] wraps a SIMD vector from the [`wide`] crate, `f32x8` with `f32` and `f64x4` with `f64`.
+//! Its operations are applied to all lanes at once using SIMD instructions where the target supports
+//! them, and fall back to scalar code elsewhere. The other types are built from [`Wide`] components.
+
+use crate::prelude::*;
+use ::wide::CmpGt;
+use std::ops::{Add, AddAssign, BitAnd, Div, Mul, Neg, Sub, SubAssign};
+
+/// The SIMD vector wrapped by [`Wide`].
+#[cfg(feature = "f32")]
+type Simd = ::wide::f32x8;
+/// The SIMD vector wrapped by [`Wide`].
+#[cfg(feature = "f64")]
+type Simd = ::wide::f64x4;
+
+/// The number of lanes in the wide types.
+///
+/// A [`Wide`] fits in a 256-bit register, like the ones used by AVX.
+#[cfg(feature = "f32")]
+pub(crate) const LANES: usize = 8;
+/// The number of lanes in the wide types.
+///
+/// A [`Wide`] fits in a 256-bit register, like the ones used by AVX.
+#[cfg(feature = "f64")]
+pub(crate) const LANES: usize = 4;
+
+/// A mask for selecting lanes of wide types.
+///
+/// Each lane has either all bits set or no bits set, like the results of SIMD comparisons.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct WideMask(Simd);
+
+impl BitAnd for WideMask {
+    type Output = Self;
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+/// [`LANES`] scalars that are operated on at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Wide(Simd);
+
+impl Wide {
+    /// All lanes set to zero.
+    pub const ZERO: Self = Self::splat(0.0);
+
+    /// Creates a [`Wide`] with all lanes set to the given value.
+    #[inline]
+    pub const fn splat(value: Scalar) -> Self {
+        Self(Simd::new([value; LANES]))
+    }
+
+    /// Creates a [`Wide`] by calling `f` for the index of each lane.
+    #[inline]
+    pub fn from_fn(f: impl FnMut(usize) -> Scalar) -> Self {
+        Self(Simd::new(std::array::from_fn(f)))
+    }
+
+    /// Returns the value in the given lane.
+    #[inline]
+    pub fn lane(&self, i: usize) -> Scalar {
+        self.0.as_array_ref()[i]
+    }
+
+    /// Sets the value in the given lane.
+    #[inline]
+    pub fn set_lane(&mut self, i: usize, value: Scalar) {
+        self.0.as_array_mut()[i] = value;
+    }
+
+    /// Returns the lane-wise maximum of `self` and `other`.
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    /// Returns the lane-wise minimum of `self` and `other`.
+    #[cfg(feature = "2d")]
+    #[inline]
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    /// Returns the lane-wise square root.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        Self(self.0.sqrt())
+    }
+
+    /// Returns a mask of the lanes where `self` is greater than `other`.
+    #[inline]
+    pub fn greater_than(self, other: Self) -> WideMask {
+        WideMask(self.0.cmp_gt(other.0))
+    }
+
+    /// Returns a mask of the lanes that are finite.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub fn is_finite(self) -> WideMask {
+        WideMask(self.0.is_finite())
+    }
+
+    /// Selects the lanes of `if_true` where the `mask` is set, and the lanes of `if_false` elsewhere.
+    #[inline]
+    pub fn select(mask: WideMask, if_true: Self, if_false: Self) -> Self {
+        Self(mask.0.blend(if_true.0, if_false.0))
+    }
+}
+
+macro_rules! impl_wide_op {
+    ($op:ident, $fn:ident, $assign_op:ident, $assign_fn:ident, $symbol:tt) => {
+        impl $op for Wide {
+            type Output = Self;
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                Self(self.0 $symbol rhs.0)
+            }
+        }
+
+        impl $assign_op for Wide {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = *self $symbol rhs;
+            }
+        }
+    };
+}
+
+impl_wide_op!(Add, add, AddAssign, add_assign, +);
+impl_wide_op!(Sub, sub, SubAssign, sub_assign, -);
+
+impl Mul for Wide {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl Div for Wide {
+    type Output = Self;
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        Self(self.0 / rhs.0)
+    }
+}
+
+impl Neg for Wide {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+/// [`LANES`] vectors stored as one [`Wide`] per component.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct WideVector {
+    pub x: Wide,
+    pub y: Wide,
+    #[cfg(feature = "3d")]
+    pub z: Wide,
+}
+
+impl WideVector {
+    /// All lanes set to the zero vector.
+    pub const ZERO: Self = Self {
+        x: Wide::ZERO,
+        y: Wide::ZERO,
+        #[cfg(feature = "3d")]
+        z: Wide::ZERO,
+    };
+
+    /// Creates a [`WideVector`] by calling `f` for the index of each lane.
+    #[inline]
+    pub fn from_fn(mut f: impl FnMut(usize) -> Vector) -> Self {
+        let mut result = Self::ZERO;
+        for i in 0..LANES {
+            result.set_lane(i, f(i));
+        }
+        result
+    }
+
+    /// Returns the vector in the given lane.
+    #[inline]
+    pub fn lane(&self, i: usize) -> Vector {
+        #[cfg(feature = "2d")]
+        {
+            Vector::new(self.x.lane(i), self.y.lane(i))
+        }
+        #[cfg(feature = "3d")]
+        {
+            Vector::new(self.x.lane(i), self.y.lane(i), self.z.lane(i))
+        }
+    }
+
+    /// Sets the vector in the given lane.
+    #[inline]
+    pub fn set_lane(&mut self, i: usize, value: Vector) {
+        self.x.set_lane(i, value.x);
+        self.y.set_lane(i, value.y);
+        #[cfg(feature = "3d")]
+        {
+            self.z.set_lane(i, value.z);
+        }
+    }
+
+    /// Returns the lane-wise dot product of `self` and `other`.
+    #[inline]
+    pub fn dot(self, other: Self) -> Wide {
+        #[cfg(feature = "2d")]
+        {
+            self.x * other.x + self.y * other.y
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.x * other.x + self.y * other.y + self.z * other.z
+        }
+    }
+
+    /// Returns the lane-wise squared length.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub fn length_squared(self) -> Wide {
+        self.dot(self)
+    }
+
+    /// Returns the lane-wise vector rotated by 90 degrees counterclockwise.
+    #[cfg(feature = "2d")]
+    #[inline]
+    pub fn perp(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Returns the lane-wise cross product of `self` and `other`.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Selects the lanes of `if_true` where the `mask` is `true`, and the lanes of `if_false` elsewhere.
+    #[cfg(feature = "3d")]
+    #[inline]
+    pub fn select(mask: WideMask, if_true: Self, if_false: Self) -> Self {
+        Self {
+            x: Wide::select(mask, if_true.x, if_false.x),
+            y: Wide::select(mask, if_true.y, if_false.y),
+            z: Wide::select(mask, if_true.z, if_false.z),
+        }
+    }
+
+    #[inline]
+    fn zip(self, other: Self, f: impl Fn(Wide, Wide) -> Wide) -> Self {
+        Self {
+            x: f(self.x, other.x),
+            y: f(self.y, other.y),
+            #[cfg(feature = "3d")]
+            z: f(self.z, other.z),
+        }
+    }
+}
+
+impl Add for WideVector {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.zip(rhs, Wide::add)
+    }
+}
+
+impl AddAssign for WideVector {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for WideVector {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(rhs, Wide::sub)
+    }
+}
+
+impl SubAssign for WideVector {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Component-wise multiplication.
+impl Mul for WideVector {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.zip(rhs, Wide::mul)
+    }
+}
+
+impl Mul<Wide> for WideVector {
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Wide) -> Self {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            #[cfg(feature = "3d")]
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl Neg for WideVector {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            #[cfg(feature = "3d")]
+            z: -self.z,
+        }
+    }
+}
+
+/// [`LANES`] 3x3 matrices stored as one [`WideVector`] per column.
+#[cfg(feature = "3d")]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct WideMatrix {
+    pub x_axis: WideVector,
+    pub y_axis: WideVector,
+    pub z_axis: WideVector,
+}
+
+#[cfg(feature = "3d")]
+impl WideMatrix {
+    /// Creates a [`WideMatrix`] by calling `f` for the index of each lane.
+    #[inline]
+    pub fn from_fn(mut f: impl FnMut(usize) -> Matrix) -> Self {
+        let mut result = Self::default();
+        for i in 0..LANES {
+            let matrix = f(i);
+            result.x_axis.set_lane(i, matrix.x_axis);
+            result.y_axis.set_lane(i, matrix.y_axis);
+            result.z_axis.set_lane(i, matrix.z_axis);
+        }
+        result
+    }
+}
+
+#[cfg(feature = "3d")]
+impl Mul<WideVector> for WideMatrix {
+    type Output = WideVector;
+    #[inline]
+    fn mul(self, rhs: WideVector) -> WideVector {
+        self.x_axis * rhs.x + self.y_axis * rhs.y + self.z_axis * rhs.z
+    }
+}
+
+/// The wide type for angular quantities like angular velocity.
+#[cfg(feature = "2d")]
+pub(crate) type WideAngular = Wide;
+/// The wide type for angular quantities like angular velocity.
+#[cfg(feature = "3d")]
+pub(crate) type WideAngular = WideVector;
+
+/// The wide type for inverse angular inertia.
+#[cfg(feature = "2d")]
+pub(crate) type WideAngularInertia = Wide;
+/// The wide type for inverse angular inertia.
+#[cfg(feature = "3d")]
+pub(crate) type WideAngularInertia = WideMatrix;
+
+/// Computes the lane-wise cross product, like [`cross`](crate::math::cross) for scalar vectors.
+#[cfg(feature = "2d")]
+#[inline]
+pub(crate) fn wide_cross(a: WideVector, b: WideVector) -> Wide {
+    a.x * b.y - a.y * b.x
+}
+
+/// Computes the lane-wise cross product, like [`cross`](crate::math::cross) for scalar vectors.
+#[cfg(feature = "3d")]
+#[inline]
+pub(crate) fn wide_cross(a: WideVector, b: WideVector) -> WideVector {
+    a.cross(b)
+}
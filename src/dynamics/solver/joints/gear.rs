@@ -0,0 +1,447 @@
+//! [`GearJoint`] and [`RackAndPinionJoint`] components.
+
+use crate::{dynamics::solver::xpbd::*, prelude::*};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A gear joint couples the rotation of two bodies with a fixed ratio, like two meshing gears.
+///
+/// When the first body turns by an angle around its axis, the second body turns around its axis
+/// by the angle multiplied by the `ratio`, in the opposite direction. For gears, the ratio is
+/// the number of teeth on the first gear divided by the number of teeth on the second gear.
+/// A negative ratio makes the bodies turn in the same direction, like gears connected by a chain.
+///
+/// The joint only couples the rotations, so the bodies are typically also attached to something
+/// with [revolute joints](RevoluteJoint). The rotations are measured in world space,
+/// relative to the rotations of the bodies when the joint was first solved.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let ground = commands.spawn(RigidBody::Static).id();
+///     let small_gear = commands.spawn((RigidBody::Dynamic, Position(Vector::X))).id();
+///     let large_gear = commands.spawn((RigidBody::Dynamic, Position(Vector::X * 4.0))).id();
+///
+///     // Mount the gears on axles.
+///     commands.spawn(RevoluteJoint::new(ground, small_gear).with_local_anchor_1(Vector::X));
+///     commands.spawn(RevoluteJoint::new(ground, large_gear).with_local_anchor_1(Vector::X * 4.0));
+///
+///     // The small gear has 10 teeth and the large gear has 30 teeth.
+///     commands.spawn(GearJoint::new(small_gear, large_gear).with_ratio(10.0 / 30.0));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, MapEntities, PartialEq)]
+pub struct GearJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// The axis of rotation of the first body in its local space.
+    #[cfg(feature = "3d")]
+    pub local_axis1: Vector,
+    /// The axis of rotation of the second body in its local space.
+    #[cfg(feature = "3d")]
+    pub local_axis2: Vector,
+    /// The ratio between the rotation of the second body and the rotation of the first body.
+    pub ratio: Scalar,
+    /// The angle that the first body has turned around its axis since the joint was first solved.
+    pub angle1: Scalar,
+    /// The angle that the second body has turned around its axis since the joint was first solved.
+    pub angle2: Scalar,
+    /// The rotations of the bodies when the angles were last updated.
+    pub(crate) previous_rotations: Option<[Rotation; 2]>,
+    /// Lagrange multiplier for the angular correction.
+    pub lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit radians / (Newton * meter).
+    pub compliance: Scalar,
+    /// The torque exerted by the joint on the second body.
+    pub torque: Torque,
+}
+
+impl XpbdConstraint<2> for GearJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        self.update_angles(body1, body2);
+
+        // The angle of the second body should be the angle of the first body scaled by the negative ratio.
+        let c = self.ratio * self.angle1 + self.angle2;
+
+        if c.abs() <= Scalar::EPSILON {
+            self.torque = Torque::ZERO;
+            return;
+        }
+
+        #[cfg(feature = "2d")]
+        let (axis1, axis2) = (1.0, 1.0);
+        #[cfg(feature = "3d")]
+        let (axis1, axis2) = (
+            *body1.rotation * self.local_axis1,
+            *body2.rotation * self.local_axis2,
+        );
+
+        let w1 = self.ratio.powi(2) * inverse_inertia_along(body1, axis1);
+        let w2 = inverse_inertia_along(body2, axis2);
+
+        let delta_lagrange =
+            self.compute_lagrange_update(self.lagrange, c, &[w1, w2], self.compliance, dt);
+        self.lagrange += delta_lagrange;
+
+        apply_rotation(body1, axis1, self.ratio * delta_lagrange);
+        apply_rotation(body2, axis2, delta_lagrange);
+
+        self.update_angles(body1, body2);
+
+        #[cfg(feature = "2d")]
+        {
+            self.torque = self.compute_torque(self.lagrange, dt);
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.torque = self.compute_torque(self.lagrange, axis2, dt);
+        }
+    }
+}
+
+impl GearJoint {
+    /// Creates a new gear joint between two entities with a ratio of `1.0`.
+    pub fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            #[cfg(feature = "3d")]
+            local_axis1: Vector::Z,
+            #[cfg(feature = "3d")]
+            local_axis2: Vector::Z,
+            ratio: 1.0,
+            angle1: 0.0,
+            angle2: 0.0,
+            previous_rotations: None,
+            lagrange: 0.0,
+            compliance: 0.0,
+            torque: Torque::ZERO,
+        }
+    }
+
+    /// Sets the ratio between the rotation of the second body and the rotation of the first body.
+    pub fn with_ratio(self, ratio: Scalar) -> Self {
+        Self { ratio, ..self }
+    }
+
+    /// Sets the axes of rotation of the bodies in their local space.
+    #[cfg(feature = "3d")]
+    pub fn with_local_axes(self, axis1: Vector, axis2: Vector) -> Self {
+        Self {
+            local_axis1: axis1.normalize_or_zero(),
+            local_axis2: axis2.normalize_or_zero(),
+            ..self
+        }
+    }
+
+    /// Sets the joint's compliance (inverse of stiffness, radians / (Newton * meter)).
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    /// Accumulates the angles that the bodies have turned since the angles were last updated.
+    fn update_angles(&mut self, body1: &RigidBodyQueryItem, body2: &RigidBodyQueryItem) {
+        if let Some([previous1, previous2]) = self.previous_rotations {
+            #[cfg(feature = "2d")]
+            {
+                self.angle1 += previous1.angle_between(*body1.rotation);
+                self.angle2 += previous2.angle_between(*body2.rotation);
+            }
+            #[cfg(feature = "3d")]
+            {
+                self.angle1 += angle_around_axis(
+                    previous1,
+                    *body1.rotation,
+                    *body1.rotation * self.local_axis1,
+                );
+                self.angle2 += angle_around_axis(
+                    previous2,
+                    *body2.rotation,
+                    *body2.rotation * self.local_axis2,
+                );
+            }
+        }
+        self.previous_rotations = Some([*body1.rotation, *body2.rotation]);
+    }
+}
+
+impl AngularConstraint for GearJoint {}
+
+impl MapEntities for GearJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
+
+/// A rack and pinion joint couples the rotation of a pinion with the translation of a rack,
+/// converting rotary motion into linear motion and vice versa.
+///
+/// When the pinion (the first body) turns by an angle around its axis, the rack (the second body) moves
+/// along its axis by the angle multiplied by the `ratio`. For a real rack and pinion, the ratio is
+/// the pitch radius of the pinion, and the rack lies below the pinion when the ratio is positive.
+///
+/// The joint only couples the motion of the bodies, so the pinion is typically also attached to something
+/// with a [revolute joint](RevoluteJoint), and the rack with a [prismatic joint](PrismaticJoint).
+/// The motion is measured in world space, relative to the poses of the bodies when the joint was first solved.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let ground = commands.spawn(RigidBody::Static).id();
+///     let pinion = commands.spawn((RigidBody::Dynamic, Position(Vector::Y))).id();
+///     let rack = commands.spawn(RigidBody::Dynamic).id();
+///
+///     commands.spawn(RevoluteJoint::new(ground, pinion).with_local_anchor_1(Vector::Y));
+///     commands.spawn(PrismaticJoint::new(ground, rack).with_free_axis(Vector::X));
+///
+///     // A pinion with a radius of 0.5 moving a rack below it.
+///     commands.spawn(RackAndPinionJoint::new(pinion, rack).with_ratio(0.5));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, MapEntities, PartialEq)]
+pub struct RackAndPinionJoint {
+    /// The pinion entity, which rotates.
+    pub entity1: Entity,
+    /// The rack entity, which translates.
+    pub entity2: Entity,
+    /// The axis of rotation of the pinion in its local space.
+    #[cfg(feature = "3d")]
+    pub pinion_axis: Vector,
+    /// The axis of translation of the rack in its local space.
+    pub rack_axis: Vector,
+    /// The distance that the rack moves for each radian that the pinion turns.
+    pub ratio: Scalar,
+    /// The angle that the pinion has turned around its axis since the joint was first solved.
+    pub angle: Scalar,
+    /// The distance that the rack has moved along its axis since the joint was first solved.
+    pub translation: Scalar,
+    /// The rotation of the pinion and the position of the rack when the motion was last updated.
+    pub(crate) previous_pose: Option<(Rotation, Vector)>,
+    /// Lagrange multiplier for the correction.
+    pub lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the joint on the rack.
+    pub force: Vector,
+    /// The torque exerted by the joint on the pinion.
+    pub torque: Torque,
+}
+
+impl XpbdConstraint<2> for RackAndPinionJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [pinion, rack] = bodies;
+
+        self.update_motion(pinion, rack);
+
+        // The translation of the rack should be the angle of the pinion scaled by the ratio.
+        let c = self.ratio * self.angle - self.translation;
+
+        if c.abs() <= Scalar::EPSILON {
+            self.force = Vector::ZERO;
+            self.torque = Torque::ZERO;
+            return;
+        }
+
+        #[cfg(feature = "2d")]
+        let pinion_axis = 1.0;
+        #[cfg(feature = "3d")]
+        let pinion_axis = *pinion.rotation * self.pinion_axis;
+        let rack_axis = *rack.rotation * self.rack_axis;
+
+        let w1 = self.ratio.powi(2) * inverse_inertia_along(pinion, pinion_axis);
+        let w2 = (rack.effective_inverse_mass() * rack_axis).dot(rack_axis);
+
+        let delta_lagrange =
+            self.compute_lagrange_update(self.lagrange, c, &[w1, w2], self.compliance, dt);
+        self.lagrange += delta_lagrange;
+
+        apply_rotation(pinion, pinion_axis, self.ratio * delta_lagrange);
+        rack.accumulated_translation.0 -=
+            rack.effective_inverse_mass() * rack_axis * delta_lagrange;
+
+        self.update_motion(pinion, rack);
+
+        self.force = self.compute_force(self.lagrange, -rack_axis, dt);
+        #[cfg(feature = "2d")]
+        {
+            self.torque = self.compute_torque(self.ratio * self.lagrange, dt);
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.torque = self.compute_torque(self.ratio * self.lagrange, pinion_axis, dt);
+        }
+    }
+}
+
+impl RackAndPinionJoint {
+    /// Creates a new rack and pinion joint between a pinion and a rack with a ratio of `1.0`.
+    pub fn new(pinion: Entity, rack: Entity) -> Self {
+        Self {
+            entity1: pinion,
+            entity2: rack,
+            #[cfg(feature = "3d")]
+            pinion_axis: Vector::Z,
+            rack_axis: Vector::X,
+            ratio: 1.0,
+            angle: 0.0,
+            translation: 0.0,
+            previous_pose: None,
+            lagrange: 0.0,
+            compliance: 0.0,
+            force: Vector::ZERO,
+            torque: Torque::ZERO,
+        }
+    }
+
+    /// Sets the distance that the rack moves for each radian that the pinion turns.
+    pub fn with_ratio(self, ratio: Scalar) -> Self {
+        Self { ratio, ..self }
+    }
+
+    /// Sets the axis of rotation of the pinion in its local space.
+    #[cfg(feature = "3d")]
+    pub fn with_pinion_axis(self, axis: Vector) -> Self {
+        Self {
+            pinion_axis: axis.normalize_or_zero(),
+            ..self
+        }
+    }
+
+    /// Sets the axis of translation of the rack in its local space.
+    pub fn with_rack_axis(self, axis: Vector) -> Self {
+        Self {
+            rack_axis: axis.normalize_or_zero(),
+            ..self
+        }
+    }
+
+    /// Sets the joint's compliance (inverse of stiffness, meters / Newton).
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    /// Accumulates the rotation of the pinion and the translation of the rack
+    /// since the motion was last updated.
+    fn update_motion(&mut self, pinion: &RigidBodyQueryItem, rack: &RigidBodyQueryItem) {
+        let rack_position = rack.current_position();
+        if let Some((previous_rotation, previous_position)) = self.previous_pose {
+            #[cfg(feature = "2d")]
+            {
+                self.angle += previous_rotation.angle_between(*pinion.rotation);
+            }
+            #[cfg(feature = "3d")]
+            {
+                self.angle += angle_around_axis(
+                    previous_rotation,
+                    *pinion.rotation,
+                    *pinion.rotation * self.pinion_axis,
+                );
+            }
+            self.translation +=
+                (rack_position - previous_position).dot(*rack.rotation * self.rack_axis);
+        }
+        self.previous_pose = Some((*pinion.rotation, rack_position));
+    }
+}
+
+impl PositionConstraint for RackAndPinionJoint {}
+
+impl AngularConstraint for RackAndPinionJoint {}
+
+impl MapEntities for RackAndPinionJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
+
+/// Returns the signed angle that a body has turned around the given world-space `axis`
+/// when rotating from `previous` to `current`.
+#[cfg(feature = "3d")]
+fn angle_around_axis(previous: Rotation, current: Rotation, axis: Vector) -> Scalar {
+    let mut difference = current.0 * previous.0.inverse();
+    if difference.w < 0.0 {
+        difference = -difference;
+    }
+    difference.to_scaled_axis().dot(axis)
+}
+
+/// Returns the inverse angular inertia of a body around the given axis, or zero if the body is not dynamic.
+#[cfg(feature = "2d")]
+fn inverse_inertia_along(body: &RigidBodyQueryItem, _axis: Scalar) -> Scalar {
+    body.effective_global_angular_inertia().inverse()
+}
+
+/// Returns the inverse angular inertia of a body around the given axis, or zero if the body is not dynamic.
+#[cfg(feature = "3d")]
+fn inverse_inertia_along(body: &RigidBodyQueryItem, axis: Vector) -> Scalar {
+    axis.dot(body.effective_global_angular_inertia().inverse() * axis)
+}
+
+/// Rotates a body around the given axis by applying an angular correction
+/// of the given magnitude, scaled by the inverse angular inertia of the body.
+#[cfg(feature = "2d")]
+fn apply_rotation(body: &mut RigidBodyQueryItem, _axis: Scalar, magnitude: Scalar) {
+    if !body.rb.is_dynamic() {
+        return;
+    }
+
+    let inverse_inertia = body.effective_global_angular_inertia().inverse();
+    *body.rotation = body.rotation.add_angle(inverse_inertia * magnitude);
+}
+
+/// Rotates a body around the given axis by applying an angular correction
+/// of the given magnitude, scaled by the inverse angular inertia of the body.
+#[cfg(feature = "3d")]
+fn apply_rotation(body: &mut RigidBodyQueryItem, axis: Vector, magnitude: Scalar) {
+    if !body.rb.is_dynamic() {
+        return;
+    }
+
+    let inverse_inertia = body.effective_global_angular_inertia().inverse();
+    let delta_quat = Quaternion::from_scaled_axis(inverse_inertia * axis * magnitude);
+    body.rotation.0 = delta_quat * body.rotation.0;
+    *body.rotation = body.rotation.fast_renormalize();
+}
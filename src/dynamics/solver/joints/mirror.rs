@@ -0,0 +1,346 @@
+//! [`MirrorConstraint`] component.
+
+use crate::{dynamics::solver::xpbd::*, prelude::*};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A mirror constraint makes the motion of the second body mirror the motion of the first body
+/// across a plane. In 2D, the plane is a line.
+///
+/// The position of the second body is kept at the reflection of the position of the first body,
+/// and its rotation is kept at the reflection of the rotation of the first body.
+/// This means that when the first body moves towards the plane, the second body moves towards it
+/// from the other side, and when the first body turns clockwise, the second body turns counterclockwise.
+///
+/// Mirror constraints can be useful for things like portal and mirror puzzles or symmetric co-op mechanics.
+/// If the first body is [kinematic](RigidBody::Kinematic) or [static](RigidBody::Static), the second body
+/// simply follows it, and otherwise the bodies affect each other based on their masses.
+///
+/// The constraint is solved for the positions like [joints](super), and the difference between the velocity
+/// of the second body and the mirrored velocity of the first body is damped based on the damping of the constraint.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let player = commands.spawn(RigidBody::Dynamic).id();
+///     let reflection = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Mirror the motion of the player across the plane at `x = 10`.
+///     commands.spawn(
+///         MirrorConstraint::new(player, reflection).with_plane(Vector::X * 10.0, Vector::X),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, MapEntities, PartialEq)]
+pub struct MirrorConstraint {
+    /// The entity whose motion is mirrored.
+    pub entity1: Entity,
+    /// The entity that mirrors the motion of the first entity.
+    pub entity2: Entity,
+    /// A point on the mirror plane in world space.
+    pub plane_point: Vector,
+    /// The unit normal of the mirror plane in world space.
+    pub plane_normal: Vector,
+    /// Linear damping of the difference between the velocities of the bodies.
+    pub damping_linear: Scalar,
+    /// Angular damping of the difference between the angular velocities of the bodies.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the positional correction.
+    pub position_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction.
+    pub rotation_lagrange: Scalar,
+    /// The constraint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the constraint.
+    pub force: Vector,
+    /// The torque exerted by the constraint.
+    pub torque: Torque,
+}
+
+impl XpbdConstraint<2> for MirrorConstraint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.position_lagrange = 0.0;
+        self.rotation_lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+        self.force = self.mirror_position(body1, body2, dt);
+        self.torque = self.mirror_rotation(body1, body2, dt);
+    }
+}
+
+impl MirrorConstraint {
+    /// Creates a new mirror constraint that makes `entity2` mirror the motion of `entity1`
+    /// across the plane going through the origin with the `X` axis as its normal.
+    pub fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            plane_point: Vector::ZERO,
+            plane_normal: Vector::X,
+            damping_linear: 1.0,
+            damping_angular: 1.0,
+            position_lagrange: 0.0,
+            rotation_lagrange: 0.0,
+            compliance: 0.0,
+            force: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            torque: 0.0,
+            #[cfg(feature = "3d")]
+            torque: Vector::ZERO,
+        }
+    }
+
+    /// Sets the mirror plane, given a point on the plane and the normal of the plane in world space.
+    pub fn with_plane(self, point: Vector, normal: Vector) -> Self {
+        Self {
+            plane_point: point,
+            plane_normal: normal.normalize_or_zero(),
+            ..self
+        }
+    }
+
+    /// Sets the constraint's compliance (inverse of stiffness, meters / Newton).
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    /// Sets the linear velocity damping caused by the constraint.
+    pub fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    /// Sets the angular velocity damping caused by the constraint.
+    pub fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    /// Reflects a point in world space across the mirror plane.
+    pub fn reflect_point(&self, point: Vector) -> Vector {
+        point - 2.0 * self.plane_normal * self.plane_normal.dot(point - self.plane_point)
+    }
+
+    /// Reflects a direction or velocity in world space across the mirror plane.
+    pub fn reflect_vector(&self, vector: Vector) -> Vector {
+        vector - 2.0 * self.plane_normal * self.plane_normal.dot(vector)
+    }
+
+    /// Reflects a rotation across the mirror plane, reversing its direction.
+    pub fn reflect_rotation(&self, rotation: Rotation) -> Rotation {
+        #[cfg(feature = "2d")]
+        {
+            rotation.inverse()
+        }
+        #[cfg(feature = "3d")]
+        {
+            let axis = self.reflect_vector(Vector::new(rotation.0.x, rotation.0.y, rotation.0.z));
+            Rotation(Quaternion::from_xyzw(
+                -axis.x,
+                -axis.y,
+                -axis.z,
+                rotation.0.w,
+            ))
+        }
+    }
+
+    /// Reflects an angular velocity across the mirror plane, reversing its direction.
+    #[cfg(feature = "2d")]
+    pub fn reflect_angular_velocity(&self, angular_velocity: Scalar) -> Scalar {
+        -angular_velocity
+    }
+
+    /// Reflects an angular velocity across the mirror plane, reversing its direction.
+    #[cfg(feature = "3d")]
+    pub fn reflect_angular_velocity(&self, angular_velocity: Vector) -> Vector {
+        -self.reflect_vector(angular_velocity)
+    }
+
+    /// Moves the second body towards the reflection of the position of the first body.
+    ///
+    /// Returns the force exerted by the constraint.
+    fn mirror_position(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let offset = body2.current_position() - self.reflect_point(body1.current_position());
+        let distance = offset.length();
+
+        if distance <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        // Moving the first body moves the target of the second body in the mirrored direction.
+        let gradient2 = offset / distance;
+        let gradient1 = -self.reflect_vector(gradient2);
+
+        // The positions are at the body origins, which can be offset from the centers of mass.
+        let r1 = -(*body1.rotation * body1.center_of_mass.0);
+        let r2 = -(*body2.rotation * body2.center_of_mass.0);
+
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, r1, gradient1);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, r2, gradient2);
+
+        let delta_lagrange = self.compute_lagrange_update(
+            self.position_lagrange,
+            distance,
+            &[w1, w2],
+            self.compliance,
+            dt,
+        );
+        self.position_lagrange += delta_lagrange;
+
+        apply_positional_correction(body1, delta_lagrange * gradient1, r1);
+        apply_positional_correction(body2, delta_lagrange * gradient2, r2);
+
+        self.compute_force(self.position_lagrange, gradient2, dt)
+    }
+
+    /// Rotates the second body towards the reflection of the rotation of the first body.
+    ///
+    /// Returns the torque exerted by the constraint.
+    fn mirror_rotation(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        #[cfg(feature = "2d")]
+        {
+            // Rotating either body by an angle changes the difference by the same angle.
+            let angle = (*body2.rotation * *body1.rotation).as_radians();
+
+            if angle.abs() <= Scalar::EPSILON {
+                return 0.0;
+            }
+
+            let w1 = body1.effective_global_angular_inertia().inverse();
+            let w2 = body2.effective_global_angular_inertia().inverse();
+
+            let delta_lagrange = self.compute_lagrange_update(
+                self.rotation_lagrange,
+                angle,
+                &[w1, w2],
+                self.compliance,
+                dt,
+            );
+            self.rotation_lagrange += delta_lagrange;
+
+            *body1.rotation = body1.rotation.add_angle(delta_lagrange * w1);
+            *body2.rotation = body2.rotation.add_angle(delta_lagrange * w2);
+
+            self.compute_torque(self.rotation_lagrange, dt)
+        }
+        #[cfg(feature = "3d")]
+        {
+            let target = self.reflect_rotation(*body1.rotation);
+            let mut difference = body2.rotation.0 * target.0.inverse();
+            if difference.w < 0.0 {
+                difference = -difference;
+            }
+
+            let rotation_difference = difference.to_scaled_axis();
+            let angle = rotation_difference.length();
+
+            if angle <= Scalar::EPSILON {
+                return Vector::ZERO;
+            }
+
+            // Rotating the first body rotates the target of the second body in the mirrored direction.
+            let gradient2 = rotation_difference / angle;
+            let gradient1 = self.reflect_vector(gradient2);
+
+            let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, gradient1);
+            let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, gradient2);
+
+            let delta_lagrange = self.compute_lagrange_update(
+                self.rotation_lagrange,
+                angle,
+                &[w1, w2],
+                self.compliance,
+                dt,
+            );
+            self.rotation_lagrange += delta_lagrange;
+
+            apply_angular_correction(body1, delta_lagrange * gradient1);
+            apply_angular_correction(body2, delta_lagrange * gradient2);
+
+            self.compute_torque(self.rotation_lagrange, gradient2, dt)
+        }
+    }
+}
+
+/// Applies a positional correction to a body at the offset `r` from its center of mass.
+///
+/// The bodies of a [`MirrorConstraint`] are corrected along different directions,
+/// so the corrections are applied to each body separately.
+fn apply_positional_correction(body: &mut RigidBodyQueryItem, impulse: Vector, r: Vector) {
+    if !body.rb.is_dynamic() {
+        return;
+    }
+
+    let inverse_inertia = body.effective_global_angular_inertia().inverse();
+    body.accumulated_translation.0 += impulse * body.effective_inverse_mass();
+
+    #[cfg(feature = "2d")]
+    {
+        *body.rotation = body
+            .rotation
+            .add_angle(inverse_inertia * r.perp_dot(impulse));
+    }
+    #[cfg(feature = "3d")]
+    {
+        let delta_quat = Quaternion::from_scaled_axis(inverse_inertia * r.cross(impulse));
+        body.rotation.0 = delta_quat * body.rotation.0;
+        *body.rotation = body.rotation.fast_renormalize();
+    }
+}
+
+/// Applies an angular correction to a body.
+#[cfg(feature = "3d")]
+fn apply_angular_correction(body: &mut RigidBodyQueryItem, impulse: Vector) {
+    if !body.rb.is_dynamic() {
+        return;
+    }
+
+    let inverse_inertia = body.effective_global_angular_inertia().inverse();
+    let delta_quat = Quaternion::from_scaled_axis(inverse_inertia * impulse);
+    body.rotation.0 = delta_quat * body.rotation.0;
+    *body.rotation = body.rotation.fast_renormalize();
+}
+
+impl PositionConstraint for MirrorConstraint {}
+
+impl AngularConstraint for MirrorConstraint {}
+
+impl MapEntities for MirrorConstraint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
@@ -22,6 +22,18 @@
     feature = "3d",
     doc = "| [`SphericalJoint`] | 1 Rotation                | 3 Rotations                 |"
 )]
+#![cfg_attr(
+    feature = "3d",
+    doc = "| [`UniversalJoint`] | -                         | 2 Rotations                 |"
+)]
+//!
+//! In addition to joints, a [`MirrorConstraint`] can be used to make the motion of one body
+//! mirror the motion of another body across a plane.
+//!
+//! Mechanical contraptions can also be built using a [`GearJoint`], which couples the rotation
+//! of two bodies with a fixed ratio, and a [`RackAndPinionJoint`], which couples the rotation
+//! of one body with the translation of another body. They don't restrict any DOF on their own,
+//! so they are typically used together with [revolute](RevoluteJoint) and [prismatic](PrismaticJoint) joints.
 //!
 //! # Using Joints
 //!
@@ -78,24 +90,34 @@
 //! like `align_position` and `align_orientation` to reduce some common boilerplate.
 //!
 //! Many joints also have joint limits. You can use [`DistanceLimit`] and [`AngleLimit`] to help store these limits
-//! and to compute the current distance from the specified limits.
+//! and to compute the current distance from the specified limits. A [`JointLimitResponse`]
+//! can be used to configure the softness and bounciness of the limits, and a [`JointSpring`]
+//! can be used to make a free axis springy.
 //!
 //! [See the code implementations](https://github.com/Jondolf/avian/tree/main/src/constraints/joints)
 //! of the implemented joints to get a better idea of how to create joints.
 
 mod distance;
 mod fixed;
+mod gear;
+mod mirror;
 mod prismatic;
 mod revolute;
 #[cfg(feature = "3d")]
 mod spherical;
+#[cfg(feature = "3d")]
+mod universal;
 
 pub use distance::*;
 pub use fixed::*;
+pub use gear::*;
+pub use mirror::*;
 pub use prismatic::*;
 pub use revolute::*;
 #[cfg(feature = "3d")]
 pub use spherical::*;
+#[cfg(feature = "3d")]
+pub use universal::*;
 
 use crate::{dynamics::solver::xpbd::*, prelude::*};
 use bevy::prelude::*;
@@ -339,12 +361,350 @@ impl AngleLimit {
     }
 }
 
+/// A motor that drives the relative angular velocity of two bodies around an axis
+/// towards a target velocity, using at most a given amount of torque.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct AngularMotor {
+    /// The target angular velocity of the second body relative to the first body around the axis.
+    pub target_velocity: Scalar,
+    /// The maximum torque that the motor can apply to reach the target velocity.
+    pub max_torque: Scalar,
+}
+
+impl AngularMotor {
+    /// Creates a new `AngularMotor` with the given target velocity and maximum torque.
+    pub const fn new(target_velocity: Scalar, max_torque: Scalar) -> Self {
+        Self {
+            target_velocity,
+            max_torque,
+        }
+    }
+
+    /// Applies an angular impulse around the given world-space `axis` that changes
+    /// the relative angular velocity of the bodies towards the target velocity.
+    #[cfg(feature = "3d")]
+    pub(crate) fn apply(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        axis: Vector,
+        dt: Scalar,
+    ) {
+        let inverse_inertia1 = body1.effective_global_angular_inertia().inverse();
+        let inverse_inertia2 = body2.effective_global_angular_inertia().inverse();
+
+        let effective_inverse_inertia =
+            axis.dot(inverse_inertia1 * axis) + axis.dot(inverse_inertia2 * axis);
+
+        if effective_inverse_inertia <= Scalar::EPSILON {
+            return;
+        }
+
+        let relative_velocity = (body2.angular_velocity.0 - body1.angular_velocity.0).dot(axis);
+        let max_impulse = self.max_torque * dt;
+        let impulse = ((self.target_velocity - relative_velocity) / effective_inverse_inertia)
+            .clamp(-max_impulse, max_impulse);
+
+        body1.angular_velocity.0 -= inverse_inertia1 * axis * impulse;
+        body2.angular_velocity.0 += inverse_inertia2 * axis * impulse;
+    }
+}
+
+/// Configures how a joint responds when the attached bodies reach one of its limits.
+///
+/// By default, limits are rigid and stop the bodies without bouncing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct JointLimitResponse {
+    /// The compliance of the limits, the inverse of stiffness. This is added to the compliance of the joint.
+    ///
+    /// Higher values make the limits softer, letting the bodies sink past them
+    /// and get pushed back gradually instead of stopping abruptly.
+    ///
+    /// Default: `0.0`
+    pub compliance: Scalar,
+    /// The coefficient of restitution of the limits, between `0.0` and `1.0`.
+    ///
+    /// When the bodies hit a limit, the relative velocity towards the limit
+    /// is reversed and scaled by this coefficient, making the bodies bounce back.
+    ///
+    /// Default: `0.0`
+    pub restitution: Scalar,
+}
+
+impl JointLimitResponse {
+    /// Rigid limits that stop the bodies without bouncing.
+    pub const RIGID: Self = Self::new(0.0, 0.0);
+
+    /// Creates a new `JointLimitResponse` with the given compliance and coefficient of restitution.
+    pub const fn new(compliance: Scalar, restitution: Scalar) -> Self {
+        Self {
+            compliance,
+            restitution,
+        }
+    }
+
+    /// Sets the compliance of the limits.
+    pub const fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+
+    /// Sets the coefficient of restitution of the limits.
+    pub const fn with_restitution(mut self, restitution: Scalar) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    /// Applies restitution for an angular limit that exerted the given `torque` during the substep.
+    ///
+    /// If the limit slowed down the relative rotation of the bodies around the axis of the torque,
+    /// the relative angular velocity before the solve is reversed and scaled by the restitution.
+    pub(crate) fn apply_angular_restitution(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        torque: Torque,
+    ) {
+        if self.restitution <= 0.0 {
+            return;
+        }
+
+        #[cfg(feature = "2d")]
+        let (axis, pre_solve_velocity, velocity) = {
+            if torque == 0.0 {
+                return;
+            }
+            let axis = torque.signum();
+            (
+                axis,
+                (body2.pre_solve_angular_velocity.0 - body1.pre_solve_angular_velocity.0) * axis,
+                (body2.angular_velocity.0 - body1.angular_velocity.0) * axis,
+            )
+        };
+        #[cfg(feature = "3d")]
+        let (axis, pre_solve_velocity, velocity) = {
+            let Some(axis) = torque.try_normalize() else {
+                return;
+            };
+            (
+                axis,
+                (body2.pre_solve_angular_velocity.0 - body1.pre_solve_angular_velocity.0).dot(axis),
+                (body2.angular_velocity.0 - body1.angular_velocity.0).dot(axis),
+            )
+        };
+
+        let inverse_inertia1 = body1.effective_global_angular_inertia().inverse();
+        let inverse_inertia2 = body2.effective_global_angular_inertia().inverse();
+
+        #[cfg(feature = "2d")]
+        let effective_inverse_inertia = inverse_inertia1 + inverse_inertia2;
+        #[cfg(feature = "3d")]
+        let effective_inverse_inertia =
+            axis.dot(inverse_inertia1 * axis) + axis.dot(inverse_inertia2 * axis);
+
+        let Some(impulse) =
+            self.restitution_impulse(pre_solve_velocity, velocity, effective_inverse_inertia)
+        else {
+            return;
+        };
+
+        body1.angular_velocity.0 -= inverse_inertia1 * axis * impulse;
+        body2.angular_velocity.0 += inverse_inertia2 * axis * impulse;
+    }
+
+    /// Applies restitution for a linear limit that exerted the given `force` at the world-space
+    /// anchors `r1` and `r2` during the substep.
+    ///
+    /// If the limit slowed down the relative motion of the anchors along the direction of the force,
+    /// the relative velocity before the solve is reversed and scaled by the restitution.
+    pub(crate) fn apply_linear_restitution(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        r1: Vector,
+        r2: Vector,
+        force: Vector,
+    ) {
+        if self.restitution <= 0.0 {
+            return;
+        }
+        let Some(axis) = force.try_normalize() else {
+            return;
+        };
+
+        let pre_solve_velocity_at_point = |body: &RigidBodyQueryItem, r: Vector| {
+            #[cfg(feature = "2d")]
+            {
+                body.pre_solve_linear_velocity.0 + body.pre_solve_angular_velocity.0 * r.perp()
+            }
+            #[cfg(feature = "3d")]
+            {
+                body.pre_solve_linear_velocity.0 + body.pre_solve_angular_velocity.0.cross(r)
+            }
+        };
+        let pre_solve_velocity = (pre_solve_velocity_at_point(body2, r2)
+            - pre_solve_velocity_at_point(body1, r1))
+        .dot(axis);
+        let velocity = (body2.velocity_at_point(r2) - body1.velocity_at_point(r1)).dot(axis);
+
+        let inverse_mass = |body: &RigidBodyQueryItem, r: Vector| {
+            if !body.rb.is_dynamic() {
+                return 0.0;
+            }
+            let inverse_inertia = body.effective_global_angular_inertia().inverse();
+            let r_cross_n = cross(r, axis);
+            #[cfg(feature = "2d")]
+            {
+                body.mass.inverse() + inverse_inertia * r_cross_n * r_cross_n
+            }
+            #[cfg(feature = "3d")]
+            {
+                body.mass.inverse() + r_cross_n.dot(inverse_inertia * r_cross_n)
+            }
+        };
+
+        let Some(impulse) = self.restitution_impulse(
+            pre_solve_velocity,
+            velocity,
+            inverse_mass(body1, r1) + inverse_mass(body2, r2),
+        ) else {
+            return;
+        };
+        let impulse = axis * impulse;
+
+        if body1.rb.is_dynamic() {
+            body1.linear_velocity.0 -= body1.effective_inverse_mass() * impulse;
+            body1.angular_velocity.0 -=
+                body1.effective_global_angular_inertia().inverse() * cross(r1, impulse);
+        }
+        if body2.rb.is_dynamic() {
+            body2.linear_velocity.0 += body2.effective_inverse_mass() * impulse;
+            body2.angular_velocity.0 +=
+                body2.effective_global_angular_inertia().inverse() * cross(r2, impulse);
+        }
+    }
+
+    /// Returns the impulse along the axis of a limit that makes the bodies bounce back
+    /// from the limit, or `None` if the limit didn't stop the bodies from moving towards it.
+    fn restitution_impulse(
+        &self,
+        pre_solve_velocity: Scalar,
+        velocity: Scalar,
+        inverse_mass: Scalar,
+    ) -> Option<Scalar> {
+        // The limit must have opposed the relative motion for the bodies to bounce.
+        let velocity_change = velocity - pre_solve_velocity;
+        if velocity_change * pre_solve_velocity >= 0.0 || inverse_mass <= Scalar::EPSILON {
+            return None;
+        }
+
+        // Only push further in the direction the limit pushed the bodies.
+        let target_velocity = -self.restitution * pre_solve_velocity;
+        let delta_velocity = target_velocity - velocity;
+        (delta_velocity * velocity_change > 0.0).then(|| delta_velocity / inverse_mass)
+    }
+}
+/// A spring that pulls a joint axis towards a rest position, with optional damping.
+///
+/// Springs make the otherwise free axis of a joint soft instead of free, which can be used
+/// for things like vehicle suspensions, self-closing doors, and wobbly attachments.
+///
+/// The spring can be configured either with a [stiffness and damping coefficient](Self::new),
+/// or with a [frequency and damping ratio](Self::from_frequency). The latter is often easier to tune,
+/// as it behaves the same regardless of the masses of the attached bodies.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum JointSpring {
+    /// A spring with a fixed stiffness and damping coefficient.
+    Stiffness {
+        /// The stiffness of the spring, in Newtons per meter for translation
+        /// or Newton-meters per radian for rotation.
+        stiffness: Scalar,
+        /// The damping coefficient, in Newton-seconds per meter for translation
+        /// or Newton-meter-seconds per radian for rotation.
+        damping: Scalar,
+    },
+    /// A spring that oscillates at a given frequency regardless of the masses of the bodies.
+    Frequency {
+        /// The undamped oscillation frequency of the spring, in Hertz.
+        frequency: Scalar,
+        /// The damping ratio of the spring. `0.0` means no damping,
+        /// and `1.0` means critical damping, where the spring returns to rest
+        /// as fast as possible without oscillating.
+        damping_ratio: Scalar,
+    },
+}
+
+impl JointSpring {
+    /// Creates a new [`JointSpring`] with the given stiffness and damping coefficient.
+    pub const fn new(stiffness: Scalar, damping: Scalar) -> Self {
+        Self::Stiffness { stiffness, damping }
+    }
+
+    /// Creates a new [`JointSpring`] with the given frequency in Hertz and damping ratio.
+    pub const fn from_frequency(frequency: Scalar, damping_ratio: Scalar) -> Self {
+        Self::Frequency {
+            frequency,
+            damping_ratio,
+        }
+    }
+
+    /// Returns the compliance of the spring for bodies with the given effective inverse mass
+    /// along the axis, or `None` if the spring has no stiffness.
+    pub(crate) fn compliance(&self, inverse_mass: Scalar) -> Option<Scalar> {
+        match *self {
+            Self::Stiffness { stiffness, .. } => (stiffness > 0.0).then(|| stiffness.recip()),
+            Self::Frequency { frequency, .. } => {
+                let angular_frequency = TAU * frequency;
+                (angular_frequency > 0.0).then(|| inverse_mass / angular_frequency.powi(2))
+            }
+        }
+    }
+
+    /// Returns the damping impulse along the axis for the given relative velocity
+    /// and effective inverse mass of the bodies.
+    ///
+    /// The damping is applied implicitly, so it is stable even for large damping coefficients.
+    pub(crate) fn damping_impulse(
+        &self,
+        relative_velocity: Scalar,
+        inverse_mass: Scalar,
+        dt: Scalar,
+    ) -> Scalar {
+        if inverse_mass <= Scalar::EPSILON {
+            return 0.0;
+        }
+
+        let damping = match *self {
+            Self::Stiffness { damping, .. } => damping,
+            Self::Frequency {
+                frequency,
+                damping_ratio,
+            } => 2.0 * damping_ratio * TAU * frequency / inverse_mass,
+        };
+
+        -relative_velocity * damping * dt / (1.0 + inverse_mass * damping * dt)
+    }
+}
+
 /// A marker component that indicates that a [joint](self) is disabled
 /// and should not constrain the bodies it is attached to.
 /// Must be on the same entity as the joint.
 ///
-/// This is useful for temporarily disabling a joint without removing it from the world.
-/// To re-enable the joint, simply remove this component.
+/// This is useful for temporarily disabling a joint without removing it from the world,
+/// for example for detaching a grapple hook or a trailer. The configuration of the joint
+/// is preserved, and to re-enable the joint, simply remove this component.
+///
+/// When the component is added or removed, the [`Sleeping`] bodies attached to the joint
+/// are woken up so that they respond to the change.
 ///
 /// Note that when re-enabling the joint, the bodies may snap back violently
 /// if they have moved significantly from the constrained positions while the joint was disabled.
@@ -353,3 +713,30 @@ impl AngleLimit {
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
 #[reflect(Debug, Component, Default)]
 pub struct JointDisabled;
+
+/// Wakes up the bodies attached to constraints of type `C` when [`JointDisabled`]
+/// is added to or removed from the constraint.
+pub(crate) fn wake_on_joint_toggled<
+    C: XpbdConstraint<ENTITY_COUNT> + Component,
+    const ENTITY_COUNT: usize,
+>(
+    mut commands: Commands,
+    disabled_joints: Query<&C, Added<JointDisabled>>,
+    joints: Query<&C>,
+    mut enabled_joints: RemovedComponents<JointDisabled>,
+    mut bodies: Query<(Entity, &mut TimeSleeping, Has<Sleeping>)>,
+) {
+    let toggled_joints = disabled_joints
+        .iter()
+        .chain(joints.iter_many(enabled_joints.read()));
+
+    for joint in toggled_joints {
+        let mut joint_bodies = bodies.iter_many_mut(joint.entities());
+        while let Some((entity, mut time_sleeping, is_sleeping)) = joint_bodies.fetch_next() {
+            if is_sleeping {
+                commands.entity(entity).remove::<Sleeping>();
+            }
+            time_sleeping.0 = 0.0;
+        }
+    }
+}
@@ -29,6 +29,12 @@ pub struct PrismaticJoint {
     pub free_axis: Vector,
     /// The extents of the allowed relative translation along the free axis.
     pub free_axis_limits: Option<DistanceLimit>,
+    /// Configures the softness and bounciness of the limits along the free axis.
+    pub limit_response: JointLimitResponse,
+    /// A spring that pulls the relative translation of the bodies along the free axis towards the `rest_translation`.
+    pub translation_spring: Option<JointSpring>,
+    /// The relative translation of the bodies along the free axis that the `translation_spring` pulls towards.
+    pub rest_translation: Scalar,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -37,12 +43,20 @@ pub struct PrismaticJoint {
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
     pub align_lagrange: Scalar,
+    /// Lagrange multiplier for the positional correction caused by the limits along the free axis.
+    pub limit_lagrange: Scalar,
+    /// Lagrange multiplier for the positional correction caused by the translation spring.
+    pub translation_spring_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
     /// The force exerted by the joint.
     pub force: Vector,
     /// The torque exerted by the joint when aligning the bodies.
     pub align_torque: Torque,
+    /// The force exerted by the joint when limiting the relative translation along the free axis.
+    pub limit_force: Vector,
+    /// The force exerted by the translation spring.
+    pub translation_spring_force: Vector,
 }
 
 impl XpbdConstraint<2> for PrismaticJoint {
@@ -53,6 +67,8 @@ impl XpbdConstraint<2> for PrismaticJoint {
     fn clear_lagrange_multipliers(&mut self) {
         self.position_lagrange = 0.0;
         self.align_lagrange = 0.0;
+        self.limit_lagrange = 0.0;
+        self.translation_spring_lagrange = 0.0;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
@@ -68,6 +84,12 @@ impl XpbdConstraint<2> for PrismaticJoint {
 
         // Constrain the relative positions of the bodies, only allowing translation along one free axis
         self.force = self.constrain_positions(body1, body2, dt);
+
+        // Apply limits along the free axis
+        self.limit_force = self.apply_translation_limits(body1, body2, dt);
+
+        // Pull the bodies towards the rest translation
+        self.translation_spring_force = self.apply_translation_spring(body1, body2, dt);
     }
 }
 
@@ -80,16 +102,23 @@ impl Joint for PrismaticJoint {
             local_anchor2: Vector::ZERO,
             free_axis: Vector::X,
             free_axis_limits: None,
+            limit_response: JointLimitResponse::RIGID,
+            translation_spring: None,
+            rest_translation: 0.0,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
+            limit_lagrange: 0.0,
+            translation_spring_lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
             align_torque: 0.0,
             #[cfg(feature = "3d")]
             align_torque: Vector::ZERO,
+            limit_force: Vector::ZERO,
+            translation_spring_force: Vector::ZERO,
         }
     }
 
@@ -158,13 +187,6 @@ impl PrismaticJoint {
         let mut delta_x = Vector::ZERO;
 
         let axis1 = *body1.rotation * self.free_axis;
-        if let Some(limits) = self.free_axis_limits {
-            delta_x += limits.compute_correction_along_axis(
-                body1.current_position() + world_r1,
-                body2.current_position() + world_r2,
-                axis1,
-            );
-        }
 
         let zero_distance_limit = DistanceLimit::ZERO;
 
@@ -230,6 +252,193 @@ impl PrismaticJoint {
         self.compute_force(self.position_lagrange, dir, dt)
     }
 
+    /// Applies limits to limit the relative translation of the bodies along the free axis.
+    ///
+    /// Returns the force exerted by the limits.
+    fn apply_translation_limits(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let Some(limits) = self.free_axis_limits else {
+            return Vector::ZERO;
+        };
+
+        let world_r1 = *body1.rotation * self.local_anchor1;
+        let world_r2 = *body2.rotation * self.local_anchor2;
+        let axis1 = *body1.rotation * self.free_axis;
+
+        let delta_x = limits.compute_correction_along_axis(
+            body1.current_position() + world_r1,
+            body2.current_position() + world_r2,
+            axis1,
+        );
+
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange = self.compute_lagrange_update(
+            self.limit_lagrange,
+            magnitude,
+            &[w1, w2],
+            self.compliance + self.limit_response.compliance,
+            dt,
+        );
+        self.limit_lagrange += delta_lagrange;
+
+        // Apply positional correction to keep the bodies within the limits
+        self.apply_positional_lagrange_update(
+            body1,
+            body2,
+            delta_lagrange,
+            dir,
+            world_r1,
+            world_r2,
+        );
+
+        // Return limit force
+        self.compute_force(self.limit_lagrange, dir, dt)
+    }
+
+    /// Applies restitution for the limits along the free axis if they were hit during the substep.
+    pub(crate) fn apply_limit_restitution(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+    ) {
+        let world_r1 = *body1.rotation * self.local_anchor1;
+        let world_r2 = *body2.rotation * self.local_anchor2;
+        self.limit_response.apply_linear_restitution(
+            body1,
+            body2,
+            world_r1,
+            world_r2,
+            self.limit_force,
+        );
+    }
+
+    /// Applies the translation spring to pull the relative translation of the bodies along the free axis
+    /// towards the `rest_translation`.
+    ///
+    /// Returns the force exerted by the spring.
+    fn apply_translation_spring(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let Some(spring) = self.translation_spring else {
+            return Vector::ZERO;
+        };
+
+        let world_r1 = *body1.rotation * self.local_anchor1;
+        let world_r2 = *body2.rotation * self.local_anchor2;
+        let axis1 = *body1.rotation * self.free_axis;
+
+        let delta_x = DistanceLimit::new(self.rest_translation, self.rest_translation)
+            .compute_correction_along_axis(
+                body1.current_position() + world_r1,
+                body2.current_position() + world_r2,
+                axis1,
+            );
+
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        let Some(spring_compliance) = spring.compliance(w1 + w2) else {
+            return Vector::ZERO;
+        };
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange = self.compute_lagrange_update(
+            self.translation_spring_lagrange,
+            magnitude,
+            &[w1, w2],
+            self.compliance + spring_compliance,
+            dt,
+        );
+        self.translation_spring_lagrange += delta_lagrange;
+
+        // Apply positional correction to pull the bodies towards the rest translation
+        self.apply_positional_lagrange_update(
+            body1,
+            body2,
+            delta_lagrange,
+            dir,
+            world_r1,
+            world_r2,
+        );
+
+        // Return spring force
+        self.compute_force(self.translation_spring_lagrange, dir, dt)
+    }
+
+    /// Damps the relative velocity of the anchors along the free axis
+    /// based on the damping of the translation spring.
+    pub(crate) fn apply_spring_damping(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) {
+        let Some(spring) = self.translation_spring else {
+            return;
+        };
+
+        let world_r1 = *body1.rotation * self.local_anchor1;
+        let world_r2 = *body2.rotation * self.local_anchor2;
+        let axis = *body1.rotation * self.free_axis;
+
+        let relative_velocity =
+            (body2.velocity_at_point(world_r2) - body1.velocity_at_point(world_r1)).dot(axis);
+
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, axis);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, axis);
+
+        let impulse = axis * spring.damping_impulse(relative_velocity, w1 + w2, dt);
+
+        if body1.rb.is_dynamic() {
+            body1.linear_velocity.0 -= body1.effective_inverse_mass() * impulse;
+            body1.angular_velocity.0 -=
+                body1.effective_global_angular_inertia().inverse() * cross(world_r1, impulse);
+        }
+        if body2.rb.is_dynamic() {
+            body2.linear_velocity.0 += body2.effective_inverse_mass() * impulse;
+            body2.angular_velocity.0 +=
+                body2.effective_global_angular_inertia().inverse() * cross(world_r2, impulse);
+        }
+    }
+
+    /// Sets a spring that pulls the relative translation of the bodies along the free axis
+    /// towards the given `rest_translation`.
+    pub fn with_translation_spring(self, rest_translation: Scalar, spring: JointSpring) -> Self {
+        Self {
+            translation_spring: Some(spring),
+            rest_translation,
+            ..self
+        }
+    }
+
     /// Sets the joint's free axis. Relative translations are allowed along this free axis.
     pub fn with_free_axis(self, axis: Vector) -> Self {
         Self {
@@ -246,6 +455,14 @@ impl PrismaticJoint {
         }
     }
 
+    /// Sets the softness and bounciness of the limits along the free axis.
+    pub fn with_limit_response(self, limit_response: JointLimitResponse) -> Self {
+        Self {
+            limit_response,
+            ..self
+        }
+    }
+
     #[cfg(feature = "2d")]
     fn get_rotation_difference(&self, rot1: &Rotation, rot2: &Rotation) -> Scalar {
         rot1.angle_between(*rot2)
@@ -35,6 +35,12 @@ pub struct RevoluteJoint {
     pub aligned_axis: Vector,
     /// The extents of the allowed relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit: Option<AngleLimit>,
+    /// Configures the softness and bounciness of the angle limits.
+    pub limit_response: JointLimitResponse,
+    /// A spring that pulls the relative rotation of the bodies around the `aligned_axis` towards the `rest_angle`.
+    pub angle_spring: Option<JointSpring>,
+    /// The relative rotation of the bodies around the `aligned_axis` that the `angle_spring` pulls towards.
+    pub rest_angle: Scalar,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -45,6 +51,8 @@ pub struct RevoluteJoint {
     pub align_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the angle limits.
     pub angle_limit_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction caused by the angle spring.
+    pub angle_spring_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
     /// The force exerted by the joint.
@@ -53,6 +61,8 @@ pub struct RevoluteJoint {
     pub align_torque: Torque,
     /// The torque exerted by the joint when limiting the relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit_torque: Torque,
+    /// The torque exerted by the angle spring.
+    pub angle_spring_torque: Torque,
 }
 
 impl XpbdConstraint<2> for RevoluteJoint {
@@ -64,6 +74,7 @@ impl XpbdConstraint<2> for RevoluteJoint {
         self.position_lagrange = 0.0;
         self.align_lagrange = 0.0;
         self.angle_limit_lagrange = 0.0;
+        self.angle_spring_lagrange = 0.0;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
@@ -83,6 +94,9 @@ impl XpbdConstraint<2> for RevoluteJoint {
         // Apply angle limits when rotating around the free axis
         self.angle_limit_torque = self.apply_angle_limits(body1, body2, dt);
 
+        // Pull the bodies towards the rest angle
+        self.angle_spring_torque = self.apply_angle_spring(body1, body2, dt);
+
         // Align positions
         let mut lagrange = self.position_lagrange;
         self.force = self.align_position(
@@ -107,11 +121,15 @@ impl Joint for RevoluteJoint {
             local_anchor2: Vector::ZERO,
             aligned_axis: Vector3::Z,
             angle_limit: None,
+            limit_response: JointLimitResponse::RIGID,
+            angle_spring: None,
+            rest_angle: 0.0,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             angle_limit_lagrange: 0.0,
+            angle_spring_lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
@@ -122,6 +140,10 @@ impl Joint for RevoluteJoint {
             angle_limit_torque: 0.0,
             #[cfg(feature = "3d")]
             angle_limit_torque: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            angle_spring_torque: 0.0,
+            #[cfg(feature = "3d")]
+            angle_spring_torque: Vector::ZERO,
         }
     }
 
@@ -192,6 +214,24 @@ impl RevoluteJoint {
         }
     }
 
+    /// Sets the softness and bounciness of the angle limits.
+    pub fn with_limit_response(self, limit_response: JointLimitResponse) -> Self {
+        Self {
+            limit_response,
+            ..self
+        }
+    }
+
+    /// Sets a spring that pulls the relative rotation of the bodies around the `aligned_axis`
+    /// towards the given `rest_angle`.
+    pub fn with_angle_spring(self, rest_angle: Scalar, spring: JointSpring) -> Self {
+        Self {
+            angle_spring: Some(spring),
+            rest_angle,
+            ..self
+        }
+    }
+
     #[cfg(feature = "3d")]
     fn get_rotation_difference(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
         let a1 = rot1 * self.aligned_axis;
@@ -199,37 +239,159 @@ impl RevoluteJoint {
         a1.cross(a2)
     }
 
+    /// Returns the `aligned_axis` of the first body in world space.
+    #[allow(unused_variables)]
+    fn world_aligned_axis(&self, body1: &RigidBodyQueryItem) -> Vector3 {
+        #[cfg(feature = "2d")]
+        {
+            self.aligned_axis
+        }
+        #[cfg(feature = "3d")]
+        {
+            *body1.rotation * self.aligned_axis
+        }
+    }
+
+    /// Returns the angular correction required to keep the relative rotation of the bodies
+    /// around the `aligned_axis` within the given `angle_limit`.
+    fn compute_angle_correction(
+        &self,
+        angle_limit: AngleLimit,
+        body1: &RigidBodyQueryItem,
+        body2: &RigidBodyQueryItem,
+    ) -> Option<Torque> {
+        #[cfg(feature = "2d")]
+        {
+            angle_limit.compute_correction(*body1.rotation, *body2.rotation, PI)
+        }
+        #[cfg(feature = "3d")]
+        {
+            // [n, n1, n2] = [a1, b1, b2], where [a, b, c] are perpendicular unit axes on the bodies.
+            let a1 = *body1.rotation * self.aligned_axis;
+            let b1 = *body1.rotation * self.aligned_axis.any_orthonormal_vector();
+            let b2 = *body2.rotation * self.aligned_axis.any_orthonormal_vector();
+            angle_limit.compute_correction(a1, b1, b2, PI)
+        }
+    }
+
+    /// Returns the sum of the generalized inverse masses of the bodies around the `aligned_axis`.
+    fn angular_inverse_mass(
+        &self,
+        body1: &RigidBodyQueryItem,
+        body2: &RigidBodyQueryItem,
+    ) -> Scalar {
+        let axis = self.world_aligned_axis(body1);
+        AngularConstraint::compute_generalized_inverse_mass(self, body1, axis)
+            + AngularConstraint::compute_generalized_inverse_mass(self, body2, axis)
+    }
+
     /// Applies angle limits to limit the relative rotation of the bodies around the `aligned_axis`.
-    #[allow(clippy::too_many_arguments)]
     fn apply_angle_limits(
         &mut self,
         body1: &mut RigidBodyQueryItem,
         body2: &mut RigidBodyQueryItem,
         dt: Scalar,
     ) -> Torque {
-        let Some(Some(correction)) = self.angle_limit.map(|angle_limit| {
-            #[cfg(feature = "2d")]
-            {
-                angle_limit.compute_correction(*body1.rotation, *body2.rotation, PI)
-            }
-            #[cfg(feature = "3d")]
-            {
-                // [n, n1, n2] = [a1, b1, b2], where [a, b, c] are perpendicular unit axes on the bodies.
-                let a1 = *body1.rotation * self.aligned_axis;
-                let b1 = *body1.rotation * self.aligned_axis.any_orthonormal_vector();
-                let b2 = *body2.rotation * self.aligned_axis.any_orthonormal_vector();
-                angle_limit.compute_correction(a1, b1, b2, PI)
-            }
-        }) else {
+        let Some(Some(correction)) = self
+            .angle_limit
+            .map(|angle_limit| self.compute_angle_correction(angle_limit, body1, body2))
+        else {
             return Torque::ZERO;
         };
 
         let mut lagrange = self.angle_limit_lagrange;
-        let torque =
-            self.align_orientation(body1, body2, correction, &mut lagrange, self.compliance, dt);
+        let torque = self.align_orientation(
+            body1,
+            body2,
+            correction,
+            &mut lagrange,
+            self.compliance + self.limit_response.compliance,
+            dt,
+        );
         self.angle_limit_lagrange = lagrange;
         torque
     }
+
+    /// Applies restitution for the angle limits if they were hit during the substep.
+    pub(crate) fn apply_limit_restitution(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+    ) {
+        self.limit_response
+            .apply_angular_restitution(body1, body2, self.angle_limit_torque);
+    }
+
+    /// Applies the angle spring to pull the relative rotation of the bodies around the `aligned_axis`
+    /// towards the `rest_angle`.
+    fn apply_angle_spring(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let Some(spring) = self.angle_spring else {
+            return Torque::ZERO;
+        };
+        let Some(spring_compliance) = spring.compliance(self.angular_inverse_mass(body1, body2))
+        else {
+            return Torque::ZERO;
+        };
+        let rest = AngleLimit::new(self.rest_angle, self.rest_angle);
+        let Some(correction) = self.compute_angle_correction(rest, body1, body2) else {
+            return Torque::ZERO;
+        };
+
+        let mut lagrange = self.angle_spring_lagrange;
+        let torque = self.align_orientation(
+            body1,
+            body2,
+            correction,
+            &mut lagrange,
+            self.compliance + spring_compliance,
+            dt,
+        );
+        self.angle_spring_lagrange = lagrange;
+        torque
+    }
+
+    /// Damps the relative angular velocity of the bodies around the `aligned_axis`
+    /// based on the damping of the angle spring.
+    pub(crate) fn apply_spring_damping(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) {
+        let Some(spring) = self.angle_spring else {
+            return;
+        };
+
+        let axis = self.world_aligned_axis(body1);
+        #[cfg(feature = "2d")]
+        let relative_velocity = body2.angular_velocity.0 - body1.angular_velocity.0;
+        #[cfg(feature = "3d")]
+        let relative_velocity = (body2.angular_velocity.0 - body1.angular_velocity.0).dot(axis);
+
+        let impulse = spring.damping_impulse(
+            relative_velocity,
+            self.angular_inverse_mass(body1, body2),
+            dt,
+        );
+        #[cfg(feature = "2d")]
+        let angular_impulse = impulse;
+        #[cfg(feature = "3d")]
+        let angular_impulse = axis * impulse;
+
+        if body1.rb.is_dynamic() {
+            body1.angular_velocity.0 -=
+                body1.effective_global_angular_inertia().inverse() * angular_impulse;
+        }
+        if body2.rb.is_dynamic() {
+            body2.angular_velocity.0 +=
+                body2.effective_global_angular_inertia().inverse() * angular_impulse;
+        }
+    }
 }
 
 impl PositionConstraint for RevoluteJoint {}
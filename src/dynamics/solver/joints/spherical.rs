@@ -33,6 +33,8 @@ pub struct SphericalJoint {
     pub swing_limit: Option<AngleLimit>,
     /// The extents of the allowed relative rotation of the bodies around the `twist_axis`.
     pub twist_limit: Option<AngleLimit>,
+    /// Configures the softness and bounciness of the swing and twist limits.
+    pub limit_response: JointLimitResponse,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -100,6 +102,7 @@ impl Joint for SphericalJoint {
             twist_axis: Vector3::Y,
             swing_limit: None,
             twist_limit: None,
+            limit_response: JointLimitResponse::RIGID,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
@@ -185,6 +188,34 @@ impl SphericalJoint {
         }
     }
 
+    /// Limits the angle between the `swing_axis` of the first body and the `swing_axis`
+    /// of the second body to `max_angle`, forming a cone that the second body can swing within.
+    ///
+    /// This is useful for joints like shoulders and hips in ragdolls.
+    pub fn with_cone_limit(self, max_angle: Scalar) -> Self {
+        self.with_swing_limits(-max_angle, max_angle)
+    }
+
+    /// Sets the softness and bounciness of the swing and twist limits.
+    pub fn with_limit_response(self, limit_response: JointLimitResponse) -> Self {
+        Self {
+            limit_response,
+            ..self
+        }
+    }
+
+    /// Applies restitution for the swing and twist limits that were hit during the substep.
+    pub(crate) fn apply_limit_restitution(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+    ) {
+        self.limit_response
+            .apply_angular_restitution(body1, body2, self.swing_torque);
+        self.limit_response
+            .apply_angular_restitution(body1, body2, self.twist_torque);
+    }
+
     /// Applies angle limits to limit the relative rotation of the bodies around the `swing_axis`.
     fn apply_swing_limits(
         &mut self,
@@ -212,7 +243,7 @@ impl SphericalJoint {
                     body2,
                     correction,
                     &mut lagrange,
-                    self.compliance,
+                    self.compliance + self.limit_response.compliance,
                     dt,
                 );
                 self.swing_lagrange = lagrange;
@@ -266,7 +297,7 @@ impl SphericalJoint {
                     body2,
                     correction,
                     &mut lagrange,
-                    self.compliance,
+                    self.compliance + self.limit_response.compliance,
                     dt,
                 );
                 self.twist_lagrange = lagrange;
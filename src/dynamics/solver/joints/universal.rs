@@ -0,0 +1,346 @@
+//! [`UniversalJoint`] component.
+
+use crate::{dynamics::solver::xpbd::*, prelude::*};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// A universal joint prevents relative translation of the attached bodies, and only allows rotation
+/// around two perpendicular hinge axes: `axis1` on the first body and `axis2` on the second body.
+///
+/// This is also known as a Cardan joint or a U-joint. Universal joints can be useful for things like
+/// drive shafts, swinging lamps and gimbals.
+///
+/// The axes are given in the local space of the bodies, and the joint assumes that they are perpendicular
+/// when the bodies have the same rotation. The rotation around each axis can be limited with
+/// [`with_axis1_limits`](Self::with_axis1_limits) and [`with_axis2_limits`](Self::with_axis2_limits),
+/// and driven with an [`AngularMotor`].
+///
+/// # Example
+///
+/// ```
+/// use avian3d::prelude::*;
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let shaft1 = commands.spawn(RigidBody::Dynamic).id();
+///     let shaft2 = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Connect the shafts so that the first one drives the second one
+///     // at a constant angular velocity around its own axis.
+///     commands.spawn(
+///         UniversalJoint::new(shaft1, shaft2)
+///             .with_local_anchor_1(Vector::X * 0.5)
+///             .with_local_anchor_2(Vector::NEG_X * 0.5)
+///             .with_axis2_limits(-0.5, 0.5)
+///             .with_axis1_motor(AngularMotor::new(5.0, 100.0)),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, MapEntities, PartialEq)]
+pub struct UniversalJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// Attachment point on the first body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The hinge axis attached to the first body. This is normally the x-axis.
+    pub axis1: Vector3,
+    /// The hinge axis attached to the second body. This is normally the y-axis.
+    pub axis2: Vector3,
+    /// The extents of the allowed relative rotation of the bodies around `axis1`.
+    pub axis1_limit: Option<AngleLimit>,
+    /// The extents of the allowed relative rotation of the bodies around `axis2`.
+    pub axis2_limit: Option<AngleLimit>,
+    /// A motor that drives the relative rotation of the bodies around `axis1`.
+    pub axis1_motor: Option<AngularMotor>,
+    /// A motor that drives the relative rotation of the bodies around `axis2`.
+    pub axis2_motor: Option<AngularMotor>,
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the positional correction.
+    pub position_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction that keeps the axes perpendicular.
+    pub align_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction caused by the limits around `axis1`.
+    pub axis1_limit_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction caused by the limits around `axis2`.
+    pub axis2_limit_lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the joint.
+    pub force: Vector,
+    /// The torque exerted by the joint when keeping the axes perpendicular.
+    pub align_torque: Torque,
+    /// The torque exerted by the joint when limiting the relative rotation of the bodies around `axis1`.
+    pub axis1_limit_torque: Torque,
+    /// The torque exerted by the joint when limiting the relative rotation of the bodies around `axis2`.
+    pub axis2_limit_torque: Torque,
+}
+
+impl XpbdConstraint<2> for UniversalJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.position_lagrange = 0.0;
+        self.align_lagrange = 0.0;
+        self.axis1_limit_lagrange = 0.0;
+        self.axis2_limit_lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+        let compliance = self.compliance;
+
+        // Keep the hinge axes perpendicular, only allowing rotation around the two axes
+        let difference = self.get_rotation_difference(&body1.rotation, &body2.rotation);
+        let mut lagrange = self.align_lagrange;
+        self.align_torque =
+            self.align_orientation(body1, body2, difference, &mut lagrange, compliance, dt);
+        self.align_lagrange = lagrange;
+
+        // Apply angle limits when rotating around the hinge axes
+        self.axis1_limit_torque = self.apply_axis1_limits(body1, body2, dt);
+        self.axis2_limit_torque = self.apply_axis2_limits(body1, body2, dt);
+
+        // Align positions
+        let mut lagrange = self.position_lagrange;
+        self.force = self.align_position(
+            body1,
+            body2,
+            self.local_anchor1,
+            self.local_anchor2,
+            &mut lagrange,
+            compliance,
+            dt,
+        );
+        self.position_lagrange = lagrange;
+    }
+}
+
+impl Joint for UniversalJoint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            axis1: Vector3::X,
+            axis2: Vector3::Y,
+            axis1_limit: None,
+            axis2_limit: None,
+            axis1_motor: None,
+            axis2_motor: None,
+            damping_linear: 1.0,
+            damping_angular: 1.0,
+            position_lagrange: 0.0,
+            align_lagrange: 0.0,
+            axis1_limit_lagrange: 0.0,
+            axis2_limit_lagrange: 0.0,
+            compliance: 0.0,
+            force: Vector::ZERO,
+            align_torque: Vector::ZERO,
+            axis1_limit_torque: Vector::ZERO,
+            axis2_limit_torque: Vector::ZERO,
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+}
+
+impl UniversalJoint {
+    /// Sets the hinge axes attached to the first and second body.
+    ///
+    /// The axes should be perpendicular unit vectors.
+    pub fn with_axes(self, axis1: Vector, axis2: Vector) -> Self {
+        Self {
+            axis1,
+            axis2,
+            ..self
+        }
+    }
+
+    /// Sets the limits of the allowed relative rotation around `axis1`.
+    pub fn with_axis1_limits(self, min: Scalar, max: Scalar) -> Self {
+        Self {
+            axis1_limit: Some(AngleLimit::new(min, max)),
+            ..self
+        }
+    }
+
+    /// Sets the limits of the allowed relative rotation around `axis2`.
+    pub fn with_axis2_limits(self, min: Scalar, max: Scalar) -> Self {
+        Self {
+            axis2_limit: Some(AngleLimit::new(min, max)),
+            ..self
+        }
+    }
+
+    /// Sets the motor that drives the relative rotation around `axis1`.
+    pub fn with_axis1_motor(self, motor: AngularMotor) -> Self {
+        Self {
+            axis1_motor: Some(motor),
+            ..self
+        }
+    }
+
+    /// Sets the motor that drives the relative rotation around `axis2`.
+    pub fn with_axis2_motor(self, motor: AngularMotor) -> Self {
+        Self {
+            axis2_motor: Some(motor),
+            ..self
+        }
+    }
+
+    /// Returns the rotation that makes the hinge axes perpendicular again.
+    fn get_rotation_difference(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
+        let a1 = rot1 * self.axis1;
+        let a2 = rot2 * self.axis2;
+
+        // Rotate the axes around their common normal until they are perpendicular.
+        let n = a1.cross(a2);
+        let n_magnitude = n.length();
+
+        if n_magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        -n / n_magnitude * a1.dot(a2).clamp(-1.0, 1.0).asin()
+    }
+
+    /// Applies angle limits to limit the relative rotation of the bodies around `axis1`.
+    fn apply_axis1_limits(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let Some(Some(correction)) = self.axis1_limit.map(|angle_limit| {
+            // The rotation around axis1 is measured from the rest direction of axis2.
+            let a1 = *body1.rotation * self.axis1;
+            let b1 = *body1.rotation * self.axis2;
+            let b2 = *body2.rotation * self.axis2;
+            angle_limit.compute_correction(a1, b1, b2, PI)
+        }) else {
+            return Torque::ZERO;
+        };
+
+        let mut lagrange = self.axis1_limit_lagrange;
+        let torque =
+            self.align_orientation(body1, body2, correction, &mut lagrange, self.compliance, dt);
+        self.axis1_limit_lagrange = lagrange;
+        torque
+    }
+
+    /// Applies angle limits to limit the relative rotation of the bodies around `axis2`.
+    fn apply_axis2_limits(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let Some(Some(correction)) = self.axis2_limit.map(|angle_limit| {
+            // The rotation around axis2 is measured from the rest direction of axis1.
+            let a2 = *body2.rotation * self.axis2;
+            let b1 = *body1.rotation * self.axis1;
+            let b2 = *body2.rotation * self.axis1;
+            angle_limit.compute_correction(a2, b1, b2, PI)
+        }) else {
+            return Torque::ZERO;
+        };
+
+        let mut lagrange = self.axis2_limit_lagrange;
+        let torque =
+            self.align_orientation(body1, body2, correction, &mut lagrange, self.compliance, dt);
+        self.axis2_limit_lagrange = lagrange;
+        torque
+    }
+
+    /// Applies the velocity changes caused by the motors of the joint.
+    pub(crate) fn apply_motors(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) {
+        if let Some(motor) = self.axis1_motor {
+            let axis = *body1.rotation * self.axis1;
+            motor.apply(body1, body2, axis, dt);
+        }
+        if let Some(motor) = self.axis2_motor {
+            let axis = *body2.rotation * self.axis2;
+            motor.apply(body1, body2, axis, dt);
+        }
+    }
+}
+
+impl PositionConstraint for UniversalJoint {}
+
+impl AngularConstraint for UniversalJoint {}
+
+impl MapEntities for UniversalJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
@@ -10,10 +10,14 @@ pub mod xpbd;
 
 use crate::prelude::*;
 use bevy::prelude::*;
+#[cfg(feature = "parallel")]
+use bevy::tasks::ComputeTaskPool;
 use schedule::SubstepSolverSet;
+#[cfg(feature = "parallel")]
+use std::ops::Range;
 
 use self::{
-    contact::ContactConstraint,
+    contact::{ContactBody, ContactConstraint, ContactConstraintBatches},
     softness_parameters::{SoftnessCoefficients, SoftnessParameters},
 };
 
@@ -26,6 +30,11 @@ use self::{
 /// The solver primarily uses TGS Soft, an impulse-based solver with substepping and [soft constraints](softness_parameters).
 /// Warm starting is used to improve convergence, along with a relaxation pass to reduce overshooting.
 ///
+/// With the `parallel` feature, contacts belonging to different [simulation islands](super::islands)
+/// are solved in parallel. This requires the [`IslandPlugin`]. The contacts of very large islands
+/// can also be solved in parallel using [graph coloring](SolverConfig::graph_coloring_threshold).
+/// Alternatively, contacts can be solved several at a time in [batches](SolverConfig::contact_batches).
+///
 /// [Speculative collision](dynamics::ccd#speculative-collision) is used by default to prevent tunneling.
 /// Optional [sweep-based Continuous Collision Detection (CCD)](dynamics::ccd#swept-ccd) is handled by the [`CcdPlugin`].
 ///
@@ -73,7 +82,8 @@ impl Plugin for SolverPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SolverConfig>()
             .init_resource::<ContactSoftnessCoefficients>()
-            .init_resource::<ContactConstraints>();
+            .init_resource::<ContactConstraints>()
+            .init_resource::<ContactConstraintBatches>();
 
         if app
             .world()
@@ -90,6 +100,24 @@ impl Plugin for SolverPlugin {
 
         physics.add_systems(update_contact_softness.before(PhysicsStepSet::NarrowPhase));
 
+        // Wake up bodies attached to joints that are disabled or re-enabled.
+        physics.add_systems(
+            (
+                joints::wake_on_joint_toggled::<FixedJoint, 2>,
+                joints::wake_on_joint_toggled::<RevoluteJoint, 2>,
+                #[cfg(feature = "3d")]
+                joints::wake_on_joint_toggled::<SphericalJoint, 2>,
+                #[cfg(feature = "3d")]
+                joints::wake_on_joint_toggled::<UniversalJoint, 2>,
+                joints::wake_on_joint_toggled::<PrismaticJoint, 2>,
+                joints::wake_on_joint_toggled::<DistanceJoint, 2>,
+                joints::wake_on_joint_toggled::<MirrorConstraint, 2>,
+                joints::wake_on_joint_toggled::<GearJoint, 2>,
+                joints::wake_on_joint_toggled::<RackAndPinionJoint, 2>,
+            )
+                .in_set(PhysicsStepSet::First),
+        );
+
         // Update previous rotations before the substepping loop.
         physics.add_systems(
             (|mut query: Query<(&Rotation, &mut PreviousRotation)>| {
@@ -108,12 +136,30 @@ impl Plugin for SolverPlugin {
                 .in_set(SolverSet::ApplyTranslation),
         );
 
+        // Build the contact batches after the constraints have been generated and sorted by island.
+        physics.add_systems(
+            build_contact_batches
+                .in_set(SolverSet::PreSubstep)
+                .after(super::islands::update_islands)
+                .run_if(contact_batches_enabled),
+        );
+
+        // Write the impulses of the contact batches back to the contact constraints.
+        physics.add_systems(
+            write_back_contact_batches
+                .in_set(SolverSet::PostSubstep)
+                .run_if(contact_batches_enabled),
+        );
+
         // Apply restitution.
         physics.add_systems(solve_restitution.in_set(SolverSet::Restitution));
 
         // Store the current contact impulses for the next frame's warm starting.
         physics.add_systems(store_contact_impulses.in_set(SolverSet::StoreContactImpulses));
 
+        // Count down the steps for bodies that skip warm starting.
+        physics.add_systems(count_down_skip_warm_start.in_set(SolverSet::StoreContactImpulses));
+
         // Get the `SubstepSchedule`, and panic if it doesn't exist.
         let substeps = app
             .get_schedule_mut(SubstepSchedule)
@@ -122,14 +168,32 @@ impl Plugin for SolverPlugin {
         // Warm start the impulses.
         // This applies the impulses stored from the previous substep,
         // which improves convergence.
-        substeps.add_systems(warm_start.in_set(SubstepSolverSet::WarmStart));
+        substeps.add_systems(
+            (
+                warm_start.run_if(not(contact_batches_enabled)),
+                warm_start_contact_batches.run_if(contact_batches_enabled),
+            )
+                .in_set(SubstepSolverSet::WarmStart),
+        );
 
         // Solve velocities using a position bias.
-        substeps.add_systems(solve_contacts::<true>.in_set(SubstepSolverSet::SolveConstraints));
+        substeps.add_systems(
+            (
+                solve_contacts::<true>.run_if(not(contact_batches_enabled)),
+                solve_contact_batches::<true>.run_if(contact_batches_enabled),
+            )
+                .in_set(SubstepSolverSet::SolveConstraints),
+        );
 
         // Relax biased velocities and impulses.
         // This reduces overshooting caused by warm starting.
-        substeps.add_systems(solve_contacts::<false>.in_set(SubstepSolverSet::Relax));
+        substeps.add_systems(
+            (
+                solve_contacts::<false>.run_if(not(contact_batches_enabled)),
+                solve_contact_batches::<false>.run_if(contact_batches_enabled),
+            )
+                .in_set(SubstepSolverSet::Relax),
+        );
 
         // Solve joints with XPBD.
         substeps.add_systems(
@@ -154,8 +218,13 @@ impl Plugin for SolverPlugin {
                 xpbd::solve_constraint::<RevoluteJoint, 2>,
                 #[cfg(feature = "3d")]
                 xpbd::solve_constraint::<SphericalJoint, 2>,
+                #[cfg(feature = "3d")]
+                xpbd::solve_constraint::<UniversalJoint, 2>,
                 xpbd::solve_constraint::<PrismaticJoint, 2>,
                 xpbd::solve_constraint::<DistanceJoint, 2>,
+                xpbd::solve_constraint::<MirrorConstraint, 2>,
+                xpbd::solve_constraint::<GearJoint, 2>,
+                xpbd::solve_constraint::<RackAndPinionJoint, 2>,
             )
                 .chain()
                 .in_set(SubstepSolverSet::SolveXpbdConstraints),
@@ -170,8 +239,15 @@ impl Plugin for SolverPlugin {
                 joint_damping::<RevoluteJoint>,
                 #[cfg(feature = "3d")]
                 joint_damping::<SphericalJoint>,
+                #[cfg(feature = "3d")]
+                joint_damping::<UniversalJoint>,
                 joint_damping::<PrismaticJoint>,
                 joint_damping::<DistanceJoint>,
+                mirror_damping,
+                joint_spring_damping,
+                #[cfg(feature = "3d")]
+                universal_joint_motors,
+                joint_limit_restitution,
             )
                 .chain()
                 .in_set(SubstepSolverSet::XpbdVelocityProjection),
@@ -188,9 +264,18 @@ impl Plugin for SolverPlugin {
 /// will interpret 100 pixels as 1 meter for internal thresholds, improving stability.
 ///
 /// Note that this is *not* used to scale forces or any other user-facing inputs or outputs.
-/// Instead, the value is only used to scale some internal length-based tolerances, such as
-/// [`SleepingThreshold::linear`] and [`NarrowPhaseConfig::default_speculative_margin`],
-/// as well as the scale used for [debug rendering](PhysicsDebugPlugin).
+/// Instead, the value is only used to scale internal length-based tolerances, so that they don't
+/// need to be tuned by hand for worlds that use centimeters, pixels, or other large or small units.
+///
+/// The following properties are scaled by the length unit:
+///
+/// - Speculative margins and contact tolerances: [`NarrowPhaseConfig::default_speculative_margin`]
+///   and [`NarrowPhaseConfig::contact_tolerance`]
+/// - Overlap resolution: [`SolverConfig::max_overlap_solve_speed`]
+/// - Restitution: [`SolverConfig::restitution_threshold`]
+/// - Sleeping: [`SleepingThreshold::linear`] and [`SleepThreshold::linear`]
+/// - Broad phase: [`BroadPhaseConfig::bvh_aabb_margin`]
+/// - The scale used for [debug rendering](PhysicsDebugPlugin)
 ///
 /// Choosing the appropriate length unit can help improve stability and robustness.
 ///
@@ -246,6 +331,8 @@ pub struct SolverConfig {
     /// Consider using a higher damping ratio if contacts seem too soft.
     /// Note that making the value too large can cause instability.
     ///
+    /// Only used by [`ContactStiffness::Automatic`].
+    ///
     /// Default: `10.0`.
     pub contact_damping_ratio: Scalar,
 
@@ -259,19 +346,44 @@ pub struct SolverConfig {
     /// This factor scales the resulting frequency, which can lead to unstable behavior
     /// if the factor is too large.
     ///
+    /// Only used by [`ContactStiffness::Automatic`].
+    ///
     /// Default: `1.5`
     pub contact_frequency_factor: Scalar,
 
+    /// Determines how the stiffness of contacts is computed.
+    ///
+    /// Default: [`ContactStiffness::Automatic`]
+    pub contact_stiffness: ContactStiffness,
+
     /// The maximum speed at which overlapping bodies are pushed apart by the solver.
     ///
     /// With a small value, overlap is resolved gently and gradually, while large values
     /// can result in more snappy behavior.
     ///
     /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    /// It can be overridden for individual bodies with the [`MaxOverlapSolveSpeed`] component.
     ///
     /// Default: `4.0`
     pub max_overlap_solve_speed: Scalar,
 
+    /// If `true`, contacts are [warm started](SubstepSolverSet::WarmStart) using the impulses
+    /// from the previous frame as the initial guess.
+    ///
+    /// When disabled, the impulses from the previous frame are discarded in the narrow phase,
+    /// but the impulses accumulated within a frame are still carried over between substeps.
+    ///
+    /// Warm starting significantly improves the stability of stacks and resting contacts,
+    /// especially at low substep counts. Disabling it makes contacts more responsive to sudden changes,
+    /// for example when a body is teleported or its velocity is reset, at the cost of stability.
+    /// The amount of warm starting can also be scaled with the [`warm_start_coefficient`](Self::warm_start_coefficient).
+    ///
+    /// The contact impulses of colliders that stop touching can be cached for a few frames
+    /// with [`NarrowPhaseConfig::impulse_cache_frames`].
+    ///
+    /// Default: `true`
+    pub warm_starting: bool,
+
     /// The coefficient in the `[0, 1]` range applied to
     /// [warm start](SubstepSolverSet::WarmStart) impulses.
     ///
@@ -309,6 +421,47 @@ pub struct SolverConfig {
     ///
     /// Default: `1`
     pub restitution_iterations: usize,
+
+    /// The model used for computing bounces caused by [restitution](Restitution).
+    ///
+    /// Default: [`RestitutionModel::Newton`]
+    pub restitution_model: RestitutionModel,
+
+    /// The minimum number of contact constraints in a [simulation island](super::islands)
+    /// for the constraints to be split into *colors* using graph coloring, or `None` to disable graph coloring.
+    ///
+    /// The constraints in a color share no dynamic bodies, so with the `parallel` feature,
+    /// they can be solved in parallel even within a single island. This can significantly speed up
+    /// scenes with very large islands, such as piles of thousands of bodies, where solving
+    /// whole islands in parallel doesn't help.
+    ///
+    /// Coloring changes the order in which the constraints of an island are solved,
+    /// which can slightly change the results of the simulation. The results are still deterministic,
+    /// and don't depend on the number of threads.
+    ///
+    /// Graph coloring requires the [`IslandPlugin`].
+    ///
+    /// Default: `None`
+    pub graph_coloring_threshold: Option<usize>,
+
+    /// If `true`, contacts are solved in batches of several contact points at once.
+    ///
+    /// Before the substepping loop, the contact points are copied into batches stored in
+    /// a structure-of-arrays layout, such that no two points in a batch share a dynamic body.
+    /// The points of each batch are then solved using SIMD instructions, 8 points at a time with `f32`
+    /// and 4 points at a time with `f64`. The speedup depends on the SIMD instruction sets
+    /// enabled for the target, for example AVX on x86-64.
+    ///
+    /// With the `parallel` feature, the batches of different [simulation islands](super::islands)
+    /// are solved in parallel, like with the default solver. This requires the [`IslandPlugin`].
+    /// The batches of a single island are solved on one thread, so [graph coloring](Self::graph_coloring_threshold)
+    /// is not used.
+    ///
+    /// Because the contact points are solved in a different order, the results can differ
+    /// slightly from the default solver.
+    ///
+    /// Default: `false`
+    pub contact_batches: bool,
 }
 
 impl Default for SolverConfig {
@@ -316,14 +469,92 @@ impl Default for SolverConfig {
         Self {
             contact_damping_ratio: 10.0,
             contact_frequency_factor: 1.5,
+            contact_stiffness: ContactStiffness::default(),
             max_overlap_solve_speed: 4.0,
+            warm_starting: true,
             warm_start_coefficient: 1.0,
             restitution_threshold: 1.0,
             restitution_iterations: 1,
+            restitution_model: RestitutionModel::default(),
+            graph_coloring_threshold: None,
+            contact_batches: false,
         }
     }
 }
 
+/// How the stiffness of contacts is computed. See [`SolverConfig::contact_stiffness`].
+///
+/// Contacts are always solved with the same substepped solver using [soft constraints](softness_parameters).
+/// This only determines how the frequency and damping ratio of the soft contact constraints are chosen.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum ContactStiffness {
+    /// The stiffness of contacts is derived from the time step and substep count,
+    /// and tuned with [`SolverConfig::contact_damping_ratio`] and [`SolverConfig::contact_frequency_factor`].
+    ///
+    /// This makes contacts as stiff as the time step allows, which works well for most applications.
+    #[default]
+    Automatic,
+    /// The stiffness of contacts is given explicitly as a frequency in hertz and a damping ratio,
+    /// independent of the time step, similar to Box2D.
+    ///
+    /// Softer contacts with a fixed frequency are more robust for heavy stacks
+    /// and large mass ratios, such as heavy containers stacked on top of light ones,
+    /// where very stiff contacts are prone to jitter and explosive corrections.
+    ///
+    /// The frequencies are limited to a quarter of the substep rate for stability.
+    Fixed {
+        /// The stiffness of contacts between dynamic bodies in hertz.
+        hertz: Scalar,
+        /// The damping ratio of contacts.
+        damping_ratio: Scalar,
+        /// The stiffness of contacts against static and kinematic bodies in hertz.
+        /// This is typically higher than `hertz` to avoid clipping through the environment.
+        static_hertz: Scalar,
+    },
+}
+
+impl ContactStiffness {
+    /// Creates a [`ContactStiffness::Fixed`] with the given frequency in hertz and damping ratio.
+    /// The frequency of contacts against static and kinematic bodies is twice the given frequency.
+    pub fn fixed(hertz: Scalar, damping_ratio: Scalar) -> Self {
+        Self::Fixed {
+            hertz,
+            damping_ratio,
+            static_hertz: 2.0 * hertz,
+        }
+    }
+}
+
+/// The model used for computing bounces caused by [restitution](Restitution).
+/// See [`SolverConfig::restitution_model`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum RestitutionModel {
+    /// Newton's hypothesis: the separating velocity at each contact point is the approach velocity
+    /// before the time step, scaled by the coefficient of restitution.
+    ///
+    /// This gives accurate bounces for single impacts, but when several bodies collide
+    /// in the same time step, like a cue ball hitting a tight cluster of billiard balls,
+    /// contacts that were not approaching before the step don't bounce at all,
+    /// and energy is lost.
+    #[default]
+    Newton,
+    /// Poisson's hypothesis: the impulse applied at each contact point during restitution
+    /// is the impulse that was needed to stop the bodies from approaching each other,
+    /// scaled by the coefficient of restitution.
+    ///
+    /// With a coefficient of `1.0`, this conserves energy even when several bodies collide
+    /// in the same time step, which makes it a good fit for things like billiards,
+    /// pachinko and Newton's cradles. [`SolverConfig::restitution_iterations`] is ignored,
+    /// as the restitution impulses are applied only once.
+    Poisson,
+}
+
 /// The [`SoftnessCoefficients`] used for contacts.
 ///
 /// **Note**: This resource is updated automatically and not intended to be modified manually.
@@ -350,25 +581,49 @@ fn update_contact_softness(
     mut coefficients: ResMut<ContactSoftnessCoefficients>,
     solver_config: Res<SolverConfig>,
     physics_time: Res<Time<Physics>>,
-    substep_time: Res<Time<Substeps>>,
+    substep_count: Res<SubstepCount>,
 ) {
-    if solver_config.is_changed() || physics_time.is_changed() || substep_time.is_changed() {
+    if solver_config.is_changed() || physics_time.is_changed() || substep_count.is_changed() {
         let dt = physics_time.delta_secs_f64() as Scalar;
-        let h = substep_time.delta_secs_f64() as Scalar;
+        // Compute the substep length from the current substep count like the substepping loop does.
+        // `Time<Substeps>` is only advanced in the loop, so it can still have the length
+        // from the previous step if the substep count was changed in between.
+        let h = physics_time
+            .delta()
+            .div_f64(substep_count.0 as f64)
+            .as_secs_f64() as Scalar;
 
-        // The contact frequency should at most be half of the time step due to Nyquist's theorem.
-        // https://en.wikipedia.org/wiki/Nyquist%E2%80%93Shannon_sampling_theorem
-        let max_hz = 1.0 / (dt * 2.0);
-        let hz = solver_config.contact_frequency_factor * max_hz.min(0.25 / h);
+        match solver_config.contact_stiffness {
+            ContactStiffness::Automatic => {
+                // The contact frequency should at most be half of the time step due to Nyquist's theorem.
+                // https://en.wikipedia.org/wiki/Nyquist%E2%80%93Shannon_sampling_theorem
+                let max_hz = 1.0 / (dt * 2.0);
+                let hz = solver_config.contact_frequency_factor * max_hz.min(0.25 / h);
 
-        coefficients.dynamic = SoftnessParameters::new(solver_config.contact_damping_ratio, hz)
-            .compute_coefficients(h);
+                coefficients.dynamic =
+                    SoftnessParameters::new(solver_config.contact_damping_ratio, hz)
+                        .compute_coefficients(h);
 
-        // TODO: Perhaps the non-dynamic softness should be configurable separately.
-        // Make contacts against static and kinematic bodies stiffer to avoid clipping through the environment.
-        coefficients.non_dynamic =
-            SoftnessParameters::new(solver_config.contact_damping_ratio, 2.0 * hz)
-                .compute_coefficients(h);
+                // Make contacts against static and kinematic bodies stiffer to avoid clipping through the environment.
+                coefficients.non_dynamic =
+                    SoftnessParameters::new(solver_config.contact_damping_ratio, 2.0 * hz)
+                        .compute_coefficients(h);
+            }
+            ContactStiffness::Fixed {
+                hertz,
+                damping_ratio,
+                static_hertz,
+            } => {
+                // Limit the frequencies to a quarter of the substep rate for stability.
+                let max_hz = 0.25 / h;
+
+                coefficients.dynamic = SoftnessParameters::new(damping_ratio, hertz.min(max_hz))
+                    .compute_coefficients(h);
+                coefficients.non_dynamic =
+                    SoftnessParameters::new(damping_ratio, static_hertz.min(max_hz))
+                        .compute_coefficients(h);
+            }
+        }
     }
 }
 
@@ -382,29 +637,31 @@ pub struct ContactConstraints(pub Vec<ContactConstraint>);
 fn warm_start(
     mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
     mut constraints: ResMut<ContactConstraints>,
+    islands: Option<Res<PhysicsIslands>>,
     solver_config: Res<SolverConfig>,
 ) {
-    for constraint in constraints.iter_mut() {
-        debug_assert!(!constraint.points.is_empty());
+    let warm_start_coefficient = solver_config.warm_start_coefficient;
 
-        let Ok([mut body1, mut body2]) =
-            bodies.get_many_mut([constraint.entity1, constraint.entity2])
-        else {
-            continue;
-        };
+    for_each_contact_constraint(
+        &mut bodies,
+        &mut constraints,
+        islands.as_deref(),
+        |constraint, body1, body2| {
+            debug_assert!(!constraint.points.is_empty());
 
-        let normal = constraint.normal;
-        let tangent_directions =
-            constraint.tangent_directions(body1.linear_velocity.0, body2.linear_velocity.0);
+            let normal = constraint.normal;
+            let tangent_directions =
+                constraint.tangent_directions(body1.linear_velocity(), body2.linear_velocity());
 
-        constraint.warm_start(
-            &mut body1,
-            &mut body2,
-            normal,
-            tangent_directions,
-            solver_config.warm_start_coefficient,
-        );
-    }
+            constraint.warm_start(
+                body1,
+                body2,
+                normal,
+                tangent_directions,
+                warm_start_coefficient,
+            );
+        },
+    );
 }
 
 /// Solves contacts by iterating through the given contact constraints
@@ -424,6 +681,7 @@ fn warm_start(
 fn solve_contacts<const USE_BIAS: bool>(
     mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
     mut constraints: ResMut<ContactConstraints>,
+    islands: Option<Res<PhysicsIslands>>,
     solver_config: Res<SolverConfig>,
     length_unit: Res<PhysicsLengthUnit>,
     time: Res<Time>,
@@ -431,21 +689,14 @@ fn solve_contacts<const USE_BIAS: bool>(
     let delta_secs = time.delta_seconds_adjusted();
     let max_overlap_solve_speed = solver_config.max_overlap_solve_speed * length_unit.0;
 
-    for constraint in &mut constraints.0 {
-        let Ok([mut body1, mut body2]) =
-            bodies.get_many_mut([constraint.entity1, constraint.entity2])
-        else {
-            continue;
-        };
-
-        constraint.solve(
-            &mut body1,
-            &mut body2,
-            delta_secs,
-            USE_BIAS,
-            max_overlap_solve_speed,
-        );
-    }
+    for_each_contact_constraint(
+        &mut bodies,
+        &mut constraints,
+        islands.as_deref(),
+        |constraint, body1, body2| {
+            constraint.solve(body1, body2, delta_secs, USE_BIAS, max_overlap_solve_speed);
+        },
+    );
 }
 
 /// Iterates through contact constraints and applies impulses to account for [`Restitution`].
@@ -455,44 +706,436 @@ fn solve_contacts<const USE_BIAS: bool>(
 ///
 /// The number of iterations can be increased with [`SolverConfig::restitution_iterations`]
 /// to apply restitution for multiple contact points more evenly.
+///
+/// The bounces are computed based on the [`SolverConfig::restitution_model`].
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 fn solve_restitution(
     mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
     mut constraints: ResMut<ContactConstraints>,
+    islands: Option<Res<PhysicsIslands>>,
     solver_config: Res<SolverConfig>,
     length_unit: Res<PhysicsLengthUnit>,
 ) {
     // The restitution threshold determining the speed required for restitution to be applied.
     let threshold = solver_config.restitution_threshold * length_unit.0;
+    let restitution_iterations = solver_config.restitution_iterations;
+    let restitution_model = solver_config.restitution_model;
 
-    for constraint in constraints.iter_mut() {
-        let restitution = constraint.restitution.coefficient;
+    for_each_contact_constraint(
+        &mut bodies,
+        &mut constraints,
+        islands.as_deref(),
+        |constraint, body1, body2| {
+            let restitution = constraint.restitution.coefficient;
 
-        if restitution == 0.0 {
-            continue;
+            if restitution == 0.0 {
+                return;
+            }
+
+            if restitution_model == RestitutionModel::Poisson {
+                constraint.apply_poisson_restitution(body1, body2, threshold);
+                return;
+            }
+
+            // Performing multiple iterations can result in more accurate restitution,
+            // but only if there are more than one contact point.
+            let restitution_iterations = if constraint.points.len() > 1 {
+                restitution_iterations
+            } else {
+                1
+            };
+
+            for _ in 0..restitution_iterations {
+                constraint.apply_restitution(body1, body2, threshold);
+            }
+        },
+    );
+}
+
+/// A run condition that returns `true` if [`SolverConfig::contact_batches`] is enabled.
+fn contact_batches_enabled(solver_config: Res<SolverConfig>) -> bool {
+    solver_config.contact_batches
+}
+
+/// Builds the [`ContactConstraintBatches`] from the [`ContactConstraints`].
+fn build_contact_batches(
+    mut batches: ResMut<ContactConstraintBatches>,
+    constraints: Res<ContactConstraints>,
+    islands: Option<Res<PhysicsIslands>>,
+    bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+) {
+    batches.build(&constraints, islands.as_deref(), &bodies);
+}
+
+/// Writes the impulses of the [`ContactConstraintBatches`] back to the [`ContactConstraints`]
+/// for restitution and warm starting.
+fn write_back_contact_batches(
+    batches: Res<ContactConstraintBatches>,
+    mut constraints: ResMut<ContactConstraints>,
+) {
+    batches.write_back(&mut constraints);
+}
+
+/// Warm starts the solver by applying the impulses stored in the [`ContactConstraintBatches`].
+///
+/// See [`SubstepSolverSet::WarmStart`] for more information.
+fn warm_start_contact_batches(
+    mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    mut batches: ResMut<ContactConstraintBatches>,
+    solver_config: Res<SolverConfig>,
+) {
+    batches.warm_start(&mut bodies, solver_config.warm_start_coefficient);
+}
+
+/// Solves the contacts stored in the [`ContactConstraintBatches`] lane-wise,
+/// like [`solve_contacts`].
+fn solve_contact_batches<const USE_BIAS: bool>(
+    mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    mut batches: ResMut<ContactConstraintBatches>,
+    solver_config: Res<SolverConfig>,
+    length_unit: Res<PhysicsLengthUnit>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    let max_overlap_solve_speed = solver_config.max_overlap_solve_speed * length_unit.0;
+
+    batches.solve(&mut bodies, delta_secs, USE_BIAS, max_overlap_solve_speed);
+}
+
+/// A rigid body accessed by the contact solver.
+///
+/// Dynamic bodies are accessed mutably by the task solving their island.
+/// Other bodies can be shared by several islands, so they are only read.
+enum SolverBody<'w> {
+    Mutable(RigidBodyQueryItem<'w>),
+    ReadOnly(RigidBodyQueryReadOnlyItem<'w>),
+}
+
+impl ContactBody for SolverBody<'_> {
+    fn rigid_body(&self) -> RigidBody {
+        match self {
+            Self::Mutable(body) => ContactBody::rigid_body(body),
+            Self::ReadOnly(body) => ContactBody::rigid_body(body),
+        }
+    }
+
+    fn rotation(&self) -> &Rotation {
+        match self {
+            Self::Mutable(body) => ContactBody::rotation(body),
+            Self::ReadOnly(body) => ContactBody::rotation(body),
+        }
+    }
+
+    fn accumulated_translation(&self) -> Vector {
+        match self {
+            Self::Mutable(body) => ContactBody::accumulated_translation(body),
+            Self::ReadOnly(body) => ContactBody::accumulated_translation(body),
+        }
+    }
+
+    fn linear_velocity(&self) -> Vector {
+        match self {
+            Self::Mutable(body) => ContactBody::linear_velocity(body),
+            Self::ReadOnly(body) => ContactBody::linear_velocity(body),
+        }
+    }
+
+    fn velocity_at_point(&self, point: Vector) -> Vector {
+        match self {
+            Self::Mutable(body) => body.velocity_at_point(point),
+            Self::ReadOnly(body) => body.velocity_at_point(point),
+        }
+    }
+
+    fn effective_inverse_mass(&self) -> Vector {
+        match self {
+            Self::Mutable(body) => body.effective_inverse_mass(),
+            Self::ReadOnly(body) => body.effective_inverse_mass(),
+        }
+    }
+
+    fn effective_global_angular_inertia(&self) -> ComputedAngularInertia {
+        match self {
+            Self::Mutable(body) => body.effective_global_angular_inertia(),
+            Self::ReadOnly(body) => body.effective_global_angular_inertia(),
+        }
+    }
+
+    fn dominance(&self) -> i8 {
+        match self {
+            Self::Mutable(body) => body.dominance(),
+            Self::ReadOnly(body) => body.dominance(),
         }
+    }
+
+    fn apply_velocity_change(
+        &mut self,
+        linear: Vector,
+        #[cfg(feature = "2d")] angular: Scalar,
+        #[cfg(feature = "3d")] angular: Vector,
+    ) {
+        if let Self::Mutable(body) = self {
+            body.apply_velocity_change(linear, angular);
+        }
+    }
+}
+
+/// Calls `solve` for each contact constraint and the two bodies participating in it.
+///
+/// If the [`PhysicsIslands`] are available and the `parallel` feature is enabled,
+/// batches of islands are solved in parallel. The constraints of different islands
+/// never share a dynamic body, so they can be solved independently.
+/// The constraints of [colored](SolverConfig::graph_coloring_threshold) islands
+/// are solved one color at a time, with the constraints of each color solved in parallel.
+fn for_each_contact_constraint(
+    bodies: &mut Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    constraints: &mut [ContactConstraint],
+    islands: Option<&PhysicsIslands>,
+    solve: impl Fn(&mut ContactConstraint, &mut SolverBody, &mut SolverBody) + Send + Sync,
+) {
+    #[cfg(feature = "parallel")]
+    if let Some(islands) = islands.filter(|islands| islands.matches_constraints(constraints)) {
+        solve_islands_parallel(bodies, constraints, islands, &solve);
+        return;
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    let _ = islands;
 
-        let Ok([mut body1, mut body2]) =
-            bodies.get_many_mut([constraint.entity1, constraint.entity2])
+    for constraint in constraints.iter_mut() {
+        let Ok([body1, body2]) = bodies.get_many_mut([constraint.entity1, constraint.entity2])
         else {
             continue;
         };
 
-        // Performing multiple iterations can result in more accurate restitution,
-        // but only if there are more than one contact point.
-        let restitution_iterations = if constraint.points.len() > 1 {
-            solver_config.restitution_iterations
-        } else {
-            1
-        };
+        solve(
+            constraint,
+            &mut SolverBody::Mutable(body1),
+            &mut SolverBody::Mutable(body2),
+        );
+    }
+}
+
+/// The minimum number of constraints in a batch solved in parallel.
+/// Smaller batches are not worth the overhead of a task.
+#[cfg(feature = "parallel")]
+const MIN_BATCH_SIZE: usize = 64;
+
+/// Solves contact constraints in parallel, splitting them into batches of whole islands.
+#[cfg(feature = "parallel")]
+fn solve_islands_parallel(
+    bodies: &Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    constraints: &mut [ContactConstraint],
+    islands: &PhysicsIslands,
+    solve: &(impl Fn(&mut ContactConstraint, &mut SolverBody, &mut SolverBody) + Send + Sync),
+) {
+    let task_pool = ComputeTaskPool::get();
+
+    // Use a few batches per thread so that the load is balanced even when island sizes vary.
+    let batch_size = (constraints.len() / (task_pool.thread_num().max(1) * 4)).max(MIN_BATCH_SIZE);
+
+    // Colored islands are solved separately after the other islands.
+    let mut colored_islands = vec![];
+
+    task_pool.scope(|scope| {
+        let mut remaining = constraints;
+        let mut island_index = 0;
+
+        while island_index < islands.islands.len() {
+            let island = &islands.islands[island_index];
+            if island.coloring.is_some() {
+                let (island_constraints, rest) =
+                    std::mem::take(&mut remaining).split_at_mut(island.contact_constraints.len());
+                remaining = rest;
+                colored_islands.push((island_index, island_constraints));
+                island_index += 1;
+                continue;
+            }
+
+            // Gather whole uncolored islands until the batch is large enough.
+            let first_island = island_index;
+            let mut len = 0;
+            while island_index < islands.islands.len()
+                && islands.islands[island_index].coloring.is_none()
+                && len < batch_size
+            {
+                len += islands.islands[island_index].contact_constraints.len();
+                island_index += 1;
+            }
+
+            let (batch, rest) = std::mem::take(&mut remaining).split_at_mut(len);
+            remaining = rest;
+
+            if !batch.is_empty() {
+                let batch_islands = first_island..island_index;
+                scope.spawn(async move {
+                    solve_batch(bodies, batch, islands, batch_islands, solve);
+                });
+            }
+        }
+
+        // The remaining constraints don't involve any dynamic bodies.
+        if !remaining.is_empty() {
+            scope.spawn(async move {
+                solve_batch(bodies, remaining, islands, 0..0, solve);
+            });
+        }
+    });
+
+    for (island_index, island_constraints) in colored_islands {
+        solve_colored_island(bodies, island_constraints, islands, island_index, solve);
+    }
+}
+
+/// Solves the contact constraints of a [colored](SolverConfig::graph_coloring_threshold) island,
+/// one color at a time. The constraints of each color are split into batches that are solved in parallel.
+#[cfg(feature = "parallel")]
+fn solve_colored_island(
+    bodies: &Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    constraints: &mut [ContactConstraint],
+    islands: &PhysicsIslands,
+    island_index: usize,
+    solve: &(impl Fn(&mut ContactConstraint, &mut SolverBody, &mut SolverBody) + Send + Sync),
+) {
+    let island = &islands.islands[island_index];
+    let Some(coloring) = &island.coloring else {
+        return;
+    };
+
+    let task_pool = ComputeTaskPool::get();
+    let offset = island.contact_constraints.start;
+    let batch_islands = island_index..island_index + 1;
+
+    #[cfg(debug_assertions)]
+    debug_assert_disjoint_colors(constraints, islands, coloring, offset);
+
+    for color in coloring.colors.iter() {
+        let color_constraints = &mut constraints[color.start - offset..color.end - offset];
+        let batch_size =
+            (color_constraints.len() / task_pool.thread_num().max(1)).max(MIN_BATCH_SIZE);
+        task_pool.scope(|scope| {
+            for batch in color_constraints.chunks_mut(batch_size) {
+                let batch_islands = batch_islands.clone();
+                scope.spawn(async move {
+                    solve_batch(bodies, batch, islands, batch_islands, solve);
+                });
+            }
+        });
+    }
+
+    // The constraints that couldn't be colored can share bodies, so they are solved serially.
+    let overflow = coloring.overflow.start - offset..coloring.overflow.end - offset;
+    solve_batch(
+        bodies,
+        &mut constraints[overflow],
+        islands,
+        batch_islands,
+        solve,
+    );
+}
+
+/// Checks that the constraints of each color never share a dynamic body,
+/// which [`solve_batch`] relies on to access the bodies of a color in parallel.
+#[cfg(all(feature = "parallel", debug_assertions))]
+fn debug_assert_disjoint_colors(
+    constraints: &[ContactConstraint],
+    islands: &PhysicsIslands,
+    coloring: &crate::dynamics::islands::IslandColoring,
+    offset: usize,
+) {
+    let mut bodies = bevy::ecs::entity::EntityHashSet::default();
 
-        for _ in 0..restitution_iterations {
-            constraint.apply_restitution(&mut body1, &mut body2, threshold);
+    for color in coloring.colors.iter() {
+        bodies.clear();
+        for constraint in &constraints[color.start - offset..color.end - offset] {
+            for entity in [constraint.entity1, constraint.entity2] {
+                if islands.body_islands.contains_key(&entity) {
+                    debug_assert!(
+                        bodies.insert(entity),
+                        "dynamic body {entity} is shared by constraints of the same color"
+                    );
+                }
+            }
         }
     }
 }
 
+/// Solves a batch of contact constraints belonging to the given range of islands.
+#[cfg(feature = "parallel")]
+fn solve_batch(
+    bodies: &Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    constraints: &mut [ContactConstraint],
+    islands: &PhysicsIslands,
+    batch_islands: Range<usize>,
+    solve: &(impl Fn(&mut ContactConstraint, &mut SolverBody, &mut SolverBody) + Send + Sync),
+) {
+    let get_body = |entity: Entity| {
+        match islands.body_islands.get(&entity) {
+            Some(island_index) if batch_islands.contains(island_index) => {
+                // SAFETY: No other task can access this body mutably at the same time:
+                //
+                //         - `body_islands` maps each dynamic body to exactly one island.
+                //         - The islands are split into disjoint ranges, and each range is solved
+                //           by exactly one task, so a body of an uncolored island is only
+                //           accessed by the task solving its island.
+                //         - The batches of a color in a colored island are solved in parallel,
+                //           but graph coloring guarantees that the constraints of a color
+                //           never share a dynamic body. This is checked in debug builds
+                //           by `debug_assert_disjoint_colors`. The overflow constraints
+                //           that can share bodies are solved by a single task.
+                //         - The two bodies of a constraint are checked to be distinct below,
+                //           so the same body is never borrowed twice by one constraint.
+                //
+                //         Static and kinematic bodies are not in `body_islands`,
+                //         and are only accessed immutably.
+                unsafe { bodies.get_unchecked(entity) }
+                    .ok()
+                    .map(SolverBody::Mutable)
+            }
+            // The body is solved by another batch, so it can't be accessed here.
+            // This should only happen if the constraints were modified after the islands were built.
+            Some(_) => None,
+            // The body is not in any island, so it should not be dynamic.
+            None => {
+                let body = bodies.get(entity).ok()?;
+                debug_assert!(
+                    !body.rb.is_dynamic(),
+                    "dynamic body {entity} is missing from the physics islands"
+                );
+                if body.rb.is_dynamic() {
+                    // Reading the body immutably would silently drop the impulses applied to it.
+                    error_once!(
+                        "Dynamic body {entity} is missing from the physics islands. \
+                        Its contact constraints are skipped until the islands are rebuilt."
+                    );
+                    return None;
+                }
+                Some(SolverBody::ReadOnly(body))
+            }
+        }
+    };
+
+    for constraint in constraints.iter_mut() {
+        // A constraint between a body and itself would borrow the body mutably twice.
+        debug_assert_ne!(
+            constraint.entity1, constraint.entity2,
+            "contact constraint between a body and itself"
+        );
+        if constraint.entity1 == constraint.entity2 {
+            continue;
+        }
+
+        let (Some(mut body1), Some(mut body2)) =
+            (get_body(constraint.entity1), get_body(constraint.entity2))
+        else {
+            continue;
+        };
+
+        solve(constraint, &mut body1, &mut body2);
+    }
+}
+
 /// Copies contact impulses from [`ContactConstraints`] to the contacts in [`Collisions`].
 /// They will be used for [warm starting](SubstepSolverSet::WarmStart).
 fn store_contact_impulses(
@@ -520,6 +1163,21 @@ fn store_contact_impulses(
     }
 }
 
+/// Counts down the remaining steps of [`SkipWarmStart`], and removes the component
+/// once the steps have elapsed.
+fn count_down_skip_warm_start(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut SkipWarmStart)>,
+) {
+    for (entity, mut skip_warm_start) in &mut query {
+        if skip_warm_start.0 <= 1 {
+            commands.entity(entity).remove::<SkipWarmStart>();
+        } else {
+            skip_warm_start.0 -= 1;
+        }
+    }
+}
+
 /// Finalizes the positions of bodies by applying the [`AccumulatedTranslation`].
 #[allow(clippy::type_complexity)]
 fn apply_translation(
@@ -612,3 +1270,149 @@ pub fn joint_damping<T: Joint>(
         }
     }
 }
+
+/// Applies the damping of the [springs](JointSpring) of [`RevoluteJoint`]s and [`PrismaticJoint`]s.
+fn joint_spring_damping(
+    mut bodies: Query<RigidBodyQuery, RigidBodyActiveFilter>,
+    revolute_joints: Query<&RevoluteJoint, (Without<RigidBody>, Without<JointDisabled>)>,
+    prismatic_joints: Query<&PrismaticJoint, (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for joint in &revolute_joints {
+        if joint.angle_spring.is_none() {
+            continue;
+        }
+        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) {
+            joint.apply_spring_damping(&mut body1, &mut body2, delta_secs);
+        }
+    }
+
+    for joint in &prismatic_joints {
+        if joint.translation_spring.is_none() {
+            continue;
+        }
+        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) {
+            joint.apply_spring_damping(&mut body1, &mut body2, delta_secs);
+        }
+    }
+}
+
+/// Applies the velocity changes caused by the [motors](AngularMotor) of [`UniversalJoint`]s.
+#[cfg(feature = "3d")]
+fn universal_joint_motors(
+    mut bodies: Query<RigidBodyQuery, RigidBodyActiveFilter>,
+    joints: Query<&UniversalJoint, (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for joint in &joints {
+        if joint.axis1_motor.is_none() && joint.axis2_motor.is_none() {
+            continue;
+        }
+        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) {
+            joint.apply_motors(&mut body1, &mut body2, delta_secs);
+        }
+    }
+}
+
+/// Applies restitution for the limits of [`RevoluteJoint`]s, [`PrismaticJoint`]s, and [`SphericalJoint`]s
+/// based on their [`JointLimitResponse`].
+fn joint_limit_restitution(
+    mut bodies: Query<RigidBodyQuery, RigidBodyActiveFilter>,
+    revolute_joints: Query<&RevoluteJoint, (Without<RigidBody>, Without<JointDisabled>)>,
+    prismatic_joints: Query<&PrismaticJoint, (Without<RigidBody>, Without<JointDisabled>)>,
+    #[cfg(feature = "3d")] spherical_joints: Query<
+        &SphericalJoint,
+        (Without<RigidBody>, Without<JointDisabled>),
+    >,
+) {
+    for joint in &revolute_joints {
+        if joint.limit_response.restitution <= 0.0 {
+            continue;
+        }
+        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) {
+            joint.apply_limit_restitution(&mut body1, &mut body2);
+        }
+    }
+
+    for joint in &prismatic_joints {
+        if joint.limit_response.restitution <= 0.0 {
+            continue;
+        }
+        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) {
+            joint.apply_limit_restitution(&mut body1, &mut body2);
+        }
+    }
+
+    #[cfg(feature = "3d")]
+    for joint in &spherical_joints {
+        if joint.limit_response.restitution <= 0.0 {
+            continue;
+        }
+        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) {
+            joint.apply_limit_restitution(&mut body1, &mut body2);
+        }
+    }
+}
+
+/// Applies velocity corrections that damp the difference between the velocity of the second body
+/// of a [`MirrorConstraint`] and the mirrored velocity of the first body.
+#[allow(clippy::type_complexity)]
+fn mirror_damping(
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+            &ComputedMass,
+        ),
+        RigidBodyActiveFilter,
+    >,
+    constraints: Query<&MirrorConstraint, (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for constraint in &constraints {
+        let Ok(
+            [(rb1, mut lin_vel1, mut ang_vel1, mass1), (rb2, mut lin_vel2, mut ang_vel2, mass2)],
+        ) = bodies.get_many_mut(constraint.entities())
+        else {
+            continue;
+        };
+
+        let w1 = if rb1.is_dynamic() {
+            mass1.inverse()
+        } else {
+            0.0
+        };
+        let w2 = if rb2.is_dynamic() {
+            mass2.inverse()
+        } else {
+            0.0
+        };
+
+        if w1 + w2 <= Scalar::EPSILON {
+            continue;
+        }
+
+        // Correcting the first body changes the mirrored velocity,
+        // so its correction is reflected across the mirror plane.
+        let delta_v = (lin_vel2.0 - constraint.reflect_vector(lin_vel1.0))
+            * (constraint.damping_linear * delta_secs).min(1.0);
+        let p = delta_v / (w1 + w2);
+        lin_vel1.0 += constraint.reflect_vector(p) * w1;
+        lin_vel2.0 -= p * w2;
+
+        let a1 = if rb1.is_dynamic() { 1.0 } else { 0.0 };
+        let a2 = if rb2.is_dynamic() { 1.0 } else { 0.0 };
+        let delta_omega = (ang_vel2.0 - constraint.reflect_angular_velocity(ang_vel1.0))
+            * (constraint.damping_angular * delta_secs).min(1.0);
+        let p = delta_omega / (a1 + a2);
+        ang_vel1.0 += constraint.reflect_angular_velocity(p) * a1;
+        ang_vel2.0 -= p * a2;
+    }
+}
@@ -17,11 +17,17 @@ pub struct SolverSchedulePlugin;
 impl Plugin for SolverSchedulePlugin {
     fn build(&self, app: &mut App) {
         // Register types.
-        app.register_type::<(Time<Substeps>, SubstepCount)>();
+        app.register_type::<(
+            Time<Substeps>,
+            SubstepCount,
+            CurrentSubstep,
+            AdaptiveSubsteps,
+        )>();
 
         // Initialize resources.
         app.insert_resource(Time::new_with(Substeps))
-            .init_resource::<SubstepCount>();
+            .init_resource::<SubstepCount>()
+            .init_resource::<CurrentSubstep>();
 
         // Get the `PhysicsSchedule`, and panic if it doesn't exist.
         let physics = app
@@ -45,6 +51,13 @@ impl Plugin for SolverSchedulePlugin {
         // Run the substepping loop.
         physics.add_systems(run_substep_schedule.in_set(SolverSet::Substep));
 
+        // Adapt the substep count for the next physics step.
+        physics.add_systems(
+            adapt_substep_count
+                .in_set(PhysicsStepSet::Last)
+                .run_if(resource_exists::<AdaptiveSubsteps>),
+        );
+
         // Set up the substep schedule, the schedule that runs systems in the inner substepping loop.
         app.edit_schedule(SubstepSchedule, |schedule| {
             schedule
@@ -168,6 +181,9 @@ pub enum SubstepSolverSet {
 ///         .run();
 /// }
 /// ```
+///
+/// To adjust the substep count automatically based on the state of the simulation,
+/// insert the [`AdaptiveSubsteps`] resource.
 #[derive(Debug, Reflect, Resource, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
@@ -180,6 +196,177 @@ impl Default for SubstepCount {
     }
 }
 
+/// A resource that enables adaptive substepping, where the [`SubstepCount`] is adjusted
+/// after each physics step based on how much action there is in the simulation.
+///
+/// The substep count is increased right away when awake dynamic bodies move fast
+/// or colliders overlap deeply, and decreased gradually once the simulation has been calm
+/// for [`decrease_delay`](Self::decrease_delay) steps. This keeps the cost of the simulation
+/// proportional to the action, while fast and violent moments still get accurate results.
+///
+/// The substep count is shared by all [simulation islands](dynamics::islands), so the most active island
+/// determines the count. Sleeping bodies don't affect it.
+///
+/// Adaptive substepping is disabled by default, and can be enabled by inserting the resource.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(AdaptiveSubsteps::new(2, 16))
+///         .run();
+/// }
+/// ```
+#[derive(Debug, Reflect, Resource, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct AdaptiveSubsteps {
+    /// The minimum number of substeps.
+    ///
+    /// Default: `2`
+    pub min_substeps: u32,
+    /// The maximum number of substeps.
+    ///
+    /// Default: `12`
+    pub max_substeps: u32,
+    /// The maximum distance that awake dynamic bodies should move during a single substep.
+    /// Faster bodies increase the substep count.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.1`
+    pub max_distance_per_substep: Scalar,
+    /// The penetration depth of contacts above which the substep count is increased.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.05`
+    pub max_penetration: Scalar,
+    /// The number of consecutive calm physics steps before the substep count is decreased by one.
+    ///
+    /// Default: `30`
+    pub decrease_delay: u32,
+    calm_steps: u32,
+}
+
+impl Default for AdaptiveSubsteps {
+    fn default() -> Self {
+        Self::new(2, 12)
+    }
+}
+
+impl AdaptiveSubsteps {
+    /// Creates a new [`AdaptiveSubsteps`] resource with the given minimum and maximum number of substeps.
+    pub fn new(min_substeps: u32, max_substeps: u32) -> Self {
+        Self {
+            min_substeps,
+            max_substeps,
+            max_distance_per_substep: 0.1,
+            max_penetration: 0.05,
+            decrease_delay: 30,
+            calm_steps: 0,
+        }
+    }
+}
+
+/// Adjusts the [`SubstepCount`] for the next physics step based on the [`AdaptiveSubsteps`].
+///
+/// The substep count is global, so this considers all awake bodies and contacts
+/// rather than adapting the count for each simulation island separately.
+fn adapt_substep_count(
+    mut adaptive: ResMut<AdaptiveSubsteps>,
+    mut substep_count: ResMut<SubstepCount>,
+    bodies: Query<(&RigidBody, &LinearVelocity), Without<Sleeping>>,
+    collisions: Option<Res<Collisions>>,
+    length_unit: Res<PhysicsLengthUnit>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    let min = adaptive.min_substeps.max(1);
+    let max = adaptive.max_substeps.max(min);
+    let current = substep_count.0;
+
+    // The number of substeps needed to keep the movement of the fastest body within the limit.
+    let max_speed = bodies
+        .iter()
+        .filter(|(rb, _)| rb.is_dynamic())
+        .map(|(_, lin_vel)| lin_vel.length())
+        .fold(0.0, Scalar::max);
+    let max_distance = adaptive.max_distance_per_substep * length_unit.0;
+    let mut required = if max_distance > 0.0 {
+        (max_speed * delta_secs / max_distance).ceil() as u32
+    } else {
+        max
+    };
+
+    // Deep penetration requires more substeps to resolve.
+    let max_penetration = collisions.map_or(0.0, |collisions| {
+        collisions
+            .iter()
+            .filter(|contacts| contacts.during_current_frame && !contacts.is_sensor)
+            .flat_map(|contacts| contacts.manifolds.iter())
+            .flat_map(|manifold| manifold.contacts.iter())
+            .map(|contact| contact.penetration)
+            .fold(0.0, Scalar::max)
+    });
+    if max_penetration > adaptive.max_penetration * length_unit.0 {
+        required = required.max(current + 1);
+    }
+
+    let target = required.clamp(min, max);
+    if target > current {
+        // Increase the substep count right away.
+        substep_count.0 = target;
+        adaptive.calm_steps = 0;
+    } else if target < current {
+        // Decrease the substep count gradually once things have been calm for a while.
+        adaptive.calm_steps += 1;
+        if adaptive.calm_steps >= adaptive.decrease_delay {
+            substep_count.0 = (current - 1).max(min);
+            adaptive.calm_steps = 0;
+        }
+    } else {
+        adaptive.calm_steps = 0;
+    }
+}
+
+/// The substep that is currently running in the [`SubstepSchedule`].
+///
+/// This is updated before each substep, and can be used for evaluating values
+/// that change over the course of a physics step, like [ramped forces](ExternalForce::ramp).
+#[derive(Debug, Reflect, Resource, Clone, Copy, PartialEq, Eq)]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct CurrentSubstep {
+    /// The index of the current substep, starting from zero.
+    pub index: u32,
+    /// The number of substeps in the current physics step.
+    pub count: u32,
+}
+
+impl Default for CurrentSubstep {
+    fn default() -> Self {
+        Self { index: 0, count: 1 }
+    }
+}
+
+impl CurrentSubstep {
+    /// Returns the fraction of the physics step at the middle of the current substep, in the `[0, 1]` range.
+    pub fn fraction(&self) -> Scalar {
+        (self.index as Scalar + 0.5) / self.count.max(1) as Scalar
+    }
+}
+
 /// Runs the [`SubstepSchedule`].
 fn run_substep_schedule(world: &mut World) {
     let delta = world.resource::<Time<Physics>>().delta();
@@ -192,6 +379,10 @@ fn run_substep_schedule(world: &mut World) {
     let _ = world.try_schedule_scope(SubstepSchedule, |world, schedule| {
         for i in 0..substeps {
             trace!("running SubstepSchedule: {i}");
+            *world.resource_mut::<CurrentSubstep>() = CurrentSubstep {
+                index: i,
+                count: substeps,
+            };
             *world.resource_mut::<Time>() = world.resource::<Time<Substeps>>().as_generic();
             schedule.run(world);
         }
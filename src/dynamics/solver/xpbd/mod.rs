@@ -30,6 +30,7 @@
 //!     - [`FixedJoint`]
 //!     - [`DistanceJoint`]
 #![cfg_attr(feature = "3d", doc = "    - [`SphericalJoint`]")]
+#![cfg_attr(feature = "3d", doc = "    - [`UniversalJoint`]")]
 //!     - [`RevoluteJoint`]
 //!     - [`PrismaticJoint`]
 //!
@@ -83,30 +84,25 @@
 //!
 //! Take a look at [`XpbdConstraint::solve`] and the constraint [theory](#theory) to learn more about what to put in `solve`.
 //!
-//! Next, we need to add a system that solves the constraint during each run of the [solver](dynamics::solver).
-//! If your constraint is a component like Avian's joints, you can use the generic [`solve_constraint`]
-//! system that handles some of the background work for you.
-//!
-//! Add the `solve_constraint::<YourConstraint, ENTITY_COUNT>` system to the [substepping schedule's](SubstepSchedule)
-//! [`SubstepSolverSet::SolveUserConstraints`](dynamics::solver::SubstepSolverSet::SolveUserConstraints) system set.
-//! It should look like this:
+//! Next, the constraint needs to be solved during each run of the [solver](dynamics::solver).
+//! If your constraint is a component like Avian's joints, you can register it with
+//! [`add_physics_constraint`](XpbdConstraintAppExt::add_physics_constraint), giving the number of participating entities:
 //!
 //! ```ignore
-//! // Get substep schedule
-//! let substeps = app
-//!     .get_schedule_mut(SubstepSchedule)
-//!     .expect("add SubstepSchedule first");
-//!
-//! // Add custom constraint
-//! substeps.add_systems(
-//!     solve_constraint::<CustomConstraint, 2>
-//!         .in_set(SubstepSolverSet::SolveUserConstraints),
-//! );
+//! app.add_plugins(PhysicsPlugins::default())
+//!     .add_physics_constraint::<CustomConstraint, 2>();
 //! ```
 //!
 //! Now, just spawn an instance of the constraint, give it the participating entities, and the constraint should be getting
 //! solved automatically according to the `solve` method!
 //!
+//! Constraints can also implement [`XpbdConstraint::solve_velocity`] for velocity-level effects like damping and motors,
+//! and [`XpbdConstraint::warm_start`] for applying cached impulses at the beginning of each substep.
+//!
+//! For more control over scheduling, the generic [`solve_constraint`] system can also be added
+//! to the [`SubstepSolverSet::SolveUserConstraints`](dynamics::solver::SubstepSolverSet::SolveUserConstraints)
+//! system set manually.
+//!
 //! You can find a working example of a custom constraint
 //! [here](https://github.com/Jondolf/avian/blob/main/crates/avian3d/examples/custom_constraint.rs).
 //!
@@ -225,6 +221,8 @@ mod positional_constraint;
 pub use angular_constraint::AngularConstraint;
 pub use positional_constraint::PositionConstraint;
 
+use super::SubstepSolverSet;
+use crate::dynamics::islands::{link_constraint_bodies, update_islands, ConstraintLinks};
 use crate::prelude::*;
 use bevy::{ecs::entity::MapEntities, prelude::*};
 
@@ -321,6 +319,79 @@ pub trait XpbdConstraint<const ENTITY_COUNT: usize>: MapEntities {
 
     /// Sets the constraint's [Lagrange multipliers](self#lagrange-multipliers) to 0.
     fn clear_lagrange_multipliers(&mut self);
+
+    /// Warm starts the constraint at the beginning of each substep, before any constraints are solved.
+    ///
+    /// This can be used for applying impulses cached from the previous substep to improve convergence.
+    /// XPBD constraints typically don't need warm starting, so this does nothing by default.
+    ///
+    /// Only called for constraints registered with [`XpbdConstraintAppExt::add_physics_constraint`].
+    fn warm_start(&mut self, _bodies: [&mut RigidBodyQueryItem; ENTITY_COUNT], _dt: Scalar) {}
+
+    /// Solves the constraint at the velocity level, after the velocities of the bodies
+    /// have been updated based on the positional corrections of the substep.
+    ///
+    /// This can be used for things like damping, restitution, and motors that operate on velocities.
+    /// By default, this does nothing.
+    ///
+    /// Only called for constraints registered with [`XpbdConstraintAppExt::add_physics_constraint`].
+    fn solve_velocity(&mut self, _bodies: [&mut RigidBodyQueryItem; ENTITY_COUNT], _dt: Scalar) {}
+}
+
+/// An extension trait for registering custom [XPBD constraints](self#custom-constraints) in an [`App`].
+pub trait XpbdConstraintAppExt {
+    /// Registers the constraint component `C` with `ENTITY_COUNT` participating entities,
+    /// so that it is solved in the substepping loop.
+    ///
+    /// The constraint is [warm started](XpbdConstraint::warm_start) in [`SubstepSolverSet::WarmStart`],
+    /// [solved](XpbdConstraint::solve) in [`SubstepSolverSet::SolveUserConstraints`], and its
+    /// [velocity is solved](XpbdConstraint::solve_velocity) in [`SubstepSolverSet::XpbdVelocityProjection`].
+    /// Like Avian's joints, the constraint can be disabled with the [`JointDisabled`] component,
+    /// and its bodies are part of the same [island](crate::dynamics::islands).
+    ///
+    /// The [`PhysicsPlugins`] must be added before calling this.
+    ///
+    /// [`SubstepSolverSet::WarmStart`]: super::SubstepSolverSet::WarmStart
+    /// [`SubstepSolverSet::SolveUserConstraints`]: super::SubstepSolverSet::SolveUserConstraints
+    /// [`SubstepSolverSet::XpbdVelocityProjection`]: super::SubstepSolverSet::XpbdVelocityProjection
+    fn add_physics_constraint<
+        C: XpbdConstraint<ENTITY_COUNT> + Component,
+        const ENTITY_COUNT: usize,
+    >(
+        &mut self,
+    ) -> &mut Self;
+}
+
+impl XpbdConstraintAppExt for App {
+    fn add_physics_constraint<
+        C: XpbdConstraint<ENTITY_COUNT> + Component,
+        const ENTITY_COUNT: usize,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        self.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems((
+                super::joints::wake_on_joint_toggled::<C, ENTITY_COUNT>
+                    .in_set(PhysicsStepSet::First),
+                link_constraint_bodies::<C, ENTITY_COUNT>
+                    .in_set(SolverSet::PreSubstep)
+                    .before(update_islands)
+                    .run_if(resource_exists::<ConstraintLinks>),
+            ));
+
+        self.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems((
+                warm_start_constraint::<C, ENTITY_COUNT>.in_set(SubstepSolverSet::WarmStart),
+                solve_constraint::<C, ENTITY_COUNT>.in_set(SubstepSolverSet::SolveUserConstraints),
+                solve_constraint_velocity::<C, ENTITY_COUNT>
+                    .in_set(SubstepSolverSet::XpbdVelocityProjection)
+                    .after(project_angular_velocity),
+            ));
+
+        self
+    }
 }
 
 // TODO: Make a joint-optimized version
@@ -332,8 +403,11 @@ pub trait XpbdConstraint<const ENTITY_COUNT: usize>: MapEntities {
 ///
 /// # User Constraints
 ///
-/// To create a new constraint, implement [`XpbdConstraint`] for a component, get the [`SubstepSchedule`] and add this system into
-/// the [`SubstepSolverSet::SolveUserConstraints`](super::SubstepSolverSet::SolveUserConstraints) set.
+/// To create a new constraint, implement [`XpbdConstraint`] for a component and register it using
+/// [`XpbdConstraintAppExt::add_physics_constraint`], which adds this system along with the other constraint systems.
+///
+/// Alternatively, get the [`SubstepSchedule`] and add this system into
+/// the [`SubstepSolverSet::SolveUserConstraints`](super::SubstepSolverSet::SolveUserConstraints) set manually.
 /// You must provide the number of entities in the constraint using generics.
 ///
 /// It should look something like this:
@@ -396,6 +470,53 @@ pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTIT
     }
 }
 
+/// Iterates through the XPBD constraints of a given type and [warm starts](XpbdConstraint::warm_start) them.
+fn warm_start_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTITY_COUNT: usize>(
+    mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    mut constraints: Query<&mut C, (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut constraint in &mut constraints {
+        if let Some(mut bodies) = active_constraint_bodies(&mut bodies, constraint.entities()) {
+            constraint.warm_start(bodies.each_mut(), delta_secs);
+        }
+    }
+}
+
+/// Iterates through the XPBD constraints of a given type and [solves their velocities](XpbdConstraint::solve_velocity).
+fn solve_constraint_velocity<
+    C: XpbdConstraint<ENTITY_COUNT> + Component,
+    const ENTITY_COUNT: usize,
+>(
+    mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    mut constraints: Query<&mut C, (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut constraint in &mut constraints {
+        if let Some(mut bodies) = active_constraint_bodies(&mut bodies, constraint.entities()) {
+            constraint.solve_velocity(bodies.each_mut(), delta_secs);
+        }
+    }
+}
+
+/// Returns the bodies participating in a constraint, or `None` if none of them are dynamic,
+/// or if all of them are either static or sleeping.
+fn active_constraint_bodies<'a, const ENTITY_COUNT: usize>(
+    bodies: &'a mut Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    entities: [Entity; ENTITY_COUNT],
+) -> Option<[RigidBodyQueryItem<'a>; ENTITY_COUNT]> {
+    let bodies = bodies.get_many_mut(entities).ok()?;
+    let none_dynamic = bodies.iter().all(|body| !body.rb.is_dynamic());
+    let all_inactive = bodies
+        .iter()
+        .all(|body| body.rb.is_static() || body.is_sleeping);
+    (!none_dynamic && !all_inactive).then_some(bodies)
+}
+
 /// Updates the linear velocity of all dynamic bodies based on the change in position from the XPBD solver.
 pub(super) fn project_linear_velocity(
     mut bodies: Query<
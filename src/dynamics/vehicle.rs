@@ -0,0 +1,1074 @@
+//! Raycast vehicles with suspension, engine torque curves, gearboxes, steering, and slip-based tire traction.
+//!
+//! See [`VehiclePlugin`].
+
+use crate::prelude::*;
+#[cfg(feature = "settings-asset")]
+use crate::settings::{RonAsset, RonAssetLoader};
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// The minimum speed used as the reference speed when computing the
+/// [slip ratio](Wheel::slip_ratio) and [slip angle](Wheel::slip_angle) of a tire.
+///
+/// This prevents the slip from growing without bounds when the vehicle is nearly at rest.
+const MIN_SLIP_REFERENCE_SPEED: Scalar = 0.5;
+
+/// A plugin for simulating [`Vehicle`]s using raycast suspension.
+///
+/// Instead of simulating the wheels as separate rigid bodies connected with joints,
+/// each wheel casts a ray downwards from the chassis to find the ground. The suspension
+/// is modeled as a spring and damper along the ray, and the tires apply friction at the contact point
+/// to drive, brake, and steer the vehicle. All of the forces are applied to the chassis
+/// as [`ExternalImpulse`]s once per physics step.
+///
+/// This is the approach used by most racing and driving games, as it is stable and cheap,
+/// and the behavior is easy to tune.
+///
+/// The handling can range from arcade to simulation:
+///
+/// - The engine torque is shaped by a [`TorqueCurve`] and multiplied by the ratio of the current gear
+///   of the [`Gearbox`]. No torque is produced past the redline, which limits the top speed in each gear.
+/// - By default, the tires use the [`TireModel::Simple`] friction circle, which cancels sliding
+///   up to the grip limit. [`TireModel::SlipCurves`] instead simulates the spin of each wheel
+///   and computes the tire forces from the slip ratio and slip angle using [`TractionCurve`]s.
+///
+/// With the `settings-asset` feature, the engine, gearbox, and tires can also be configured
+/// with a [`VehicleConfig`] asset, which is applied to vehicles with a [`VehicleConfigHandle`].
+///
+/// The plugin is not included in the [`PhysicsPlugins`] by default, and must be added after them.
+/// The [`SpatialQueryPlugin`] is required for the wheel raycasts.
+///
+/// # Example
+///
+/// ```no_run
+/// use avian3d::prelude::*;
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), VehiclePlugin::default()))
+///         .add_systems(Startup, setup)
+///         .add_systems(Update, drive)
+///         .run();
+/// }
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::cuboid(2.0, 0.5, 4.0),
+///         Transform::from_xyz(0.0, 2.0, 0.0),
+///         Vehicle::new(vec![
+///             // Steered front wheels.
+///             Wheel::new(Vec3::new(-1.0, -0.25, -1.5), 0.4).with_steering(),
+///             Wheel::new(Vec3::new(1.0, -0.25, -1.5), 0.4).with_steering(),
+///             // Driven rear wheels.
+///             Wheel::new(Vec3::new(-1.0, -0.25, 1.5), 0.4).with_drive(),
+///             Wheel::new(Vec3::new(1.0, -0.25, 1.5), 0.4).with_drive(),
+///         ]),
+///     ));
+/// }
+/// # #[cfg(not(feature = "f32"))]
+/// # fn setup() {}
+///
+/// fn drive(keyboard: Res<ButtonInput<KeyCode>>, mut vehicles: Query<&mut Vehicle>) {
+///     for mut vehicle in &mut vehicles {
+///         vehicle.throttle = keyboard.pressed(KeyCode::KeyW) as u8 as Scalar
+///             - keyboard.pressed(KeyCode::KeyS) as u8 as Scalar;
+///         vehicle.steering = keyboard.pressed(KeyCode::KeyA) as u8 as Scalar
+///             - keyboard.pressed(KeyCode::KeyD) as u8 as Scalar;
+///         vehicle.brake = keyboard.pressed(KeyCode::Space) as u8 as Scalar;
+///     }
+/// }
+/// ```
+pub struct VehiclePlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl VehiclePlugin {
+    /// Creates a [`VehiclePlugin`] with the schedule that the vehicles are updated in.
+    ///
+    /// The default schedule is [`PhysicsSchedule`].
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for VehiclePlugin {
+    fn default() -> Self {
+        Self::new(PhysicsSchedule)
+    }
+}
+
+impl Plugin for VehiclePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(Vehicle, Wheel, WheelContact)>()
+            .register_type::<(TorqueCurve, Gearbox, TireModel, TractionCurve)>();
+
+        app.add_systems(
+            self.schedule.intern(),
+            update_vehicles.in_set(PhysicsStepSet::First),
+        );
+
+        #[cfg(feature = "settings-asset")]
+        if app.is_plugin_added::<AssetPlugin>() {
+            app.init_asset::<VehicleConfig>()
+                .init_asset_loader::<RonAssetLoader<VehicleConfig>>()
+                .register_asset_reflect::<VehicleConfig>()
+                .register_type::<VehicleConfigHandle>();
+
+            app.add_systems(PreUpdate, apply_vehicle_configs);
+        }
+    }
+}
+
+/// A component for a raycast vehicle, simulated by the [`VehiclePlugin`].
+///
+/// The entity should be a dynamic [rigid body](RigidBody) with a [`Collider`], acting as the chassis.
+/// The [`Wheel`]s are attached to the chassis in its local space. The forward direction of the vehicle
+/// is the local `-Z` axis, and the suspension points along the local `-Y` axis.
+///
+/// The vehicle is controlled by setting the [`throttle`](Self::throttle), [`brake`](Self::brake),
+/// and [`steering`](Self::steering) inputs, typically in a system based on user input.
+///
+/// See the [`VehiclePlugin`] for an example.
+#[derive(Reflect, Clone, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct Vehicle {
+    /// The wheels of the vehicle.
+    pub wheels: Vec<Wheel>,
+    /// The maximum torque applied by the engine to each [driven](Wheel::driven) wheel,
+    /// before it is shaped by the [`torque_curve`](Self::torque_curve) and multiplied
+    /// by the ratio of the [`gearbox`](Self::gearbox).
+    ///
+    /// Default: `1500.0`
+    pub engine_torque: Scalar,
+    /// The fraction of the [`engine_torque`](Self::engine_torque) available at each engine speed.
+    ///
+    /// Default: a flat curve with full torque at all engine speeds
+    pub torque_curve: TorqueCurve,
+    /// The gear ratios between the engine and the [driven](Wheel::driven) wheels.
+    ///
+    /// Default: a single gear with a ratio of `1.0`
+    pub gearbox: Gearbox,
+    /// The maximum torque applied by the brakes to each wheel.
+    ///
+    /// Default: `3000.0`
+    pub brake_torque: Scalar,
+    /// The maximum angle in radians that [steered](Wheel::steered) wheels can turn.
+    ///
+    /// Default: `0.5`
+    pub max_steering_angle: Scalar,
+    /// The throttle input in the `[-1, 1]` range. Negative values drive backwards.
+    pub throttle: Scalar,
+    /// The brake input in the `[0, 1]` range.
+    pub brake: Scalar,
+    /// The steering input in the `[-1, 1]` range. Positive values turn left.
+    pub steering: Scalar,
+    /// The current speed of the engine in revolutions per minute,
+    /// computed from the spin of the [driven](Wheel::driven) wheels and the current gear ratio.
+    pub engine_rpm: Scalar,
+}
+
+impl Default for Vehicle {
+    fn default() -> Self {
+        Self {
+            wheels: vec![],
+            engine_torque: 1500.0,
+            torque_curve: TorqueCurve::default(),
+            gearbox: Gearbox::default(),
+            brake_torque: 3000.0,
+            max_steering_angle: 0.5,
+            throttle: 0.0,
+            brake: 0.0,
+            steering: 0.0,
+            engine_rpm: 0.0,
+        }
+    }
+}
+
+impl Vehicle {
+    /// Creates a new [`Vehicle`] with the given wheels.
+    pub fn new(wheels: Vec<Wheel>) -> Self {
+        Self {
+            wheels,
+            ..default()
+        }
+    }
+
+    /// Sets the maximum torque applied by the engine to each [driven](Wheel::driven) wheel.
+    pub fn with_engine_torque(mut self, torque: Scalar) -> Self {
+        self.engine_torque = torque;
+        self
+    }
+
+    /// Sets the fraction of the [engine torque](Self::engine_torque) available at each engine speed.
+    pub fn with_torque_curve(mut self, torque_curve: TorqueCurve) -> Self {
+        self.torque_curve = torque_curve;
+        self
+    }
+
+    /// Sets the gear ratios between the engine and the [driven](Wheel::driven) wheels.
+    pub fn with_gearbox(mut self, gearbox: Gearbox) -> Self {
+        self.gearbox = gearbox;
+        self
+    }
+
+    /// Sets the maximum torque applied by the brakes to each wheel.
+    pub fn with_brake_torque(mut self, torque: Scalar) -> Self {
+        self.brake_torque = torque;
+        self
+    }
+
+    /// Sets the maximum angle in radians that [steered](Wheel::steered) wheels can turn.
+    pub fn with_max_steering_angle(mut self, angle: Scalar) -> Self {
+        self.max_steering_angle = angle;
+        self
+    }
+
+    /// Returns `true` if any of the wheels are touching the ground.
+    pub fn is_grounded(&self) -> bool {
+        self.wheels.iter().any(|wheel| wheel.contact.is_some())
+    }
+
+    /// Returns the highest speed that the [driven](Wheel::driven) wheels can reach in the current gear
+    /// before the engine hits the [redline](TorqueCurve::redline), or `None` if the torque curve has no redline.
+    pub fn top_speed(&self) -> Option<Scalar> {
+        let redline = self.torque_curve.redline()?;
+        let ratio = self.gearbox.ratio(false);
+        let radius = self
+            .wheels
+            .iter()
+            .filter(|wheel| wheel.driven)
+            .map(|wheel| wheel.radius)
+            .reduce(Scalar::max)?;
+        (ratio > 0.0).then(|| redline * 2.0 * PI / 60.0 / ratio * radius)
+    }
+}
+
+/// The fraction of the [engine torque](Vehicle::engine_torque) that a [`Vehicle`] can produce
+/// at each engine speed.
+///
+/// The curve is defined by points of the engine speed in revolutions per minute
+/// and the corresponding torque fraction, typically in the `[0, 1]` range. The fraction
+/// is interpolated linearly between the points, and the first point is used below its engine speed.
+/// Past the engine speed of the last point, the [redline](Self::redline), the engine produces no torque,
+/// which limits the top speed of the vehicle in each gear.
+///
+/// An empty curve provides full torque at all engine speeds.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "f32")]
+/// # {
+/// use avian3d::prelude::*;
+///
+/// // Peak torque at 4000 RPM, and a redline at 6500 RPM.
+/// let curve = TorqueCurve::new([(1000.0, 0.6), (4000.0, 1.0), (6500.0, 0.8)]);
+///
+/// assert_eq!(curve.sample(500.0), 0.6);
+/// assert_eq!(curve.sample(4000.0), 1.0);
+/// assert_eq!(curve.sample(7000.0), 0.0);
+/// assert_eq!(curve.redline(), Some(6500.0));
+/// # }
+/// ```
+#[derive(Reflect, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(transparent))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct TorqueCurve {
+    /// The points of the curve as `(rpm, torque_fraction)` pairs, sorted by the engine speed.
+    pub points: Vec<(Scalar, Scalar)>,
+}
+
+impl TorqueCurve {
+    /// Creates a new [`TorqueCurve`] from `(rpm, torque_fraction)` pairs.
+    ///
+    /// The points are sorted by the engine speed.
+    pub fn new(points: impl IntoIterator<Item = (Scalar, Scalar)>) -> Self {
+        let mut points: Vec<_> = points.into_iter().collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { points }
+    }
+
+    /// Returns the engine speed past which no torque is produced, or `None` if the curve is empty.
+    pub fn redline(&self) -> Option<Scalar> {
+        self.points.last().map(|point| point.0)
+    }
+
+    /// Returns the fraction of the engine torque available at the given engine speed.
+    pub fn sample(&self, rpm: Scalar) -> Scalar {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return 1.0;
+        };
+
+        if rpm <= first.0 {
+            return first.1;
+        }
+        if rpm > last.0 {
+            return 0.0;
+        }
+
+        // Find the segment containing the engine speed and interpolate linearly.
+        let i = self.points.partition_point(|point| point.0 < rpm);
+        let (rpm1, torque1) = self.points[i - 1];
+        let (rpm2, torque2) = self.points[i];
+        let t = (rpm - rpm1) / (rpm2 - rpm1);
+        torque1 + (torque2 - torque1) * t
+    }
+}
+
+/// The gear ratios between the engine and the [driven](Wheel::driven) wheels of a [`Vehicle`].
+///
+/// The gearbox shifts automatically: it shifts up when the engine speed exceeds the [`upshift_rpm`](Self::upshift_rpm),
+/// and down when it drops below the [`downshift_rpm`](Self::downshift_rpm). A negative
+/// [throttle](Vehicle::throttle) uses the [`reverse_ratio`](Self::reverse_ratio).
+///
+/// The torque at the wheels is multiplied by the ratio of the current gear and the [`final_drive`](Self::final_drive),
+/// and the engine spins faster than the wheels by the same factor.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct Gearbox {
+    /// The ratios of the forward gears, starting from the first gear.
+    ///
+    /// Default: `[1.0]`
+    pub ratios: Vec<Scalar>,
+    /// The ratio of the reverse gear.
+    ///
+    /// Default: `1.0`
+    pub reverse_ratio: Scalar,
+    /// The ratio of the differential, applied on top of the ratio of every gear.
+    ///
+    /// Default: `1.0`
+    pub final_drive: Scalar,
+    /// The engine speed in revolutions per minute above which the gearbox shifts up.
+    ///
+    /// Default: `6000.0`
+    pub upshift_rpm: Scalar,
+    /// The engine speed in revolutions per minute below which the gearbox shifts down.
+    ///
+    /// Default: `2500.0`
+    pub downshift_rpm: Scalar,
+    /// The index of the current forward gear in the [`ratios`](Self::ratios).
+    pub gear: usize,
+}
+
+impl Default for Gearbox {
+    fn default() -> Self {
+        Self {
+            ratios: vec![1.0],
+            reverse_ratio: 1.0,
+            final_drive: 1.0,
+            upshift_rpm: 6000.0,
+            downshift_rpm: 2500.0,
+            gear: 0,
+        }
+    }
+}
+
+impl Gearbox {
+    /// Creates a new [`Gearbox`] with the given forward gear ratios, reverse ratio, and final drive ratio.
+    pub fn new(ratios: Vec<Scalar>, reverse_ratio: Scalar, final_drive: Scalar) -> Self {
+        Self {
+            ratios,
+            reverse_ratio,
+            final_drive,
+            ..default()
+        }
+    }
+
+    /// Sets the engine speeds at which the gearbox shifts up and down.
+    pub fn with_shift_rpm(mut self, upshift_rpm: Scalar, downshift_rpm: Scalar) -> Self {
+        self.upshift_rpm = upshift_rpm;
+        self.downshift_rpm = downshift_rpm;
+        self
+    }
+
+    /// Returns the total ratio between the engine and the wheels, including the [`final_drive`](Self::final_drive).
+    ///
+    /// If `reverse` is `true`, the [`reverse_ratio`](Self::reverse_ratio) is used instead of the current gear.
+    pub fn ratio(&self, reverse: bool) -> Scalar {
+        let gear_ratio = if reverse {
+            self.reverse_ratio
+        } else {
+            self.ratios.get(self.gear).copied().unwrap_or(1.0)
+        };
+        gear_ratio * self.final_drive
+    }
+
+    /// Shifts up or down by one gear based on the given engine speed.
+    ///
+    /// Returns `true` if the gear changed.
+    pub fn shift(&mut self, rpm: Scalar) -> bool {
+        if rpm > self.upshift_rpm && self.gear + 1 < self.ratios.len() {
+            self.gear += 1;
+            true
+        } else if rpm < self.downshift_rpm && self.gear > 0 {
+            self.gear -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A simplified version of the Pacejka "magic formula" tire model, mapping the slip of a tire
+/// to the friction coefficient of the resulting tire force.
+///
+/// The curve is evaluated as `peak * sin(shape * atan(B * x - curvature * (B * x - atan(B * x))))`,
+/// where `B` is the [`stiffness`](Self::stiffness) and `x` is the slip. The force rises steeply for small slips,
+/// reaches the [`peak`](Self::peak) grip, and falls off as the tire starts sliding.
+///
+/// Used by the [`TireModel::SlipCurves`] tire model.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct TractionCurve {
+    /// The stiffness factor `B`, controlling how quickly the grip rises with the slip.
+    pub stiffness: Scalar,
+    /// The shape factor `C`, controlling how much the grip falls off past the peak.
+    pub shape: Scalar,
+    /// The peak factor `D`, the maximum friction coefficient relative to the [tire friction](Wheel::friction).
+    pub peak: Scalar,
+    /// The curvature factor `E`, controlling the sharpness of the peak.
+    pub curvature: Scalar,
+}
+
+impl Default for TractionCurve {
+    fn default() -> Self {
+        Self::LONGITUDINAL
+    }
+}
+
+impl TractionCurve {
+    /// A typical curve for the longitudinal slip ratio, peaking at a slip ratio of roughly `0.1`.
+    pub const LONGITUDINAL: Self = Self {
+        stiffness: 10.0,
+        shape: 1.9,
+        peak: 1.0,
+        curvature: 0.0,
+    };
+
+    /// A typical curve for the lateral slip angle in radians, peaking at roughly 10 degrees.
+    pub const LATERAL: Self = Self {
+        stiffness: 14.0,
+        shape: 1.3,
+        peak: 1.0,
+        curvature: 0.0,
+    };
+
+    /// Creates a new [`TractionCurve`] with the given stiffness, shape, peak, and curvature factors.
+    pub const fn new(stiffness: Scalar, shape: Scalar, peak: Scalar, curvature: Scalar) -> Self {
+        Self {
+            stiffness,
+            shape,
+            peak,
+            curvature,
+        }
+    }
+
+    /// Returns the friction coefficient for the given slip, relative to the [tire friction](Wheel::friction).
+    ///
+    /// The result has the same sign as the slip.
+    pub fn evaluate(&self, slip: Scalar) -> Scalar {
+        let x = self.stiffness * slip;
+        self.peak * (self.shape * (x - self.curvature * (x - x.atan())).atan()).sin()
+    }
+}
+
+/// The model used for computing the forces of the tires of a [`Wheel`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum TireModel {
+    /// The tires cancel all sliding up to the limit of the [friction circle](Wheel::friction),
+    /// and the wheels roll at the speed of the ground.
+    ///
+    /// This is stable and predictable, and well suited for arcade handling.
+    #[default]
+    Simple,
+    /// The spin of each wheel is simulated, and the tire forces are computed from the
+    /// [slip ratio](Wheel::slip_ratio) and [slip angle](Wheel::slip_angle) using traction curves.
+    ///
+    /// This allows wheelspin, locked brakes, and drifting, for more realistic handling.
+    SlipCurves {
+        /// The curve for the longitudinal force based on the slip ratio.
+        longitudinal: TractionCurve,
+        /// The curve for the lateral force based on the slip angle in radians.
+        lateral: TractionCurve,
+    },
+}
+
+impl TireModel {
+    /// A [`TireModel::SlipCurves`] model using the [`TractionCurve::LONGITUDINAL`]
+    /// and [`TractionCurve::LATERAL`] curves.
+    pub const SLIP_CURVES: Self = Self::SlipCurves {
+        longitudinal: TractionCurve::LONGITUDINAL,
+        lateral: TractionCurve::LATERAL,
+    };
+}
+
+/// A wheel of a [`Vehicle`].
+///
+/// The wheel casts a ray from its [`anchor`](Self::anchor) along the local `-Y` axis of the chassis.
+/// The suspension pushes the chassis up with a spring force proportional to its compression,
+/// and a damping force proportional to the compression speed.
+///
+/// The state of the wheel, like its [`contact`](Self::contact) and [`suspension_length`](Self::suspension_length),
+/// is updated by the [`VehiclePlugin`] every physics step, and can be used for positioning wheel meshes.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct Wheel {
+    /// The point where the suspension is attached to the chassis, in the local space of the chassis.
+    pub anchor: Vector,
+    /// The radius of the wheel.
+    pub radius: Scalar,
+    /// The length of the suspension when it is not compressed.
+    ///
+    /// Default: `0.5`
+    pub suspension_rest_length: Scalar,
+    /// The stiffness of the suspension spring in Newtons per meter of compression.
+    ///
+    /// Default: `30000.0`
+    pub suspension_stiffness: Scalar,
+    /// The damping of the suspension in Newtons per meter per second of compression speed.
+    ///
+    /// Default: `3000.0`
+    pub suspension_damping: Scalar,
+    /// The coefficient of friction of the tire, limiting the grip relative to the suspension force.
+    ///
+    /// Default: `1.5`
+    pub friction: Scalar,
+    /// The model used for computing the tire forces.
+    ///
+    /// Default: [`TireModel::Simple`]
+    pub tire_model: TireModel,
+    /// The moment of inertia of the wheel around its axle, resisting changes to its spin.
+    ///
+    /// Only used by the [`TireModel::SlipCurves`] tire model.
+    ///
+    /// Default: `1.5`
+    pub inertia: Scalar,
+    /// If `true`, the engine applies torque to the wheel.
+    pub driven: bool,
+    /// If `true`, the wheel turns based on the steering input.
+    pub steered: bool,
+    /// The current contact of the wheel with the ground, or `None` if the wheel is in the air.
+    pub contact: Option<WheelContact>,
+    /// The current length of the suspension.
+    pub suspension_length: Scalar,
+    /// The current steering angle of the wheel in radians.
+    pub steering_angle: Scalar,
+    /// The current rotation angle of the wheel around its axle in radians, useful for spinning wheel meshes.
+    pub rotation_angle: Scalar,
+    /// The current angular velocity of the wheel around its axle in radians per second.
+    /// Positive values roll the wheel forward.
+    pub angular_velocity: Scalar,
+    /// The current longitudinal slip ratio of the tire, the difference between the speed of the tire surface
+    /// and the speed of the ground relative to the ground speed. Positive values mean that the wheel is spinning,
+    /// and negative values mean that it is locking up.
+    ///
+    /// Always zero for the [`TireModel::Simple`] tire model.
+    pub slip_ratio: Scalar,
+    /// The current slip angle of the tire in radians, the angle between the direction of the tire
+    /// and the direction that it is moving in.
+    pub slip_angle: Scalar,
+}
+
+impl Wheel {
+    /// Creates a new [`Wheel`] attached to the chassis at the given local `anchor`, with the given `radius`.
+    pub fn new(anchor: Vector, radius: Scalar) -> Self {
+        Self {
+            anchor,
+            radius,
+            suspension_rest_length: 0.5,
+            suspension_stiffness: 30000.0,
+            suspension_damping: 3000.0,
+            friction: 1.5,
+            tire_model: TireModel::Simple,
+            inertia: 1.5,
+            driven: false,
+            steered: false,
+            contact: None,
+            suspension_length: 0.5,
+            steering_angle: 0.0,
+            rotation_angle: 0.0,
+            angular_velocity: 0.0,
+            slip_ratio: 0.0,
+            slip_angle: 0.0,
+        }
+    }
+
+    /// Makes the engine apply torque to the wheel.
+    pub fn with_drive(mut self) -> Self {
+        self.driven = true;
+        self
+    }
+
+    /// Makes the wheel turn based on the steering input.
+    pub fn with_steering(mut self) -> Self {
+        self.steered = true;
+        self
+    }
+
+    /// Sets the rest length, stiffness, and damping of the suspension.
+    pub fn with_suspension(
+        mut self,
+        rest_length: Scalar,
+        stiffness: Scalar,
+        damping: Scalar,
+    ) -> Self {
+        self.suspension_rest_length = rest_length;
+        self.suspension_length = rest_length;
+        self.suspension_stiffness = stiffness;
+        self.suspension_damping = damping;
+        self
+    }
+
+    /// Sets the coefficient of friction of the tire.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the model used for computing the tire forces.
+    pub fn with_tire_model(mut self, tire_model: TireModel) -> Self {
+        self.tire_model = tire_model;
+        self
+    }
+
+    /// Sets the moment of inertia of the wheel around its axle.
+    pub fn with_inertia(mut self, inertia: Scalar) -> Self {
+        self.inertia = inertia;
+        self
+    }
+
+    /// Returns the position of the center of the wheel in the local space of the chassis,
+    /// based on the current [`suspension_length`](Self::suspension_length).
+    pub fn local_center(&self) -> Vector {
+        self.anchor - Vector::Y * self.suspension_length
+    }
+}
+
+/// The contact of a [`Wheel`] with the ground.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct WheelContact {
+    /// The entity of the collider that the wheel is touching.
+    pub entity: Entity,
+    /// The contact point in world space.
+    pub point: Vector,
+    /// The surface normal at the contact point in world space.
+    pub normal: Vector,
+    /// The magnitude of the force applied by the suspension.
+    pub suspension_force: Scalar,
+}
+
+/// The handling configuration of a [`Vehicle`] that can be loaded from a `.vehicle.ron` file.
+///
+/// The configuration is applied to vehicles with a [`VehicleConfigHandle`] whenever the asset
+/// is loaded or modified, which allows tuning the handling without recompiling.
+///
+/// All fields are optional. Fields that are omitted leave the corresponding properties of the vehicle unchanged.
+///
+/// # Example
+///
+/// A configuration file could look like this:
+///
+/// ```ron
+/// (
+///     engine_torque: Some(400.0),
+///     brake_torque: Some(3000.0),
+///     torque_curve: Some([(1000.0, 0.6), (4500.0, 1.0), (7000.0, 0.8)]),
+///     gearbox: Some((
+///         ratios: [3.5, 2.2, 1.5, 1.1, 0.9],
+///         reverse_ratio: 3.2,
+///         final_drive: 3.7,
+///         upshift_rpm: 6500.0,
+///         downshift_rpm: 3000.0,
+///     )),
+///     tire_model: Some(SlipCurves(
+///         longitudinal: (stiffness: 10.0, shape: 1.9, peak: 1.0, curvature: 0.0),
+///         lateral: (stiffness: 14.0, shape: 1.3, peak: 1.0, curvature: 0.0),
+///     )),
+/// )
+/// ```
+#[cfg(feature = "settings-asset")]
+#[derive(
+    Asset, Reflect, Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+#[reflect(Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct VehicleConfig {
+    /// See [`Vehicle::engine_torque`].
+    pub engine_torque: Option<Scalar>,
+    /// See [`Vehicle::torque_curve`].
+    pub torque_curve: Option<TorqueCurve>,
+    /// See [`Vehicle::gearbox`].
+    pub gearbox: Option<Gearbox>,
+    /// See [`Vehicle::brake_torque`].
+    pub brake_torque: Option<Scalar>,
+    /// See [`Vehicle::max_steering_angle`].
+    pub max_steering_angle: Option<Scalar>,
+    /// The [`TireModel`] of all wheels.
+    pub tire_model: Option<TireModel>,
+    /// The [friction](Wheel::friction) of all wheels.
+    pub tire_friction: Option<Scalar>,
+}
+
+#[cfg(feature = "settings-asset")]
+impl VehicleConfig {
+    /// Applies the configuration to the given [`Vehicle`].
+    pub fn apply(&self, vehicle: &mut Vehicle) {
+        if let Some(value) = self.engine_torque {
+            vehicle.engine_torque = value;
+        }
+        if let Some(value) = &self.torque_curve {
+            vehicle.torque_curve = value.clone();
+        }
+        if let Some(value) = &self.gearbox {
+            // Keep the current gear if it still exists.
+            let gear = vehicle
+                .gearbox
+                .gear
+                .min(value.ratios.len().saturating_sub(1));
+            vehicle.gearbox = Gearbox {
+                gear,
+                ..value.clone()
+            };
+        }
+        if let Some(value) = self.brake_torque {
+            vehicle.brake_torque = value;
+        }
+        if let Some(value) = self.max_steering_angle {
+            vehicle.max_steering_angle = value;
+        }
+        for wheel in vehicle.wheels.iter_mut() {
+            if let Some(value) = self.tire_model {
+                wheel.tire_model = value;
+            }
+            if let Some(value) = self.tire_friction {
+                wheel.friction = value;
+            }
+        }
+    }
+}
+
+/// A component that configures the [`Vehicle`] on the same entity with a [`VehicleConfig`] asset.
+///
+/// The configuration is applied whenever the asset is loaded or modified.
+#[cfg(feature = "settings-asset")]
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Component, Debug, Default, PartialEq)]
+pub struct VehicleConfigHandle(pub Handle<VehicleConfig>);
+
+#[cfg(feature = "settings-asset")]
+impl RonAsset for VehicleConfig {
+    const EXTENSIONS: &'static [&'static str] = &["vehicle.ron"];
+}
+
+/// Applies [`VehicleConfig`]s to [`Vehicle`]s when the handle is changed, or when the asset is loaded or modified.
+#[cfg(feature = "settings-asset")]
+fn apply_vehicle_configs(
+    mut asset_events: EventReader<AssetEvent<VehicleConfig>>,
+    configs: Res<Assets<VehicleConfig>>,
+    mut vehicles: Query<(Ref<VehicleConfigHandle>, &mut Vehicle)>,
+) {
+    let changed: bevy::utils::HashSet<AssetId<VehicleConfig>> = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (handle, mut vehicle) in &mut vehicles {
+        if !handle.is_changed() && !changed.contains(&handle.0.id()) {
+            continue;
+        }
+        if let Some(config) = configs.get(&handle.0) {
+            config.apply(&mut vehicle);
+        }
+    }
+}
+
+/// Casts the wheel rays of [`Vehicle`]s and applies the suspension and tire forces to the chassis.
+#[allow(clippy::type_complexity)]
+fn update_vehicles(
+    mut vehicles: Query<
+        (
+            Entity,
+            &mut Vehicle,
+            &RigidBody,
+            &Position,
+            &Rotation,
+            &LinearVelocity,
+            &AngularVelocity,
+            &ComputedMass,
+            &ComputedCenterOfMass,
+            &mut ExternalImpulse,
+        ),
+        RigidBodyActiveFilter,
+    >,
+    collider_parents: Query<&ColliderParent>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (
+        entity,
+        mut vehicle,
+        rb,
+        position,
+        rotation,
+        lin_vel,
+        ang_vel,
+        mass,
+        center_of_mass,
+        mut impulse,
+    ) in &mut vehicles
+    {
+        if !rb.is_dynamic() || vehicle.wheels.is_empty() {
+            continue;
+        }
+
+        let vehicle = &mut *vehicle;
+        let center_of_mass = position.0 + *rotation * center_of_mass.0;
+        let up = *rotation * Vector::Y;
+        let down = *rotation * Dir::NEG_Y;
+
+        // The mass supported by each wheel, used for limiting the tire impulses.
+        let wheel_mass = mass.value() / vehicle.wheels.len() as Scalar;
+        let throttle = vehicle.throttle.clamp(-1.0, 1.0);
+        let brake = vehicle.brake.clamp(0.0, 1.0);
+        let steering_angle = vehicle.steering.clamp(-1.0, 1.0) * vehicle.max_steering_angle;
+
+        // Compute the engine speed from the average spin of the driven wheels,
+        // and shift gears based on it.
+        let reverse = throttle < 0.0;
+        let (spin_sum, driven_count) = vehicle
+            .wheels
+            .iter()
+            .filter(|wheel| wheel.driven)
+            .fold((0.0, 0), |(sum, count), wheel| {
+                (sum + wheel.angular_velocity.abs(), count + 1)
+            });
+        let wheel_rpm = if driven_count > 0 {
+            spin_sum / driven_count as Scalar * 60.0 / (2.0 * PI)
+        } else {
+            0.0
+        };
+        if !reverse {
+            let rpm = wheel_rpm * vehicle.gearbox.ratio(false);
+            vehicle.gearbox.shift(rpm);
+        }
+        let gear_ratio = vehicle.gearbox.ratio(reverse);
+        vehicle.engine_rpm = wheel_rpm * gear_ratio;
+
+        // The torque applied to each driven wheel.
+        let drive_torque = throttle
+            * vehicle.engine_torque
+            * vehicle.torque_curve.sample(vehicle.engine_rpm)
+            * gear_ratio;
+
+        // Ignore the colliders of the vehicle itself.
+        let filter = SpatialQueryFilter::default().with_excluded_entities([entity]);
+        let predicate = |collider: Entity| {
+            !collider_parents
+                .get(collider)
+                .is_ok_and(|p| p.get() == entity)
+        };
+
+        for wheel in vehicle.wheels.iter_mut() {
+            wheel.steering_angle = if wheel.steered { steering_angle } else { 0.0 };
+
+            let anchor = position.0 + *rotation * wheel.anchor;
+            let max_distance = wheel.suspension_rest_length + wheel.radius;
+            let max_brake_torque = brake * vehicle.brake_torque;
+            let wheel_drive_torque = if wheel.driven { drive_torque } else { 0.0 };
+
+            let Some(hit) = spatial_query.cast_ray_predicate(
+                anchor,
+                down,
+                max_distance,
+                true,
+                &filter,
+                &predicate,
+            ) else {
+                wheel.contact = None;
+                wheel.suspension_length = wheel.suspension_rest_length;
+                wheel.slip_ratio = 0.0;
+                wheel.slip_angle = 0.0;
+
+                // Wheels in the air are only spun up by the engine and slowed down by the brakes.
+                if matches!(wheel.tire_model, TireModel::SlipCurves { .. }) {
+                    wheel.angular_velocity = spin_wheel(
+                        wheel.angular_velocity,
+                        wheel_drive_torque,
+                        max_brake_torque,
+                        wheel.inertia,
+                        delta_secs,
+                    );
+                }
+                wheel.rotation_angle =
+                    (wheel.rotation_angle + wheel.angular_velocity * delta_secs) % (2.0 * PI);
+                continue;
+            };
+
+            let point = anchor - up * hit.distance;
+            let point_velocity = lin_vel.0 + ang_vel.0.cross(point - center_of_mass);
+
+            // Suspension: a spring and damper along the suspension axis.
+            wheel.suspension_length = (hit.distance - wheel.radius).max(0.0);
+            let compression = wheel.suspension_rest_length - wheel.suspension_length;
+            let suspension_force = (wheel.suspension_stiffness * compression
+                - wheel.suspension_damping * point_velocity.dot(up))
+            .max(0.0);
+            impulse.apply_impulse_at_point(
+                up * suspension_force * delta_secs,
+                point,
+                center_of_mass,
+            );
+
+            // The forward and side directions of the tire, projected onto the ground.
+            let wheel_rotation = rotation.0 * Quaternion::from_rotation_y(wheel.steering_angle);
+            let normal = hit.normal;
+            let project = |direction: Vector| {
+                (direction - normal * direction.dot(normal)).normalize_or_zero()
+            };
+            let forward = project(wheel_rotation * Vector::NEG_Z);
+            let side = project(wheel_rotation * Vector::X);
+
+            let forward_speed = point_velocity.dot(forward);
+            let side_speed = point_velocity.dot(side);
+            let max_tire_impulse = wheel.friction * suspension_force * delta_secs;
+
+            let tire_impulse = match wheel.tire_model {
+                TireModel::Simple => {
+                    // Longitudinal impulse from the engine and brakes.
+                    let mut forward_impulse = wheel_drive_torque / wheel.radius * delta_secs;
+                    if brake > 0.0 {
+                        // The brakes can only stop the wheel, not reverse it.
+                        let max_brake_impulse = max_brake_torque / wheel.radius * delta_secs;
+                        forward_impulse -= (forward_speed * wheel_mass)
+                            .clamp(-max_brake_impulse, max_brake_impulse);
+                    }
+
+                    // Lateral impulse that cancels the sideways sliding of the tire.
+                    let side_impulse = -side_speed * wheel_mass;
+
+                    // The wheel rolls at the speed of the ground.
+                    wheel.angular_velocity = forward_speed / wheel.radius;
+                    wheel.slip_angle = side_speed.atan2(forward_speed.abs());
+
+                    // Limit the tire impulse by the friction circle.
+                    let tire_impulse = forward * forward_impulse + side * side_impulse;
+                    if tire_impulse.length_squared() > max_tire_impulse * max_tire_impulse {
+                        tire_impulse.normalize_or_zero() * max_tire_impulse
+                    } else {
+                        tire_impulse
+                    }
+                }
+                TireModel::SlipCurves {
+                    longitudinal,
+                    lateral,
+                } => {
+                    let angular_velocity = spin_wheel(
+                        wheel.angular_velocity,
+                        wheel_drive_torque,
+                        max_brake_torque,
+                        wheel.inertia,
+                        delta_secs,
+                    );
+                    let surface_speed = angular_velocity * wheel.radius;
+
+                    // The slip of the tire relative to the ground.
+                    let reference_speed = forward_speed
+                        .abs()
+                        .max(surface_speed.abs())
+                        .max(MIN_SLIP_REFERENCE_SPEED);
+                    wheel.slip_ratio = (surface_speed - forward_speed) / reference_speed;
+                    wheel.slip_angle =
+                        side_speed.atan2(forward_speed.abs().max(MIN_SLIP_REFERENCE_SPEED));
+
+                    // Evaluate the traction curves, and limit the combined impulse by the friction circle.
+                    let mut forward_impulse =
+                        longitudinal.evaluate(wheel.slip_ratio) * max_tire_impulse;
+                    let mut side_impulse = -lateral.evaluate(wheel.slip_angle) * max_tire_impulse;
+                    let length = forward_impulse.hypot(side_impulse);
+                    if length > max_tire_impulse {
+                        forward_impulse *= max_tire_impulse / length;
+                        side_impulse *= max_tire_impulse / length;
+                    }
+
+                    // The tire impulses should not overshoot and reverse the slip within a single step.
+                    // The longitudinal impulse acts on both the chassis and the spin of the wheel.
+                    let effective_mass =
+                        1.0 / (1.0 / wheel_mass + wheel.radius * wheel.radius / wheel.inertia);
+                    let max_forward_impulse =
+                        (surface_speed - forward_speed).abs() * effective_mass;
+                    let max_side_impulse = side_speed.abs() * wheel_mass;
+                    let forward_impulse =
+                        forward_impulse.clamp(-max_forward_impulse, max_forward_impulse);
+                    let side_impulse = side_impulse.clamp(-max_side_impulse, max_side_impulse);
+
+                    // The ground pushes back on the wheel, slowing down its spin.
+                    wheel.angular_velocity =
+                        angular_velocity - forward_impulse * wheel.radius / wheel.inertia;
+
+                    forward * forward_impulse + side * side_impulse
+                }
+            };
+            impulse.apply_impulse_at_point(tire_impulse, point, center_of_mass);
+
+            // Roll the wheel along the ground.
+            wheel.rotation_angle =
+                (wheel.rotation_angle + wheel.angular_velocity * delta_secs) % (2.0 * PI);
+
+            wheel.contact = Some(WheelContact {
+                entity: hit.entity,
+                point,
+                normal,
+                suspension_force,
+            });
+        }
+    }
+}
+
+/// Applies the drive and brake torques to the spin of a wheel, returning the new angular velocity.
+///
+/// The brakes can only stop the wheel, not reverse its spin.
+fn spin_wheel(
+    angular_velocity: Scalar,
+    drive_torque: Scalar,
+    max_brake_torque: Scalar,
+    inertia: Scalar,
+    delta_secs: Scalar,
+) -> Scalar {
+    let angular_velocity = angular_velocity + drive_torque / inertia * delta_secs;
+    let max_brake_change = max_brake_torque / inertia * delta_secs;
+    angular_velocity - angular_velocity.clamp(-max_brake_change, max_brake_change)
+}
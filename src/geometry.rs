@@ -0,0 +1,63 @@
+//! Stateless geometric queries between shapes, usable without a Bevy `App`.
+//!
+//! This module gathers the queries that only take [`Collider`]s and their poses,
+//! and don't need any ECS data. This makes them usable in tools, tests, and other code
+//! that runs outside of the physics simulation.
+//!
+//! | Query                         | Description                                                               |
+//! | ----------------------------- | ------------------------------------------------------------------------- |
+//! | [`contact`]                   | Computes one pair of contact points between two [`Collider`]s.            |
+//! | [`contact_manifolds`]         | Computes all [`ContactManifold`]s between two [`Collider`]s.              |
+//! | [`closest_points`]            | Computes the closest points between two [`Collider`]s.                    |
+//! | [`distance`]                  | Computes the minimum distance separating two [`Collider`]s.               |
+//! | [`intersection_test`]         | Tests whether two [`Collider`]s are intersecting each other.              |
+//! | [`time_of_impact`]            | Computes when two moving [`Collider`]s hit each other for the first time. |
+//! | [`Collider::cast_ray`]        | Computes the distance and normal where a ray hits a [`Collider`].         |
+//! | [`Collider::intersects_ray`]  | Tests whether a ray intersects a [`Collider`].                            |
+//! | [`Collider::project_point`]   | Projects a point onto a [`Collider`].                                     |
+//! | [`Collider::contains_point`]  | Tests whether a point is inside of a [`Collider`].                        |
+//!
+//! The pairwise queries are defined in [`contact_query`](crate::collision::contact_query),
+//! and are re-exported here together with the types they use.
+//!
+//! For geometric queries that query the entire world, see [spatial queries](crate::spatial_query).
+//!
+//! # Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::{geometry, prelude::*};")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::{geometry, prelude::*};")]
+//!
+//! # #[cfg(all(feature = "default-collider", feature = "f32"))]
+//! # {
+#![cfg_attr(feature = "2d", doc = "let ball = Collider::circle(0.5);")]
+#![cfg_attr(feature = "3d", doc = "let ball = Collider::sphere(0.5);")]
+//!
+//! // Two balls two units apart are separated by one unit.
+//! let rotation = Rotation::default();
+//! let distance = geometry::distance(
+//!     &ball,
+//!     Vector::ZERO,
+//!     rotation,
+//!     &ball,
+//!     Vector::X * 2.0,
+//!     rotation,
+//! )
+//! .unwrap();
+//! assert_eq!(distance, 1.0);
+//!
+//! // A ray cast from the left hits the ball at its surface.
+//! let (hit_distance, _normal) = ball
+//!     .cast_ray(Vector::ZERO, rotation, Vector::NEG_X * 2.0, Vector::X, 10.0, true)
+//!     .unwrap();
+//! assert_eq!(hit_distance, 1.5);
+//! # }
+//! ```
+
+pub use crate::collision::{
+    contact_query::{
+        closest_points, contact, contact_manifolds, distance, intersection_test, time_of_impact,
+        ClosestPoints, TimeOfImpact, TimeOfImpactStatus, UnsupportedShape,
+    },
+    Collider, ContactManifold, SingleContact,
+};
@@ -2,8 +2,8 @@
 //!
 //! See [`PhysicsInterpolationPlugin`].
 
-use bevy::{ecs::query::QueryData, prelude::*};
-use bevy_transform_interpolation::{prelude::*, VelocitySource};
+use bevy::{app::RunFixedMainLoopSystem, ecs::query::QueryData, prelude::*};
+use bevy_transform_interpolation::{prelude::*, TransformEasingSet, VelocitySource};
 
 pub use bevy_transform_interpolation::prelude::{
     NoRotationEasing, NoScaleEasing, NoTransformEasing, NoTranslationEasing, RotationExtrapolation,
@@ -168,6 +168,18 @@ use crate::prelude::*;
 ///
 /// Note that scale interpolation is always linear, and does not support Hermite interpolation.
 ///
+/// # Custom Physics Timestep
+///
+/// Easing is normally performed in between runs of the fixed timestep schedules, based on [`Time<Fixed>`](Fixed).
+/// If physics is configured to use its own [fixed timestep](PhysicsTime::with_fixed_timestep),
+/// interpolated rigid bodies are instead eased over the physics timestep, between their poses
+/// before and after the latest physics step. The interpolation factor is the time accumulated
+/// towards the next physics step, including the [overstep](PhysicsTime::overstep) of [`Time<Physics>`](Physics)
+/// and [`Time<Fixed>`](Fixed), divided by the physics timestep.
+///
+/// This only applies to interpolation of rigid bodies that have no parent.
+/// Extrapolation and Hermite interpolation still use the timestep of [`Time<Fixed>`](Fixed).
+///
 /// # General Interpolation or Extrapolation
 ///
 /// Avian uses [`bevy_transform_interpolation`] for interpolation and extrapolation.
@@ -287,14 +299,37 @@ impl Plugin for PhysicsInterpolationPlugin {
             let _ = app.try_register_required_components::<RigidBody, RotationExtrapolation>();
         }
 
-        // Update previous velocity components for Hermite interpolation.
+        // Store the poses of bodies before each physics step for easing over a custom physics timestep.
+        app.register_required_components::<TranslationInterpolation, PreviousPhysicsPose>();
+        app.register_required_components::<RotationInterpolation, PreviousPhysicsPose>();
+
+        // Update previous velocities for Hermite interpolation,
+        // and previous poses for easing over a custom physics timestep.
         app.add_systems(
             PhysicsSchedule,
-            update_previous_velocity.in_set(PhysicsStepSet::First),
+            (
+                update_previous_velocity,
+                update_previous_physics_pose.run_if(has_fixed_physics_timestep),
+            )
+                .in_set(PhysicsStepSet::First),
+        );
+
+        // Ease over the physics timestep instead of the fixed timestep if physics uses its own timestep.
+        app.add_systems(
+            RunFixedMainLoop,
+            ease_over_physics_timestep
+                .in_set(RunFixedMainLoopSystem::AfterFixedMainLoop)
+                .after(TransformEasingSet::Ease)
+                .run_if(has_fixed_physics_timestep),
         );
     }
 }
 
+/// The pose of a body before the latest physics step, used for easing over a
+/// [custom physics timestep](PhysicsInterpolationPlugin#custom-physics-timestep).
+#[derive(Component, Default)]
+struct PreviousPhysicsPose(Option<(Vector, Rotation)>);
+
 /// The previous linear velocity of an entity indicating its movement speed and direction during the previous frame.
 #[derive(Component, Default, Deref, DerefMut)]
 struct PreviousLinearVelocity(Vector);
@@ -376,3 +411,91 @@ fn update_previous_velocity(
         prev_ang_vel.0 = *ang_vel;
     }
 }
+
+/// A run condition that returns `true` if physics uses its own [fixed timestep](PhysicsTime::with_fixed_timestep).
+fn has_fixed_physics_timestep(time: Res<Time<Physics>>) -> bool {
+    time.fixed_timestep().is_some()
+}
+
+fn update_previous_physics_pose(
+    mut query: Query<(&Position, &Rotation, &mut PreviousPhysicsPose)>,
+) {
+    for (position, rotation, mut previous_pose) in &mut query {
+        previous_pose.0 = Some((position.0, *rotation));
+    }
+}
+
+/// Eases the transforms of interpolated rigid bodies over the physics timestep,
+/// overriding the easing performed by [`bevy_transform_interpolation`] over the timestep of [`Time<Fixed>`](Fixed).
+///
+/// The overridden transforms are reset to the poses of the bodies at the start of the next fixed tick,
+/// so this doesn't affect the simulation.
+#[allow(clippy::type_complexity)]
+fn ease_over_physics_timestep(
+    mut query: Query<
+        (
+            &mut Transform,
+            &Position,
+            &Rotation,
+            &PreviousPhysicsPose,
+            Has<TranslationInterpolation>,
+            Has<RotationInterpolation>,
+            Has<NoTransformEasing>,
+            Has<NoTranslationEasing>,
+            Has<NoRotationEasing>,
+        ),
+        (With<RigidBody>, Without<Parent>),
+    >,
+    physics_time: Res<Time<Physics>>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let Some(timestep) = physics_time.fixed_timestep() else {
+        return;
+    };
+
+    // The time accumulated towards the next physics step. Physics runs in the fixed timestep schedules,
+    // so the overstep of `Time<Fixed>` hasn't been added to the physics clock yet.
+    let overstep = physics_time.overstep() + fixed_time.overstep();
+    let t = (overstep.as_secs_f64() / timestep.as_secs_f64()).min(1.0) as Scalar;
+
+    for (
+        mut transform,
+        position,
+        rotation,
+        previous_pose,
+        interpolate_translation,
+        interpolate_rotation,
+        no_transform_easing,
+        no_translation_easing,
+        no_rotation_easing,
+    ) in &mut query
+    {
+        let Some((previous_position, previous_rotation)) = previous_pose.0 else {
+            continue;
+        };
+
+        if interpolate_translation && !no_transform_easing && !no_translation_easing {
+            let translation = previous_position.lerp(position.0, t).f32();
+            #[cfg(feature = "2d")]
+            {
+                transform.translation = translation.extend(transform.translation.z);
+            }
+            #[cfg(feature = "3d")]
+            {
+                transform.translation = translation;
+            }
+        }
+
+        if interpolate_rotation && !no_transform_easing && !no_rotation_easing {
+            let rotation = previous_rotation.slerp(*rotation, t);
+            #[cfg(feature = "2d")]
+            {
+                transform.rotation = Quaternion::from(rotation).f32();
+            }
+            #[cfg(feature = "3d")]
+            {
+                transform.rotation = rotation.f32();
+            }
+        }
+    }
+}
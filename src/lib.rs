@@ -64,6 +64,7 @@
 //! | `parallel`             | Enables some extra multithreading, which improves performance for larger simulations but can add some overhead for smaller ones.          | Yes                     |
 //! | `simd`                 | Enables [SIMD] optimizations.                                                                                                             | No                      |
 //! | `serialize`            | Enables support for serialization and deserialization using Serde.                                                                        | No                      |
+//! | `settings-asset`       | Enables loading and hot-reloading [`PhysicsSettings`](settings::PhysicsSettings) from RON files. Also enables the `serialize` feature.     | No                      |
 //!
 //! [SIMD]: https://en.wikipedia.org/wiki/Single_instruction,_multiple_data
 //!
@@ -132,6 +133,7 @@
 //!     - [Speculative collision](dynamics::ccd#speculative-collision)
 //!     - [Swept CCD](dynamics::ccd#swept-ccd)
 //! - [Temporarily disabling a rigid body](RigidBodyDisabled)
+//! - [Pausing the simulation of a rigid body](Frozen)
 //! - [Automatic deactivation with sleeping](Sleeping)
 //!
 //! See the [`dynamics`] module for more details about rigid body dynamics in Avian.
@@ -164,10 +166,16 @@
 //!     - [Prismatic joint](PrismaticJoint)
 //!     - [Revolute joint](RevoluteJoint)
 #![cfg_attr(feature = "3d", doc = "    - [Spherical joint](SphericalJoint)")]
+#![cfg_attr(feature = "3d", doc = "    - [Universal joint](UniversalJoint)")]
+//!     - [Gear joint](GearJoint) and [rack and pinion joint](RackAndPinionJoint)
 //! - [Temporarily disabling a joint](JointDisabled)
 //! - [Custom XPBD constraints](dynamics::solver::xpbd#constraints) (advanced)
 //!
-//! Joint motors and articulations are not supported yet, but they will be implemented in a future release.
+#![cfg_attr(
+    feature = "3d",
+    doc = "Joint motors are currently only supported by the [`UniversalJoint`] using an [`AngularMotor`]."
+)]
+//! Articulations are not supported yet, but they will be implemented in a future release.
 //!
 //! ### Spatial Queries
 //!
@@ -468,14 +476,24 @@ pub extern crate parry3d_f64 as parry;
 pub mod collision;
 #[cfg(feature = "debug-plugin")]
 pub mod debug_render;
+pub mod diagnostics;
 pub mod dynamics;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod geometry;
 pub mod interpolation;
 pub mod math;
 #[cfg(feature = "bevy_picking")]
 pub mod picking;
 pub mod position;
 pub mod prepare;
+#[cfg(feature = "bevy_scene")]
+pub mod scene;
 pub mod schedule;
+#[cfg(feature = "settings-asset")]
+pub mod settings;
 pub mod spatial_query;
 pub mod sync;
 
@@ -490,24 +508,39 @@ pub mod prelude {
     pub use crate::picking::{PhysicsPickable, PhysicsPickingPlugin, PhysicsPickingSettings};
     #[cfg(feature = "default-collider")]
     pub(crate) use crate::position::RotationValue;
+    #[cfg(feature = "bevy_scene")]
+    pub use crate::scene::PhysicsSceneExt;
+    #[cfg(feature = "settings-asset")]
+    pub use crate::settings::{PhysicsSettings, PhysicsSettingsPlugin};
     pub use crate::{
         collision::{
             self,
-            broad_phase::{BroadCollisionPairs, BroadPhasePlugin},
+            broad_phase::{
+                BroadCollisionPairs, BroadPhaseConfig, BroadPhaseMethod, BroadPhasePairFilter,
+                BroadPhasePairFilterPlugin, BroadPhasePlugin,
+            },
             collider::{ColliderBackendPlugin, ColliderHierarchyPlugin},
             contact_reporting::{
-                Collision, CollisionEnded, CollisionStarted, ContactReportingPlugin,
+                Collision, CollisionEnded, CollisionEventMode, CollisionEventsEnabled,
+                CollisionStarted, ContactReportingPlugin,
             },
             narrow_phase::{NarrowPhaseConfig, NarrowPhasePlugin},
             *,
         },
+        diagnostics::{
+            PhysicsCapture, PhysicsCapturePlugin, PhysicsChecksum, PhysicsChecksumPlugin,
+            PhysicsDebugExt,
+        },
         dynamics::{self, ccd::SpeculativeMargin, prelude::*},
         interpolation::*,
         position::{Position, Rotation},
-        prepare::{init_transforms, PrepareConfig, PreparePlugin},
+        prepare::{
+            init_transforms, InvalidPhysicsValue, PhysicsProperty, PrepareConfig, PrepareOverride,
+            PreparePlugin, ValidationConfig, ValidationMode,
+        },
         schedule::*,
         spatial_query::{self, *},
-        sync::SyncPlugin,
+        sync::{SyncMode, SyncPlugin},
         type_registration::PhysicsTypeRegistrationPlugin,
         PhysicsPlugins,
     };
@@ -549,8 +582,10 @@ use prelude::*;
 /// | [`SolverSchedulePlugin`]          | Sets up the solver and substepping loop by initializing the necessary schedules, sets and resources.                                                       |
 /// | [`IntegratorPlugin`]              | Handles motion caused by velocity, and applies external forces and gravity.                                                                                |
 /// | [`SolverPlugin`]                  | Manages and solves contacts, [joints](dynamics::solver::joints), and other constraints.                                                                    |
+/// | [`IslandPlugin`]                  | Splits bodies into independent [simulation islands](dynamics::islands) that can be solved in parallel.                                                     |
 /// | [`CcdPlugin`]                     | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component.                                           |
 /// | [`SleepingPlugin`]                | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                                                   |
+/// | [`RemoteBodyPlugin`]              | Moves [`RemoteBody`] entities along buffered authoritative state, for network interpolation.                                                               |
 /// | [`SpatialQueryPlugin`]            | Handles spatial queries like [raycasting](spatial_query#raycasting) and [shapecasting](spatial_query#shapecasting).                                        |
 /// | [`PhysicsInterpolationPlugin`]    | [`Transform`] interpolation and extrapolation for rigid bodies.                                                                                            |
 /// | [`SyncPlugin`]                    | Keeps [`Position`] and [`Rotation`] in sync with `Transform`.                                                                                              |
@@ -561,6 +596,7 @@ use prelude::*;
 /// | --------------------------------- | ---------------------------------------------------------------------------------------------------------------------------------------------------------- |
 /// | [`PhysicsPickingPlugin`]          | Enables a physics picking backend for [`bevy_picking`](bevy::picking) (only with `bevy_picking` feature enabled).                                          |
 /// | [`PhysicsDebugPlugin`]            | Renders physics objects and events like [AABBs](ColliderAabb) and [contacts](Collision) for debugging purposes (only with `debug-plugin` feature enabled). |
+/// | [`PhysicsChecksumPlugin`]         | Computes a [`PhysicsChecksum`] of rigid body state every step for detecting desyncs in lockstep networking.                                                |
 ///
 /// Refer to the documentation of the plugins for more information about their responsibilities and implementations.
 ///
@@ -762,8 +798,10 @@ impl PluginGroup for PhysicsPlugins {
             .add(IntegratorPlugin::default())
             .add(SolverPlugin::new_with_length_unit(self.length_unit))
             .add(SolverSchedulePlugin)
+            .add(IslandPlugin)
             .add(CcdPlugin)
             .add(SleepingPlugin)
+            .add(RemoteBodyPlugin)
             .add(SpatialQueryPlugin)
             .add(SyncPlugin::new(self.schedule))
             .add(PhysicsInterpolationPlugin::default())
@@ -0,0 +1,1067 @@
+//! Runs systems that prepare and initialize components used by physics.
+//!
+//! See [`PreparePlugin`].
+
+#![allow(clippy::type_complexity)]
+
+mod validation;
+
+pub use validation::{InvalidPhysicsValue, PhysicsProperty, ValidationConfig, ValidationMode};
+
+use crate::{
+    dynamics::rigid_body::{mass_properties::MassPropertySystems, missing_rigid_body_components},
+    prelude::*,
+    sync::SyncConfig,
+};
+use bevy::{
+    ecs::{intern::Interned, query::QueryFilter, schedule::ScheduleLabel},
+    math::Affine3A,
+    prelude::*,
+};
+use validation::{validate_damping, validate_density, validate_materials};
+
+/// Runs systems at the start of each physics frame. Initializes [rigid bodies](RigidBody)
+/// and updates components.
+///
+/// - Adds missing rigid body components for entities with a [`RigidBody`] component
+/// - Validates physics properties like [`Restitution`] and [`Friction`], see [`ValidationConfig`]
+///
+/// The [`Transform`] component will be initialized based on [`Position`] or [`Rotation`]
+/// and vice versa. This also happens when a [`Position`] or [`Rotation`] is inserted
+/// on an existing rigid body. You can configure this synchronization using the [`PrepareConfig`] resource.
+///
+/// The plugin takes a collider type. This should be [`Collider`] for
+/// the vast majority of applications, but for custom collisión backends
+/// you may use any collider that implements the [`AnyCollider`] trait.
+///
+/// The systems run in [`PhysicsSet::Prepare`].
+pub struct PreparePlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PreparePlugin {
+    /// Creates a [`PreparePlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `FixedPostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PreparePlugin {
+    fn default() -> Self {
+        Self::new(FixedPostUpdate)
+    }
+}
+
+/// Systems sets for initializing and syncing missing components.
+/// You can use these to schedule your own initialization systems
+/// without having to worry about implementation details.
+///
+/// 1. `First`: Runs at the start of the preparation step.
+/// 2. `PropagateTransforms`: Responsible for propagating transforms.
+/// 3. `InitTransforms`: Responsible for initializing [`Transform`] based on [`Position`] and [`Rotation`]
+///    or vice versa.
+/// 4. `Finalize`: Responsible for performing final updates after everything is initialized and updated.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrepareSet {
+    /// Runs at the start of the preparation step.
+    First,
+    /// Responsible for propagating transforms.
+    PropagateTransforms,
+    /// Responsible for initializing [`Transform`] based on [`Position`] and [`Rotation`]
+    /// or vice versa. Parts of this system can be disabled with [`PrepareConfig`].
+    /// Schedule your system with this to implement custom behavior for initializing transforms.
+    InitTransforms,
+    /// Responsible for performing final updates after everything is initialized.
+    /// Updates mass properties and validates physics properties like collider density and restitution
+    /// according to the [`ValidationConfig`].
+    Finalize,
+}
+
+impl Plugin for PreparePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SyncConfig>()
+            .register_type::<SyncConfig>();
+        app.configure_sets(
+            self.schedule,
+            (
+                PrepareSet::First,
+                PrepareSet::PropagateTransforms,
+                PrepareSet::InitTransforms,
+                PrepareSet::Finalize,
+            )
+                .chain()
+                .in_set(PhysicsSet::Prepare),
+        );
+
+        app.init_resource::<PrepareConfig>()
+            .register_type::<PrepareConfig>()
+            .register_type::<PrepareOverride>();
+
+        // Note: Collider logic is handled by the `ColliderBackendPlugin`
+        app.add_systems(
+            self.schedule,
+            // Run transform propagation if new bodies have been added
+            (
+                crate::sync::sync_simple_transforms_physics,
+                crate::sync::propagate_transforms_physics,
+            )
+                .chain()
+                .run_if(match_any::<Added<RigidBody>>)
+                .in_set(PrepareSet::PropagateTransforms),
+        )
+        .add_systems(
+            self.schedule,
+            (
+                init_transforms::<RigidBody>,
+                init_transforms_for_inserted_poses,
+            )
+                .chain()
+                .in_set(PrepareSet::InitTransforms),
+        )
+        .add_systems(
+            self.schedule,
+            report_missing_components.in_set(PrepareSet::Finalize),
+        );
+
+        app.init_resource::<ValidationConfig>()
+            .register_type::<ValidationConfig>()
+            .add_event::<InvalidPhysicsValue>();
+
+        // Validate physics properties before they are used for computing mass properties.
+        app.add_systems(
+            self.schedule,
+            (validate_materials, validate_density, validate_damping)
+                .in_set(PrepareSet::Finalize)
+                .before(MassPropertySystems::UpdateColliderMassProperties),
+        );
+    }
+}
+
+/// Configures what is initialized by the [`PreparePlugin`] and how.
+///
+/// This can be overridden for specific entities using the [`PrepareOverride`] component.
+#[derive(Resource, Reflect, Clone, Debug, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct PrepareConfig {
+    /// Initializes [`Transform`] based on [`Position`] and [`Rotation`].
+    /// Defaults to true.
+    pub position_to_transform: bool,
+    /// Initializes [`Position`] and [`Rotation`] based on [`Transform`].
+    /// Defaults to true.
+    pub transform_to_position: bool,
+}
+
+impl Default for PrepareConfig {
+    fn default() -> Self {
+        PrepareConfig {
+            position_to_transform: true,
+            transform_to_position: true,
+        }
+    }
+}
+
+/// Overrides the global [`PrepareConfig`] for a specific entity.
+///
+/// This can be used when entities that are authored in scenes and entities spawned
+/// by other means, for example over the network, need their transforms to be initialized differently.
+///
+/// [`PrepareOverride::DISABLED`] can be used to opt out of automatically inserting the [`Position`]
+/// and [`Rotation`] of a [rigid body](RigidBody), for example when they are provided by other means.
+/// They must then be inserted manually before the next physics step, or an error is logged.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn spawn_networked_body(mut commands: Commands) {
+///     // The position is authoritative and received from the server,
+///     // so the `Transform` should be initialized from it, not the other way around.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Position::from_xy(5.0, 2.0),")]
+#[cfg_attr(feature = "3d", doc = "        Position::from_xyz(5.0, 2.0, 0.0),")]
+///         PrepareOverride::POSITION_TO_TRANSFORM,
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct PrepareOverride {
+    /// Initializes [`Transform`] based on [`Position`] and [`Rotation`].
+    pub position_to_transform: bool,
+    /// Initializes [`Position`] and [`Rotation`] based on [`Transform`].
+    pub transform_to_position: bool,
+}
+
+impl PrepareOverride {
+    /// Opts the entity out of transform initialization entirely.
+    pub const DISABLED: Self = Self {
+        position_to_transform: false,
+        transform_to_position: false,
+    };
+
+    /// Only initializes [`Transform`] based on [`Position`] and [`Rotation`].
+    pub const POSITION_TO_TRANSFORM: Self = Self {
+        position_to_transform: true,
+        transform_to_position: false,
+    };
+
+    /// Only initializes [`Position`] and [`Rotation`] based on [`Transform`].
+    pub const TRANSFORM_TO_POSITION: Self = Self {
+        position_to_transform: false,
+        transform_to_position: true,
+    };
+}
+
+impl From<PrepareOverride> for PrepareConfig {
+    fn from(value: PrepareOverride) -> Self {
+        Self {
+            position_to_transform: value.position_to_transform,
+            transform_to_position: value.transform_to_position,
+        }
+    }
+}
+
+/// A run condition that returns `true` if any entity matches the given query filter.
+pub(crate) fn match_any<F: QueryFilter>(query: Query<(), F>) -> bool {
+    !query.is_empty()
+}
+
+type InitTransformsQueryData = (
+    Entity,
+    Option<&'static Transform>,
+    Option<&'static GlobalTransform>,
+    Option<&'static Position>,
+    Option<&'static Rotation>,
+    Option<&'static PreviousRotation>,
+    Option<&'static Parent>,
+    Has<RigidBody>,
+    Option<&'static PrepareOverride>,
+);
+
+type InitTransformsQueryItem<'w> = (
+    Entity,
+    Option<&'w Transform>,
+    Option<&'w GlobalTransform>,
+    Option<&'w Position>,
+    Option<&'w Rotation>,
+    Option<&'w PreviousRotation>,
+    Option<&'w Parent>,
+    bool,
+    Option<&'w PrepareOverride>,
+);
+
+type InitTransformsParentQueryData = (
+    Option<&'static Position>,
+    Option<&'static Rotation>,
+    Option<&'static GlobalTransform>,
+);
+
+/// Initializes [`Transform`] based on [`Position`] and [`Rotation`] or vice versa
+/// when a component of the given type is inserted.
+///
+/// For child entities, the translation, rotation, and scale of the parent are taken into account
+/// when converting between the local `Transform` and the global [`Position`] and [`Rotation`].
+/// A warning is logged once if a parent has shear, which cannot be represented accurately.
+pub fn init_transforms<C: Component>(
+    mut commands: Commands,
+    config: Res<PrepareConfig>,
+    query: Query<InitTransformsQueryData, Added<C>>,
+    parents: Query<InitTransformsParentQueryData, With<Children>>,
+) {
+    initialize_transforms(&mut commands, &config, query.iter(), &parents);
+}
+
+/// Initializes [`Transform`] based on [`Position`] and [`Rotation`] or vice versa
+/// when a [`Position`] or [`Rotation`] is inserted on an existing [rigid body](RigidBody).
+///
+/// This allows physics to be attached to existing entities at runtime, or detached and reattached
+/// by removing and reinserting the components. Rigid bodies added this frame are
+/// handled by [`init_transforms`] instead.
+pub fn init_transforms_for_inserted_poses(
+    mut commands: Commands,
+    config: Res<PrepareConfig>,
+    query: Query<(InitTransformsQueryData, Ref<RigidBody>), Or<(Added<Position>, Added<Rotation>)>>,
+    parents: Query<InitTransformsParentQueryData, With<Children>>,
+) {
+    let inserted = query
+        .iter()
+        .filter(|(_, rb)| !rb.is_added())
+        .map(|(data, _)| data);
+    initialize_transforms(&mut commands, &config, inserted, &parents);
+}
+
+/// Logs an error for new [rigid bodies](RigidBody) that are missing any of their
+/// [required components](RigidBody#required-components) after transform initialization,
+/// for example because they opted out with [`PrepareOverride`] or [`NoAutoRigidBodyComponents`].
+///
+/// Such bodies are not simulated, so this is most likely a mistake.
+fn report_missing_components(query: Query<EntityRef, Added<RigidBody>>) {
+    for entity in &query {
+        let missing = missing_rigid_body_components(entity);
+        if missing.is_empty() {
+            continue;
+        }
+        error!(
+            "Rigid body {} is missing {} and will not be simulated. \
+            Insert the components manually when opting out of their initialization \
+            with `PrepareOverride` or `NoAutoRigidBodyComponents`.",
+            entity.id(),
+            missing.join(", ")
+        );
+    }
+}
+
+fn initialize_transforms<'w>(
+    commands: &mut Commands,
+    config: &PrepareConfig,
+    query: impl IntoIterator<Item = InitTransformsQueryItem<'w>>,
+    parents: &Query<InitTransformsParentQueryData, With<Children>>,
+) {
+    for (
+        entity,
+        transform,
+        global_transform,
+        pos,
+        rot,
+        previous_rot,
+        parent,
+        has_rigid_body,
+        prepare_override,
+    ) in query
+    {
+        // Entities can override the global configuration.
+        let config = prepare_override.map_or_else(|| config.clone(), |&o| PrepareConfig::from(o));
+
+        if !config.position_to_transform && !config.transform_to_position {
+            // Nothing to do
+            continue;
+        }
+
+        // The global transform of the parent, if the entity has one.
+        // This takes the parent's scale into account.
+        let parent_transform = parent
+            .and_then(|parent| parents.get(parent.get()).ok())
+            .map(|(parent_pos, parent_rot, parent_global_trans)| {
+                if parent_global_trans.is_some_and(has_shear) {
+                    warn_once!(
+                        "The parent of entity {entity} has a sheared `GlobalTransform`, \
+                        likely caused by non-uniform scaling of a rotated hierarchy. \
+                        Physics transforms may be initialized incorrectly."
+                    );
+                }
+                parent_global_transform(parent_pos, parent_rot, parent_global_trans)
+            });
+
+        let mut new_transform = if config.position_to_transform {
+            Some(transform.copied().unwrap_or_default())
+        } else {
+            None
+        };
+
+        // Compute Transform based on Position or vice versa
+        let new_position = if let Some(pos) = pos {
+            if let Some(transform) = &mut new_transform {
+                // Initialize new translation as global position
+                #[cfg(feature = "2d")]
+                let mut new_translation = pos.f32().extend(transform.translation.z);
+                #[cfg(feature = "3d")]
+                let mut new_translation = pos.f32();
+
+                // If the body is a child, transform the global position
+                // into the local space of the parent
+                if let Some(parent_transform) = parent_transform {
+                    #[cfg(feature = "2d")]
+                    {
+                        // Keep the local z coordinate as is.
+                        let (parent_scale, _, parent_translation) =
+                            parent_transform.to_scale_rotation_translation();
+                        new_translation.z =
+                            parent_translation.z + transform.translation.z * parent_scale.z;
+                    }
+                    new_translation = parent_transform
+                        .affine()
+                        .inverse()
+                        .transform_point3(new_translation);
+                }
+                transform.translation = new_translation;
+            }
+            pos.0
+        } else if config.transform_to_position {
+            let translation = transform.map_or_else(
+                || global_transform.map_or(Vec3::ZERO, |t| t.translation()),
+                |t| match parent_transform {
+                    // Transform the local translation into global space
+                    Some(parent_transform) => parent_transform.transform_point(t.translation),
+                    None => t.translation,
+                },
+            );
+
+            #[cfg(feature = "2d")]
+            {
+                translation.truncate().adjust_precision()
+            }
+            #[cfg(feature = "3d")]
+            {
+                translation.adjust_precision()
+            }
+        } else {
+            default()
+        };
+
+        // Compute Transform based on Rotation or vice versa
+        let new_rotation = if let Some(rot) = rot {
+            if let Some(transform) = &mut new_transform {
+                // Initialize new rotation as global rotation
+                let mut new_rotation = Quaternion::from(*rot).f32();
+
+                // If the body is a child, subtract the parent's global rotation
+                // to get the local rotation
+                if let Some(parent_transform) = parent_transform {
+                    new_rotation = parent_transform.rotation().inverse() * new_rotation;
+                }
+                transform.rotation = new_rotation;
+            }
+            *rot
+        } else if config.transform_to_position {
+            transform.map_or_else(
+                || global_transform.map_or(Rotation::default(), |t| t.rotation().into()),
+                |t| match parent_transform {
+                    // Add the parent's global rotation to get the global rotation
+                    Some(parent_transform) => (parent_transform.rotation() * t.rotation).into(),
+                    None => t.rotation.into(),
+                },
+            )
+        } else {
+            default()
+        };
+
+        let mut cmds = commands.entity(entity);
+
+        // Insert the position and rotation.
+        // The values are either unchanged (Position and Rotation already exist)
+        // or computed based on the GlobalTransform.
+        // If the entity isn't a rigid body, adding PreSolveAccumulatedTranslation and PreviousRotation
+        // is unnecessary.
+        match (has_rigid_body, new_transform) {
+            (true, None) => {
+                cmds.try_insert((
+                    Position(new_position),
+                    new_rotation,
+                    PreSolveAccumulatedTranslation::default(),
+                    *previous_rot.unwrap_or(&PreviousRotation(new_rotation)),
+                    PreSolveRotation::default(),
+                ));
+            }
+            (true, Some(transform)) => {
+                cmds.try_insert((
+                    transform,
+                    Position(new_position),
+                    new_rotation,
+                    PreSolveAccumulatedTranslation::default(),
+                    *previous_rot.unwrap_or(&PreviousRotation(new_rotation)),
+                    PreSolveRotation::default(),
+                ));
+            }
+            (false, None) => {
+                cmds.try_insert((Position(new_position), new_rotation));
+            }
+            (false, Some(transform)) => {
+                cmds.try_insert((transform, Position(new_position), new_rotation));
+            }
+        }
+    }
+}
+
+/// Computes the global transform of a parent for initializing the transforms of its children.
+///
+/// The [`Position`] and [`Rotation`] of the parent are used if it has them, as its `GlobalTransform`
+/// may not be up to date yet. The scale is always taken from the `GlobalTransform`.
+fn parent_global_transform(
+    position: Option<&Position>,
+    rotation: Option<&Rotation>,
+    global_transform: Option<&GlobalTransform>,
+) -> GlobalTransform {
+    let (scale, global_rotation, global_translation) = global_transform
+        .map_or((Vec3::ONE, Quat::IDENTITY, Vec3::ZERO), |transform| {
+            transform.to_scale_rotation_translation()
+        });
+
+    #[cfg(feature = "2d")]
+    let translation = position.map_or(global_translation, |pos| {
+        pos.f32().extend(global_translation.z)
+    });
+    #[cfg(feature = "3d")]
+    let translation = position.map_or(global_translation, |pos| pos.f32());
+    let rotation = rotation.map_or(global_rotation, |rot| Quaternion::from(*rot).f32());
+
+    GlobalTransform::from(Transform {
+        translation,
+        rotation,
+        scale,
+    })
+}
+
+/// Returns `true` if the given transform has shear, which cannot be represented
+/// by a translation, rotation, and scale.
+fn has_shear(transform: &GlobalTransform) -> bool {
+    let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+    let recomposed = Affine3A::from_scale_rotation_translation(scale, rotation, translation);
+    let tolerance = 1e-4 * scale.abs().max_element().max(1.0);
+    !recomposed
+        .matrix3
+        .abs_diff_eq(transform.affine().matrix3, tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_transforms_basics() {
+        let mut app = App::new();
+
+        // Add system under test
+        app.add_systems(Update, init_transforms::<RigidBody>);
+
+        // Test all possible config permutations
+        for (position_to_transform, transform_to_position) in
+            [(true, true), (true, false), (false, true), (false, false)]
+        {
+            let config = PrepareConfig {
+                position_to_transform,
+                transform_to_position,
+            };
+            app.insert_resource(dbg!(config.clone()));
+
+            // Spawn entities with `Position` and `Rotation`
+            let (pos_0, rot_0) = {
+                #[cfg(feature = "2d")]
+                {
+                    (Position::from_xy(1., 2.), Rotation::radians(0.5))
+                }
+                #[cfg(feature = "3d")]
+                {
+                    (
+                        Position::from_xyz(1., 2., 3.),
+                        Rotation(Quaternion::from_axis_angle(Vector::Y, 0.5)),
+                    )
+                }
+            };
+            let e_0_with_pos_and_rot = app
+                .world_mut()
+                .spawn((RigidBody::Dynamic, pos_0, rot_0))
+                .id();
+
+            let (pos_1, rot_1) = {
+                #[cfg(feature = "2d")]
+                {
+                    (Position::from_xy(-1., 3.), Rotation::radians(0.1))
+                }
+                #[cfg(feature = "3d")]
+                {
+                    (
+                        Position::from_xyz(-1., 3., -3.),
+                        Rotation(Quaternion::from_axis_angle(Vector::X, 0.1)),
+                    )
+                }
+            };
+            let e_1_with_pos_and_rot = app
+                .world_mut()
+                .spawn((RigidBody::Dynamic, pos_1, rot_1))
+                .id();
+
+            // Spawn an entity with only `Position`
+            let pos_2 = {
+                #[cfg(feature = "2d")]
+                {
+                    Position::from_xy(10., 1.)
+                }
+                #[cfg(feature = "3d")]
+                {
+                    Position::from_xyz(10., 1., 5.)
+                }
+            };
+            let e_2_with_pos = app.world_mut().spawn((RigidBody::Dynamic, pos_2)).id();
+
+            // Spawn an entity with only `Rotation`
+            let rot_3 = {
+                #[cfg(feature = "2d")]
+                {
+                    Rotation::radians(0.4)
+                }
+                #[cfg(feature = "3d")]
+                {
+                    Rotation(Quaternion::from_axis_angle(Vector::Z, 0.4))
+                }
+            };
+            let e_3_with_rot = app.world_mut().spawn((RigidBody::Dynamic, rot_3)).id();
+
+            // Spawn entities with `Transform`
+            let trans_4 = {
+                Transform {
+                    translation: Vec3::new(-1.1, 6., -7.),
+                    rotation: Quat::from_axis_angle(Vec3::Y, 0.1),
+                    scale: Vec3::ONE,
+                }
+            };
+            let e_4_with_trans = app.world_mut().spawn((RigidBody::Dynamic, trans_4)).id();
+
+            let trans_5 = {
+                Transform {
+                    translation: Vec3::new(8., -1., 0.),
+                    rotation: Quat::from_axis_angle(Vec3::Y, -0.1),
+                    scale: Vec3::ONE,
+                }
+            };
+            let e_5_with_trans = app.world_mut().spawn((RigidBody::Dynamic, trans_5)).id();
+
+            // Spawn entity without any transforms
+            let e_6_without_trans = app.world_mut().spawn(RigidBody::Dynamic).id();
+
+            // Spawn entity without a ridid body
+            let e_7_without_rb = app.world_mut().spawn(()).id();
+
+            // Run the system
+            app.update();
+
+            // Check the results are as expected
+            if config.position_to_transform {
+                assert!(app.world().get::<Transform>(e_0_with_pos_and_rot).is_some());
+                let transform = app.world().get::<Transform>(e_0_with_pos_and_rot).unwrap();
+                let expected: Vec3 = {
+                    #[cfg(feature = "2d")]
+                    {
+                        pos_0.f32().extend(0.)
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        pos_0.f32()
+                    }
+                };
+                assert_eq!(transform.translation, expected);
+                let expected = Quaternion::from(rot_0).f32();
+                assert_eq!(transform.rotation, expected);
+
+                assert!(app.world().get::<Transform>(e_1_with_pos_and_rot).is_some());
+                let transform = app.world().get::<Transform>(e_1_with_pos_and_rot).unwrap();
+                let expected: Vec3 = {
+                    #[cfg(feature = "2d")]
+                    {
+                        pos_1.f32().extend(0.)
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        pos_1.f32()
+                    }
+                };
+                assert_eq!(transform.translation, expected);
+                let expected = Quaternion::from(rot_1).f32();
+                assert_eq!(transform.rotation, expected);
+
+                assert!(app.world().get::<Transform>(e_2_with_pos).is_some());
+                let transform = app.world().get::<Transform>(e_2_with_pos).unwrap();
+                let expected: Vec3 = {
+                    #[cfg(feature = "2d")]
+                    {
+                        pos_2.f32().extend(0.)
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        pos_2.f32()
+                    }
+                };
+                assert_eq!(transform.translation, expected);
+                let expected = Quat::default();
+                assert_eq!(transform.rotation, expected);
+
+                assert!(app.world().get::<Transform>(e_3_with_rot).is_some());
+                let transform = app.world().get::<Transform>(e_3_with_rot).unwrap();
+                let expected: Vec3 = Vec3::default();
+                assert_eq!(transform.translation, expected);
+                let expected = Quaternion::from(rot_3).f32();
+                assert_eq!(transform.rotation, expected);
+
+                assert!(app.world().get::<Transform>(e_4_with_trans).is_some());
+                let transform = app.world().get::<Transform>(e_4_with_trans).unwrap();
+                assert_eq!(transform, &trans_4);
+
+                assert!(app.world().get::<Transform>(e_5_with_trans).is_some());
+                let transform = app.world().get::<Transform>(e_5_with_trans).unwrap();
+                assert_eq!(transform, &trans_5);
+
+                assert!(app.world().get::<Transform>(e_6_without_trans).is_some());
+                let transform = app.world().get::<Transform>(e_6_without_trans).unwrap();
+                assert_eq!(transform, &Transform::default());
+
+                assert!(app.world().get::<Transform>(e_7_without_rb).is_none());
+            }
+
+            if config.transform_to_position {
+                assert!(app.world().get::<Position>(e_0_with_pos_and_rot).is_some());
+                let pos = app.world().get::<Position>(e_0_with_pos_and_rot).unwrap();
+                assert_eq!(pos, &pos_0);
+                assert!(app.world().get::<Rotation>(e_0_with_pos_and_rot).is_some());
+                let rot = app.world().get::<Rotation>(e_0_with_pos_and_rot).unwrap();
+                assert_eq!(rot, &rot_0);
+
+                assert!(app.world().get::<Position>(e_1_with_pos_and_rot).is_some());
+                let pos = app.world().get::<Position>(e_1_with_pos_and_rot).unwrap();
+                assert_eq!(pos, &pos_1);
+                assert!(app.world().get::<Rotation>(e_1_with_pos_and_rot).is_some());
+                let rot = app.world().get::<Rotation>(e_1_with_pos_and_rot).unwrap();
+                assert_eq!(rot, &rot_1);
+
+                assert!(app.world().get::<Position>(e_2_with_pos).is_some());
+                let pos = app.world().get::<Position>(e_2_with_pos).unwrap();
+                assert_eq!(pos, &pos_2);
+                assert!(app.world().get::<Rotation>(e_2_with_pos).is_some());
+                let rot = app.world().get::<Rotation>(e_2_with_pos).unwrap();
+                assert_eq!(rot, &Rotation::default());
+
+                assert!(app.world().get::<Position>(e_3_with_rot).is_some());
+                let pos = app.world().get::<Position>(e_3_with_rot).unwrap();
+                assert_eq!(pos, &Position::default());
+                assert!(app.world().get::<Rotation>(e_3_with_rot).is_some());
+                let rot = app.world().get::<Rotation>(e_3_with_rot).unwrap();
+                assert_eq!(rot, &rot_3);
+
+                assert!(app.world().get::<Position>(e_4_with_trans).is_some());
+                let pos = app.world().get::<Position>(e_4_with_trans).unwrap();
+                let expected: Position = Position::new({
+                    #[cfg(feature = "2d")]
+                    {
+                        trans_4.translation.truncate().adjust_precision()
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        trans_4.translation.adjust_precision()
+                    }
+                });
+                assert_eq!(pos, &expected);
+                assert!(app.world().get::<Rotation>(e_4_with_trans).is_some());
+                let rot = app.world().get::<Rotation>(e_4_with_trans).unwrap();
+                assert_eq!(rot, &Rotation::from(trans_4.rotation));
+
+                assert!(app.world().get::<Position>(e_5_with_trans).is_some());
+                let pos = app.world().get::<Position>(e_5_with_trans).unwrap();
+                let expected: Position = Position::new({
+                    #[cfg(feature = "2d")]
+                    {
+                        trans_5.translation.truncate().adjust_precision()
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        trans_5.translation.adjust_precision()
+                    }
+                });
+                assert_eq!(pos, &expected);
+                assert!(app.world().get::<Rotation>(e_5_with_trans).is_some());
+                let rot = app.world().get::<Rotation>(e_5_with_trans).unwrap();
+                assert_eq!(rot, &Rotation::from(trans_5.rotation));
+
+                assert!(app.world().get::<Position>(e_6_without_trans).is_some());
+                let pos = app.world().get::<Position>(e_6_without_trans).unwrap();
+                assert_eq!(pos, &Position::default());
+                assert!(app.world().get::<Rotation>(e_6_without_trans).is_some());
+                let rot = app.world().get::<Rotation>(e_6_without_trans).unwrap();
+                assert_eq!(rot, &Rotation::default());
+
+                assert!(app.world().get::<Position>(e_7_without_rb).is_none());
+                assert!(app.world().get::<Rotation>(e_7_without_rb).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_init_transforms_scaled_parent() {
+        let mut app = App::new();
+
+        app.init_resource::<PrepareConfig>();
+        app.add_systems(Update, init_transforms::<RigidBody>);
+
+        let parent_transform = Transform::from_xyz(1.0, 0.0, 0.0)
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+            .with_scale(Vec3::new(2.0, 4.0, 1.0));
+        let parent = app
+            .world_mut()
+            .spawn((parent_transform, GlobalTransform::from(parent_transform)))
+            .id();
+
+        // A child with a global `Position` but no `Transform`
+        #[cfg(feature = "2d")]
+        let position = Position::from_xy(1.0, 4.0);
+        #[cfg(feature = "3d")]
+        let position = Position::from_xyz(1.0, 4.0, 0.0);
+        let child_with_pos = app.world_mut().spawn((RigidBody::Dynamic, position)).id();
+
+        // A child with a local `Transform` but no `Position`
+        let child_with_trans = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, Transform::from_xyz(2.0, 0.0, 0.0)))
+            .id();
+
+        app.world_mut()
+            .entity_mut(parent)
+            .add_children(&[child_with_pos, child_with_trans]);
+
+        app.update();
+
+        // The parent is rotated by 90 degrees and scaled by 2 along its local X axis,
+        // so a local offset of 2 along X is a global offset of 4 along Y.
+        let transform = app.world().get::<Transform>(child_with_pos).unwrap();
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1e-5));
+
+        let position = app.world().get::<Position>(child_with_trans).unwrap();
+        #[cfg(feature = "2d")]
+        assert!(position.abs_diff_eq(Vector::new(1.0, 4.0), 1e-5));
+        #[cfg(feature = "3d")]
+        assert!(position.abs_diff_eq(Vector::new(1.0, 4.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn test_init_transforms_for_inserted_poses() {
+        let mut app = App::new();
+
+        app.init_resource::<PrepareConfig>();
+        app.add_systems(
+            Update,
+            (
+                init_transforms::<RigidBody>,
+                init_transforms_for_inserted_poses,
+            )
+                .chain(),
+        );
+
+        let entity = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, Transform::from_xyz(1.0, 0.0, 0.0)))
+            .id();
+
+        app.update();
+
+        assert_eq!(app.world().get::<Position>(entity).unwrap().x, 1.0);
+
+        // Detach physics by removing the position, and reattach it somewhere else.
+        app.world_mut().entity_mut(entity).remove::<Position>();
+        app.update();
+        #[cfg(feature = "2d")]
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Position::from_xy(5.0, 0.0));
+        #[cfg(feature = "3d")]
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Position::from_xyz(5.0, 0.0, 0.0));
+        app.update();
+
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation, Vec3::new(5.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_init_transforms_prepare_override() {
+        let mut app = App::new();
+
+        app.init_resource::<PrepareConfig>();
+        app.add_systems(Update, init_transforms::<RigidBody>);
+
+        #[cfg(feature = "2d")]
+        let position = Position::from_xy(5.0, 0.0);
+        #[cfg(feature = "3d")]
+        let position = Position::from_xyz(5.0, 0.0, 0.0);
+
+        let default_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                position,
+            ))
+            .id();
+        let disabled_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                position,
+                PrepareOverride::DISABLED,
+            ))
+            .id();
+
+        app.update();
+
+        let transform = app.world().get::<Transform>(default_entity).unwrap();
+        assert_eq!(transform.translation, Vec3::new(5.0, 0.0, 0.0));
+
+        // The entity opted out, so its transform is left as is.
+        let transform = app.world().get::<Transform>(disabled_entity).unwrap();
+        assert_eq!(transform.translation, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_prepare_override_disabled_skips_insertion() {
+        let mut app = App::new();
+
+        app.init_resource::<PrepareConfig>();
+        app.add_systems(Update, init_transforms::<RigidBody>);
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                PrepareOverride::DISABLED,
+            ))
+            .id();
+
+        app.update();
+
+        assert!(app.world().get::<Position>(entity).is_none());
+        assert!(app.world().get::<Rotation>(entity).is_none());
+    }
+
+    #[test]
+    fn test_no_auto_rigid_body_components() {
+        let mut app = App::new();
+
+        let default_entity = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, LinearVelocity(Vector::X)))
+            .id();
+        let opted_out_entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                LinearVelocity(Vector::X),
+                NoAutoRigidBodyComponents,
+            ))
+            .id();
+
+        // Apply the commands queued by the component hooks.
+        app.world_mut().flush();
+
+        // The missing components are inserted, and existing ones are left as is.
+        let default_entity = app.world().entity(default_entity);
+        assert!(default_entity.contains::<AngularVelocity>());
+        assert_eq!(
+            default_entity.get::<LinearVelocity>(),
+            Some(&LinearVelocity(Vector::X))
+        );
+        assert_eq!(
+            missing_rigid_body_components(default_entity),
+            vec!["Position", "Rotation"]
+        );
+
+        // The entity opted out, so all of the components were removed.
+        let opted_out = app.world().entity(opted_out_entity);
+        assert!(!opted_out.contains::<LinearVelocity>());
+        assert!(!opted_out.contains::<AngularVelocity>());
+        assert!(!opted_out.contains::<ComputedMass>());
+
+        // Components inserted afterwards are kept.
+        app.world_mut()
+            .entity_mut(opted_out_entity)
+            .insert(LinearVelocity(Vector::X));
+        app.world_mut().flush();
+
+        let missing = missing_rigid_body_components(app.world().entity(opted_out_entity));
+        assert!(missing.contains(&"AngularVelocity"));
+        assert!(missing.contains(&"AccumulatedTranslation"));
+        assert!(!missing.contains(&"LinearVelocity"));
+    }
+
+    #[test]
+    fn test_validate_materials() {
+        let mut app = App::new();
+
+        app.init_resource::<ValidationConfig>()
+            .add_event::<InvalidPhysicsValue>()
+            .add_systems(Update, validate_materials);
+
+        let entity = app
+            .world_mut()
+            .spawn((Restitution::new(1.5), Friction::new(0.5)))
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Restitution>(entity).unwrap().coefficient,
+            1.0
+        );
+
+        let events = app.world().resource::<Events<InvalidPhysicsValue>>();
+        let mut reader = events.get_cursor();
+        let invalid_values: Vec<_> = reader.read(events).copied().collect();
+        assert_eq!(
+            invalid_values,
+            vec![InvalidPhysicsValue {
+                entity,
+                property: PhysicsProperty::Restitution,
+                value: 1.5,
+                corrected: 1.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validation_can_be_disabled() {
+        let mut app = App::new();
+
+        app.insert_resource(ValidationConfig {
+            materials: false,
+            ..default()
+        })
+        .add_event::<InvalidPhysicsValue>()
+        .add_systems(Update, (validate_materials, validate_damping));
+
+        let entity = app
+            .world_mut()
+            .spawn((Restitution::new(1.5), LinearDamping(-1.0)))
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Restitution>(entity).unwrap().coefficient,
+            1.5
+        );
+        assert_eq!(app.world().get::<LinearDamping>(entity).unwrap().0, 0.0);
+    }
+
+    #[test]
+    fn test_validate_density_is_positive() {
+        let mut app = App::new();
+
+        app.init_resource::<ValidationConfig>()
+            .add_event::<InvalidPhysicsValue>()
+            .add_systems(Update, validate_density);
+
+        let zero = app.world_mut().spawn(ColliderDensity(0.0)).id();
+        let negative = app.world_mut().spawn(ColliderDensity(-2.0)).id();
+        let valid = app.world_mut().spawn(ColliderDensity(2.0)).id();
+
+        app.update();
+
+        for entity in [zero, negative] {
+            let density = app.world().get::<ColliderDensity>(entity).unwrap().0;
+            assert!(density > 0.0);
+        }
+        assert_eq!(app.world().get::<ColliderDensity>(valid).unwrap().0, 2.0);
+
+        let events = app.world().resource::<Events<InvalidPhysicsValue>>();
+        assert_eq!(events.len(), 2);
+    }
+}
@@ -0,0 +1,308 @@
+//! Validation of physics properties like [`Restitution`] and [`Friction`].
+//!
+//! See [`ValidationConfig`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Configures how the [`PreparePlugin`] handles invalid values of physics properties,
+/// like a negative [`Friction`] or a [`Restitution`] larger than `1.0`.
+///
+/// Invalid values are always clamped to the closest valid value, as they could otherwise
+/// cause unstable or explosive behavior. The [`ValidationMode`] determines whether this
+/// happens silently or if the invalid values are also reported. This can help catch
+/// typos and other mistakes in physics configurations.
+///
+/// The following properties are validated when they are added or changed:
+///
+/// - [`Restitution`] coefficients must be between `0.0` and `1.0`.
+/// - [`Friction`] coefficients must be non-negative.
+/// - [`ColliderDensity`] must be positive.
+/// - [`LinearDamping`] and [`AngularDamping`] must be non-negative.
+///
+/// Validation can be disabled for each group of properties, for example if the values
+/// are intentionally out of range or are already validated elsewhere.
+///
+/// Validation runs in [`PrepareSet::Finalize`].
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(ValidationConfig {
+///             // Panic on invalid values in debug builds.
+///             mode: ValidationMode::Strict,
+///             // Allow super bouncy materials.
+///             materials: false,
+///             ..default()
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct ValidationConfig {
+    /// How invalid values are reported.
+    ///
+    /// Default: [`ValidationMode::Warn`]
+    pub mode: ValidationMode,
+    /// Whether [`Restitution`] and [`Friction`] coefficients are validated.
+    ///
+    /// Default: `true`
+    pub materials: bool,
+    /// Whether [`ColliderDensity`] is validated.
+    ///
+    /// Default: `true`
+    pub density: bool,
+    /// Whether [`LinearDamping`] and [`AngularDamping`] are validated.
+    ///
+    /// Default: `true`
+    pub damping: bool,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            mode: ValidationMode::default(),
+            materials: true,
+            density: true,
+            damping: true,
+        }
+    }
+}
+
+/// Determines how invalid values of physics properties are reported. See [`ValidationConfig`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum ValidationMode {
+    /// Invalid values are clamped silently.
+    Clamp,
+    /// Invalid values are clamped, and an [`InvalidPhysicsValue`] event is sent and a warning is logged.
+    #[default]
+    Warn,
+    /// Like [`ValidationMode::Warn`], but panics in debug builds to make mistakes impossible to miss.
+    Strict,
+}
+
+/// A physics property validated according to the [`ValidationConfig`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum PhysicsProperty {
+    /// The [`Restitution::coefficient`].
+    Restitution,
+    /// The [`Friction::dynamic_coefficient`].
+    DynamicFriction,
+    /// The [`Friction::static_coefficient`].
+    StaticFriction,
+    /// The [`ColliderDensity`].
+    ColliderDensity,
+    /// The [`LinearDamping`].
+    LinearDamping,
+    /// The [`AngularDamping`].
+    AngularDamping,
+}
+
+/// An event that is sent when an invalid value is found for a [`PhysicsProperty`]
+/// and [`ValidationMode::Warn`] or [`ValidationMode::Strict`] is used.
+///
+/// By the time the event is read, the value has already been replaced with the `corrected` value.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn report_invalid_values(mut events: EventReader<InvalidPhysicsValue>, names: Query<&Name>) {
+///     for event in events.read() {
+///         let name = names.get(event.entity).map_or("unnamed", |name| name.as_str());
+///         println!(
+///             "{name} had an invalid {:?} of {}",
+///             event.property, event.value
+///         );
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct InvalidPhysicsValue {
+    /// The entity with the invalid value.
+    pub entity: Entity,
+    /// The property with the invalid value.
+    pub property: PhysicsProperty,
+    /// The invalid value.
+    pub value: Scalar,
+    /// The valid value that the property was clamped to.
+    pub corrected: Scalar,
+}
+
+impl PhysicsProperty {
+    /// Returns the range of valid values for the property.
+    pub fn valid_range(self) -> (Scalar, Scalar) {
+        match self {
+            Self::Restitution => (0.0, 1.0),
+            // The density must be positive, so zero is not a valid value.
+            Self::ColliderDensity => (f32::MIN_POSITIVE as Scalar, Scalar::INFINITY),
+            _ => (0.0, Scalar::INFINITY),
+        }
+    }
+
+    /// Returns the closest valid value to the given `value`,
+    /// or `None` if the value is already valid.
+    ///
+    /// `NaN` is replaced with the lower bound of the [valid range](Self::valid_range).
+    pub fn correct(self, value: Scalar) -> Option<Scalar> {
+        let (min, max) = self.valid_range();
+
+        if value.is_nan() {
+            Some(min)
+        } else if value < min || value > max {
+            Some(value.clamp(min, max))
+        } else {
+            None
+        }
+    }
+}
+
+/// Checks the given value and clamps it if it is invalid, reporting it according to the [`ValidationMode`].
+fn validate(
+    entity: Entity,
+    property: PhysicsProperty,
+    value: &mut Scalar,
+    mode: ValidationMode,
+    events: &mut EventWriter<InvalidPhysicsValue>,
+) {
+    let Some(corrected) = property.correct(*value) else {
+        return;
+    };
+
+    if mode == ValidationMode::Strict && cfg!(debug_assertions) {
+        panic!(
+            "Entity {entity} has an invalid {property:?} of {}. The valid range is {:?}.",
+            *value,
+            property.valid_range()
+        );
+    }
+
+    if mode != ValidationMode::Clamp {
+        warn!(
+            "Entity {entity} has an invalid {property:?} of {}. It was clamped to {corrected}.",
+            *value
+        );
+        events.send(InvalidPhysicsValue {
+            entity,
+            property,
+            value: *value,
+            corrected,
+        });
+    }
+
+    *value = corrected;
+}
+
+/// Validates [`Restitution`] and [`Friction`] coefficients.
+pub(crate) fn validate_materials(
+    mut restitutions: Query<(Entity, &mut Restitution), Changed<Restitution>>,
+    mut frictions: Query<(Entity, &mut Friction), Changed<Friction>>,
+    config: Res<ValidationConfig>,
+    mut events: EventWriter<InvalidPhysicsValue>,
+) {
+    if !config.materials {
+        return;
+    }
+
+    for (entity, mut restitution) in &mut restitutions {
+        validate(
+            entity,
+            PhysicsProperty::Restitution,
+            // The component was just changed, so change detection doesn't need to be triggered again.
+            &mut restitution.bypass_change_detection().coefficient,
+            config.mode,
+            &mut events,
+        );
+    }
+
+    for (entity, mut friction) in &mut frictions {
+        let friction = friction.bypass_change_detection();
+        validate(
+            entity,
+            PhysicsProperty::DynamicFriction,
+            &mut friction.dynamic_coefficient,
+            config.mode,
+            &mut events,
+        );
+        validate(
+            entity,
+            PhysicsProperty::StaticFriction,
+            &mut friction.static_coefficient,
+            config.mode,
+            &mut events,
+        );
+    }
+}
+
+/// Validates [`ColliderDensity`].
+pub(crate) fn validate_density(
+    mut densities: Query<(Entity, &mut ColliderDensity), Changed<ColliderDensity>>,
+    config: Res<ValidationConfig>,
+    mut events: EventWriter<InvalidPhysicsValue>,
+) {
+    if !config.density {
+        return;
+    }
+
+    for (entity, mut density) in &mut densities {
+        let density = density.bypass_change_detection();
+        let mut value = density.0 as Scalar;
+        validate(
+            entity,
+            PhysicsProperty::ColliderDensity,
+            &mut value,
+            config.mode,
+            &mut events,
+        );
+        density.0 = value as f32;
+    }
+}
+
+/// Validates [`LinearDamping`] and [`AngularDamping`].
+pub(crate) fn validate_damping(
+    mut linear_dampings: Query<(Entity, &mut LinearDamping), Changed<LinearDamping>>,
+    mut angular_dampings: Query<(Entity, &mut AngularDamping), Changed<AngularDamping>>,
+    config: Res<ValidationConfig>,
+    mut events: EventWriter<InvalidPhysicsValue>,
+) {
+    if !config.damping {
+        return;
+    }
+
+    for (entity, mut damping) in &mut linear_dampings {
+        validate(
+            entity,
+            PhysicsProperty::LinearDamping,
+            &mut damping.bypass_change_detection().0,
+            config.mode,
+            &mut events,
+        );
+    }
+
+    for (entity, mut damping) in &mut angular_dampings {
+        validate(
+            entity,
+            PhysicsProperty::AngularDamping,
+            &mut damping.bypass_change_detection().0,
+            config.mode,
+            &mut events,
+        );
+    }
+}
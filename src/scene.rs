@@ -0,0 +1,154 @@
+//! Saving and restoring the state of the physics simulation using scenes.
+//!
+//! See [`PhysicsSceneExt`].
+
+use crate::{prelude::*, sync::PreviousGlobalTransform};
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    scene::{DynamicScene, DynamicSceneBuilder, SceneFilter, SceneSpawnError},
+};
+
+/// An extension trait for [`World`] for saving and restoring the runtime state
+/// of the physics simulation using a [`DynamicScene`].
+///
+/// The scene contains the state that changes as the simulation runs, such as
+/// [positions](Position), [rotations](Rotation), [velocities](LinearVelocity), sleeping state,
+/// and the internal state of [joints](dynamics::solver::joints). Restoring all of it together
+/// lets a simulation resume where it left off, without bodies popping or jittering
+/// from mismatched internal state.
+///
+/// Configuration like [colliders](Collider), [materials](Friction), and mass properties
+/// is *not* included. The scene is meant to be applied on top of a world where the physics
+/// entities have already been spawned, for example by loading the level as usual.
+/// Contacts are also not stored, and are recomputed on the next physics step.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::{ecs::entity::EntityHashMap, prelude::*};
+///
+/// fn save_and_restore(world: &mut World) {
+///     let scene = world.extract_physics_scene();
+///
+///     // ...the simulation keeps running...
+///
+///     // Restore the saved state, mapping the saved entities
+///     // to the same entities they were extracted from.
+///     let mut entity_map: EntityHashMap<Entity> = world
+///         .iter_entities()
+///         .map(|entity| (entity.id(), entity.id()))
+///         .collect();
+///     world
+///         .restore_physics_scene(&scene, &mut entity_map)
+///         .expect("failed to restore physics state");
+/// }
+/// ```
+pub trait PhysicsSceneExt {
+    /// Extracts the runtime state of all [rigid bodies](RigidBody) and [joints](dynamics::solver::joints)
+    /// into a [`DynamicScene`].
+    fn extract_physics_scene(&self) -> DynamicScene;
+
+    /// Writes the physics state stored in the given `scene` back into the world.
+    ///
+    /// The `entity_map` maps entities in the scene to entities in the world.
+    /// Entities that are not in the map are spawned as new entities and added to the map.
+    fn restore_physics_scene(
+        &mut self,
+        scene: &DynamicScene,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) -> Result<(), SceneSpawnError>;
+}
+
+impl PhysicsSceneExt for World {
+    fn extract_physics_scene(&self) -> DynamicScene {
+        let entities = self
+            .iter_entities()
+            .filter(|entity| entity.contains::<RigidBody>() || is_joint(entity))
+            .map(|entity| entity.id());
+
+        DynamicSceneBuilder::from_world(self)
+            .with_component_filter(physics_state_filter())
+            .extract_entities(entities)
+            .build()
+    }
+
+    fn restore_physics_scene(
+        &mut self,
+        scene: &DynamicScene,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) -> Result<(), SceneSpawnError> {
+        // Scenes can only insert components, not remove them.
+        // Remove the sleeping markers so that the scene determines
+        // which of the restored bodies are sleeping.
+        for scene_entity in scene.entities.iter() {
+            let Some(&entity) = entity_map.get(&scene_entity.entity) else {
+                continue;
+            };
+            if let Ok(mut entity) = self.get_entity_mut(entity) {
+                entity.remove::<Sleeping>();
+            }
+        }
+
+        scene.write_to_world(self, entity_map)
+    }
+}
+
+/// Returns `true` if the entity has any of the built-in joint components.
+fn is_joint(entity: &EntityRef) -> bool {
+    #[cfg(feature = "3d")]
+    if entity.contains::<SphericalJoint>() || entity.contains::<UniversalJoint>() {
+        return true;
+    }
+
+    entity.contains::<FixedJoint>()
+        || entity.contains::<DistanceJoint>()
+        || entity.contains::<PrismaticJoint>()
+        || entity.contains::<RevoluteJoint>()
+        || entity.contains::<MirrorConstraint>()
+        || entity.contains::<GearJoint>()
+        || entity.contains::<RackAndPinionJoint>()
+}
+
+/// A [`SceneFilter`] that only allows the components storing the runtime state of the simulation.
+fn physics_state_filter() -> SceneFilter {
+    let filter = SceneFilter::deny_all()
+        // Transforms, so that syncing doesn't override the restored positions.
+        .allow::<Transform>()
+        .allow::<GlobalTransform>()
+        .allow::<PreviousGlobalTransform>()
+        // Position and velocity
+        .allow::<Position>()
+        .allow::<Rotation>()
+        .allow::<PreviousRotation>()
+        .allow::<PreSolveRotation>()
+        .allow::<AccumulatedTranslation>()
+        .allow::<PreSolveAccumulatedTranslation>()
+        .allow::<LinearVelocity>()
+        .allow::<AngularVelocity>()
+        .allow::<PreSolveLinearVelocity>()
+        .allow::<PreSolveAngularVelocity>()
+        // Forces
+        .allow::<ExternalForce>()
+        .allow::<ExternalTorque>()
+        .allow::<ExternalImpulse>()
+        .allow::<ExternalAngularImpulse>()
+        // Sleeping
+        .allow::<Sleeping>()
+        .allow::<TimeSleeping>()
+        // Joints, including their accumulated Lagrange multipliers
+        .allow::<FixedJoint>()
+        .allow::<DistanceJoint>()
+        .allow::<PrismaticJoint>()
+        .allow::<RevoluteJoint>()
+        .allow::<MirrorConstraint>()
+        .allow::<GearJoint>()
+        .allow::<RackAndPinionJoint>();
+
+    #[cfg(feature = "3d")]
+    let filter = filter.allow::<SphericalJoint>().allow::<UniversalJoint>();
+
+    filter
+}
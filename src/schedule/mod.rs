@@ -240,36 +240,31 @@ fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>)
     let _ = world.try_schedule_scope(PhysicsSchedule, |world, schedule| {
         let is_paused = world.resource::<Time<Physics>>().is_paused();
         let old_clock = world.resource::<Time>().as_generic();
-        let physics_clock = world.resource_mut::<Time<Physics>>();
+        let physics_clock = world.resource::<Time<Physics>>();
 
-        // Get the scaled timestep delta time based on the timestep mode.
-        let timestep = old_clock
+        // Get the scaled delta time of the clock of the schedule that physics runs in.
+        let delta = old_clock
             .delta()
             .mul_f64(physics_clock.relative_speed_f64());
 
-        // Advance physics clock by timestep if not paused.
-        if !is_paused {
-            world.resource_mut::<Time<Physics>>().advance_by(timestep);
-
-            // Advance substep clock already so that systems running before the substepping loop have the right delta.
-            let SubstepCount(substeps) = *world.resource::<SubstepCount>();
-            let sub_delta = timestep.div_f64(substeps as f64);
-            world.resource_mut::<Time<Substeps>>().advance_by(sub_delta);
-        }
-
-        // Set generic `Time` resource to `Time<Physics>`.
-        *world.resource_mut::<Time>() = world.resource::<Time<Physics>>().as_generic();
-
-        // Advance simulation.
-        trace!("running PhysicsSchedule");
-        schedule.run(world);
-
-        // If physics is paused, reset delta time to stop simulation
-        // unless users manually advance `Time<Physics>`.
         if is_paused {
+            run_physics_step(world, schedule, None);
+
+            // If physics is paused, reset delta time to stop simulation
+            // unless users manually advance `Time<Physics>`.
             world
                 .resource_mut::<Time<Physics>>()
                 .advance_by(Duration::ZERO);
+        } else if let Some(fixed_timestep) = physics_clock.fixed_timestep() {
+            // Accumulate time and run as many fixed steps as it covers.
+            world.resource_mut::<Time<Physics>>().context_mut().overstep += delta;
+
+            while world.resource::<Time<Physics>>().overstep() >= fixed_timestep {
+                world.resource_mut::<Time<Physics>>().context_mut().overstep -= fixed_timestep;
+                run_physics_step(world, schedule, Some(fixed_timestep));
+            }
+        } else {
+            run_physics_step(world, schedule, Some(delta));
         }
 
         // Set generic `Time` resource back to the clock that was active before physics.
@@ -278,3 +273,24 @@ fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>)
 
     is_first_run.0 = false;
 }
+
+/// Advances the physics clocks by the given timestep, and runs the [`PhysicsSchedule`] once.
+///
+/// If `timestep` is `None`, the clocks are not advanced.
+fn run_physics_step(world: &mut World, schedule: &mut Schedule, timestep: Option<Duration>) {
+    if let Some(timestep) = timestep {
+        world.resource_mut::<Time<Physics>>().advance_by(timestep);
+
+        // Advance substep clock already so that systems running before the substepping loop have the right delta.
+        let SubstepCount(substeps) = *world.resource::<SubstepCount>();
+        let sub_delta = timestep.div_f64(substeps as f64);
+        world.resource_mut::<Time<Substeps>>().advance_by(sub_delta);
+    }
+
+    // Set generic `Time` resource to `Time<Physics>`.
+    *world.resource_mut::<Time>() = world.resource::<Time<Physics>>().as_generic();
+
+    // Advance simulation.
+    trace!("running PhysicsSchedule");
+    schedule.run(world);
+}
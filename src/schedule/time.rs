@@ -1,12 +1,13 @@
 //! Clocks used for tracking physics simulation time.
 
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::Duration};
 
 /// The clock representing physics time, following the [`Time`] clock used by the schedule that physics runs in.
 ///
 /// In [`FixedPostUpdate`] and other fixed schedules, this uses [`Time<Fixed>`](Fixed), while in schedules such as [`Update`],
-/// a variable timestep following [`Time<Virtual>`](Virtual) is used.
+/// a variable timestep following [`Time<Virtual>`](Virtual) is used. Physics can also be configured
+/// to use its own [fixed timestep](#physics-timestep) independent of the schedule.
 ///
 /// [`Time<Physics>`](Physics) is automatically set as the generic [`Time`] resource for
 /// the [`PhysicsSchedule`].
@@ -35,6 +36,39 @@ use bevy::prelude::*;
 /// [`with_relative_speed`]: PhysicsTime::with_relative_speed
 /// [`set_relative_speed`]: PhysicsTime::set_relative_speed
 ///
+/// ## Physics Timestep
+///
+/// By default, the simulation is stepped once per run of the schedule that physics runs in,
+/// using the delta time of that schedule. To run physics at its own rate independent of the schedule,
+/// for example at 30 Hz while gameplay logic in [`FixedUpdate`] runs at 60 Hz, you can configure
+/// a fixed physics timestep using [`with_fixed_timestep`] or [`set_fixed_timestep`]:
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         // Run game logic at 60 Hz
+///         .insert_resource(Time::<Fixed>::from_hz(60.0))
+///         // Run physics at 30 Hz
+///         .insert_resource(Time::<Physics>::default().with_fixed_timestep_hz(30.0))
+///         .run();
+/// }
+/// ```
+///
+/// The elapsed time of the schedule's clock is then accumulated, and the [`PhysicsSchedule`]
+/// is run as many times as the accumulated time allows, which can also be zero times.
+/// The time left over is the [`overstep`], and [`overstep_fraction`] can be used to ease
+/// visuals in between physics steps.
+///
+/// [`with_fixed_timestep`]: PhysicsTime::with_fixed_timestep
+/// [`set_fixed_timestep`]: PhysicsTime::set_fixed_timestep
+/// [`overstep`]: PhysicsTime::overstep
+/// [`overstep_fraction`]: PhysicsTime::overstep_fraction
+///
 /// ## Pausing, Resuming, and Stepping Physics
 ///
 /// [`Time<Physics>`](Physics) can be used to pause and resume the simulation:
@@ -122,6 +156,8 @@ use bevy::prelude::*;
 pub struct Physics {
     paused: bool,
     relative_speed: f64,
+    fixed_timestep: Option<Duration>,
+    pub(crate) overstep: Duration,
 }
 
 impl Default for Physics {
@@ -129,6 +165,8 @@ impl Default for Physics {
         Self {
             paused: false,
             relative_speed: 1.0,
+            fixed_timestep: None,
+            overstep: Duration::ZERO,
         }
     }
 }
@@ -216,6 +254,46 @@ pub trait PhysicsTime {
     /// Resumes the clock if paused.
     #[doc(alias = "resume")]
     fn unpause(&mut self);
+
+    /// Returns the fixed timestep used for stepping physics, or `None` if physics
+    /// is stepped once per run of the schedule that it runs in.
+    fn fixed_timestep(&self) -> Option<Duration>;
+
+    /// Sets the fixed timestep used for stepping physics independently of the schedule that it runs in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestep` is zero.
+    fn with_fixed_timestep(self, timestep: Duration) -> Self;
+
+    /// Sets the fixed timestep used for stepping physics independently of the schedule that it runs in,
+    /// given as a frequency in Hertz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative, or not finite.
+    fn with_fixed_timestep_hz(self, hz: f64) -> Self;
+
+    /// Sets the fixed timestep used for stepping physics independently of the schedule that it runs in.
+    /// If `None`, physics is stepped once per run of the schedule, and the [overstep](Self::overstep) is discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestep` is zero.
+    fn set_fixed_timestep(&mut self, timestep: Option<Duration>);
+
+    /// Returns the amount of accumulated time that hasn't been simulated yet
+    /// because it is less than the [fixed timestep](Self::fixed_timestep).
+    ///
+    /// This is always zero if no fixed timestep is configured.
+    fn overstep(&self) -> Duration;
+
+    /// Returns the [overstep](Self::overstep) as a fraction of the [fixed timestep](Self::fixed_timestep),
+    /// typically in the range `[0.0, 1.0)`.
+    ///
+    /// This can be used for easing visuals in between physics steps. If no fixed timestep is configured,
+    /// this is always `0.0`.
+    fn overstep_fraction(&self) -> f32;
 }
 
 impl PhysicsTime for Time<Physics> {
@@ -259,6 +337,44 @@ impl PhysicsTime for Time<Physics> {
     fn is_paused(&self) -> bool {
         self.context().paused
     }
+
+    fn fixed_timestep(&self) -> Option<Duration> {
+        self.context().fixed_timestep
+    }
+
+    fn with_fixed_timestep(mut self, timestep: Duration) -> Self {
+        self.set_fixed_timestep(Some(timestep));
+        self
+    }
+
+    fn with_fixed_timestep_hz(self, hz: f64) -> Self {
+        assert!(hz.is_finite(), "tried to step infinitely fast");
+        assert!(hz > 0.0, "tried to step at a non-positive rate");
+        self.with_fixed_timestep(Duration::from_secs_f64(1.0 / hz))
+    }
+
+    fn set_fixed_timestep(&mut self, timestep: Option<Duration>) {
+        assert_ne!(
+            timestep,
+            Some(Duration::ZERO),
+            "attempted to set fixed physics timestep to zero"
+        );
+        let context = self.context_mut();
+        context.fixed_timestep = timestep;
+        if timestep.is_none() {
+            context.overstep = Duration::ZERO;
+        }
+    }
+
+    fn overstep(&self) -> Duration {
+        self.context().overstep
+    }
+
+    fn overstep_fraction(&self) -> f32 {
+        self.context().fixed_timestep.map_or(0.0, |timestep| {
+            self.context().overstep.as_secs_f32() / timestep.as_secs_f32()
+        })
+    }
 }
 
 /// The clock representing physics substep time. It is updated based on
@@ -0,0 +1,275 @@
+//! Loading global physics configuration from [RON] assets.
+//!
+//! See [`PhysicsSettingsPlugin`].
+//!
+//! [RON]: https://github.com/ron-rs/ron
+
+use crate::{
+    dynamics::solver::{RestitutionModel, SolverConfig},
+    prelude::*,
+};
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// A plugin that loads a [`PhysicsSettings`] asset and applies it to the corresponding
+/// physics resources whenever it is loaded or modified.
+///
+/// With Bevy's `file_watcher` feature enabled, edits to the settings file are picked up
+/// while the app is running, which makes it easy to tune the simulation without recompiling.
+///
+/// The plugin must be added after the [`PhysicsPlugins`], and requires the `AssetPlugin`.
+///
+/// # Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             // Loads `assets/settings.physics.ron`.
+///             PhysicsSettingsPlugin::new("settings.physics.ron"),
+///         ))
+///         .run();
+/// }
+/// ```
+pub struct PhysicsSettingsPlugin {
+    path: String,
+}
+
+impl PhysicsSettingsPlugin {
+    /// Creates a [`PhysicsSettingsPlugin`] that loads the [`PhysicsSettings`] asset at the given path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Plugin for PhysicsSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<PhysicsSettings>()
+            .init_asset_loader::<RonAssetLoader<PhysicsSettings>>()
+            .register_asset_reflect::<PhysicsSettings>();
+
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load::<PhysicsSettings>(self.path.clone());
+        app.insert_resource(PhysicsSettingsHandle(handle));
+
+        app.add_systems(PreUpdate, apply_physics_settings);
+    }
+}
+
+/// Global physics configuration that can be loaded from a `.physics.ron` file
+/// using the [`PhysicsSettingsPlugin`].
+///
+/// All fields are optional. Fields that are omitted leave the corresponding resource unchanged.
+///
+/// # Example
+///
+/// A settings file could look like this:
+///
+/// ```ron
+/// (
+#[cfg_attr(feature = "2d", doc = "    gravity: Some((0.0, -9.81)),")]
+#[cfg_attr(feature = "3d", doc = "    gravity: Some((0.0, -9.81, 0.0)),")]
+///     substeps: Some(8),
+///     restitution_iterations: Some(1),
+///     restitution_model: Some(Poisson),
+///     sleep_linear_threshold: Some(0.1),
+///     sleep_angular_threshold: Some(0.1),
+///     deactivation_time: Some(0.5),
+///     length_unit: Some(1.0),
+///     collision_layers: Some({
+///         "default": 0,
+///         "player": 1,
+///         "enemy": 2,
+///         "terrain": 3,
+///     }),
+/// )
+/// ```
+#[derive(Asset, Reflect, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[reflect(Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct PhysicsSettings {
+    /// The global [`Gravity`].
+    pub gravity: Option<Vector>,
+    /// The number of substeps used by the solver. See [`SubstepCount`].
+    pub substeps: Option<u32>,
+    /// The number of iterations used for restitution. See [`SolverConfig::restitution_iterations`].
+    pub restitution_iterations: Option<usize>,
+    /// The model used for computing bounces. See [`SolverConfig::restitution_model`].
+    pub restitution_model: Option<RestitutionModel>,
+    /// The linear velocity threshold for sleeping. See [`SleepingThreshold::linear`].
+    pub sleep_linear_threshold: Option<Scalar>,
+    /// The angular velocity threshold for sleeping. See [`SleepingThreshold::angular`].
+    pub sleep_angular_threshold: Option<Scalar>,
+    /// How long bodies need to stay below the sleeping threshold before sleeping. See [`DeactivationTime`].
+    pub deactivation_time: Option<Scalar>,
+    /// The characteristic length unit of the simulation. See [`PhysicsLengthUnit`].
+    pub length_unit: Option<Scalar>,
+    /// The names of the collision layers, replacing the [`CollisionLayerRegistry`] resource.
+    pub collision_layers: Option<CollisionLayerRegistry>,
+}
+
+/// A resource storing the handle of the [`PhysicsSettings`] loaded by the [`PhysicsSettingsPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct PhysicsSettingsHandle(pub Handle<PhysicsSettings>);
+
+/// An asset that can be loaded from a [RON] file by a [`RonAssetLoader`].
+///
+/// [RON]: https://github.com/ron-rs/ron
+pub trait RonAsset: Asset + DeserializeOwned {
+    /// The file extensions of the asset, for example `"physics.ron"`.
+    const EXTENSIONS: &'static [&'static str];
+}
+
+impl RonAsset for PhysicsSettings {
+    const EXTENSIONS: &'static [&'static str] = &["physics.ron"];
+}
+
+/// An [`AssetLoader`] for [`RonAsset`]s stored in the [RON] format,
+/// such as [`PhysicsSettings`].
+///
+/// [RON]: https://github.com/ron-rs/ron
+pub struct RonAssetLoader<A: RonAsset>(PhantomData<fn() -> A>);
+
+impl<A: RonAsset> Default for RonAssetLoader<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// An error that can occur when loading a [`RonAsset`].
+#[derive(Debug)]
+pub enum RonAssetLoaderError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not valid RON, or does not match the format of the asset.
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for RonAssetLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read asset: {error}"),
+            Self::Ron(error) => write!(f, "could not parse asset: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for RonAssetLoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Ron(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for RonAssetLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for RonAssetLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+impl<A: RonAsset> AssetLoader for RonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        A::EXTENSIONS
+    }
+}
+
+/// Applies the [`PhysicsSettings`] to the physics resources whenever the asset is loaded or modified.
+#[allow(clippy::too_many_arguments)]
+fn apply_physics_settings(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<PhysicsSettings>>,
+    handle: Res<PhysicsSettingsHandle>,
+    settings: Res<Assets<PhysicsSettings>>,
+    gravity: Option<ResMut<Gravity>>,
+    substep_count: Option<ResMut<SubstepCount>>,
+    solver_config: Option<ResMut<SolverConfig>>,
+    sleeping_threshold: Option<ResMut<SleepingThreshold>>,
+    deactivation_time: Option<ResMut<DeactivationTime>>,
+    length_unit: Option<ResMut<PhysicsLengthUnit>>,
+) {
+    // Read all events so that none of them are left over for the next run.
+    let changed = asset_events
+        .read()
+        .filter(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => *id == handle.0.id(),
+            _ => false,
+        })
+        .count();
+
+    if changed == 0 {
+        return;
+    }
+
+    let Some(settings) = settings.get(&handle.0) else {
+        return;
+    };
+
+    if let (Some(value), Some(mut gravity)) = (settings.gravity, gravity) {
+        gravity.0 = value;
+    }
+    if let (Some(value), Some(mut substep_count)) = (settings.substeps, substep_count) {
+        substep_count.0 = value;
+    }
+    if let Some(mut solver_config) = solver_config {
+        if let Some(value) = settings.restitution_iterations {
+            solver_config.restitution_iterations = value;
+        }
+        if let Some(value) = settings.restitution_model {
+            solver_config.restitution_model = value;
+        }
+    }
+    if let Some(mut sleeping_threshold) = sleeping_threshold {
+        if let Some(value) = settings.sleep_linear_threshold {
+            sleeping_threshold.linear = value;
+        }
+        if let Some(value) = settings.sleep_angular_threshold {
+            sleeping_threshold.angular = value;
+        }
+    }
+    if let (Some(value), Some(mut deactivation_time)) =
+        (settings.deactivation_time, deactivation_time)
+    {
+        deactivation_time.0 = value;
+    }
+    if let (Some(value), Some(mut length_unit)) = (settings.length_unit, length_unit) {
+        length_unit.0 = value;
+    }
+    if let Some(registry) = &settings.collision_layers {
+        commands.insert_resource(registry.clone());
+    }
+}
@@ -129,6 +129,12 @@
 //! Point projection can be done with the [`project_point`](SpatialQuery::project_point) method of the [`SpatialQuery`]
 //! system parameter. See its documentation for more information.
 //!
+//! To find the closest point on the surface of any collider, even from inside of one,
+//! use [`closest_point`](SpatialQuery::closest_point). The distance and closest points between
+//! two specific colliders can be computed with [`distance_between`](SpatialQuery::distance_between).
+//! To find several of the closest colliders at once, for example for picking targets, use
+//! [`k_nearest`](SpatialQuery::k_nearest).
+//!
 //! To specify which colliders should be considered in the query, use a [spatial query filter](`SpatialQueryFilter`).
 //!
 //! # Intersection tests
@@ -136,11 +142,13 @@
 //! **Intersection tests** are spatial queries that return the entities of colliders that are intersecting a given
 //! shape or area.
 //!
-//! There are three types of intersection tests. They are all methods of the [`SpatialQuery`] system parameter,
+//! There are four types of intersection tests. They are all methods of the [`SpatialQuery`] system parameter,
 //! and they all have callback variants that call a given callback on each intersection.
 //!
 //! - [`point_intersections`](SpatialQuery::point_intersections): Finds all entities with a collider that contains
 //!   the given point.
+//! - [`aabb_intersections`](SpatialQuery::aabb_intersections): Finds all entities with a collider
+//!   whose AABB is intersecting the given [`ColliderAabb`]. Only the bounding boxes are tested.
 //! - [`aabb_intersections_with_aabb`](SpatialQuery::aabb_intersections_with_aabb):
 //!   Finds all entities with a [`ColliderAabb`] that is intersecting the given [`ColliderAabb`].
 //! - [`shape_intersections`](SpatialQuery::shape_intersections): Finds all entities with a [collider](Collider)
@@ -153,6 +161,8 @@
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 mod pipeline;
 mod query_filter;
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+mod radial_impulse;
 mod ray_caster;
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 mod shape_caster;
@@ -162,6 +172,8 @@ mod system_param;
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 pub use pipeline::*;
 pub use query_filter::*;
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+pub use radial_impulse::*;
 pub use ray_caster::*;
 #[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
 pub use shape_caster::*;
@@ -174,6 +186,9 @@ use bevy::prelude::*;
 /// Initializes the [`SpatialQueryPipeline`] resource and handles component-based [spatial queries](spatial_query)
 /// like [raycasting](spatial_query#raycasting) and [shapecasting](spatial_query#shapecasting) with
 /// [`RayCaster`] and [`ShapeCaster`].
+///
+/// By default, the pipeline is rebuilt every physics step. This can be configured
+/// with the `SpatialQueryPipelineConfig` resource.
 pub struct SpatialQueryPlugin;
 
 impl Plugin for SpatialQueryPlugin {
@@ -182,7 +197,10 @@ impl Plugin for SpatialQueryPlugin {
             feature = "default-collider",
             any(feature = "parry-f32", feature = "parry-f64")
         ))]
-        app.init_resource::<SpatialQueryPipeline>();
+        app.init_resource::<SpatialQueryPipeline>()
+            .init_resource::<SpatialQueryPipelineConfig>()
+            .init_resource::<SpatialQuerySnapshot>()
+            .register_type::<SpatialQueryPipelineConfig>();
 
         let physics_schedule = app
             .get_schedule_mut(PhysicsSchedule)
@@ -197,7 +215,7 @@ impl Plugin for SpatialQueryPlugin {
                 ))]
                 (
                     update_shape_caster_positions,
-                    |mut spatial_query: SpatialQuery| spatial_query.update_pipeline(),
+                    update_spatial_query_pipeline,
                     raycast,
                     shapecast,
                 )
@@ -209,6 +227,42 @@ impl Plugin for SpatialQueryPlugin {
     }
 }
 
+/// Updates the [`SpatialQueryPipeline`] according to the [`SpatialQueryPipelineConfig`].
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn update_spatial_query_pipeline(
+    mut spatial_query: SpatialQuery,
+    config: Res<SpatialQueryPipelineConfig>,
+    mut snapshot: ResMut<SpatialQuerySnapshot>,
+    mut step: Local<u32>,
+) {
+    let should_update = match config.update_mode {
+        SpatialQueryUpdateMode::EveryStep => true,
+        SpatialQueryUpdateMode::EveryNthStep(n) => {
+            let should_update = *step % n.max(1) == 0;
+            *step = step.wrapping_add(1);
+            should_update
+        }
+        SpatialQueryUpdateMode::Manual => false,
+    };
+
+    if !should_update {
+        return;
+    }
+
+    if config.incremental {
+        spatial_query.update_pipeline_incremental();
+    } else {
+        spatial_query.update_pipeline();
+    }
+
+    if config.store_snapshot {
+        *snapshot = spatial_query.query_pipeline.snapshot();
+    }
+}
+
 type RayCasterPositionQueryComponents = (
     &'static mut RayCaster,
     Option<&'static Position>,
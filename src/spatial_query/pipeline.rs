@@ -1,11 +1,11 @@
-use std::sync::Arc;
+use std::{cmp::Ordering, collections::BinaryHeap, sync::Arc};
 
 use crate::prelude::*;
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{ecs::component::Tick, prelude::*, utils::HashMap};
 use parry::{
-    bounding_volume::Aabb,
-    math::Isometry,
-    partitioning::Qbvh,
+    bounding_volume::{Aabb, BoundingVolume},
+    math::{Isometry, Point, SimdReal, SIMD_WIDTH},
+    partitioning::{Qbvh, QbvhUpdateWorkspace},
     query::{
         details::{
             NormalConstraints, RayCompositeShapeToiAndNormalBestFirstVisitor,
@@ -15,9 +15,10 @@ use parry::{
         visitors::{
             BoundingVolumeIntersectionsVisitor, PointIntersectionsVisitor, RayIntersectionsVisitor,
         },
-        DefaultQueryDispatcher, QueryDispatcher, ShapeCastOptions,
+        DefaultQueryDispatcher, PointQuery, QueryDispatcher, RayCast, ShapeCastOptions,
     },
     shape::{Shape, TypedSimdCompositeShape},
+    simba::simd::SimdValue,
 };
 
 /// A resource for the spatial query pipeline.
@@ -30,6 +31,8 @@ pub struct SpatialQueryPipeline {
     pub(crate) dispatcher: Arc<dyn QueryDispatcher>,
     pub(crate) colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
     pub(crate) entity_generations: HashMap<u32, u32>,
+    /// The change tick of the last update, used for finding changed colliders in incremental updates.
+    pub(crate) last_update_tick: Tick,
 }
 
 impl Default for SpatialQueryPipeline {
@@ -39,6 +42,7 @@ impl Default for SpatialQueryPipeline {
             dispatcher: Arc::new(DefaultQueryDispatcher),
             colliders: HashMap::default(),
             entity_generations: HashMap::default(),
+            last_update_tick: Tick::default(),
         }
     }
 }
@@ -103,6 +107,71 @@ impl SpatialQueryPipeline {
         self.update_internal(colliders, added_colliders)
     }
 
+    /// Updates the associated acceleration structures for the given changed and removed colliders,
+    /// keeping the rest of the structure intact.
+    ///
+    /// `changed_colliders` must contain all colliders that were added, moved, or otherwise modified
+    /// since the last update, and `removed_colliders` all colliders that were removed or disabled.
+    /// Other colliders keep the state they had when they were last updated.
+    ///
+    /// This is much cheaper than a full [`update`](Self::update) when only a small portion
+    /// of the colliders have changed, for example in worlds with a lot of static geometry.
+    pub fn update_incremental<'a>(
+        &mut self,
+        changed_colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a Position,
+                &'a Rotation,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+            ),
+        >,
+        removed_colliders: impl Iterator<Item = Entity>,
+    ) {
+        for entity in removed_colliders {
+            if self.colliders.remove(&entity).is_some() {
+                self.qbvh.remove(entity.index());
+            }
+        }
+
+        for (entity, position, rotation, collider, layers) in changed_colliders {
+            self.entity_generations
+                .insert(entity.index(), entity.generation());
+            self.colliders.insert(
+                entity,
+                (
+                    make_isometry(position.0, *rotation),
+                    collider.clone(),
+                    layers.map_or(CollisionLayers::default(), |layers| *layers),
+                ),
+            );
+            self.qbvh.pre_update_or_insert(entity.index());
+        }
+
+        let Self {
+            qbvh,
+            colliders,
+            entity_generations,
+            ..
+        } = self;
+
+        let mut workspace = QbvhUpdateWorkspace::default();
+        qbvh.refit(0.01, &mut workspace, |index| {
+            let entity = entity_from_index_and_gen(*index, entity_generations[index]);
+            let (iso, collider, _) = &colliders[&entity];
+            collider.shape_scaled().compute_aabb(iso)
+        });
+        qbvh.rebalance(0.01, &mut workspace);
+    }
+
+    /// Creates a read-only [`SpatialQuerySnapshot`] of the pipeline in its current state.
+    ///
+    /// The snapshot is not affected by later updates of the pipeline.
+    pub fn snapshot(&self) -> SpatialQuerySnapshot {
+        SpatialQuerySnapshot(Arc::new(self.clone()))
+    }
+
     fn update_internal(
         &mut self,
         colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
@@ -148,6 +217,26 @@ impl SpatialQueryPipeline {
         entity_from_index_and_gen(index, *self.entity_generations.get(&index).unwrap())
     }
 
+    /// Finds the child shape of the given compound collider closest to a `point` in world space.
+    pub(crate) fn sub_shape_hit(&self, entity: Entity, point: Vector) -> Option<SubShapeHit> {
+        let (isometry, collider, _) = self.colliders.get(&entity)?;
+        collider.sub_shape_at_point_with_isometry(isometry, point)
+    }
+
+    /// Finds the child shape of the given compound collider that is hit by a `ray` in world space.
+    pub(crate) fn sub_shape_ray_hit(
+        &self,
+        entity: Entity,
+        ray: &parry::query::Ray,
+        max_distance: Scalar,
+        solid: bool,
+    ) -> Option<SubShapeHit> {
+        let (isometry, collider, _) = self.colliders.get(&entity)?;
+        collider
+            .cast_ray_with_sub_shape(isometry, ray, max_distance, solid)?
+            .1
+    }
+
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
     /// If there are no hits, `None` is returned.
     ///
@@ -214,10 +303,14 @@ impl SpatialQueryPipeline {
 
         self.qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                distance: hit.time_of_impact,
-                normal: hit.normal.into(),
+            .map(|(_, (entity_index, hit))| {
+                let entity = self.entity_from_index(entity_index);
+                RayHitData {
+                    entity,
+                    distance: hit.time_of_impact,
+                    normal: hit.normal.into(),
+                    sub_shape: self.sub_shape_ray_hit(entity, &ray, max_distance, solid),
+                }
             })
     }
 
@@ -295,15 +388,14 @@ impl SpatialQueryPipeline {
             let entity = self.entity_from_index(*entity_index);
             if let Some((iso, shape, layers)) = colliders.get(&entity) {
                 if filter.test(entity, *layers) {
-                    if let Some(hit) =
-                        shape
-                            .shape_scaled()
-                            .cast_ray_and_get_normal(iso, &ray, max_distance, solid)
+                    if let Some((hit, sub_shape)) =
+                        shape.cast_ray_with_sub_shape(iso, &ray, max_distance, solid)
                     {
                         let hit = RayHitData {
                             entity,
                             distance: hit.time_of_impact,
                             normal: hit.normal.into(),
+                            sub_shape,
                         };
 
                         return callback(hit);
@@ -317,6 +409,70 @@ impl SpatialQueryPipeline {
         self.qbvh.traverse_depth_first(&mut visitor);
     }
 
+    /// Casts a [ray](spatial_query#raycasting) and returns an iterator over its [hits](RayHitData),
+    /// sorted by distance in ascending order.
+    ///
+    /// The hits are computed lazily. Colliders whose bounding boxes intersect the ray are collected up front,
+    /// but the exact intersection tests and the [`SpatialQueryFilter`] are only evaluated as needed
+    /// to find the next closest hit. This makes it cheap to stop at the first hit that passes
+    /// some gameplay test.
+    ///
+    /// # Arguments
+    ///
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_distance`: The maximum distance the ray can travel.
+    /// - `solid`: If true *and* the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    ///   Otherwise, the collider will be treated as hollow, and the hit point will be at its boundary.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::cast_ray`]
+    /// - [`SpatialQueryPipeline::ray_hits`]
+    pub fn ray_hits_sorted<'a>(
+        &'a self,
+        origin: Vector,
+        direction: Dir,
+        max_distance: Scalar,
+        solid: bool,
+        filter: &'a SpatialQueryFilter,
+    ) -> SortedRayHits<'a> {
+        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+
+        let mut candidates = vec![];
+        let mut leaf_callback = &mut |entity_index: &u32| {
+            let entity = self.entity_from_index(*entity_index);
+            if let Some((iso, collider, _)) = self.colliders.get(&entity) {
+                // The distance to the AABB is a lower bound for the distance to the collider.
+                if let Some(aabb_distance) = collider
+                    .shape_scaled()
+                    .compute_aabb(iso)
+                    .cast_local_ray(&ray, max_distance, true)
+                {
+                    candidates.push((aabb_distance, entity));
+                }
+            }
+            true
+        };
+
+        let mut visitor = RayIntersectionsVisitor::new(&ray, max_distance, &mut leaf_callback);
+        self.qbvh.traverse_depth_first(&mut visitor);
+
+        // Sort in descending order so that the closest candidate can be popped from the end.
+        candidates.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+        SortedRayHits {
+            pipeline: self,
+            ray,
+            max_distance,
+            solid,
+            filter,
+            candidates,
+            hits: vec![],
+        }
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeHits)
     /// with a collider. If there are no hits, `None` is returned.
     ///
@@ -417,13 +573,17 @@ impl SpatialQueryPipeline {
 
         self.qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| ShapeHitData {
-                entity: self.entity_from_index(entity_index),
-                distance: hit.time_of_impact,
-                point1: hit.witness1.into(),
-                point2: hit.witness2.into(),
-                normal1: hit.normal1.into(),
-                normal2: hit.normal2.into(),
+            .map(|(_, (entity_index, hit))| {
+                let entity = self.entity_from_index(entity_index);
+                ShapeHitData {
+                    entity,
+                    distance: hit.time_of_impact,
+                    point1: hit.witness1.into(),
+                    point2: hit.witness2.into(),
+                    normal1: hit.normal1.into(),
+                    normal2: hit.normal2.into(),
+                    sub_shape: self.sub_shape_hit(entity, hit.witness1.into()),
+                }
             })
     }
 
@@ -541,13 +701,17 @@ impl SpatialQueryPipeline {
             if let Some(hit) =
                 self.qbvh
                     .traverse_best_first(&mut visitor)
-                    .map(|(_, (entity_index, hit))| ShapeHitData {
-                        entity: self.entity_from_index(entity_index),
-                        distance: hit.time_of_impact,
-                        point1: hit.witness1.into(),
-                        point2: hit.witness2.into(),
-                        normal1: hit.normal1.into(),
-                        normal2: hit.normal2.into(),
+                    .map(|(_, (entity_index, hit))| {
+                        let entity = self.entity_from_index(entity_index);
+                        ShapeHitData {
+                            entity,
+                            distance: hit.time_of_impact,
+                            point1: hit.witness1.into(),
+                            point2: hit.witness2.into(),
+                            normal1: hit.normal1.into(),
+                            normal2: hit.normal2.into(),
+                            sub_shape: self.sub_shape_hit(entity, hit.witness1.into()),
+                        }
                     })
             {
                 query_filter.excluded_entities.insert(hit.entity);
@@ -561,6 +725,166 @@ impl SpatialQueryPipeline {
         }
     }
 
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes all [hits](ShapeHitData)
+    /// along its path, sorted by distance.
+    ///
+    /// Unlike [`SpatialQueryPipeline::shape_hits`], which performs a new cast for every hit,
+    /// this finds all hits in a single sweep, which is much cheaper when the shape passes through
+    /// many colliders, for example with piercing projectiles or wide attack sweeps.
+    /// Each collider is hit at most once, at the distance where the shape first reaches it.
+    ///
+    /// # Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `config`: A [`ShapeCastConfig`] that determines the behavior of the cast.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::cast_shape_all_predicate`]
+    /// - [`SpatialQueryPipeline::shape_hits`]
+    pub fn cast_shape_all(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<ShapeHitData> {
+        self.cast_shape_all_predicate(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            config,
+            filter,
+            &|_| true,
+        )
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes all [hits](ShapeHitData)
+    /// along its path, sorted by distance. Only colliders for which the `predicate` returns `true` are hit.
+    ///
+    /// See [`SpatialQueryPipeline::cast_shape_all`] for more information.
+    ///
+    /// # Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `config`: A [`ShapeCastConfig`] that determines the behavior of the cast.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `predicate`: A function called on each entity in the path of the shape. Entities for which it returns `false` are skipped.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::cast_shape_all`]
+    /// - [`SpatialQueryPipeline::shape_hits`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_all_predicate(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Vec<ShapeHitData> {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let mut hits = vec![];
+        self.sweep_shape(
+            shape, origin, rotation, direction, config, filter, predicate, &mut hits,
+        );
+        hits
+    }
+
+    /// Sweeps the given shape through the pipeline, pushing all hits into `hits` sorted by distance.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn sweep_shape(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        rotation: Rotation,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+        hits: &mut Vec<ShapeHitData>,
+    ) {
+        let shape_cast_options = ShapeCastOptions {
+            max_time_of_impact: config.max_distance,
+            target_distance: config.target_distance,
+            stop_at_penetration: !config.ignore_origin_penetration,
+            compute_impact_geometry_on_penetration: config.compute_contact_on_penetration,
+        };
+
+        let direction = direction.adjust_precision();
+        let shape_isometry = make_isometry(origin, rotation);
+        let end_isometry = make_isometry(origin + direction * config.max_distance, rotation);
+        let shape_direction: parry::math::Vector<Scalar> = direction.into();
+
+        let start_index = hits.len();
+        let dispatcher = &*self.dispatcher;
+
+        let mut leaf_callback = &mut |entity_index: &u32| {
+            let entity = self.entity_from_index(*entity_index);
+
+            if let Some((collider_isometry, collider, layers)) = self.colliders.get(&entity) {
+                if filter.test(entity, *layers) && predicate(entity) {
+                    // The collider is the first shape, so that `point1` and `normal1` belong to it.
+                    let isometry = collider_isometry.inv_mul(&shape_isometry);
+                    let local_direction =
+                        collider_isometry.inverse_transform_vector(&shape_direction);
+
+                    if let Ok(Some(hit)) = dispatcher.cast_shapes(
+                        &isometry,
+                        &local_direction,
+                        &**collider.shape_scaled(),
+                        &**shape.shape_scaled(),
+                        shape_cast_options,
+                    ) {
+                        let point1: Vector = (collider_isometry * hit.witness1).into();
+                        hits.push(ShapeHitData {
+                            entity,
+                            distance: hit.time_of_impact,
+                            point1,
+                            point2: (shape_isometry * hit.witness2).into(),
+                            normal1: (collider_isometry * hit.normal1).into(),
+                            normal2: (shape_isometry * hit.normal2).into(),
+                            sub_shape: collider
+                                .sub_shape_at_point_with_isometry(collider_isometry, point1),
+                        });
+                    }
+                }
+            }
+            true
+        };
+
+        let swept_aabb = shape
+            .shape_scaled()
+            .compute_swept_aabb(&shape_isometry, &end_isometry)
+            .loosened(config.target_distance);
+        let mut visitor = BoundingVolumeIntersectionsVisitor::new(&swept_aabb, &mut leaf_callback);
+        self.qbvh.traverse_depth_first(&mut visitor);
+
+        hits[start_index..].sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    }
+
     /// Finds the [projection](spatial_query#point-projection) of a given point on the closest [collider](Collider).
     /// If one isn't found, `None` is returned.
     ///
@@ -618,6 +942,216 @@ impl SpatialQueryPipeline {
             })
     }
 
+    /// Finds the closest point on the surface of any [collider](Collider) to the given point.
+    /// If there are no colliders, `None` is returned.
+    ///
+    /// This is equivalent to a hollow [point projection](spatial_query#point-projection), so even
+    /// if the point is inside of a collider, the returned point is on the collider's boundary.
+    ///
+    /// # Arguments
+    ///
+    /// - `point`: The point that the closest point is computed for.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::project_point`]
+    /// - [`SpatialQueryPipeline::distance_between`]
+    pub fn closest_point(
+        &self,
+        point: Vector,
+        filter: &SpatialQueryFilter,
+    ) -> Option<PointProjection> {
+        self.project_point(point, false, filter)
+    }
+
+    /// Computes the distance and the closest points between the colliders of two entities.
+    ///
+    /// Returns `None` if either entity does not have a collider in the pipeline,
+    /// or if the distance between the shapes can not be computed.
+    ///
+    /// The colliders are evaluated at the positions they had when the pipeline was last updated.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::closest_point`]
+    pub fn distance_between(&self, entity1: Entity, entity2: Entity) -> Option<ColliderDistance> {
+        let (isometry1, collider1, _) = self.colliders.get(&entity1)?;
+        let (isometry2, collider2, _) = self.colliders.get(&entity2)?;
+
+        // With an infinite prediction distance, a contact is always computed.
+        let contact = parry::query::contact(
+            isometry1,
+            &**collider1.shape_scaled(),
+            isometry2,
+            &**collider2.shape_scaled(),
+            Scalar::MAX,
+        )
+        .ok()
+        .flatten()?;
+
+        Some(ColliderDistance {
+            distance: contact.dist,
+            point1: contact.point1.into(),
+            point2: contact.point2.into(),
+            normal1: contact.normal1.into(),
+            normal2: contact.normal2.into(),
+        })
+    }
+
+    /// Finds the `k` [colliders](Collider) closest to the given point, along with their distances.
+    /// The results are sorted by distance in ascending order.
+    ///
+    /// Colliders that contain the point have a distance of zero.
+    /// The tree is traversed best-first in order of the distance to its bounding boxes,
+    /// and branches further away than `max_distance` or the `k`-th closest collider found so far
+    /// are pruned, so the exact distance is only computed for colliders that could be among the results.
+    ///
+    /// # Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `k`: The maximum number of colliders to return.
+    /// - `max_distance`: The maximum distance from the point. Colliders further away are ignored.
+    ///   Can be [`Scalar::INFINITY`] to consider all colliders.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::k_nearest_predicate`]
+    /// - [`SpatialQueryPipeline::closest_point`]
+    pub fn k_nearest(
+        &self,
+        point: Vector,
+        k: usize,
+        max_distance: Scalar,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<NearestCollider> {
+        self.k_nearest_predicate(point, k, max_distance, filter, &|_| true)
+    }
+
+    /// Finds the `k` [colliders](Collider) closest to the given point that match the `predicate`,
+    /// along with their distances. The results are sorted by distance in ascending order.
+    ///
+    /// Colliders that contain the point have a distance of zero.
+    /// Colliders rejected by the predicate don't count towards the `k` results.
+    ///
+    /// # Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `k`: The maximum number of colliders to return.
+    /// - `max_distance`: The maximum distance from the point. Colliders further away are ignored.
+    ///   Can be [`Scalar::INFINITY`] to consider all colliders.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `predicate`: A function for filtering which entities are considered in the query. Entities for which it returns `false` are skipped.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::k_nearest`]
+    pub fn k_nearest_predicate(
+        &self,
+        point: Vector,
+        k: usize,
+        max_distance: Scalar,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Vec<NearestCollider> {
+        if k == 0 {
+            return vec![];
+        }
+
+        let nodes = self.qbvh.raw_nodes();
+        let proxies = self.qbvh.raw_proxies();
+        if nodes.is_empty() {
+            return vec![];
+        }
+
+        let query_point: Point<Scalar> = point.into();
+        let simd_point: Point<SimdReal> = Point::splat(query_point);
+
+        let mut nearest: Vec<NearestCollider> = Vec::with_capacity(k);
+
+        // Visit the nodes of the tree in order of the distance from the point to their bounding boxes.
+        let mut queue = BinaryHeap::new();
+        queue.push(QueuedNode {
+            distance: 0.0,
+            index: 0,
+        });
+
+        while let Some(QueuedNode { distance, index }) = queue.pop() {
+            // Bounding boxes can't be further away than the colliders they contain,
+            // so once a node is further away than the `k`-th closest collider found so far,
+            // none of the remaining nodes can contain a closer collider.
+            let mut bound = if nearest.len() == k {
+                nearest[k - 1].distance.min(max_distance)
+            } else {
+                max_distance
+            };
+            if distance > bound {
+                break;
+            }
+
+            let node = &nodes[index as usize];
+            let distances: [Scalar; SIMD_WIDTH] =
+                node.simd_aabb.distance_to_local_point(&simd_point).into();
+
+            for (lane, &child) in node.children.iter().enumerate() {
+                if distances[lane] > bound {
+                    continue;
+                }
+
+                if !node.is_leaf() {
+                    if (child as usize) < nodes.len() {
+                        queue.push(QueuedNode {
+                            distance: distances[lane],
+                            index: child,
+                        });
+                    }
+                    continue;
+                }
+
+                let Some(proxy) = proxies.get(child as usize) else {
+                    continue;
+                };
+                let entity = self.entity_from_index(proxy.data);
+                let Some((isometry, collider, layers)) = self.colliders.get(&entity) else {
+                    continue;
+                };
+                if !filter.test(entity, *layers) || !predicate(entity) {
+                    continue;
+                }
+
+                let projection =
+                    collider
+                        .shape_scaled()
+                        .project_point(isometry, &query_point, true);
+                let closest_point: Vector = projection.point.into();
+                let distance = closest_point.distance(point);
+
+                if distance > bound {
+                    continue;
+                }
+
+                let insert_index = nearest.partition_point(|hit| hit.distance <= distance);
+                if insert_index < k {
+                    nearest.truncate(k - 1);
+                    nearest.insert(
+                        insert_index,
+                        NearestCollider {
+                            entity,
+                            point: closest_point,
+                            distance,
+                        },
+                    );
+                    if nearest.len() == k {
+                        bound = bound.min(nearest[k - 1].distance);
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that contains the given point.
     ///
@@ -717,6 +1251,80 @@ impl SpatialQueryPipeline {
         self.qbvh.traverse_depth_first(&mut visitor);
     }
 
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
+    /// whose AABB is intersecting the given `aabb`.
+    ///
+    /// Only the bounding boxes are tested, so this is much cheaper than a [shape intersection](Self::shape_intersections)
+    /// test against a cuboid, but it can also return colliders whose actual shape doesn't overlap the region.
+    ///
+    /// # Arguments
+    ///
+    /// - `aabb`: The region that intersections are tested against.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::aabb_intersections_callback`]
+    pub fn aabb_intersections(
+        &self,
+        aabb: ColliderAabb,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<Entity> {
+        let mut intersections = vec![];
+        self.aabb_intersections_callback(aabb, filter, |e| {
+            intersections.push(e);
+            true
+        });
+        intersections
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
+    /// whose AABB is intersecting the given `aabb`, calling `callback` for each intersection.
+    /// The search stops when `callback` returns `false` or all intersections have been found.
+    ///
+    /// Only the bounding boxes are tested, so this is much cheaper than a [shape intersection](Self::shape_intersections)
+    /// test against a cuboid, but it can also return colliders whose actual shape doesn't overlap the region.
+    ///
+    /// # Arguments
+    ///
+    /// - `aabb`: The region that intersections are tested against.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `callback`: A callback function called for each intersection.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQueryPipeline::aabb_intersections`]
+    pub fn aabb_intersections_callback(
+        &self,
+        aabb: ColliderAabb,
+        filter: &SpatialQueryFilter,
+        mut callback: impl FnMut(Entity) -> bool,
+    ) {
+        let aabb = Aabb {
+            mins: aabb.min.into(),
+            maxs: aabb.max.into(),
+        };
+
+        let mut leaf_callback = |entity_index: &u32| {
+            let entity = self.entity_from_index(*entity_index);
+            if let Some((isometry, collider, layers)) = self.colliders.get(&entity) {
+                // The tree stores slightly enlarged AABBs, so test the exact AABB of the collider.
+                if filter.test(entity, *layers)
+                    && collider
+                        .shape_scaled()
+                        .compute_aabb(isometry)
+                        .intersects(&aabb)
+                {
+                    return callback(entity);
+                }
+            }
+            true
+        };
+
+        let mut visitor = BoundingVolumeIntersectionsVisitor::new(&aabb, &mut leaf_callback);
+        self.qbvh.traverse_depth_first(&mut visitor);
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
     /// that is intersecting the given `shape` with a given position and rotation.
     ///
@@ -905,6 +1513,204 @@ fn entity_from_index_and_gen(index: u32, generation: u32) -> bevy::prelude::Enti
     bevy::prelude::Entity::from_bits((generation as u64) << 32 | index as u64)
 }
 
+/// The distance and closest points between two [colliders](Collider),
+/// computed by [`SpatialQuery::distance_between`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct ColliderDistance {
+    /// The distance between the colliders.
+    ///
+    /// If the colliders are penetrating, this is negative, and its magnitude is the penetration depth.
+    pub distance: Scalar,
+    /// The closest point on the first collider, expressed in world space.
+    pub point1: Vector,
+    /// The closest point on the second collider, expressed in world space.
+    pub point2: Vector,
+    /// The outward surface normal on the first collider at `point1`, expressed in world space.
+    pub normal1: Vector,
+    /// The outward surface normal on the second collider at `point2`, expressed in world space.
+    pub normal2: Vector,
+}
+
+/// A cheaply cloneable, read-only snapshot of a [`SpatialQueryPipeline`].
+///
+/// The snapshot dereferences to the [`SpatialQueryPipeline`], so all of its query methods can be used.
+/// Because it doesn't borrow the pipeline resource, it can be sent to other threads, such as async tasks,
+/// or be queried by systems that run in parallel with the physics step.
+///
+/// A snapshot can be created with [`SpatialQueryPipeline::snapshot`]. If [`SpatialQueryPipelineConfig::store_snapshot`]
+/// is enabled, the snapshot stored in the [`SpatialQuerySnapshot`] resource is also refreshed every time
+/// the pipeline is updated.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "2d")]
+/// # use avian2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use avian3d::prelude::*;
+/// use bevy::{prelude::*, tasks::AsyncComputeTaskPool};
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn spawn_visibility_task(snapshot: Res<SpatialQuerySnapshot>) {
+///     let snapshot = snapshot.clone();
+///
+///     AsyncComputeTaskPool::get()
+///         .spawn(async move {
+///             let filter = SpatialQueryFilter::default();
+///             snapshot.cast_ray(Vec3::ZERO, Dir3::X, 100.0, true, &filter)
+///         })
+///         .detach();
+/// }
+/// ```
+#[derive(Resource, Clone, Default, Deref)]
+pub struct SpatialQuerySnapshot(Arc<SpatialQueryPipeline>);
+
+/// Configuration for how and when the [`SpatialQueryPipeline`] is updated by the [`SpatialQueryPlugin`].
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource, Debug, Default, PartialEq)]
+pub struct SpatialQueryPipelineConfig {
+    /// When the pipeline is updated. By default, it is updated every physics step.
+    pub update_mode: SpatialQueryUpdateMode,
+    /// If `true`, only the colliders that have changed since the last update are updated
+    /// using [`SpatialQueryPipeline::update_incremental`]. Otherwise, the whole acceleration
+    /// structure is rebuilt.
+    ///
+    /// Incremental updates are typically faster when most colliders don't move,
+    /// but the quality of the acceleration structure can degrade over time.
+    ///
+    /// Default: `false`
+    pub incremental: bool,
+    /// If `true`, the [`SpatialQuerySnapshot`] resource is refreshed after every update of the pipeline.
+    ///
+    /// This clones the pipeline, so it is disabled by default.
+    ///
+    /// Default: `false`
+    pub store_snapshot: bool,
+}
+
+/// Determines when the [`SpatialQueryPipeline`] is updated. See [`SpatialQueryPipelineConfig`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum SpatialQueryUpdateMode {
+    /// The pipeline is updated every physics step.
+    #[default]
+    EveryStep,
+    /// The pipeline is updated every `n` physics steps. Queries in between use the state of the last update.
+    EveryNthStep(u32),
+    /// The pipeline is only updated when requested with [`SpatialQuery::update_pipeline`]
+    /// or [`SpatialQuery::update_pipeline_incremental`].
+    Manual,
+}
+
+/// An iterator over the [hits](RayHitData) of a [ray](spatial_query#raycasting),
+/// sorted by distance in ascending order.
+///
+/// Created by [`SpatialQuery::ray_hits_sorted`].
+pub struct SortedRayHits<'a> {
+    pipeline: &'a SpatialQueryPipeline,
+    ray: parry::query::Ray,
+    max_distance: Scalar,
+    solid: bool,
+    filter: &'a SpatialQueryFilter,
+    /// Colliders whose AABBs are hit by the ray, sorted by the AABB distance in descending order.
+    candidates: Vec<(Scalar, Entity)>,
+    /// Exact hits that have not been returned yet, sorted by distance in descending order.
+    hits: Vec<RayHitData>,
+}
+
+impl Iterator for SortedRayHits<'_> {
+    type Item = RayHitData;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // A computed hit can be returned once no remaining candidate can be closer.
+            let bound = self
+                .candidates
+                .last()
+                .map_or(Scalar::INFINITY, |(distance, _)| *distance);
+            if self.hits.last().is_some_and(|hit| hit.distance <= bound) {
+                return self.hits.pop();
+            }
+
+            let (_, entity) = self.candidates.pop()?;
+            let Some((iso, collider, layers)) = self.pipeline.colliders.get(&entity) else {
+                continue;
+            };
+
+            if !self.filter.test(entity, *layers) {
+                continue;
+            }
+
+            if let Some((hit, sub_shape)) =
+                collider.cast_ray_with_sub_shape(iso, &self.ray, self.max_distance, self.solid)
+            {
+                let hit = RayHitData {
+                    entity,
+                    distance: hit.time_of_impact,
+                    normal: hit.normal.into(),
+                    sub_shape,
+                };
+                let index = self
+                    .hits
+                    .partition_point(|other| other.distance > hit.distance);
+                self.hits.insert(index, hit);
+            }
+        }
+    }
+}
+
+/// A [collider](Collider) found by [`SpatialQuery::k_nearest`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct NearestCollider {
+    /// The entity of the collider.
+    pub entity: Entity,
+    /// The closest point on the collider to the query point, expressed in world space.
+    ///
+    /// If the query point is inside of the collider, this is the query point itself.
+    pub point: Vector,
+    /// The distance between the query point and the collider.
+    pub distance: Scalar,
+}
+
+/// A node of the [`Qbvh`] queued for a best-first traversal in [`SpatialQueryPipeline::k_nearest`].
+///
+/// Nodes are ordered by their distance in reverse, so that the closest node
+/// is popped first from a [`BinaryHeap`].
+struct QueuedNode {
+    distance: Scalar,
+    index: u32,
+}
+
+impl PartialEq for QueuedNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedNode {}
+
+impl PartialOrd for QueuedNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
 /// The result of a [point projection](spatial_query#point-projection) on a [collider](Collider).
 #[derive(Clone, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -31,6 +31,37 @@ use crate::prelude::*;
 ///     commands.spawn(RayCaster::default().with_query_filter(query_filter));
 /// }
 /// ```
+///
+/// # Predicates
+///
+/// For rules that depend on gameplay state, like ignoring friendly units, use the `*_predicate` variants
+/// of the queries, such as [`SpatialQuery::cast_ray_predicate`] and [`SpatialQuery::k_nearest_predicate`].
+/// The predicate is only evaluated for colliders that pass the filter, and it can borrow existing state,
+/// so the [excluded entities](Self::excluded_entities) don't need to be rebuilt every frame.
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::{ecs::entity::EntityHashSet, prelude::*};
+///
+/// #[derive(Resource)]
+/// struct FriendlyUnits(EntityHashSet);
+///
+/// fn shoot(spatial_query: SpatialQuery, friendly_units: Res<FriendlyUnits>) {
+///     let filter = SpatialQueryFilter::default();
+///
+///     // Ignore friendly units without copying them into the filter.
+///     let hit = spatial_query.cast_ray_predicate(
+///         Vector::ZERO,
+#[cfg_attr(feature = "2d", doc = "        Dir2::X,")]
+#[cfg_attr(feature = "3d", doc = "        Dir3::X,")]
+///         100.0,
+///         true,
+///         &filter,
+///         &|entity| !friendly_units.0.contains(&entity),
+///     );
+/// }
+/// ```
 #[derive(Clone, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
@@ -0,0 +1,195 @@
+use crate::prelude::*;
+use bevy::{
+    ecs::{entity::EntityHashMap, world::Command},
+    prelude::*,
+};
+
+/// An extension trait for [`Commands`] for physics operations that are commonly written by hand.
+pub trait PhysicsCommandsExt {
+    /// Applies an outward impulse to all dynamic bodies with colliders within `radius` of `center`,
+    /// like an explosion. See [`RadialImpulse`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+    /// use bevy::prelude::*;
+    ///
+    /// fn explode(mut commands: Commands, bombs: Query<&Position, With<Bomb>>) {
+    ///     for position in &bombs {
+    ///         commands.apply_radial_impulse(
+    ///             position.0,
+    ///             5.0,
+    ///             20.0,
+    ///             ImpulseFalloff::Linear,
+    ///             SpatialQueryFilter::default(),
+    ///         );
+    ///     }
+    /// }
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Bomb;
+    /// ```
+    fn apply_radial_impulse(
+        &mut self,
+        center: Vector,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: ImpulseFalloff,
+        filter: SpatialQueryFilter,
+    );
+}
+
+impl PhysicsCommandsExt for Commands<'_, '_> {
+    fn apply_radial_impulse(
+        &mut self,
+        center: Vector,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: ImpulseFalloff,
+        filter: SpatialQueryFilter,
+    ) {
+        self.queue(RadialImpulse {
+            center,
+            radius,
+            strength,
+            falloff,
+            filter,
+        });
+    }
+}
+
+/// A [`Command`] that applies an outward impulse to all dynamic bodies with colliders within
+/// a given `radius` of a `center` point, like an explosion.
+///
+/// The affected colliders are found using the [`SpatialQueryPipeline`]. The impulse is applied at the point
+/// of each body closest to the center, pointing away from the center, so bodies also start spinning
+/// depending on where they were hit. Each body is only affected once, even if it has several colliders.
+/// Affected bodies are woken up if they are [`Sleeping`].
+///
+/// The magnitude of the impulse is the `strength` scaled by the [`ImpulseFalloff`] based on the distance
+/// from the center. Bodies with the center inside of them are pushed away from the center along the direction
+/// to their center of mass.
+///
+/// Typically, this is used through [`PhysicsCommandsExt::apply_radial_impulse`].
+#[derive(Clone, Debug)]
+pub struct RadialImpulse {
+    /// The center of the impulse.
+    pub center: Vector,
+    /// The radius of the affected area.
+    pub radius: Scalar,
+    /// The magnitude of the impulse at the center.
+    pub strength: Scalar,
+    /// How the magnitude of the impulse decreases with distance from the center.
+    pub falloff: ImpulseFalloff,
+    /// A filter that determines which colliders are affected.
+    pub filter: SpatialQueryFilter,
+}
+
+/// Determines how the magnitude of a [`RadialImpulse`] decreases with distance from its center.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum ImpulseFalloff {
+    /// The impulse has the same magnitude everywhere within the radius.
+    Constant,
+    /// The impulse decreases linearly to zero at the radius.
+    #[default]
+    Linear,
+    /// The impulse decreases quadratically to zero at the radius, concentrating it near the center.
+    Quadratic,
+}
+
+impl ImpulseFalloff {
+    /// Returns the factor that the impulse is scaled by at the given `distance` from the center
+    /// for an impulse with the given `radius`.
+    pub fn factor(self, distance: Scalar, radius: Scalar) -> Scalar {
+        if radius <= 0.0 {
+            return 0.0;
+        }
+        let remaining = (1.0 - distance / radius).clamp(0.0, 1.0);
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => remaining,
+            Self::Quadratic => remaining * remaining,
+        }
+    }
+}
+
+impl Command for RadialImpulse {
+    fn apply(self, world: &mut World) {
+        let Some(pipeline) = world.get_resource::<SpatialQueryPipeline>() else {
+            return;
+        };
+
+        #[cfg(feature = "2d")]
+        let shape = Collider::circle(self.radius);
+        #[cfg(feature = "3d")]
+        let shape = Collider::sphere(self.radius);
+
+        // Find the closest point on each affected body.
+        let mut closest_points = EntityHashMap::<Vector>::default();
+        for collider in pipeline.shape_intersections(&shape, self.center, default(), &self.filter) {
+            let Some(body) = world.get::<ColliderParent>(collider).map(|p| p.get()) else {
+                continue;
+            };
+            let Some(projection) =
+                pipeline.project_point_predicate(self.center, true, &self.filter, &|entity| {
+                    entity == collider
+                })
+            else {
+                continue;
+            };
+
+            let point = closest_points.entry(body).or_insert(projection.point);
+            if projection.point.distance_squared(self.center) < point.distance_squared(self.center)
+            {
+                *point = projection.point;
+            }
+        }
+
+        // Apply the impulses and wake up the bodies.
+        for (body, point) in closest_points {
+            let Ok(mut entity_mut) = world.get_entity_mut(body) else {
+                continue;
+            };
+            if !entity_mut
+                .get::<RigidBody>()
+                .is_some_and(|rb| rb.is_dynamic())
+            {
+                continue;
+            }
+
+            let position = entity_mut.get::<Position>().map_or(Vector::ZERO, |p| p.0);
+            let rotation = entity_mut.get::<Rotation>().copied().unwrap_or_default();
+            let local_center_of_mass = entity_mut
+                .get::<ComputedCenterOfMass>()
+                .map_or(Vector::ZERO, |com| com.0);
+            let center_of_mass = position + rotation * local_center_of_mass;
+
+            let distance = point.distance(self.center);
+            let Some(direction) = (point - self.center)
+                .try_normalize()
+                .or_else(|| (center_of_mass - self.center).try_normalize())
+            else {
+                continue;
+            };
+            let impulse = direction * self.strength * self.falloff.factor(distance, self.radius);
+
+            if let Some(mut external_impulse) = entity_mut.get_mut::<ExternalImpulse>() {
+                external_impulse.apply_impulse_at_point(impulse, point, center_of_mass);
+            } else {
+                let mut external_impulse = ExternalImpulse::default();
+                external_impulse.apply_impulse_at_point(impulse, point, center_of_mass);
+                entity_mut.insert(external_impulse);
+            }
+
+            if let Some(mut time_sleeping) = entity_mut.get_mut::<TimeSleeping>() {
+                time_sleeping.0 = 0.0;
+            }
+            entity_mut.remove::<Sleeping>();
+        }
+    }
+}
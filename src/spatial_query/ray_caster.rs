@@ -283,10 +283,19 @@ impl RayCaster {
             );
 
             if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| RayHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    distance: hit.time_of_impact,
-                    normal: hit.normal.into(),
+                |(_, (entity_index, hit))| {
+                    let entity = query_pipeline.entity_from_index(entity_index);
+                    RayHitData {
+                        entity,
+                        distance: hit.time_of_impact,
+                        normal: hit.normal.into(),
+                        sub_shape: query_pipeline.sub_shape_ray_hit(
+                            entity,
+                            &ray,
+                            self.max_distance,
+                            self.solid,
+                        ),
+                    }
                 },
             ) {
                 if (hits.vector.len() as u32) < hits.count + 1 {
@@ -306,24 +315,19 @@ impl RayCaster {
                 let entity = query_pipeline.entity_from_index(*entity_index);
                 if let Some((iso, shape, layers)) = query_pipeline.colliders.get(&entity) {
                     if self.query_filter.test(entity, *layers) {
-                        if let Some(hit) = shape.shape_scaled().cast_ray_and_get_normal(
-                            iso,
-                            &ray,
-                            self.max_distance,
-                            self.solid,
-                        ) {
+                        if let Some((hit, sub_shape)) =
+                            shape.cast_ray_with_sub_shape(iso, &ray, self.max_distance, self.solid)
+                        {
+                            let hit = RayHitData {
+                                entity,
+                                distance: hit.time_of_impact,
+                                normal: hit.normal.into(),
+                                sub_shape,
+                            };
                             if (hits.vector.len() as u32) < hits.count + 1 {
-                                hits.vector.push(RayHitData {
-                                    entity,
-                                    distance: hit.time_of_impact,
-                                    normal: hit.normal.into(),
-                                });
+                                hits.vector.push(hit);
                             } else {
-                                hits.vector[hits.count as usize] = RayHitData {
-                                    entity,
-                                    distance: hit.time_of_impact,
-                                    normal: hit.normal.into(),
-                                };
+                                hits.vector[hits.count as usize] = hit;
                             }
 
                             hits.count += 1;
@@ -458,6 +462,9 @@ pub struct RayHitData {
 
     /// The normal at the point of intersection, expressed in world space.
     pub normal: Vector,
+
+    /// The child shape that was hit if the collider is a [compound](Collider::compound) collider.
+    pub sub_shape: Option<SubShapeHit>,
 }
 
 impl MapEntities for RayHitData {
@@ -465,3 +472,19 @@ impl MapEntities for RayHitData {
         self.entity = entity_mapper.map_entity(self.entity);
     }
 }
+
+/// The child shape of a [compound](Collider::compound) collider that was hit by a
+/// [ray](RayHitData) or [shape](ShapeHitData) cast.
+///
+/// This can be used for distinguishing different hitboxes in a single compound collider,
+/// like the head and body of a character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct SubShapeHit {
+    /// The index of the child shape in the compound collider.
+    pub index: u32,
+    /// The user-defined tag of the child shape, set using [`Collider::with_sub_shape_tags`].
+    pub tag: Option<u32>,
+}
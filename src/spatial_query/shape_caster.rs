@@ -108,6 +108,9 @@ pub struct ShapeCaster {
     global_direction: Dir,
 
     /// The maximum number of hits allowed. By default this is one and only the first hit is returned.
+    ///
+    /// If more than one hit is allowed, all hits are found in a single sweep
+    /// like in [`SpatialQuery::cast_shape_all`]. Use `u32::MAX` to get every hit along the path.
     pub max_hits: u32,
 
     /// The maximum distance the shape can travel.
@@ -344,6 +347,11 @@ impl ShapeCaster {
         hits: &mut ShapeHits,
         query_pipeline: &SpatialQueryPipeline,
     ) {
+        if self.max_hits > 1 {
+            self.cast_all(caster_entity, hits, query_pipeline);
+            return;
+        }
+
         // TODO: This clone is here so that the excluded entities in the original `query_filter` aren't modified.
         //       We could remove this if shapecasting could compute multiple hits without just doing casts in a loop.
         //       See https://github.com/Jondolf/avian/issues/403.
@@ -384,13 +392,17 @@ impl ShapeCaster {
             );
 
             if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| ShapeHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    distance: hit.time_of_impact,
-                    point1: hit.witness1.into(),
-                    point2: hit.witness2.into(),
-                    normal1: hit.normal1.into(),
-                    normal2: hit.normal2.into(),
+                |(_, (entity_index, hit))| {
+                    let entity = query_pipeline.entity_from_index(entity_index);
+                    ShapeHitData {
+                        entity,
+                        distance: hit.time_of_impact,
+                        point1: hit.witness1.into(),
+                        point2: hit.witness2.into(),
+                        normal1: hit.normal1.into(),
+                        normal2: hit.normal2.into(),
+                        sub_shape: query_pipeline.sub_shape_hit(entity, hit.witness1.into()),
+                    }
                 },
             ) {
                 if (hits.vector.len() as u32) < hits.count + 1 {
@@ -406,6 +418,44 @@ impl ShapeCaster {
             }
         }
     }
+
+    /// Computes all hits in a single sweep, keeping at most `max_hits` of the closest ones.
+    fn cast_all(
+        &self,
+        caster_entity: Entity,
+        hits: &mut ShapeHits,
+        query_pipeline: &SpatialQueryPipeline,
+    ) {
+        let shape_rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            shape_rotation = Rotation::radians(self.global_shape_rotation());
+        }
+        #[cfg(feature = "3d")]
+        {
+            shape_rotation = Rotation::from(self.global_shape_rotation());
+        }
+
+        let config = ShapeCastConfig {
+            max_distance: self.max_distance,
+            target_distance: self.target_distance,
+            compute_contact_on_penetration: self.compute_contact_on_penetration,
+            ignore_origin_penetration: self.ignore_origin_penetration,
+        };
+
+        hits.vector.clear();
+        query_pipeline.sweep_shape(
+            &self.shape,
+            self.global_origin(),
+            shape_rotation,
+            self.global_direction(),
+            &config,
+            &self.query_filter,
+            &|entity| !self.ignore_self || entity != caster_entity,
+            &mut hits.vector,
+        );
+        hits.count = hits.vector.len().min(self.max_hits as usize) as u32;
+    }
 }
 
 fn on_add_shape_caster(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
@@ -604,6 +654,9 @@ pub struct ShapeHitData {
 
     /// The outward surface normal on the cast shape at `point2`, expressed in world space.
     pub normal2: Vector,
+
+    /// The child shape that was hit if the collider is a [compound](Collider::compound) collider.
+    pub sub_shape: Option<SubShapeHit>,
 }
 
 impl MapEntities for ShapeHitData {
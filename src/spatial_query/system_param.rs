@@ -1,19 +1,31 @@
 use crate::prelude::*;
-use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy::{
+    ecs::{
+        component::Tick,
+        system::{SystemChangeTick, SystemParam},
+    },
+    prelude::*,
+};
 
 /// A system parameter for performing [spatial queries](spatial_query).
 ///
 /// # Methods
 ///
 /// - [Raycasting](spatial_query#raycasting): [`cast_ray`](SpatialQuery::cast_ray), [`cast_ray_predicate`](SpatialQuery::cast_ray_predicate),
-///   [`ray_hits`](SpatialQuery::ray_hits), [`ray_hits_callback`](SpatialQuery::ray_hits_callback)
+///   [`ray_hits`](SpatialQuery::ray_hits), [`ray_hits_callback`](SpatialQuery::ray_hits_callback),
+///   [`ray_hits_sorted`](SpatialQuery::ray_hits_sorted)
 /// - [Shapecasting](spatial_query#shapecasting): [`cast_shape`](SpatialQuery::cast_shape), [`cast_shape_predicate`](SpatialQuery::cast_shape_predicate),
-///   [`shape_hits`](SpatialQuery::shape_hits), [`shape_hits_callback`](SpatialQuery::shape_hits_callback)
+///   [`shape_hits`](SpatialQuery::shape_hits), [`shape_hits_callback`](SpatialQuery::shape_hits_callback),
+///   [`cast_shape_all`](SpatialQuery::cast_shape_all), [`cast_shape_all_predicate`](SpatialQuery::cast_shape_all_predicate)
 /// - [Point projection](spatial_query#point-projection): [`project_point`](SpatialQuery::project_point) and [`project_point_predicate`](SpatialQuery::project_point_predicate)
+/// - Closest points: [`closest_point`](SpatialQuery::closest_point), [`distance_between`](SpatialQuery::distance_between)
+///   and [`k_nearest`](SpatialQuery::k_nearest)
 /// - [Intersection tests](spatial_query#intersection-tests)
 ///     - Point intersections: [`point_intersections`](SpatialQuery::point_intersections),
 ///       [`point_intersections_callback`](SpatialQuery::point_intersections_callback)
-///     - AABB intersections: [`aabb_intersections_with_aabb`](SpatialQuery::aabb_intersections_with_aabb),
+///     - AABB intersections: [`aabb_intersections`](SpatialQuery::aabb_intersections),
+///       [`aabb_intersections_callback`](SpatialQuery::aabb_intersections_callback),
+///       [`aabb_intersections_with_aabb`](SpatialQuery::aabb_intersections_with_aabb),
 ///       [`aabb_intersections_with_aabb_callback`](SpatialQuery::aabb_intersections_with_aabb_callback)
 ///     - Shape intersections: [`shape_intersections`](SpatialQuery::shape_intersections)
 ///       [`shape_intersections_callback`](SpatialQuery::shape_intersections_callback)
@@ -62,14 +74,15 @@ pub struct SpatialQuery<'w, 's> {
         's,
         (
             Entity,
-            &'static Position,
-            &'static Rotation,
-            &'static Collider,
-            Option<&'static CollisionLayers>,
+            Ref<'static, Position>,
+            Ref<'static, Rotation>,
+            Ref<'static, Collider>,
+            Option<Ref<'static, CollisionLayers>>,
         ),
         Without<ColliderDisabled>,
     >,
     pub(crate) added_colliders: Query<'w, 's, Entity, Added<Collider>>,
+    pub(crate) change_tick: SystemChangeTick,
     /// The [`SpatialQueryPipeline`].
     pub query_pipeline: ResMut<'w, SpatialQueryPipeline>,
 }
@@ -78,9 +91,70 @@ impl SpatialQuery<'_, '_> {
     /// Updates the colliders in the pipeline. This is done automatically once per physics frame in
     /// [`PhysicsStepSet::SpatialQuery`], but if you modify colliders or their positions before that, you can
     /// call this to make sure the data is up to date when performing spatial queries using [`SpatialQuery`].
+    ///
+    /// This rebuilds the whole acceleration structure. See [`update_pipeline_incremental`](Self::update_pipeline_incremental)
+    /// for only updating the colliders that have changed.
     pub fn update_pipeline(&mut self) {
+        self.query_pipeline.update(
+            self.colliders
+                .iter()
+                .map(|(entity, position, rotation, collider, layers)| {
+                    (
+                        entity,
+                        position.into_inner(),
+                        rotation.into_inner(),
+                        collider.into_inner(),
+                        layers.map(Ref::into_inner),
+                    )
+                }),
+            self.added_colliders.iter(),
+        );
+        self.query_pipeline.last_update_tick = self.change_tick.this_run();
+    }
+
+    /// Updates the colliders that have been added, changed, or removed since the last update of the pipeline,
+    /// keeping the rest of the acceleration structure intact.
+    ///
+    /// See [`SpatialQueryPipeline::update_incremental`] for more information.
+    pub fn update_pipeline_incremental(&mut self) {
+        let last_update = self.query_pipeline.last_update_tick;
+        let this_run = self.change_tick.this_run();
+        let pipeline = &self.query_pipeline;
+
+        let changed_colliders: Vec<_> = self
+            .colliders
+            .iter()
+            .filter(|(entity, position, rotation, collider, layers)| {
+                let changed = |tick: Tick| tick.is_newer_than(last_update, this_run);
+                !pipeline.colliders.contains_key(entity)
+                    || changed(position.last_changed())
+                    || changed(rotation.last_changed())
+                    || changed(collider.last_changed())
+                    || layers
+                        .as_ref()
+                        .is_some_and(|layers| changed(layers.last_changed()))
+            })
+            .map(|(entity, position, rotation, collider, layers)| {
+                (
+                    entity,
+                    position.into_inner(),
+                    rotation.into_inner(),
+                    collider.into_inner(),
+                    layers.map(Ref::into_inner),
+                )
+            })
+            .collect();
+
+        let removed_colliders: Vec<Entity> = pipeline
+            .colliders
+            .keys()
+            .filter(|entity| !self.colliders.contains(**entity))
+            .copied()
+            .collect();
+
         self.query_pipeline
-            .update(self.colliders.iter(), self.added_colliders.iter());
+            .update_incremental(changed_colliders.into_iter(), removed_colliders.into_iter());
+        self.query_pipeline.last_update_tick = this_run;
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -348,6 +422,65 @@ impl SpatialQuery<'_, '_> {
         )
     }
 
+    /// Casts a [ray](spatial_query#raycasting) and returns an iterator over its [hits](RayHitData),
+    /// sorted by distance in ascending order.
+    ///
+    /// The hits are computed lazily, so stopping the iteration early skips the intersection tests
+    /// for colliders further along the ray. This is useful for finding the first hit that passes
+    /// a gameplay test that can't be expressed with a [`SpatialQueryFilter`].
+    ///
+    /// # Arguments
+    ///
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_distance`: The maximum distance the ray can travel.
+    /// - `solid`: If true *and* the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    ///   Otherwise, the collider will be treated as hollow, and the hit point will be at its boundary.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which entities are included in the cast.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Glass;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn first_opaque_hit(spatial_query: SpatialQuery, glass: Query<(), With<Glass>>) {
+    ///     let filter = SpatialQueryFilter::default();
+    ///
+    ///     // Find the closest hit that isn't glass.
+    ///     let hit = spatial_query
+    ///         .ray_hits_sorted(Vec3::ZERO, Dir3::X, 100.0, true, &filter)
+    ///         .find(|hit| !glass.contains(hit.entity));
+    ///
+    ///     if let Some(hit) = hit {
+    ///         println!("Hit: {:?}", hit);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::cast_ray`]
+    /// - [`SpatialQuery::ray_hits`]
+    pub fn ray_hits_sorted<'a>(
+        &'a self,
+        origin: Vector,
+        direction: Dir,
+        max_distance: Scalar,
+        solid: bool,
+        filter: &'a SpatialQueryFilter,
+    ) -> SortedRayHits<'a> {
+        self.query_pipeline
+            .ray_hits_sorted(origin, direction, max_distance, solid, filter)
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeHitData)
     /// with a collider. If there are no hits, `None` is returned.
     ///
@@ -636,6 +769,108 @@ impl SpatialQuery<'_, '_> {
         )
     }
 
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes all [hits](ShapeHitData)
+    /// along its path, sorted by distance.
+    ///
+    /// Unlike [`SpatialQuery::shape_hits`], which performs a new cast for every hit,
+    /// this finds all hits in a single sweep, which is much cheaper when the shape passes through
+    /// many colliders, for example with piercing projectiles or wide attack sweeps.
+    /// Each collider is hit at most once, at the distance where the shape first reaches it.
+    ///
+    /// # Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `config`: A [`ShapeCastConfig`] that determines the behavior of the cast.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which entities are included in the cast.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn sword_swing(spatial_query: SpatialQuery) {
+    ///     // A wide box swept in front of the player
+    ///     let shape = Collider::cuboid(2.0, 1.0, 0.2);
+    ///     let origin = Vec3::ZERO;
+    ///     let rotation = Quat::default();
+    ///     let direction = Dir3::NEG_Z;
+    ///
+    ///     let config = ShapeCastConfig::from_max_distance(3.0);
+    ///     let filter = SpatialQueryFilter::default();
+    ///
+    ///     // Damage everything in the path of the swing, closest first
+    ///     for hit in spatial_query.cast_shape_all(&shape, origin, rotation, direction, &config, &filter) {
+    ///         println!("Hit {} at distance {}", hit.entity, hit.distance);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::cast_shape_all_predicate`]
+    /// - [`SpatialQuery::shape_hits`]
+    pub fn cast_shape_all(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<ShapeHitData> {
+        self.query_pipeline
+            .cast_shape_all(shape, origin, shape_rotation, direction, config, filter)
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes all [hits](ShapeHitData)
+    /// along its path, sorted by distance. Only colliders for which the `predicate` returns `true` are hit.
+    ///
+    /// See [`SpatialQuery::cast_shape_all`] for more information.
+    ///
+    /// # Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `config`: A [`ShapeCastConfig`] that determines the behavior of the cast.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which entities are included in the cast.
+    /// - `predicate`: A function called on each entity in the path of the shape. Entities for which it returns `false` are skipped.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::cast_shape_all`]
+    /// - [`SpatialQuery::shape_hits`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_all_predicate(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Vec<ShapeHitData> {
+        self.query_pipeline.cast_shape_all_predicate(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            config,
+            filter,
+            predicate,
+        )
+    }
+
     /// Finds the [projection](spatial_query#point-projection) of a given point on the closest [collider](Collider).
     /// If one isn't found, `None` is returned.
     ///
@@ -734,6 +969,214 @@ impl SpatialQuery<'_, '_> {
             .project_point_predicate(point, solid, filter, predicate)
     }
 
+    /// Finds the closest point on the surface of any [collider](Collider) to the given point.
+    /// If there are no colliders, `None` is returned.
+    ///
+    /// This is equivalent to a hollow [point projection](spatial_query#point-projection), so even
+    /// if the point is inside of a collider, the returned point is on the collider's boundary.
+    ///
+    /// # Arguments
+    ///
+    /// - `point`: The point that the closest point is computed for.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Cursor;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn snap_to_surface(spatial_query: SpatialQuery, mut cursor: Single<&mut Transform, With<Cursor>>) {
+    ///     let filter = SpatialQueryFilter::default();
+    ///
+    ///     if let Some(projection) = spatial_query.closest_point(cursor.translation, &filter) {
+    ///         cursor.translation = projection.point;
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::project_point`]
+    /// - [`SpatialQuery::distance_between`]
+    pub fn closest_point(
+        &self,
+        point: Vector,
+        filter: &SpatialQueryFilter,
+    ) -> Option<PointProjection> {
+        self.query_pipeline.closest_point(point, filter)
+    }
+
+    /// Computes the distance and the closest points between the colliders of two entities.
+    ///
+    /// Returns `None` if either entity does not have a collider,
+    /// or if the distance between the shapes can not be computed.
+    ///
+    /// The colliders are evaluated at the positions they had when the [`SpatialQueryPipeline`] was last updated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Agent {
+    ///     target: Entity,
+    /// }
+    ///
+    /// fn print_distances(spatial_query: SpatialQuery, agents: Query<(Entity, &Agent)>) {
+    ///     for (entity, agent) in &agents {
+    ///         if let Some(result) = spatial_query.distance_between(entity, agent.target) {
+    ///             println!("Distance to target: {}", result.distance);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::closest_point`]
+    pub fn distance_between(&self, entity1: Entity, entity2: Entity) -> Option<ColliderDistance> {
+        self.query_pipeline.distance_between(entity1, entity2)
+    }
+
+    /// Finds the `k` [colliders](Collider) closest to the given point, along with their distances.
+    /// The results are sorted by distance in ascending order.
+    ///
+    /// Colliders that contain the point have a distance of zero.
+    ///
+    /// # Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `k`: The maximum number of colliders to return.
+    /// - `max_distance`: The maximum distance from the point. Colliders further away are ignored.
+    ///   Can be [`Scalar::INFINITY`] to consider all colliders, but a finite distance
+    ///   lets the query skip colliders that are far away.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Turret;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn pick_targets(spatial_query: SpatialQuery, turrets: Query<(Entity, &Transform), With<Turret>>) {
+    ///     for (entity, transform) in &turrets {
+    ///         // Ignore the turret's own collider.
+    ///         let filter = SpatialQueryFilter::default().with_excluded_entities([entity]);
+    ///
+    ///         // Find the three closest colliders within 50 units.
+    ///         for target in spatial_query.k_nearest(transform.translation, 3, 50.0, &filter) {
+    ///             println!("Target {} at distance {}", target.entity, target.distance);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::k_nearest_predicate`]
+    /// - [`SpatialQuery::closest_point`]
+    pub fn k_nearest(
+        &self,
+        point: Vector,
+        k: usize,
+        max_distance: Scalar,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<NearestCollider> {
+        self.query_pipeline
+            .k_nearest(point, k, max_distance, filter)
+    }
+
+    /// Finds the `k` [colliders](Collider) closest to the given point that match the `predicate`,
+    /// along with their distances. The results are sorted by distance in ascending order.
+    ///
+    /// Colliders that contain the point have a distance of zero.
+    /// Colliders rejected by the predicate don't count towards the `k` results.
+    ///
+    /// # Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `k`: The maximum number of colliders to return.
+    /// - `max_distance`: The maximum distance from the point. Colliders further away are ignored.
+    ///   Can be [`Scalar::INFINITY`] to consider all colliders, but a finite distance
+    ///   lets the query skip colliders that are far away.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `predicate`: A function for filtering which entities are considered in the query. Entities for which it returns `false` are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Turret;
+    ///
+    /// #[derive(Component)]
+    /// struct Friendly;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn pick_targets(
+    ///     spatial_query: SpatialQuery,
+    ///     turrets: Query<(Entity, &Transform), With<Turret>>,
+    ///     friendly: Query<(), With<Friendly>>,
+    /// ) {
+    ///     for (entity, transform) in &turrets {
+    ///         let filter = SpatialQueryFilter::default().with_excluded_entities([entity]);
+    ///
+    ///         // Find the three closest colliders within 50 units, ignoring friendly units.
+    ///         let targets = spatial_query.k_nearest_predicate(
+    ///             transform.translation,
+    ///             3,
+    ///             50.0,
+    ///             &filter,
+    ///             &|entity| !friendly.contains(entity),
+    ///         );
+    ///
+    ///         for target in targets {
+    ///             println!("Target {} at distance {}", target.entity, target.distance);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::k_nearest`]
+    pub fn k_nearest_predicate(
+        &self,
+        point: Vector,
+        k: usize,
+        max_distance: Scalar,
+        filter: &SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Vec<NearestCollider> {
+        self.query_pipeline
+            .k_nearest_predicate(point, k, max_distance, filter, predicate)
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that contains the given point.
     ///
@@ -893,6 +1336,74 @@ impl SpatialQuery<'_, '_> {
             .aabb_intersections_with_aabb_callback(aabb, callback)
     }
 
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
+    /// whose AABB is intersecting the given `aabb`.
+    ///
+    /// Only the bounding boxes are tested, so this is much cheaper than a [shape intersection](Self::shape_intersections)
+    /// test against a cuboid, but it can also return colliders whose actual shape doesn't overlap the region.
+    /// This makes it well suited for coarse queries like activating the contents of world chunks.
+    ///
+    /// # Arguments
+    ///
+    /// - `aabb`: The region that intersections are tested against.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "2d")]
+    /// # use avian2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use avian3d::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_chunk_contents(spatial_query: SpatialQuery) {
+    ///     let chunk = ColliderAabb::new(Vec3::splat(16.0), Vec3::splat(16.0));
+    ///     let filter = SpatialQueryFilter::default();
+    ///
+    ///     for entity in spatial_query.aabb_intersections(chunk, &filter) {
+    ///         println!("Entity: {}", entity);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::aabb_intersections_callback`]
+    pub fn aabb_intersections(
+        &self,
+        aabb: ColliderAabb,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<Entity> {
+        self.query_pipeline.aabb_intersections(aabb, filter)
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
+    /// whose AABB is intersecting the given `aabb`, calling `callback` for each intersection.
+    /// The search stops when `callback` returns `false` or all intersections have been found.
+    ///
+    /// See [`SpatialQuery::aabb_intersections`] for more information.
+    ///
+    /// # Arguments
+    ///
+    /// - `aabb`: The region that intersections are tested against.
+    /// - `filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `callback`: A callback function called for each intersection.
+    ///
+    /// # Related Methods
+    ///
+    /// - [`SpatialQuery::aabb_intersections`]
+    pub fn aabb_intersections_callback(
+        &self,
+        aabb: ColliderAabb,
+        filter: &SpatialQueryFilter,
+        callback: impl FnMut(Entity) -> bool,
+    ) {
+        self.query_pipeline
+            .aabb_intersections_callback(aabb, filter, callback)
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
     /// that is intersecting the given `shape` with a given position and rotation.
     ///
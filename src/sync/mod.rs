@@ -23,7 +23,8 @@ pub mod ancestor_marker;
 /// or position bodies, and the changes be reflected in the other components.
 ///
 /// You can configure what data is synchronized and how it is synchronized
-/// using the [`SyncConfig`] resource.
+/// using the [`SyncConfig`] resource. The [`SyncMode`] component can be used to only synchronize
+/// the position or rotation of specific entities.
 ///
 /// # `Transform` Hierarchies
 ///
@@ -173,6 +174,56 @@ impl Default for SyncConfig {
     }
 }
 
+/// Configures which parts of the transform of an entity are synchronized
+/// with its [`Position`] and [`Rotation`].
+///
+/// By default, both the position and the rotation are synchronized. This can be useful
+/// when one of them is driven by something other than physics. For example, the rotation
+/// of a character could be driven by animation, while its position is driven by physics.
+///
+/// [`SyncConfig`] still determines the directions in which data is synchronized.
+/// If a direction is disabled there, it is disabled for all entities.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // Physics only moves the body. Its rotation is controlled through `Transform`.
+///     commands.spawn((RigidBody::Kinematic, SyncMode::PositionOnly));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub enum SyncMode {
+    /// Both the position and the rotation are synchronized.
+    #[default]
+    Full,
+    /// Only the position is synchronized with the translation of the transform.
+    PositionOnly,
+    /// Only the rotation is synchronized with the rotation of the transform.
+    RotationOnly,
+    /// Neither the position nor the rotation is synchronized.
+    None,
+}
+
+impl SyncMode {
+    /// Returns `true` if the position is synchronized.
+    pub fn syncs_position(self) -> bool {
+        matches!(self, Self::Full | Self::PositionOnly)
+    }
+
+    /// Returns `true` if the rotation is synchronized.
+    pub fn syncs_rotation(self) -> bool {
+        matches!(self, Self::Full | Self::RotationOnly)
+    }
+}
+
 /// System sets for systems running in [`PhysicsSet::Sync`].
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SyncSet {
@@ -191,7 +242,9 @@ pub enum SyncSet {
 /// The global transform of a body at the end of the previous frame.
 /// Used for detecting if the transform was modified before the start of the physics schedule.
 #[derive(Component, Reflect, Clone, Copy, Debug, Default, Deref, DerefMut, PartialEq)]
-#[reflect(Component)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
 pub struct PreviousGlobalTransform(pub GlobalTransform);
 
 #[allow(clippy::type_complexity)]
@@ -220,6 +273,7 @@ pub fn transform_to_position(
         &mut Rotation,
         Option<&PreviousRotation>,
         Option<&ComputedCenterOfMass>,
+        Option<&SyncMode>,
     )>,
 ) {
     for (
@@ -230,6 +284,7 @@ pub fn transform_to_position(
         mut rotation,
         previous_rotation,
         center_of_mass,
+        sync_mode,
     ) in &mut query
     {
         // Skip entity if the global transform value hasn't changed
@@ -237,6 +292,8 @@ pub fn transform_to_position(
             continue;
         }
 
+        let sync_mode = sync_mode.copied().unwrap_or_default();
+
         let transform = global_transform.compute_transform();
         let previous_transform = previous_transform.compute_transform();
         let pos = position.0
@@ -250,20 +307,24 @@ pub fn transform_to_position(
             });
 
         #[cfg(feature = "2d")]
-        {
+        if sync_mode.syncs_position() {
             position.0 = (previous_transform.translation.truncate()
                 + (transform.translation - previous_transform.translation).truncate())
             .adjust_precision()
                 + (pos - previous_transform.translation.truncate().adjust_precision());
         }
         #[cfg(feature = "3d")]
-        {
+        if sync_mode.syncs_position() {
             position.0 = (previous_transform.translation
                 + (transform.translation - previous_transform.translation))
                 .adjust_precision()
                 + (pos - previous_transform.translation.adjust_precision());
         }
 
+        if !sync_mode.syncs_rotation() {
+            continue;
+        }
+
         #[cfg(feature = "2d")]
         {
             let rot = Rotation::from(transform.rotation.adjust_precision());
@@ -285,6 +346,7 @@ type PosToTransformComponents = (
     &'static Position,
     &'static Rotation,
     Option<&'static Parent>,
+    Option<&'static SyncMode>,
 );
 
 type PosToTransformFilter = (With<RigidBody>, Or<(Changed<Position>, Changed<Rotation>)>);
@@ -305,7 +367,9 @@ pub fn position_to_transform(
     mut query: Query<PosToTransformComponents, PosToTransformFilter>,
     parents: Query<ParentComponents, With<Children>>,
 ) {
-    for (mut transform, pos, rot, parent) in &mut query {
+    for (mut transform, pos, rot, parent, sync_mode) in &mut query {
+        let sync_mode = sync_mode.copied().unwrap_or_default();
+
         if let Some(parent) = parent {
             if let Ok((parent_transform, parent_pos, parent_rot)) = parents.get(**parent) {
                 // Compute the global transform of the parent using its Position and Rotation
@@ -332,12 +396,20 @@ pub fn position_to_transform(
                 )
                 .reparented_to(&GlobalTransform::from(parent_transform));
 
-                transform.translation = new_transform.translation;
-                transform.rotation = new_transform.rotation;
+                if sync_mode.syncs_position() {
+                    transform.translation = new_transform.translation;
+                }
+                if sync_mode.syncs_rotation() {
+                    transform.rotation = new_transform.rotation;
+                }
             }
         } else {
-            transform.translation = pos.f32().extend(transform.translation.z);
-            transform.rotation = Quaternion::from(*rot).f32();
+            if sync_mode.syncs_position() {
+                transform.translation = pos.f32().extend(transform.translation.z);
+            }
+            if sync_mode.syncs_rotation() {
+                transform.rotation = Quaternion::from(*rot).f32();
+            }
         }
     }
 }
@@ -352,7 +424,9 @@ pub fn position_to_transform(
     mut query: Query<PosToTransformComponents, PosToTransformFilter>,
     parents: Query<ParentComponents, With<Children>>,
 ) {
-    for (mut transform, pos, rot, parent) in &mut query {
+    for (mut transform, pos, rot, parent, sync_mode) in &mut query {
+        let sync_mode = sync_mode.copied().unwrap_or_default();
+
         if let Some(parent) = parent {
             if let Ok((parent_transform, parent_pos, parent_rot)) = parents.get(**parent) {
                 // Compute the global transform of the parent using its Position and Rotation
@@ -371,12 +445,20 @@ pub fn position_to_transform(
                 )
                 .reparented_to(&GlobalTransform::from(parent_transform));
 
-                transform.translation = new_transform.translation;
-                transform.rotation = new_transform.rotation;
+                if sync_mode.syncs_position() {
+                    transform.translation = new_transform.translation;
+                }
+                if sync_mode.syncs_rotation() {
+                    transform.rotation = new_transform.rotation;
+                }
             }
         } else {
-            transform.translation = pos.f32();
-            transform.rotation = rot.f32();
+            if sync_mode.syncs_position() {
+                transform.translation = pos.f32();
+            }
+            if sync_mode.syncs_rotation() {
+                transform.rotation = rot.f32();
+            }
         }
     }
 }
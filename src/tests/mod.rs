@@ -47,6 +47,19 @@ fn tick_app(app: &mut App, timestep: f64) {
     app.update();
 }
 
+/// Creates a square in 2D or a cube in 3D with the given side length.
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn cuboid(size: Scalar) -> Collider {
+    #[cfg(feature = "2d")]
+    {
+        Collider::rectangle(size, size)
+    }
+    #[cfg(feature = "3d")]
+    {
+        Collider::cuboid(size, size, size)
+    }
+}
+
 #[cfg(all(feature = "3d", feature = "default-collider"))]
 fn setup_cubes_simulation(mut commands: Commands) {
     let mut next_id = 0;
@@ -141,6 +154,5303 @@ fn body_with_velocity_moves() {
     );
 }
 
+#[test]
+#[cfg(feature = "bevy_scene")]
+fn physics_scene_restores_body_state() {
+    use bevy::ecs::entity::EntityHashMap;
+
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            Transform::default(),
+            RigidBody::Dynamic,
+            LinearVelocity(Vector::X),
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::from_shape(&Circle::new(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::from_shape(&Sphere::new(0.5), 1.0),
+        ))
+        .id();
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let saved_position = *app.world().get::<Position>(entity).unwrap();
+    let scene = app.world().extract_physics_scene();
+
+    // Change the state after saving.
+    app.world_mut().get_mut::<LinearVelocity>(entity).unwrap().0 = Vector::Y;
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert_ne!(
+        *app.world().get::<Position>(entity).unwrap(),
+        saved_position
+    );
+
+    let mut entity_map = EntityHashMap::from_iter([(entity, entity)]);
+    app.world_mut()
+        .restore_physics_scene(&scene, &mut entity_map)
+        .unwrap();
+
+    assert_eq!(
+        *app.world().get::<Position>(entity).unwrap(),
+        saved_position
+    );
+    assert_eq!(
+        *app.world().get::<LinearVelocity>(entity).unwrap(),
+        LinearVelocity(Vector::X)
+    );
+
+    // Stepping after restoring should continue from the saved state.
+    tick_app(&mut app, 1.0 / 60.0);
+    let position = app.world().get::<Position>(entity).unwrap();
+    assert!(position.x > saved_position.x);
+    assert_eq!(position.y, saved_position.y);
+}
+
+#[test]
+fn sync_mode_position_only_keeps_transform_rotation() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            Transform::default(),
+            RigidBody::Kinematic,
+            SyncMode::PositionOnly,
+            LinearVelocity(Vector::X),
+            #[cfg(feature = "2d")]
+            AngularVelocity(1.0),
+            #[cfg(feature = "3d")]
+            AngularVelocity(Vector::Y),
+        ))
+        .id();
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let transform = app.world().get::<Transform>(entity).unwrap();
+    assert!(transform.translation.x > 0.0);
+    assert_eq!(transform.rotation, Quat::IDENTITY);
+    assert_ne!(
+        *app.world().get::<Rotation>(entity).unwrap(),
+        Rotation::default()
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn touching_bodies_share_island() {
+    use crate::dynamics::solver::ContactConstraints;
+
+    let mut app = create_app();
+
+    // The ground is static, so it doesn't connect the bodies resting on it.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        cuboid(50.0),
+    ));
+    let stack = [
+        app.world_mut()
+            .spawn((RigidBody::Dynamic, Position(Vector::Y * 0.5), cuboid(1.0)))
+            .id(),
+        app.world_mut()
+            .spawn((RigidBody::Dynamic, Position(Vector::Y * 1.5), cuboid(1.0)))
+            .id(),
+    ];
+    let single = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::X * 10.0 + Vector::Y * 0.5),
+            cuboid(1.0),
+        ))
+        .id();
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let islands = app.world().resource::<PhysicsIslands>();
+    assert_eq!(islands.len(), 2);
+    assert_eq!(
+        islands.island_index(stack[0]),
+        islands.island_index(stack[1])
+    );
+    assert_ne!(islands.island_index(stack[0]), islands.island_index(single));
+    assert_eq!(islands.island_of(stack[0]).unwrap().len(), 2);
+    assert_eq!(islands.island_of(single).unwrap().len(), 1);
+
+    // The constraints of each island are stored contiguously.
+    let constraints = app.world().resource::<ContactConstraints>();
+    for (index, island) in islands.iter().enumerate() {
+        for constraint in &constraints[island.contact_constraints.clone()] {
+            let island1 = islands.island_index(constraint.entity1);
+            let island2 = islands.island_index(constraint.entity2);
+            assert!(island1 == Some(index) || island2 == Some(index));
+        }
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn island_ids_are_stored_on_bodies() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let pair = [
+        app.world_mut()
+            .spawn((RigidBody::Dynamic, collider.clone()))
+            .id(),
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                collider.clone(),
+                Position(Vector::X * 0.9),
+            ))
+            .id(),
+    ];
+    let single = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            Position(Vector::X * 10.0),
+        ))
+        .id();
+    let static_body = app
+        .world_mut()
+        .spawn((RigidBody::Static, collider, Position(Vector::Y * 10.0)))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let island_id = |app: &App, entity| app.world().get::<PhysicsIslandId>(entity).copied();
+    let islands = app.world().resource::<PhysicsIslands>();
+    for entity in pair.into_iter().chain([single]) {
+        assert_eq!(
+            island_id(&app, entity),
+            islands.island_index(entity).map(PhysicsIslandId)
+        );
+    }
+    assert_eq!(island_id(&app, pair[0]), island_id(&app, pair[1]));
+    assert_ne!(island_id(&app, pair[0]), island_id(&app, single));
+    assert_eq!(island_id(&app, static_body), None);
+
+    // Bodies that are no longer dynamic are removed from their islands.
+    app.world_mut().entity_mut(single).insert(RigidBody::Static);
+    tick_app(&mut app, 1.0 / 60.0);
+    assert_eq!(island_id(&app, single), None);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn island_merges_and_splits_send_events() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body1 = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider.clone()))
+        .id();
+    let body2 = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider, Position(Vector::X * 5.0)))
+        .id();
+
+    let mut merged_cursor = app.world().resource::<Events<IslandsMerged>>().get_cursor();
+    let mut split_cursor = app.world().resource::<Events<IslandSplit>>().get_cursor();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    // Move the bodies into contact, merging their islands.
+    app.world_mut().get_mut::<Position>(body2).unwrap().0 = Vector::X * 0.9;
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let merged: Vec<IslandsMerged> = merged_cursor
+        .read(app.world().resource::<Events<IslandsMerged>>())
+        .cloned()
+        .collect();
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].bodies.len(), 2);
+    assert_eq!(merged[0].previous_islands, vec![vec![body1], vec![body2]]);
+
+    // Move the bodies apart, splitting the island.
+    app.world_mut().get_mut::<Position>(body2).unwrap().0 = Vector::X * 10.0;
+    for _ in 0..2 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let split: Vec<IslandSplit> = split_cursor
+        .read(app.world().resource::<Events<IslandSplit>>())
+        .cloned()
+        .collect();
+    assert_eq!(split.len(), 1);
+    assert_eq!(split[0].islands, vec![vec![body1], vec![body2]]);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn stacked_bodies_sleep_and_wake_together() {
+    let mut app = create_app();
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        cuboid(50.0),
+    ));
+    let bottom = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 0.5), cuboid(1.0)))
+        .id();
+    let top = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 1.5), cuboid(1.0)))
+        .id();
+
+    for _ in 0..180 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The whole stack falls asleep together.
+    assert!(app.world().entity(bottom).contains::<Sleeping>());
+    assert!(app.world().entity(top).contains::<Sleeping>());
+
+    // Waking up the top body wakes up the whole island.
+    app.world_mut().get_mut::<LinearVelocity>(top).unwrap().0 = Vector::X;
+    tick_app(&mut app, 1.0 / 60.0);
+
+    assert!(!app.world().entity(bottom).contains::<Sleeping>());
+    assert!(!app.world().entity(top).contains::<Sleeping>());
+}
+
+#[test]
+fn sleep_threshold_overrides_global_threshold() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let slow_rotation = AngularVelocity(0.05);
+    #[cfg(feature = "3d")]
+    let slow_rotation = AngularVelocity(Vector::Y * 0.05);
+
+    let default_body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, slow_rotation))
+        .id();
+    let puzzle_piece = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            slow_rotation,
+            SleepThreshold::new(0.01, 0.01, 0.5),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The rotation is below the global threshold, but above the custom one.
+    assert!(app.world().entity(default_body).contains::<Sleeping>());
+    assert!(!app.world().entity(puzzle_piece).contains::<Sleeping>());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn bvh_broad_phase_finds_same_pairs_as_sweep_and_prune() {
+    fn run(method: BroadPhaseMethod) -> Vec<(usize, usize)> {
+        let mut app = create_app();
+
+        app.insert_resource(Gravity::ZERO)
+            .insert_resource(BroadPhaseConfig {
+                method,
+                ..default()
+            });
+
+        // A row of overlapping bodies spread out along the y-axis,
+        // a few bodies that don't touch anything, and two overlapping static bodies,
+        // one of which touches the bottom of the row.
+        let mut bodies = Vec::new();
+        for i in 0..10 {
+            let y = i as Scalar * 0.9;
+            bodies.push(
+                app.world_mut()
+                    .spawn((
+                        RigidBody::Dynamic,
+                        Position(Vector::Y * y),
+                        #[cfg(feature = "2d")]
+                        Collider::circle(0.5),
+                        #[cfg(feature = "3d")]
+                        Collider::sphere(0.5),
+                    ))
+                    .id(),
+            );
+        }
+        for i in 0..3 {
+            bodies.push(
+                app.world_mut()
+                    .spawn((
+                        RigidBody::Dynamic,
+                        Position(Vector::X * (10.0 + i as Scalar * 5.0)),
+                        #[cfg(feature = "2d")]
+                        Collider::circle(0.5),
+                        #[cfg(feature = "3d")]
+                        Collider::sphere(0.5),
+                    ))
+                    .id(),
+            );
+        }
+
+        for y in [-0.9, -1.5] {
+            bodies.push(
+                app.world_mut()
+                    .spawn((
+                        RigidBody::Static,
+                        Position(Vector::Y * y),
+                        #[cfg(feature = "2d")]
+                        Collider::circle(0.5),
+                        #[cfg(feature = "3d")]
+                        Collider::sphere(0.5),
+                    ))
+                    .id(),
+            );
+        }
+
+        tick_app(&mut app, 1.0 / 60.0);
+
+        let collisions = app.world().resource::<Collisions>();
+        let mut pairs = Vec::new();
+        for i in 0..bodies.len() {
+            for j in i + 1..bodies.len() {
+                if collisions.contains(bodies[i], bodies[j]) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    let pairs = run(BroadPhaseMethod::Bvh);
+    // Static bodies are never paired with each other.
+    assert_eq!(pairs.len(), 10);
+    assert_eq!(pairs, run(BroadPhaseMethod::SweepAndPrune));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn broad_phase_pairs_can_be_filtered() {
+    use crate::collision::broad_phase::BroadPhaseSet;
+
+    #[derive(Component)]
+    struct NoCollisions;
+
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO).add_systems(
+        PhysicsSchedule,
+        (|mut pairs: ResMut<BroadCollisionPairs>, query: Query<(), With<NoCollisions>>| {
+            pairs
+                .retain(|&(entity1, entity2)| !query.contains(entity1) && !query.contains(entity2));
+        })
+        .in_set(BroadPhaseSet::FilterPairs),
+    );
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider.clone()))
+        .id();
+    let filtered = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::X * 0.5),
+            collider.clone(),
+            NoCollisions,
+        ))
+        .id();
+    let other = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::NEG_X * 0.5), collider))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let collisions = app.world().resource::<Collisions>();
+    assert!(!collisions.contains(body, filtered));
+    assert!(collisions.contains(body, other));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn collision_user_data_is_stored_in_contacts() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let owner = app.world_mut().spawn_empty().id();
+    let body1 = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            CollisionUserData::Value(7),
+        ))
+        .id();
+    let body2 = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::X * 0.5),
+            collider,
+            CollisionUserData::Entity(owner),
+        ))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let collisions = app.world().resource::<Collisions>();
+    let contacts = collisions.get(body1, body2).expect("bodies should collide");
+    let (data1, data2) = if contacts.entity1 == body1 {
+        (contacts.user_data1, contacts.user_data2)
+    } else {
+        (contacts.user_data2, contacts.user_data1)
+    };
+    assert_eq!(data1.and_then(|data| data.value()), Some(7));
+    assert_eq!(data2.and_then(|data| data.entity()), Some(owner));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn dump_physics_state_reports_contacts_and_joints() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body1 = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider.clone()))
+        .id();
+    let body2 = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::X * 0.5), collider))
+        .id();
+    let joint = app.world_mut().spawn(DistanceJoint::new(body1, body2)).id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let report = app.world().dump_physics_state(body1).unwrap();
+
+    assert_eq!(report.entity, body1);
+    assert!(report
+        .components
+        .iter()
+        .any(|(name, _)| *name == "LinearVelocity"));
+    assert_eq!(report.contacts.len(), 1);
+    assert_eq!(report.joints, vec![(joint, "DistanceJoint")]);
+    assert!(report.island.is_some());
+    assert!(!report.is_sleeping);
+    assert!(report.to_string().contains("DistanceJoint"));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn physics_capture_round_trips_frames() {
+    use crate::diagnostics::PhysicsCaptureWriter;
+
+    let path =
+        std::env::temp_dir().join(format!("avian_capture_test_{}.avcap", std::process::id()));
+
+    let mut app = create_app();
+    app.add_plugins(PhysicsCapturePlugin::new(&path));
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider.clone()))
+        .id();
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::NEG_Y * 0.9), collider));
+
+    for _ in 0..3 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // Removing the writer flushes the file.
+    app.world_mut().remove_resource::<PhysicsCaptureWriter>();
+
+    let capture = PhysicsCapture::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!capture.frames.is_empty());
+    for (index, frame) in capture.frames.iter().enumerate() {
+        assert_eq!(frame.step, index as u64);
+    }
+
+    let last_frame = capture.frames.last().unwrap();
+    let captured_body = last_frame
+        .bodies
+        .iter()
+        .find(|captured| captured.entity == body)
+        .unwrap();
+    let position = app.world().get::<Position>(body).unwrap();
+    assert_eq!(captured_body.position, position.0);
+    assert_eq!(last_frame.contacts.len(), 1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn cast_shape_all_returns_every_hit_in_order() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    // Spawn the colliders in reverse order so that they aren't already sorted by distance.
+    let targets: Vec<Entity> = (1..=4)
+        .rev()
+        .map(|i| {
+            app.world_mut()
+                .spawn((
+                    RigidBody::Static,
+                    Position(Vector::X * 3.0 * i as Scalar),
+                    collider.clone(),
+                ))
+                .id()
+        })
+        .collect();
+    // This one is behind the origin.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_X * 3.0),
+        collider.clone(),
+    ));
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let hits = pipeline.cast_shape_all(
+        &collider,
+        Vector::ZERO,
+        default(),
+        Dir::X,
+        &ShapeCastConfig::from_max_distance(100.0),
+        &SpatialQueryFilter::default(),
+    );
+
+    let hit_entities: Vec<Entity> = hits.iter().map(|hit| hit.entity).collect();
+    assert_eq!(
+        hit_entities,
+        targets.iter().rev().copied().collect::<Vec<_>>()
+    );
+    for (i, hit) in hits.iter().enumerate() {
+        assert_relative_eq!(hit.distance, 3.0 * (i + 1) as Scalar - 1.0, epsilon = 1e-3);
+    }
+
+    // The predicate can skip colliders.
+    let hits = pipeline.cast_shape_all_predicate(
+        &collider,
+        Vector::ZERO,
+        default(),
+        Dir::X,
+        &ShapeCastConfig::from_max_distance(100.0),
+        &SpatialQueryFilter::default(),
+        &|entity| entity != targets[3],
+    );
+    assert_eq!(hits.len(), 3);
+    assert_eq!(hits[0].entity, targets[2]);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn closest_point_and_distance_between_colliders() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(1.0);
+
+    let entity1 = app
+        .world_mut()
+        .spawn((RigidBody::Static, collider.clone()))
+        .id();
+    let entity2 = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::X * 5.0), collider))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+
+    // The closest point is on the surface even when the point is inside of a collider.
+    let projection = pipeline
+        .closest_point(Vector::X * 0.5, &SpatialQueryFilter::default())
+        .unwrap();
+    assert_eq!(projection.entity, entity1);
+    assert_relative_eq!(projection.point.x, 1.0, epsilon = 1e-4);
+
+    let result = pipeline.distance_between(entity1, entity2).unwrap();
+    assert_relative_eq!(result.distance, 3.0, epsilon = 1e-4);
+    assert_relative_eq!(result.point1.x, 1.0, epsilon = 1e-4);
+    assert_relative_eq!(result.point2.x, 4.0, epsilon = 1e-4);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn ray_hits_sorted_yields_hits_in_order() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    // Spawn the colliders out of order.
+    let entities: Vec<Entity> = [6.0, 2.0, 10.0, 4.0]
+        .into_iter()
+        .map(|x| {
+            app.world_mut()
+                .spawn((RigidBody::Static, Position(Vector::X * x), collider.clone()))
+                .id()
+        })
+        .collect();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let filter = SpatialQueryFilter::default().with_excluded_entities([entities[3]]);
+
+    let hits: Vec<RayHitData> = pipeline
+        .ray_hits_sorted(Vector::ZERO, Dir::X, 8.0, true, &filter)
+        .collect();
+
+    assert_eq!(
+        hits.iter().map(|hit| hit.entity).collect::<Vec<_>>(),
+        vec![entities[1], entities[0]]
+    );
+    assert_relative_eq!(hits[0].distance, 1.5, epsilon = 1e-4);
+    assert_relative_eq!(hits[1].distance, 5.5, epsilon = 1e-4);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn incremental_pipeline_updates_track_changes() {
+    let mut app = create_app();
+
+    app.insert_resource(SpatialQueryPipelineConfig {
+        incremental: true,
+        store_snapshot: true,
+        ..default()
+    });
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let moved = app
+        .world_mut()
+        .spawn((RigidBody::Static, collider.clone()))
+        .id();
+    let removed = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::X * 5.0), collider))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let snapshot = app.world().resource::<SpatialQuerySnapshot>().clone();
+    let filter = SpatialQueryFilter::default();
+    assert_eq!(
+        snapshot.point_intersections(Vector::ZERO, &filter),
+        vec![moved]
+    );
+
+    app.world_mut().get_mut::<Position>(moved).unwrap().0 = Vector::NEG_X * 5.0;
+    app.world_mut().despawn(removed);
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    assert!(pipeline
+        .point_intersections(Vector::ZERO, &filter)
+        .is_empty());
+    assert_eq!(
+        pipeline.point_intersections(Vector::NEG_X * 5.0, &filter),
+        vec![moved]
+    );
+    assert!(pipeline
+        .point_intersections(Vector::X * 5.0, &filter)
+        .is_empty());
+
+    // The old snapshot still contains the state of the first update.
+    assert_eq!(
+        snapshot.point_intersections(Vector::ZERO, &filter),
+        vec![moved]
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn k_nearest_returns_closest_colliders_in_order() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let entities: Vec<Entity> = [2.0, -4.0, 6.0, 20.0]
+        .into_iter()
+        .map(|x| {
+            app.world_mut()
+                .spawn((RigidBody::Static, Position(Vector::X * x), collider.clone()))
+                .id()
+        })
+        .collect();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let filter = SpatialQueryFilter::default();
+
+    let nearest = pipeline.k_nearest(Vector::ZERO, 2, Scalar::INFINITY, &filter);
+    assert_eq!(nearest.len(), 2);
+    assert_eq!(nearest[0].entity, entities[0]);
+    assert_relative_eq!(nearest[0].distance, 1.5, epsilon = 1e-4);
+    assert_eq!(nearest[1].entity, entities[1]);
+    assert_relative_eq!(nearest[1].distance, 3.5, epsilon = 1e-4);
+
+    // Colliders further than the maximum distance are ignored.
+    let nearest = pipeline.k_nearest(Vector::ZERO, 10, 10.0, &filter);
+    assert_eq!(
+        nearest.iter().map(|hit| hit.entity).collect::<Vec<_>>(),
+        entities[..3]
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn k_nearest_matches_brute_force_search() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    // Enough colliders for the tree to have several levels.
+    let positions: Vec<Vector> = (0..64)
+        .map(|i| {
+            Vector::X * ((i * 37 % 64) as Scalar * 1.5) + Vector::Y * ((i % 8) as Scalar * 2.0)
+        })
+        .collect();
+    for &position in &positions {
+        app.world_mut()
+            .spawn((RigidBody::Static, Position(position), collider.clone()));
+    }
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let point = Vector::X * 40.3 + Vector::Y * 3.1;
+    let mut expected: Vec<Scalar> = positions
+        .iter()
+        .map(|position| (position.distance(point) - 0.5).max(0.0))
+        .collect();
+    expected.sort_by(|a, b| a.total_cmp(b));
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let nearest = pipeline.k_nearest(point, 5, Scalar::INFINITY, &SpatialQueryFilter::default());
+
+    assert_eq!(nearest.len(), 5);
+    for (hit, expected) in nearest.iter().zip(expected) {
+        assert_relative_eq!(hit.distance, expected, epsilon = 1e-4);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn aabb_intersections_respects_filter() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let inside = app
+        .world_mut()
+        .spawn((RigidBody::Static, collider.clone()))
+        .id();
+    let excluded = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::X), collider.clone()))
+        .id();
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::X * 10.0), collider));
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let region = ColliderAabb::new(Vector::ZERO, Vector::splat(2.0));
+
+    let mut intersections = pipeline.aabb_intersections(region, &SpatialQueryFilter::default());
+    intersections.sort();
+    assert_eq!(intersections, vec![inside, excluded]);
+
+    let filter = SpatialQueryFilter::default().with_excluded_entities([excluded]);
+    assert_eq!(pipeline.aabb_intersections(region, &filter), vec![inside]);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spatial_query_predicates_skip_entities() {
+    use bevy::ecs::entity::EntityHashSet;
+
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let near = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X * 2.0),
+            collider.clone(),
+        ))
+        .id();
+    let far = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::X * 5.0), collider))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let filter = SpatialQueryFilter::default();
+
+    // The predicate borrows the set of ignored entities.
+    let ignored = EntityHashSet::from_iter([near]);
+    let predicate = |entity: Entity| !ignored.contains(&entity);
+
+    // The ray passes through the near collider, which is rejected by the predicate.
+    let hit = pipeline
+        .cast_ray_predicate(Vector::ZERO, Dir::X, 100.0, true, &filter, &predicate)
+        .unwrap();
+    assert_eq!(hit.entity, far);
+
+    // Rejected colliders don't count towards the `k` nearest colliders.
+    let nearest =
+        pipeline.k_nearest_predicate(Vector::ZERO, 1, Scalar::INFINITY, &filter, &predicate);
+    assert_eq!(nearest.len(), 1);
+    assert_eq!(nearest[0].entity, far);
+}
+
+#[test]
+fn rigid_body_limits_clamp_angular_velocity_and_inertia() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO)
+        .insert_resource(RigidBodyLimitsConfig {
+            max_angular_speed: 10.0,
+            min_inertia_radius: 0.5,
+        });
+
+    #[cfg(feature = "2d")]
+    let (inertia, angular_velocity) = (AngularInertia(1e-6), AngularVelocity(100.0));
+    #[cfg(feature = "3d")]
+    let (inertia, angular_velocity) = (
+        AngularInertia::new(Vector::splat(1e-6).f32()),
+        AngularVelocity(Vector::Z * 100.0),
+    );
+
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Mass(2.0), inertia, angular_velocity))
+        .id();
+    let overridden = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Mass(2.0),
+            inertia,
+            angular_velocity,
+            MaxAngularSpeed(50.0),
+            MinInertiaRadius(1.0),
+        ))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let world = app.world();
+
+    // The angular inertia is at least `mass * radius²`.
+    let inverse_inertia = |entity| {
+        let inertia = world.get::<ComputedAngularInertia>(entity).unwrap();
+        #[cfg(feature = "2d")]
+        return inertia.inverse();
+        #[cfg(feature = "3d")]
+        return inertia.inverse_tensor().z_axis.z;
+    };
+    assert_relative_eq!(inverse_inertia(body), 1.0 / (2.0 * 0.25), epsilon = 1e-4);
+    assert_relative_eq!(inverse_inertia(overridden), 1.0 / 2.0, epsilon = 1e-4);
+
+    let angular_speed = |entity| {
+        let angular_velocity = world.get::<AngularVelocity>(entity).unwrap();
+        #[cfg(feature = "2d")]
+        return angular_velocity.0.abs();
+        #[cfg(feature = "3d")]
+        return angular_velocity.length();
+    };
+    assert!(angular_speed(body) <= 10.0 + 1e-4);
+    assert!(angular_speed(overridden) > 10.0);
+    assert!(angular_speed(overridden) <= 50.0 + 1e-4);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn non_linear_swept_ccd_aabb_covers_fast_rotation() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let (collider, angular_velocity) = (Collider::rectangle(4.0, 0.1), AngularVelocity(PI * 60.0));
+    #[cfg(feature = "3d")]
+    let (collider, angular_velocity) = (
+        Collider::cuboid(4.0, 0.1, 0.1),
+        AngularVelocity(Vector::Z * PI * 60.0),
+    );
+
+    // The rod makes half a turn each step, so it is horizontal at the start and end of the step.
+    let linear = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            angular_velocity,
+            SweptCcd::LINEAR,
+        ))
+        .id();
+    let non_linear = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider,
+            angular_velocity,
+            SweptCcd::NON_LINEAR,
+        ))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let aabb_height = |entity| {
+        let aabb = app.world().get::<ColliderAabb>(entity).unwrap();
+        aabb.max.y - aabb.min.y
+    };
+
+    // Only the AABB of the non-linear body covers the vertical orientation in the middle of the step.
+    assert!(aabb_height(linear) < 1.0);
+    assert!(aabb_height(non_linear) > 3.9);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn sensor_zones_are_spawned_and_maintained() {
+    let mut app = create_app();
+    app.add_plugins(SensorZonesPlugin);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let owner = app
+        .world_mut()
+        .spawn((
+            RigidBody::Kinematic,
+            collider.clone(),
+            SensorZones::new([
+                SensorZone::hitbox(collider),
+                SensorZone::ground_probe(0.2, Vector::NEG_Y),
+            ]),
+        ))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let zone_entities = |app: &App| {
+        app.world()
+            .get::<SensorZoneEntities>(owner)
+            .map(|entities| entities.0.clone())
+    };
+    let entities = zone_entities(&app).unwrap();
+    assert_eq!(entities.len(), 2);
+    for (index, entity) in entities.iter().enumerate() {
+        let entity_ref = app.world().entity(*entity);
+        assert!(entity_ref.contains::<Sensor>());
+        assert_eq!(entity_ref.get::<Parent>().map(|p| p.get()), Some(owner));
+        assert_eq!(entity_ref.get::<SensorZoneOf>().unwrap().index, index);
+    }
+
+    // Despawned sensors are respawned.
+    app.world_mut().entity_mut(entities[1]).despawn_recursive();
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let respawned = zone_entities(&app).unwrap();
+    assert_eq!(respawned[0], entities[0]);
+    assert_ne!(respawned[1], entities[1]);
+    assert_eq!(
+        app.world().get::<SensorZoneOf>(respawned[1]).unwrap().kind,
+        SensorZoneKind::GroundProbe
+    );
+
+    // Removing the zones despawns the sensors.
+    app.world_mut().entity_mut(owner).remove::<SensorZones>();
+    tick_app(&mut app, 1.0 / 60.0);
+
+    assert!(zone_entities(&app).is_none());
+    assert!(respawned
+        .iter()
+        .all(|entity| app.world().get_entity(*entity).is_err()));
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn child_colliders_use_speculative_margin_of_body() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let mut spawn_body = |margin: Option<SpeculativeMargin>| {
+        let mut body = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, LinearVelocity(Vector::X * 120.0)));
+        if let Some(margin) = margin {
+            body.insert(margin);
+        }
+        let mut child = None;
+        body.with_children(|children| {
+            child = Some(
+                children
+                    .spawn((collider.clone(), Transform::default()))
+                    .id(),
+            );
+        });
+        child.unwrap()
+    };
+
+    let unbounded = spawn_body(None);
+    let bounded = spawn_body(Some(SpeculativeMargin::ZERO));
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let aabb_width = |entity| {
+        let aabb = app.world().get::<ColliderAabb>(entity).unwrap();
+        aabb.max.x - aabb.min.x
+    };
+
+    // The AABB of the collider is only expanded by its velocity if the body allows speculative contacts.
+    assert!(aabb_width(unbounded) > 2.0);
+    assert!(aabb_width(bounded) < 1.5);
+}
+
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn z_layers_filter_collisions_between_lanes() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let mut spawn = |x: Scalar, z_layer: Option<ZLayer>| {
+        let mut entity = app.world_mut().spawn((
+            RigidBody::Dynamic,
+            Collider::circle(0.5),
+            Position(Vector::X * x),
+        ));
+        if let Some(z_layer) = z_layer {
+            entity.insert(z_layer);
+        }
+        entity.id()
+    };
+
+    let lane0 = spawn(0.0, Some(ZLayer::new(0)));
+    let lane1 = spawn(0.25, Some(ZLayer::new(1)));
+    let band = spawn(0.5, Some(ZLayer::band(1, 2)));
+    let no_lane = spawn(-0.25, None);
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let collisions = app.world().resource::<Collisions>();
+    assert!(!collisions.contains(lane0, lane1));
+    assert!(!collisions.contains(lane0, band));
+    assert!(collisions.contains(lane1, band));
+    assert!(collisions.contains(no_lane, lane0));
+    assert!(collisions.contains(no_lane, lane1));
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn child_collider_aabbs_include_collision_margin_of_body() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let mut child = None;
+    app.world_mut()
+        .spawn((RigidBody::Dynamic, CollisionMargin(0.2)))
+        .with_children(|children| {
+            child = Some(children.spawn((collider, Transform::default())).id());
+        });
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let aabb = app.world().get::<ColliderAabb>(child.unwrap()).unwrap();
+    assert!(aabb.max.x - aabb.min.x > 1.35);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn contact_response_disabled_keeps_contacts_without_response() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let ghost = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            ContactResponseDisabled,
+        ))
+        .id();
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider, Position(Vector::X * 0.5)))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let contacts = app
+        .world()
+        .resource::<Collisions>()
+        .get(ghost, body)
+        .unwrap();
+    assert!(!contacts.is_sensor);
+    assert!(!contacts.manifolds.is_empty());
+    assert_eq!(contacts.total_normal_impulse, 0.0);
+
+    // The overlapping bodies are not pushed apart.
+    assert_eq!(app.world().get::<Position>(ghost).unwrap().0, Vector::ZERO);
+    assert_eq!(
+        app.world().get::<Position>(body).unwrap().0,
+        Vector::X * 0.5
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn max_linear_speed_is_enforced_after_solver() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    // Spawn fast kinematic bodies that hit dynamic bodies, so that the solver launches them.
+    let mut spawn_pair = |y: Scalar, max_speed: Option<MaxLinearSpeed>| {
+        app.world_mut().spawn((
+            RigidBody::Kinematic,
+            collider.clone(),
+            Position(Vector::Y * y - Vector::X),
+            LinearVelocity(Vector::X * 50.0),
+        ));
+        let mut body = app.world_mut().spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            Position(Vector::Y * y),
+        ));
+        if let Some(max_speed) = max_speed {
+            body.insert(max_speed);
+        }
+        body.id()
+    };
+
+    let unlimited = spawn_pair(0.0, None);
+    let limited = spawn_pair(10.0, Some(MaxLinearSpeed(1.0)));
+
+    for _ in 0..2 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let speed = |entity| app.world().get::<LinearVelocity>(entity).unwrap().length();
+    assert!(speed(unlimited) > 1.0);
+    assert!(speed(limited) <= 1.0 + 1e-4);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn max_overlap_solve_speed_limits_depenetration() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    // Spawn dynamic bodies deeply overlapping static bodies.
+    let mut spawn_pair = |y: Scalar, max_speed: Option<MaxOverlapSolveSpeed>| {
+        app.world_mut().spawn((
+            RigidBody::Static,
+            collider.clone(),
+            Position(Vector::Y * y - Vector::X * 0.5),
+        ));
+        let mut body = app.world_mut().spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            Position(Vector::Y * y),
+        ));
+        if let Some(max_speed) = max_speed {
+            body.insert(max_speed);
+        }
+        body.id()
+    };
+
+    let unlimited = spawn_pair(0.0, None);
+    let limited = spawn_pair(10.0, Some(MaxOverlapSolveSpeed(0.1)));
+
+    let delta_secs = 1.0 / 60.0;
+    for _ in 0..2 {
+        tick_app(&mut app, delta_secs);
+    }
+
+    let displacement = |entity| app.world().get::<Position>(entity).unwrap().x;
+    assert!(displacement(unlimited) > 0.05);
+    assert!(displacement(limited) <= 2.0 * 0.1 * delta_secs + 1e-4);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn ramped_external_force_is_evaluated_per_substep() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let force = Vector::X * 10.0;
+
+    // A force ramping from zero to twice the constant force should
+    // have the same effect as the constant force over one step.
+    let ramped = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            ExternalForce::ramp(Vector::ZERO, force * 2.0),
+        ))
+        .id();
+    let constant = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider,
+            Position(Vector::Y * 10.0),
+            ExternalForce::new(force),
+        ))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let velocity = |entity| app.world().get::<LinearVelocity>(entity).unwrap().0;
+    assert!(velocity(constant).x > 0.0);
+    assert!((velocity(ramped) - velocity(constant)).length() < 1e-4);
+
+    // The persistent force stays at the end of the ramp.
+    let ramped_force = app.world().get::<ExternalForce>(ramped).unwrap();
+    assert_eq!(ramped_force.force(), force * 2.0);
+    assert_eq!(ramped_force.ramp_end(), None);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn gravity_fields_replace_global_gravity() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let (ball, volume) = (Collider::circle(0.5), Collider::rectangle(10.0, 10.0));
+    #[cfg(feature = "3d")]
+    let (ball, volume) = (Collider::sphere(0.5), Collider::cuboid(10.0, 10.0, 10.0));
+
+    // A volume where gravity points up, and a volume that pulls bodies towards its center.
+    app.world_mut()
+        .spawn((volume.clone(), Sensor, GravityField::directional(Vector::Y)));
+    let planet = Vector::X * 100.0;
+    app.world_mut()
+        .spawn((volume, Sensor, Position(planet), GravityField::point(5.0)));
+
+    let flipped = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, ball.clone()))
+        .id();
+    let orbiting = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            ball.clone(),
+            Position(planet + Vector::X * 3.0),
+        ))
+        .id();
+    let outside = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, ball, Position(Vector::X * 50.0)))
+        .id();
+
+    for _ in 0..5 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let velocity = |entity| app.world().get::<LinearVelocity>(entity).unwrap().0;
+    assert!(velocity(flipped).y > 0.0);
+    assert!(velocity(orbiting).x < 0.0);
+    assert!(velocity(orbiting).y.abs() < 1e-4);
+    assert!(velocity(outside).y < 0.0);
+
+    let active_fields = app.world().get::<ActiveGravityFields>(flipped).unwrap();
+    assert_eq!(active_fields.fields().len(), 1);
+    assert!(app.world().get::<ActiveGravityFields>(outside).is_none());
+}
+
+#[test]
+fn gravity_override_replaces_global_gravity() {
+    let mut app = create_app();
+
+    let sideways = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::from_shape(&Circle::new(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::from_shape(&Sphere::new(0.5), 1.0),
+            GravityOverride(Vector::X * 5.0),
+            GravityScale(2.0),
+        ))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let velocity = app.world().get::<LinearVelocity>(sideways).unwrap().0;
+    assert_relative_eq!(velocity.x, 10.0 / 60.0, epsilon = 1e-4);
+    assert_relative_eq!(velocity.y, 0.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn blocker_volumes_only_block_matching_layers() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let (ball, wall) = (Collider::circle(0.5), Collider::rectangle(20.0, 1.0));
+    #[cfg(feature = "3d")]
+    let (ball, wall) = (Collider::sphere(0.5), Collider::cuboid(20.0, 1.0, 20.0));
+
+    let blocker = app
+        .world_mut()
+        .spawn((RigidBody::Static, wall, BlockerVolume::new(0b10)))
+        .id();
+
+    let blocked = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            ball.clone(),
+            Position(Vector::Y * 2.0),
+            CollisionLayers::new(0b10, LayerMask::ALL),
+        ))
+        .id();
+    let ignored = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            ball,
+            Position(Vector::X * 5.0 + Vector::Y * 2.0),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let y = |entity| app.world().get::<Position>(entity).unwrap().y;
+    assert!(y(blocked) > 0.5);
+    assert!(y(ignored) < -2.0);
+
+    let collisions = app.world().resource::<Collisions>();
+    assert!(collisions.get(blocker, blocked).is_some());
+    assert!(collisions.get(blocker, ignored).is_none());
+}
+
+#[test]
+fn aerodynamic_drag_slows_bodies_relative_to_wind() {
+    let mut app = create_app();
+    app.add_plugins(AerodynamicsPlugin::default())
+        .insert_resource(Gravity::ZERO)
+        .insert_resource(Wind(Vector::X * 5.0));
+
+    let mut spawn_body = |velocity: Vector, drag: Option<Drag>| {
+        let mut body = app.world_mut().spawn((
+            RigidBody::Dynamic,
+            LinearVelocity(velocity),
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::from_shape(&Circle::new(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::from_shape(&Sphere::new(0.5), 1.0),
+        ));
+        if let Some(drag) = drag {
+            body.insert(drag);
+        }
+        body.id()
+    };
+
+    let moving = spawn_body(Vector::Y * 10.0, Some(Drag::new(1.0, 1.0)));
+    let resting = spawn_body(Vector::ZERO, Some(Drag::new(1.0, 1.0)));
+    let no_drag = spawn_body(Vector::Y * 10.0, None);
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let velocity = |entity| app.world().get::<LinearVelocity>(entity).unwrap().0;
+
+    // Drag slows the body down and pushes it along with the wind.
+    assert!(velocity(moving).y < 10.0 && velocity(moving).y > 0.0);
+    assert!(velocity(moving).x > 0.0);
+
+    // The wind accelerates the resting body, but never beyond the wind speed.
+    assert!(velocity(resting).x > 0.0 && velocity(resting).x < 5.0);
+
+    assert_eq!(velocity(no_drag), Vector::Y * 10.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn drag_reference_area_depends_on_collider_orientation() {
+    use crate::dynamics::aerodynamics::projected_area;
+
+    #[cfg(feature = "2d")]
+    let (plate, ball) = (Collider::rectangle(2.0, 0.5), Collider::circle(0.5));
+    #[cfg(feature = "3d")]
+    let (plate, ball) = (Collider::cuboid(2.0, 0.5, 1.0), Collider::sphere(0.5));
+
+    let identity = Rotation::default();
+    #[cfg(feature = "2d")]
+    let rotated = Rotation::degrees(90.0);
+    #[cfg(feature = "3d")]
+    let rotated = Rotation(Quaternion::from_rotation_z(FRAC_PI_2));
+
+    // A flat plate falling broadside has a larger area than a plate falling edge-on.
+    let broadside = projected_area(&plate, &identity, Vector::NEG_Y);
+    let edge_on = projected_area(&plate, &rotated, Vector::NEG_Y);
+    assert_relative_eq!(broadside, 2.0, epsilon = 1e-4);
+    assert_relative_eq!(edge_on, 0.5, epsilon = 1e-4);
+
+    // The area of a ball is the same in every direction.
+    #[cfg(feature = "2d")]
+    let ball_area = 1.0;
+    #[cfg(feature = "3d")]
+    let ball_area = PI * 0.25;
+    assert_relative_eq!(
+        projected_area(&ball, &rotated, Vector::X),
+        ball_area,
+        epsilon = 1e-4
+    );
+    assert_relative_eq!(
+        projected_area(&ball, &identity, Vector::ONE),
+        ball_area,
+        epsilon = 1e-4
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn radial_impulse_pushes_nearby_bodies_away() {
+    use bevy::ecs::world::Command;
+
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let near = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            Position(Vector::X * 2.0),
+            Sleeping,
+        ))
+        .id();
+    let far = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider, Position(Vector::NEG_X * 20.0)))
+        .id();
+
+    // Update the spatial query pipeline.
+    tick_app(&mut app, 1.0 / 60.0);
+
+    RadialImpulse {
+        center: Vector::ZERO,
+        radius: 5.0,
+        strength: 10.0,
+        falloff: ImpulseFalloff::Linear,
+        filter: SpatialQueryFilter::default(),
+    }
+    .apply(app.world_mut());
+
+    assert!(!app.world().entity(near).contains::<Sleeping>());
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let velocity = |entity| app.world().get::<LinearVelocity>(entity).unwrap().0;
+    assert!(velocity(near).x > 0.0);
+    assert_relative_eq!(velocity(near).y, 0.0, epsilon = 1e-4);
+    assert_eq!(velocity(far), Vector::ZERO);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn ray_hits_report_compound_sub_shapes() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let part = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let part = Collider::sphere(0.5);
+
+    let entity = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            Collider::compound(vec![
+                (Position(Vector::NEG_X), Rotation::default(), part.clone()),
+                (Position(Vector::X), Rotation::default(), part),
+            ])
+            .with_sub_shape_tags([10, 20]),
+        ))
+        .id();
+
+    // Update the spatial query pipeline.
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let filter = SpatialQueryFilter::default();
+
+    let hit = pipeline
+        .cast_ray(Vector::X * 5.0, Dir::NEG_X, 10.0, true, &filter)
+        .unwrap();
+    assert_eq!(hit.entity, entity);
+    assert_eq!(
+        hit.sub_shape,
+        Some(SubShapeHit {
+            index: 1,
+            tag: Some(20)
+        })
+    );
+
+    let hit = pipeline
+        .cast_ray(Vector::NEG_X * 5.0, Dir::X, 10.0, true, &filter)
+        .unwrap();
+    assert_eq!(
+        hit.sub_shape,
+        Some(SubShapeHit {
+            index: 0,
+            tag: Some(10)
+        })
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn ray_hits_report_the_overlapping_sub_shape_that_was_hit() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let part = Collider::circle(1.0);
+    #[cfg(feature = "3d")]
+    let part = Collider::sphere(1.0);
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::compound(vec![
+            (
+                Position(Vector::NEG_X * 0.5),
+                Rotation::default(),
+                part.clone(),
+            ),
+            (Position(Vector::X * 0.5), Rotation::default(), part),
+        ]),
+    ));
+
+    // Update the spatial query pipeline.
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let filter = SpatialQueryFilter::default();
+
+    // A hollow ray from the center first exits the right part. The hit point is also
+    // inside the left part, so both parts are equally close to it.
+    let hit = pipeline
+        .cast_ray(Vector::ZERO, Dir::NEG_X, 10.0, false, &filter)
+        .unwrap();
+    assert_eq!(hit.sub_shape.map(|sub_shape| sub_shape.index), Some(1));
+
+    let hits = pipeline.ray_hits(Vector::ZERO, Dir::NEG_X, 10.0, 1, false, &filter);
+    assert_eq!(hits[0].sub_shape.map(|sub_shape| sub_shape.index), Some(1));
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn local_external_force_follows_body_rotation() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+    #[cfg(feature = "2d")]
+    let rotation = Rotation::degrees(90.0);
+    #[cfg(feature = "3d")]
+    let rotation = Rotation(Quaternion::from_rotation_z(FRAC_PI_2));
+
+    // The local X axis of the body points along the world Y axis.
+    let entity = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            collider,
+            rotation,
+            ExternalForce::new_local(Vector::X * 10.0),
+        ))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let velocity = app.world().get::<LinearVelocity>(entity).unwrap().0;
+    assert!(velocity.y > 0.0);
+    assert_relative_eq!(velocity.x, 0.0, epsilon = 1e-4);
+
+    // The stored force stays in local space.
+    let force = app.world().get::<ExternalForce>(entity).unwrap();
+    assert!(force.is_local());
+    assert_eq!(force.force(), Vector::X * 10.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn internal_edge_fixing_can_be_toggled_per_collider() {
+    // A flat quad made of two triangles.
+    #[cfg(feature = "2d")]
+    let vertices = vec![
+        Vector::new(-1.0, -1.0),
+        Vector::new(1.0, -1.0),
+        Vector::new(1.0, 1.0),
+        Vector::new(-1.0, 1.0),
+    ];
+    #[cfg(feature = "3d")]
+    let vertices = vec![
+        Vector::new(-1.0, 0.0, -1.0),
+        Vector::new(1.0, 0.0, -1.0),
+        Vector::new(1.0, 0.0, 1.0),
+        Vector::new(-1.0, 0.0, 1.0),
+    ];
+    let indices = vec![[0, 2, 1], [0, 3, 2]];
+
+    let mut floor = Collider::trimesh(vertices, indices).with_internal_edge_fixing(true);
+    assert!(floor.fixes_internal_edges());
+
+    floor.set_internal_edge_fixing(false);
+    assert!(!floor.fixes_internal_edges());
+
+    // Other shapes are not affected.
+    #[cfg(feature = "2d")]
+    let ball = Collider::circle(0.5).with_internal_edge_fixing(true);
+    #[cfg(feature = "3d")]
+    let ball = Collider::sphere(0.5).with_internal_edge_fixing(true);
+    assert!(!ball.fixes_internal_edges());
+}
+
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+#[test]
+fn vehicle_rests_on_suspension_and_drives_forward() {
+    let mut app = create_app();
+    app.add_plugins(VehiclePlugin::default());
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::cuboid(100.0, 1.0, 100.0),
+        Position(Vector::NEG_Y * 0.5),
+    ));
+
+    // A 1000 kg chassis with steered front wheels and driven rear wheels.
+    let vehicle = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(2.0, 0.5, 4.0),
+            ColliderDensity(250.0),
+            Position(Vector::Y),
+            Vehicle::new(vec![
+                Wheel::new(Vector::new(-1.0, -0.25, -1.5), 0.4).with_steering(),
+                Wheel::new(Vector::new(1.0, -0.25, -1.5), 0.4).with_steering(),
+                Wheel::new(Vector::new(-1.0, -0.25, 1.5), 0.4).with_drive(),
+                Wheel::new(Vector::new(1.0, -0.25, 1.5), 0.4).with_drive(),
+            ]),
+        ))
+        .id();
+
+    // Let the vehicle settle on its suspension.
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let vehicle_ref = app.world().get::<Vehicle>(vehicle).unwrap();
+    assert!(vehicle_ref
+        .wheels
+        .iter()
+        .all(|wheel| wheel.contact.is_some()));
+    let position = app.world().get::<Position>(vehicle).unwrap().0;
+    assert!(position.y > 0.7 && position.y < 1.2);
+
+    app.world_mut()
+        .get_mut::<Vehicle>(vehicle)
+        .unwrap()
+        .throttle = 1.0;
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The vehicle drives forward along its local -Z axis without tipping over.
+    let velocity = app.world().get::<LinearVelocity>(vehicle).unwrap().0;
+    assert!(velocity.z < -1.0);
+    assert!(velocity.x.abs() < 0.1);
+    let rotation = app.world().get::<Rotation>(vehicle).unwrap();
+    assert!((*rotation * Vector::Y).y > 0.9);
+}
+
+#[cfg(all(feature = "2d", any(feature = "parry-f32", feature = "parry-f64")))]
+#[test]
+fn smooth_polyline_normals_remove_bumps_at_segment_joins() {
+    use contact_query::contact_manifolds;
+
+    // Flat ground made of segments with joins at integer x coordinates.
+    let vertices: Vec<Vector> = (-5..=5).map(|x| Vector::new(x as Scalar, 0.0)).collect();
+    let ground = Collider::polyline(vertices.clone(), None);
+    let smooth_ground = Collider::polyline_with_config(vertices, None, true);
+    assert!(smooth_ground.fixes_internal_edges());
+
+    // A circle slightly penetrating the ground next to the join at the origin.
+    let ball = Collider::circle(0.5);
+    let ball_position = Vector::new(0.1, 0.45);
+
+    let manifolds = contact_manifolds(&ground, Vector::ZERO, 0.0, &ball, ball_position, 0.0, 0.0);
+    assert!(manifolds
+        .iter()
+        .any(|manifold| manifold.normal1.dot(Vector::Y) < 0.999));
+
+    let manifolds = contact_manifolds(
+        &smooth_ground,
+        Vector::ZERO,
+        0.0,
+        &ball,
+        ball_position,
+        0.0,
+        0.0,
+    );
+    assert!(!manifolds.is_empty());
+    for manifold in manifolds {
+        assert_relative_eq!(manifold.normal1, Vector::Y, epsilon = 1e-4);
+        assert_relative_eq!(manifold.normal2, Vector::NEG_Y, epsilon = 1e-4);
+    }
+
+    // The polyline can also be the second collider.
+    let manifolds = contact_manifolds(
+        &ball,
+        ball_position,
+        0.0,
+        &smooth_ground,
+        Vector::ZERO,
+        0.0,
+        0.0,
+    );
+    assert!(!manifolds.is_empty());
+    for manifold in manifolds {
+        assert_relative_eq!(manifold.normal1, Vector::NEG_Y, epsilon = 1e-4);
+    }
+}
+
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+#[test]
+fn rotating_platforms_carry_bodies_tangentially() {
+    let mut app = create_app();
+
+    let mut spawn_platform = |x: Scalar, transfer: PlatformVelocityTransfer| {
+        app.world_mut().spawn((
+            RigidBody::Kinematic,
+            Collider::cuboid(6.0, 1.0, 6.0),
+            Position(Vector::X * x),
+            AngularVelocity(Vector::Y),
+            transfer,
+        ));
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Collider::cuboid(0.5, 0.5, 0.5),
+                Position(Vector::new(x + 2.0, 0.75, 0.0)),
+            ))
+            .id()
+    };
+
+    let carried = spawn_platform(0.0, PlatformVelocityTransfer::FULL);
+    let not_carried = spawn_platform(20.0, PlatformVelocityTransfer::NONE);
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let velocity = |entity| app.world().get::<LinearVelocity>(entity).unwrap().0;
+
+    // The platform rotates around the Y axis, so the tangential velocity at +X points along -Z.
+    assert!(velocity(carried).z < -1.0);
+    assert!(velocity(not_carried).z.abs() < 0.1);
+}
+
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+#[test]
+fn ragdoll_follows_animation_and_then_simulates() {
+    use bevy::ecs::world::Command;
+
+    let mut app = create_app();
+    app.add_plugins(RagdollPlugin::default());
+
+    // A chain of bones hanging down from the root, ending in a leaf bone.
+    let world = app.world_mut();
+    let root = world.spawn(Transform::from_xyz(0.0, 3.0, 0.0)).id();
+    let upper = world
+        .spawn(Transform::from_xyz(0.0, -1.0, 0.0))
+        .set_parent(root)
+        .id();
+    let lower = world
+        .spawn((Name::new("Knee"), Transform::from_xyz(0.0, -1.0, 0.0)))
+        .set_parent(upper)
+        .id();
+    world
+        .spawn(Transform::from_xyz(0.0, -1.0, 0.0))
+        .set_parent(lower);
+
+    RagdollBuilder::new(root)
+        .with_joint("Knee", RagdollJoint::revolute(Vector::X, -2.0, 0.0))
+        .apply(app.world_mut());
+
+    let bodies = app.world().get::<Ragdoll>(root).unwrap().bodies().to_vec();
+    assert_eq!(bodies.len(), 3);
+    assert!(bodies
+        .iter()
+        .all(|&body| app.world().get::<RigidBody>(body) == Some(&RigidBody::Kinematic)));
+
+    // Move the animated root, and check that the body follows it.
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    app.world_mut()
+        .get_mut::<Transform>(root)
+        .unwrap()
+        .translation
+        .x = 1.0;
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    let position = app.world().get::<Position>(bodies[0]).unwrap().0;
+    assert!((position - Vector::new(1.0, 3.0, 0.0)).length() < 0.05);
+
+    // Simulate the ragdoll, making it fall and move the bones with it.
+    app.world_mut().get_mut::<Ragdoll>(root).unwrap().mode = RagdollMode::Simulated;
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert!(bodies
+        .iter()
+        .all(|&body| app.world().get::<RigidBody>(body) == Some(&RigidBody::Dynamic)));
+    let root_translation = app.world().get::<Transform>(root).unwrap().translation;
+    assert!(root_translation.y < 2.5);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn mirror_constraint_mirrors_motion_across_plane() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    // A kinematic body moving towards the mirror plane at `x = 5` while spinning.
+    let body1 = app
+        .world_mut()
+        .spawn((
+            RigidBody::Kinematic,
+            collider.clone(),
+            LinearVelocity(Vector::X),
+            #[cfg(feature = "2d")]
+            AngularVelocity(1.0),
+            #[cfg(feature = "3d")]
+            AngularVelocity(Vector::Y),
+        ))
+        .id();
+    let body2 = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider, Position(Vector::X * 10.0)))
+        .id();
+    app.world_mut()
+        .spawn(MirrorConstraint::new(body1, body2).with_plane(Vector::X * 5.0, Vector::X));
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The second body moves towards the plane from the other side, despite gravity.
+    let position1 = app.world().get::<Position>(body1).unwrap().0;
+    let position2 = app.world().get::<Position>(body2).unwrap().0;
+    assert!(position1.x > 0.5);
+    let mut expected = position1;
+    expected.x = 10.0 - position1.x;
+    assert!((position2 - expected).length() < 0.05);
+
+    // The second body spins in the opposite direction.
+    let rotation1 = *app.world().get::<Rotation>(body1).unwrap();
+    let rotation2 = *app.world().get::<Rotation>(body2).unwrap();
+    #[cfg(feature = "2d")]
+    assert!((rotation1.as_radians() + rotation2.as_radians()).abs() < 0.05);
+    #[cfg(feature = "3d")]
+    assert!(rotation2.0.angle_between(rotation1.0.inverse()) < 0.05);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn cloth_collides_and_follows_attachments() {
+    let mut app = create_app();
+    app.add_plugins(ClothPlugin);
+
+    // Ground with its top surface at `y = 0`.
+    #[cfg(feature = "2d")]
+    let ground_collider = Collider::rectangle(20.0, 1.0);
+    #[cfg(feature = "3d")]
+    let ground_collider = Collider::cuboid(20.0, 1.0, 20.0);
+    app.world_mut().spawn((
+        RigidBody::Static,
+        ground_collider,
+        Position(Vector::NEG_Y * 0.5),
+    ));
+
+    #[cfg(feature = "2d")]
+    let height = Vector::Y * 0.5;
+    #[cfg(feature = "3d")]
+    let height = Vector::Z * 2.0;
+
+    // A cloth falling onto the ground.
+    let falling = app
+        .world_mut()
+        .spawn(Cloth::grid(Vector::Y, Vector::X * 2.0, height, 5, 5))
+        .id();
+
+    // A cloth hanging from a kinematic body moving sideways, far above the ground.
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Kinematic,
+            Position(Vector::Y * 10.0),
+            LinearVelocity(Vector::X),
+        ))
+        .id();
+    let mut hanging = Cloth::grid(Vector::Y * 10.0, Vector::X * 2.0, height, 5, 5);
+    hanging.attach(0, body, Vector::ZERO);
+    let hanging = app.world_mut().spawn(hanging).id();
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The falling cloth rests on the ground.
+    let cloth = app.world().get::<Cloth>(falling).unwrap();
+    for position in cloth.positions() {
+        assert!(position.y > -0.01);
+        assert!(position.y < height.y + 0.1);
+    }
+
+    // The attached particle follows the body.
+    let body_position = app.world().get::<Position>(body).unwrap().0;
+    assert!(body_position.x > 1.5);
+    let cloth = app.world().get::<Cloth>(hanging).unwrap();
+    assert!((cloth.positions()[0] - body_position).length() < 0.01);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn impact_events_are_limited_per_material_pair() {
+    let mut app = create_app();
+    app.add_plugins(ImpactEventsPlugin)
+        .insert_resource(ImpactEventConfig {
+            max_events_per_material_pair: 2,
+            ..default()
+        });
+
+    #[cfg(feature = "2d")]
+    let (ground_collider, box_collider) = (
+        Collider::rectangle(40.0, 1.0),
+        Collider::rectangle(1.0, 1.0),
+    );
+    #[cfg(feature = "3d")]
+    let (ground_collider, box_collider) = (
+        Collider::cuboid(40.0, 1.0, 40.0),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    );
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        ground_collider,
+        Position(Vector::NEG_Y * 0.5),
+    ));
+
+    // Identical boxes that all hit the ground during the same physics step.
+    for i in 0..8 {
+        app.world_mut().spawn((
+            RigidBody::Dynamic,
+            box_collider.clone(),
+            Position(Vector::X * (i as Scalar * 2.0 - 8.0) + Vector::Y),
+        ));
+    }
+
+    let mut cursor = app.world().resource::<Events<ImpactEvent>>().get_cursor();
+    let mut impacts = vec![];
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+        impacts.extend(
+            cursor
+                .read(app.world().resource::<Events<ImpactEvent>>())
+                .copied(),
+        );
+    }
+
+    // Only the two strongest impacts of the default material pair are reported,
+    // and resting contacts don't produce new impacts.
+    assert_eq!(impacts.len(), 2);
+    for impact in impacts {
+        assert!(impact.impulse > 0.0);
+        assert_eq!(impact.materials, [ImpactMaterial::DEFAULT; 2]);
+    }
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn rope_hangs_from_attachment_and_collides() {
+    let mut app = create_app();
+    app.add_plugins(RopePlugin);
+
+    // A static anchor body, and a ball below it that the rope should drape over.
+    let anchor = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::Y * 5.0)))
+        .id();
+    #[cfg(feature = "2d")]
+    let ball_collider = Collider::circle(1.0);
+    #[cfg(feature = "3d")]
+    let ball_collider = Collider::sphere(1.0);
+    let ball_position = Vector::X * 1.5 + Vector::Y * 2.0;
+    app.world_mut()
+        .spawn((RigidBody::Static, ball_collider, Position(ball_position)));
+
+    // A horizontal rope attached at one end, so that it swings down and hits the ball.
+    let mut rope = Rope::new(Vector::Y * 5.0, Vector::X * 4.0 + Vector::Y * 5.0, 40);
+    rope.attach_start(anchor, Vector::ZERO);
+    let rope = app.world_mut().spawn(rope).id();
+
+    for _ in 0..180 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let rope = app.world().get::<Rope>(rope).unwrap();
+
+    // The attached end stays at the anchor, and the rope barely stretches.
+    assert!((rope.positions()[0] - Vector::Y * 5.0).length() < 0.001);
+    assert!(rope.current_length() < rope.rest_length() * 1.05);
+
+    // No particle is inside the ball.
+    for position in rope.positions() {
+        assert!(position.distance(ball_position) > 1.0 + rope.radius - 0.02);
+    }
+}
+
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn soft_body_keeps_area_and_rests_on_ground() {
+    let mut app = create_app();
+    app.add_plugins(SoftBodyPlugin);
+
+    // Ground with its top surface at `y = 0`.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::rectangle(20.0, 1.0),
+        Position(Vector::NEG_Y * 0.5),
+    ));
+
+    let soft_body = app
+        .world_mut()
+        .spawn(SoftBody::circle(Vector::Y * 2.0, 1.0, 24))
+        .id();
+
+    for _ in 0..180 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let soft_body = app.world().get::<SoftBody>(soft_body).unwrap();
+
+    // The soft body has landed on the ground without losing much of its area.
+    assert!(soft_body.center().y < 1.5);
+    assert!(soft_body.center().y > 0.5);
+    assert!((soft_body.area() - soft_body.rest_area()).abs() < soft_body.rest_area() * 0.05);
+    for position in soft_body.positions() {
+        assert!(position.y > -0.01);
+    }
+}
+
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn fluid_floats_light_bodies_and_sinks_heavy_bodies() {
+    let mut app = create_app();
+    app.add_plugins(FluidPlugin);
+
+    // A container with its floor at `y = 0` and walls at `x = -1.2` and `x = 1.2`.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::compound(vec![
+            (
+                Position::from_xy(0.0, -0.5),
+                Rotation::default(),
+                Collider::rectangle(4.0, 1.0),
+            ),
+            (
+                Position::from_xy(-1.7, 2.0),
+                Rotation::default(),
+                Collider::rectangle(1.0, 6.0),
+            ),
+            (
+                Position::from_xy(1.7, 2.0),
+                Rotation::default(),
+                Collider::rectangle(1.0, 6.0),
+            ),
+        ]),
+    ));
+
+    let fluid = app
+        .world_mut()
+        .spawn(Fluid::block(
+            Vector::new(-1.2, 0.0),
+            Vector::new(1.2, 1.0),
+            0.05,
+        ))
+        .id();
+
+    let light_box = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::rectangle(0.4, 0.4),
+            ColliderDensity(200.0),
+            Position::from_xy(-0.5, 1.5),
+        ))
+        .id();
+    let heavy_box = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::rectangle(0.4, 0.4),
+            ColliderDensity(10000.0),
+            Position::from_xy(0.5, 1.5),
+        ))
+        .id();
+
+    for _ in 0..240 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The light box floats on the surface, and the heavy box sinks to the bottom.
+    let light_y = app.world().get::<Position>(light_box).unwrap().y;
+    let heavy_y = app.world().get::<Position>(heavy_box).unwrap().y;
+    assert!(light_y > 0.7);
+    assert!(heavy_y < 0.5);
+
+    // The particles stay inside the container.
+    let fluid = app.world().get::<Fluid>(fluid).unwrap();
+    for position in fluid.positions() {
+        assert!(position.y > -0.01);
+        assert!(position.x.abs() < 1.21);
+    }
+}
+
+#[test]
+fn debris_manager_evicts_excess_and_expired_debris() {
+    let mut app = create_app();
+    app.add_plugins(DebrisPlugin)
+        .insert_resource(DebrisManager::new(3).with_lifetime(0.5));
+
+    let first = app
+        .world_mut()
+        .spawn((Debris::default(), RigidBody::Dynamic, Position::default()))
+        .id();
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // Spawn more debris than the budget allows.
+    let later: Vec<Entity> = (0..4)
+        .map(|_| {
+            app.world_mut()
+                .spawn((Debris::default(), RigidBody::Dynamic, Position::default()))
+                .id()
+        })
+        .collect();
+
+    for _ in 0..5 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The oldest body and one of the new ones are evicted to stay within the budget.
+    assert!(app.world().get_entity(first).is_err());
+    let alive = later
+        .iter()
+        .filter(|&&entity| app.world().get_entity(entity).is_ok())
+        .count();
+    assert_eq!(alive, 3);
+    assert_eq!(app.world().resource::<DebrisManager>().live_bodies(), 3);
+
+    // The remaining debris expires after its lifetime.
+    for _ in 0..40 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert!(later
+        .iter()
+        .all(|&entity| app.world().get_entity(entity).is_err()));
+}
+
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn fluid_pushes_dynamic_bodies_in_3d() {
+    let mut app = create_app();
+    app.add_plugins(FluidPlugin);
+
+    // A container with its floor at `y = 0` and walls at 0.5 units from the origin.
+    let wall = |x: Scalar, z: Scalar, collider: Collider| {
+        (Position::from_xyz(x, 1.0, z), Rotation::default(), collider)
+    };
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::compound(vec![
+            (
+                Position::from_xyz(0.0, -0.5, 0.0),
+                Rotation::default(),
+                Collider::cuboid(2.0, 1.0, 2.0),
+            ),
+            wall(-1.0, 0.0, Collider::cuboid(1.0, 2.0, 2.0)),
+            wall(1.0, 0.0, Collider::cuboid(1.0, 2.0, 2.0)),
+            wall(0.0, -1.0, Collider::cuboid(2.0, 2.0, 1.0)),
+            wall(0.0, 1.0, Collider::cuboid(2.0, 2.0, 1.0)),
+        ]),
+    ));
+
+    let fluid = app
+        .world_mut()
+        .spawn(Fluid::block(
+            Vector::new(-0.5, 0.0, -0.5),
+            Vector::new(0.5, 0.4, 0.5),
+            0.05,
+        ))
+        .id();
+
+    // A light box dropped into the water.
+    let light_box = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(0.3, 0.3, 0.3),
+            ColliderDensity(200.0),
+            Position::from_xyz(0.0, 1.0, 0.0),
+        ))
+        .id();
+
+    for _ in 0..240 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The box is held up by the water instead of resting on the floor.
+    assert!(app.world().get::<Position>(light_box).unwrap().y > 0.3);
+
+    // The particles stay inside the container.
+    let fluid = app.world().get::<Fluid>(fluid).unwrap();
+    for position in fluid.positions() {
+        assert!(position.y > -0.01);
+        assert!(position.x.abs() < 0.51 && position.z.abs() < 0.51);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn query_only_colliders_are_hit_by_queries_but_not_bodies() {
+    let mut app = create_app();
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let trigger_volume = app
+        .world_mut()
+        .spawn((RigidBody::Static, collider.clone(), QueryOnly))
+        .id();
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 0.5), collider))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    // The body overlaps the query-only collider, but no contacts are created.
+    let collisions = app.world().resource::<Collisions>();
+    assert!(!collisions.contains(trigger_volume, body));
+
+    // Spatial queries still see the query-only collider.
+    let pipeline = app.world().resource::<SpatialQueryPipeline>();
+    let filter = SpatialQueryFilter::default().with_excluded_entities([body]);
+    let hit = pipeline
+        .cast_ray(Vector::X * 5.0, Dir::NEG_X, 10.0, true, &filter)
+        .unwrap();
+    assert_eq!(hit.entity, trigger_volume);
+}
+
+#[test]
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn universal_joint_motor_drives_second_body() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    let body1 = app
+        .world_mut()
+        .spawn((RigidBody::Static, Collider::sphere(0.5)))
+        .id();
+    let body2 = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(1.0, 0.2, 0.2),
+            Position(Vector::X * 2.0),
+        ))
+        .id();
+    app.world_mut().spawn(
+        UniversalJoint::new(body1, body2)
+            .with_local_anchor_1(Vector::X)
+            .with_local_anchor_2(Vector::NEG_X)
+            .with_axis1_motor(AngularMotor::new(3.0, 1000.0)),
+    );
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The second body spins around the first hinge axis at the target velocity.
+    let angular_velocity = app.world().get::<AngularVelocity>(body2).unwrap().0;
+    assert!((angular_velocity.x - 3.0).abs() < 0.1);
+
+    // The hinge axes stay perpendicular, and the anchors stay together.
+    let rotation = *app.world().get::<Rotation>(body2).unwrap();
+    assert!((rotation * Vector::Y).dot(Vector::X).abs() < 0.01);
+    let position = app.world().get::<Position>(body2).unwrap().0;
+    assert!((position - Vector::X * 2.0).length() < 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn poisson_restitution_conserves_energy_in_chained_impacts() {
+    use crate::dynamics::solver::{RestitutionModel, SolverConfig};
+
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+    app.insert_resource(SolverConfig {
+        restitution_model: RestitutionModel::Poisson,
+        ..default()
+    });
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    // A ball hits two touching balls, like in a Newton's cradle.
+    let balls: Vec<Entity> = [(0.0, 5.0), (3.0, 0.0), (4.0, 0.0)]
+        .into_iter()
+        .map(|(x, speed)| {
+            app.world_mut()
+                .spawn((
+                    RigidBody::Dynamic,
+                    collider.clone(),
+                    Position(Vector::X * x),
+                    LinearVelocity(Vector::X * speed),
+                    Restitution::new(1.0),
+                    Friction::ZERO,
+                ))
+                .id()
+        })
+        .collect();
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let velocities: Vec<Vector> = balls
+        .iter()
+        .map(|&entity| app.world().get::<LinearVelocity>(entity).unwrap().0)
+        .collect();
+
+    // Momentum and kinetic energy are conserved.
+    let momentum: Scalar = velocities.iter().map(|velocity| velocity.x).sum();
+    let energy: Scalar = velocities
+        .iter()
+        .map(|velocity| velocity.length_squared())
+        .sum();
+    assert!((momentum - 5.0).abs() < 0.1);
+    assert!(energy > 0.95 * 25.0);
+    assert!(velocities[2].x > velocities[1].x);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn gear_and_rack_and_pinion_joints_couple_motion() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let ground = app.world_mut().spawn(RigidBody::Static).id();
+    let mut spawn_body = |position: Vector| {
+        app.world_mut()
+            .spawn((RigidBody::Dynamic, collider.clone(), Position(position)))
+            .id()
+    };
+    let gear1 = spawn_body(Vector::ZERO);
+    let gear2 = spawn_body(Vector::X * 5.0);
+    let rack = spawn_body(Vector::X * 10.0);
+
+    app.world_mut().spawn_batch([
+        RevoluteJoint::new(ground, gear1),
+        RevoluteJoint::new(ground, gear2).with_local_anchor_1(Vector::X * 5.0),
+    ]);
+    app.world_mut()
+        .spawn(PrismaticJoint::new(ground, rack).with_local_anchor_1(Vector::X * 10.0));
+    app.world_mut()
+        .spawn(GearJoint::new(gear1, gear2).with_ratio(0.5));
+    app.world_mut()
+        .spawn(RackAndPinionJoint::new(gear1, rack).with_ratio(0.5));
+
+    #[cfg(feature = "2d")]
+    app.world_mut()
+        .entity_mut(gear1)
+        .insert(AngularVelocity(2.0));
+    #[cfg(feature = "3d")]
+    app.world_mut()
+        .entity_mut(gear1)
+        .insert(AngularVelocity(Vector::Z * 2.0));
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    #[cfg(feature = "2d")]
+    let angle = |entity| app.world().get::<Rotation>(entity).unwrap().as_radians();
+    #[cfg(feature = "3d")]
+    let angle = |entity| {
+        app.world()
+            .get::<Rotation>(entity)
+            .unwrap()
+            .0
+            .to_scaled_axis()
+            .z
+    };
+
+    // The second gear turns at half the rate in the opposite direction,
+    // and the rack moves by the pitch radius for each radian.
+    let angle1 = angle(gear1);
+    assert!(angle1 > 0.1);
+    assert!((angle(gear2) + 0.5 * angle1).abs() < 0.01);
+    let rack_position = app.world().get::<Position>(rack).unwrap().0;
+    assert!((rack_position.x - 10.0 - 0.5 * angle1).abs() < 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn cloned_colliders_share_shapes_until_modified() {
+    let mut app = create_app();
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let entities: Vec<Entity> = app
+        .world_mut()
+        .spawn_batch((0..100).map(|i| {
+            (
+                RigidBody::Static,
+                collider.clone_shared(),
+                Position(Vector::X * i as Scalar),
+            )
+        }))
+        .collect();
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    for &entity in &entities {
+        let shared = app.world().get::<Collider>(entity).unwrap();
+        assert!(shared.shares_shape_with(&collider));
+    }
+
+    // Modifying one of the colliders copies its shape.
+    let mut modified = app.world_mut().get_mut::<Collider>(entities[0]).unwrap();
+    modified.modify_shape(|shape| shape.as_ball_mut().unwrap().radius = 1.0);
+    assert!(!modified.shares_shape_with(&collider));
+    assert_eq!(modified.shape().as_ball().unwrap().radius, 1.0);
+    assert_eq!(collider.shape().as_ball().unwrap().radius, 0.5);
+
+    let other = app.world().get::<Collider>(entities[1]).unwrap();
+    assert!(other.shares_shape_with(&collider));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn contact_columns_match_nested_contact_data() {
+    let mut app = create_app();
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::rectangle(1.0, 1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(1.0, 1.0, 1.0);
+
+    for i in 0..3 {
+        app.world_mut().spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            Position(Vector::Y * i as Scalar * 0.9),
+        ));
+    }
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let collisions = app.world().resource::<Collisions>();
+    let mut columns = ContactColumns::default();
+    collisions.write_columns(&mut columns);
+
+    let contacts: Vec<(&Contacts, &ContactData)> = collisions
+        .iter()
+        .flat_map(|contacts| {
+            contacts.manifolds.iter().flat_map(move |manifold| {
+                manifold.contacts.iter().map(move |data| (contacts, data))
+            })
+        })
+        .collect();
+
+    assert!(!columns.is_empty());
+    assert_eq!(columns.len(), contacts.len());
+    for (i, (contacts, data)) in contacts.into_iter().enumerate() {
+        assert_eq!(
+            columns.entity_pairs()[i],
+            (contacts.entity1, contacts.entity2)
+        );
+        assert_eq!(columns.points1()[i], data.point1);
+        assert_eq!(columns.normals2()[i], data.normal2);
+        assert_eq!(columns.penetrations()[i], data.penetration);
+        assert_eq!(columns.normal_impulses()[i], data.normal_impulse);
+        assert_eq!(columns.tangent_impulses()[i], data.tangent_impulse);
+    }
+
+    // Writing the columns again replaces the old data.
+    collisions.write_columns(&mut columns);
+    assert_eq!(columns.points2().len(), columns.len());
+    assert_eq!(columns.normals1().len(), columns.len());
+}
+
+#[test]
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+fn spherical_joint_cone_limit_keeps_body_within_cone() {
+    let mut app = create_app();
+    app.finish();
+
+    let max_angle = 0.5;
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            MassPropertiesBundle::from_shape(&Sphere::new(0.5), 1.0),
+            // Start within the cone, swinging towards its edge.
+            Position(Vector::Y * -1.0),
+            AngularVelocity(Vector::Z * 5.0),
+        ))
+        .id();
+    let mut joint = SphericalJoint::new(anchor, body)
+        .with_local_anchor_2(Vector::Y)
+        .with_cone_limit(max_angle)
+        .with_limit_response(JointLimitResponse::new(0.0, 0.5));
+    joint.swing_axis = Vector::NEG_Y;
+    app.world_mut().spawn(joint);
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+
+        let rotation = app.world().get::<Rotation>(body).unwrap();
+        let swing_axis = *rotation * Vector::NEG_Y;
+        let angle = swing_axis.angle_between(Vector::NEG_Y);
+        assert!(angle < max_angle + 0.05, "swing angle {angle} exceeds cone");
+    }
+}
+
+#[test]
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+fn broad_phase_pair_filter_rejects_pairs() {
+    use bevy::ecs::system::SystemParam;
+
+    #[derive(Component)]
+    struct Ghost;
+
+    // Ghosts don't collide with anything.
+    #[derive(SystemParam)]
+    struct GhostFilter<'w, 's> {
+        ghosts: Query<'w, 's, (), With<Ghost>>,
+    }
+
+    impl BroadPhasePairFilter for GhostFilter<'_, '_> {
+        fn filter_pair(&self, collider1: Entity, collider2: Entity) -> bool {
+            !self.ghosts.contains(collider1) && !self.ghosts.contains(collider2)
+        }
+    }
+
+    let mut app = create_app();
+    app.add_plugins(BroadPhasePairFilterPlugin::<GhostFilter>::default());
+    app.finish();
+
+    app.world_mut().spawn((RigidBody::Static, cuboid(10.0)));
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, cuboid(1.0), Position(Vector::Y * 6.0)))
+        .id();
+    let ghost = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            cuboid(1.0),
+            Position(Vector::X * 3.0 + Vector::Y * 6.0),
+            Ghost,
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The regular body lands on the ground, but the ghost falls through it.
+    let body_position = app.world().get::<Position>(body).unwrap();
+    let ghost_position = app.world().get::<Position>(ghost).unwrap();
+    assert!(body_position.y > 5.0);
+    assert!(ghost_position.y < 0.0);
+}
+
+#[test]
+fn prismatic_joint_spring_settles_at_equilibrium() {
+    let mut app = create_app();
+    app.finish();
+
+    let stiffness = 100.0;
+    let mass: f32 = 1.0;
+
+    #[cfg(feature = "2d")]
+    let inertia = AngularInertia(1.0);
+    #[cfg(feature = "3d")]
+    let inertia = AngularInertia::new(Vector::ONE.f32());
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Mass(mass), inertia))
+        .id();
+    app.world_mut().spawn(
+        PrismaticJoint::new(anchor, body)
+            .with_free_axis(Vector::Y)
+            .with_translation_spring(0.0, JointSpring::new(stiffness, 20.0)),
+    );
+
+    for _ in 0..300 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The spring force balances gravity at a displacement of m * g / k.
+    let gravity = app.world().resource::<Gravity>().0;
+    let position = app.world().get::<Position>(body).unwrap();
+    assert_relative_eq!(
+        position.y,
+        gravity.y * Scalar::from(mass) / stiffness,
+        epsilon = 0.01
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn sensor_entry_reports_entry_point_and_normal() {
+    let mut app = create_app();
+    app.add_plugins(SensorEntryPlugin)
+        .insert_resource(Gravity::ZERO);
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let (sensor_collider, ball_collider) = (Collider::rectangle(2.0, 2.0), Collider::circle(0.25));
+    #[cfg(feature = "3d")]
+    let (sensor_collider, ball_collider) =
+        (Collider::cuboid(2.0, 2.0, 2.0), Collider::sphere(0.25));
+
+    let sensor = app
+        .world_mut()
+        .spawn((RigidBody::Static, sensor_collider, Sensor))
+        .id();
+    let ball = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            ball_collider,
+            Position(Vector::X * -3.0),
+            LinearVelocity(Vector::X * 10.0),
+        ))
+        .id();
+
+    let mut cursor = app.world().resource::<Events<SensorEntered>>().get_cursor();
+    let mut events = vec![];
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+        events.extend(
+            cursor
+                .read(app.world().resource::<Events<SensorEntered>>())
+                .copied(),
+        );
+    }
+
+    // The ball enters through the face of the sensor facing it.
+    assert_eq!(events.len(), 1);
+    let event = events[0];
+    assert_eq!((event.sensor, event.entity), (sensor, ball));
+    assert_relative_eq!(event.point.x, -1.0, epsilon = 0.05);
+    assert_relative_eq!(event.normal.x, -1.0, epsilon = 0.05);
+}
+
+#[test]
+fn revolute_joint_limit_restitution_bounces_back() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let (inertia, angular_velocity) = (AngularInertia(1.0), AngularVelocity(2.0));
+    #[cfg(feature = "3d")]
+    let (inertia, angular_velocity) = (
+        AngularInertia::new(Vector::ONE.f32()),
+        AngularVelocity(Vector::Z * 2.0),
+    );
+
+    let mut spawn_hinge = |limit_response: JointLimitResponse| {
+        let anchor = app.world_mut().spawn(RigidBody::Static).id();
+        let body = app
+            .world_mut()
+            .spawn((RigidBody::Dynamic, Mass(1.0), inertia, angular_velocity))
+            .id();
+        app.world_mut().spawn(
+            RevoluteJoint::new(anchor, body)
+                .with_angle_limits(-0.5, 0.5)
+                .with_limit_response(limit_response)
+                .with_angular_velocity_damping(0.0),
+        );
+        body
+    };
+
+    let rigid = spawn_hinge(JointLimitResponse::RIGID);
+    let bouncy = spawn_hinge(JointLimitResponse::new(0.0, 0.8));
+
+    let angular_speed = |app: &App, body: Entity| {
+        let angular_velocity = app.world().get::<AngularVelocity>(body).unwrap();
+        #[cfg(feature = "2d")]
+        {
+            angular_velocity.0
+        }
+        #[cfg(feature = "3d")]
+        {
+            angular_velocity.z
+        }
+    };
+
+    let mut min_rigid_speed = Scalar::MAX;
+    let mut min_bouncy_speed = Scalar::MAX;
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+        min_rigid_speed = min_rigid_speed.min(angular_speed(&app, rigid));
+        min_bouncy_speed = min_bouncy_speed.min(angular_speed(&app, bouncy));
+    }
+
+    // The rigid limit stops the body, but the bouncy limit reverses its rotation.
+    assert!(min_rigid_speed > -0.1);
+    assert!(min_bouncy_speed < -1.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn frozen_body_pauses_and_resumes_with_velocity() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    let frozen = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            cuboid(1.0),
+            LinearVelocity(Vector::Y * 2.0),
+            Frozen::default(),
+        ))
+        .id();
+    // A body moving towards the frozen body is stopped by it.
+    let other = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            cuboid(1.0),
+            Position(Vector::X * -3.0),
+            LinearVelocity(Vector::X * 5.0),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let position = app.world().get::<Position>(frozen).unwrap();
+    assert_eq!(position.0, Vector::ZERO);
+    let other_position = app.world().get::<Position>(other).unwrap();
+    assert!(other_position.x < -0.9);
+
+    // The body resumes with the velocity it had when it was frozen.
+    app.world_mut().entity_mut(frozen).remove::<Frozen>();
+    assert_eq!(
+        app.world().get::<RigidBody>(frozen),
+        Some(&RigidBody::Dynamic)
+    );
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let velocity = app.world().get::<LinearVelocity>(frozen).unwrap();
+    assert_relative_eq!(velocity.y, 2.0, epsilon = 0.01);
+}
+
+#[test]
+fn fixed_physics_timestep_decouples_physics_from_fixed_update() {
+    #[derive(Resource, Default)]
+    struct StepCounts {
+        fixed: usize,
+        physics: usize,
+    }
+
+    let mut app = create_app();
+
+    // Run `FixedUpdate` at 60 Hz and physics at 30 Hz.
+    let fixed_timestep = Duration::from_secs_f64(1.0 / 60.0);
+    let physics_timestep = fixed_timestep * 2;
+
+    app.insert_resource(Time::<Fixed>::from_duration(fixed_timestep))
+        .insert_resource(Time::<Physics>::default().with_fixed_timestep(physics_timestep))
+        .init_resource::<StepCounts>()
+        .add_systems(FixedUpdate, |mut counts: ResMut<StepCounts>| {
+            counts.fixed += 1;
+        })
+        .add_systems(
+            PhysicsSchedule,
+            (move |mut counts: ResMut<StepCounts>, time: Res<Time>| {
+                assert_eq!(time.delta(), physics_timestep);
+                counts.physics += 1;
+            })
+            .in_set(PhysicsStepSet::First),
+        );
+
+    app.finish();
+
+    for _ in 0..20 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let counts = app.world().resource::<StepCounts>();
+    assert!(counts.physics > 0);
+    assert_eq!(counts.physics, counts.fixed / 2);
+}
+
+#[test]
+fn interpolation_eases_over_a_fixed_physics_timestep() {
+    let mut app = create_app();
+
+    // Render at 120 Hz, run `FixedUpdate` at 60 Hz and physics at 30 Hz.
+    app.insert_resource(Gravity::ZERO)
+        .insert_resource(Time::<Fixed>::from_hz(60.0))
+        .insert_resource(Time::<Physics>::default().with_fixed_timestep_hz(30.0));
+
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            LinearVelocity(Vector::X),
+            TransformInterpolation,
+        ))
+        .id();
+
+    app.finish();
+
+    for _ in 0..20 {
+        tick_app(&mut app, 1.0 / 120.0);
+    }
+
+    // The body moves smoothly on every frame, not just on the frames where physics is stepped,
+    // and stays behind the latest physics pose.
+    let mut previous_x = app.world().get::<Transform>(body).unwrap().translation.x;
+    for _ in 0..8 {
+        tick_app(&mut app, 1.0 / 120.0);
+        let x = app.world().get::<Transform>(body).unwrap().translation.x;
+        let position = app.world().get::<Position>(body).unwrap();
+        assert!(x > previous_x);
+        assert!(x <= position.x as f32 + 1e-5);
+        previous_x = x;
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn toggling_joint_disabled_wakes_up_attached_bodies() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body1 = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider.clone()))
+        .id();
+    let body2 = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::X * 2.0), collider))
+        .id();
+    let joint = app
+        .world_mut()
+        .spawn(DistanceJoint::new(body1, body2).with_rest_length(2.0))
+        .id();
+
+    app.finish();
+
+    let assert_sleeping = |app: &App, sleeping: bool| {
+        assert_eq!(app.world().entity(body1).contains::<Sleeping>(), sleeping);
+        assert_eq!(app.world().entity(body2).contains::<Sleeping>(), sleeping);
+    };
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert_sleeping(&app, true);
+
+    // Disabling the joint wakes up the bodies.
+    app.world_mut().entity_mut(joint).insert(JointDisabled);
+    tick_app(&mut app, 1.0 / 60.0);
+    assert_sleeping(&app, false);
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert_sleeping(&app, true);
+
+    // Re-enabling the joint wakes up the bodies again.
+    app.world_mut().entity_mut(joint).remove::<JointDisabled>();
+    tick_app(&mut app, 1.0 / 60.0);
+    assert_sleeping(&app, false);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn disabled_rigid_body_keeps_its_state() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            LinearVelocity(Vector::X * 2.0),
+            collider,
+            RigidBodyDisabled,
+        ))
+        .id();
+
+    app.finish();
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The disabled body is not moved by its velocity or by gravity.
+    let world = app.world();
+    assert_eq!(world.get::<Position>(body).unwrap().0, Vector::ZERO);
+    assert_eq!(
+        world.get::<LinearVelocity>(body).unwrap().0,
+        Vector::X * 2.0
+    );
+
+    // Re-enabling the body resumes the simulation from the same state.
+    app.world_mut()
+        .entity_mut(body)
+        .remove::<RigidBodyDisabled>();
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let position = app.world().get::<Position>(body).unwrap().0;
+    assert!(position.x > 0.0);
+    assert!(position.y < 0.0);
+}
+
+#[test]
+fn skip_warm_start_is_removed_after_its_steps() {
+    let mut app = create_app();
+
+    let body = app.world_mut().spawn(RigidBody::Dynamic).id();
+
+    app.finish();
+    tick_app(&mut app, 1.0 / 60.0);
+
+    // Teleporting a body, for example, can skip warm starting for a few steps.
+    app.world_mut().entity_mut(body).insert(SkipWarmStart(2));
+
+    tick_app(&mut app, 1.0 / 60.0);
+    assert_eq!(
+        app.world().get::<SkipWarmStart>(body),
+        Some(&SkipWarmStart(1))
+    );
+
+    tick_app(&mut app, 1.0 / 60.0);
+    assert!(!app.world().entity(body).contains::<SkipWarmStart>());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_steps_over_low_obstacles() {
+    let mut app = create_app();
+    app.add_plugins(CharacterControllerPlugin::default());
+
+    #[cfg(feature = "2d")]
+    let (floor, step, ball) = (
+        Collider::rectangle(20.0, 1.0),
+        Collider::rectangle(2.0, 0.2),
+        Collider::circle(0.5),
+    );
+    #[cfg(feature = "3d")]
+    let (floor, step, ball) = (
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Collider::cuboid(2.0, 0.2, 20.0),
+        Collider::sphere(0.5),
+    );
+
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::NEG_Y * 0.5), floor));
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::X * 2.0 + Vector::Y * 0.1),
+        step,
+    ));
+    let character = app
+        .world_mut()
+        .spawn((
+            CharacterController {
+                movement: Vector::X * 2.0,
+                ..default()
+            },
+            Position(Vector::Y * 0.51),
+            ball,
+        ))
+        .id();
+
+    app.finish();
+
+    for _ in 0..75 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The character walked onto the step, which is lower than the maximum step height.
+    let position = app.world().get::<Position>(character).unwrap().0;
+    let controller = app.world().get::<CharacterController>(character).unwrap();
+    assert!(position.x > 1.5, "character got stuck at {position}");
+    assert!(position.y > 0.65);
+    assert!(controller.is_grounded());
+}
+
+#[test]
+fn custom_constraint_registered_with_add_physics_constraint_is_solved() {
+    use crate::dynamics::solver::xpbd::XpbdConstraint;
+    use bevy::ecs::entity::{EntityMapper, MapEntities};
+
+    #[derive(Component)]
+    struct StopConstraint {
+        entity: Entity,
+        solve_count: usize,
+    }
+
+    impl MapEntities for StopConstraint {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.entity = entity_mapper.map_entity(self.entity);
+        }
+    }
+
+    impl XpbdConstraint<1> for StopConstraint {
+        fn entities(&self) -> [Entity; 1] {
+            [self.entity]
+        }
+
+        fn clear_lagrange_multipliers(&mut self) {}
+
+        fn solve(&mut self, _bodies: [&mut RigidBodyQueryItem; 1], _dt: Scalar) {
+            self.solve_count += 1;
+        }
+
+        fn solve_velocity(&mut self, bodies: [&mut RigidBodyQueryItem; 1], _dt: Scalar) {
+            bodies[0].linear_velocity.0 = Vector::ZERO;
+        }
+    }
+
+    let mut app = create_app();
+    app.add_physics_constraint::<StopConstraint, 1>();
+    app.finish();
+
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::from_shape(&Circle::new(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::from_shape(&Sphere::new(0.5), 1.0),
+            LinearVelocity(Vector::X * 10.0),
+        ))
+        .id();
+    let constraint = app
+        .world_mut()
+        .spawn(StopConstraint {
+            entity: body,
+            solve_count: 0,
+        })
+        .id();
+
+    for _ in 0..5 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let world = app.world();
+    assert!(world.get::<StopConstraint>(constraint).unwrap().solve_count > 0);
+    assert_eq!(world.get::<LinearVelocity>(body).unwrap().0, Vector::ZERO);
+}
+
+#[test]
+fn custom_constraints_connect_islands() {
+    use crate::dynamics::solver::xpbd::XpbdConstraint;
+    use bevy::ecs::entity::{EntityMapper, MapEntities};
+
+    #[derive(Component)]
+    struct LinkConstraint {
+        entities: [Entity; 2],
+    }
+
+    impl MapEntities for LinkConstraint {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.entities = self.entities.map(|entity| entity_mapper.map_entity(entity));
+        }
+    }
+
+    impl XpbdConstraint<2> for LinkConstraint {
+        fn entities(&self) -> [Entity; 2] {
+            self.entities
+        }
+
+        fn clear_lagrange_multipliers(&mut self) {}
+
+        fn solve(&mut self, _bodies: [&mut RigidBodyQueryItem; 2], _dt: Scalar) {}
+    }
+
+    let mut app = create_app();
+    app.add_physics_constraint::<LinkConstraint, 2>();
+    app.finish();
+
+    let mut spawn_body = |x: Scalar| {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * x),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::from_shape(&Circle::new(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::from_shape(&Sphere::new(0.5), 1.0),
+            ))
+            .id()
+    };
+    let body1 = spawn_body(0.0);
+    let body2 = spawn_body(10.0);
+    let body3 = spawn_body(20.0);
+
+    app.world_mut().spawn(LinkConstraint {
+        entities: [body1, body2],
+    });
+
+    for _ in 0..2 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The linked bodies are in the same island, even though they are not touching.
+    let islands = app.world().resource::<PhysicsIslands>();
+    assert_eq!(islands.len(), 2);
+    let island_id = |entity| app.world().get::<PhysicsIslandId>(entity).copied();
+    assert_eq!(island_id(body1), island_id(body2));
+    assert_ne!(island_id(body1), island_id(body3));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn graph_coloring_splits_large_islands_into_independent_colors() {
+    use crate::dynamics::solver::{ContactConstraints, SolverConfig};
+
+    let mut app = create_app();
+    app.insert_resource(SolverConfig {
+        graph_coloring_threshold: Some(2),
+        ..default()
+    });
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        cuboid(50.0),
+    ));
+    let stack: Vec<Entity> = (0..4)
+        .map(|i| {
+            app.world_mut()
+                .spawn((
+                    RigidBody::Dynamic,
+                    Position(Vector::Y * (0.5 + i as Scalar)),
+                    cuboid(1.0),
+                ))
+                .id()
+        })
+        .collect();
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let islands = app.world().resource::<PhysicsIslands>();
+    let island = islands.island_of(stack[0]).unwrap();
+    assert_eq!(island.len(), 4);
+    assert!(island.color_count() >= 2);
+
+    // The constraints of a color never share a dynamic body.
+    let constraints = app.world().resource::<ContactConstraints>();
+    let coloring = island.coloring.as_ref().unwrap();
+    for color in coloring.colors.iter() {
+        let mut bodies: Vec<Entity> = constraints[color.clone()]
+            .iter()
+            .flat_map(|constraint| [constraint.entity1, constraint.entity2])
+            .filter(|entity| stack.contains(entity))
+            .collect();
+        let count = bodies.len();
+        bodies.sort();
+        bodies.dedup();
+        assert_eq!(bodies.len(), count);
+    }
+
+    // The stack stays upright.
+    for (i, entity) in stack.iter().enumerate() {
+        let position = app.world().get::<Position>(*entity).unwrap();
+        assert!((position.y - (0.5 + i as Scalar)).abs() < 0.1);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_does_not_stand_up_into_ceilings() {
+    let mut app = create_app();
+    app.add_plugins(CharacterControllerPlugin::default());
+
+    #[cfg(feature = "2d")]
+    let (floor, ceiling) = (
+        Collider::rectangle(20.0, 1.0),
+        Collider::rectangle(4.0, 1.0),
+    );
+    #[cfg(feature = "3d")]
+    let (floor, ceiling) = (
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Collider::cuboid(4.0, 1.0, 20.0),
+    );
+
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::NEG_Y * 0.5), floor));
+    // The bottom of the ceiling is at a height of 1.5.
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::Y * 2.0), ceiling));
+
+    // A crouching character with a height of 1.0 that wants to stand up to a height of 1.8.
+    let character = app
+        .world_mut()
+        .spawn((
+            CharacterController::default().with_target_height(1.8),
+            Collider::capsule(0.4, 0.2),
+            Position(Vector::Y * 0.51),
+        ))
+        .id();
+
+    app.finish();
+
+    let height = |app: &App| {
+        let capsule = app
+            .world()
+            .get::<Collider>(character)
+            .unwrap()
+            .shape()
+            .as_capsule()
+            .unwrap();
+        capsule.height() + 2.0 * capsule.radius
+    };
+    let bottom = |app: &App| app.world().get::<Position>(character).unwrap().y - height(app) * 0.5;
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The character stands up only as far as the ceiling allows.
+    assert!(height(&app) > 1.0 && height(&app) < 1.5);
+    assert!(bottom(&app).abs() < 0.05);
+    assert!(app
+        .world()
+        .get::<CharacterController>(character)
+        .unwrap()
+        .is_height_blocked());
+
+    // Walk out from under the ceiling.
+    app.world_mut()
+        .get_mut::<CharacterController>(character)
+        .unwrap()
+        .movement = Vector::X * 4.0;
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let controller = app.world().get::<CharacterController>(character).unwrap();
+    assert!(!controller.is_height_blocked());
+    assert!((height(&app) - 1.8).abs() < 0.01);
+    assert!(bottom(&app).abs() < 0.05);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn contact_batches_keep_stacks_resting() {
+    use crate::dynamics::solver::SolverConfig;
+
+    let mut app = create_app();
+    app.insert_resource(SolverConfig {
+        contact_batches: true,
+        ..default()
+    });
+
+    #[cfg(feature = "2d")]
+    let ground = Collider::rectangle(200.0, 1.0);
+    #[cfg(feature = "3d")]
+    let ground = Collider::cuboid(200.0, 1.0, 200.0);
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::NEG_Y * 0.5), ground));
+
+    // Many stacks, so that batches contain points of different bodies, and the islands
+    // of the stacks are split into several groups that are solved in parallel.
+    let stacks: Vec<Vec<Entity>> = (0..30)
+        .map(|x| {
+            (0..3)
+                .map(|i| {
+                    app.world_mut()
+                        .spawn((
+                            RigidBody::Dynamic,
+                            Position(
+                                Vector::X * (x as Scalar * 3.0) + Vector::Y * (0.5 + i as Scalar),
+                            ),
+                            cuboid(1.0),
+                        ))
+                        .id()
+                })
+                .collect()
+        })
+        .collect();
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    for (x, stack) in stacks.iter().enumerate() {
+        for (i, entity) in stack.iter().enumerate() {
+            let position = app.world().get::<Position>(*entity).unwrap();
+            assert!((position.x - x as Scalar * 3.0).abs() < 0.1);
+            assert!((position.y - (0.5 + i as Scalar)).abs() < 0.1);
+        }
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_floats_at_the_surface_of_fluid_volumes() {
+    let mut app = create_app();
+    app.add_plugins(CharacterControllerPlugin::default());
+
+    #[cfg(feature = "2d")]
+    let (floor, water) = (
+        Collider::rectangle(20.0, 1.0),
+        Collider::rectangle(20.0, 4.0),
+    );
+    #[cfg(feature = "3d")]
+    let (floor, water) = (
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Collider::cuboid(20.0, 4.0, 20.0),
+    );
+
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::NEG_Y * 0.5), floor));
+    // The surface of the water is at a height of 4.0.
+    app.world_mut()
+        .spawn((FluidVolume::new(1.2, 2.0), Position(Vector::Y * 2.0), water));
+
+    // A fully submerged character with a height of 1.8.
+    let character = app
+        .world_mut()
+        .spawn((
+            CharacterController::default(),
+            Collider::capsule(0.4, 1.0),
+            Position(Vector::Y * 2.0),
+        ))
+        .id();
+
+    app.finish();
+
+    for _ in 0..300 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The character floats up and settles with 1.0 / 1.2 of its height submerged.
+    let controller = app.world().get::<CharacterController>(character).unwrap();
+    let swimming = controller.swimming().expect("character should be swimming");
+    assert!(swimming.is_at_surface());
+    assert!((swimming.submersion - 1.0 / 1.2).abs() < 0.05);
+
+    let position = app.world().get::<Position>(character).unwrap();
+    assert!((position.y - 3.4).abs() < 0.1, "{}", position.y);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_movement_can_be_resolved_outside_of_the_schedule() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let floor = Collider::rectangle(20.0, 1.0);
+    #[cfg(feature = "3d")]
+    let floor = Collider::cuboid(20.0, 1.0, 20.0);
+
+    app.world_mut()
+        .spawn((RigidBody::Static, Position(Vector::NEG_Y * 0.5), floor));
+
+    app.finish();
+
+    // Update the spatial query pipeline.
+    for _ in 0..2 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let query_pipeline = app.world().resource::<SpatialQueryPipeline>();
+
+    // A character just above the floor, walking along it.
+    let collider = Collider::capsule(0.4, 1.0);
+    let state = CharacterController {
+        movement: Vector::X * 2.0,
+        ..default()
+    };
+    let resolve = |delta_secs: Scalar| {
+        resolve_movement(
+            MovementInput {
+                collider: &collider,
+                position: Vector::Y * 0.905,
+                rotation: Rotation::default(),
+                gravity: Vector::NEG_Y * 9.81,
+                delta_secs,
+                ground_velocity: Vector::ZERO,
+                filter: SpatialQueryFilter::default(),
+                predicate: &|_| true,
+                is_dynamic: &|_| false,
+                walkable: &|_| Walkable::Yes,
+                fluid_volume: &|_| None,
+            },
+            &state,
+            query_pipeline,
+        )
+    };
+
+    // The same input and state always produce the same result.
+    let result = resolve(1.0 / 60.0);
+    let other = resolve(1.0 / 60.0);
+    assert_eq!(result.linear_velocity, other.linear_velocity);
+    assert_eq!(result.velocity, other.velocity);
+    assert_eq!(result.ground, other.ground);
+
+    // The character lands on the floor and walks along it.
+    assert!(result.ground.is_some());
+    assert!((result.linear_velocity.x - 2.0).abs() < 0.01);
+
+    // The state is only updated when the result is applied.
+    let mut controller = state;
+    result.apply_to(&mut controller);
+    assert!(controller.is_grounded());
+    assert!(!state.is_grounded());
+
+    // Without a timestep, the character doesn't move and the state is kept.
+    let result = resolve(0.0);
+    assert_eq!(result.position, Vector::Y * 0.905);
+    assert_eq!(result.linear_velocity, Vector::ZERO);
+    assert!(result.ground.is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn fixed_contact_stiffness_uses_contact_hertz_and_keeps_heavy_stacks_stable() {
+    use crate::dynamics::solver::{
+        softness_parameters::SoftnessParameters, ContactSoftnessCoefficients, SolverConfig,
+    };
+
+    let mut app = create_app();
+    app.insert_resource(SolverConfig {
+        contact_stiffness: ContactStiffness::fixed(20.0, 5.0),
+        ..default()
+    });
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        cuboid(50.0),
+    ));
+    // A heavy container on top of a light one.
+    let light = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 0.5), cuboid(1.0)))
+        .id();
+    let heavy = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 1.5),
+            cuboid(1.0),
+            ColliderDensity(1000.0),
+        ))
+        .id();
+
+    app.finish();
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The contact stiffness is given by the configured frequencies.
+    let h = app.world().resource::<Time<Substeps>>().delta_secs_f64() as Scalar;
+    let coefficients = app.world().resource::<ContactSoftnessCoefficients>();
+    assert_eq!(
+        coefficients.dynamic,
+        SoftnessParameters::new(5.0, 20.0).compute_coefficients(h)
+    );
+    assert_eq!(
+        coefficients.non_dynamic,
+        SoftnessParameters::new(5.0, 40.0).compute_coefficients(h)
+    );
+
+    // The stack stays upright.
+    let light_position = app.world().get::<Position>(light).unwrap();
+    let heavy_position = app.world().get::<Position>(heavy).unwrap();
+    assert!((light_position.y - 0.5).abs() < 0.1);
+    assert!((heavy_position.y - 1.5).abs() < 0.1);
+    assert!(light_position.x.abs() < 0.1 && heavy_position.x.abs() < 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn crowd_agents_walk_on_the_ground_without_contacts_against_non_dynamic_bodies() {
+    let mut app = create_app();
+    app.add_plugins(CrowdPlugin);
+
+    #[cfg(feature = "2d")]
+    let floor = Collider::rectangle(100.0, 1.0);
+    #[cfg(feature = "3d")]
+    let floor = Collider::cuboid(100.0, 1.0, 100.0);
+
+    let floor = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::NEG_Y * 0.5), floor))
+        .id();
+
+    app.finish();
+
+    // Overlapping agents slightly above the floor.
+    let agents: Vec<Entity> = (0..10)
+        .map(|i| {
+            app.world_mut()
+                .spawn((
+                    CrowdAgent::new(Vector::X),
+                    Position(Vector::X * (i as Scalar * 0.5) + Vector::Y * 1.2),
+                ))
+                .id()
+        })
+        .collect();
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let collisions = app.world().resource::<Collisions>();
+    for (i, &entity) in agents.iter().enumerate() {
+        // The agent shares the collider of the crowd, and walks on the floor.
+        assert!(app.world().entity(entity).contains::<Collider>());
+        let agent = app.world().get::<CrowdAgent>(entity).unwrap();
+        assert_eq!(agent.ground(), Some(floor));
+
+        let position = app.world().get::<Position>(entity).unwrap();
+        assert!((position.y - 0.9).abs() < 0.05, "{}", position.y);
+        assert!(position.x > i as Scalar * 0.5 + 0.9);
+
+        // No contacts are generated against the floor or other agents.
+        assert!(!collisions.contains(entity, floor));
+        for &other in agents.iter() {
+            assert!(!collisions.contains(entity, other));
+        }
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn contact_impulses_are_cached_after_colliders_separate() {
+    use crate::collision::narrow_phase::ContactImpulseCache;
+
+    let mut app = create_app();
+    app.insert_resource(NarrowPhaseConfig {
+        impulse_cache_frames: 3,
+        ..default()
+    });
+
+    let floor = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            cuboid(50.0),
+        ))
+        .id();
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 0.5), cuboid(1.0)))
+        .id();
+
+    app.finish();
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let cached = |app: &App| {
+        app.world()
+            .resource::<ContactImpulseCache>()
+            .get(body, floor)
+            .map(|contacts| contacts.to_vec())
+    };
+    assert!(cached(&app).is_none());
+
+    // Lift the body off of the floor.
+    app.world_mut().get_mut::<Position>(body).unwrap().0 = Vector::Y * 3.0;
+    app.world_mut().get_mut::<LinearVelocity>(body).unwrap().0 = Vector::ZERO;
+    tick_app(&mut app, 1.0 / 60.0);
+
+    // The impulses of the resting contact are cached.
+    let contacts = cached(&app).expect("contacts should be cached");
+    let total_impulse: Scalar = contacts.iter().map(|contact| contact.normal_impulse).sum();
+    assert!(total_impulse > 0.0);
+
+    // The cached contacts are removed once they are older than the configured number of frames.
+    for _ in 0..4 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert!(cached(&app).is_none());
+}
+
+#[test]
+fn adaptive_substeps_increase_for_fast_bodies_and_decrease_when_calm() {
+    use crate::dynamics::solver::{
+        softness_parameters::SoftnessParameters, ContactSoftnessCoefficients,
+    };
+
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO)
+        .insert_resource(AdaptiveSubsteps {
+            decrease_delay: 5,
+            ..AdaptiveSubsteps::new(2, 12)
+        });
+
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, LinearVelocity(Vector::X * 100.0)))
+        .id();
+
+    app.finish();
+
+    tick_app(&mut app, 1.0 / 60.0);
+    tick_app(&mut app, 1.0 / 60.0);
+
+    // Moving 100 units per second would need 17 substeps to move at most 0.1 units per substep.
+    assert_eq!(app.world().resource::<SubstepCount>().0, 12);
+
+    app.world_mut()
+        .entity_mut(body)
+        .insert(LinearVelocity::ZERO);
+
+    // The substep count should not be decreased right away.
+    tick_app(&mut app, 1.0 / 60.0);
+    assert_eq!(app.world().resource::<SubstepCount>().0, 12);
+
+    // The contact softness should use the new substep length in the first step with the new count.
+    let dt = app.world().resource::<Time<Physics>>().delta_secs_f64() as Scalar;
+    let h = app.world().resource::<Time<Substeps>>().delta_secs_f64() as Scalar;
+    let hz = 1.5 * (1.0 / (2.0 * dt)).min(0.25 / h);
+    assert_eq!(
+        app.world()
+            .resource::<ContactSoftnessCoefficients>()
+            .dynamic,
+        SoftnessParameters::new(10.0, hz).compute_coefficients(h)
+    );
+
+    // Decrease by one every five calm steps until the minimum is reached.
+    for _ in 0..5 * 10 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert_eq!(app.world().resource::<SubstepCount>().0, 2);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn body_moved_events_are_sent_for_bodies_that_moved_past_the_threshold() {
+    let mut app = create_app();
+    app.add_plugins(BodyMovedPlugin)
+        .insert_resource(Gravity::ZERO);
+
+    let fast = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, LinearVelocity(Vector::X * 2.0)))
+        .id();
+    // Moves less than the default threshold of 0.01 units per step.
+    let slow = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, LinearVelocity(Vector::X * 0.1)))
+        .id();
+    let static_body = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::Y * 2.0)))
+        .id();
+
+    app.finish();
+
+    let mut cursor = app.world().resource::<Events<BodyMoved>>().get_cursor();
+    let mut events = vec![];
+
+    for _ in 0..10 {
+        tick_app(&mut app, 1.0 / 60.0);
+        events.extend(
+            cursor
+                .read(app.world().resource::<Events<BodyMoved>>())
+                .copied(),
+        );
+    }
+
+    let (fast_events, slow_events): (Vec<_>, Vec<_>) =
+        events.iter().partition(|event| event.entity == fast);
+    assert!(!fast_events.is_empty());
+    for event in fast_events {
+        assert_relative_eq!(event.distance(), 2.0 / 60.0, epsilon = 1e-4);
+        assert!(event.position.x > event.previous_position.x);
+    }
+
+    // The slow body is reported once its movement since it was last reported exceeds the threshold.
+    assert_eq!(slow_events.len(), 1);
+    assert_eq!(slow_events[0].entity, slow);
+    assert!(slow_events[0].distance() > 0.01);
+    assert!(slow_events[0].distance() < 0.01 + 0.1 / 60.0 + 1e-4);
+
+    // Static bodies are only reported when they are moved.
+    assert!(events.iter().all(|event| event.entity != static_body));
+    app.world_mut().get_mut::<Position>(static_body).unwrap().0 = Vector::Y * 3.0;
+    tick_app(&mut app, 1.0 / 60.0);
+    let static_event = cursor
+        .read(app.world().resource::<Events<BodyMoved>>())
+        .find(|event| event.entity == static_body)
+        .copied()
+        .expect("teleported static body should be reported");
+    assert_relative_eq!(static_event.distance(), 1.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn collision_started_events_carry_bodies_sensor_state_and_normal() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let (ground_collider, ball_collider) = (Collider::rectangle(10.0, 1.0), Collider::circle(0.5));
+    #[cfg(feature = "3d")]
+    let (ground_collider, ball_collider) =
+        (Collider::cuboid(10.0, 1.0, 10.0), Collider::sphere(0.5));
+
+    let ground = app
+        .world_mut()
+        .spawn((RigidBody::Static, ground_collider))
+        .id();
+    let ball = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y),
+            ball_collider.clone(),
+        ))
+        .id();
+    let sensor = app
+        .world_mut()
+        .spawn((Sensor, Position(Vector::Y), ball_collider))
+        .id();
+
+    app.finish();
+
+    let mut cursor = app
+        .world()
+        .resource::<Events<CollisionStarted>>()
+        .get_cursor();
+    let mut events = vec![];
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+        events.extend(
+            cursor
+                .read(app.world().resource::<Events<CollisionStarted>>())
+                .copied(),
+        );
+    }
+
+    let ground_event = events
+        .iter()
+        .find(|event| event.entity1 == ground || event.entity2 == ground)
+        .expect("the ball should hit the ground");
+    assert!(!ground_event.is_sensor);
+    let (ground_body, ball_body, up) = if ground_event.entity1 == ground {
+        (ground_event.body_entity1, ground_event.body_entity2, 1.0)
+    } else {
+        (ground_event.body_entity2, ground_event.body_entity1, -1.0)
+    };
+    assert_eq!(ground_body, Some(ground));
+    assert_eq!(ball_body, Some(ball));
+    // The normal points from the first collider towards the second.
+    let normal = ground_event
+        .normal
+        .expect("the collision should have a normal");
+    assert_relative_eq!(normal.y, up, epsilon = 1e-3);
+
+    let sensor_event = events
+        .iter()
+        .find(|event| event.entity1 == sensor || event.entity2 == sensor)
+        .expect("the ball should overlap the sensor");
+    assert!(sensor_event.is_sensor);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn angular_velocity_to_reach_rotates_kinematic_bodies_to_the_target() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let target = Rotation::radians(0.5);
+    #[cfg(feature = "3d")]
+    let target = Rotation(Quaternion::from_rotation_z(0.5));
+
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Kinematic, Rotation::default()))
+        .id();
+
+    app.finish();
+
+    // The first tick doesn't advance the simulation.
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let rotation = *app.world().get::<Rotation>(body).unwrap();
+    let angular_velocity = AngularVelocity::to_reach(rotation, target, 1.0 / 60.0);
+    app.world_mut().entity_mut(body).insert(angular_velocity);
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let rotation = *app.world().get::<Rotation>(body).unwrap();
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(rotation.angle_between(target), 0.0, epsilon = 1e-3);
+    #[cfg(feature = "3d")]
+    assert_relative_eq!(rotation.0.angle_between(target.0), 0.0, epsilon = 1e-2);
+
+    // Zero time gives zero velocity instead of an infinite one.
+    assert_eq!(
+        AngularVelocity::to_reach(rotation, target, 0.0),
+        AngularVelocity::ZERO
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn collision_events_are_only_sent_for_opted_in_entities() {
+    let mut app = create_app();
+    app.insert_resource(CollisionEventMode::OptIn);
+
+    #[cfg(feature = "2d")]
+    let (ground_collider, ball_collider) = (Collider::rectangle(20.0, 1.0), Collider::circle(0.5));
+    #[cfg(feature = "3d")]
+    let (ground_collider, ball_collider) =
+        (Collider::cuboid(20.0, 1.0, 20.0), Collider::sphere(0.5));
+
+    app.world_mut().spawn((RigidBody::Static, ground_collider));
+    let interesting = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y),
+            ball_collider.clone(),
+            CollisionEventsEnabled,
+        ))
+        .id();
+    let uninteresting = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::X * 5.0 + Vector::Y),
+            ball_collider,
+            CollidingEntities::default(),
+        ))
+        .id();
+
+    app.finish();
+
+    let mut cursor = app
+        .world()
+        .resource::<Events<CollisionStarted>>()
+        .get_cursor();
+    let mut events = vec![];
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+        events.extend(
+            cursor
+                .read(app.world().resource::<Events<CollisionStarted>>())
+                .copied(),
+        );
+    }
+
+    assert_eq!(events.len(), 1);
+    assert!(events[0].entity1 == interesting || events[0].entity2 == interesting);
+
+    // Colliding entities are still tracked for all colliders.
+    assert!(!app
+        .world()
+        .get::<CollidingEntities>(uninteresting)
+        .unwrap()
+        .is_empty());
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn kinematic_targets_are_reached_through_velocity() {
+    let mut app = create_app();
+
+    let target = Vector::X * 2.0 + Vector::Y;
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Kinematic, KinematicTarget::from_position(target)))
+        .id();
+
+    app.finish();
+
+    // The first tick doesn't advance the simulation.
+    tick_app(&mut app, 1.0 / 60.0);
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let position = app.world().get::<Position>(body).unwrap().0;
+    assert_relative_eq!(position.x, target.x, epsilon = 1e-4);
+    assert_relative_eq!(position.y, target.y, epsilon = 1e-4);
+    // The body moved with velocity instead of being teleported.
+    assert_relative_eq!(
+        app.world().get::<LinearVelocity>(body).unwrap().0.length(),
+        target.length() * 60.0,
+        epsilon = 1e-2
+    );
+
+    // Once the target is reached, the body stops.
+    tick_app(&mut app, 1.0 / 60.0);
+    assert_relative_eq!(
+        app.world().get::<LinearVelocity>(body).unwrap().0.length(),
+        0.0,
+        epsilon = 1e-2
+    );
+    assert_eq!(
+        LinearVelocity::to_reach(position, target, 0.0),
+        LinearVelocity::ZERO
+    );
+}
+
+/// Records the initial normal impulses of the contact constraints after the narrow phase,
+/// before the solver has modified them.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[derive(Resource, Default)]
+struct WarmStartImpulses(Vec<Scalar>);
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn record_warm_start_impulses(
+    constraints: Res<crate::dynamics::solver::ContactConstraints>,
+    mut impulses: ResMut<WarmStartImpulses>,
+) {
+    impulses.0 = constraints
+        .iter()
+        .flat_map(|constraint| constraint.points.iter())
+        .map(|point| point.normal_part.impulse)
+        .collect();
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn disabled_warm_starting_never_pulls_resting_bodies() {
+    use crate::{collision::narrow_phase::NarrowPhaseSet, dynamics::solver::SolverConfig};
+
+    let mut app = create_app();
+    app.insert_resource(SolverConfig {
+        warm_starting: false,
+        ..default()
+    });
+    app.init_resource::<WarmStartImpulses>();
+    app.add_systems(
+        PhysicsSchedule,
+        record_warm_start_impulses.in_set(NarrowPhaseSet::Last),
+    );
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        cuboid(50.0),
+    ));
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 0.5), cuboid(1.0)))
+        .id();
+
+    app.finish();
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+
+        // The impulses from the previous frame are discarded.
+        let impulses = &app.world().resource::<WarmStartImpulses>().0;
+        assert!(impulses.iter().all(|&impulse| impulse == 0.0));
+
+        // The contact impulses only push the box away from the ground.
+        for contacts in app.world().resource::<Collisions>().iter() {
+            for manifold in contacts.manifolds.iter() {
+                for contact in manifold.contacts.iter() {
+                    assert!(contact.normal_impulse >= 0.0);
+                }
+            }
+        }
+    }
+
+    // The box still rests on the ground.
+    let position = app.world().get::<Position>(body).unwrap();
+    assert!((position.y - 0.5).abs() < 0.05);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn cached_contact_impulses_are_reused_when_contact_resumes() {
+    use crate::collision::narrow_phase::NarrowPhaseSet;
+
+    let mut app = create_app();
+    app.insert_resource(NarrowPhaseConfig {
+        impulse_cache_frames: 3,
+        ..default()
+    });
+    app.init_resource::<WarmStartImpulses>();
+    app.add_systems(
+        PhysicsSchedule,
+        record_warm_start_impulses.in_set(NarrowPhaseSet::Last),
+    );
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        cuboid(50.0),
+    ));
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 0.5), cuboid(1.0)))
+        .id();
+
+    app.finish();
+
+    for _ in 0..30 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // Lift the body off of the floor for a frame.
+    app.world_mut().get_mut::<Position>(body).unwrap().0 = Vector::Y * 3.0;
+    app.world_mut().get_mut::<LinearVelocity>(body).unwrap().0 = Vector::ZERO;
+    tick_app(&mut app, 1.0 / 60.0);
+    assert!(app.world().resource::<WarmStartImpulses>().0.is_empty());
+
+    // Put the body back on the floor.
+    app.world_mut().get_mut::<Position>(body).unwrap().0 = Vector::Y * 0.5;
+    app.world_mut().get_mut::<LinearVelocity>(body).unwrap().0 = Vector::ZERO;
+    tick_app(&mut app, 1.0 / 60.0);
+
+    // The resumed contacts are warm started with the cached impulses.
+    let impulses = &app.world().resource::<WarmStartImpulses>().0;
+    assert!(!impulses.is_empty());
+    assert!(impulses.iter().sum::<Scalar>() > 0.0);
+}
+
+/// Spawns a ground plane and a 1000 kg vehicle with steered front wheels and driven rear wheels.
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+fn spawn_vehicle_on_ground(app: &mut App, vehicle: Vehicle, wheel: Wheel) -> Entity {
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Collider::cuboid(400.0, 1.0, 400.0),
+        Position(Vector::NEG_Y * 0.5),
+    ));
+
+    let wheels = vec![
+        Wheel::new(Vector::new(-1.0, -0.25, -1.5), 0.4).with_steering(),
+        Wheel::new(Vector::new(1.0, -0.25, -1.5), 0.4).with_steering(),
+        Wheel::new(Vector::new(-1.0, -0.25, 1.5), 0.4).with_drive(),
+        Wheel::new(Vector::new(1.0, -0.25, 1.5), 0.4).with_drive(),
+    ]
+    .into_iter()
+    .map(|w| Wheel {
+        anchor: w.anchor,
+        driven: w.driven,
+        steered: w.steered,
+        ..wheel
+    })
+    .collect();
+
+    app.world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(2.0, 0.5, 4.0),
+            ColliderDensity(250.0),
+            Position(Vector::Y),
+            Vehicle { wheels, ..vehicle },
+        ))
+        .id()
+}
+
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+#[test]
+fn vehicle_top_speed_is_limited_by_the_redline() {
+    let mut app = create_app();
+    app.add_plugins(VehiclePlugin::default());
+
+    // Full torque up to the redline at 120 RPM, or roughly 5 m/s.
+    let vehicle = spawn_vehicle_on_ground(
+        &mut app,
+        Vehicle::default().with_torque_curve(TorqueCurve::new([(0.0, 1.0), (120.0, 1.0)])),
+        Wheel::new(Vector::ZERO, 0.4),
+    );
+    let top_speed = app
+        .world()
+        .get::<Vehicle>(vehicle)
+        .unwrap()
+        .top_speed()
+        .unwrap();
+    assert!((top_speed - 5.0).abs() < 0.1);
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    app.world_mut()
+        .get_mut::<Vehicle>(vehicle)
+        .unwrap()
+        .throttle = 1.0;
+
+    for _ in 0..180 {
+        tick_app(&mut app, 1.0 / 60.0);
+        let velocity = app.world().get::<LinearVelocity>(vehicle).unwrap().0;
+        assert!(-velocity.z < top_speed + 0.5);
+    }
+
+    // The vehicle reaches its top speed, and the engine runs at the redline.
+    let velocity = app.world().get::<LinearVelocity>(vehicle).unwrap().0;
+    assert!(-velocity.z > top_speed - 1.0);
+    let engine_rpm = app.world().get::<Vehicle>(vehicle).unwrap().engine_rpm;
+    assert!((engine_rpm - 120.0).abs() < 15.0);
+}
+
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+#[test]
+fn vehicle_gearbox_shifts_up_while_accelerating() {
+    let mut app = create_app();
+    app.add_plugins(VehiclePlugin::default());
+
+    let gearbox = Gearbox::new(vec![3.0, 2.0, 1.0], 3.0, 1.0).with_shift_rpm(150.0, 50.0);
+    let vehicle = spawn_vehicle_on_ground(
+        &mut app,
+        Vehicle::default()
+            .with_engine_torque(500.0)
+            .with_gearbox(gearbox),
+        Wheel::new(Vector::ZERO, 0.4),
+    );
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    assert_eq!(app.world().get::<Vehicle>(vehicle).unwrap().gearbox.gear, 0);
+
+    app.world_mut()
+        .get_mut::<Vehicle>(vehicle)
+        .unwrap()
+        .throttle = 1.0;
+
+    for _ in 0..240 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let vehicle_ref = app.world().get::<Vehicle>(vehicle).unwrap();
+    assert_eq!(vehicle_ref.gearbox.gear, 2);
+    assert!(vehicle_ref.engine_rpm > 50.0);
+}
+
+#[cfg(all(feature = "3d", any(feature = "parry-f32", feature = "parry-f64")))]
+#[test]
+fn slip_curve_tires_spin_under_throttle_and_lock_under_braking() {
+    let mut app = create_app();
+    app.add_plugins(VehiclePlugin::default());
+
+    let vehicle = spawn_vehicle_on_ground(
+        &mut app,
+        Vehicle::default().with_engine_torque(1000.0),
+        Wheel::new(Vector::ZERO, 0.4).with_tire_model(TireModel::SLIP_CURVES),
+    );
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+    app.world_mut()
+        .get_mut::<Vehicle>(vehicle)
+        .unwrap()
+        .throttle = 1.0;
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The driven wheels spin faster than the ground while accelerating.
+    let vehicle_ref = app.world().get::<Vehicle>(vehicle).unwrap();
+    assert!(vehicle_ref
+        .wheels
+        .iter()
+        .filter(|wheel| wheel.driven)
+        .all(|wheel| wheel.slip_ratio > 0.0 && wheel.angular_velocity > 0.0));
+    let speed = -app.world().get::<LinearVelocity>(vehicle).unwrap().z;
+    assert!(speed > 1.0);
+
+    {
+        let mut vehicle_mut = app.world_mut().get_mut::<Vehicle>(vehicle).unwrap();
+        vehicle_mut.throttle = 0.0;
+        vehicle_mut.brake = 1.0;
+    }
+    tick_app(&mut app, 1.0 / 60.0);
+
+    // The wheels slow down faster than the ground under braking.
+    let vehicle_ref = app.world().get::<Vehicle>(vehicle).unwrap();
+    assert!(vehicle_ref
+        .wheels
+        .iter()
+        .all(|wheel| wheel.slip_ratio < 0.0));
+
+    for _ in 0..180 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // The vehicle comes to a stop without sliding sideways.
+    let velocity = app.world().get::<LinearVelocity>(vehicle).unwrap().0;
+    assert!(velocity.length() < 0.2);
+}
+
+#[cfg(all(
+    feature = "3d",
+    feature = "settings-asset",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn vehicle_config_asset_is_applied_to_vehicles() {
+    let mut app = create_app();
+    app.add_plugins(VehiclePlugin::default());
+
+    let config = VehicleConfig {
+        engine_torque: Some(400.0),
+        gearbox: Some(Gearbox::new(vec![3.5, 2.0, 1.2], 3.0, 3.7)),
+        tire_model: Some(TireModel::SLIP_CURVES),
+        ..default()
+    };
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<VehicleConfig>>()
+        .add(config);
+
+    let vehicle =
+        spawn_vehicle_on_ground(&mut app, Vehicle::default(), Wheel::new(Vector::ZERO, 0.4));
+    app.world_mut()
+        .entity_mut(vehicle)
+        .insert(VehicleConfigHandle(handle.clone()));
+
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let vehicle_ref = app.world().get::<Vehicle>(vehicle).unwrap();
+    assert_eq!(vehicle_ref.engine_torque, 400.0);
+    assert_eq!(vehicle_ref.gearbox.ratios, vec![3.5, 2.0, 1.2]);
+    assert_eq!(vehicle_ref.gearbox.final_drive, 3.7);
+    assert!(vehicle_ref
+        .wheels
+        .iter()
+        .all(|wheel| wheel.tire_model == TireModel::SLIP_CURVES));
+    // Omitted fields are left unchanged.
+    assert_eq!(vehicle_ref.brake_torque, Vehicle::default().brake_torque);
+
+    // Modifying the asset applies it again.
+    app.world_mut()
+        .resource_mut::<Assets<VehicleConfig>>()
+        .get_mut(&handle)
+        .unwrap()
+        .engine_torque = Some(800.0);
+
+    // The asset event is sent at the end of the frame, and handled in the next one.
+    tick_app(&mut app, 1.0 / 60.0);
+    tick_app(&mut app, 1.0 / 60.0);
+
+    let vehicle_ref = app.world().get::<Vehicle>(vehicle).unwrap();
+    assert_eq!(vehicle_ref.engine_torque, 800.0);
+}
+
+#[test]
+#[cfg(all(feature = "3d", feature = "settings-asset"))]
+fn vehicle_config_can_be_parsed_from_ron() {
+    let config: VehicleConfig = ron::de::from_str(
+        "(
+            torque_curve: Some([(1000.0, 0.6), (4500.0, 1.0)]),
+            gearbox: Some((ratios: [3.5, 2.2], final_drive: 3.7)),
+            tire_model: Some(SlipCurves(
+                longitudinal: (stiffness: 12.0),
+                lateral: (stiffness: 14.0, shape: 1.3, peak: 1.0, curvature: 0.0),
+            )),
+        )",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.torque_curve,
+        Some(TorqueCurve::new([(1000.0, 0.6), (4500.0, 1.0)]))
+    );
+    let gearbox = config.gearbox.unwrap();
+    assert_eq!(gearbox.ratios, vec![3.5, 2.2]);
+    assert_eq!(gearbox.final_drive, 3.7);
+    assert_eq!(gearbox.reverse_ratio, Gearbox::default().reverse_ratio);
+    assert_eq!(
+        config.tire_model,
+        Some(TireModel::SlipCurves {
+            longitudinal: TractionCurve {
+                stiffness: 12.0,
+                ..TractionCurve::LONGITUDINAL
+            },
+            lateral: TractionCurve::LATERAL,
+        })
+    );
+    assert_eq!(config.engine_torque, None);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_only_stands_on_walkable_surfaces() {
+    let mut app = create_app();
+    app.add_plugins(CharacterControllerPlugin::default());
+
+    #[cfg(feature = "2d")]
+    let (floor, ball) = (Collider::rectangle(4.0, 1.0), Collider::circle(0.5));
+    #[cfg(feature = "3d")]
+    let (floor, ball) = (Collider::cuboid(4.0, 1.0, 4.0), Collider::sphere(0.5));
+
+    // A character above each kind of floor.
+    let characters = [Walkable::Yes, Walkable::Slippery, Walkable::No].map(|walkable| {
+        let x = match walkable {
+            Walkable::Yes => -10.0,
+            Walkable::Slippery => 0.0,
+            Walkable::No => 10.0,
+        };
+        app.world_mut().spawn((
+            RigidBody::Static,
+            Position(Vector::X * x + Vector::NEG_Y * 0.5),
+            floor.clone(),
+            walkable,
+        ));
+        app.world_mut()
+            .spawn((
+                CharacterController::default(),
+                Position(Vector::X * x + Vector::Y * 0.6),
+                ball.clone(),
+            ))
+            .id()
+    });
+
+    app.finish();
+
+    for _ in 0..60 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let [walkable, slippery, forbidden] =
+        characters.map(|entity| *app.world().get::<CharacterController>(entity).unwrap());
+    assert!(walkable.is_grounded());
+    assert!(!slippery.is_grounded());
+    assert!(!forbidden.is_grounded());
+
+    // The characters still rest on top of the floors.
+    for entity in characters {
+        let position = app.world().get::<Position>(entity).unwrap().0;
+        assert!(position.y > 0.45, "character fell through at {position}");
+    }
+
+    // Surfaces are classified based on the flag and the slope.
+    let controller = CharacterController::default();
+    #[cfg(feature = "2d")]
+    let steep = Vector::new(2.0, 1.0).normalize();
+    #[cfg(feature = "3d")]
+    let steep = Vector::new(2.0, 1.0, 0.0).normalize();
+    assert_eq!(
+        controller.classify_surface(Vector::Y, Walkable::Yes),
+        SurfaceClass::Walkable
+    );
+    assert_eq!(
+        controller.classify_surface(steep, Walkable::Yes),
+        SurfaceClass::TooSteep
+    );
+    assert_eq!(
+        controller.classify_surface(Vector::Y, Walkable::Slippery),
+        SurfaceClass::Slippery
+    );
+    assert_eq!(
+        controller.classify_surface(Vector::Y, Walkable::No),
+        SurfaceClass::Forbidden
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_slides_down_slippery_slopes() {
+    // Returns the horizontal distance that a character moved on a shallow slope with the given flag.
+    let slide = |walkable: Walkable| {
+        let mut app = create_app();
+        app.add_plugins(CharacterControllerPlugin::default());
+
+        // A 15 degree slope going down towards negative X.
+        #[cfg(feature = "2d")]
+        let (slope, rotation, ball) = (
+            Collider::rectangle(20.0, 1.0),
+            Rotation::degrees(15.0),
+            Collider::circle(0.5),
+        );
+        #[cfg(feature = "3d")]
+        let (slope, rotation, ball) = (
+            Collider::cuboid(20.0, 1.0, 20.0),
+            Rotation(Quaternion::from_rotation_z((15.0 as Scalar).to_radians())),
+            Collider::sphere(0.5),
+        );
+
+        app.world_mut().spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            rotation,
+            slope,
+            walkable,
+        ));
+        let character = app
+            .world_mut()
+            .spawn((
+                CharacterController::default(),
+                Position(Vector::Y * 0.6),
+                ball,
+            ))
+            .id();
+
+        app.finish();
+
+        for _ in 0..60 {
+            tick_app(&mut app, 1.0 / 60.0);
+        }
+
+        app.world().get::<Position>(character).unwrap().x
+    };
+
+    // The slope is shallow enough to stand on, unless it is slippery.
+    assert!(slide(Walkable::Yes).abs() < 0.1);
+    assert!(slide(Walkable::Slippery) < -0.3);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn remote_body_follows_interpolated_samples() {
+    let mut app = create_app();
+    app.finish();
+
+    #[cfg(feature = "2d")]
+    let end_rotation = Rotation::radians(1.0);
+    #[cfg(feature = "3d")]
+    let end_rotation = Rotation(Quaternion::from_rotation_z(1.0));
+
+    let mut remote_body = RemoteBody::new(0.0);
+    remote_body.push_sample(RemoteSample {
+        time: 0.0,
+        ..default()
+    });
+    remote_body.push_sample(RemoteSample {
+        time: 1.0,
+        position: Vector::X,
+        rotation: end_rotation,
+        ..default()
+    });
+
+    // Between samples with zero velocity, the Hermite curve eases in and out.
+    let halfway = remote_body.sample_at(0.5).unwrap();
+    assert_relative_eq!(halfway.position, Vector::X * 0.5, epsilon = 1e-6);
+    assert_relative_eq!(halfway.linear_velocity, Vector::X * 1.5, epsilon = 1e-6);
+
+    let body = app.world_mut().spawn(remote_body).id();
+
+    for _ in 0..31 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    let time = app.world().resource::<Time<Physics>>().elapsed_secs_f64();
+    assert!(time > 0.25 && time < 0.75);
+
+    // The body reached the interpolated pose of the current time.
+    let world = app.world();
+    let target = world
+        .get::<RemoteBody>(body)
+        .unwrap()
+        .sample_at(time)
+        .unwrap();
+    let position = world.get::<Position>(body).unwrap().0;
+    let rotation = *world.get::<Rotation>(body).unwrap();
+    assert_eq!(world.get::<RigidBody>(body), Some(&RigidBody::Kinematic));
+    assert_relative_eq!(position, target.position, epsilon = 1e-4);
+    assert!(position.x > 0.0 && position.x < 1.0);
+
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(
+        rotation.as_radians(),
+        target.rotation.as_radians(),
+        epsilon = 1e-4
+    );
+    #[cfg(feature = "3d")]
+    assert!(rotation.0.angle_between(target.rotation.0) < 1e-3);
+
+    // The rotation is slerped between the samples.
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(rotation.as_radians(), time as Scalar, epsilon = 1e-4);
+    #[cfg(feature = "3d")]
+    assert_relative_eq!(
+        rotation.0.angle_between(Quaternion::IDENTITY),
+        time as Scalar,
+        epsilon = 1e-3
+    );
+}
+
+#[test]
+#[cfg(feature = "settings-asset")]
+fn physics_settings_asset_updates_physics_resources() {
+    use crate::dynamics::solver::SolverConfig;
+    use bevy::asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        AssetMetaCheck, AssetPlugin,
+    };
+    use std::path::Path;
+
+    #[cfg(feature = "2d")]
+    let gravity = "(0.0, -20.0)";
+    #[cfg(feature = "3d")]
+    let gravity = "(0.0, -20.0, 0.0)";
+
+    let dir = Dir::default();
+    dir.insert_asset_text(
+        Path::new("settings.physics.ron"),
+        &format!(
+            r#"(
+                gravity: Some({gravity}),
+                substeps: Some(4),
+                restitution_iterations: Some(3),
+                sleep_linear_threshold: Some(0.5),
+                deactivation_time: Some(1.5),
+                collision_layers: Some({{
+                    "default": 0,
+                    "water": 4,
+                }}),
+            )"#
+        ),
+    );
+    let reader = MemoryAssetReader { root: dir };
+
+    let mut app = App::new();
+    app.register_asset_source(
+        AssetSourceId::Default,
+        AssetSource::build().with_reader(move || Box::new(reader.clone())),
+    )
+    .add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        PhysicsPlugins::default()
+            .build()
+            .disable::<ColliderHierarchyPlugin>(),
+        AssetPlugin {
+            meta_check: AssetMetaCheck::Never,
+            ..default()
+        },
+        #[cfg(feature = "bevy_scene")]
+        bevy::scene::ScenePlugin,
+    ))
+    .init_resource::<Assets<Mesh>>()
+    .add_plugins(PhysicsSettingsPlugin::new("settings.physics.ron"));
+
+    app.finish();
+
+    // The asset is loaded in the background, so wait for the settings to be applied.
+    for _ in 0..1000 {
+        tick_app(&mut app, 1.0 / 60.0);
+        if app.world().resource::<SubstepCount>().0 == 4 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    let world = app.world();
+    assert_eq!(world.resource::<SubstepCount>().0, 4);
+    assert_eq!(world.resource::<Gravity>().0, Vector::NEG_Y * 20.0);
+    assert_eq!(world.resource::<SolverConfig>().restitution_iterations, 3);
+    assert_eq!(world.resource::<SleepingThreshold>().linear, 0.5);
+    assert_eq!(world.resource::<DeactivationTime>().0, 1.5);
+
+    // Fields that are omitted are left unchanged.
+    assert_eq!(
+        world.resource::<SleepingThreshold>().angular,
+        SleepingThreshold::default().angular
+    );
+
+    let registry = world.resource::<CollisionLayerRegistry>();
+    assert_eq!(registry.get("water"), Some(LayerMask(1 << 4)));
+}
+
+/// Spawns the given rigid bodies in order, runs one physics step, and returns the checksum.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn physics_checksum_for(
+    bodies: &[(Position, Rotation, LinearVelocity, AngularVelocity)],
+) -> PhysicsChecksum {
+    let mut app = create_app();
+    app.add_plugins(PhysicsChecksumPlugin::default())
+        .insert_resource(Gravity::ZERO);
+
+    for (position, rotation, linear_velocity, angular_velocity) in bodies {
+        app.world_mut().spawn((
+            RigidBody::Dynamic,
+            cuboid(1.0),
+            *position,
+            *rotation,
+            *linear_velocity,
+            *angular_velocity,
+        ));
+    }
+
+    // The first tick has a zero delta and doesn't run a physics step.
+    tick_app(&mut app, 1.0 / 60.0);
+    tick_app(&mut app, 1.0 / 60.0);
+
+    *app.world().resource::<PhysicsChecksum>()
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn physics_checksum_is_independent_of_iteration_order() {
+    // The bodies are far enough apart not to collide.
+    let bodies: Vec<_> = (0..4)
+        .map(|i| {
+            let i = i as Scalar;
+            (
+                Position(Vector::X * 10.0 * i),
+                Rotation::default(),
+                LinearVelocity(Vector::Y * i),
+                AngularVelocity::ZERO,
+            )
+        })
+        .collect();
+
+    // Bodies in the same archetype are iterated in spawn order.
+    let mut reversed = bodies.clone();
+    reversed.reverse();
+    let mut rotated = bodies.clone();
+    rotated.rotate_left(1);
+
+    let checksum = physics_checksum_for(&bodies);
+    assert_ne!(checksum.value(), 0);
+    assert_eq!(physics_checksum_for(&reversed), checksum);
+    assert_eq!(physics_checksum_for(&rotated), checksum);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn physics_checksum_changes_with_body_state() {
+    let bodies = vec![
+        (
+            Position(Vector::ZERO),
+            Rotation::default(),
+            LinearVelocity(Vector::X),
+            AngularVelocity::ZERO,
+        ),
+        (
+            Position(Vector::X * 10.0),
+            Rotation::default(),
+            LinearVelocity::ZERO,
+            AngularVelocity::ZERO,
+        ),
+    ];
+    let checksum = physics_checksum_for(&bodies).value();
+
+    // Changing any of the hashed components of any body changes the checksum.
+    for index in 0..bodies.len() {
+        let mut changed = bodies.clone();
+        changed[index].0 .0 += Vector::Y * 0.001;
+        assert_ne!(physics_checksum_for(&changed).value(), checksum);
+
+        let mut changed = bodies.clone();
+        #[cfg(feature = "2d")]
+        {
+            changed[index].1 = Rotation::radians(0.1);
+        }
+        #[cfg(feature = "3d")]
+        {
+            changed[index].1 = Rotation(Quaternion::from_rotation_z(0.1));
+        }
+        assert_ne!(physics_checksum_for(&changed).value(), checksum);
+
+        let mut changed = bodies.clone();
+        changed[index].2 .0 += Vector::Y * 0.001;
+        assert_ne!(physics_checksum_for(&changed).value(), checksum);
+
+        let mut changed = bodies.clone();
+        #[cfg(feature = "2d")]
+        {
+            changed[index].3 = AngularVelocity(0.1);
+        }
+        #[cfg(feature = "3d")]
+        {
+            changed[index].3 = AngularVelocity(Vector::Z * 0.1);
+        }
+        assert_ne!(physics_checksum_for(&changed).value(), checksum);
+    }
+}
+
+#[test]
+fn physics_checksum_step_advances_once_per_physics_step() {
+    #[derive(Resource, Default)]
+    struct PhysicsStepCount(u64);
+
+    let mut app = create_app();
+    app.add_plugins(PhysicsChecksumPlugin::default())
+        .init_resource::<PhysicsStepCount>()
+        .add_systems(PhysicsSchedule, |mut count: ResMut<PhysicsStepCount>| {
+            count.0 += 1;
+        });
+
+    // The first tick has a zero delta and doesn't run a physics step.
+    tick_app(&mut app, 1.0 / 60.0);
+    assert_eq!(app.world().resource::<PhysicsChecksum>().step(), 0);
+
+    // Larger deltas run several physics steps in a single frame.
+    for timestep in [1.0 / 60.0, 1.0 / 20.0, 1.0 / 240.0, 1.0 / 60.0] {
+        tick_app(&mut app, timestep);
+        assert_eq!(
+            app.world().resource::<PhysicsChecksum>().step(),
+            app.world().resource::<PhysicsStepCount>().0
+        );
+    }
+    assert!(app.world().resource::<PhysicsStepCount>().0 > 4);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn static_colliders_are_updated_when_changed() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, collider.clone()))
+        .id();
+    let static_body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X * 5.0),
+            collider.clone(),
+        ))
+        .id();
+
+    // Two overlapping static bodies, far away from the others.
+    let static_body1 = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::Y * 10.0),
+            collider.clone(),
+        ))
+        .id();
+    let static_body2 = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::Y * 10.5), collider))
+        .id();
+
+    tick_app(&mut app, 1.0 / 60.0);
+    assert!(!app
+        .world()
+        .resource::<Collisions>()
+        .contains(body, static_body));
+    assert!(!app
+        .world()
+        .resource::<Collisions>()
+        .contains(static_body1, static_body2));
+
+    // Moving a static body updates it in the broad phase.
+    app.world_mut().get_mut::<Position>(static_body).unwrap().0 = Vector::X * 0.9;
+    tick_app(&mut app, 1.0 / 60.0);
+    assert!(app
+        .world()
+        .resource::<Collisions>()
+        .contains(body, static_body));
+
+    // Despawning a static body removes it from the broad phase.
+    app.world_mut().despawn(static_body);
+    tick_app(&mut app, 1.0 / 60.0);
+    assert!(!app
+        .world()
+        .resource::<Collisions>()
+        .contains(body, static_body));
+
+    // Static colliders of bodies that stop being static can collide with other static colliders.
+    *app.world_mut().get_mut::<RigidBody>(static_body2).unwrap() = RigidBody::Dynamic;
+    tick_app(&mut app, 1.0 / 60.0);
+    assert!(app
+        .world()
+        .resource::<Collisions>()
+        .contains(static_body1, static_body2));
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn rope_attachments_pull_dynamic_bodies() {
+    let mut app = create_app();
+    app.add_plugins(RopePlugin);
+
+    let anchor = app
+        .world_mut()
+        .spawn((RigidBody::Static, Position(Vector::Y * 5.0)))
+        .id();
+    #[cfg(feature = "2d")]
+    let ball_collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let ball_collider = Collider::sphere(0.5);
+    let ball = app
+        .world_mut()
+        .spawn((RigidBody::Dynamic, ball_collider, Position(Vector::Y * 3.0)))
+        .id();
+
+    // A rope hanging from the anchor, holding the ball at its other end.
+    // The rope doesn't collide with the ball.
+    let mut rope = Rope::new(Vector::Y * 5.0, Vector::Y * 3.0, 20);
+    rope.attach_start(anchor, Vector::ZERO);
+    rope.attach_end(ball, Vector::ZERO);
+    app.world_mut().spawn((rope, CollisionLayers::NONE));
+
+    for _ in 0..120 {
+        tick_app(&mut app, 1.0 / 60.0);
+    }
+
+    // Without the reaction impulses, the ball would have fallen for two seconds.
+    let ball_position = app.world().get::<Position>(ball).unwrap().0;
+    assert!(ball_position.y > 2.5);
+    assert!(ball_position.y < 3.5);
+}
+
 #[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
 #[cfg(feature = "3d")]
 struct Id(usize);
@@ -4,7 +4,7 @@ use crate::{
 };
 use bevy::prelude::*;
 use broad_phase::AabbIntersections;
-use dynamics::solver::{schedule::SubstepCount, SolverConfig};
+use dynamics::solver::{schedule::SubstepCount, ContactStiffness, SolverConfig};
 
 /// Registers physics types to the `TypeRegistry` resource in `bevy_reflect`.
 pub struct PhysicsTypeRegistrationPlugin;
@@ -21,17 +21,21 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<Gravity>()
             .register_type::<RigidBody>()
             .register_type::<RigidBodyDisabled>()
+            .register_type::<Frozen>()
             .register_type::<Sleeping>()
             .register_type::<SleepingDisabled>()
+            .register_type::<SleepThreshold>()
             .register_type::<TimeSleeping>()
             .register_type::<Position>()
             .register_type::<Rotation>()
             .register_type::<PreSolveAccumulatedTranslation>()
+            .register_type::<PreSolveRotation>()
             .register_type::<PreviousRotation>()
             .register_type::<PreviousGlobalTransform>()
             .register_type::<AccumulatedTranslation>()
             .register_type::<LinearVelocity>()
             .register_type::<AngularVelocity>()
+            .register_type::<KinematicTarget>()
             .register_type::<PreSolveLinearVelocity>()
             .register_type::<PreSolveAngularVelocity>()
             .register_type::<Restitution>()
@@ -43,30 +47,50 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<ExternalImpulse>()
             .register_type::<ExternalAngularImpulse>()
             .register_type::<GravityScale>()
+            .register_type::<GravityOverride>()
+            .register_type::<MaxLinearSpeed>()
+            .register_type::<MaxAngularSpeed>()
+            .register_type::<MaxOverlapSolveSpeed>()
+            .register_type::<SkipWarmStart>()
+            .register_type::<PlatformVelocityTransfer>()
+            .register_type::<MinInertiaRadius>()
+            .register_type::<RigidBodyLimitsConfig>()
             .register_type::<ColliderDensity>()
             .register_type::<ColliderMassProperties>()
             .register_type::<LockedAxes>()
             .register_type::<ColliderParent>()
             .register_type::<Dominance>()
             .register_type::<ColliderAabb>()
+            .register_type::<ColliderBoundingSphere>()
             .register_type::<CollisionLayers>()
             .register_type::<CollidingEntities>()
+            .register_type::<CollisionLayerRegistry>()
+            .register_type::<CollisionEventsEnabled>()
+            .register_type::<CollisionEventMode>()
             .register_type::<CoefficientCombine>()
             .register_type::<Sensor>()
+            .register_type::<ContactResponseDisabled>()
+            .register_type::<QueryOnly>()
             .register_type::<ColliderTransform>()
             .register_type::<SpeculativeMargin>()
             .register_type::<SweptCcd>()
             .register_type::<CollisionMargin>()
+            .register_type::<CollisionUserData>()
             .register_type::<NarrowPhaseConfig>()
             .register_type::<SolverConfig>()
+            .register_type::<ContactStiffness>()
             .register_type::<SyncConfig>()
+            .register_type::<SyncMode>()
             .register_type::<AncestorMarker<RigidBody>>()
             .register_type::<AncestorMarker<ColliderMarker>>()
             .register_type::<RayCaster>()
             .register_type::<DistanceJoint>()
             .register_type::<FixedJoint>()
             .register_type::<PrismaticJoint>()
-            .register_type::<RevoluteJoint>();
+            .register_type::<RevoluteJoint>()
+            .register_type::<MirrorConstraint>()
+            .register_type::<GearJoint>()
+            .register_type::<RackAndPinionJoint>();
 
         #[cfg(feature = "default-collider")]
         app.register_type::<ColliderConstructor>()
@@ -74,7 +98,13 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<ColliderConstructorHierarchyConfig>()
             .register_type::<ShapeCaster>();
 
+        app.register_type::<BlockerVolume>();
+
+        #[cfg(feature = "2d")]
+        app.register_type::<ZLayer>();
+
         #[cfg(feature = "3d")]
-        app.register_type::<SphericalJoint>();
+        app.register_type::<SphericalJoint>()
+            .register_type::<UniversalJoint>();
     }
 }